@@ -0,0 +1,271 @@
+//! Blocking client.
+
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::{BoxError, Error, Result};
+use crate::protocol::{Request, Response};
+
+/// Hook that starts a service's daemon, given the service name.
+///
+/// It should return once the daemon is accepting connections, or fail.
+pub type StartHook = Box<dyn FnOnce(&str) -> Result<(), BoxError>>;
+
+/// Configures a [`Client`] before connecting.
+pub struct Builder {
+    service: String,
+    socket: PathBuf,
+    timeout: Option<Duration>,
+    start: Option<StartHook>,
+}
+
+impl Builder {
+    /// Use `socket` instead of the service's default socket path.
+    pub fn socket(mut self, socket: impl Into<PathBuf>) -> Self {
+        self.socket = socket.into();
+        self
+    }
+
+    /// Give up on a call if the daemon doesn't answer within `timeout`.
+    /// There is no timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Run `hook` when the daemon isn't running, instead of failing with
+    /// [`Error::NotRunning`].
+    pub fn auto_start(mut self, hook: impl FnOnce(&str) -> Result<(), BoxError> + 'static) -> Self {
+        self.start = Some(Box::new(hook));
+        self
+    }
+
+    /// Check the daemon is running (starting it if a hook was set) and
+    /// return a client for it.
+    pub fn connect(self) -> Result<Client> {
+        if !self.socket.exists() {
+            let Some(start) = self.start else {
+                return Err(Error::NotRunning {
+                    service: self.service,
+                    socket: self.socket,
+                });
+            };
+            if let Err(source) = start(&self.service) {
+                return Err(Error::Start {
+                    service: self.service,
+                    source,
+                });
+            }
+            if !self.socket.exists() {
+                return Err(Error::Start {
+                    source: format!("no socket at {}", self.socket.display()).into(),
+                    service: self.service,
+                });
+            }
+        }
+
+        Ok(Client {
+            socket: self.socket,
+            timeout: self.timeout,
+        })
+    }
+}
+
+/// A blocking client for one daemon.
+///
+/// Each call opens its own connection, so a client can be kept around and
+/// shared between threads.
+#[derive(Debug, Clone)]
+pub struct Client {
+    socket: PathBuf,
+    timeout: Option<Duration>,
+}
+
+impl Client {
+    /// A client for a running service, using its default socket.
+    pub fn connect(service: &str) -> Result<Self> {
+        Self::builder(service).connect()
+    }
+
+    /// Configure a client for `service`.
+    pub fn builder(service: &str) -> Builder {
+        Builder {
+            service: service.to_string(),
+            socket: crate::socket_path(service),
+            timeout: None,
+            start: None,
+        }
+    }
+
+    /// A client for the daemon at `socket`, without checking that it exists.
+    pub fn at(socket: impl Into<PathBuf>) -> Self {
+        Client {
+            socket: socket.into(),
+            timeout: None,
+        }
+    }
+
+    /// The socket this client talks to.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket
+    }
+
+    /// The per-call timeout, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Call `method` with `params`.
+    pub fn call(&self, method: &str, params: Value) -> Result<Response> {
+        self.send(&Request::new(method, params))
+    }
+
+    /// Send a prepared request and read its response.
+    pub fn send(&self, request: &Request) -> Result<Response> {
+        let mut reader = self.open(request)?;
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        Response::from_line(&line)
+    }
+
+    /// The built-in `health` method.
+    pub fn health(&self) -> Result<Response> {
+        self.call("health", Value::Object(Default::default()))
+    }
+
+    /// The built-in `methods` method.
+    pub fn methods(&self) -> Result<Response> {
+        self.call("methods", Value::Object(Default::default()))
+    }
+
+    /// Send a request to a streaming method and read its frames as they
+    /// arrive.
+    pub fn stream(&self, request: &Request) -> Result<Stream> {
+        Ok(Stream {
+            reader: self.open(request)?,
+            done: false,
+        })
+    }
+
+    /// Open a connection that stays up for several calls, sent one after
+    /// another.
+    pub fn open_connection(&self) -> Result<Connection> {
+        let writer = self.connect_socket()?;
+        Ok(Connection {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        })
+    }
+
+    /// Connect and write `request`.
+    fn open(&self, request: &Request) -> Result<BufReader<UnixStream>> {
+        let mut stream = self.connect_socket()?;
+        stream.write_all(request.to_line()?.as_bytes())?;
+        stream.flush()?;
+        Ok(BufReader::new(stream))
+    }
+
+    fn connect_socket(&self) -> Result<UnixStream> {
+        let stream = UnixStream::connect(&self.socket).map_err(|source| Error::Connect {
+            socket: self.socket.clone(),
+            source,
+        })?;
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+        Ok(stream)
+    }
+}
+
+/// A connection kept open for several calls; see
+/// [`Client::open_connection`].
+pub struct Connection {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Connection {
+    /// Send a request and read its response.
+    ///
+    /// Daemons may close connections that sit idle. Writing to one fails
+    /// before any of the request is sent, which is reported as
+    /// [`Error::NotSent`]: the daemon never saw the call, so it is safe to
+    /// send again on a new connection. Any other error may come after the
+    /// daemon started handling it.
+    pub fn send(&mut self, request: &Request) -> Result<Response> {
+        let line = request.to_line()?;
+        let written = self.writer.write(line.as_bytes()).map_err(Error::NotSent)?;
+        self.writer.write_all(&line.as_bytes()[written..])?;
+        self.writer.flush()?;
+
+        let mut response = String::new();
+        self.reader.read_line(&mut response)?;
+        Response::from_line(&response)
+    }
+}
+
+/// Frames from a streaming call.
+///
+/// Each item is one raw frame. Iteration ends after a frame with
+/// `"done": true`, when the daemon closes the connection, or after the
+/// first error.
+pub struct Stream {
+    reader: BufReader<UnixStream>,
+    done: bool,
+}
+
+impl Stream {
+    /// A handle that can end the stream from another thread (for example a
+    /// Ctrl-C handler), unblocking a pending read.
+    pub fn shutdown_handle(&self) -> Result<ShutdownHandle> {
+        Ok(ShutdownHandle(self.reader.get_ref().try_clone()?))
+    }
+}
+
+impl Iterator for Stream {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => self.done = true,
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => {
+                    let frame = serde_json::from_str::<Value>(&line)
+                        .map_err(|e| Error::Protocol(format!("{} in {}", e, line.trim())));
+                    self.done = match &frame {
+                        Ok(frame) => frame["done"] == Value::Bool(true),
+                        Err(_) => true,
+                    };
+                    return Some(frame);
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        let _ = self.reader.get_ref().shutdown(Shutdown::Both);
+    }
+}
+
+/// Ends a [`Stream`] from elsewhere; see [`Stream::shutdown_handle`].
+pub struct ShutdownHandle(UnixStream);
+
+impl ShutdownHandle {
+    /// Close the connection. The stream's next read ends with an error or
+    /// end of stream.
+    pub fn shutdown(&self) {
+        let _ = self.0.shutdown(Shutdown::Both);
+    }
+}
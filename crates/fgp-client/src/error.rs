@@ -0,0 +1,69 @@
+//! Error taxonomy.
+
+use std::io;
+use std::path::PathBuf;
+
+/// Boxed error returned by an auto-start hook.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result type for client operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Anything that can go wrong talking to a daemon.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The daemon's socket does not exist and no auto-start hook was set.
+    #[error("service '{service}' is not running (no socket at {})", socket.display())]
+    NotRunning { service: String, socket: PathBuf },
+
+    /// The auto-start hook failed, or the socket never appeared after it ran.
+    #[error("failed to start service '{service}': {source}")]
+    Start {
+        service: String,
+        #[source]
+        source: BoxError,
+    },
+
+    /// The socket exists but nothing accepted the connection.
+    #[error("failed to connect to {}: {source}", socket.display())]
+    Connect {
+        socket: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The daemon did not answer within the client's timeout.
+    #[error("timed out waiting for the daemon")]
+    Timeout,
+
+    /// Reading from or writing to an established connection failed.
+    #[error("I/O error talking to the daemon: {0}")]
+    Io(#[source] io::Error),
+
+    /// A [`Connection`](crate::Connection) was closed before any of the
+    /// request was written, so the daemon never saw it.
+    #[error("the connection was closed before the request was sent: {0}")]
+    NotSent(#[source] io::Error),
+
+    /// The daemon closed the connection without sending a response.
+    #[error("the daemon closed the connection without responding")]
+    Closed,
+
+    /// The daemon sent something that is not a protocol response.
+    #[error("invalid response from the daemon: {0}")]
+    Protocol(String),
+
+    /// The daemon handled the call and reported an error.
+    #[error("{code}: {message}")]
+    Daemon { code: String, message: String },
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Error::Timeout,
+            _ => Error::Io(e),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Client for Fast Gateway Protocol (FGP) daemons.
+//!
+//! This is the client the `fgp` CLI itself uses for `fgp call`, `fgp health`
+//! and `fgp methods`, published so other tools can talk to daemons directly
+//! instead of shelling out to `fgp call`.
+//!
+//! ```no_run
+//! use serde_json::json;
+//!
+//! let client = fgp_client::Client::connect("gmail")?;
+//! let messages = client.call("gmail.list", json!({"limit": 10}))?.into_result()?;
+//! println!("{}", messages);
+//! # Ok::<(), fgp_client::Error>(())
+//! ```
+//!
+//! With the `async` feature, [`nonblocking::Client`] offers the same calls on
+//! Tokio.
+//!
+//! # Wire protocol
+//!
+//! Each daemon listens on a Unix socket at
+//! `~/.fgp/services/<service>/daemon.sock` (see [`socket_path`]). A call opens
+//! a connection, writes one request as a single line of JSON terminated by
+//! `\n`, and reads the response line:
+//!
+//! ```json
+//! {"id": "4211-1", "v": 1, "method": "gmail.list", "params": {"limit": 10}}
+//! ```
+//!
+//! - `id` is chosen by the client and echoed back by the daemon.
+//! - `v` is the protocol version, currently [`PROTOCOL_VERSION`].
+//! - `method` is `<service>.<action>`, or one of the built-ins every daemon
+//!   answers without a namespace: `health`, `methods`, `stop` and `bundle`.
+//! - `params` is a JSON object of arguments (`{}` when there are none).
+//! - `context` is optional material for agent-capability daemons, such as a
+//!   prior conversation or a system prompt, kept apart from `params`. It is
+//!   omitted entirely when there is none.
+//!
+//! ```json
+//! {"id": "4211-1", "ok": true, "result": {...}, "error": null, "meta": {"server_ms": 1.2}}
+//! ```
+//!
+//! - `ok` says whether the call succeeded.
+//! - `result` holds the method's return value when `ok` is true.
+//! - `error` is `{"code": ..., "message": ...}` when `ok` is false.
+//! - `meta.server_ms` is the time the daemon spent handling the call.
+//!
+//! A connection may also carry several calls one after another, each request
+//! written once the previous response has been read (see [`Connection`]).
+//!
+//! Streaming methods write several response lines on the same connection
+//! (each a frame with its own `result`) and finish with a frame carrying
+//! `"done": true`, or by closing the connection. See [`Client::stream`].
+//!
+//! # Errors
+//!
+//! [`Error`] separates transport failures (the daemon is not running, the
+//! socket could not be reached, a timeout, a malformed response) from errors
+//! the daemon reported itself. A daemon error is still a successful exchange:
+//! [`Client::call`] returns it as a [`Response`] with `ok: false`, and
+//! [`Response::into_result`] turns it into [`Error::Daemon`].
+//!
+//! # Stability
+//!
+//! This crate follows semver. The wire types ([`Request`], [`Response`],
+//! [`ErrorBody`], [`Meta`]) and [`Error`] are `#[non_exhaustive]`, so new
+//! protocol fields and error kinds can be added in minor releases; anything
+//! else that changes the public API needs a major release.
+
+mod client;
+mod error;
+mod protocol;
+
+#[cfg(feature = "async")]
+pub mod nonblocking;
+
+pub use client::{Builder, Client, Connection, ShutdownHandle, StartHook, Stream};
+pub use error::{BoxError, Error, Result};
+pub use protocol::{ErrorBody, Meta, Request, Response, BUILTIN_METHODS, PROTOCOL_VERSION};
+
+use std::path::PathBuf;
+
+/// Directory holding one subdirectory per installed service (`~/.fgp/services`).
+pub fn services_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".fgp")
+        .join("services")
+}
+
+/// Socket a service's daemon listens on (`~/.fgp/services/<service>/daemon.sock`).
+pub fn socket_path(service: &str) -> PathBuf {
+    services_dir().join(service).join("daemon.sock")
+}
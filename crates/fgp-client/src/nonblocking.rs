@@ -0,0 +1,101 @@
+//! Async client on Tokio (`async` feature).
+//!
+//! Mirrors the blocking [`crate::Client`]. To auto-start a daemon, connect
+//! with [`crate::Client::builder`] and convert the result with `.into()`.
+
+use serde_json::Value;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+use crate::error::{Error, Result};
+use crate::protocol::{Request, Response};
+
+/// An async client for one daemon.
+#[derive(Debug, Clone)]
+pub struct Client {
+    socket: PathBuf,
+    timeout: Option<Duration>,
+}
+
+impl Client {
+    /// A client for a running service, using its default socket.
+    pub fn connect(service: &str) -> Result<Self> {
+        crate::Client::connect(service).map(Into::into)
+    }
+
+    /// A client for the daemon at `socket`, without checking that it exists.
+    pub fn at(socket: impl Into<PathBuf>) -> Self {
+        Client {
+            socket: socket.into(),
+            timeout: None,
+        }
+    }
+
+    /// Give up on a call if the daemon doesn't answer within `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The socket this client talks to.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket
+    }
+
+    /// Call `method` with `params`.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Response> {
+        self.send(&Request::new(method, params)).await
+    }
+
+    /// Send a prepared request and read its response.
+    pub async fn send(&self, request: &Request) -> Result<Response> {
+        self.timed(async {
+            let mut stream =
+                UnixStream::connect(&self.socket)
+                    .await
+                    .map_err(|source| Error::Connect {
+                        socket: self.socket.clone(),
+                        source,
+                    })?;
+            stream.write_all(request.to_line()?.as_bytes()).await?;
+            stream.flush().await?;
+
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).await?;
+            Response::from_line(&line)
+        })
+        .await
+    }
+
+    /// The built-in `health` method.
+    pub async fn health(&self) -> Result<Response> {
+        self.call("health", Value::Object(Default::default())).await
+    }
+
+    /// The built-in `methods` method.
+    pub async fn methods(&self) -> Result<Response> {
+        self.call("methods", Value::Object(Default::default()))
+            .await
+    }
+
+    async fn timed<T>(&self, call: impl Future<Output = Result<T>>) -> Result<T> {
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .unwrap_or(Err(Error::Timeout)),
+            None => call.await,
+        }
+    }
+}
+
+impl From<crate::Client> for Client {
+    fn from(client: crate::Client) -> Self {
+        Client {
+            socket: client.socket_path().to_path_buf(),
+            timeout: client.timeout(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Request and response envelopes (see the crate docs for the wire format).
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{Error, Result};
+
+/// Protocol version sent in every request's `v` field.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Methods every daemon answers, without a service namespace.
+pub const BUILTIN_METHODS: &[&str] = &["health", "methods", "stop", "bundle"];
+
+/// A call to send to a daemon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Request {
+    pub id: String,
+    pub v: u32,
+    pub method: String,
+    pub params: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Value>,
+}
+
+impl Request {
+    /// A request with a fresh id and no context.
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let n = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Request {
+            id: format!("{}-{}", std::process::id(), n),
+            v: PROTOCOL_VERSION,
+            method: method.into(),
+            params,
+            context: None,
+        }
+    }
+
+    /// Use `id` instead of the generated one.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Attach a `context` value.
+    pub fn with_context(mut self, context: Value) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// The request as one line of JSON, including the trailing newline.
+    pub(crate) fn to_line(&self) -> Result<String> {
+        let mut line = serde_json::to_string(self).map_err(|e| Error::Protocol(e.to_string()))?;
+        line.push('\n');
+        Ok(line)
+    }
+}
+
+/// A daemon's answer to a [`Request`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Response {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub ok: bool,
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<ErrorBody>,
+    #[serde(default)]
+    pub meta: Meta,
+}
+
+impl Response {
+    /// Parse one response line.
+    pub fn from_line(line: &str) -> Result<Self> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Err(Error::Closed);
+        }
+        serde_json::from_str(line).map_err(|e| Error::Protocol(format!("{} in {}", e, line)))
+    }
+
+    /// Parse a response that has already been read as JSON.
+    pub fn from_value(value: Value) -> Result<Self> {
+        serde_json::from_value(value).map_err(|e| Error::Protocol(e.to_string()))
+    }
+
+    /// The daemon's error, if the call failed. A failed response without an
+    /// `error` object still counts as an error.
+    pub fn error(&self) -> Option<ErrorBody> {
+        if self.ok {
+            None
+        } else {
+            Some(self.error.clone().unwrap_or_default())
+        }
+    }
+
+    /// The call's result, or [`Error::Daemon`] if it failed. A successful
+    /// call without a result gives `null`.
+    pub fn into_result(self) -> Result<Value> {
+        match self.error() {
+            Some(error) => Err(Error::Daemon {
+                code: error.code,
+                message: error.message,
+            }),
+            None => Ok(self.result.unwrap_or(Value::Null)),
+        }
+    }
+}
+
+/// The `error` object of a failed response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorBody {
+    /// Error code; numeric codes are kept as their decimal string
+    #[serde(default = "default_code", deserialize_with = "code_string")]
+    pub code: String,
+    #[serde(default = "default_message")]
+    pub message: String,
+}
+
+impl Default for ErrorBody {
+    fn default() -> Self {
+        ErrorBody {
+            code: default_code(),
+            message: default_message(),
+        }
+    }
+}
+
+fn default_code() -> String {
+    "error".to_string()
+}
+
+fn default_message() -> String {
+    "unknown error".to_string()
+}
+
+fn code_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(code) => code,
+        Value::Null => default_code(),
+        other => other.to_string(),
+    })
+}
+
+/// The `meta` object of a response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Meta {
+    /// Time the daemon spent handling the call, in milliseconds
+    #[serde(default)]
+    pub server_ms: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_line() {
+        let request = Request::new("gmail.list", json!({"limit": 5})).with_id("1");
+        assert_eq!(
+            request.to_line().unwrap(),
+            "{\"id\":\"1\",\"v\":1,\"method\":\"gmail.list\",\"params\":{\"limit\":5}}\n"
+        );
+
+        let request = request.with_context(json!(["hi"]));
+        let value: Value = serde_json::from_str(&request.to_line().unwrap()).unwrap();
+        assert_eq!(value["context"], json!(["hi"]));
+
+        // Ids are unique within a process
+        assert_ne!(
+            Request::new("a", json!({})).id,
+            Request::new("a", json!({})).id
+        );
+    }
+
+    #[test]
+    fn test_response_parsing() {
+        let ok = Response::from_line(r#"{"ok": true, "result": [1], "meta": {"server_ms": 2.5}}"#)
+            .unwrap();
+        assert_eq!(ok.meta.server_ms, 2.5);
+        assert_eq!(ok.into_result().unwrap(), json!([1]));
+
+        let failed =
+            Response::from_line(r#"{"ok": false, "error": {"code": 404, "message": "nope"}}"#)
+                .unwrap();
+        assert!(matches!(
+            failed.into_result(),
+            Err(Error::Daemon { code, message }) if code == "404" && message == "nope"
+        ));
+
+        // Missing fields fall back to a generic error
+        let bare = Response::from_line("{}").unwrap();
+        assert_eq!(bare.error(), Some(ErrorBody::default()));
+
+        assert!(matches!(Response::from_line("\n"), Err(Error::Closed)));
+        assert!(matches!(
+            Response::from_line("not json"),
+            Err(Error::Protocol(_))
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! Client tests against a fake daemon on a temporary socket.
+
+use fgp_client::{Client, Error, Request};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixListener;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Accept `connections` connections on `socket`, answering each request line
+/// with whatever `reply` writes back.
+fn fake_daemon(
+    socket: &Path,
+    connections: usize,
+    reply: impl Fn(Value) -> Vec<Value> + Send + 'static,
+) -> std::thread::JoinHandle<()> {
+    let listener = UnixListener::bind(socket).unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(connections) {
+            let mut stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            for frame in reply(serde_json::from_str(&line).unwrap()) {
+                let _ = writeln!(stream, "{}", frame);
+            }
+        }
+    })
+}
+
+/// Echo the request back as the result.
+fn echo(request: Value) -> Vec<Value> {
+    vec![json!({"id": request["id"], "ok": true, "result": request, "meta": {"server_ms": 0.5}})]
+}
+
+fn socket_in(dir: &tempfile::TempDir) -> PathBuf {
+    dir.path().join("daemon.sock")
+}
+
+#[test]
+fn test_call_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let daemon = fake_daemon(&socket, 2, echo);
+
+    let client = Client::builder("echo").socket(&socket).connect().unwrap();
+    let response = client.call("echo.ping", json!({"n": 1})).unwrap();
+    assert_eq!(response.meta.server_ms, 0.5);
+    let request = response.into_result().unwrap();
+    assert_eq!(request["v"], 1);
+    assert_eq!(request["method"], "echo.ping");
+    assert_eq!(request["params"], json!({"n": 1}));
+    assert!(request.get("context").is_none());
+
+    let request = Request::new("echo.ping", json!({})).with_context(json!("be brief"));
+    let echoed = client.send(&request).unwrap().into_result().unwrap();
+    assert_eq!(echoed["context"], "be brief");
+    assert_eq!(echoed["id"], request.id.as_str());
+
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_daemon_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let daemon = fake_daemon(&socket, 1, |_| {
+        vec![json!({"ok": false, "error": {"code": "NOT_FOUND", "message": "no such method"}})]
+    });
+
+    let response = Client::at(&socket).call("echo.nope", json!({})).unwrap();
+    assert!(!response.ok);
+    match response.into_result() {
+        Err(Error::Daemon { code, message }) => {
+            assert_eq!(code, "NOT_FOUND");
+            assert_eq!(message, "no such method");
+        }
+        other => panic!("expected a daemon error, got {:?}", other),
+    }
+
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_not_running_and_auto_start() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+
+    let err = Client::builder("echo")
+        .socket(&socket)
+        .connect()
+        .unwrap_err();
+    assert!(matches!(err, Error::NotRunning { ref service, .. } if service == "echo"));
+
+    let err = Client::builder("echo")
+        .socket(&socket)
+        .auto_start(|_| Err("no binary".into()))
+        .connect()
+        .unwrap_err();
+    assert!(matches!(err, Error::Start { .. }));
+
+    let started = socket.clone();
+    let client = Client::builder("echo")
+        .socket(&socket)
+        .auto_start(move |service| {
+            assert_eq!(service, "echo");
+            fake_daemon(&started, 1, echo);
+            Ok(())
+        })
+        .connect()
+        .unwrap();
+    assert!(client.health().unwrap().ok);
+}
+
+#[test]
+fn test_timeout() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let _daemon = fake_daemon(&socket, 1, |_| {
+        std::thread::sleep(Duration::from_millis(500));
+        Vec::new()
+    });
+
+    let err = Client::builder("slow")
+        .socket(&socket)
+        .timeout(Duration::from_millis(50))
+        .connect()
+        .unwrap()
+        .call("slow.wait", json!({}))
+        .unwrap_err();
+    assert!(matches!(err, Error::Timeout), "{:?}", err);
+}
+
+#[test]
+fn test_closed_without_response() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let daemon = fake_daemon(&socket, 1, |_| Vec::new());
+
+    let err = Client::at(&socket)
+        .call("echo.ping", json!({}))
+        .unwrap_err();
+    assert!(matches!(err, Error::Closed), "{:?}", err);
+
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_stream() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let daemon = fake_daemon(&socket, 1, |_| {
+        vec![
+            json!({"ok": true, "result": 1}),
+            json!({"ok": true, "result": 2}),
+            json!({"ok": true, "done": true}),
+            json!({"ok": true, "result": "after done"}),
+        ]
+    });
+
+    let frames: Vec<Value> = Client::at(&socket)
+        .stream(&Request::new("count.up", json!({})))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[1]["result"], 2);
+    assert_eq!(frames[2]["done"], true);
+
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_connection_reuse() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let listener = UnixListener::bind(&socket).unwrap();
+    // Answer two requests on one connection, then close it
+    let daemon = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        for line in reader.lines().take(2) {
+            let request: Value = serde_json::from_str(&line.unwrap()).unwrap();
+            writeln!(stream, "{}", echo(request)[0]).unwrap();
+        }
+    });
+
+    let mut connection = Client::at(&socket).open_connection().unwrap();
+    for method in ["echo.one", "echo.two"] {
+        let request = connection
+            .send(&Request::new(method, json!({})))
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert_eq!(request["method"], method);
+    }
+
+    daemon.join().unwrap();
+    let err = connection
+        .send(&Request::new("echo.three", json!({})))
+        .unwrap_err();
+    assert!(matches!(err, Error::NotSent(_)), "{:?}", err);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_async_call() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = socket_in(&dir);
+    let daemon = fake_daemon(&socket, 1, echo);
+
+    let client = fgp_client::nonblocking::Client::at(&socket).with_timeout(Duration::from_secs(5));
+    let request = client
+        .call("echo.ping", json!({"n": 2}))
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+    assert_eq!(request["params"]["n"], 2);
+
+    daemon.join().unwrap();
+}
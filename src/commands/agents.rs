@@ -1,61 +1,208 @@
 //! Detect installed AI agents on the system.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
-use std::path::Path;
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long an agent's `--version` may take before it is skipped.
+const VERSION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where an agent keeps FGP registrations.
+enum Registrations {
+    /// A directory with one `<daemon>-fgp` (or `fgp-<daemon>`) entry per skill
+    SkillDirs(&'static str),
+    /// A JSON file with a server map (`mcpServers` and friends) keyed `fgp-<daemon>`
+    ServerMap(&'static str, &'static str),
+    /// Not tracked
+    Unknown,
+}
+
+/// A known AI agent.
+struct Agent {
+    name: &'static str,
+    /// `fgp skill export` target for this agent, if there is one
+    target: Option<&'static str>,
+    /// Config locations, checked in order; the first that exists is reported
+    paths: &'static [&'static str],
+    format: &'static str,
+    /// Executable that answers `--version`
+    binary: Option<&'static str>,
+    registrations: Registrations,
+}
 
 /// Known AI agent configurations.
-const AGENT_PATHS: &[(&str, &str, &str)] = &[
-    ("Claude Code", "~/.claude/skills", "SKILL.md files"),
-    ("Codex", "~/.codex/skills", "SKILL.md files"),
-    (
-        "Gemini CLI",
-        "~/.gemini/extensions",
-        "Extension directories",
-    ),
-    ("Antigravity", "~/.gemini/antigravity", "MCP config"),
-    ("Cursor", "~/.cursor", ".mdc rules"),
-    ("Windsurf", "~/.windsurf", "Workflow files"),
-    (
-        "Cline",
-        "~/.config/Code/User/globalStorage/saoudrizwan.claude-dev",
-        "MCP config",
-    ),
-    ("Continue", "~/.continue", "YAML config"),
+const AGENTS: &[Agent] = &[
+    Agent {
+        name: "Claude Code",
+        target: Some("claude-code"),
+        paths: &["~/.claude"],
+        format: "SKILL.md files",
+        binary: Some("claude"),
+        registrations: Registrations::SkillDirs("~/.claude/skills"),
+    },
+    Agent {
+        name: "Codex",
+        target: Some("codex"),
+        paths: &["~/.codex"],
+        format: "SKILL.md files",
+        binary: Some("codex"),
+        registrations: Registrations::SkillDirs("~/.codex/skills"),
+    },
+    Agent {
+        name: "Gemini CLI",
+        target: Some("gemini"),
+        paths: &["~/.gemini"],
+        format: "Extension directories",
+        binary: Some("gemini"),
+        registrations: Registrations::SkillDirs("~/.gemini/extensions"),
+    },
+    Agent {
+        name: "Antigravity",
+        target: None,
+        paths: &["~/.gemini/antigravity"],
+        format: "MCP config",
+        binary: None,
+        registrations: Registrations::Unknown,
+    },
+    Agent {
+        name: "Cursor",
+        target: Some("cursor"),
+        paths: &["~/.cursor"],
+        format: ".mdc rules",
+        binary: Some("cursor"),
+        registrations: Registrations::ServerMap("~/.cursor/mcp.json", "mcpServers"),
+    },
+    Agent {
+        name: "Windsurf",
+        target: Some("windsurf"),
+        paths: &["~/.codeium/windsurf", "~/.windsurf"],
+        format: "Workflow files",
+        binary: Some("windsurf"),
+        registrations: Registrations::SkillDirs("~/.windsurf/skills"),
+    },
+    Agent {
+        name: "Cline",
+        target: None,
+        paths: &["~/.config/Code/User/globalStorage/saoudrizwan.claude-dev"],
+        format: "MCP config",
+        binary: None,
+        registrations: Registrations::ServerMap(
+            "~/.config/Code/User/globalStorage/saoudrizwan.claude-dev/settings/cline_mcp_settings.json",
+            "mcpServers",
+        ),
+    },
+    Agent {
+        name: "Continue",
+        target: None,
+        paths: &["~/.continue"],
+        format: "YAML config",
+        binary: None,
+        registrations: Registrations::Unknown,
+    },
     // Additional AI coding tools
-    ("Aider", "~/.aider.conf.yml", "YAML config"),
-    ("Zed AI", "~/.config/zed", "JSON settings"),
-    ("GitHub Copilot", "~/.config/github-copilot", "JSON config"),
-    ("Sourcegraph Cody", "~/.sourcegraph", "JSON config"),
-    ("Amazon Q", "~/.aws/amazonq", "YAML/JSON config"),
-    ("Opencode", "~/.config/opencode", "JSON config"),
+    Agent {
+        name: "Aider",
+        target: Some("aider"),
+        paths: &["~/.aider.conf.yml"],
+        format: "YAML config",
+        binary: Some("aider"),
+        registrations: Registrations::Unknown,
+    },
+    Agent {
+        name: "Zed AI",
+        target: Some("zed"),
+        paths: &["~/.config/zed", "~/Library/Application Support/Zed"],
+        format: "JSON settings",
+        binary: Some("zed"),
+        registrations: Registrations::ServerMap("~/.config/zed/settings.json", "context_servers"),
+    },
+    Agent {
+        name: "GitHub Copilot",
+        target: None,
+        paths: &["~/.config/github-copilot"],
+        format: "JSON config",
+        binary: None,
+        registrations: Registrations::Unknown,
+    },
+    Agent {
+        name: "Sourcegraph Cody",
+        target: None,
+        paths: &["~/.sourcegraph"],
+        format: "JSON config",
+        binary: None,
+        registrations: Registrations::Unknown,
+    },
+    Agent {
+        name: "Amazon Q",
+        target: None,
+        paths: &["~/.aws/amazonq"],
+        format: "YAML/JSON config",
+        binary: None,
+        registrations: Registrations::Unknown,
+    },
+    Agent {
+        name: "Opencode",
+        target: None,
+        paths: &["~/.config/opencode"],
+        format: "JSON config",
+        binary: Some("opencode"),
+        registrations: Registrations::Unknown,
+    },
 ];
 
-pub fn run() -> Result<()> {
+/// Machine-readable detection result for one agent (used by `--json`).
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentSnapshot {
+    pub name: String,
+    /// Whether the agent's config directory exists (a binary on PATH alone
+    /// still reports `false`, with its version)
+    pub detected: bool,
+    pub version: Option<String>,
+    /// The config directory found, or where it would be
+    pub config_dir: String,
+    pub format: String,
+    /// `fgp skill export` target for this agent
+    pub export_target: Option<String>,
+    /// FGP skills or servers registered with the agent; `null` when FGP
+    /// doesn't know where this agent keeps them
+    pub fgp_skills: Option<Vec<String>>,
+}
+
+pub fn run(json: bool) -> Result<()> {
+    let mut snapshots: Vec<AgentSnapshot> = AGENTS.iter().map(snapshot).collect();
+    if json {
+        fill_versions(&mut snapshots);
+        println!("{}", serde_json::to_string_pretty(&snapshots)?);
+        return Ok(());
+    }
+
     println!("{}", "Detecting installed AI agents...".bold());
     println!();
 
     let mut found_any = false;
 
-    for (name, path, format) in AGENT_PATHS {
-        let expanded = shellexpand::tilde(path);
-        let path = Path::new(expanded.as_ref());
-
-        if path.exists() {
-            found_any = true;
-            println!("  {} {}", "✓".green().bold(), name.bold());
-            println!("    Path: {}", path.display().to_string().dimmed());
-            println!("    Format: {}", format.dimmed());
-            println!();
+    for agent in snapshots.iter().filter(|a| a.detected) {
+        found_any = true;
+        println!("  {} {}", "✓".green().bold(), agent.name.bold());
+        println!("    Path: {}", agent.config_dir.dimmed());
+        println!("    Format: {}", agent.format.dimmed());
+        if let Some(skills) = agent.fgp_skills.as_ref().filter(|s| !s.is_empty()) {
+            println!("    FGP skills: {}", skills.join(", ").dimmed());
         }
+        println!();
     }
 
     if !found_any {
         println!("  {} No supported AI agents detected.", "!".yellow().bold());
         println!();
         println!("  Supported agents:");
-        for (name, _, _) in AGENT_PATHS {
-            println!("    - {}", name);
+        for agent in AGENTS {
+            println!("    - {}", agent.name);
         }
     } else {
         println!(
@@ -66,3 +213,279 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// Detect one agent.
+fn snapshot(agent: &Agent) -> AgentSnapshot {
+    let paths: Vec<PathBuf> = agent.paths.iter().map(|p| expand(p)).collect();
+    let found = paths.iter().find(|p| p.exists());
+    let detected = found.is_some();
+
+    let fgp_skills = match agent.registrations {
+        Registrations::SkillDirs(dir) => Some(fgp_skill_dirs(&expand(dir))),
+        Registrations::ServerMap(file, key) => Some(fgp_servers(&expand(file), key)),
+        Registrations::Unknown => None,
+    };
+
+    AgentSnapshot {
+        name: agent.name.to_string(),
+        detected,
+        version: None,
+        config_dir: found.unwrap_or(&paths[0]).display().to_string(),
+        format: agent.format.to_string(),
+        export_target: agent.target.map(|t| t.to_string()),
+        fgp_skills,
+    }
+}
+
+fn expand(path: &str) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(path).as_ref())
+}
+
+/// FGP-installed entries look like `gmail-fgp` or `fgp-gmail`.
+fn is_fgp_name(name: &str) -> bool {
+    name.ends_with("-fgp") || name.starts_with("fgp-")
+}
+
+/// FGP entries in a skills directory.
+fn fgp_skill_dirs(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|name| is_fgp_name(name))
+        .collect();
+    names.sort();
+    names
+}
+
+/// FGP entries in a JSON config's server map.
+fn fgp_servers(file: &Path, key: &str) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = config[key]
+        .as_object()
+        .map(|servers| {
+            servers
+                .keys()
+                .filter(|name| is_fgp_name(name))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Where an agent has one daemon registered.
+#[derive(Debug, PartialEq)]
+pub enum Registration {
+    /// A `<daemon>-fgp` or `fgp-<daemon>` skill directory
+    SkillDir(PathBuf),
+    /// An entry in a JSON config's server map
+    Server {
+        file: PathBuf,
+        key: &'static str,
+        name: String,
+    },
+}
+
+/// Every agent's registrations of `daemon`, with the agent's name.
+pub fn registrations_of(daemon: &str) -> Vec<(&'static str, Registration)> {
+    let mut found = Vec::new();
+    for agent in AGENTS {
+        match agent.registrations {
+            Registrations::SkillDirs(dir) => {
+                let dir = expand(dir);
+                for name in daemon_entry_names(daemon) {
+                    let path = dir.join(name);
+                    if path.exists() {
+                        found.push((agent.name, Registration::SkillDir(path)));
+                    }
+                }
+            }
+            Registrations::ServerMap(file, key) => {
+                let file = expand(file);
+                for name in daemon_servers(&file, key, daemon) {
+                    let registration = Registration::Server {
+                        file: file.clone(),
+                        key,
+                        name,
+                    };
+                    found.push((agent.name, registration));
+                }
+            }
+            Registrations::Unknown => {}
+        }
+    }
+    found
+}
+
+fn daemon_entry_names(daemon: &str) -> [String; 2] {
+    [format!("{}-fgp", daemon), format!("fgp-{}", daemon)]
+}
+
+/// Entries in a JSON config's server map for `daemon`: named after it, or
+/// running `fgp` with `--service <daemon>` (a custom server name).
+fn daemon_servers(file: &Path, key: &str, daemon: &str) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let Some(servers) = config[key].as_object() else {
+        return Vec::new();
+    };
+
+    let names = daemon_entry_names(daemon);
+    let mut found: Vec<String> = servers
+        .iter()
+        .filter(|(name, server)| {
+            let args: Vec<&str> = server["args"]
+                .as_array()
+                .map(|args| args.iter().filter_map(|a| a.as_str()).collect())
+                .unwrap_or_default();
+            names.contains(name)
+                || (server["command"] == "fgp"
+                    && args.windows(2).any(|pair| pair == ["--service", daemon]))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    found.sort();
+    found
+}
+
+/// Remove a server entry from a JSON config's server map.
+pub fn remove_server(file: &Path, key: &str, name: &str) -> Result<()> {
+    let content =
+        fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let mut config: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", file.display()))?;
+    let removed = config[key]
+        .as_object_mut()
+        .and_then(|servers| servers.remove(name));
+    if removed.is_some() {
+        fs::write(file, serde_json::to_string_pretty(&config)? + "\n")
+            .with_context(|| format!("Failed to write {}", file.display()))?;
+    }
+    Ok(())
+}
+
+/// Ask every agent's CLI for its version, all at once so slow ones only
+/// cost [`VERSION_TIMEOUT`] in total.
+fn fill_versions(snapshots: &mut [AgentSnapshot]) {
+    std::thread::scope(|scope| {
+        for (snapshot, agent) in snapshots.iter_mut().zip(AGENTS) {
+            if let Some(binary) = agent.binary {
+                scope.spawn(move || snapshot.version = binary_version(binary));
+            }
+        }
+    });
+}
+
+/// Ask `binary --version`, giving up after [`VERSION_TIMEOUT`].
+fn binary_version(binary: &str) -> Option<String> {
+    let mut child = Command::new(binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait().ok()? {
+            Some(status) if status.success() => break,
+            Some(_) => return None,
+            None if start.elapsed() > VERSION_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => std::thread::sleep(Duration::from_millis(20)),
+        }
+    }
+
+    let mut output = String::new();
+    child.stdout?.read_to_string(&mut output).ok()?;
+    parse_version(&output)
+}
+
+/// The first version number in `--version` output ("claude 1.0.3 (Claude Code)").
+fn parse_version(output: &str) -> Option<String> {
+    let pattern = regex::Regex::new(r"\d+\.\d+(?:\.\d+)?(?:[-+][0-9A-Za-z.]+)?").unwrap();
+    pattern.find(output).map(|m| m.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("1.0.3 (Claude Code)\n").as_deref(),
+            Some("1.0.3")
+        );
+        assert_eq!(
+            parse_version("codex-cli 0.20.0-alpha.1").as_deref(),
+            Some("0.20.0-alpha.1")
+        );
+        assert_eq!(parse_version("Zed 0.180").as_deref(), Some("0.180"));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn test_fgp_registrations() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["gmail-fgp", "fgp-slack", "other"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        assert_eq!(fgp_skill_dirs(dir.path()), vec!["fgp-slack", "gmail-fgp"]);
+
+        let mcp = dir.path().join("mcp.json");
+        fs::write(&mcp, r#"{"mcpServers": {"fgp-gmail": {}, "github": {}}}"#).unwrap();
+        assert_eq!(fgp_servers(&mcp, "mcpServers"), vec!["fgp-gmail"]);
+        assert!(fgp_servers(&mcp, "context_servers").is_empty());
+        assert!(fgp_servers(&dir.path().join("missing.json"), "mcpServers").is_empty());
+    }
+
+    #[test]
+    fn test_daemon_servers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mcp = dir.path().join("mcp.json");
+        fs::write(
+            &mcp,
+            r#"{"mcpServers": {
+                "fgp-gmail": {"command": "fgp", "args": ["mcp", "--service", "gmail"]},
+                "mail": {"command": "fgp", "args": ["mcp", "--service", "gmail"]},
+                "fgp-gmail-work": {"command": "fgp", "args": ["mcp", "--service", "gmail-work"]},
+                "github": {"command": "gh-mcp", "args": ["--service", "gmail"]}
+            }, "other": 1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            daemon_servers(&mcp, "mcpServers", "gmail"),
+            vec!["fgp-gmail", "mail"]
+        );
+
+        remove_server(&mcp, "mcpServers", "mail").unwrap();
+        remove_server(&mcp, "mcpServers", "fgp-gmail").unwrap();
+        // Already gone
+        remove_server(&mcp, "mcpServers", "fgp-gmail").unwrap();
+        assert!(daemon_servers(&mcp, "mcpServers", "gmail").is_empty());
+
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&mcp).unwrap()).unwrap();
+        assert_eq!(config["other"], 1);
+        assert!(config["mcpServers"]["fgp-gmail-work"].is_object());
+    }
+}
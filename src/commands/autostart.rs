@@ -0,0 +1,116 @@
+//! Bring the declared autostart stack up or down.
+//!
+//! Services are listed in `~/.fgp/config.toml`:
+//!
+//! ```toml
+//! [autostart]
+//! services = ["gmail", "calendar", "github"]
+//! ```
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::time::Duration;
+
+use super::restart::wait_for_healthy;
+use super::status::snapshot_service;
+use crate::config;
+
+/// Load the autostart list, failing with a hint if it is empty.
+fn autostart_services() -> Result<Vec<String>> {
+    let config = config::load()?;
+    if config.autostart.services.is_empty() {
+        bail!(
+            "No autostart services configured.\n  Add an [autostart] section to {}:\n\n    [autostart]\n    services = [\"gmail\", \"calendar\"]",
+            config::config_path().display()
+        );
+    }
+    Ok(config.autostart.services)
+}
+
+/// Start every autostart service and wait for each to report healthy.
+pub fn up(wait_timeout: u64) -> Result<()> {
+    let services = autostart_services()?;
+    let timeout = Duration::from_secs(wait_timeout);
+    let mut failed = Vec::new();
+
+    for service in &services {
+        if snapshot_service(service).running {
+            println!("{} {} already running", "○".dimmed(), service.bold());
+            continue;
+        }
+
+        println!("{} Starting {}...", "→".blue().bold(), service.bold());
+        if let Err(e) = super::daemon_command::start_service(service) {
+            eprintln!("{} {}: {}", "✗".red().bold(), service.bold(), e);
+            failed.push(service.clone());
+            continue;
+        }
+
+        match wait_for_healthy(service, timeout) {
+            Some(pid) => println!(
+                "{} {} healthy (PID: {})",
+                "✓".green().bold(),
+                service.bold(),
+                pid
+            ),
+            None => {
+                eprintln!(
+                    "{} {} did not become healthy within {}s",
+                    "✗".red().bold(),
+                    service.bold(),
+                    wait_timeout
+                );
+                failed.push(service.clone());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} service(s) failed to start: {}",
+            failed.len(),
+            services.len(),
+            failed.join(", ")
+        );
+    }
+
+    println!();
+    println!(
+        "{} Stack is up ({} services)",
+        "✓".green().bold(),
+        services.len()
+    );
+    Ok(())
+}
+
+/// Stop every autostart service.
+pub fn down() -> Result<()> {
+    let services = autostart_services()?;
+    let mut failed = Vec::new();
+
+    for service in &services {
+        if !snapshot_service(service).running {
+            println!("{} {} not running", "○".dimmed(), service.bold());
+            continue;
+        }
+
+        match super::daemon_command::stop_service(service) {
+            Ok(()) => println!("{} {} stopped", "✓".green().bold(), service.bold()),
+            Err(e) => {
+                eprintln!("{} {}: {}", "✗".red().bold(), service.bold(), e);
+                failed.push(service.clone());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} service(s) failed to stop: {}",
+            failed.len(),
+            services.len(),
+            failed.join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -1,14 +1,103 @@
 //! Call a method on a daemon.
+//!
+//! Requests are sent as a JSON envelope, one per line:
+//!
+//! ```json
+//! {"id": "...", "v": 1, "method": "gmail.list", "params": {...}, "context": ...}
+//! ```
+//!
+//! `context` is only present with `--context <file>` (or `-` for stdin). It
+//! carries material such as a prior conversation or a system prompt for
+//! agent-capability daemons, kept apart from `params` so a method's arguments
+//! are the same with or without it. A file holding a JSON object or array is
+//! sent as that JSON; anything else is sent as a string.
+//!
+//! Params are checked against the method's params in the service's
+//! manifest.json before they're sent: that required ones are there and have
+//! the declared types. `--validate` checks them against the params' full JSON
+//! Schema instead (see `json_schema`), naming each field that fails and why.
+//!
+//! Methods the manifest marks destructive are confirmed before they are sent:
+//! interactively from a terminal, otherwise by the guardrails policy (see
+//! `guardrails`). `--yes` answers the question, but never overrides the
+//! policy.
+//!
+//! An unknown method with exactly one close match is an error naming it,
+//! and with `--yes` calls the match instead, unless the match is destructive:
+//! `--yes` confirms the method that was typed, not one fgp picked.
+//!
+//! `--param-from-call id=contacts.search:results.0.id` makes a lookup call
+//! first and sends a field of its result as one of the call's params. The
+//! path after `:` is dotted, with numeric segments indexing arrays (as in
+//! workflow templates); without it, the field named like the param is used.
+//! `--from-call-params` gives the lookup's own params. Anything longer than
+//! one lookup belongs in a workflow.
+//!
+//! `--select <pointer>` prints only the part of the result at a JSON pointer
+//! (`/messages/0/id`). `--expect-exit <code>` and `--expect-contains <text>`
+//! turn a call into an assertion for scripts: the call's exit status (0 for a
+//! result, 1 for an error response) and its output (the printed result, or
+//! the error's `code: message`) must match, or `fgp call` prints what it got
+//! and exits 1. An expected error response exits 0.
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use colored::Colorize;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::io::{IsTerminal, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
-pub fn run(
-    method: &str,
-    params: &str,
-    service_override: Option<&str>,
-    no_auto_start: bool,
-) -> Result<()> {
+use super::client::{self, Client, Request, Response, BUILTIN_METHODS};
+use super::guardrails::{self, Decision};
+use super::history::{self, HistoryEntry};
+use super::json_schema::json_type;
+use super::skill_import::{ManifestMethod, ManifestParam};
+
+/// How long to wait for an auto-started daemon to become healthy.
+const START_TIMEOUT_SECS: u64 = 10;
+
+/// Options controlling how `fgp call` resolves and executes a call.
+#[derive(Debug, Default)]
+pub struct CallOptions<'a> {
+    /// Explicit service (otherwise inferred from the method namespace)
+    pub service: Option<&'a str>,
+    /// Fail instead of starting the daemon if it is not running
+    pub no_auto_start: bool,
+    /// Print the resolved call without sending it
+    pub dry_run: bool,
+    /// Read newline-delimited JSON frames until the daemon finishes
+    pub stream: bool,
+    /// Collect streamed frames into a single JSON array
+    pub json_output: bool,
+    /// Don't record the call in ~/.fgp/history.jsonl
+    pub no_history: bool,
+    /// Don't check params against the service's manifest.json
+    pub skip_validation: bool,
+    /// Check params against the full JSON Schema of the method's params
+    pub validate: bool,
+    /// Run the suggested method when a misspelled one has a single close match,
+    /// and destructive methods without asking
+    pub yes: bool,
+    /// File (or `-` for stdin) to send as the request's `context`
+    pub context: Option<&'a str>,
+    /// `PARAM=METHOD[:PATH]`: fill a param from a field of another call's result
+    pub param_from_call: Option<&'a str>,
+    /// JSON params for the `param_from_call` lookup
+    pub from_call_params: &'a str,
+    /// Print the lookup's result
+    pub verbose: bool,
+    /// JSON pointer to the part of the result to print
+    pub select: Option<&'a str>,
+    /// Succeed only if the call exits with this status
+    pub expect_exit: Option<i32>,
+    /// Succeed only if the output contains this text
+    pub expect_contains: Option<&'a str>,
+}
+
+pub fn run(method: &str, params: &str, pairs: &[String], options: &CallOptions) -> Result<()> {
     // Resolve service/socket and normalize the method we send over the wire.
     //
     // Preferred:
@@ -17,7 +106,7 @@ pub fn run(
     // Also supported:
     // - Built-in methods with explicit service: `fgp call methods --service gmail`
     // - Action-only with explicit service: `fgp call search --service gmail`
-    let (service, wire_method) = if let Some(service) = service_override {
+    let (service, wire_method) = if let Some(service) = options.service {
         if method.contains('.') {
             // If the user provided --service, ensure it matches the namespace.
             let namespace = method.split('.').next().unwrap_or("");
@@ -31,9 +120,10 @@ pub fn run(
             (service.to_string(), method.to_string())
         } else {
             // Built-ins are un-namespaced; service methods get namespaced here.
-            let wire_method = match method {
-                "health" | "methods" | "stop" | "bundle" => method.to_string(),
-                _ => format!("{}.{}", service, method),
+            let wire_method = if BUILTIN_METHODS.contains(&method) {
+                method.to_string()
+            } else {
+                format!("{}.{}", service, method)
             };
             (service.to_string(), wire_method)
         }
@@ -45,57 +135,1427 @@ pub fn run(
         (service.to_string(), method.to_string())
     };
 
-    // Parse params as JSON
+    // Parse params as JSON, then layer key=value pairs on top
     let params_value: serde_json::Value = serde_json::from_str(params)
         .context("Invalid JSON in params. Use format: '{\"key\": \"value\"}'")?;
+    let params_value = apply_pairs(params_value, pairs)?;
+    let context = options.context.map(load_context).transpose()?;
+    let chain = options
+        .param_from_call
+        .map(ParamFromCall::parse)
+        .transpose()?;
 
-    // Create client - with or without auto-start
-    let client = if no_auto_start {
-        // Explicit opt-out: fail if daemon is not running
-        let socket_path = fgp_daemon::service_socket_path(&service);
-        if !socket_path.exists() {
-            bail!(
-                "Service '{}' is not running. Run 'fgp start {}' first (or remove --no-auto-start).",
-                service,
-                service
-            );
-        }
-        fgp_daemon::FgpClient::new(&socket_path).context("Failed to connect to daemon")?
+    let wire_method = if options.skip_validation {
+        wire_method
     } else {
-        // Default: auto-start daemon if not running
-        fgp_daemon::FgpClient::for_service(&service).context("Failed to create client")?
+        check_method(&service, wire_method, options.yes)?
     };
 
-    let start = std::time::Instant::now();
-    let response = client.call(&wire_method, params_value)?;
-    let elapsed = start.elapsed();
+    let params_value = match chain {
+        Some(chain) => chain.resolve(params_value, options)?,
+        None => params_value,
+    };
+    // A dry run doesn't make the lookup, so the chained param has no value yet
+    let chained_dry_run = options.dry_run && options.param_from_call.is_some();
+    if !options.skip_validation && !chained_dry_run {
+        validate_params(&service, &wire_method, &params_value, options.validate)?;
+    }
 
-    // Print response
-    if response.ok {
-        if let Some(result) = response.result {
-            println!("{}", serde_json::to_string_pretty(&result)?);
+    if options.dry_run {
+        println!(
+            "{} Would call {} on service {}",
+            "→".blue().bold(),
+            wire_method.bold(),
+            service.bold()
+        );
+        println!("{}", serde_json::to_string_pretty(&params_value)?);
+        if let Some(ref context) = context {
+            println!("{}", "Context:".bold());
+            println!("{}", serde_json::to_string_pretty(context)?);
         }
-    } else {
-        let error = response.error.unwrap_or_default();
-        eprintln!(
-            "{} Error ({}): {}",
-            "✗".red().bold(),
-            error.code,
-            error.message
+        return Ok(());
+    }
+
+    let params_value = guard_destructive(&service, &wire_method, params_value, options.yes)?;
+
+    if options.stream {
+        return stream_call(
+            &service,
+            &wire_method,
+            params_value,
+            context.as_ref(),
+            options,
         );
-        std::process::exit(1);
+    }
+
+    let start = std::time::Instant::now();
+    let response = match call_daemon(
+        &service,
+        &wire_method,
+        &params_value,
+        context.as_ref(),
+        options,
+    ) {
+        Ok(response) => response,
+        Err(e) => {
+            let entry = HistoryEntry::new(&service, &wire_method, &params_value, start.elapsed());
+            record_history(options, entry.failed(e.to_string()));
+            return Err(e);
+        }
+    };
+    let elapsed = response.elapsed;
+    let entry = HistoryEntry::new(&service, &wire_method, &params_value, elapsed);
+
+    // Print response
+    if let Some((code, message)) = response.error {
+        let error = format!("{}: {}", code, message);
+        record_history(options, entry.failed(error.clone()));
+        eprintln!("{} Error ({}): {}", "✗".red().bold(), code, message);
+        return finish(options, 1, &error);
+    }
+
+    record_history(options, entry);
+    let output = match response.result {
+        Some(result) => Some(serde_json::to_string_pretty(&select(
+            result,
+            options.select,
+        )?)?),
+        None => None,
+    };
+    if let Some(ref output) = output {
+        println!("{}", output);
     }
 
     // Print timing in stderr so it doesn't interfere with JSON output
     eprintln!(
         "{}",
         format!(
-            "({:.1}ms client, {:.1}ms server)",
+            "({:.1}ms client{}, {:.1}ms server)",
             elapsed.as_secs_f64() * 1000.0,
-            response.meta.server_ms
+            if response.via_proxy { " via proxy" } else { "" },
+            response.server_ms
         )
         .dimmed()
     );
 
+    finish(options, 0, output.as_deref().unwrap_or_default())
+}
+
+/// The part of `result` at the `--select` pointer, if one was given.
+fn select(result: Value, pointer: Option<&str>) -> Result<Value> {
+    let Some(pointer) = pointer else {
+        return Ok(result);
+    };
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        bail!(
+            "--select takes a JSON pointer such as /{}",
+            pointer.replace('.', "/")
+        );
+    }
+    result
+        .pointer(pointer)
+        .cloned()
+        .ok_or_else(|| anyhow!("Nothing at {} in the result", pointer))
+}
+
+/// End a call with exit status `status`, unless `--expect-*` was given: then
+/// succeed if `status` and `output` match, and fail otherwise.
+fn finish(options: &CallOptions, status: i32, output: &str) -> Result<()> {
+    if let Err(failure) = check_expectations(options, status, output) {
+        eprintln!("{} assertion failed: {}", "✗".red().bold(), failure);
+        std::process::exit(1);
+    }
+    if status != 0 && options.expect_exit.is_none() {
+        std::process::exit(status);
+    }
+    Ok(())
+}
+
+/// How a call's outcome differs from `--expect-exit` / `--expect-contains`.
+fn check_expectations(options: &CallOptions, status: i32, output: &str) -> Result<(), String> {
+    if let Some(expected) = options.expect_exit {
+        if status != expected {
+            return Err(format!("expected exit {}, got {}", expected, status));
+        }
+    }
+    if let Some(expected) = options.expect_contains {
+        if !output.contains(expected) {
+            let got = if output.is_empty() {
+                "no output".to_string()
+            } else {
+                format!("'{}'", output_excerpt(output))
+            };
+            return Err(format!(
+                "expected output containing '{}', got {}",
+                expected, got
+            ));
+        }
+    }
     Ok(())
 }
+
+/// `output` on one line, cut short when long.
+fn output_excerpt(output: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    let line = output.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line,
+    }
+}
+
+/// Hold a call to a destructive method until it is confirmed: by the user
+/// from a terminal (`yes` answers for them), otherwise by the guardrails
+/// policy, which `yes` doesn't get around.
+///
+/// Returns the params to send, which ask for a dry run when the policy only
+/// allows previews.
+fn guard_destructive(service: &str, method: &str, params: Value, yes: bool) -> Result<Value> {
+    let declared = guardrails::declared_methods(service);
+    let Some(definition) = guardrails::find_destructive(&declared, service, method) else {
+        return Ok(params);
+    };
+
+    if std::io::stdin().is_terminal() {
+        let question = format!("{} is marked destructive. Run it?", method.bold());
+        if yes || super::confirm(&question)? {
+            return Ok(params);
+        }
+        bail!("Cancelled; {} was not called", method);
+    }
+
+    match guardrails::check(service, definition)? {
+        Decision::Proceed => Ok(params),
+        Decision::Preview => {
+            eprintln!(
+                "{} {} is destructive; sending a dry run (guardrails policy)",
+                "!".yellow().bold(),
+                method
+            );
+            Ok(guardrails::with_dry_run(params))
+        }
+        Decision::Deny(reason) => bail!("{}", reason),
+    }
+}
+
+/// A daemon response, however the call was routed.
+struct Reply {
+    result: Option<Value>,
+    /// Error code and message when the call failed
+    error: Option<(String, String)>,
+    server_ms: f64,
+    /// Round-trip time of the call itself (excluding any daemon auto-start)
+    elapsed: Duration,
+    via_proxy: bool,
+}
+
+impl Reply {
+    fn from_response(response: Response, elapsed: Duration, via_proxy: bool) -> Self {
+        Reply {
+            error: response.error().map(|error| (error.code, error.message)),
+            result: response.result.filter(|r| !r.is_null()),
+            server_ms: response.meta.server_ms,
+            elapsed,
+            via_proxy,
+        }
+    }
+}
+
+/// The request envelope for a call (see the module docs).
+fn request_envelope(method: &str, params: &Value, context: Option<&Value>) -> Request {
+    let request = Request::new(method, params.clone());
+    match context {
+        Some(context) => request.with_context(context.clone()),
+        None => request,
+    }
+}
+
+/// Read `--context` from a file, or stdin for `-`.
+fn load_context(source: &str) -> Result<Value> {
+    let content = if source == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read context from stdin")?;
+        content
+    } else {
+        let path = shellexpand::tilde(source).to_string();
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read context file {}", path))?
+    };
+    Ok(context_value(content))
+}
+
+/// JSON objects and arrays are passed through; anything else is text.
+fn context_value(content: String) -> Value {
+    match serde_json::from_str::<Value>(&content) {
+        Ok(value @ (Value::Object(_) | Value::Array(_))) => value,
+        _ => Value::String(content),
+    }
+}
+
+/// Send a call through `fgp daemon-proxy` if it is running, otherwise
+/// straight to the daemon.
+fn call_daemon(
+    service: &str,
+    method: &str,
+    params: &Value,
+    context: Option<&Value>,
+    options: &CallOptions,
+) -> Result<Reply> {
+    let client = connect(service, options.no_auto_start)?;
+    let request = request_envelope(method, params, context);
+
+    if let Some(proxy) = super::daemon_proxy::ProxyClient::connect() {
+        let start = std::time::Instant::now();
+        let response = proxy.call(client.socket_path(), &serde_json::to_value(&request)?)?;
+        let response = Response::from_value(response).context("Invalid response from proxy")?;
+        return Ok(Reply::from_response(response, start.elapsed(), true));
+    }
+
+    let start = std::time::Instant::now();
+    let response = client.send(&request)?;
+    Ok(Reply::from_response(response, start.elapsed(), false))
+}
+
+/// A client for the service's daemon, starting it unless disabled.
+fn connect(service: &str, no_auto_start: bool) -> Result<Client> {
+    let builder = Client::builder(service);
+    let builder = if no_auto_start {
+        builder
+    } else {
+        builder.auto_start(|service| start_and_wait(service).map_err(Into::into))
+    };
+
+    builder.connect().map_err(|e| match e {
+        client::Error::NotRunning { .. } => anyhow!(
+            "Service '{}' is not running. Run 'fgp start {}' first (or remove --no-auto-start).",
+            service,
+            service
+        ),
+        e => e.into(),
+    })
+}
+
+/// Append a call to the history log unless disabled.
+fn record_history(options: &CallOptions, entry: HistoryEntry) {
+    if !options.no_history {
+        history::record(&entry);
+    }
+}
+
+// ============================================================================
+// Streaming calls
+// ============================================================================
+
+/// Call a method and print each newline-delimited JSON frame as it arrives.
+///
+/// The stream ends when the daemon closes the connection or sends a
+/// `{"done": true}` frame. Ctrl-C shuts the socket down cleanly.
+fn stream_call(
+    service: &str,
+    method: &str,
+    params: Value,
+    context: Option<&Value>,
+    options: &CallOptions,
+) -> Result<()> {
+    let client = connect(service, options.no_auto_start)?;
+    let request = request_envelope(method, &params, context);
+    let history_params = params;
+    let stream = client.stream(&request)?;
+
+    // Closing the socket from the handler unblocks the read loop below
+    let interrupted = Arc::new(AtomicBool::new(false));
+    {
+        let interrupted = interrupted.clone();
+        let handle = stream.shutdown_handle()?;
+        ctrlc::set_handler(move || {
+            interrupted.store(true, Ordering::SeqCst);
+            handle.shutdown();
+        })
+        .context("Failed to install Ctrl-C handler")?;
+    }
+
+    let start = std::time::Instant::now();
+    let mut frames = Vec::new();
+
+    for frame in stream {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(_) if interrupted.load(Ordering::SeqCst) => break,
+            Err(e) => return Err(e).context("Failed to read from daemon"),
+        };
+
+        if frame["ok"] == Value::Bool(false) {
+            let code = frame["error"]["code"].as_str().unwrap_or("error");
+            let message = frame["error"]["message"]
+                .as_str()
+                .unwrap_or("unknown error");
+            eprintln!("{} Error ({}): {}", "✗".red().bold(), code, message);
+            record_history(
+                options,
+                HistoryEntry::new(service, method, &history_params, start.elapsed())
+                    .failed(format!("{}: {}", code, message)),
+            );
+            std::process::exit(1);
+        }
+
+        let done = frame["done"] == Value::Bool(true);
+        let payload = match frame.get("result") {
+            Some(result) if !result.is_null() => result.clone(),
+            _ if done => break,
+            _ => frame,
+        };
+
+        if options.json_output {
+            frames.push(payload);
+        } else {
+            println!("{}", serde_json::to_string_pretty(&payload)?);
+        }
+    }
+
+    let entry = HistoryEntry::new(service, method, &history_params, start.elapsed());
+    record_history(
+        options,
+        if interrupted.load(Ordering::SeqCst) {
+            entry.failed("interrupted")
+        } else {
+            entry
+        },
+    );
+
+    if options.json_output {
+        println!("{}", serde_json::to_string_pretty(&Value::Array(frames))?);
+    }
+
+    if interrupted.load(Ordering::SeqCst) {
+        eprintln!("{}", "(interrupted)".dimmed());
+        // Conventional exit status for SIGINT
+        std::process::exit(130);
+    } else {
+        eprintln!(
+            "{}",
+            format!("({:.1}ms stream)", start.elapsed().as_secs_f64() * 1000.0).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Make sure the daemon's socket exists, starting the service unless disabled.
+pub fn ensure_running(service: &str, socket_path: &Path, no_auto_start: bool) -> Result<()> {
+    if socket_path.exists() {
+        return Ok(());
+    }
+
+    if no_auto_start {
+        bail!(
+            "Service '{}' is not running. Run 'fgp start {}' first (or remove --no-auto-start).",
+            service,
+            service
+        );
+    }
+
+    start_and_wait(service)
+}
+
+/// Start a service and wait for it to report healthy.
+fn start_and_wait(service: &str) -> Result<()> {
+    super::daemon_command::start_service(service)
+        .with_context(|| format!("Failed to start service '{}'", service))?;
+
+    let timeout = Duration::from_secs(START_TIMEOUT_SECS);
+    if super::restart::wait_for_healthy(service, timeout).is_none() {
+        bail!(
+            "Service '{}' did not become healthy within {}s",
+            service,
+            START_TIMEOUT_SECS
+        );
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Method suggestions
+// ============================================================================
+
+/// Suggestions within this edit distance count as close matches for `--yes`.
+const CLOSE_MATCH_DISTANCE: usize = 2;
+
+/// Make sure the method exists in the service's manifest.json, suggesting
+/// the closest names if it doesn't.
+///
+/// A method missing from the manifest is checked against the running
+/// daemon's `methods` reply, since the manifest can lag behind the daemon.
+/// If the daemon can't be asked, the call goes ahead with a warning.
+///
+/// With `yes`, a method with exactly one close match is replaced by it.
+/// Services without a manifest are not checked.
+fn check_method(service: &str, method: String, yes: bool) -> Result<String> {
+    if BUILTIN_METHODS.contains(&method.as_str()) {
+        return Ok(method);
+    }
+
+    let manifest_path = super::fgp_services_dir()
+        .join(service)
+        .join("manifest.json");
+    let Ok(manifest) = super::skill_import::load_manifest(&manifest_path) else {
+        return Ok(method);
+    };
+    if manifest.methods.is_empty() {
+        return Ok(method);
+    }
+
+    // Manifests and daemons list methods either fully qualified or by their
+    // short name
+    let qualify = |name: &str| {
+        if name.contains('.') {
+            name.to_string()
+        } else {
+            format!("{}.{}", service, name)
+        }
+    };
+    let mut known: Vec<String> = manifest.methods.iter().map(|m| qualify(&m.name)).collect();
+    if known.contains(&method) {
+        return Ok(method);
+    }
+
+    let Some(daemon_known) = daemon_method_names(service) else {
+        eprintln!(
+            "{} {} is not in {}'s manifest.json and the daemon couldn't be asked; calling it anyway",
+            "!".yellow().bold(),
+            method.bold(),
+            service
+        );
+        return Ok(method);
+    };
+    for name in daemon_known {
+        let name = qualify(&name);
+        if name == method {
+            return Ok(method);
+        }
+        if !known.contains(&name) {
+            known.push(name);
+        }
+    }
+
+    correct_method(service, method, &known, &manifest.methods, yes)
+}
+
+/// Resolve a `method` that isn't one of the `known` ones: with `--yes`, the
+/// one close match, unless `declared` marks it destructive (`--yes` also
+/// skips confirming it, so a typo must not turn into an unconfirmed call);
+/// otherwise an error suggesting what was meant.
+fn correct_method(
+    service: &str,
+    method: String,
+    known: &[String],
+    declared: &[ManifestMethod],
+    yes: bool,
+) -> Result<String> {
+    let suggestions = suggest_methods(&method, known);
+    let close: Vec<&(String, usize)> = suggestions
+        .iter()
+        .filter(|(_, distance)| *distance <= CLOSE_MATCH_DISTANCE)
+        .collect();
+    let mut destructive_match = false;
+    if let [(name, _)] = close.as_slice() {
+        destructive_match = guardrails::find_destructive(declared, service, name).is_some();
+        if yes && !destructive_match {
+            eprintln!(
+                "{} Unknown method {}; calling {} instead",
+                "!".yellow().bold(),
+                method,
+                name.bold()
+            );
+            return Ok(name.clone());
+        }
+    }
+
+    let mut message = format!("Unknown method '{}' for service '{}'.", method, service);
+    if !suggestions.is_empty() {
+        let names: Vec<String> = suggestions
+            .iter()
+            .map(|(name, _)| format!("`{}`", name))
+            .collect();
+        message.push_str(&format!(" Did you mean {}?", names.join(" or ")));
+    }
+    if yes && destructive_match {
+        message.push_str(" It is destructive, so --yes doesn't call it in place of a typo.");
+    }
+    message.push_str(&format!(
+        "\nRun 'fgp methods {}' to list its methods.",
+        service
+    ));
+    bail!(message)
+}
+
+/// Method names the running daemon reports, or `None` if it isn't running or
+/// can't list them.
+fn daemon_method_names(service: &str) -> Option<Vec<String>> {
+    let socket = super::service_socket_path(service);
+    let result = Client::at(socket).methods().ok()?.into_result().ok()?;
+    let methods = result["methods"].as_array()?;
+    Some(
+        methods
+            .iter()
+            .filter_map(|method| method["name"].as_str().map(|name| name.to_string()))
+            .collect(),
+    )
+}
+
+/// Up to three known methods closest to `method`, nearest first.
+fn suggest_methods(method: &str, known: &[String]) -> Vec<(String, usize)> {
+    // Allow roughly one edit per three characters of the method's short name
+    let short_len = method.rsplit('.').next().unwrap_or(method).chars().count();
+    let max_distance = (short_len / 3).max(CLOSE_MATCH_DISTANCE);
+
+    let mut candidates: Vec<(String, usize)> = known
+        .iter()
+        .map(|name| (name.clone(), edit_distance(method, name)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .collect();
+    candidates.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    candidates.truncate(3);
+    candidates
+}
+
+/// Levenshtein distance between two strings, compared case-insensitively.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// ============================================================================
+// Param validation
+// ============================================================================
+
+/// Check params against the method's declared params in the service's
+/// manifest.json, failing before the daemon is contacted. With `full_schema`
+/// (`--validate`), params are checked against the whole JSON Schema the
+/// manifest gives for them, not just their types and whether they're
+/// required.
+///
+/// Methods without a manifest entry (including built-ins like `health`) are
+/// not checked.
+fn validate_params(service: &str, method: &str, params: &Value, full_schema: bool) -> Result<()> {
+    let manifest_path = super::fgp_services_dir()
+        .join(service)
+        .join("manifest.json");
+    let short = method.split('.').next_back().unwrap_or(method);
+    let manifest = super::skill_import::load_manifest(&manifest_path).ok();
+    let Some(declared) = manifest.as_ref().and_then(|manifest| {
+        manifest
+            .methods
+            .iter()
+            .find(|m| m.name == method || m.name == short)
+    }) else {
+        if full_schema && !BUILTIN_METHODS.contains(&short) {
+            eprintln!(
+                "{} {} isn't declared in {}; --validate has no schema to check against",
+                "!".yellow().bold(),
+                method,
+                manifest_path.display()
+            );
+        }
+        return Ok(());
+    };
+
+    let problems = if full_schema {
+        let schema = super::methods_export::params_schema(&declared.params);
+        super::json_schema::validate(&schema, params, "params")
+    } else {
+        param_problems(&declared.params, params)
+    };
+
+    let known: HashSet<&str> = declared.params.iter().map(|p| p.name.as_str()).collect();
+    if let Value::Object(map) = params {
+        for key in map.keys().filter(|k| !known.contains(k.as_str())) {
+            if !declared.params.is_empty() {
+                eprintln!(
+                    "{} '{}' is not a documented param of {}",
+                    "!".yellow().bold(),
+                    key,
+                    method
+                );
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let mut message = format!("Invalid params for {}:\n", method);
+    for problem in &problems {
+        message.push_str(&format!("  - {}\n", problem));
+    }
+    message.push_str("\nExpected params:\n");
+    for param in &declared.params {
+        message.push_str(&format!(
+            "  {} ({}{})",
+            param.name,
+            param.param_type.as_deref().unwrap_or("any"),
+            if param.required { ", required" } else { "" }
+        ));
+        if let Some(ref description) = param.description {
+            message.push_str(&format!(" - {}", description));
+        }
+        message.push('\n');
+    }
+    message.push_str(&format!(
+        "\nSee {}. Use --skip-validation if the manifest is out of date.",
+        manifest_path.display()
+    ));
+
+    bail!(message)
+}
+
+/// Describe every way `params` fails to match the declared params.
+fn param_problems(declared: &[ManifestParam], params: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let map = match params {
+        Value::Object(map) => map,
+        Value::Null => &empty,
+        _ => return vec!["params must be a JSON object".to_string()],
+    };
+
+    let mut problems = Vec::new();
+    for param in declared {
+        match map.get(&param.name) {
+            None | Some(Value::Null) if param.required && param.default.is_none() => {
+                problems.push(format!("missing required param '{}'", param.name));
+            }
+            Some(value) if !value.is_null() => {
+                let Some(ref expected) = param.param_type else {
+                    continue;
+                };
+                if !type_matches(expected, value) {
+                    let mut problem = format!(
+                        "'{}' should be {} {}, got {}",
+                        param.name,
+                        article(expected),
+                        expected,
+                        json_type(value)
+                    );
+                    // key=value always produces strings
+                    if value.is_string() && expected != "string" {
+                        problem.push_str(&format!(
+                            " (use {}:=<json> to pass a non-string value)",
+                            param.name
+                        ));
+                    }
+                    problems.push(problem);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    problems
+}
+
+/// Whether a value roughly matches a declared param type. Unknown types match.
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn article(word: &str) -> &'static str {
+    match word.chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u') => "an",
+        _ => "a",
+    }
+}
+
+// ============================================================================
+// Chained calls
+// ============================================================================
+
+/// A parsed `--param-from-call PARAM=METHOD[:PATH]`.
+#[derive(Debug, PartialEq)]
+struct ParamFromCall {
+    /// Param to fill; dotted keys nest
+    param: Vec<String>,
+    method: String,
+    /// Field of the lookup's result to use
+    path: Vec<String>,
+}
+
+impl ParamFromCall {
+    fn parse(raw: &str) -> Result<Self> {
+        let usage =
+            "Use --param-from-call PARAM=METHOD[:PATH], e.g. id=contacts.search:results.0.id";
+        let Some((param, rest)) = raw.split_once('=') else {
+            bail!("Invalid --param-from-call '{}'. {}", raw, usage);
+        };
+        let (method, path) = match rest.split_once(':') {
+            Some((method, path)) => (method, path),
+            None => (rest, param),
+        };
+
+        if !method.contains('.') || method.starts_with('.') || method.ends_with('.') {
+            bail!(
+                "Invalid --param-from-call '{}': '{}' is not a service.method name. {}",
+                raw,
+                method,
+                usage
+            );
+        }
+        let segments = |text: &str, what: &str| -> Result<Vec<String>> {
+            let segments: Vec<String> = text.split('.').map(|s| s.to_string()).collect();
+            if segments.iter().any(|segment| segment.is_empty()) {
+                bail!(
+                    "Invalid --param-from-call '{}': empty segment in {} '{}'. {}",
+                    raw,
+                    what,
+                    text,
+                    usage
+                );
+            }
+            Ok(segments)
+        };
+
+        Ok(Self {
+            param: segments(param, "param")?,
+            method: method.to_string(),
+            path: segments(path, "path")?,
+        })
+    }
+
+    /// Make the lookup call and set the param from its result. A dry run
+    /// only says what would be looked up.
+    fn resolve(&self, params: Value, options: &CallOptions) -> Result<Value> {
+        let param = self.param.join(".");
+        let path = self.path.join(".");
+        let Value::Object(mut params) = params else {
+            bail!("--param-from-call requires --params to be a JSON object");
+        };
+        if super::workflow_engine::lookup_path(&Value::Object(params.clone()), &self.param)
+            .is_some()
+        {
+            bail!(
+                "'{}' is set both by a parameter and by --param-from-call",
+                param
+            );
+        }
+
+        if options.dry_run {
+            println!(
+                "{} Would first call {} and use '{}' from its result as '{}'",
+                "→".blue().bold(),
+                self.method.bold(),
+                path,
+                param
+            );
+            insert_path(
+                &mut params,
+                &self.param,
+                Value::String(format!("<{}:{}>", self.method, path)),
+            );
+            return Ok(Value::Object(params));
+        }
+
+        let result = self.lookup(options)?;
+        if options.verbose {
+            eprintln!("{} {} returned:", "→".blue().bold(), self.method.bold());
+            eprintln!("{}", serde_json::to_string_pretty(&result)?);
+        }
+
+        let value = match super::workflow_engine::lookup_path(&result, &self.path) {
+            Some(value) => value.clone(),
+            None => bail!(
+                "'{}' not found in the result of {}{}",
+                path,
+                self.method,
+                available_fields(&result, &self.path)
+            ),
+        };
+        if options.verbose {
+            eprintln!("{} {} = {}", "→".blue().bold(), param, value);
+        }
+
+        insert_path(&mut params, &self.param, value);
+        Ok(Value::Object(params))
+    }
+
+    /// Call the lookup method, with the same checks as any other call.
+    fn lookup(&self, options: &CallOptions) -> Result<Value> {
+        let service = self.method.split('.').next().unwrap_or(&self.method);
+        let params: Value = match options.from_call_params {
+            "" => Value::Object(Map::new()),
+            params => serde_json::from_str(params).context("Invalid JSON in --from-call-params")?,
+        };
+
+        let method = if options.skip_validation {
+            self.method.clone()
+        } else {
+            let method = check_method(service, self.method.clone(), options.yes)?;
+            validate_params(service, &method, &params, options.validate)?;
+            method
+        };
+        let params = guard_destructive(service, &method, params, options.yes)?;
+
+        let start = std::time::Instant::now();
+        let entry = |elapsed| HistoryEntry::new(service, &method, &params, elapsed);
+        let response = match call_daemon(service, &method, &params, None, options) {
+            Ok(response) => response,
+            Err(e) => {
+                record_history(options, entry(start.elapsed()).failed(e.to_string()));
+                return Err(e.context(format!("Lookup call {} failed", method)));
+            }
+        };
+        if let Some((code, message)) = response.error {
+            let error = format!("{}: {}", code, message);
+            record_history(options, entry(response.elapsed).failed(error));
+            bail!("Lookup call {} failed ({}): {}", method, code, message);
+        }
+        record_history(options, entry(response.elapsed));
+
+        Ok(response.result.unwrap_or(Value::Null))
+    }
+}
+
+/// " (fields at 'a.b': x, y)" for the deepest part of `path` that exists in
+/// `value`, to say what a missing path could have been.
+fn available_fields(value: &Value, path: &[String]) -> String {
+    let mut found = 0;
+    let mut current = value;
+    for segment in path {
+        match super::workflow_engine::lookup_path(current, std::slice::from_ref(segment)) {
+            Some(next) => {
+                current = next;
+                found += 1;
+            }
+            None => break,
+        }
+    }
+
+    let at = if found == 0 {
+        "the top level".to_string()
+    } else {
+        format!("'{}'", path[..found].join("."))
+    };
+    match current {
+        Value::Object(map) if !map.is_empty() => {
+            let keys: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+            format!(" (fields at {}: {})", at, keys.join(", "))
+        }
+        Value::Array(items) => format!(" ({} is an array of {} item(s))", at, items.len()),
+        Value::Object(_) => format!(" ({} is an empty object)", at),
+        other => format!(
+            " ({} is {} {})",
+            at,
+            article(json_type(other)),
+            json_type(other)
+        ),
+    }
+}
+
+/// Set a dotted path in a params object, creating objects on the way.
+fn insert_path(params: &mut Map<String, Value>, path: &[String], value: Value) {
+    let (last, intermediate) = path.split_last().expect("path is non-empty");
+    let mut current = params;
+    for segment in intermediate {
+        let entry = current
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry.as_object_mut().expect("just ensured object");
+    }
+    current.insert(last.clone(), value);
+}
+
+// ============================================================================
+// key=value / key:=json pair parsing
+// ============================================================================
+
+/// A parsed `key=value` or `key:=json` argument.
+#[derive(Debug, PartialEq)]
+struct Pair {
+    path: Vec<String>,
+    value: Value,
+}
+
+/// Parse a single pair. `=` assigns a string, `:=` assigns a raw JSON value.
+fn parse_pair(raw: &str) -> Result<Pair> {
+    let eq = match raw.find('=') {
+        Some(i) => i,
+        None => bail!(
+            "Invalid parameter '{}'. Use key=value (string) or key:=value (JSON)",
+            raw
+        ),
+    };
+
+    let (key, is_json) = if raw[..eq].ends_with(':') {
+        (&raw[..eq - 1], true)
+    } else {
+        (&raw[..eq], false)
+    };
+    let rest = &raw[eq + 1..];
+
+    if key.is_empty() {
+        bail!("Invalid parameter '{}': missing key before '='", raw);
+    }
+
+    let path: Vec<String> = key.split('.').map(|s| s.to_string()).collect();
+    if path.iter().any(|segment| segment.is_empty()) {
+        bail!(
+            "Invalid parameter '{}': empty segment in key '{}'",
+            raw,
+            key
+        );
+    }
+
+    let value = if is_json {
+        serde_json::from_str(rest).with_context(|| {
+            format!(
+                "Invalid JSON for '{}' in '{}'. Use key=value for plain strings",
+                key, raw
+            )
+        })?
+    } else {
+        Value::String(rest.to_string())
+    };
+
+    Ok(Pair { path, value })
+}
+
+/// Merge pairs into the base params object. Pairs override keys from `-p`;
+/// repeating a key collects its values into an array.
+fn apply_pairs(base: Value, pairs: &[String]) -> Result<Value> {
+    if pairs.is_empty() {
+        return Ok(base);
+    }
+
+    let mut root = match base {
+        Value::Object(map) => map,
+        _ => bail!("key=value parameters require --params to be a JSON object"),
+    };
+
+    // Full paths assigned by pairs, paths that now have children, and paths
+    // that were turned into arrays by repetition.
+    let mut assigned: HashSet<String> = HashSet::new();
+    let mut parents: HashSet<String> = HashSet::new();
+    let mut repeated: HashSet<String> = HashSet::new();
+
+    for raw in pairs {
+        let pair = parse_pair(raw)?;
+        let full = pair.path.join(".");
+
+        for i in 1..pair.path.len() {
+            let prefix = pair.path[..i].join(".");
+            if assigned.contains(&prefix) {
+                bail!(
+                    "Parameter '{}' conflicts with '{}', which was already set to a value",
+                    full,
+                    prefix
+                );
+            }
+        }
+        if parents.contains(&full) {
+            bail!(
+                "Parameter '{}' conflicts with nested keys already set under '{}.'",
+                full,
+                full
+            );
+        }
+
+        let (last, intermediate) = pair.path.split_last().expect("path is non-empty");
+        let mut current = &mut root;
+        for segment in intermediate {
+            let entry = current
+                .entry(segment.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                // Scalar from the -p base: pairs win
+                *entry = Value::Object(Map::new());
+            }
+            current = entry.as_object_mut().expect("just ensured object");
+        }
+
+        if assigned.contains(&full) {
+            let existing = current.get_mut(last).expect("assigned key exists");
+            if repeated.contains(&full) {
+                if let Value::Array(items) = existing {
+                    items.push(pair.value);
+                }
+            } else {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, pair.value]);
+                repeated.insert(full);
+            }
+        } else {
+            current.insert(last.clone(), pair.value);
+            for i in 1..pair.path.len() {
+                parents.insert(pair.path[..i].join("."));
+            }
+            assigned.insert(full);
+        }
+    }
+
+    Ok(Value::Object(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pairs(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_context_envelope() {
+        let params = serde_json::json!({"limit": 5});
+        let request = serde_json::to_value(request_envelope("chat.reply", &params, None)).unwrap();
+        assert!(request.get("context").is_none());
+
+        let conversation = context_value(r#"[{"role": "user", "content": "hi"}]"#.to_string());
+        let request = request_envelope("chat.reply", &params, Some(&conversation));
+        let request = serde_json::to_value(request).unwrap();
+        assert_eq!(request["context"][0]["role"], "user");
+        assert_eq!(request["params"], params);
+
+        // Only objects and arrays are treated as JSON
+        assert_eq!(
+            context_value("42\n".to_string()),
+            Value::String("42\n".to_string())
+        );
+        assert_eq!(
+            context_value("You are terse.".to_string()),
+            Value::String("You are terse.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_method_suggestions() {
+        assert_eq!(edit_distance("gmail.lst", "gmail.list"), 1);
+        assert_eq!(edit_distance("Gmail.List", "gmail.list"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+
+        let known: Vec<String> = ["gmail.list", "gmail.last", "gmail.send", "gmail.unread"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let names = |method: &str| -> Vec<String> {
+            suggest_methods(method, &known)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert_eq!(names("gmail.lst"), vec!["gmail.last", "gmail.list"]);
+        assert_eq!(names("gmail.sned"), vec!["gmail.send"]);
+        assert!(names("gmail.archive").is_empty());
+    }
+
+    #[test]
+    fn test_yes_never_corrects_to_a_destructive_method() {
+        let declared: Vec<ManifestMethod> = serde_json::from_value(json!([
+            {"name": "files.delete", "destructive": true},
+            {"name": "list"},
+        ]))
+        .unwrap();
+        let known = vec!["files.delete".to_string(), "files.list".to_string()];
+        let correct =
+            |method: &str, yes| correct_method("files", method.to_string(), &known, &declared, yes);
+
+        assert_eq!(correct("files.lsit", true).unwrap(), "files.list");
+        assert!(correct("files.lsit", false).is_err());
+
+        // `files.delte --yes` must not become an unconfirmed files.delete
+        let err = correct("files.delte", true).unwrap_err().to_string();
+        assert!(err.contains("Did you mean `files.delete`?"), "{}", err);
+        assert!(err.contains("It is destructive"), "{}", err);
+    }
+
+    #[test]
+    fn test_string_and_json_pairs() {
+        let result = apply_pairs(
+            json!({}),
+            &pairs(&["query=from:boss", "limit:=5", "archived:=false"]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            json!({"query": "from:boss", "limit": 5, "archived": false})
+        );
+    }
+
+    #[test]
+    fn test_quoting_edge_cases() {
+        // Only the first '=' separates key and value
+        let result = apply_pairs(json!({}), &pairs(&["q=a=b", "expr=x:=y", "empty="])).unwrap();
+        assert_eq!(result, json!({"q": "a=b", "expr": "x:=y", "empty": ""}));
+
+        // Quotes survive as literal characters when the shell passes them through
+        let result =
+            apply_pairs(json!({}), &pairs(&["q=\"quoted\"", "s:=\"json string\""])).unwrap();
+        assert_eq!(result, json!({"q": "\"quoted\"", "s": "json string"}));
+    }
+
+    #[test]
+    fn test_malformed_pairs() {
+        assert!(parse_pair("novalue").is_err());
+        assert!(parse_pair("=value").is_err());
+        assert!(parse_pair(":=1").is_err());
+        assert!(parse_pair("a..b=1").is_err());
+        assert!(parse_pair("limit:=five").is_err());
+    }
+
+    #[test]
+    fn test_nested_keys() {
+        let result = apply_pairs(
+            json!({}),
+            &pairs(&["filter.label=work", "filter.unread:=true"]),
+        )
+        .unwrap();
+        assert_eq!(result, json!({"filter": {"label": "work", "unread": true}}));
+    }
+
+    #[test]
+    fn test_nested_key_collides_with_scalar() {
+        assert!(apply_pairs(json!({}), &pairs(&["filter=x", "filter.label=work"])).is_err());
+        assert!(apply_pairs(json!({}), &pairs(&["filter.label=work", "filter=x"])).is_err());
+
+        // A scalar from the -p base is overridden rather than rejected
+        let result = apply_pairs(json!({"filter": "x"}), &pairs(&["filter.label=work"])).unwrap();
+        assert_eq!(result, json!({"filter": {"label": "work"}}));
+    }
+
+    #[test]
+    fn test_repeated_keys_become_arrays() {
+        let result = apply_pairs(
+            json!({}),
+            &pairs(&["to=a@example.com", "to=b@example.com", "to=c@example.com"]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            json!({"to": ["a@example.com", "b@example.com", "c@example.com"]})
+        );
+    }
+
+    #[test]
+    fn test_pairs_override_base() {
+        let result =
+            apply_pairs(json!({"limit": 10, "keep": true}), &pairs(&["limit:=5"])).unwrap();
+        assert_eq!(result, json!({"limit": 5, "keep": true}));
+        assert!(apply_pairs(json!([1, 2]), &pairs(&["a=b"])).is_err());
+    }
+
+    fn param(name: &str, param_type: &str, required: bool) -> ManifestParam {
+        ManifestParam {
+            name: name.to_string(),
+            param_type: Some(param_type.to_string()),
+            required,
+            default: None,
+            description: None,
+            items: None,
+            properties: None,
+            schema: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_required_param() {
+        let declared = vec![param("to", "string", true), param("cc", "string", false)];
+
+        assert!(param_problems(&declared, &json!({"to": "a@example.com"})).is_empty());
+
+        let problems = param_problems(&declared, &json!({"too": "a@example.com"}));
+        assert_eq!(problems, vec!["missing required param 'to'"]);
+        assert_eq!(
+            param_problems(&declared, &json!({"to": null})),
+            vec!["missing required param 'to'"]
+        );
+        assert_eq!(param_problems(&declared, &Value::Null).len(), 1);
+
+        // A default satisfies a required param
+        let mut with_default = param("limit", "integer", true);
+        with_default.default = Some(json!(10));
+        assert!(param_problems(&[with_default], &json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_wrong_param_type() {
+        let declared = vec![
+            param("query", "string", false),
+            param("limit", "integer", false),
+            param("unread", "boolean", false),
+            param("extra", "custom", false),
+        ];
+
+        assert!(param_problems(
+            &declared,
+            &json!({"query": "x", "limit": 5, "unread": true, "extra": [1]})
+        )
+        .is_empty());
+
+        let problems = param_problems(&declared, &json!({"limit": "5", "unread": 1}));
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("'limit' should be an integer, got string"));
+        assert!(problems[0].contains("limit:=<json>"));
+        assert!(problems[1].contains("'unread' should be a boolean, got integer"));
+
+        assert_eq!(
+            param_problems(&declared, &json!({"limit": 2.5})),
+            vec!["'limit' should be an integer, got number"]
+        );
+        assert_eq!(param_problems(&declared, &json!([1])).len(), 1);
+    }
+
+    #[test]
+    fn test_unicode_values() {
+        let result = apply_pairs(
+            json!({}),
+            &pairs(&["subject=Grüße 👋", "名前=値", "tags:=[\"日本\", \"é\"]"]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            json!({"subject": "Grüße 👋", "名前": "値", "tags": ["日本", "é"]})
+        );
+    }
+
+    #[test]
+    fn test_parse_param_from_call() {
+        let chain = ParamFromCall::parse("id=contacts.search:results.0.id").unwrap();
+        assert_eq!(
+            chain,
+            ParamFromCall {
+                param: vec!["id".to_string()],
+                method: "contacts.search".to_string(),
+                path: vec!["results".to_string(), "0".to_string(), "id".to_string()],
+            }
+        );
+
+        // The path defaults to the param
+        let chain = ParamFromCall::parse("thread.id=gmail.latest").unwrap();
+        assert_eq!(chain.path, chain.param);
+        assert_eq!(chain.method, "gmail.latest");
+
+        for bad in [
+            "id",
+            "id=search",
+            "id=gmail.",
+            "id=gmail.latest:a..b",
+            "=gmail.latest",
+        ] {
+            assert!(ParamFromCall::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_param_from_call_dry_run() {
+        let chain = ParamFromCall::parse("to.id=contacts.search:results.0.id").unwrap();
+        let options = CallOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let params = chain.resolve(json!({"body": "hi"}), &options).unwrap();
+        assert_eq!(
+            params,
+            json!({"body": "hi", "to": {"id": "<contacts.search:results.0.id>"}})
+        );
+
+        let err = chain
+            .resolve(json!({"to": {"id": "x"}}), &options)
+            .unwrap_err();
+        assert!(err.to_string().contains("set both"), "{}", err);
+    }
+
+    #[test]
+    fn test_select() {
+        let result = json!({"messages": [{"id": "m1"}], "ok": true});
+        assert_eq!(select(result.clone(), None).unwrap(), result);
+        assert_eq!(
+            select(result.clone(), Some("/messages/0/id")).unwrap(),
+            json!("m1")
+        );
+        let err = select(result.clone(), Some("/messages/1")).unwrap_err();
+        assert_eq!(err.to_string(), "Nothing at /messages/1 in the result");
+        let err = select(result, Some("messages.0.id")).unwrap_err();
+        assert!(err.to_string().contains("/messages/0/id"), "{}", err);
+    }
+
+    #[test]
+    fn test_expectations() {
+        let output = serde_json::to_string_pretty(&json!({"ok": true})).unwrap();
+        let options = CallOptions {
+            expect_exit: Some(0),
+            expect_contains: Some("\"ok\""),
+            ..Default::default()
+        };
+        assert!(check_expectations(&options, 0, &output).is_ok());
+        assert_eq!(
+            check_expectations(&options, 1, "auth: token expired").unwrap_err(),
+            "expected exit 0, got 1"
+        );
+
+        let options = CallOptions {
+            expect_contains: Some("\"degraded\""),
+            ..Default::default()
+        };
+        assert_eq!(
+            check_expectations(&options, 0, &output).unwrap_err(),
+            "expected output containing '\"degraded\"', got '{ \"ok\": true }'"
+        );
+        assert_eq!(
+            check_expectations(&options, 0, "").unwrap_err(),
+            "expected output containing '\"degraded\"', got no output"
+        );
+        // An expected failure
+        let options = CallOptions {
+            expect_exit: Some(1),
+            expect_contains: Some("expired"),
+            ..Default::default()
+        };
+        assert!(check_expectations(&options, 1, "auth: token expired").is_ok());
+    }
+
+    #[test]
+    fn test_available_fields() {
+        let result = json!({"results": [{"id": 1, "name": "Ada"}], "total": 1});
+        let path = |p: &str| -> Vec<String> { p.split('.').map(|s| s.to_string()).collect() };
+
+        assert_eq!(
+            available_fields(&result, &path("results.0.email")),
+            " (fields at 'results.0': id, name)"
+        );
+        assert_eq!(
+            available_fields(&result, &path("results.3.id")),
+            " ('results' is an array of 1 item(s))"
+        );
+        assert_eq!(
+            available_fields(&result, &path("items")),
+            " (fields at the top level: results, total)"
+        );
+        assert_eq!(
+            available_fields(&result, &path("total.value")),
+            " ('total' is an integer)"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Shell completion scripts.
+//!
+//! `fgp completions <shell>` prints a static script covering subcommands and
+//! flags. `--dynamic` prints a small registration script instead, which asks
+//! `fgp` itself for candidates on every <Tab> (via the `COMPLETE` environment
+//! variable handled at startup), so service names, installed skill names, and
+//! `fgp call` method names complete from what is installed right now.
+
+use anyhow::{Context, Result};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{Bash, Elvish, EnvCompleter, Fish, Powershell, Zsh};
+use clap_complete::Shell;
+use std::fs;
+use std::io::Write;
+
+use super::fgp_services_dir;
+use super::restart::installed_services;
+
+/// Environment variable that switches `fgp` into completion mode.
+pub const COMPLETE_ENV: &str = "COMPLETE";
+
+/// Write the completion script for `shell` to `output`, or stdout.
+pub fn run(
+    mut cmd: clap::Command,
+    shell: Shell,
+    dynamic: bool,
+    output: Option<&str>,
+) -> Result<()> {
+    let mut script = Vec::new();
+    if dynamic {
+        let completer: &dyn EnvCompleter = match shell {
+            Shell::Bash => &Bash,
+            Shell::Elvish => &Elvish,
+            Shell::Fish => &Fish,
+            Shell::PowerShell => &Powershell,
+            Shell::Zsh => &Zsh,
+            _ => anyhow::bail!("Dynamic completion is not supported for {}", shell),
+        };
+        completer.write_registration(COMPLETE_ENV, "fgp", "fgp", "fgp", &mut script)?;
+    } else {
+        clap_complete::generate(shell, &mut cmd, "fgp", &mut script);
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(path, &script).with_context(|| format!("Failed to write {}", path))?;
+            eprintln!("Wrote {} completions to {}", shell, path);
+        }
+        None => std::io::stdout().write_all(&script)?,
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Dynamic candidates
+// ============================================================================
+
+/// Installed service names (services in `~/.fgp/services` with a manifest).
+pub fn services() -> Vec<CompletionCandidate> {
+    installed_services()
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Installed skill names.
+pub fn skills() -> Vec<CompletionCandidate> {
+    super::skill::installed_skill_sources()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, _)| CompletionCandidate::new(name))
+        .collect()
+}
+
+/// `service.method` names from installed services' manifests, with the
+/// method description as help.
+pub fn methods() -> Vec<CompletionCandidate> {
+    let mut candidates = Vec::new();
+    for service in installed_services().unwrap_or_default() {
+        let manifest_path = fgp_services_dir().join(&service).join("manifest.json");
+        let Ok(manifest) = super::skill_import::load_manifest(&manifest_path) else {
+            continue;
+        };
+        for method in manifest.methods {
+            candidates.push(
+                CompletionCandidate::new(qualified_method(&service, &method.name))
+                    .help(method.description.map(Into::into)),
+            );
+        }
+    }
+    candidates
+}
+
+/// Manifests list methods as `send` or `gmail.send`; `fgp call` takes the latter.
+fn qualified_method(service: &str, method: &str) -> String {
+    if method.starts_with(&format!("{}.", service)) {
+        method.to_string()
+    } else {
+        format!("{}.{}", service, method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualified_method() {
+        assert_eq!(qualified_method("gmail", "send"), "gmail.send");
+        assert_eq!(qualified_method("gmail", "gmail.send"), "gmail.send");
+        assert_eq!(
+            qualified_method("gmail", "gmailx.send"),
+            "gmail.gmailx.send"
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Start and stop commands configured for a daemon.
+//!
+//! A service's manifest.json can say what to run to start and stop its daemon
+//! instead of the daemon's entrypoint and the protocol's `stop` call, as a
+//! program and its arguments in the `daemon` section. `fgp start` and
+//! `fgp stop` use them, and so does everything else that starts or stops a
+//! daemon, through [`start_service`] and [`stop_service`]:
+//!
+//! ```json
+//! "daemon": {
+//!   "entrypoint": "./bin/gateway",
+//!   "start_command": ["{service_dir}/bin/gateway", "--socket", "{socket}", "--log", "{log_file}"],
+//!   "stop_command": ["{service_dir}/bin/gateway", "stop", "--pid-file", "{pid_file}"]
+//! }
+//! ```
+//!
+//! (A legacy skill.json's `daemon.start_command` and `daemon.stop_command`
+//! are used when the manifest has none.) Placeholders are expanded when the
+//! command runs, so manifests don't need absolute paths and work wherever
+//! the services directory is:
+//!
+//! - `{socket}` - the service's socket
+//! - `{service_dir}` - `~/.fgp/services/<service>`
+//! - `{log_file}` - where `fgp logs` reads the service's log
+//! - `{pid_file}` - the service's PID file
+//! - `{env:VAR}` - the environment variable `VAR` (an error if it's unset)
+//!
+//! `{{` and `}}` stand for literal braces; any other placeholder is an error,
+//! reported when the package is installed. Commands aren't run through a
+//! shell, so a value with spaces stays one argument. For commands that are a
+//! shell script (`sh -c '<script>'`, or bash or zsh), the values substituted
+//! into the script are single-quoted.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::{fgp_services_dir, service_pid_path, service_socket_path};
+
+/// Placeholders other than `{env:VAR}`.
+pub const PLACEHOLDERS: &[&str] = &["socket", "service_dir", "log_file", "pid_file"];
+
+/// Shells whose `-c` script gets its substituted values quoted.
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash"];
+
+/// Values of the path placeholders for one service.
+#[derive(Debug, Clone)]
+pub struct CommandPaths {
+    pub socket: PathBuf,
+    pub service_dir: PathBuf,
+    pub log_file: PathBuf,
+    pub pid_file: PathBuf,
+}
+
+impl CommandPaths {
+    pub fn for_service(service: &str) -> Self {
+        Self {
+            socket: service_socket_path(service),
+            service_dir: fgp_services_dir().join(service),
+            log_file: super::logs::log_file_path(service),
+            pid_file: service_pid_path(service),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&PathBuf> {
+        match name {
+            "socket" => Some(&self.socket),
+            "service_dir" => Some(&self.service_dir),
+            "log_file" => Some(&self.log_file),
+            "pid_file" => Some(&self.pid_file),
+            _ => None,
+        }
+    }
+}
+
+/// A piece of a command argument.
+#[derive(Debug, PartialEq)]
+enum Piece<'a> {
+    Text(String),
+    Placeholder(&'a str),
+}
+
+/// The `key` command (`start_command` or `stop_command`) configured for
+/// `service`: from its installed manifest.json, or else from an installed
+/// skill's legacy daemon config. `None` if neither sets one.
+pub fn configured(service: &str, key: &str) -> Result<Option<Vec<String>>> {
+    let manifest_path = fgp_services_dir().join(service).join("manifest.json");
+    if let Ok(content) = std::fs::read_to_string(&manifest_path) {
+        let manifest: Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+        if let Some(command) = from_manifest(&manifest, key)? {
+            return Ok(Some(command));
+        }
+    }
+
+    let legacy = super::skill::daemon_config(service).map(|daemon| match key {
+        "start_command" => daemon.start_command,
+        _ => daemon.stop_command,
+    });
+    Ok(legacy.filter(|command| !command.is_empty()))
+}
+
+/// Start `service` in the background, the way everything but `fgp start`
+/// does (restarts, `fgp up`, auto-start, the TUI, the MCP bridge): with its
+/// `start_command` if it has one, or else the daemon's entrypoint. Its output
+/// goes to the service's log, and callers wait for it to become healthy.
+pub fn start_service(service: &str) -> Result<()> {
+    let Some(start_command) = configured(service, "start_command")? else {
+        return fgp_daemon::lifecycle::start_service(service);
+    };
+
+    spawn(&start_command, &CommandPaths::for_service(service))?;
+    Ok(())
+}
+
+/// Run `start_command` in the background with its output going to the log.
+fn spawn(start_command: &[String], paths: &CommandPaths) -> Result<std::process::Child> {
+    let args = expand(start_command, paths).context("Invalid daemon.start_command")?;
+    if let Some(dir) = paths.log_file.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&paths.log_file)
+        .with_context(|| format!("Failed to open {}", paths.log_file.display()))?;
+
+    let mut command = Command::new(&args[0]);
+    command
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    if paths.service_dir.is_dir() {
+        command.current_dir(&paths.service_dir);
+    }
+    command
+        .spawn()
+        .with_context(|| format!("Failed to run start command '{}'", args[0]))
+}
+
+/// Stop `service` with its `stop_command` if it has one, or else the
+/// protocol's `stop` call. Nothing is printed, so the TUI and the MCP bridge
+/// can use it too.
+pub fn stop_service(service: &str) -> Result<()> {
+    let Some(stop_command) = configured(service, "stop_command")? else {
+        return fgp_daemon::lifecycle::stop_service(service);
+    };
+
+    let paths = CommandPaths::for_service(service);
+    let args = expand(&stop_command, &paths).context("Invalid daemon.stop_command")?;
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]).stdin(Stdio::null());
+    if paths.service_dir.is_dir() {
+        command.current_dir(&paths.service_dir);
+    }
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run stop command '{}'", args[0]))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match stderr.trim() {
+            "" => bail!("Stop command exited with status: {}", output.status),
+            message => bail!(
+                "Stop command exited with status: {} ({})",
+                output.status,
+                message
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// `daemon.<key>` in a manifest.json, which must be an array of strings.
+pub fn from_manifest(manifest: &Value, key: &str) -> Result<Option<Vec<String>>> {
+    match &manifest["daemon"][key] {
+        Value::Null => Ok(None),
+        Value::Array(args) if args.is_empty() => Ok(None),
+        Value::Array(args) => args
+            .iter()
+            .map(|arg| arg.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .map(Some)
+            .with_context(|| format!("daemon.{} must be an array of strings", key)),
+        _ => bail!("daemon.{} must be an array of strings", key),
+    }
+}
+
+/// Everything wrong with the placeholders in `command`.
+pub fn check(command: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for arg in command {
+        match parse(arg) {
+            Ok(pieces) => {
+                for piece in pieces {
+                    if let Piece::Placeholder(name) = piece {
+                        if let Err(e) = check_placeholder(name) {
+                            problems.push(format!("{} in \"{}\"", e, arg));
+                        }
+                    }
+                }
+            }
+            Err(e) => problems.push(format!("{} in \"{}\"", e, arg)),
+        }
+    }
+    problems
+}
+
+/// `command` with its placeholders replaced, ready to run.
+pub fn expand(command: &[String], paths: &CommandPaths) -> Result<Vec<String>> {
+    let program = command
+        .first()
+        .map(|program| program.rsplit('/').next().unwrap_or(program))
+        .unwrap_or_default();
+    let is_shell = SHELLS.contains(&program);
+
+    let mut expanded = Vec::with_capacity(command.len());
+    for (i, arg) in command.iter().enumerate() {
+        let quote = is_shell && i >= 2 && command[i - 1] == "-c";
+        let mut out = String::new();
+        for piece in parse(arg).map_err(|e| anyhow::anyhow!("{} in \"{}\"", e, arg))? {
+            let value = match piece {
+                Piece::Text(text) => {
+                    out.push_str(&text);
+                    continue;
+                }
+                Piece::Placeholder(name) => {
+                    check_placeholder(name).map_err(|e| anyhow::anyhow!("{} in \"{}\"", e, arg))?;
+                    match name.strip_prefix("env:") {
+                        Some(var) => std::env::var(var).map_err(|_| {
+                            anyhow::anyhow!(
+                                "{{{}}} in \"{}\" needs the environment variable {}, which isn't set",
+                                name,
+                                arg,
+                                var
+                            )
+                        })?,
+                        None => paths
+                            .get(name)
+                            .map(|path| path.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                    }
+                }
+            };
+            if quote {
+                out.push_str(&shell_quote(&value));
+            } else {
+                out.push_str(&value);
+            }
+        }
+        expanded.push(out);
+    }
+    Ok(expanded)
+}
+
+/// Split an argument into text and placeholders.
+fn parse(arg: &str) -> Result<Vec<Piece<'_>>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut rest = arg;
+    while let Some(i) = rest.find(['{', '}']) {
+        text.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+            text.push_str(&tail[..1]);
+            rest = after;
+        } else if tail.starts_with('}') {
+            return Err("unmatched '}' (write '}}' for a literal brace)".to_string());
+        } else {
+            let Some(end) = tail.find('}') else {
+                return Err("unclosed '{' (write '{{' for a literal brace)".to_string());
+            };
+            if !text.is_empty() {
+                pieces.push(Piece::Text(std::mem::take(&mut text)));
+            }
+            pieces.push(Piece::Placeholder(&tail[1..end]));
+            rest = &tail[end + 1..];
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+fn check_placeholder(name: &str) -> Result<(), String> {
+    if PLACEHOLDERS.contains(&name) {
+        return Ok(());
+    }
+    match name.strip_prefix("env:") {
+        Some(var)
+            if var.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            Ok(())
+        }
+        Some(var) => Err(format!("invalid environment variable name '{}'", var)),
+        None => Err(format!(
+            "unknown placeholder {{{}}} (expected {}, or {{env:VAR}})",
+            name,
+            PLACEHOLDERS
+                .iter()
+                .map(|p| format!("{{{}}}", p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// `value` single-quoted for a POSIX shell.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths() -> CommandPaths {
+        CommandPaths {
+            socket: PathBuf::from("/home/ada/My Files/.fgp/services/gmail/daemon.sock"),
+            service_dir: PathBuf::from("/home/ada/My Files/.fgp/services/gmail"),
+            log_file: PathBuf::from("/home/ada/My Files/.fgp/services/gmail/logs/daemon.log"),
+            pid_file: PathBuf::from("/home/ada/My Files/.fgp/services/gmail/daemon.pid"),
+        }
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expand() {
+        let command = strings(&[
+            "{service_dir}/bin/gateway",
+            "--socket={socket}",
+            "--log",
+            "{log_file}",
+            "{{literal}}",
+        ]);
+        assert_eq!(
+            expand(&command, &paths()).unwrap(),
+            vec![
+                "/home/ada/My Files/.fgp/services/gmail/bin/gateway",
+                "--socket=/home/ada/My Files/.fgp/services/gmail/daemon.sock",
+                "--log",
+                "/home/ada/My Files/.fgp/services/gmail/logs/daemon.log",
+                "{literal}",
+            ]
+        );
+
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(
+            expand(&strings(&["--path={env:PATH}"]), &paths()).unwrap(),
+            vec![format!("--path={}", path)]
+        );
+        let err = expand(&strings(&["{env:FGP_TEST_DAEMON_UNSET}"]), &paths())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("FGP_TEST_DAEMON_UNSET, which isn't set"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_expand_shell_script() {
+        let command = strings(&[
+            "/bin/sh",
+            "-c",
+            "exec ./gateway --pid-file {pid_file} >> {log_file} 2>&1",
+        ]);
+        let expanded = expand(&command, &paths()).unwrap();
+        assert_eq!(
+            expanded[2],
+            "exec ./gateway --pid-file '/home/ada/My Files/.fgp/services/gmail/daemon.pid' \
+             >> '/home/ada/My Files/.fgp/services/gmail/logs/daemon.log' 2>&1"
+        );
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+
+        // Run it: the quoted path survives word splitting
+        #[cfg(unix)]
+        {
+            let dir = tempfile::tempdir().unwrap();
+            let paths = CommandPaths {
+                service_dir: dir.path().join("with space"),
+                ..paths()
+            };
+            let command = strings(&["sh", "-c", "mkdir -p {service_dir} && ls -d {service_dir}"]);
+            let args = expand(&command, &paths).unwrap();
+            let out = std::process::Command::new(&args[0])
+                .args(&args[1..])
+                .output()
+                .unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&out.stdout).trim(),
+                paths.service_dir.to_string_lossy()
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_logs_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = CommandPaths {
+            socket: dir.path().join("daemon.sock"),
+            service_dir: dir.path().to_path_buf(),
+            log_file: dir.path().join("logs/daemon.log"),
+            pid_file: dir.path().join("daemon.pid"),
+        };
+        let command = strings(&["sh", "-c", "echo listening on {socket}; pwd >&2"]);
+        let status = spawn(&command, &paths).unwrap().wait().unwrap();
+        assert!(status.success());
+
+        let log = std::fs::read_to_string(&paths.log_file).unwrap();
+        assert_eq!(
+            log,
+            format!(
+                "listening on {}\n{}\n",
+                paths.socket.display(),
+                dir.path().canonicalize().unwrap().display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(&strings(&["{service_dir}/gw", "{env:HOME}", "{{x}}"])).is_empty());
+
+        let problems = check(&strings(&["{socket_path}", "{env:1X}", "{open", "close}"]));
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].starts_with("unknown placeholder {socket_path}"));
+        assert!(problems[1].contains("invalid environment variable name '1X'"));
+        assert!(problems[2].starts_with("unclosed '{'"));
+        assert!(problems[3].starts_with("unmatched '}'"));
+
+        let manifest =
+            serde_json::json!({"daemon": {"start_command": ["a", 1], "stop_command": []}});
+        assert!(from_manifest(&manifest, "start_command").is_err());
+        assert_eq!(from_manifest(&manifest, "stop_command").unwrap(), None);
+    }
+}
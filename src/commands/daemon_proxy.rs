@@ -0,0 +1,538 @@
+//! `fgp daemon-proxy`: a background process that keeps daemon connections
+//! open between `fgp call` invocations.
+//!
+//! When the proxy's control socket (`~/.fgp/proxy.sock`) answers, `fgp call`
+//! sends its request there instead of connecting to the daemon itself. The
+//! proxy forwards it over a pooled connection and relays the response line
+//! back. With no proxy running, calls connect directly as before.
+//!
+//! The control protocol is one JSON object per line:
+//!
+//! - `{"socket": "<daemon socket>", "request": {...}}` forwards a request
+//! - `{"control": "status"}` reports pool statistics
+//! - `{"control": "stop"}` shuts the proxy down
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::client::{self, Client, Connection, Request, Response};
+use super::fgp_home;
+
+/// Idle connections kept open per daemon; extras are closed when released.
+const MAX_IDLE_PER_DAEMON: usize = 4;
+
+/// How long `fgp daemon-proxy` waits for a detached proxy to answer.
+const START_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Control socket `fgp call` looks for.
+pub fn control_socket_path() -> PathBuf {
+    fgp_home().join("proxy.sock")
+}
+
+fn pid_path() -> PathBuf {
+    fgp_home().join("proxy.pid")
+}
+
+fn log_path() -> PathBuf {
+    fgp_home().join("proxy.log")
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Start the proxy, detached unless `foreground` is set.
+pub fn start(foreground: bool) -> Result<()> {
+    let control = control_socket_path();
+
+    if let Ok(status) = exchange(&control, &serde_json::json!({"control": "status"})) {
+        println!(
+            "{} daemon-proxy is already running (PID: {})",
+            "!".yellow().bold(),
+            status["pid"]
+        );
+        return Ok(());
+    }
+
+    fs::create_dir_all(fgp_home()).context("Failed to create ~/.fgp")?;
+
+    if foreground {
+        println!(
+            "{} daemon-proxy listening on {} (Ctrl+C to stop)",
+            "→".blue().bold(),
+            control.display()
+        );
+        return serve(&control);
+    }
+
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path())
+        .with_context(|| format!("Failed to open {}", log_path().display()))?;
+
+    // A separate process group keeps Ctrl-C in this shell from reaching it
+    let child = Command::new(std::env::current_exe().context("Failed to get executable path")?)
+        .args(["daemon-proxy", "start", "--foreground"])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .process_group(0)
+        .spawn()
+        .context("Failed to start daemon-proxy")?;
+
+    let deadline = Instant::now() + START_TIMEOUT;
+    while Instant::now() < deadline {
+        if exchange(&control, &serde_json::json!({"control": "status"})).is_ok() {
+            println!(
+                "{} daemon-proxy started (PID: {})",
+                "✓".green().bold(),
+                child.id()
+            );
+            println!("  Socket: {}", control.display().to_string().dimmed());
+            println!(
+                "  {} now reuses daemon connections; stop with {}",
+                "fgp call".cyan(),
+                "fgp daemon-proxy stop".cyan()
+            );
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    bail!(
+        "daemon-proxy did not start within {}s (see {})",
+        START_TIMEOUT.as_secs(),
+        log_path().display()
+    )
+}
+
+/// Ask a running proxy to shut down.
+pub fn stop() -> Result<()> {
+    let control = control_socket_path();
+
+    match exchange(&control, &serde_json::json!({"control": "stop"})) {
+        Ok(_) => println!("{} daemon-proxy stopped.", "✓".green().bold()),
+        Err(_) => {
+            println!("{} daemon-proxy is not running.", "!".yellow().bold());
+            // Left behind by a proxy that was killed
+            let _ = fs::remove_file(&control);
+            let _ = fs::remove_file(pid_path());
+        }
+    }
+
+    Ok(())
+}
+
+/// Show whether the proxy is running and what it holds open.
+pub fn status() -> Result<()> {
+    let status = match exchange(
+        &control_socket_path(),
+        &serde_json::json!({"control": "status"}),
+    ) {
+        Ok(status) => status,
+        Err(_) => {
+            println!("{} daemon-proxy is not running", "○".dimmed());
+            println!(
+                "  Start it with {} to reuse connections across calls.",
+                "fgp daemon-proxy".cyan()
+            );
+            return Ok(());
+        }
+    };
+
+    println!(
+        "{} daemon-proxy running (PID: {}, up {}s)",
+        "✓".green().bold(),
+        status["pid"],
+        status["uptime_secs"]
+    );
+    println!("  Requests forwarded: {}", status["requests"]);
+
+    let idle = status["idle"].as_object().cloned().unwrap_or_default();
+    if idle.is_empty() {
+        println!("  {}", "No idle connections".dimmed());
+    } else {
+        for (socket, count) in idle {
+            println!("  {} idle connection(s) to {}", count, socket.dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// A connection to a running proxy.
+pub struct ProxyClient(UnixStream);
+
+impl ProxyClient {
+    /// Connect to the proxy, or `None` if none is running (callers then
+    /// connect to the daemon directly).
+    pub fn connect() -> Option<Self> {
+        UnixStream::connect(control_socket_path()).ok().map(Self)
+    }
+
+    /// Forward one request to the daemon listening on `daemon_socket`.
+    pub fn call(self, daemon_socket: &Path, request: &Value) -> Result<Value> {
+        send(
+            self.0,
+            &serde_json::json!({"socket": daemon_socket, "request": request}),
+        )
+    }
+}
+
+/// Connect to a control socket and exchange one message.
+fn exchange(control: &Path, message: &Value) -> Result<Value> {
+    let stream = UnixStream::connect(control)
+        .with_context(|| format!("Failed to connect to {}", control.display()))?;
+    send(stream, message)
+}
+
+fn send(mut stream: UnixStream, message: &Value) -> Result<Value> {
+    writeln!(stream, "{}", message)?;
+    stream.flush()?;
+
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .context("Failed to read from daemon-proxy")?;
+    if line.trim().is_empty() {
+        bail!("daemon-proxy closed the connection without responding");
+    }
+
+    serde_json::from_str(&line).context("Invalid response from daemon-proxy")
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Message {
+    Forward { socket: PathBuf, request: Request },
+    Control { control: String },
+}
+
+/// Shared state of a running proxy.
+struct Proxy {
+    pool: Mutex<HashMap<PathBuf, Vec<Connection>>>,
+    requests: AtomicU64,
+    started: Instant,
+}
+
+impl Proxy {
+    fn new() -> Self {
+        Self {
+            pool: Mutex::new(HashMap::new()),
+            requests: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// Forward a request to a daemon and return its response.
+    fn forward(&self, socket: &Path, request: &Request) -> Result<Response> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        let pooled = self
+            .pool
+            .lock()
+            .unwrap()
+            .get_mut(socket)
+            .and_then(|idle| idle.pop());
+
+        // Daemons may close idle connections. Writing to one fails before
+        // the daemon sees anything, so only then is a fresh connection tried
+        if let Some(mut conn) = pooled {
+            match conn.send(request) {
+                Ok(response) => {
+                    self.release(socket, conn);
+                    return Ok(response);
+                }
+                Err(client::Error::NotSent(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let mut conn = Client::at(socket).open_connection()?;
+        let response = conn.send(request)?;
+        self.release(socket, conn);
+        Ok(response)
+    }
+
+    fn release(&self, socket: &Path, conn: Connection) {
+        let mut pool = self.pool.lock().unwrap();
+        let idle = pool.entry(socket.to_path_buf()).or_default();
+        if idle.len() < MAX_IDLE_PER_DAEMON {
+            idle.push(conn);
+        }
+    }
+
+    fn status(&self) -> Value {
+        let idle: serde_json::Map<String, Value> = self
+            .pool
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, conns)| !conns.is_empty())
+            .map(|(socket, conns)| (socket.display().to_string(), conns.len().into()))
+            .collect();
+
+        serde_json::json!({
+            "ok": true,
+            "pid": std::process::id(),
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "requests": self.requests.load(Ordering::Relaxed),
+            "idle": idle,
+        })
+    }
+
+    /// Serve one client connection until it closes.
+    ///
+    /// Returns `true` if the client asked the proxy to stop.
+    fn handle(&self, stream: UnixStream) -> Result<bool> {
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (reply, stop) = match serde_json::from_str::<Message>(&line) {
+                Ok(Message::Forward { socket, request }) => {
+                    let reply = self
+                        .forward(&socket, &request)
+                        .and_then(|response| Ok(serde_json::to_string(&response)?))
+                        .unwrap_or_else(|e| proxy_error(&format!("{:#}", e)));
+                    (reply, false)
+                }
+                Ok(Message::Control { control }) => match control.as_str() {
+                    "status" => (self.status().to_string(), false),
+                    "stop" => (serde_json::json!({"ok": true}).to_string(), true),
+                    other => (proxy_error(&format!("Unknown control '{}'", other)), false),
+                },
+                Err(e) => (proxy_error(&format!("Invalid message: {}", e)), false),
+            };
+
+            writeln!(writer, "{}", reply)?;
+            writer.flush()?;
+            if stop {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+fn proxy_error(message: &str) -> String {
+    serde_json::json!({
+        "ok": false,
+        "error": {"code": "PROXY_ERROR", "message": message},
+    })
+    .to_string()
+}
+
+/// Listen on the control socket until stopped.
+fn serve(control: &Path) -> Result<()> {
+    // Only reached when nothing answered on the socket, so it is stale
+    if control.exists() {
+        fs::remove_file(control)?;
+    }
+
+    let listener = UnixListener::bind(control)
+        .with_context(|| format!("Failed to bind {}", control.display()))?;
+    fs::write(pid_path(), std::process::id().to_string())?;
+
+    let cleanup = {
+        let control = control.to_path_buf();
+        move || {
+            let _ = fs::remove_file(&control);
+            let _ = fs::remove_file(pid_path());
+        }
+    };
+
+    {
+        let cleanup = cleanup.clone();
+        ctrlc::set_handler(move || {
+            cleanup();
+            std::process::exit(0);
+        })
+        .context("Failed to install Ctrl-C handler")?;
+    }
+
+    let proxy = Arc::new(Proxy::new());
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("daemon-proxy: accept failed: {}", e);
+                continue;
+            }
+        };
+
+        let proxy = proxy.clone();
+        let cleanup = cleanup.clone();
+        std::thread::spawn(move || match proxy.handle(stream) {
+            Ok(true) => {
+                cleanup();
+                std::process::exit(0);
+            }
+            Ok(false) => {}
+            Err(e) => eprintln!("daemon-proxy: {:#}", e),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A daemon that echoes the method, counting connections. With
+    /// `keep_alive` unset it closes each connection after one response;
+    /// `*.drop` methods close the connection without responding.
+    fn fake_daemon(keep_alive: bool) -> (tempfile::TempDir, PathBuf, Arc<AtomicUsize>) {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let counter = connections.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone().unwrap());
+                    for line in reader.lines() {
+                        let request: Value = serde_json::from_str(&line.unwrap()).unwrap();
+                        if request["method"].as_str().unwrap_or("").ends_with(".drop") {
+                            break;
+                        }
+                        let response = serde_json::json!({
+                            "ok": true,
+                            "result": {"method": request["method"]},
+                        });
+                        writeln!(stream, "{}", response).unwrap();
+                        if !keep_alive {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        (dir, socket, connections)
+    }
+
+    fn request(method: &str) -> Request {
+        Request::new(method, serde_json::json!({}))
+    }
+
+    #[test]
+    fn test_connections_are_reused() {
+        let (_dir, socket, connections) = fake_daemon(true);
+        let proxy = Proxy::new();
+
+        for method in ["a.one", "a.two", "a.three"] {
+            let response = proxy.forward(&socket, &request(method)).unwrap();
+            assert_eq!(response.result.unwrap()["method"], method);
+        }
+
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        assert_eq!(proxy.status()["requests"], 3);
+    }
+
+    #[test]
+    fn test_closed_connection_is_replaced() {
+        let (_dir, socket, connections) = fake_daemon(false);
+        let proxy = Proxy::new();
+
+        proxy.forward(&socket, &request("a.one")).unwrap();
+        // Let the daemon close its end of the pooled connection
+        std::thread::sleep(Duration::from_millis(100));
+        let response = proxy.forward(&socket, &request("a.two")).unwrap();
+
+        assert_eq!(response.result.unwrap()["method"], "a.two");
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_unanswered_request_is_not_resent() {
+        let (_dir, socket, connections) = fake_daemon(true);
+        let proxy = Proxy::new();
+
+        proxy.forward(&socket, &request("a.one")).unwrap();
+        let err = proxy.forward(&socket, &request("a.drop")).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<client::Error>(),
+            Some(client::Error::Closed)
+        ));
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_control_protocol() {
+        let (dir, socket, _) = fake_daemon(true);
+        let control = dir.path().join("proxy.sock");
+        let listener = UnixListener::bind(&control).unwrap();
+
+        let proxy = Arc::new(Proxy::new());
+        let server = proxy.clone();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            server.handle(stream).unwrap()
+        });
+
+        let stream = UnixStream::connect(&control).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut roundtrip = |message: Value| -> Value {
+            writeln!(&stream, "{}", message).unwrap();
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        };
+
+        let forwarded = roundtrip(serde_json::json!({
+            "socket": socket,
+            "request": {"id": "t", "v": 1, "method": "a.one", "params": {}},
+        }));
+        assert_eq!(forwarded["result"]["method"], "a.one");
+
+        let status = roundtrip(serde_json::json!({"control": "status"}));
+        assert_eq!(status["idle"][socket.display().to_string()], 1);
+
+        let missing = roundtrip(serde_json::json!({
+            "socket": dir.path().join("nope.sock"),
+            "request": request("a.one"),
+        }));
+        assert_eq!(missing["error"]["code"], "PROXY_ERROR");
+
+        assert_eq!(
+            roundtrip(serde_json::json!({"control": "stop"}))["ok"],
+            true
+        );
+        assert!(handle.join().unwrap());
+    }
+}
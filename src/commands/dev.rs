@@ -0,0 +1,342 @@
+//! `fgp dev`: the edit-and-try loop for authoring a skill.
+//!
+//! `fgp dev <skill> --targets claude-code,cursor` exports the skill straight
+//! into each agent's directory (`~/.claude/skills/<name>/SKILL.md`,
+//! `~/.cursor/rules/<name>.mdc`), then watches the skill's files and
+//! re-exports whenever one changes, so the agent always sees the current
+//! draft. Without `--targets`, every installable target whose agent is
+//! detected is used.
+//!
+//! Ctrl-C stops watching. With `--clean`, exports `fgp dev` created are then
+//! removed again; files that were already there before it started (say, from
+//! an earlier install of the skill) are left with the last draft in them.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use super::skill_export::{self, ExportChange, INSTALLABLE_TARGETS};
+use super::skill_manifest;
+
+/// How often the skill's files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Directories under a skill that never hold anything it exports.
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Modification time and size of every file under a skill directory.
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+pub fn run(skill: &str, targets: &[String], clean: bool) -> Result<()> {
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    let skill_path = Path::new(skill);
+    let loaded = skill_manifest::load(skill_path)?;
+    let targets = resolve_targets(targets, &home)?;
+
+    println!(
+        "{} Developing {} for {} (Ctrl-C to stop)",
+        "→".blue().bold(),
+        loaded.manifest.name.bold(),
+        targets.join(", ").cyan()
+    );
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = stop.clone();
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+            .context("Failed to install Ctrl-C handler")?;
+    }
+
+    let mut session = Session::new(skill_path, &home, targets);
+    let mut last = snapshot(&loaded.root);
+    let status = session.install();
+    print_status(&status);
+
+    while !stop.load(Ordering::SeqCst) {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = snapshot(&loaded.root);
+        if current != last {
+            last = current;
+            let status = session.install();
+            print_status(&status);
+        }
+    }
+    println!();
+
+    if clean {
+        let removed = session.clean()?;
+        println!(
+            "{} Removed {} dev export(s)",
+            "✓".green().bold(),
+            removed.len()
+        );
+    } else {
+        println!(
+            "{} Stopped; the exports stay installed (use --clean to remove them)",
+            "✓".green().bold()
+        );
+    }
+    Ok(())
+}
+
+/// The targets to install: the ones asked for, or those whose agent is
+/// detected.
+fn resolve_targets(targets: &[String], home: &Path) -> Result<Vec<&'static str>> {
+    if targets.is_empty() {
+        let detected: Vec<&'static str> = INSTALLABLE_TARGETS
+            .iter()
+            .copied()
+            .filter(|target| home.join(agent_dir(target)).exists())
+            .collect();
+        if detected.is_empty() {
+            bail!(
+                "No supported agent detected; choose one with --targets ({})",
+                INSTALLABLE_TARGETS.join(", ")
+            );
+        }
+        return Ok(detected);
+    }
+
+    let mut resolved = Vec::new();
+    for target in targets {
+        let name = match target.as_str() {
+            "claude" => "claude-code",
+            other => other,
+        };
+        let Some(&target) = INSTALLABLE_TARGETS.iter().find(|t| **t == name) else {
+            bail!(
+                "fgp dev can't install {} exports\nValid targets: {}",
+                target,
+                INSTALLABLE_TARGETS.join(", ")
+            );
+        };
+        if !resolved.contains(&target) {
+            resolved.push(target);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Where an agent's configuration lives, relative to the home directory.
+fn agent_dir(target: &str) -> &'static str {
+    match target {
+        "cursor" => ".cursor",
+        _ => ".claude",
+    }
+}
+
+/// Exports installed by one `fgp dev` run.
+struct Session<'a> {
+    skill: &'a Path,
+    root: &'a Path,
+    targets: Vec<&'static str>,
+    /// Files this run created, rather than overwrote
+    created: Vec<PathBuf>,
+}
+
+/// Result of installing every target once: what changed, by target.
+struct Status {
+    name: Option<String>,
+    targets: Vec<(&'static str, Result<Vec<ExportChange>>)>,
+}
+
+impl<'a> Session<'a> {
+    fn new(skill: &'a Path, root: &'a Path, targets: Vec<&'static str>) -> Self {
+        Self {
+            skill,
+            root,
+            targets,
+            created: Vec::new(),
+        }
+    }
+
+    /// Export the skill for each target into `root`.
+    fn install(&mut self) -> Status {
+        let mut status = Status {
+            name: skill_manifest::load(self.skill)
+                .ok()
+                .map(|loaded| loaded.manifest.name),
+            targets: Vec::new(),
+        };
+        for &target in &self.targets {
+            let result = skill_export::export_quietly(target, self.skill, self.root);
+            if let Ok(ref files) = result {
+                self.created.extend(
+                    files
+                        .iter()
+                        .filter(|(_, change)| *change == ExportChange::Created)
+                        .map(|(path, _)| path.clone()),
+                );
+            }
+            let changes = result.map(|files| files.into_iter().map(|(_, c)| c).collect());
+            status.targets.push((target, changes));
+        }
+        status
+    }
+
+    /// Remove the files this run created. Returns the ones removed.
+    fn clean(&self) -> Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        for path in &self.created {
+            if !path.exists() {
+                continue;
+            }
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed.push(path.clone());
+            // A Claude Code skill's own directory, skills/<name>/, goes too once empty
+            if let Some(dir) = path.parent() {
+                if dir.parent().is_some_and(|p| p.ends_with("skills")) {
+                    let _ = fs::remove_dir(dir);
+                }
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl Status {
+    /// One line: the time, then what happened for each target.
+    fn line(&self) -> String {
+        let time = chrono::Local::now().format("%H:%M:%S");
+        let name = self.name.as_deref().unwrap_or("skill");
+        let failed = self.targets.iter().any(|(_, result)| result.is_err());
+        let targets: Vec<String> = self
+            .targets
+            .iter()
+            .map(|(target, result)| match result {
+                Ok(changes) if changes.iter().all(|c| *c == ExportChange::Unchanged) => {
+                    format!("{} {}", target, "unchanged".dimmed())
+                }
+                Ok(_) => format!("{} {}", target, "updated".green()),
+                Err(e) => format!("{} {}", target, format!("failed: {:#}", e).red()),
+            })
+            .collect();
+        format!(
+            "{} {} {}: {}",
+            format!("[{}]", time).dimmed(),
+            if failed { "✗".red() } else { "✓".green() },
+            name.bold(),
+            targets.join(", ")
+        )
+    }
+}
+
+/// Show `status`, replacing the previous line on a terminal.
+fn print_status(status: &Status) {
+    // Errors can span lines; the status stays on one
+    let line = status.line().replace('\n', " ");
+    if std::io::stdout().is_terminal() {
+        print!("\r\x1b[2K{}", line);
+        let _ = std::io::stdout().flush();
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Modification time and size of every file under `dir`.
+fn snapshot(dir: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                let name = entry.file_name();
+                if !SKIP_DIRS.iter().any(|skip| name == *skip) {
+                    pending.push(path);
+                }
+            } else {
+                files.insert(path, (metadata.modified().ok(), metadata.len()));
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, description: &str) {
+        fs::write(
+            dir.join("skill.yaml"),
+            format!(
+                "name: dev-skill\nversion: 0.1.0\ndescription: {}\nauthor: Ada\n",
+                description
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_session_install_and_clean() {
+        let skill = tempfile::tempdir().unwrap();
+        let home = tempfile::tempdir().unwrap();
+        write_skill(skill.path(), "First draft");
+
+        // An existing export is overwritten but not cleaned up
+        let rules = home.path().join(".cursor/rules");
+        fs::create_dir_all(&rules).unwrap();
+        fs::write(rules.join("dev-skill.mdc"), "installed").unwrap();
+
+        let mut session = Session::new(skill.path(), home.path(), vec!["claude-code", "cursor"]);
+        let before = snapshot(skill.path());
+        let status = session.install();
+        assert!(status.targets.iter().all(|(_, result)| result.is_ok()));
+        let skill_md = home.path().join(".claude/skills/dev-skill/SKILL.md");
+        assert!(fs::read_to_string(&skill_md)
+            .unwrap()
+            .contains("First draft"));
+        assert_eq!(session.created, vec![skill_md.clone()]);
+
+        // Nothing changed: nothing rewritten
+        let status = session.install();
+        assert!(status.line().contains("unchanged"));
+
+        write_skill(skill.path(), "Second draft, now longer");
+        assert_ne!(snapshot(skill.path()), before);
+        session.install();
+        assert!(fs::read_to_string(&skill_md)
+            .unwrap()
+            .contains("Second draft"));
+        assert!(fs::read_to_string(rules.join("dev-skill.mdc"))
+            .unwrap()
+            .contains("Second draft"));
+
+        assert_eq!(session.clean().unwrap(), vec![skill_md.clone()]);
+        assert!(!skill_md.parent().unwrap().exists());
+        assert!(home.path().join(".claude/skills").exists());
+        assert!(rules.join("dev-skill.mdc").exists());
+    }
+
+    #[test]
+    fn test_resolve_targets() {
+        let home = tempfile::tempdir().unwrap();
+        let targets = |names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            resolve_targets(&names, home.path())
+        };
+        assert_eq!(
+            targets(&["claude", "cursor", "claude-code"]).unwrap(),
+            vec!["claude-code", "cursor"]
+        );
+        assert!(targets(&["codex"]).is_err());
+        // Nothing asked for and no agent detected
+        assert!(targets(&[]).is_err());
+        fs::create_dir(home.path().join(".cursor")).unwrap();
+        assert_eq!(targets(&[]).unwrap(), vec!["cursor"]);
+    }
+}
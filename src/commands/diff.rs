@@ -0,0 +1,346 @@
+//! Terminal rendering for line diffs.
+//!
+//! Every command that shows a diff (`fgp skill diff` today) renders it here,
+//! so they all look the same. Callers compute the diff themselves with
+//! [`similar`] (choosing the algorithm, timeout and so on) and pass in the
+//! [`TextDiff`]; this module only decides how it looks:
+//!
+//! - a unified diff with `-` lines in red and `+` lines in green, or old and
+//!   new side by side when the terminal is wide enough for two columns;
+//! - a header naming the files, and hunk headers with their line ranges;
+//! - at most [`MAX_LINES`] lines per diff, then an "N more lines" trailer,
+//!   unless the caller asks for everything (`--full-diff`).
+//!
+//! Colour follows the `colored` crate's settings (`NO_COLOR`,
+//! `CLICOLOR_FORCE`, and whether stdout is a terminal), but the escape codes
+//! are written here so a rendering doesn't depend on global state.
+
+use similar::udiff::UnifiedHunkHeader;
+use similar::{ChangeTag, DiffOp, TextDiff};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Lines shown per diff before the rest is summarized.
+pub const MAX_LINES: usize = 200;
+
+/// Unchanged lines shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Narrowest terminal that gets side-by-side output.
+const MIN_SIDE_BY_SIDE_WIDTH: usize = 100;
+
+/// How to render a diff.
+#[derive(Debug, Clone)]
+pub struct DiffStyle {
+    /// Write ANSI colours
+    pub color: bool,
+    /// Show old and new side by side, if `width` leaves room for both
+    pub side_by_side: bool,
+    /// Terminal width in columns
+    pub width: usize,
+    /// Unchanged lines around each change
+    pub context: usize,
+    /// Most lines shown per diff; `None` shows everything
+    pub max_lines: Option<usize>,
+}
+
+impl DiffStyle {
+    /// For printing to stdout: coloured as `colored` decides, as wide as the
+    /// terminal, and cut at [`MAX_LINES`] unless `full`.
+    pub fn terminal(side_by_side: bool, full: bool) -> Self {
+        Self {
+            color: colored::control::SHOULD_COLORIZE.should_colorize(),
+            side_by_side,
+            width: crossterm::terminal::size()
+                .map(|(columns, _)| columns as usize)
+                .unwrap_or(80),
+            context: CONTEXT_LINES,
+            max_lines: (!full).then_some(MAX_LINES),
+        }
+    }
+
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.color && !text.is_empty() {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn bold(&self, text: &str) -> String {
+        self.paint(text, "1")
+    }
+
+    fn red(&self, text: &str) -> String {
+        self.paint(text, "31")
+    }
+
+    fn green(&self, text: &str) -> String {
+        self.paint(text, "32")
+    }
+
+    fn cyan(&self, text: &str) -> String {
+        self.paint(text, "36")
+    }
+
+    fn dimmed(&self, text: &str) -> String {
+        self.paint(text, "2")
+    }
+}
+
+/// Render a line diff of `old_path` (as `a/...`, say) and `new_path`.
+/// Returns an empty string when nothing changed.
+pub fn render(
+    diff: &TextDiff<'_, '_, '_, str>,
+    old_path: &str,
+    new_path: &str,
+    style: &DiffStyle,
+) -> String {
+    let groups = diff.grouped_ops(style.context);
+    if groups.is_empty() {
+        return String::new();
+    }
+
+    let side_by_side = style.side_by_side && style.width >= MIN_SIDE_BY_SIDE_WIDTH;
+    let (mut out, lines) = if side_by_side {
+        (
+            format!("{}\n", style.bold(&format!("{} → {}", old_path, new_path))),
+            side_by_side_lines(diff, &groups, new_path, style),
+        )
+    } else {
+        (
+            format!(
+                "{}\n{}\n",
+                style.bold(&format!("--- {}", old_path)),
+                style.bold(&format!("+++ {}", new_path))
+            ),
+            unified_lines(diff, &groups, style),
+        )
+    };
+
+    let shown = style.max_lines.unwrap_or(usize::MAX).min(lines.len());
+    for line in &lines[..shown] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if shown < lines.len() {
+        let hidden = lines.len() - shown;
+        out.push_str(&style.dimmed(&format!(
+            "… {} more line{} (use --full-diff to show all)",
+            hidden,
+            if hidden == 1 { "" } else { "s" }
+        )));
+        out.push('\n');
+    }
+    out
+}
+
+fn unified_lines(
+    diff: &TextDiff<'_, '_, '_, str>,
+    groups: &[Vec<DiffOp>],
+    style: &DiffStyle,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for group in groups {
+        lines.push(style.cyan(&UnifiedHunkHeader::new(group).to_string()));
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let text = change.value();
+                let text = text.strip_suffix('\n').unwrap_or(text);
+                lines.push(match change.tag() {
+                    ChangeTag::Equal => format!(" {}", text),
+                    ChangeTag::Delete => style.red(&format!("-{}", text)),
+                    ChangeTag::Insert => style.green(&format!("+{}", text)),
+                });
+                if change.missing_newline() {
+                    lines.push(style.dimmed("\\ No newline at end of file"));
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// One row of a side-by-side diff: a line number and text for each side.
+type Row<'a> = (Option<(usize, &'a str)>, Option<(usize, &'a str)>);
+
+fn side_by_side_lines(
+    diff: &TextDiff<'_, '_, '_, str>,
+    groups: &[Vec<DiffOp>],
+    new_path: &str,
+    style: &DiffStyle,
+) -> Vec<String> {
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let number_width = old_lines.len().max(new_lines.len()).to_string().len();
+    // "<number> <marker><text>" on each side of " │ "
+    let column = (style.width - 3) / 2 - number_width - 2;
+
+    let mut lines = Vec::new();
+    for group in groups {
+        lines.push(style.cyan(&format!("{} {}", UnifiedHunkHeader::new(group), new_path)));
+
+        for op in group {
+            let old: Vec<(usize, &str)> = op
+                .old_range()
+                .map(|index| (index + 1, old_lines[index]))
+                .collect();
+            let new: Vec<(usize, &str)> = op
+                .new_range()
+                .map(|index| (index + 1, new_lines[index]))
+                .collect();
+            // Equal lines pair up one to one; a replacement pairs its
+            // deleted and inserted lines in order, padding the shorter side
+            let rows: Vec<Row> = (0..old.len().max(new.len()))
+                .map(|i| (old.get(i).copied(), new.get(i).copied()))
+                .collect();
+            let changed = !matches!(op, DiffOp::Equal { .. });
+
+            for (old, new) in rows {
+                let left = side(old, '-', changed, number_width, column, style);
+                let right = side(new, '+', changed, number_width, column, style);
+                lines.push(format!("{} │ {}", left, right).trim_end().to_string());
+            }
+        }
+    }
+    lines
+}
+
+/// One side of a side-by-side row, padded to the column width.
+fn side(
+    line: Option<(usize, &str)>,
+    marker: char,
+    changed: bool,
+    number_width: usize,
+    column: usize,
+    style: &DiffStyle,
+) -> String {
+    let Some((number, text)) = line else {
+        return " ".repeat(number_width + 2 + column);
+    };
+    let marker = if changed { marker } else { ' ' };
+    let text = fit(text.trim_end_matches(['\n', '\r']), column);
+    let padding = " ".repeat(column - text.width());
+    let body = format!("{}{}", marker, text);
+    let body = match (changed, marker) {
+        (false, _) => body,
+        (true, '-') => style.red(&body),
+        (true, _) => style.green(&body),
+    };
+    format!(
+        "{:>width$} {}{}",
+        number,
+        body,
+        padding,
+        width = number_width
+    )
+}
+
+/// `text` cut to `width` columns, ending in `…` if anything was cut. Tabs
+/// count as four spaces.
+fn fit(text: &str, width: usize) -> String {
+    let text = text.replace('\t', "    ");
+    if text.width() <= width {
+        return text;
+    }
+
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // Leave a column for the ellipsis
+        if used + char_width >= width {
+            break;
+        }
+        fitted.push(c);
+        used += char_width;
+    }
+    fitted.push('…');
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const OLD: &str =
+        "# Gmail\n\nSend mail.\nKeep replies short.\n\n## Methods\n\n- gmail.send\n- gmail.list\n";
+    const NEW: &str = "# Gmail\n\nSend and read mail.\nKeep replies short.\n\n## Methods\n\n- gmail.send\n- gmail.list\n- gmail.thread\n- gmail.labels";
+
+    fn style(color: bool, side_by_side: bool) -> DiffStyle {
+        DiffStyle {
+            color,
+            side_by_side,
+            width: 120,
+            context: 1,
+            max_lines: None,
+        }
+    }
+
+    /// Renderings of a small diff, compared with the golden files in
+    /// tests/fixtures/diff. After an intended change, regenerate them with
+    /// `UPDATE_GOLDEN=1 cargo test test_render_snapshots` and review the diff.
+    #[test]
+    fn test_render_snapshots() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/diff");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let diff = TextDiff::from_lines(OLD, NEW);
+
+        for (name, style) in [
+            ("unified.txt", style(false, false)),
+            ("unified-color.txt", style(true, false)),
+            ("side-by-side.txt", style(false, true)),
+            ("side-by-side-color.txt", style(true, true)),
+        ] {
+            let actual = render(&diff, "a/core.md", "b/core.md", &style);
+            let golden = root.join(name);
+            if update {
+                fs::write(&golden, &actual).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&golden).unwrap_or_default();
+            assert_eq!(
+                actual,
+                expected,
+                "{} differs from its golden output (rerun with UPDATE_GOLDEN=1 if the change is intended)",
+                golden.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_limits() {
+        let diff = TextDiff::from_lines(OLD, NEW);
+        assert_eq!(
+            render(
+                &TextDiff::from_lines(OLD, OLD),
+                "a",
+                "b",
+                &style(true, false)
+            ),
+            ""
+        );
+
+        let limited = DiffStyle {
+            max_lines: Some(3),
+            ..style(false, false)
+        };
+        let out = render(&diff, "a/core.md", "b/core.md", &limited);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2 + 3 + 1);
+        assert_eq!(lines[5], "… 7 more lines (use --full-diff to show all)");
+
+        // Too narrow for two columns
+        let narrow = DiffStyle {
+            width: 80,
+            ..style(false, true)
+        };
+        assert!(render(&diff, "a/core.md", "b/core.md", &narrow).starts_with("--- a/core.md\n"));
+
+        assert_eq!(fit("short", 10), "short");
+        assert_eq!(fit("a longer line", 8), "a longe…");
+        assert_eq!(fit("日本語のテキスト", 7), "日本語…");
+        assert_eq!(fit("\tx", 8), "    x");
+    }
+}
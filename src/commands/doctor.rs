@@ -0,0 +1,417 @@
+//! Diagnose common problems with the local FGP setup, and optionally fix them.
+//!
+//! `fgp doctor` only reports. `fgp doctor --fix` applies the safe fixes
+//! (creating directories, removing stale sockets and PID files, relinking
+//! skills whose source moved, writing missing MCP manifests) and asks before
+//! anything that overwrites existing files. Problems that need a decision,
+//! such as migrating legacy marketplaces to taps or stopping orphaned daemon
+//! processes, are reported with the command to run.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use super::client::Client;
+use super::orphans;
+use super::status::read_pid_file;
+use super::{confirm, fgp_home, fgp_services_dir, service_pid_path, service_socket_path};
+
+/// Subdirectories of `~/.fgp` that commands expect to exist.
+const FGP_SUBDIRS: &[&str] = &["services", "skills", "taps", "workflows"];
+
+/// A problem found by a check.
+struct Issue {
+    message: String,
+    fix: Option<Fix>,
+}
+
+/// A remediation for an issue.
+enum Fix {
+    CreateDir(PathBuf),
+    RemoveFile(PathBuf),
+    Relink {
+        link: PathBuf,
+        target: PathBuf,
+    },
+    WriteManifest {
+        path: PathBuf,
+        content: String,
+        overwrite: bool,
+    },
+}
+
+impl Fix {
+    /// Fixes that replace existing content need confirmation.
+    fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Fix::WriteManifest {
+                overwrite: true,
+                ..
+            }
+        )
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Fix::CreateDir(path) => format!("create {}", path.display()),
+            Fix::RemoveFile(path) => format!("remove {}", path.display()),
+            Fix::Relink { link, target } => {
+                format!("relink {} → {}", link.display(), target.display())
+            }
+            Fix::WriteManifest {
+                path, overwrite, ..
+            } => {
+                if *overwrite {
+                    format!("overwrite {}", path.display())
+                } else {
+                    format!("write {}", path.display())
+                }
+            }
+        }
+    }
+
+    fn apply(&self) -> Result<()> {
+        match self {
+            Fix::CreateDir(path) => fs::create_dir_all(path)?,
+            Fix::RemoveFile(path) => fs::remove_file(path)?,
+            Fix::Relink { link, target } => {
+                fs::remove_file(link)?;
+                std::os::unix::fs::symlink(target, link)?;
+            }
+            Fix::WriteManifest { path, content, .. } => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run all checks, applying fixes when `fix` is set.
+///
+/// Destructive fixes are confirmed interactively unless `yes` is set.
+pub fn run(fix: bool, yes: bool) -> Result<()> {
+    println!("{} Checking FGP installation...", "→".blue().bold());
+    println!();
+
+    let sections = [
+        ("Directories", check_directories()),
+        ("Services", check_services()?),
+        ("Orphaned daemons", check_orphans()),
+        ("Skill sources", check_skill_sources()?),
+        ("MCP registrations", check_registrations()?),
+        ("Legacy marketplaces", check_legacy_marketplaces()?),
+    ];
+
+    let mut found = 0;
+    let mut fixed = 0;
+    let mut skipped = 0;
+    let mut manual = 0;
+
+    for (title, issues) in &sections {
+        if issues.is_empty() {
+            println!("{} {}", "✓".green().bold(), title);
+            continue;
+        }
+
+        println!("{} {}", "✗".red().bold(), title);
+        for issue in issues {
+            found += 1;
+            println!("    {}", issue.message);
+
+            let Some(ref remedy) = issue.fix else {
+                manual += 1;
+                continue;
+            };
+
+            if !fix {
+                println!("      {}", format!("fix: {}", remedy.describe()).dimmed());
+                continue;
+            }
+
+            if remedy.is_destructive() && !yes && !confirm_fix(&remedy.describe())? {
+                println!("      {} skipped", "○".dimmed());
+                skipped += 1;
+                continue;
+            }
+
+            match remedy.apply() {
+                Ok(()) => {
+                    println!("      {} {}", "✓".green(), remedy.describe());
+                    fixed += 1;
+                }
+                Err(e) => {
+                    println!("      {} {}: {}", "✗".red(), remedy.describe(), e);
+                    manual += 1;
+                }
+            }
+        }
+    }
+
+    println!();
+    if found == 0 {
+        println!("{} No problems found.", "✓".green().bold());
+    } else if fix {
+        println!(
+            "{} {} fixed, {} skipped, {} need attention",
+            if fixed == found {
+                "✓".green().bold()
+            } else {
+                "!".yellow().bold()
+            },
+            fixed,
+            skipped,
+            manual
+        );
+    } else {
+        println!(
+            "{} {} problem(s) found, {} fixable automatically",
+            "!".yellow().bold(),
+            found,
+            found - manual
+        );
+        if found > manual {
+            println!("  Run {} to repair them.", "fgp doctor --fix".cyan());
+        }
+    }
+
+    Ok(())
+}
+
+/// Ask before a fix that overwrites something; without a terminal to ask
+/// on, the answer is no.
+fn confirm_fix(action: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        println!(
+            "      {} needs confirmation; rerun with --yes to {}",
+            "!".yellow().bold(),
+            action
+        );
+        return Ok(false);
+    }
+    confirm(&format!("{}?", action))
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// Missing `~/.fgp` subdirectories.
+fn check_directories() -> Vec<Issue> {
+    let home = fgp_home();
+    FGP_SUBDIRS
+        .iter()
+        .map(|name| home.join(name))
+        .filter(|dir| !dir.is_dir())
+        .map(|dir| Issue {
+            message: format!("Missing directory {}", dir.display()),
+            fix: Some(Fix::CreateDir(dir)),
+        })
+        .collect()
+}
+
+/// Stale sockets and PID files, and services with unreadable manifests.
+fn check_services() -> Result<Vec<Issue>> {
+    let services_dir = fgp_services_dir();
+    if !services_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(&services_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .collect();
+    names.sort();
+
+    let mut issues = Vec::new();
+    for name in names {
+        let manifest_path = services_dir.join(&name).join("manifest.json");
+        match fs::read_to_string(&manifest_path) {
+            Ok(content) => {
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(&content) {
+                    issues.push(Issue {
+                        message: format!("{}: manifest.json does not parse ({})", name, e),
+                        fix: None,
+                    });
+                }
+            }
+            Err(_) => issues.push(Issue {
+                message: format!(
+                    "{}: no manifest.json (reinstall with 'fgp install <path>')",
+                    name
+                ),
+                fix: None,
+            }),
+        }
+
+        let pid_path = service_pid_path(&name);
+        let pid_alive = read_pid_file(&name).map(orphans::is_running);
+        if pid_alive == Some(false) {
+            issues.push(Issue {
+                message: format!("{}: PID file refers to a process that is not running", name),
+                fix: Some(Fix::RemoveFile(pid_path)),
+            });
+        }
+
+        // A socket with a live process behind it may just be busy; leave it
+        let socket_path = service_socket_path(&name);
+        if socket_path.exists() && pid_alive != Some(true) && !socket_responds(&socket_path) {
+            issues.push(Issue {
+                message: format!("{}: stale socket (daemon not responding)", name),
+                fix: Some(Fix::RemoveFile(socket_path)),
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Daemon processes that no socket or PID file accounts for.
+fn check_orphans() -> Vec<Issue> {
+    super::orphans::scan()
+        .into_iter()
+        .map(|orphan| Issue {
+            message: format!(
+                "{}: PID {}{} is running without a socket or PID file (stop it with 'fgp stop --orphan {}')",
+                orphan.service,
+                orphan.pid,
+                orphan
+                    .started
+                    .map(|t| format!(" (started {})", t))
+                    .unwrap_or_default(),
+                orphan.pid
+            ),
+            fix: None,
+        })
+        .collect()
+}
+
+/// Installed skills whose `source` symlink is dangling.
+fn check_skill_sources() -> Result<Vec<Issue>> {
+    Ok(super::skill::broken_sources()?
+        .into_iter()
+        .map(|broken| match broken.relink_to {
+            Some(target) => Issue {
+                message: format!("{}: source link is broken", broken.skill),
+                fix: Some(Fix::Relink {
+                    link: broken.link,
+                    target,
+                }),
+            },
+            None => Issue {
+                message: format!(
+                    "{}: source link is broken and the source was not found (reinstall with 'fgp skill install {}')",
+                    broken.skill, broken.skill
+                ),
+                fix: None,
+            },
+        })
+        .collect())
+}
+
+/// Installed skills whose MCP manifest is missing or out of date.
+fn check_registrations() -> Result<Vec<Issue>> {
+    Ok(super::skill::stale_registrations()?
+        .into_iter()
+        .map(|(skill, registration, overwrite)| Issue {
+            message: if overwrite {
+                format!(
+                    "{}: manifest for '{}' is out of date",
+                    skill, registration.daemon_name
+                )
+            } else {
+                format!(
+                    "{}: not registered (no manifest for '{}')",
+                    skill, registration.daemon_name
+                )
+            },
+            fix: Some(Fix::WriteManifest {
+                path: registration.manifest_path,
+                content: registration.content,
+                overwrite,
+            }),
+        })
+        .collect())
+}
+
+/// Configured legacy marketplaces, with the tap migration for each.
+fn check_legacy_marketplaces() -> Result<Vec<Issue>> {
+    Ok(super::skill_legacy::legacy_marketplaces()?
+        .into_iter()
+        .map(|marketplace| Issue {
+            message: if marketplace.tap_compatible {
+                format!(
+                    "{}: deprecated marketplace (migrate with 'fgp skill marketplace migrate {}')",
+                    marketplace.name, marketplace.name
+                )
+            } else {
+                format!(
+                    "{}: deprecated marketplace, but {} has no tap layout yet",
+                    marketplace.name, marketplace.repo
+                )
+            },
+            fix: None,
+        })
+        .collect())
+}
+
+fn socket_responds(socket_path: &Path) -> bool {
+    Client::at(socket_path).health().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixes_apply() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let created = dir.path().join("a").join("b");
+        Fix::CreateDir(created.clone()).apply().unwrap();
+        assert!(created.is_dir());
+
+        let target = dir.path().join("moved");
+        fs::create_dir(&target).unwrap();
+        let link = dir.path().join("source");
+        std::os::unix::fs::symlink(dir.path().join("gone"), &link).unwrap();
+        assert!(!link.exists());
+        Fix::Relink {
+            link: link.clone(),
+            target: target.clone(),
+        }
+        .apply()
+        .unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), target);
+
+        let manifest = dir.path().join("svc").join("manifest.json");
+        let write = Fix::WriteManifest {
+            path: manifest.clone(),
+            content: "{}".to_string(),
+            overwrite: false,
+        };
+        assert!(!write.is_destructive());
+        write.apply().unwrap();
+        assert_eq!(fs::read_to_string(&manifest).unwrap(), "{}");
+
+        Fix::RemoveFile(manifest.clone()).apply().unwrap();
+        assert!(!manifest.exists());
+    }
+
+    #[test]
+    fn test_overwrite_is_destructive() {
+        let fix = Fix::WriteManifest {
+            path: PathBuf::from("manifest.json"),
+            content: String::new(),
+            overwrite: true,
+        };
+        assert!(fix.is_destructive());
+        assert!(!Fix::RemoveFile(PathBuf::from("daemon.sock")).is_destructive());
+    }
+}
@@ -1,12 +1,98 @@
 //! Generate command - scaffolds new FGP daemons from templates.
 //!
-//! Uses the Python generator script from the generator/ directory.
+//! Uses the Python generator script from the generator/ directory. When an
+//! auth style or environments are chosen, it renders the built-in template
+//! (a Rust daemon with an API client, in `src/templates/generate/`) instead,
+//! and with `--template <dir>` it renders a local directory of template files.
+//!
+//! Template files are copied into `<output>/<service>/` with these
+//! placeholders substituted, in both file contents and file names:
+//!
+//! - `{{service}}` - the service name
+//! - `{{display_name}}` - `--display-name`, or the service name in Title Case
+//! - `{{api_url}}` - `--api-url` (required if the template uses it)
+//! - `{{env_token}}` - `--env-token`, or `<SERVICE>_API_TOKEN`
+//! - `{{author}}` - `--author`
+//! - `{{auth_style}}` - `bearer`, `header`, `query`, `basic` or `oauth2`
+//!   (`--auth-style`)
+//! - `{{auth_manifest}}` - the manifest's `auth` block, as JSON
+//! - `{{auth_client}}` - Rust: `authorize(request)`, which adds the
+//!   credentials from `{{env_token}}` to a `reqwest::blocking::RequestBuilder`
+//!   the way the API expects (so the crate needs `reqwest` with `blocking`).
+//!   With `oauth2` it refreshes access tokens using `<SERVICE>_CLIENT_ID`,
+//!   `<SERVICE>_CLIENT_SECRET` and the refresh token in `{{env_token}}`, and
+//!   caches them in `~/.fgp/services/<service>/tokens.json` (so the crate
+//!   needs `serde_json` too)
+//! - `{{env_config}}` - Rust: `base_url()`, picking the environment's base
+//!   URL (`--environments`) with `<SERVICE>_ENV`
+//! - `{{auth_readme}}` - README sections on setting up auth and environments
+//!
+//! A trailing `.tmpl` is dropped from file names. The template must contain a
+//! manifest (`manifest.json.tmpl` or `manifest.json`) at its top level.
+//!
+//! `fgp generate show <service>` prints what a preset sets up, as the
+//! generator script describes it with `--show-preset`.
+//!
+//! The generator script doesn't take `--auth-style` or `--environments`, so
+//! giving either one (or answering the questions `fgp generate new` asks from
+//! a terminal when neither is given) switches to the built-in template. With
+//! `--preset`, the preset's display name, API URL and token variable fill in
+//! what isn't given.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::path::PathBuf;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Placeholders substituted in `--template` files.
+const PLACEHOLDERS: &[&str] = &[
+    "service",
+    "display_name",
+    "api_url",
+    "env_token",
+    "author",
+    "auth_style",
+    "auth_manifest",
+    "auth_client",
+    "env_config",
+    "auth_readme",
+];
+
+/// Names a template's manifest may have.
+const MANIFEST_TEMPLATES: &[&str] = &["manifest.json.tmpl", "manifest.json"];
+
+/// The built-in template, embedded at compile time.
+const BUILTIN_TEMPLATE: &[(&str, &str)] = &[
+    (
+        "Cargo.toml.tmpl",
+        include_str!("../templates/generate/Cargo.toml.tmpl"),
+    ),
+    (
+        "manifest.json.tmpl",
+        include_str!("../templates/generate/manifest.json.tmpl"),
+    ),
+    (
+        "README.md.tmpl",
+        include_str!("../templates/generate/README.md.tmpl"),
+    ),
+    (
+        "src/api.rs.tmpl",
+        include_str!("../templates/generate/src/api.rs.tmpl"),
+    ),
+    (
+        "src/main.rs.tmpl",
+        include_str!("../templates/generate/src/main.rs.tmpl"),
+    ),
+];
+
+/// A template file: its path relative to the template and its contents.
+type TemplateFile = (PathBuf, Vec<u8>);
+
 /// Get the path to the generator script.
 fn generator_script_path() -> Result<PathBuf> {
     // Try relative to the CLI binary first (installed location)
@@ -61,16 +147,181 @@ pub fn list() -> Result<()> {
     Ok(())
 }
 
-/// Generate a new daemon from a service preset.
-pub fn new_daemon(
-    service: &str,
-    preset: bool,
-    display_name: Option<&str>,
-    api_url: Option<&str>,
-    env_token: Option<&str>,
-    output_dir: Option<&str>,
-    author: &str,
-) -> Result<()> {
+/// A preset, as `generate.py --show-preset` describes it.
+#[derive(Debug, Deserialize)]
+struct PresetDetails {
+    display_name: String,
+    api_url: String,
+    env_token: String,
+    #[serde(default)]
+    methods: Vec<PresetMethod>,
+}
+
+/// A method the preset's template scaffolds: its name, or name and
+/// description.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PresetMethod {
+    Name(String),
+    Described {
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+    },
+}
+
+/// Show what a service preset sets up.
+pub fn show(service: &str) -> Result<()> {
+    let details = preset_details(service)?;
+    print!("{}", render_preset(service, &details));
+    Ok(())
+}
+
+/// Ask the generator script to describe a preset.
+fn preset_details(service: &str) -> Result<PresetDetails> {
+    let script_path = generator_script_path()?;
+
+    let output = Command::new("python3")
+        .arg(&script_path)
+        .arg("--show-preset")
+        .arg(service)
+        .output()
+        .context("Failed to run generator script")?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        if stderr.contains("--show-preset") {
+            bail!(
+                "The generator at {} doesn't support showing presets. Update the generator to use 'fgp generate show'.",
+                script_path.display()
+            );
+        }
+        bail!("Generator failed:\n{}", stderr);
+    }
+
+    serde_json::from_slice(&output.stdout).context("Failed to parse the generator's preset details")
+}
+
+fn render_preset(service: &str, details: &PresetDetails) -> String {
+    let mut out = format!(
+        "{} ({})\n\n  {:<12} {}\n  {:<12} {}\n",
+        details.display_name.bold(),
+        service,
+        "API URL:",
+        details.api_url,
+        "Token env:",
+        details.env_token
+    );
+    if details.methods.is_empty() {
+        out.push_str(&format!("\n  {}\n", "No methods scaffolded".dimmed()));
+        return out;
+    }
+
+    out.push_str(&format!("\n{}\n", "Methods:".bold()));
+    for method in &details.methods {
+        match method {
+            PresetMethod::Name(name)
+            | PresetMethod::Described {
+                name,
+                description: None,
+            } => out.push_str(&format!("  {}\n", name)),
+            PresetMethod::Described {
+                name,
+                description: Some(description),
+            } => out.push_str(&format!("  {:<24} {}\n", name, description.dimmed())),
+        }
+    }
+    out
+}
+
+/// Options for `fgp generate new`.
+#[derive(Debug, Default)]
+pub struct NewDaemonOptions<'a> {
+    /// Use the generator's preset configuration for known services
+    pub preset: bool,
+    pub display_name: Option<&'a str>,
+    pub api_url: Option<&'a str>,
+    pub env_token: Option<&'a str>,
+    /// Directory to create the daemon in (default: current directory)
+    pub output_dir: Option<&'a str>,
+    pub author: &'a str,
+    /// Local template directory to render instead of the generator
+    pub template: Option<&'a str>,
+    /// How requests are authenticated (see [`AuthStyle::parse`])
+    pub auth_style: Option<&'a str>,
+    /// Environment names, each optionally `name=base-url`
+    pub environments: &'a [String],
+}
+
+/// Generate a new daemon from a service preset, the built-in template or a
+/// template directory.
+pub fn new_daemon(service: &str, options: &NewDaemonOptions) -> Result<()> {
+    let (auth_style, environments) = if options.auth_style.is_none()
+        && options.environments.is_empty()
+        && std::io::stdin().is_terminal()
+        && std::io::stdout().is_terminal()
+    {
+        prompt_auth()?
+    } else {
+        let style = options.auth_style.map(AuthStyle::parse).transpose()?;
+        (style, parse_environments(options.environments)?)
+    };
+
+    if let Some(template) = options.template {
+        let template_dir = Path::new(template);
+        check_template_dir(template_dir)?;
+        return render_daemon(
+            service,
+            &format!("template {}", template_dir.display()),
+            read_template(template_dir)?,
+            options,
+            &auth_style.unwrap_or(AuthStyle::Bearer),
+            &environments,
+        );
+    }
+    if auth_style.is_some() || !environments.is_empty() {
+        let details = match options.preset {
+            true => Some(preset_details(service)?),
+            false => None,
+        };
+        let options = NewDaemonOptions {
+            display_name: options
+                .display_name
+                .or(details.as_ref().map(|d| d.display_name.as_str())),
+            api_url: options
+                .api_url
+                .or(details.as_ref().map(|d| d.api_url.as_str())),
+            env_token: options
+                .env_token
+                .or(details.as_ref().map(|d| d.env_token.as_str())),
+            ..*options
+        };
+        let files = BUILTIN_TEMPLATE
+            .iter()
+            .map(|(path, text)| (PathBuf::from(path), text.as_bytes().to_vec()))
+            .collect();
+        return render_daemon(
+            service,
+            match details {
+                Some(_) => "built-in template, preset",
+                None => "built-in template",
+            },
+            files,
+            &options,
+            &auth_style.unwrap_or(AuthStyle::Bearer),
+            &environments,
+        );
+    }
+
+    let NewDaemonOptions {
+        preset,
+        display_name,
+        api_url,
+        env_token,
+        output_dir,
+        author,
+        ..
+    } = *options;
     let script_path = generator_script_path()?;
 
     println!();
@@ -128,3 +379,1124 @@ pub fn new_daemon(
 
     Ok(())
 }
+
+// ============================================================================
+// Custom templates
+// ============================================================================
+
+/// Render template files into `<output>/<service>/`. `source` says where
+/// they came from.
+fn render_daemon(
+    service: &str,
+    source: &str,
+    files: Vec<TemplateFile>,
+    options: &NewDaemonOptions,
+    auth_style: &AuthStyle,
+    environments: &[Environment],
+) -> Result<()> {
+    let target = Path::new(options.output_dir.unwrap_or(".")).join(service);
+    if target.exists() {
+        bail!("Directory '{}' already exists", target.display());
+    }
+
+    println!();
+    println!(
+        "{} Generating FGP daemon: {} ({})",
+        "→".blue(),
+        service.bold(),
+        source
+    );
+
+    let vars = template_vars(service, options, auth_style, environments);
+    let used = used_placeholders(&files);
+    if used.contains("api_url") && options.api_url.is_none() {
+        bail!("Template uses {{{{api_url}}}} but --api-url was not given");
+    }
+    // Environments without a URL of their own fall back to the API URL
+    let needs_api_url = environments.is_empty() || environments.iter().any(|e| e.url.is_none());
+    if used.contains("env_config") && needs_api_url && options.api_url.is_none() {
+        bail!(
+            "--api-url is needed for the API's base URL{}",
+            if environments.is_empty() {
+                " (or give every environment one with --environments name=url)"
+            } else {
+                " (not every environment has its own)"
+            }
+        );
+    }
+    let unknown = used
+        .iter()
+        .filter(|name| !PLACEHOLDERS.contains(&name.as_str()));
+    for name in unknown {
+        println!(
+            "  {} Unknown placeholder {{{{{}}}}} left as-is",
+            "!".yellow(),
+            name
+        );
+    }
+
+    let count = files.len();
+    for (file, bytes) in files {
+        let out = target.join(render_path(&file, &vars));
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        // Binary files are copied unchanged
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => render(&text, &vars).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        fs::write(&out, bytes).with_context(|| format!("Failed to write {}", out.display()))?;
+        println!(
+            "  {} Generated {}",
+            "✓".green(),
+            out.strip_prefix(&target).unwrap_or(&out).display()
+        );
+    }
+
+    println!();
+    println!(
+        "{} Created {} ({} files)",
+        "✓".green().bold(),
+        target.display(),
+        count
+    );
+
+    Ok(())
+}
+
+/// Make sure `dir` is a template directory with a manifest.
+fn check_template_dir(dir: &Path) -> Result<()> {
+    if !dir.is_dir() {
+        bail!("Template directory not found: {}", dir.display());
+    }
+    let has_manifest = MANIFEST_TEMPLATES
+        .iter()
+        .any(|name| dir.join(name).is_file());
+    if !has_manifest {
+        bail!(
+            "Template directory {} has no manifest template (expected {})",
+            dir.display(),
+            MANIFEST_TEMPLATES.join(" or ")
+        );
+    }
+    Ok(())
+}
+
+/// Values for each placeholder, with defaults filled in.
+fn template_vars(
+    service: &str,
+    options: &NewDaemonOptions,
+    auth_style: &AuthStyle,
+    environments: &[Environment],
+) -> Vec<(&'static str, String)> {
+    let display_name = options
+        .display_name
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| super::new::to_title_case(&service.replace('_', "-")));
+    let env_token = options
+        .env_token
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("{}_API_TOKEN", env_prefix(service)));
+    let api_url = options.api_url.unwrap_or_default();
+    let env_var = format!("{}_ENV", env_prefix(service));
+
+    vec![
+        ("service", service.to_string()),
+        ("display_name", display_name),
+        ("api_url", api_url.to_string()),
+        ("author", options.author.to_string()),
+        ("auth_style", auth_style.kind().to_string()),
+        (
+            "auth_manifest",
+            serde_json::to_string_pretty(&auth_style.manifest(service, &env_token))
+                .unwrap_or_default(),
+        ),
+        ("auth_client", auth_style.client_code(service, &env_token)),
+        ("env_config", env_config(api_url, environments, &env_var)),
+        (
+            "auth_readme",
+            auth_readme(auth_style, service, &env_token, environments, &env_var),
+        ),
+        // Last: the snippets above use it too
+        ("env_token", env_token),
+    ]
+}
+
+/// `my-api` as an environment variable prefix: `MY_API`.
+fn env_prefix(service: &str) -> String {
+    service.to_uppercase().replace('-', "_")
+}
+
+/// Substitute `{{name}}` placeholders. The lines of a multi-line value after
+/// the first get the indentation of the line the placeholder is on.
+fn render(text: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(text.to_string(), |text, (name, value)| {
+        let placeholder = format!("{{{{{}}}}}", name);
+        if !value.contains('\n') {
+            return text.replace(&placeholder, value);
+        }
+        text.split_inclusive('\n')
+            .map(|line| {
+                let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+                let value = value
+                    .split('\n')
+                    .enumerate()
+                    .map(|(i, part)| match i {
+                        0 => part.to_string(),
+                        _ if part.is_empty() => String::new(),
+                        _ => format!("{}{}", indent, part),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                line.replace(&placeholder, &value)
+            })
+            .collect()
+    })
+}
+
+/// Output path for a template file: placeholders substituted and a
+/// trailing `.tmpl` dropped.
+fn render_path(file: &Path, vars: &[(&str, String)]) -> PathBuf {
+    let path = render(&file.to_string_lossy(), vars);
+    PathBuf::from(path.strip_suffix(".tmpl").unwrap_or(&path))
+}
+
+/// Every `{{name}}` placeholder used in the template's files or file names.
+fn used_placeholders(files: &[TemplateFile]) -> BTreeSet<String> {
+    let pattern = regex::Regex::new(r"\{\{([a-z_]+)\}\}").unwrap();
+    let mut used = BTreeSet::new();
+    for (file, bytes) in files {
+        let text = std::str::from_utf8(bytes).unwrap_or_default();
+        for text in [text, &file.to_string_lossy()] {
+            used.extend(pattern.captures_iter(text).map(|c| c[1].to_string()));
+        }
+    }
+    used
+}
+
+/// The files of a template directory.
+fn read_template(dir: &Path) -> Result<Vec<TemplateFile>> {
+    template_files(dir)?
+        .into_iter()
+        .map(|file| {
+            let bytes = fs::read(dir.join(&file))
+                .with_context(|| format!("Failed to read template file {}", file.display()))?;
+            Ok((file, bytes))
+        })
+        .collect()
+}
+
+/// Files under `dir`, relative to it and sorted. `.git` is skipped.
+fn template_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else {
+                files.push(path.strip_prefix(root)?.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(dir, dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+// ============================================================================
+// Auth styles and environments
+// ============================================================================
+
+/// How a generated daemon authenticates its API requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthStyle {
+    /// `Authorization: Bearer <token>`
+    Bearer,
+    /// The token in a custom header, e.g. `X-Api-Key`
+    Header(String),
+    /// The token in a query parameter, e.g. `?api_key=...`
+    Query(String),
+    /// `Authorization: Basic`, with the token holding `user:password`
+    Basic,
+    /// OAuth2 access tokens, refreshed at the token URL (from
+    /// `<SERVICE>_TOKEN_URL` when not given) and cached on disk
+    OAuth2(Option<String>),
+}
+
+impl AuthStyle {
+    /// Parse `bearer`, `header:<name>`, `query:<name>`, `basic`, or `oauth2`
+    /// (optionally `oauth2:<token-url>`).
+    pub fn parse(style: &str) -> Result<Self> {
+        let name_of = |name: &str| -> Result<String> {
+            if !is_name(name) {
+                bail!("Invalid name '{}' in auth style '{}'", name, style);
+            }
+            Ok(name.to_string())
+        };
+        match style.split_once(':') {
+            None if style == "bearer" => Ok(Self::Bearer),
+            None if style == "basic" => Ok(Self::Basic),
+            None if style == "oauth2" => Ok(Self::OAuth2(None)),
+            Some(("header", name)) => Ok(Self::Header(name_of(name)?)),
+            Some(("query", name)) => Ok(Self::Query(name_of(name)?)),
+            Some(("oauth2", url)) if url.starts_with("https://") || url.starts_with("http://") => {
+                Ok(Self::OAuth2(Some(url.to_string())))
+            }
+            Some(("oauth2", url)) => bail!("Invalid token URL '{}' in auth style '{}'", url, style),
+            _ => bail!(
+                "Unknown auth style '{}'\nValid styles: bearer, header:<name>, query:<name>, basic, oauth2[:<token-url>]",
+                style
+            ),
+        }
+    }
+
+    /// `bearer`, `header`, `query`, `basic` or `oauth2`.
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Bearer => "bearer",
+            Self::Header(_) => "header",
+            Self::Query(_) => "query",
+            Self::Basic => "basic",
+            Self::OAuth2(_) => "oauth2",
+        }
+    }
+
+    /// The manifest's `auth` block.
+    fn manifest(&self, service: &str, env_token: &str) -> serde_json::Value {
+        let mut auth = serde_json::json!({
+            "type": match self {
+                Self::Bearer => "bearer",
+                Self::Header(_) | Self::Query(_) => "api_key",
+                Self::Basic => "basic",
+                Self::OAuth2(_) => "oauth2",
+            },
+            "env": env_token,
+        });
+        match self {
+            Self::Header(name) => auth["header"] = name.as_str().into(),
+            Self::Query(name) => auth["query"] = name.as_str().into(),
+            Self::OAuth2(token_url) => {
+                let prefix = env_prefix(service);
+                auth["provider"] = service.into();
+                auth["client_id_env"] = format!("{}_CLIENT_ID", prefix).into();
+                auth["client_secret_env"] = format!("{}_CLIENT_SECRET", prefix).into();
+                auth["token_url"] = match token_url {
+                    Some(url) => url.as_str().into(),
+                    None => format!("${}_TOKEN_URL", prefix).into(),
+                };
+                // `fgp install` reports whether this file exists yet
+                auth["credentials_path"] = token_cache(service).into();
+                auth["token_path"] = token_cache(service).into();
+            }
+            Self::Bearer | Self::Basic => {}
+        }
+        auth
+    }
+
+    /// Rust source for `authorize(request)`, which adds the credentials to a
+    /// `reqwest::blocking::RequestBuilder`, reading the token from `env_token`
+    /// (for OAuth2, the first refresh token).
+    fn client_code(&self, service: &str, env_token: &str) -> String {
+        if let Self::OAuth2(ref token_url) = *self {
+            return oauth2_code(service, env_token, token_url.as_deref());
+        }
+        let apply = match self {
+            Self::Bearer => "    Ok(request.bearer_auth(token))".to_string(),
+            Self::Header(name) => format!("    Ok(request.header(\"{}\", token))", name),
+            // reqwest percent-encodes query values
+            Self::Query(name) => format!("    Ok(request.query(&[(\"{}\", token)]))", name),
+            Self::Basic => "    Ok(match token.split_once(':') {\n        \
+                            Some((user, password)) => request.basic_auth(user, Some(password)),\n        \
+                            None => request.basic_auth(token, None::<&str>),\n    \
+                            })"
+            .to_string(),
+            Self::OAuth2(_) => unreachable!(),
+        };
+
+        format!(
+            "/// Add the API credentials (from `{env}`) to a request.\n\
+             pub fn authorize(\n    \
+             request: reqwest::blocking::RequestBuilder,\n\
+             ) -> Result<reqwest::blocking::RequestBuilder, String> {{\n    \
+             let token = std::env::var(\"{env}\").map_err(|_| \"{env} is not set\".to_string())?;\n\
+             {apply}\n\
+             }}\n",
+            env = env_token,
+            apply = apply
+        )
+    }
+}
+
+impl fmt::Display for AuthStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Header(name) | Self::Query(name) => write!(f, "{}:{}", self.kind(), name),
+            Self::OAuth2(Some(url)) => write!(f, "{}:{}", self.kind(), url),
+            _ => write!(f, "{}", self.kind()),
+        }
+    }
+}
+
+/// A header, query parameter or environment name: letters, digits, `-`, `_`.
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Where a generated OAuth2 daemon caches its tokens.
+fn token_cache(service: &str) -> String {
+    format!("~/.fgp/services/{}/tokens.json", service)
+}
+
+/// Rust source for an OAuth2 `authorize(request)`: it sends a cached
+/// access token while it's valid, and otherwise gets a new one with the
+/// refresh token (cached, or from `env_token` the first time) and the client
+/// credentials in `<SERVICE>_CLIENT_ID` and `<SERVICE>_CLIENT_SECRET`.
+fn oauth2_code(service: &str, env_token: &str, token_url: Option<&str>) -> String {
+    let prefix = env_prefix(service);
+    let token_url = match token_url {
+        Some(url) => format!("Ok({:?}.to_string())", url),
+        None => format!(
+            "std::env::var(\"{0}_TOKEN_URL\").map_err(|_| \"{0}_TOKEN_URL is not set\".to_string())",
+            prefix
+        ),
+    };
+    format!(
+        "/// Token cache, relative to the home directory.\n\
+         const TOKEN_CACHE: &str = \".fgp/services/{service}/tokens.json\";\n\
+         /// Refresh token used when nothing is cached yet.\n\
+         const REFRESH_TOKEN_ENV: &str = \"{env}\";\n\
+         const CLIENT_ID_ENV: &str = \"{prefix}_CLIENT_ID\";\n\
+         const CLIENT_SECRET_ENV: &str = \"{prefix}_CLIENT_SECRET\";\n\
+         \n\
+         /// OAuth2 token endpoint.\n\
+         fn token_url() -> Result<String, String> {{\n    \
+         {token_url}\n\
+         }}\n\
+         \n\
+         /// Add an OAuth2 access token to a request.\n\
+         pub fn authorize(\n    \
+         request: reqwest::blocking::RequestBuilder,\n\
+         ) -> Result<reqwest::blocking::RequestBuilder, String> {{\n    \
+         Ok(request.bearer_auth(access_token()?))\n\
+         }}\n\
+         {code}",
+        service = service,
+        env = env_token,
+        prefix = prefix,
+        token_url = token_url,
+        code = OAUTH2_CODE
+    )
+}
+
+/// The token cache and refresh behind [`oauth2_code`]'s `authorize`. Tokens
+/// are refreshed with reqwest and the cache is JSON, so the module needs
+/// `serde_json` too.
+const OAUTH2_CODE: &str = r#"
+/// A cached access token with at least a minute left, or a refreshed one.
+fn access_token() -> Result<String, String> {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .ok_or("Could not find the home directory")?;
+    let cache = std::path::Path::new(&home).join(TOKEN_CACHE);
+    let cached: serde_json::Value = std::fs::read_to_string(&cache)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    if let (Some(token), Some(expires_at)) = (
+        cached["access_token"].as_str(),
+        cached["expires_at"].as_u64(),
+    ) {
+        if expires_at > now + 60 {
+            return Ok(token.to_string());
+        }
+    }
+
+    let refresh_token = match cached["refresh_token"].as_str() {
+        Some(token) => token.to_string(),
+        None => std::env::var(REFRESH_TOKEN_ENV)
+            .map_err(|_| format!("No refresh token: set {}", REFRESH_TOKEN_ENV))?,
+    };
+    let env = |name: &str| std::env::var(name).map_err(|_| format!("{} is not set", name));
+    let form = [
+        ("grant_type", "refresh_token".to_string()),
+        ("refresh_token", refresh_token.clone()),
+        ("client_id", env(CLIENT_ID_ENV)?),
+        ("client_secret", env(CLIENT_SECRET_ENV)?),
+    ];
+    let response = reqwest::blocking::Client::new()
+        .post(token_url()?)
+        .header(reqwest::header::ACCEPT, "application/json")
+        .form(&form)
+        .send()
+        .map_err(|e| format!("Token refresh failed: {}", e))?;
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .map_err(|e| format!("Token refresh failed: {} ({})", status, e))?;
+
+    let token = body["access_token"]
+        .as_str()
+        .ok_or_else(|| format!("Token refresh failed: {} {}", status, body))?
+        .to_string();
+    let expires_at = now + body["expires_in"].as_u64().unwrap_or(3600);
+    // Some providers hand out a new refresh token with each access token
+    let refresh_token = body["refresh_token"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or(refresh_token);
+    save_tokens(
+        &cache,
+        &serde_json::json!({
+            "access_token": token,
+            "refresh_token": refresh_token,
+            "expires_at": expires_at,
+        }),
+    )?;
+    Ok(token)
+}
+
+/// Write the token cache, readable only by its owner.
+fn save_tokens(cache: &std::path::Path, tokens: &serde_json::Value) -> Result<(), String> {
+    use std::io::Write;
+
+    let failed = |e: std::io::Error| format!("Failed to write {}: {}", cache.display(), e);
+    if let Some(dir) = cache.parent() {
+        std::fs::create_dir_all(dir).map_err(failed)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(cache).map_err(failed)?;
+    // A cache written before may have been readable by others
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(failed)?;
+    }
+    let text = serde_json::to_string_pretty(tokens).map_err(|e| e.to_string())?;
+    writeln!(file, "{}", text).map_err(failed)
+}
+"#;
+
+/// A deployment of the API the daemon talks to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub name: String,
+    /// Base URL, if given as `name=url`
+    pub url: Option<String>,
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.url {
+            Some(ref url) => write!(f, "{}={}", self.name, url),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Parse `--environments` entries (`dev`, or `prod=https://...`).
+fn parse_environments(entries: &[String]) -> Result<Vec<Environment>> {
+    let mut environments: Vec<Environment> = Vec::new();
+    for entry in entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let (name, url) = match entry.split_once('=') {
+            Some((name, url)) => (name.trim(), Some(url.trim().to_string())),
+            None => (entry, None),
+        };
+        if !is_name(name) {
+            bail!("Invalid environment name '{}'", name);
+        }
+        if environments.iter().any(|e| e.name == name) {
+            bail!("Environment '{}' is listed twice", name);
+        }
+        environments.push(Environment {
+            name: name.to_string(),
+            url,
+        });
+    }
+    Ok(environments)
+}
+
+/// Rust source for `base_url()`: the API URL, or with environments the one
+/// `env_var` names (the first environment when it's unset).
+fn env_config(api_url: &str, environments: &[Environment], env_var: &str) -> String {
+    if environments.is_empty() {
+        return format!(
+            "/// Base URL of the API.\n\
+             pub fn base_url() -> Result<String, String> {{\n    \
+             Ok(\"{}\".to_string())\n\
+             }}\n",
+            api_url
+        );
+    }
+
+    let mut code = format!(
+        "/// Base URL of each environment; `{}` picks one (default: {}).\n\
+         pub const ENVIRONMENTS: &[(&str, &str)] = &[\n",
+        env_var, environments[0].name
+    );
+    for environment in environments {
+        // TODO markers make unset URLs easy to find in the generated code
+        let url = environment
+            .url
+            .clone()
+            .unwrap_or_else(|| format!("{} (TODO: {} URL)", api_url, environment.name));
+        code.push_str(&format!(
+            "    ({:?}, {:?}),\n",
+            environment.name,
+            url.trim()
+        ));
+    }
+    code.push_str(&format!(
+        "];\n\n\
+         /// Base URL of the environment `{env}` selects.\n\
+         pub fn base_url() -> Result<String, String> {{\n    \
+         let name = std::env::var(\"{env}\").unwrap_or_else(|_| ENVIRONMENTS[0].0.to_string());\n    \
+         ENVIRONMENTS\n        \
+         .iter()\n        \
+         .find(|(env, _)| *env == name)\n        \
+         .map(|(_, url)| url.to_string())\n        \
+         .ok_or_else(|| format!(\"Unknown {env} '{{}}'\", name))\n\
+         }}\n",
+        env = env_var
+    ));
+    code
+}
+
+/// README sections on auth and, with more than one, environments.
+fn auth_readme(
+    auth_style: &AuthStyle,
+    service: &str,
+    env_token: &str,
+    environments: &[Environment],
+    env_var: &str,
+) -> String {
+    let sent_as = |how: &str| {
+        format!(
+            "Set `{}` to your API credentials. The daemon sends them as {}.",
+            env_token, how
+        )
+    };
+    let auth = match auth_style {
+        AuthStyle::Bearer => sent_as("a Bearer token in the `Authorization` header"),
+        AuthStyle::Header(name) => sent_as(&format!("the `{}` header", name)),
+        AuthStyle::Query(name) => sent_as(&format!("the `{}` query parameter", name)),
+        AuthStyle::Basic => sent_as("HTTP Basic auth (set it to `user:password`)"),
+        AuthStyle::OAuth2(token_url) => {
+            let prefix = env_prefix(service);
+            let token_url = match token_url {
+                Some(url) => format!("`{}`", url),
+                None => format!("the URL in `{}_TOKEN_URL`", prefix),
+            };
+            format!(
+                "The daemon uses OAuth2. Set `{prefix}_CLIENT_ID` and `{prefix}_CLIENT_SECRET` \
+                 to your app's credentials, and `{env}` to a refresh token. It gets access \
+                 tokens from {url} and caches them, with any new refresh token, in `{cache}`; \
+                 after the first refresh `{env}` is no longer needed.",
+                prefix = prefix,
+                env = env_token,
+                url = token_url,
+                cache = token_cache(service)
+            )
+        }
+    };
+    let mut readme = format!("## Authentication\n\n{}\n", auth);
+
+    if !environments.is_empty() {
+        readme.push_str(&format!(
+            "\n## Environments\n\n\
+             Set `{}` to pick the API environment (default: `{}`):\n\n\
+             | Environment | Base URL |\n\
+             |-------------|----------|\n",
+            env_var, environments[0].name
+        ));
+        for environment in environments {
+            readme.push_str(&format!(
+                "| `{}` | {} |\n",
+                environment.name,
+                environment.url.as_deref().unwrap_or("(set in src)")
+            ));
+        }
+    }
+    readme
+}
+
+/// Ask for the auth style and environments. No answer for the auth style
+/// leaves it unset, which means bearer.
+fn prompt_auth() -> Result<(Option<AuthStyle>, Vec<Environment>)> {
+    let ask = |question: &str| -> Result<String> {
+        print!("{} {} ", "?".yellow().bold(), question);
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .context("Failed to read answer")?;
+        Ok(answer.trim().to_string())
+    };
+
+    let style = loop {
+        let answer =
+            ask("Auth style (bearer, header:<name>, query:<name>, basic, oauth2[:<token-url>]) [bearer]:")?;
+        if answer.is_empty() {
+            break None;
+        }
+        match AuthStyle::parse(&answer) {
+            Ok(style) => break Some(style),
+            Err(e) => println!("  {} {}", "!".yellow(), e),
+        }
+    };
+    let environments = loop {
+        let answer = ask("Environments, comma-separated (e.g. dev,staging,prod) [none]:")?;
+        let entries: Vec<String> = answer.split(',').map(str::to_string).collect();
+        match parse_environments(&entries) {
+            Ok(environments) => break environments,
+            Err(e) => println!("  {} {}", "!".yellow(), e),
+        }
+    };
+    Ok((style, environments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("template");
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::write(
+            template.join("manifest.json.tmpl"),
+            r#"{"name": "{{service}}", "title": "{{display_name}}", "x": "{{other}}"}"#,
+        )
+        .unwrap();
+        fs::write(
+            template.join("src/{{service}}.rs"),
+            "// {{env_token}} by {{author}}",
+        )
+        .unwrap();
+
+        let output = dir.path().to_string_lossy().to_string();
+        let options = NewDaemonOptions {
+            output_dir: Some(&output),
+            author: "Ada",
+            template: Some(template.to_str().unwrap()),
+            auth_style: Some("bearer"),
+            ..Default::default()
+        };
+        new_daemon("my-api", &options).unwrap();
+
+        let target = dir.path().join("my-api");
+        assert_eq!(
+            fs::read_to_string(target.join("manifest.json")).unwrap(),
+            r#"{"name": "my-api", "title": "My Api", "x": "{{other}}"}"#
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("src/my-api.rs")).unwrap(),
+            "// MY_API_API_TOKEN by Ada"
+        );
+
+        // The target must not exist yet, and api_url is required once used
+        assert!(new_daemon("my-api", &options).is_err());
+        fs::write(template.join("README.md"), "{{api_url}}").unwrap();
+        assert!(new_daemon("other", &options).is_err());
+
+        fs::remove_file(template.join("manifest.json.tmpl")).unwrap();
+        assert!(check_template_dir(&template).is_err());
+
+        // Multi-line values keep the placeholder's indentation
+        assert_eq!(
+            render(
+                "{\n  \"auth\": {{auth_manifest}},\n}\n",
+                &[("auth_manifest", "{\n  \"type\": \"basic\"\n}".to_string())]
+            ),
+            "{\n  \"auth\": {\n    \"type\": \"basic\"\n  },\n}\n"
+        );
+    }
+
+    /// Build a cargo crate at `<target>/generated-clients/<name>` with a
+    /// binary for each `(name, source)` in `bins`, depending on what a
+    /// generated API client does. Returns the directory with the binaries.
+    fn build_clients(name: &str, bins: &[(String, String)]) -> PathBuf {
+        let exe = std::env::current_exe().unwrap();
+        let root = exe
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("generated-clients");
+        let dir = root.join(name);
+        let _ = fs::remove_dir_all(dir.join("src"));
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                 [workspace]\n\n\
+                 [dependencies]\n\
+                 anyhow = \"1\"\n\
+                 reqwest = {{ version = \"0.12\", default-features = false, features = [\"blocking\", \"json\"] }}\n\
+                 serde_json = \"1\"\n",
+                name
+            ),
+        )
+        .unwrap();
+        for (bin, source) in bins {
+            fs::write(dir.join("src/bin").join(format!("{}.rs", bin)), source).unwrap();
+        }
+
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let target = root.join("target");
+        let built = Command::new(cargo)
+            .args(["build", "--quiet"])
+            .current_dir(&dir)
+            .env("CARGO_TARGET_DIR", &target)
+            .output()
+            .unwrap();
+        assert!(
+            built.status.success(),
+            "generated clients failed to build:\n{}",
+            String::from_utf8_lossy(&built.stderr)
+        );
+        target.join("debug")
+    }
+
+    /// Answer one HTTP request per response, in order, on a local port, and
+    /// send each request (head and body) down the returned channel.
+    fn serve(responses: Vec<String>) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::Read;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for body in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8_lossy(&content));
+                let _ = sender.send(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    /// Generate a daemon from the built-in template with each auth style,
+    /// build its API client and check the requests it sends.
+    #[test]
+    fn test_auth_styles() {
+        let (url, requests) = serve(vec![r#"{"ok": true}"#.to_string(); 4]);
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().to_string_lossy().to_string();
+        let environments = vec![format!("dev={}/dev", url), format!("prod={}/api", url)];
+
+        let cases = [
+            (
+                "bearer",
+                "dev",
+                "s3cret",
+                "get /dev/things ",
+                "authorization: bearer s3cret",
+            ),
+            (
+                "header:X-Api-Key",
+                "dev",
+                "s3cret",
+                "get /dev/things ",
+                "x-api-key: s3cret",
+            ),
+            // The token is percent-encoded in the query
+            (
+                "query:api_key",
+                "prod",
+                "s&cret+1",
+                "get /api/things?api_key=s%26cret%2b1 ",
+                "",
+            ),
+            (
+                "basic",
+                "prod",
+                "ada:s3cret",
+                "get /api/things ",
+                "authorization: basic ywrhonmzy3jlda==",
+            ),
+        ];
+        let mut bins = Vec::new();
+        for (i, (style, ..)) in cases.iter().enumerate() {
+            let service = format!("svc-{}", i);
+            let options = NewDaemonOptions {
+                api_url: Some("https://example.com"),
+                output_dir: Some(&output),
+                auth_style: Some(style),
+                environments: &environments,
+                ..Default::default()
+            };
+            new_daemon(&service, &options).unwrap();
+
+            let target = dir.path().join(&service);
+            let manifest =
+                super::super::skill_import::load_manifest(&target.join("manifest.json")).unwrap();
+            assert_eq!(manifest.methods[0].name, format!("{}.request", service));
+            let manifest: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(target.join("manifest.json")).unwrap())
+                    .unwrap();
+            assert_eq!(manifest["auth"]["env"], format!("SVC_{}_API_TOKEN", i));
+            assert_eq!(
+                manifest["daemon"]["start_command"][0],
+                format!("{{service_dir}}/target/release/fgp-{}", service)
+            );
+            let readme = fs::read_to_string(target.join("README.md")).unwrap();
+            assert!(readme.contains(&format!("`SVC_{}_ENV`", i)), "{}", readme);
+            let cargo_toml = fs::read_to_string(target.join("Cargo.toml")).unwrap();
+            assert!(cargo_toml.contains("reqwest"), "{}", cargo_toml);
+
+            bins.push((
+                format!("auth{}", i),
+                format!(
+                    "#[path = {:?}]\n\
+                     mod api;\n\
+                     fn main() {{\n    \
+                     let client = reqwest::blocking::Client::new();\n    \
+                     match api::get(&client, \"/things\") {{\n        \
+                     Ok(value) => println!(\"{{}}\", value),\n        \
+                     Err(e) => println!(\"error: {{:#}}\", e),\n    \
+                     }}\n\
+                     }}\n",
+                    target.join("src/api.rs")
+                ),
+            ));
+        }
+
+        let bin_dir = build_clients("auth-styles", &bins);
+        for (i, (style, env, token, request_line, header)) in cases.iter().enumerate() {
+            let run = Command::new(bin_dir.join(format!("auth{}", i)))
+                .env(format!("SVC_{}_API_TOKEN", i), token)
+                .env(format!("SVC_{}_ENV", i), env)
+                .output()
+                .unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&run.stdout),
+                "{\"ok\":true}\n",
+                "{}",
+                style
+            );
+            let request = requests.recv().unwrap().to_ascii_lowercase();
+            assert!(request.starts_with(request_line), "{}: {}", style, request);
+            assert!(request.contains(header), "{}: {}", style, request);
+        }
+
+        // Without a token the request isn't sent
+        let run = Command::new(bin_dir.join("auth0"))
+            .env_remove("SVC_0_API_TOKEN")
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&run.stdout),
+            "error: SVC_0_API_TOKEN is not set\n"
+        );
+
+        // Environments without a URL fall back to --api-url
+        let options = NewDaemonOptions {
+            output_dir: Some(&output),
+            environments: &["dev".to_string()],
+            ..Default::default()
+        };
+        assert!(new_daemon("no-url", &options).is_err());
+
+        assert!(AuthStyle::parse("header:").is_err());
+        assert!(AuthStyle::parse("digest").is_err());
+        assert!(parse_environments(&["dev".into(), "dev=x".into()]).is_err());
+    }
+
+    /// The `oauth2` client sends a cached access token while it's valid and
+    /// refreshes it at the token endpoint (a local server here) once it
+    /// expires.
+    #[test]
+    fn test_oauth2_client() {
+        let (url, requests) = serve(vec![
+            r#"{"access_token": "fresh", "expires_in": 3600, "refresh_token": "r2"}"#.to_string(),
+            r#"{"error": "invalid_grant"}"#.to_string(),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().to_string_lossy().to_string();
+        let auth_style = format!("oauth2:{}/token", url);
+        let options = NewDaemonOptions {
+            api_url: Some("https://crm.example.com"),
+            output_dir: Some(&output),
+            auth_style: Some(&auth_style),
+            ..Default::default()
+        };
+        new_daemon("crm", &options).unwrap();
+
+        let target = dir.path().join("crm");
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(target.join("manifest.json")).unwrap())
+                .unwrap();
+        let auth = &manifest["auth"];
+        assert_eq!(auth["type"], "oauth2");
+        assert_eq!(auth["client_id_env"], "CRM_CLIENT_ID");
+        assert_eq!(auth["token_path"], "~/.fgp/services/crm/tokens.json");
+        let readme = fs::read_to_string(target.join("README.md")).unwrap();
+        assert!(readme.contains("`CRM_CLIENT_SECRET`"), "{}", readme);
+
+        let source = format!(
+            "#[path = {:?}]\n\
+             mod api;\n\
+             fn main() {{\n    \
+             let request = reqwest::blocking::Client::new().get(\"http://localhost/\");\n    \
+             match api::authorize(request) {{\n        \
+             Ok(request) => {{\n            \
+             let request = request.build().unwrap();\n            \
+             println!(\"{{}}\", request.headers()[\"authorization\"].to_str().unwrap());\n        \
+             }}\n        \
+             Err(e) => println!(\"error: {{}}\", e),\n    \
+             }}\n\
+             }}\n",
+            target.join("src/api.rs")
+        );
+        let binary = build_clients("oauth2", &[("oauth2".to_string(), source)]).join("oauth2");
+
+        let home = dir.path().join("home");
+        let cache = home.join(".fgp/services/crm/tokens.json");
+        fs::create_dir_all(cache.parent().unwrap()).unwrap();
+        fs::write(
+            &cache,
+            r#"{"access_token": "cached", "refresh_token": "r1", "expires_at": 99999999999}"#,
+        )
+        .unwrap();
+        let run = || {
+            let out = Command::new(&binary)
+                .env("HOME", &home)
+                .env("CRM_CLIENT_ID", "id")
+                .env("CRM_CLIENT_SECRET", "s&cret")
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&out.stdout).to_string()
+        };
+        assert_eq!(run(), "Bearer cached\n");
+
+        // Expired, with no client credentials: it says which are missing
+        fs::write(
+            &cache,
+            r#"{"access_token": "cached", "refresh_token": "r1", "expires_at": 1}"#,
+        )
+        .unwrap();
+        let out = Command::new(&binary).env("HOME", &home).output().unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "error: CRM_CLIENT_ID is not set\n"
+        );
+
+        // Refreshed with a form POST, and the new tokens cached
+        assert_eq!(run(), "Bearer fresh\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /token "), "{}", request);
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("content-type: application/x-www-form-urlencoded"),
+            "{}",
+            request
+        );
+        assert!(
+            request.ends_with(
+                "grant_type=refresh_token&refresh_token=r1&client_id=id&client_secret=s%26cret"
+            ),
+            "{}",
+            request
+        );
+        let cached: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&cache).unwrap()).unwrap();
+        assert_eq!(cached["access_token"], "fresh");
+        assert_eq!(cached["refresh_token"], "r2");
+        assert!(cached["expires_at"].as_u64().unwrap() > 3600);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                fs::metadata(&cache).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+        }
+
+        // The new token is used from the cache
+        assert_eq!(run(), "Bearer fresh\n");
+        assert!(requests.try_recv().is_err());
+
+        // A refresh the endpoint turns down reports its answer
+        fs::write(
+            &cache,
+            r#"{"access_token": "fresh", "refresh_token": "r2", "expires_at": 1}"#,
+        )
+        .unwrap();
+        let out = run();
+        assert!(out.starts_with("error: Token refresh failed"), "{}", out);
+        assert!(out.contains("invalid_grant"), "{}", out);
+        assert!(requests.recv().unwrap().contains("refresh_token=r2"));
+
+        assert!(AuthStyle::parse("oauth2:token").is_err());
+    }
+
+    #[test]
+    fn test_render_preset() {
+        let details: PresetDetails = serde_json::from_str(
+            r#"{"display_name": "Linear", "api_url": "https://api.linear.app/graphql",
+                "env_token": "LINEAR_API_KEY",
+                "methods": ["issues", {"name": "create_issue", "description": "Create an issue"}]}"#,
+        )
+        .unwrap();
+        let out = render_preset("linear", &details);
+        assert!(out.contains("https://api.linear.app/graphql"), "{}", out);
+        assert!(out.contains("LINEAR_API_KEY"), "{}", out);
+        assert!(out.contains("  issues\n"), "{}", out);
+        assert!(out.contains("create_issue"), "{}", out);
+        assert!(out.contains("Create an issue"), "{}", out);
+    }
+}
@@ -0,0 +1,164 @@
+//! Guardrails for destructive daemon methods.
+//!
+//! A service's manifest.json can mark methods that send, change or delete
+//! things with `"destructive": true`, and with `"supports_dry_run": true` if
+//! the method takes `dry_run: true` to preview its effect instead.
+//!
+//! `fgp call` asks before running a destructive method from a terminal
+//! (`--yes` skips the question). Calls nobody can confirm, such as an agent
+//! calling through the MCP bridge or a script calling `fgp call`, follow the
+//! `[guardrails]` policy in ~/.fgp/config.toml, whether or not they pass
+//! `--yes`:
+//!
+//! ```toml
+//! [guardrails]
+//! default = "deny"     # allow | dry-run | deny
+//!
+//! [guardrails.services]
+//! calendar = "allow"   # blanket approval for one daemon
+//! files = "dry-run"    # previews only
+//! ```
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::fgp_services_dir;
+use super::methods::find_method;
+use super::skill_import::{load_manifest, ManifestMethod};
+use crate::config::{self, Policy};
+
+/// What to do with an unconfirmed call to a destructive method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Send the call as-is
+    Proceed,
+    /// Send it with `dry_run: true`, so the daemon only previews it
+    Preview,
+    /// Don't send it, for this reason
+    Deny(String),
+}
+
+/// Methods declared in a service's installed manifest.json (none if it has
+/// no readable manifest).
+pub fn declared_methods(service: &str) -> Vec<ManifestMethod> {
+    load_manifest(&fgp_services_dir().join(service).join("manifest.json"))
+        .map(|manifest| manifest.methods)
+        .unwrap_or_default()
+}
+
+/// The definition of `method` if it is declared destructive.
+pub fn find_destructive<'a>(
+    declared: &'a [ManifestMethod],
+    service: &str,
+    method: &str,
+) -> Option<&'a ManifestMethod> {
+    find_method(declared, service, method).filter(|m| m.destructive)
+}
+
+/// Decide on an unconfirmed call using the configured policy for `service`.
+pub fn check(service: &str, method: &ManifestMethod) -> Result<Decision> {
+    let policy = config::load()?.guardrails.policy(service);
+    Ok(decide(policy, service, method))
+}
+
+fn decide(policy: Policy, service: &str, method: &ManifestMethod) -> Decision {
+    let approve = format!(
+        "To approve it, set `{} = \"allow\"` under [guardrails.services] in {}.",
+        service,
+        config::config_path().display()
+    );
+    match policy {
+        Policy::Allow => Decision::Proceed,
+        Policy::DryRun if method.supports_dry_run => Decision::Preview,
+        Policy::DryRun => Decision::Deny(format!(
+            "{} is a destructive method without dry-run support, and the guardrails policy for '{}' only allows previews. {}",
+            method.name, service, approve
+        )),
+        Policy::Deny => Decision::Deny(format!(
+            "{} is a destructive method, and the guardrails policy for '{}' denies calls that nobody confirmed. {}",
+            method.name, service, approve
+        )),
+    }
+}
+
+/// `params` with `dry_run: true` set.
+pub fn with_dry_run(params: Value) -> Value {
+    let mut params = match params {
+        Value::Object(map) => map,
+        _ => Default::default(),
+    };
+    params.insert("dry_run".to_string(), Value::Bool(true));
+    Value::Object(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn method(name: &str, destructive: bool, supports_dry_run: bool) -> ManifestMethod {
+        serde_json::from_value(json!({
+            "name": name,
+            "destructive": destructive,
+            "supports_dry_run": supports_dry_run,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_destructive() {
+        let declared = [
+            method("files.delete", true, false),
+            method("list", false, false),
+        ];
+        assert!(find_destructive(&declared, "files", "delete").is_some());
+        assert!(find_destructive(&declared, "files", "files.list").is_none());
+        assert!(find_destructive(&declared, "files", "files.move").is_none());
+    }
+
+    #[test]
+    fn test_decide() {
+        let send = method("gmail.send", true, true);
+        let delete = method("files.delete", true, false);
+
+        assert_eq!(decide(Policy::Allow, "gmail", &send), Decision::Proceed);
+        assert_eq!(decide(Policy::DryRun, "gmail", &send), Decision::Preview);
+        assert!(matches!(
+            decide(Policy::DryRun, "files", &delete),
+            Decision::Deny(reason) if reason.contains("without dry-run support")
+        ));
+        assert!(matches!(
+            decide(Policy::Deny, "gmail", &send),
+            Decision::Deny(reason) if reason.contains("`gmail = \"allow\"`")
+        ));
+    }
+
+    #[test]
+    fn test_policy_config() {
+        let config: config::FgpConfig = toml::from_str(
+            r#"
+            [guardrails]
+            default = "dry-run"
+
+            [guardrails.services]
+            calendar = "allow"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.guardrails.policy("calendar"), Policy::Allow);
+        assert_eq!(config.guardrails.policy("gmail"), Policy::DryRun);
+        assert_eq!(
+            config::GuardrailsConfig::default().policy("gmail"),
+            Policy::Deny
+        );
+    }
+
+    #[test]
+    fn test_with_dry_run() {
+        assert_eq!(
+            with_dry_run(json!({"path": "/tmp/x"})),
+            json!({"path": "/tmp/x", "dry_run": true})
+        );
+        assert_eq!(with_dry_run(json!(null)), json!({"dry_run": true}));
+    }
+}
@@ -1,64 +1,92 @@
-//! Check health of a specific service.
+//! Check the health of one service, or of every installed service.
+//!
+//! `--all` prints a table of every service's status, health-call latency and
+//! version; `--watch` redraws it every `--interval` seconds. Each daemon gets
+//! its own `--timeout`, and all of them are asked at once, so a hung daemon
+//! can't stall the sweep.
+//!
+//! The table views exit with the aggregate health, for cron and monitoring:
+//! 0 if every service is healthy, 1 if any is unhealthy, 2 if any could not
+//! be contacted.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::io::Write;
+use std::time::{Duration, Instant};
+use tabled::{Table, Tabled};
 
-use super::service_socket_path;
+use super::client::{self, Client};
 
-pub fn run(service: &str) -> Result<()> {
-    let socket_path = service_socket_path(service);
+/// Options for `fgp health`.
+#[derive(Debug)]
+pub struct HealthOptions {
+    /// Check every installed service instead of one
+    pub all: bool,
+    /// Redraw the table every `interval` until interrupted
+    pub watch: bool,
+    pub interval: Duration,
+    /// How long each daemon has to answer
+    pub timeout: Duration,
+}
 
-    if !socket_path.exists() {
-        bail!(
-            "Service '{}' is not running. Run 'fgp start {}' first.",
-            service,
-            service
+pub fn run(service: Option<&str>, options: &HealthOptions) -> Result<()> {
+    let services = match service {
+        Some(service) if !options.all => vec![service.to_string()],
+        _ => super::restart::installed_services()?,
+    };
+
+    if !options.all && !options.watch {
+        return show_service(&services[0], options.timeout);
+    }
+
+    if services.is_empty() {
+        println!("{} No services installed", "!".yellow().bold());
+        return Ok(());
+    }
+
+    if !options.watch {
+        let results = sweep(&services, options.timeout);
+        print_table(&services, &results);
+        std::process::exit(exit_code(&results));
+    }
+
+    loop {
+        let results = sweep(&services, options.timeout);
+        // Clear the screen and move the cursor home before redrawing
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{}",
+            format!(
+                "Every {}s, {} (Ctrl-C to stop)",
+                options.interval.as_secs(),
+                chrono::Local::now().format("%H:%M:%S")
+            )
+            .dimmed()
         );
+        println!();
+        print_table(&services, &results);
+        std::io::stdout().flush()?;
+        std::thread::sleep(options.interval);
     }
+}
 
-    let client = fgp_daemon::FgpClient::new(&socket_path).context("Failed to connect to daemon")?;
+/// Detailed health of a single service.
+fn show_service(service: &str, timeout: Duration) -> Result<()> {
+    let client = match Client::builder(service).timeout(timeout).connect() {
+        Ok(client) => client,
+        Err(client::Error::NotRunning { .. }) => bail!(
+            "Service '{}' is not running. Run 'fgp start {}' first.",
+            service,
+            service
+        ),
+        Err(e) => return Err(e).context("Failed to connect to daemon"),
+    };
 
     let start = std::time::Instant::now();
     let response = client.health().context("Failed to get health")?;
     let elapsed = start.elapsed();
 
-    if response.ok {
-        let result = response.result.unwrap_or_default();
-
-        let status = result["status"].as_str().unwrap_or("unknown");
-        let version = result["version"].as_str().unwrap_or("?");
-        let uptime = result["uptime_seconds"].as_u64().unwrap_or(0);
-        let pid = result["pid"].as_u64().unwrap_or(0);
-
-        let status_icon = match status {
-            "healthy" => "●".green(),
-            "degraded" => "◐".yellow(),
-            "unhealthy" => "○".red(),
-            _ => "?".dimmed(),
-        };
-
-        println!("{} {} {}", status_icon, service.bold(), status);
-        println!();
-        println!("  Version:  {}", version);
-        println!("  PID:      {}", pid);
-        println!("  Uptime:   {}", format_uptime(uptime));
-        println!("  Latency:  {:.1}ms", elapsed.as_secs_f64() * 1000.0);
-
-        // Print sub-services if any
-        if let Some(services) = result["services"].as_object() {
-            if !services.is_empty() {
-                println!();
-                println!("  Sub-services:");
-                for (name, status) in services {
-                    let ok = status["ok"].as_bool().unwrap_or(false);
-                    let icon = if ok { "✓".green() } else { "✗".red() };
-                    let msg = status["message"].as_str().unwrap_or("");
-                    println!("    {} {}: {}", icon, name, msg);
-                }
-            }
-        }
-    } else {
-        let error = response.error.unwrap_or_default();
+    if let Some(error) = response.error() {
         eprintln!(
             "{} {} - Error ({}): {}",
             "○".red(),
@@ -69,9 +97,197 @@ pub fn run(service: &str) -> Result<()> {
         std::process::exit(1);
     }
 
+    let result = response.result.unwrap_or_default();
+
+    let status = result["status"].as_str().unwrap_or("unknown");
+    let version = result["version"].as_str().unwrap_or("?");
+    let uptime = result["uptime_seconds"].as_u64().unwrap_or(0);
+    let pid = result["pid"].as_u64().unwrap_or(0);
+
+    let status_icon = match status {
+        "healthy" => "●".green(),
+        "degraded" => "◐".yellow(),
+        "unhealthy" => "○".red(),
+        _ => "?".dimmed(),
+    };
+
+    println!("{} {} {}", status_icon, service.bold(), status);
+    println!();
+    println!("  Version:  {}", version);
+    println!("  PID:      {}", pid);
+    println!("  Uptime:   {}", format_uptime(uptime));
+    println!("  Latency:  {:.1}ms", elapsed.as_secs_f64() * 1000.0);
+
+    // Print sub-services if any
+    if let Some(services) = result["services"].as_object() {
+        if !services.is_empty() {
+            println!();
+            println!("  Sub-services:");
+            for (name, status) in services {
+                let ok = status["ok"].as_bool().unwrap_or(false);
+                let icon = if ok { "✓".green() } else { "✗".red() };
+                let msg = status["message"].as_str().unwrap_or("");
+                println!("    {} {}: {}", icon, name, msg);
+            }
+        }
+    }
+
     Ok(())
 }
 
+// ============================================================================
+// Sweeps
+// ============================================================================
+
+/// Outcome of one daemon's health call.
+#[derive(Debug, Clone, PartialEq)]
+enum Health {
+    /// The daemon answered with its status ("healthy", "degraded", ...)
+    Answered {
+        status: String,
+        version: Option<String>,
+        latency: Duration,
+    },
+    /// The daemon answered with an error
+    Failed { message: String, latency: Duration },
+    /// Not running, not answering in time, or the socket refused
+    Unreachable(String),
+}
+
+impl Health {
+    fn is_healthy(&self) -> bool {
+        matches!(self, Health::Answered { status, .. } if status == "healthy")
+    }
+}
+
+/// Exit status for a sweep: 2 if any daemon could not be contacted, 1 if
+/// any is unhealthy, otherwise 0.
+fn exit_code(results: &[Health]) -> i32 {
+    if results.iter().any(|h| matches!(h, Health::Unreachable(_))) {
+        2
+    } else if results.iter().all(Health::is_healthy) {
+        0
+    } else {
+        1
+    }
+}
+
+/// Ask every service for its health at once.
+fn sweep(services: &[String], timeout: Duration) -> Vec<Health> {
+    std::thread::scope(|scope| {
+        let checks: Vec<_> = services
+            .iter()
+            .map(|service| scope.spawn(move || check(service, timeout)))
+            .collect();
+        checks
+            .into_iter()
+            .map(|check| {
+                check
+                    .join()
+                    .unwrap_or_else(|_| Health::Unreachable("health check failed".to_string()))
+            })
+            .collect()
+    })
+}
+
+/// One health call, giving up after `timeout`.
+fn check(service: &str, timeout: Duration) -> Health {
+    probe(Client::builder(service), timeout)
+}
+
+fn probe(builder: client::Builder, timeout: Duration) -> Health {
+    let client = match builder.timeout(timeout).connect() {
+        Ok(client) => client,
+        Err(client::Error::NotRunning { .. }) => {
+            return Health::Unreachable("not running".to_string())
+        }
+        Err(e) => return Health::Unreachable(e.to_string()),
+    };
+
+    let start = Instant::now();
+    let response = match client.health() {
+        Ok(response) => response,
+        Err(client::Error::Timeout) => {
+            return Health::Unreachable(format!("no answer within {:.1}s", timeout.as_secs_f64()))
+        }
+        // A socket nobody is listening on, usually left by a crashed daemon
+        Err(client::Error::Connect { .. }) => {
+            return Health::Unreachable("not responding".to_string())
+        }
+        Err(e) => return Health::Unreachable(e.to_string()),
+    };
+    let latency = start.elapsed();
+
+    match response.error() {
+        Some(error) => Health::Failed {
+            message: format!("{}: {}", error.code, error.message),
+            latency,
+        },
+        None => {
+            let result = response.result.unwrap_or_default();
+            Health::Answered {
+                // Daemons that don't report a status are healthy if they answer
+                status: result["status"].as_str().unwrap_or("healthy").to_string(),
+                version: result["version"].as_str().map(|v| v.to_string()),
+                latency,
+            }
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct HealthRow {
+    #[tabled(rename = "Service")]
+    service: String,
+    #[tabled(rename = "Health")]
+    health: String,
+    #[tabled(rename = "Latency")]
+    latency: String,
+    #[tabled(rename = "Version")]
+    version: String,
+}
+
+fn print_table(services: &[String], results: &[Health]) {
+    let rows: Vec<HealthRow> = services
+        .iter()
+        .zip(results)
+        .map(|(service, health)| {
+            let (health, latency, version) = match health {
+                Health::Answered {
+                    status,
+                    version,
+                    latency,
+                } => {
+                    let status = match status.as_str() {
+                        "healthy" => "● healthy".green().to_string(),
+                        "degraded" => "◐ degraded".yellow().to_string(),
+                        other => format!("○ {}", other).red().to_string(),
+                    };
+                    (status, Some(*latency), version.clone())
+                }
+                Health::Failed { message, latency } => (
+                    format!("○ error ({})", message).red().to_string(),
+                    Some(*latency),
+                    None,
+                ),
+                Health::Unreachable(reason) => {
+                    (format!("○ {}", reason).dimmed().to_string(), None, None)
+                }
+            };
+            HealthRow {
+                service: service.clone(),
+                health,
+                latency: latency
+                    .map(|l| format!("{:.1}ms", l.as_secs_f64() * 1000.0))
+                    .unwrap_or_else(|| "-".to_string()),
+                version: version.unwrap_or_else(|| "-".to_string()),
+            }
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+}
+
 /// Format uptime seconds into human-readable string.
 fn format_uptime(secs: u64) -> String {
     if secs < 60 {
@@ -90,3 +306,64 @@ fn format_uptime(secs: u64) -> String {
         format!("{} days {} hours", days, hours)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answered(status: &str) -> Health {
+        Health::Answered {
+            status: status.to_string(),
+            version: Some("1.0.0".to_string()),
+            latency: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(&[answered("healthy"), answered("healthy")]), 0);
+        assert_eq!(exit_code(&[answered("healthy"), answered("degraded")]), 1);
+        let failed = Health::Failed {
+            message: "AUTH: token expired".to_string(),
+            latency: Duration::from_millis(1),
+        };
+        assert_eq!(exit_code(&[answered("healthy"), failed.clone()]), 1);
+        let down = Health::Unreachable("not running".to_string());
+        assert_eq!(exit_code(&[failed, down]), 2);
+        assert_eq!(exit_code(&[]), 0);
+    }
+
+    #[test]
+    fn test_probe_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        // Accepts connections but never answers
+        let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+
+        let start = Instant::now();
+        let health = probe(
+            Client::builder("hung").socket(&socket),
+            Duration::from_millis(50),
+        );
+        assert!(
+            matches!(health, Health::Unreachable(ref reason) if reason.starts_with("no answer")),
+            "{:?}",
+            health
+        );
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        let missing = probe(
+            Client::builder("gone").socket(dir.path().join("missing.sock")),
+            Duration::from_millis(50),
+        );
+        assert_eq!(missing, Health::Unreachable("not running".to_string()));
+
+        // A stale socket file with no daemon behind it
+        drop(listener);
+        let stale = probe(
+            Client::builder("hung").socket(&socket),
+            Duration::from_millis(50),
+        );
+        assert_eq!(stale, Health::Unreachable("not responding".to_string()));
+    }
+}
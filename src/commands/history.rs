@@ -0,0 +1,415 @@
+//! Method-call history (`~/.fgp/history.jsonl`).
+//!
+//! `fgp call` appends one JSON line per invocation; `fgp history` reads them
+//! back with optional filters. Recording is best-effort and never fails a call.
+//!
+//! Params are recorded with the values of secret-looking keys (see
+//! [`is_secret_key`]) replaced by `"[redacted]"`, so tokens and passwords
+//! passed to a daemon don't end up in the log. The file is created readable
+//! only by its owner.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use tabled::{Table, Tabled};
+
+/// A single recorded method call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// RFC 3339 timestamp (UTC)
+    pub timestamp: String,
+    pub service: String,
+    pub method: String,
+    pub params: Value,
+    pub latency_ms: f64,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(service: &str, method: &str, params: &Value, latency: std::time::Duration) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            service: service.to_string(),
+            method: method.to_string(),
+            params: redact(params),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+            ok: true,
+            error: None,
+        }
+    }
+
+    /// Parsed timestamp, if valid.
+    fn time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Mark the call as failed.
+    pub fn failed(mut self, error: impl Into<String>) -> Self {
+        self.ok = false;
+        self.error = Some(error.into());
+        self
+    }
+}
+
+/// Key fragments that mark a param as secret, compared case-insensitively
+/// with `_` and `-` removed.
+const SECRET_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "passwd",
+    "passphrase",
+    "secret",
+    "token",
+    "apikey",
+    "authorization",
+    "credential",
+    "cookie",
+    "privatekey",
+];
+
+/// Whether a param key such as `api_key` or `refreshToken` names a secret.
+fn is_secret_key(key: &str) -> bool {
+    let key: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    SECRET_KEY_FRAGMENTS
+        .iter()
+        .any(|fragment| key.contains(fragment))
+}
+
+/// Copy of `params` with the values of secret keys replaced, at any depth.
+fn redact(params: &Value) -> Value {
+    match params {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_secret_key(key) && !value.is_null() {
+                        Value::String("[redacted]".to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Filters for `fgp history`.
+#[derive(Debug, Default)]
+pub struct HistoryFilter<'a> {
+    pub service: Option<&'a str>,
+    /// Exact method name, or a glob such as `gmail.*`
+    pub method: Option<&'a str>,
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl HistoryFilter<'_> {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        self.service.map(|s| entry.service == s).unwrap_or(true)
+            && self
+                .method
+                .map(|m| super::skill::glob_match(m, &entry.method))
+                .unwrap_or(true)
+            && self
+                .since
+                .map(|since| entry.time().map(|t| t >= since).unwrap_or(false))
+                .unwrap_or(true)
+    }
+}
+
+#[derive(Tabled)]
+struct HistoryRow {
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "Service")]
+    service: String,
+    #[tabled(rename = "Method")]
+    method: String,
+    #[tabled(rename = "Latency")]
+    latency: String,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+/// Path of the history file.
+pub fn history_path() -> PathBuf {
+    super::fgp_home().join("history.jsonl")
+}
+
+/// Append an entry to the history file, ignoring any I/O errors.
+pub fn record(entry: &HistoryEntry) {
+    let _ = try_record(entry);
+}
+
+fn try_record(entry: &HistoryEntry) -> Result<()> {
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    // Params can identify people and accounts; keep the log to its owner
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Show recent calls.
+pub fn run(
+    service: Option<&str>,
+    method: Option<&str>,
+    since: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let filter = HistoryFilter {
+        service,
+        method,
+        since: since
+            .map(|s| parse_duration(s).map(|d| Utc::now() - d))
+            .transpose()?,
+    };
+
+    let path = history_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    // Skip lines that fail to parse (e.g. a partially written final line)
+    let entries: Vec<HistoryEntry> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+        .filter(|entry| filter.matches(entry))
+        .collect();
+
+    let recent = &entries[entries.len().saturating_sub(limit)..];
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(recent)?);
+        return Ok(());
+    }
+
+    if recent.is_empty() {
+        println!(
+            "{} No matching calls in {}",
+            "!".yellow().bold(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<HistoryRow> = recent
+        .iter()
+        .map(|entry| HistoryRow {
+            time: entry
+                .time()
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_else(|| entry.timestamp.clone()),
+            service: entry.service.clone(),
+            method: entry.method.clone(),
+            latency: format!("{:.1}ms", entry.latency_ms),
+            status: if entry.ok {
+                "ok".green().to_string()
+            } else {
+                format!("error: {}", entry.error.as_deref().unwrap_or("unknown"))
+                    .red()
+                    .to_string()
+            },
+        })
+        .collect();
+
+    println!("{}", Table::new(rows));
+    println!(
+        "{}",
+        format!(
+            "Showing {} of {} matching call(s)",
+            recent.len(),
+            entries.len()
+        )
+        .dimmed()
+    );
+
+    Ok(())
+}
+
+/// Parse a duration such as `500ms`, `30s`, `15m`, `2h`, `7d`, or `1h30m`.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("Empty duration");
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value: i64 = number.parse().with_context(|| {
+            format!(
+                "Invalid duration '{}': expected a number before '{}'",
+                input, c
+            )
+        })?;
+        number.clear();
+
+        total += match c {
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                Duration::milliseconds(value)
+            }
+            's' => Duration::seconds(value),
+            'm' => Duration::minutes(value),
+            'h' => Duration::hours(value),
+            'd' => Duration::days(value),
+            'w' => Duration::weeks(value),
+            _ => bail!(
+                "Invalid duration '{}': unknown unit '{}' (use ms, s, m, h, d, or w)",
+                input,
+                c
+            ),
+        };
+    }
+
+    if !number.is_empty() {
+        bail!(
+            "Invalid duration '{}': missing unit after {}",
+            input,
+            number
+        );
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::seconds(30));
+        assert_eq!(parse_duration("15m").unwrap(), Duration::minutes(15));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::days(7));
+        assert_eq!(
+            parse_duration("250ms").unwrap(),
+            Duration::milliseconds(250)
+        );
+        assert_eq!(
+            parse_duration("1m30ms").unwrap(),
+            Duration::milliseconds(60_030)
+        );
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let entry = HistoryEntry::new(
+            "gmail",
+            "gmail.search",
+            &serde_json::json!({"q": "invoice"}),
+            std::time::Duration::from_millis(12),
+        );
+
+        assert!(HistoryFilter::default().matches(&entry));
+        assert!(HistoryFilter {
+            service: Some("gmail"),
+            method: Some("gmail.*"),
+            since: Some(Utc::now() - Duration::hours(1)),
+        }
+        .matches(&entry));
+        assert!(!HistoryFilter {
+            service: Some("slack"),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!HistoryFilter {
+            method: Some("gmail.send"),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!HistoryFilter {
+            since: Some(Utc::now() + Duration::hours(1)),
+            ..Default::default()
+        }
+        .matches(&entry));
+    }
+
+    #[test]
+    fn test_params_redacted() {
+        let entry = HistoryEntry::new(
+            "slack",
+            "slack.post",
+            &serde_json::json!({
+                "channel": "#ops",
+                "api_key": "xoxb-1",
+                "auth": {"refreshToken": "r1", "user": "ana"},
+                "headers": [{"Authorization": "Bearer t"}],
+                "password": null,
+            }),
+            std::time::Duration::from_millis(3),
+        );
+
+        assert_eq!(
+            entry.params,
+            serde_json::json!({
+                "channel": "#ops",
+                "api_key": "[redacted]",
+                "auth": {"refreshToken": "[redacted]", "user": "ana"},
+                "headers": [{"Authorization": "[redacted]"}],
+                "password": null,
+            })
+        );
+        assert!(!is_secret_key("author"));
+        assert!(!is_secret_key("keywords"));
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = HistoryEntry::new(
+            "gmail",
+            "gmail.send",
+            &serde_json::json!({}),
+            std::time::Duration::from_millis(5),
+        )
+        .failed("timeout");
+
+        let line = serde_json::to_string(&entry).unwrap();
+        let parsed: HistoryEntry = serde_json::from_str(&line).unwrap();
+        assert!(!parsed.ok);
+        assert_eq!(parsed.error.as_deref(), Some("timeout"));
+        assert_eq!(parsed.method, "gmail.send");
+    }
+}
@@ -1,13 +1,28 @@
-//! Install a package from local path.
+//! Install a package from a local path, a tarball, or a git repository.
+//!
+//! Tarballs are extracted and git repositories shallow-cloned into a
+//! temporary directory, which is removed once the package is installed. The
+//! package is the directory holding `manifest.json`: the root of the archive
+//! or checkout, or its single top-level directory.
+//!
+//! The daemon files are staged next to `~/.fgp/services/<name>/` and only
+//! moved into place once every file has been copied, so a failed install
+//! leaves no partial service directory behind. Before that, the manifest's
+//! daemon commands and the JSON Schema of its methods' params are checked, so
+//! a broken manifest fails the install rather than a later start or call.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use super::fgp_services_dir;
+use super::methods_export::params_schema;
+use super::network::Network;
+use super::skill_import::ManifestMethod;
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
@@ -20,6 +35,8 @@ struct Manifest {
     protocol: String,
     daemon: DaemonConfig,
     #[serde(default)]
+    methods: Vec<ManifestMethod>,
+    #[serde(default)]
     skills: HashMap<String, SkillConfig>,
     #[serde(default)]
     auth: Option<AuthConfig>,
@@ -33,6 +50,10 @@ struct DaemonConfig {
     socket: String,
     #[serde(default)]
     dependencies: Vec<String>,
+    #[serde(default)]
+    start_command: Vec<String>,
+    #[serde(default)]
+    stop_command: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,9 +85,181 @@ const AGENT_CONFIGS: &[(&str, &str, &str)] = &[
     ("continue", "~/.continue/rules", "Continue"),
 ];
 
-pub fn run(path: &str) -> Result<()> {
-    let package_path = Path::new(path);
+/// Archive extensions `fgp install` extracts with `tar`.
+const TARBALL_EXTENSIONS: &[&str] = &[".tar.gz", ".tgz", ".tar", ".tar.xz", ".tar.bz2"];
+
+/// Where a package comes from.
+#[derive(Debug, PartialEq)]
+enum PackageSource {
+    /// A package directory or its manifest.json
+    Local(PathBuf),
+    /// A local tarball
+    Tarball(PathBuf),
+    /// A git repository, optionally at a tag or branch
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+}
+
+impl PackageSource {
+    /// Classify `source`. A `#<ref>` fragment on a git URL, or `git_ref`,
+    /// selects the tag or branch to install.
+    fn parse(source: &str, git_ref: Option<&str>) -> Result<Self> {
+        let is_url = ["https://", "http://", "ssh://", "git://", "file://", "git@"]
+            .iter()
+            .any(|scheme| source.starts_with(scheme));
+        let is_repo = source.split('#').next().unwrap_or(source).ends_with(".git");
+        let is_tarball = TARBALL_EXTENSIONS.iter().any(|ext| source.ends_with(ext));
+
+        if is_url && is_tarball {
+            bail!(
+                "Remote tarballs aren't supported; download {} and install the file",
+                source
+            );
+        }
+
+        if is_url || is_repo {
+            let (url, fragment) = match source.split_once('#') {
+                Some((url, fragment)) => (url, Some(fragment)),
+                None => (source, None),
+            };
+            let reference = match (fragment, git_ref) {
+                (Some(fragment), Some(git_ref)) if fragment != git_ref => bail!(
+                    "Conflicting refs: #{} in the URL and --ref {}",
+                    fragment,
+                    git_ref
+                ),
+                (fragment, git_ref) => fragment.or(git_ref).map(String::from),
+            };
+            if reference.as_deref() == Some("") {
+                bail!("Empty ref in {}", source);
+            }
+            return Ok(PackageSource::Git {
+                url: url.to_string(),
+                reference,
+            });
+        }
+
+        if git_ref.is_some() {
+            bail!("--ref only applies to git URLs");
+        }
+        let path = PathBuf::from(shellexpand::tilde(source).to_string());
+        if is_tarball {
+            Ok(PackageSource::Tarball(path))
+        } else {
+            Ok(PackageSource::Local(path))
+        }
+    }
+}
+
+/// Install a package from a local directory or manifest.json, a tarball, or
+/// a git URL (at `git_ref` or a `#<ref>` fragment, if given).
+pub fn run(source: &str, git_ref: Option<&str>, network: Network) -> Result<()> {
+    match PackageSource::parse(source, git_ref)? {
+        PackageSource::Local(path) => install_from_path(&path),
+        PackageSource::Tarball(path) => {
+            if !path.is_file() {
+                bail!("Tarball not found: {}", path.display());
+            }
+            let extracted =
+                tempfile::tempdir().context("Failed to create a temporary directory")?;
+            println!("{} Extracting {}...", "→".blue().bold(), path.display());
+            extract_tarball(&path, extracted.path())?;
+            install_from_path(&package_root(extracted.path())?)
+        }
+        PackageSource::Git { url, reference } => {
+            network.require_repo(&url, &format!("Cloning {}", url))?;
+            let checkout = tempfile::tempdir().context("Failed to create a temporary directory")?;
+            println!(
+                "{} Cloning {}{}...",
+                "→".blue().bold(),
+                url,
+                reference
+                    .as_deref()
+                    .map(|r| format!(" at {}", r))
+                    .unwrap_or_default()
+            );
+            shallow_clone(&url, reference.as_deref(), checkout.path())?;
+            // The repository's history isn't part of the package
+            fs::remove_dir_all(checkout.path().join(".git")).ok();
+            install_from_path(&package_root(checkout.path())?)
+        }
+    }
+}
+
+/// Clone `url` (at tag or branch `reference`) into `dest` with no history.
+fn shallow_clone(url: &str, reference: Option<&str>, dest: &Path) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args([
+        "-c",
+        "advice.detachedHead=false",
+        "clone",
+        "--quiet",
+        "--depth",
+        "1",
+    ]);
+    if let Some(reference) = reference {
+        command.args(["--branch", reference]);
+    }
+    // `--` keeps a URL starting with `-` from being read as an option
+    let status = command
+        .arg("--")
+        .arg(url)
+        .arg(dest)
+        .stdout(Stdio::null())
+        .status()
+        .context("Failed to run git")?;
+
+    if !status.success() {
+        match reference {
+            Some(reference) => bail!(
+                "Failed to clone {} at {} (is it a tag or branch?)",
+                url,
+                reference
+            ),
+            None => bail!("Failed to clone {}", url),
+        }
+    }
+    Ok(())
+}
+
+/// Extract `tarball` into `dest`; tar detects the compression.
+fn extract_tarball(tarball: &Path, dest: &Path) -> Result<()> {
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(tarball)
+        .arg("-C")
+        .arg(dest)
+        .status()
+        .context("Failed to run tar")?;
+
+    if !status.success() {
+        bail!("Failed to extract {}", tarball.display());
+    }
+    Ok(())
+}
+
+/// The package directory inside an extracted archive or checkout: `dir`
+/// itself, or its only subdirectory, whichever holds manifest.json.
+fn package_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join("manifest.json").is_file() {
+        return Ok(dir.to_path_buf());
+    }
+
+    let subdirs: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    match subdirs.as_slice() {
+        [only] if only.join("manifest.json").is_file() => Ok(only.clone()),
+        _ => bail!("No manifest.json at the top of the package"),
+    }
+}
 
+/// Install the package in `package_path` (a directory or its manifest.json).
+fn install_from_path(package_path: &Path) -> Result<()> {
     // Support both directory and manifest.json path
     let (package_dir, manifest_path) = if package_path.is_dir() {
         (
@@ -98,6 +291,9 @@ pub fn run(path: &str) -> Result<()> {
         fs::read_to_string(&manifest_path).context("Failed to read manifest.json")?;
     let manifest: Manifest =
         serde_json::from_str(&manifest_content).context("Failed to parse manifest.json")?;
+    check_service_name(&manifest.name)?;
+    check_daemon_commands(&manifest.daemon)?;
+    check_param_schemas(&manifest.methods)?;
 
     println!();
     println!(
@@ -121,16 +317,15 @@ pub fn run(path: &str) -> Result<()> {
         );
     }
 
-    // Step 2: Create service directory and copy daemon files
-    let service_dir = fgp_services_dir().join(&manifest.name);
-    fs::create_dir_all(&service_dir).context("Failed to create service directory")?;
+    // Step 2: Copy daemon files into the service directory
+    install_service(&package_dir, &fgp_services_dir(), &manifest.name)
+        .context("Failed to copy daemon files")?;
 
     println!(
         "  {} Daemon installed to {}",
         "✓".green(),
         format!("~/.fgp/services/{}/", manifest.name).dimmed()
     );
-    copy_dir_contents(&package_dir, &service_dir).context("Failed to copy daemon files")?;
 
     // Step 3: Install skill files for detected agents
     let mut installed_skills = Vec::new();
@@ -205,6 +400,153 @@ pub fn run(path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Fail on unknown or malformed placeholders in the daemon's start and stop
+/// commands, rather than when the daemon is first started or stopped.
+fn check_daemon_commands(daemon: &DaemonConfig) -> Result<()> {
+    let problems: Vec<String> = [
+        ("start_command", &daemon.start_command),
+        ("stop_command", &daemon.stop_command),
+    ]
+    .into_iter()
+    .flat_map(|(key, command)| {
+        super::daemon_command::check(command)
+            .into_iter()
+            .map(move |problem| format!("daemon.{}: {}", key, problem))
+    })
+    .collect();
+    if !problems.is_empty() {
+        bail!("Invalid manifest.json:\n  {}", problems.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Fail unless the manifest's name is a single plain path component, since
+/// it names the directory under the services directory that gets replaced.
+fn check_service_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        bail!(
+            "Invalid manifest.json: name '{}' must be a plain directory name",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Fail on malformed JSON Schema in the methods' params, which would
+/// otherwise only show up when `fgp call --validate` or an MCP client used it.
+fn check_param_schemas(methods: &[ManifestMethod]) -> Result<()> {
+    let problems: Vec<String> = methods
+        .iter()
+        .flat_map(|method| {
+            super::json_schema::check(
+                &params_schema(&method.params),
+                &format!("methods.{}.params", method.name),
+            )
+        })
+        .collect();
+    if !problems.is_empty() {
+        bail!("Invalid manifest.json:\n  {}", problems.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Copy `package_dir` into `<services_dir>/<name>/`.
+///
+/// The files are first copied to a `.<name>.installing-*` staging directory
+/// beside it, which is removed if any copy fails. A new service's staging
+/// directory is then renamed into place. An existing one is moved into a
+/// `.<name>.previous-*` directory and the staged copy renamed in its place
+/// (the old one is put back if that fails); whatever the old directory held
+/// that the package doesn't ship (the daemon's PID file, socket, logs,
+/// tokens) is then moved over into the new one.
+fn install_service(package_dir: &Path, services_dir: &Path, name: &str) -> Result<()> {
+    fs::create_dir_all(services_dir).context("Failed to create services directory")?;
+    let staging = tempfile::Builder::new()
+        .prefix(&format!(".{}.installing-", name))
+        .tempdir_in(services_dir)
+        .context("Failed to create staging directory")?;
+    copy_dir_contents(package_dir, staging.path())?;
+
+    let service_dir = services_dir.join(name);
+    if !service_dir.exists() {
+        fs::rename(staging.path(), &service_dir)?;
+        return Ok(());
+    }
+
+    // Swap the new tree in with renames, keeping the old one until that has
+    // worked, then carry over what the package doesn't ship (the daemon's
+    // pid file, socket, tokens, ...)
+    let previous = tempfile::Builder::new()
+        .prefix(&format!(".{}.previous-", name))
+        .tempdir_in(services_dir)
+        .context("Failed to create backup directory")?;
+    let backup = previous.path().join(name);
+    fs::rename(&service_dir, &backup).context("Failed to move the installed service aside")?;
+    if let Err(e) = fs::rename(staging.path(), &service_dir) {
+        fs::rename(&backup, &service_dir).with_context(|| {
+            format!(
+                "Failed to put the previous install back; it is at {}",
+                backup.display()
+            )
+        })?;
+        return Err(e).context("Failed to move the new install into place");
+    }
+    for entry in fs::read_dir(&backup)? {
+        let entry = entry?;
+        let target = service_dir.join(entry.file_name());
+        if fs::symlink_metadata(&target).is_err() {
+            fs::rename(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Skill files `fgp install` copied for the service installed at
+/// `service_dir`, grouped by the agent directory they were copied into.
+///
+/// Only files that still exist are listed, so removing them twice is
+/// harmless.
+pub(crate) fn installed_skill_files(service_dir: &Path) -> Vec<(PathBuf, Vec<PathBuf>)> {
+    let Ok(content) = fs::read_to_string(service_dir.join("manifest.json")) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = serde_json::from_str::<Manifest>(&content) else {
+        return Vec::new();
+    };
+
+    let mut installed = Vec::new();
+    for skill in manifest.skills.values() {
+        let target = PathBuf::from(shellexpand::tilde(&skill.target).as_ref());
+        let mut files = Vec::new();
+        relative_files(&service_dir.join(&skill.source), Path::new(""), &mut files);
+        let files: Vec<PathBuf> = files
+            .into_iter()
+            .map(|file| target.join(file))
+            .filter(|file| file.exists())
+            .collect();
+        if !files.is_empty() {
+            installed.push((target, files));
+        }
+    }
+    installed.sort();
+    installed
+}
+
+/// Files under `dir`, relative to it.
+fn relative_files(dir: &Path, prefix: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let relative = prefix.join(entry.file_name());
+        if entry.path().is_dir() {
+            relative_files(&entry.path(), &relative, files);
+        } else {
+            files.push(relative);
+        }
+    }
+}
+
 /// Detect which AI agents are installed on the system.
 fn detect_agents() -> Vec<(&'static str, &'static str)> {
     let mut agents = Vec::new();
@@ -239,3 +581,183 @@ fn copy_dir_contents(src: &Path, dst: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_source() {
+        let git = |url: &str, reference: Option<&str>| PackageSource::Git {
+            url: url.to_string(),
+            reference: reference.map(String::from),
+        };
+
+        assert_eq!(
+            PackageSource::parse("https://github.com/acme/fgp-foo-daemon", None).unwrap(),
+            git("https://github.com/acme/fgp-foo-daemon", None)
+        );
+        assert_eq!(
+            PackageSource::parse("https://github.com/acme/fgp-foo-daemon#v1.2.0", None).unwrap(),
+            git("https://github.com/acme/fgp-foo-daemon", Some("v1.2.0"))
+        );
+        assert_eq!(
+            PackageSource::parse("git@github.com:acme/foo.git", Some("v1.2.0")).unwrap(),
+            git("git@github.com:acme/foo.git", Some("v1.2.0"))
+        );
+        assert_eq!(
+            PackageSource::parse("../foo.git#main", None).unwrap(),
+            git("../foo.git", Some("main"))
+        );
+        assert!(PackageSource::parse("https://example.com/foo#v1", Some("v2")).is_err());
+
+        assert_eq!(
+            PackageSource::parse("./foo-daemon-1.2.0.tar.gz", None).unwrap(),
+            PackageSource::Tarball(PathBuf::from("./foo-daemon-1.2.0.tar.gz"))
+        );
+        assert_eq!(
+            PackageSource::parse("./foo-daemon", None).unwrap(),
+            PackageSource::Local(PathBuf::from("./foo-daemon"))
+        );
+        assert!(PackageSource::parse("./foo-daemon", Some("v1.2.0")).is_err());
+        assert!(PackageSource::parse("https://example.com/foo.tgz", None).is_err());
+    }
+
+    #[test]
+    fn test_package_root() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(package_root(dir.path()).is_err());
+
+        let nested = dir.path().join("foo-daemon-1.2.0");
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join("manifest.json"), "{}").unwrap();
+        assert_eq!(package_root(dir.path()).unwrap(), nested);
+
+        fs::write(dir.path().join("manifest.json"), "{}").unwrap();
+        assert_eq!(package_root(dir.path()).unwrap(), dir.path());
+    }
+
+    #[test]
+    fn test_check_daemon_commands() {
+        let daemon = |start: &str| -> DaemonConfig {
+            serde_json::from_str(&format!(
+                r#"{{"entrypoint": "./gw", "start_command": {}, "stop_command": ["kill", "{{pid_file}}"]}}"#,
+                start
+            ))
+            .unwrap()
+        };
+        assert!(check_daemon_commands(&daemon(r#"["{service_dir}/gw", "{env:TOKEN}"]"#)).is_ok());
+        let err = check_daemon_commands(&daemon(r#"["/opt/gw", "--socket", "{sock}"]"#))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("daemon.start_command: unknown placeholder {sock}"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_check_param_schemas() {
+        let methods = |params: &str| -> Vec<ManifestMethod> {
+            serde_json::from_str(&format!(r#"[{{"name": "send", "params": {}}}]"#, params)).unwrap()
+        };
+        let valid = methods(
+            r#"{"type": "object", "properties": {"to": {"type": "string", "pattern": "@"}}, "required": ["to"]}"#,
+        );
+        assert!(check_param_schemas(&valid).is_ok());
+        assert_eq!(valid[0].params[0].name, "to");
+        assert!(valid[0].params[0].required);
+        assert!(check_param_schemas(&methods(
+            r#"[{"name": "limit", "type": "int", "schema": {"minimum": 1}}]"#
+        ))
+        .is_ok());
+
+        let err = check_param_schemas(&methods(
+            r#"[{"name": "limit", "type": "integer", "schema": {"minimum": "one"}}]"#,
+        ))
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains(
+                "methods.send.params.properties.limit.minimum: \"one\" is not of type \"number\""
+            ),
+            "{}",
+            err
+        );
+
+        // Patterns are compiled at install time, not on the first call
+        let err = check_param_schemas(&methods(
+            r#"[{"name": "to", "type": "string", "schema": {"pattern": "(unclosed"}}]"#,
+        ))
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("methods.send.params.properties.to.pattern: "),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_check_service_name() {
+        assert!(check_service_name("gmail").is_ok());
+        assert!(check_service_name("my-api_2").is_ok());
+        for name in ["", ".", "..", "../..", "a/b", "a\\b", "/etc", ".hidden"] {
+            assert!(check_service_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_install_service_is_all_or_nothing() {
+        let package = tempfile::tempdir().unwrap();
+        let services = tempfile::tempdir().unwrap();
+        fs::create_dir(package.path().join("bin")).unwrap();
+        fs::write(package.path().join("manifest.json"), "{}").unwrap();
+        fs::write(package.path().join("bin").join("daemon"), "v2").unwrap();
+
+        // A file that can't be copied fails the install before anything lands
+        #[cfg(unix)]
+        {
+            let broken = package.path().join("bin").join("missing");
+            std::os::unix::fs::symlink("/nonexistent/fgp-target", &broken).unwrap();
+            assert!(install_service(package.path(), services.path(), "foo").is_err());
+            assert_eq!(fs::read_dir(services.path()).unwrap().count(), 0);
+            fs::remove_file(&broken).unwrap();
+        }
+
+        install_service(package.path(), services.path(), "foo").unwrap();
+        let service = services.path().join("foo");
+        assert_eq!(
+            fs::read_to_string(service.join("bin/daemon")).unwrap(),
+            "v2"
+        );
+        assert_eq!(fs::read_dir(services.path()).unwrap().count(), 1);
+
+        // A failed reinstall leaves the installed service as it was
+        #[cfg(unix)]
+        {
+            let broken = package.path().join("bin").join("missing");
+            std::os::unix::fs::symlink("/nonexistent/fgp-target", &broken).unwrap();
+            fs::write(package.path().join("bin").join("daemon"), "broken").unwrap();
+            assert!(install_service(package.path(), services.path(), "foo").is_err());
+            assert_eq!(
+                fs::read_to_string(service.join("bin/daemon")).unwrap(),
+                "v2"
+            );
+            assert_eq!(fs::read_dir(services.path()).unwrap().count(), 1);
+            fs::remove_file(&broken).unwrap();
+        }
+
+        // Reinstalling keeps the daemon's runtime files
+        fs::write(service.join("daemon.pid"), "42").unwrap();
+        fs::write(package.path().join("bin").join("daemon"), "v3").unwrap();
+        install_service(package.path(), services.path(), "foo").unwrap();
+        assert_eq!(
+            fs::read_to_string(service.join("bin/daemon")).unwrap(),
+            "v3"
+        );
+        assert!(service.join("daemon.pid").exists());
+        assert_eq!(fs::read_dir(services.path()).unwrap().count(), 1);
+    }
+}
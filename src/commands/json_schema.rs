@@ -0,0 +1,248 @@
+//! JSON Schema for method params.
+//!
+//! A daemon's manifest.json can describe each param with the shorthand
+//! `type`/`items`/`properties` fields, with a full JSON Schema in `schema`,
+//! or give a method's `params` as one JSON Schema object:
+//!
+//! ```json
+//! "params": {
+//!   "type": "object",
+//!   "properties": {
+//!     "to": {"type": "string", "pattern": "^[^@]+@[^@]+$"},
+//!     "priority": {"enum": ["low", "normal", "high"]}
+//!   },
+//!   "required": ["to"]
+//! }
+//! ```
+//!
+//! `fgp install` checks that these schemas are well formed ([`check`]), and
+//! `fgp call --validate` checks params against them before sending
+//! ([`validate`]), both with the `jsonschema` crate. A schema is read as the
+//! draft its `$schema` names, or draft 2020-12 without one.
+
+use serde_json::Value;
+
+/// Everything malformed in `schema`, each prefixed with where it is, starting
+/// from `path`. Invalid `pattern`s are reported here, so they fail
+/// `fgp install` rather than a later call.
+pub fn check(schema: &Value, path: &str) -> Vec<String> {
+    let meta = match jsonschema::meta::validator_for(schema) {
+        Ok(meta) => meta,
+        Err(e) => return vec![format!("{}: {}", path, e)],
+    };
+    let mut problems: Vec<String> = Vec::new();
+    for e in meta.iter_errors(schema) {
+        // A keyword checked by several of the meta-schema's vocabularies
+        // fails once for each
+        let problem = format!("{}: {}", field(path, &e.instance_path().to_string()), e);
+        if !problems.contains(&problem) {
+            problems.push(problem);
+        }
+    }
+    if !problems.is_empty() {
+        return problems;
+    }
+
+    // The meta-schema doesn't compile regular expressions; building a
+    // validator does
+    match jsonschema::validator_for(schema) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![format!(
+            "{}: {}",
+            field(path, &e.schema_path().to_string()),
+            e
+        )],
+    }
+}
+
+/// Every way `value` fails to match `schema`, as "<field>: <why>" with
+/// fields named from `path` (`params.to`, `params.cc[1]`).
+pub fn validate(schema: &Value, value: &Value, path: &str) -> Vec<String> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(e) => return vec![format!("{}: invalid schema: {}", path, e)],
+    };
+    validator
+        .iter_errors(value)
+        .map(|e| format!("{}: {}", field(path, &e.instance_path().to_string()), e))
+        .collect()
+}
+
+/// `path` extended by a JSON pointer into the value: `/cc/1` under `params`
+/// is `params.cc[1]`.
+fn field(path: &str, pointer: &str) -> String {
+    let mut field = path.to_string();
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if segment.parse::<usize>().is_ok() {
+            field.push_str(&format!("[{}]", segment));
+        } else {
+            field.push('.');
+            field.push_str(&segment);
+        }
+    }
+    field
+}
+
+/// The JSON type of `value`, telling integers from other numbers.
+pub fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "to": {"type": ["string", "null"], "pattern": "^[^@]+@[^@]+$"},
+                "cc": {"type": "array", "items": {"type": "string"}, "uniqueItems": true},
+                "priority": {"enum": ["low", "high"]},
+                "anything": true
+            },
+            "required": ["to"],
+            "additionalProperties": false
+        });
+        assert!(check(&schema, "params").is_empty());
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "to": {"type": "strng"},
+                "cc": {"items": {"minItems": -1}},
+                "limit": {"minimum": "1", "multipleOf": 0}
+            },
+            "required": "to",
+            "anyOf": [],
+            "not": 3
+        });
+        let problems = check(&schema, "params");
+        assert_eq!(problems.len(), 7, "{:#?}", problems);
+        for expected in [
+            "params.required: \"to\" is not of type \"array\"",
+            "params.properties.to.type: ",
+            "params.properties.cc.items.minItems: -1 is less than the minimum of 0",
+            "params.properties.limit.minimum: \"1\" is not of type \"number\"",
+            "params.properties.limit.multipleOf: ",
+            "params.not: ",
+            "params.anyOf: ",
+        ] {
+            assert!(
+                problems.iter().any(|p| p.starts_with(expected)),
+                "no '{}' in {:#?}",
+                expected,
+                problems
+            );
+        }
+
+        assert_eq!(
+            check(
+                &json!({"properties": {"to": {"pattern": "(unclosed"}}}),
+                "params"
+            ),
+            vec!["params.properties.to.pattern: \"(unclosed\" is not a \"regex\""]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "to": {"type": "string", "pattern": "^[^@]+@[^@]+$"},
+                "cc": {"type": "array", "items": {"type": "string"}, "maxItems": 2, "uniqueItems": true},
+                "limit": {"type": "integer", "minimum": 1, "maximum": 100},
+                "priority": {"enum": ["low", "normal", "high"]},
+                "filter": {
+                    "type": "object",
+                    "properties": {"label": {"type": "string", "minLength": 1}},
+                    "additionalProperties": false
+                }
+            },
+            "required": ["to"]
+        });
+        assert!(validate(
+            &schema,
+            &json!({"to": "ada@example.com", "cc": ["bob@example.com"], "limit": 10.0,
+                    "priority": "high", "filter": {"label": "work"}, "extra": 1}),
+            "params"
+        )
+        .is_empty());
+
+        let problems = validate(
+            &schema,
+            &json!({"cc": ["a", "b", "a"], "limit": 0, "priority": "urgent",
+                    "filter": {"label": "", "starred": true}}),
+            "params",
+        );
+        assert_eq!(
+            problems,
+            vec![
+                "params: \"to\" is a required property",
+                "params.cc: [\"a\",\"b\",\"a\"] has more than 2 items",
+                "params.cc: [\"a\",\"b\",\"a\"] has non-unique elements",
+                "params.filter.label: \"\" is shorter than 1 character",
+                "params.filter: Additional properties are not allowed ('starred' was unexpected)",
+                "params.limit: 0 is less than the minimum of 1",
+                "params.priority: \"urgent\" is not one of \"low\", \"normal\" or \"high\"",
+            ]
+        );
+
+        assert_eq!(
+            validate(&schema, &json!({"to": "nobody", "limit": "5"}), "params"),
+            vec![
+                "params.limit: \"5\" is not of type \"integer\"",
+                "params.to: \"nobody\" does not match \"^[^@]+@[^@]+$\"",
+            ]
+        );
+        assert_eq!(
+            validate(&schema, &json!({"to": "a@b", "cc": [1]}), "params"),
+            vec!["params.cc[0]: 1 is not of type \"string\""]
+        );
+        assert_eq!(
+            validate(&schema, &json!([1]), "params"),
+            vec!["params: [1] is not of type \"object\""]
+        );
+    }
+
+    #[test]
+    fn test_validate_combinators() {
+        let schema =
+            json!({"oneOf": [{"type": "integer"}, {"type": "number", "exclusiveMinimum": 0}]});
+        assert!(validate(&schema, &json!(-1), "n").is_empty());
+        assert!(validate(&schema, &json!(0.5), "n").is_empty());
+        assert_eq!(validate(&schema, &json!(2), "n").len(), 1);
+        assert_eq!(validate(&schema, &json!("2"), "n").len(), 1);
+
+        let schema = json!({"anyOf": [{"const": "all"}, {"type": "integer"}], "not": {"const": 0}});
+        assert!(validate(&schema, &json!("all"), "limit").is_empty());
+        assert_eq!(validate(&schema, &json!(0), "limit").len(), 1);
+
+        // Older drafts' boolean exclusiveMinimum, when the schema names its draft
+        assert_eq!(
+            validate(
+                &json!({"$schema": "http://json-schema.org/draft-04/schema#",
+                        "minimum": 5, "exclusiveMinimum": true}),
+                &json!(5),
+                "x"
+            ),
+            vec!["x: 5 is less than or equal to the minimum of 5"]
+        );
+        assert_eq!(
+            validate(&json!({"multipleOf": 0.1}), &json!(0.3), "x"),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Findings from `fgp skill validate` and `fgp workflow validate --lint`, and
+//! the one format both print them in:
+//!
+//! ```text
+//! error[always-fails] step 2 (gmail.send): uses 'steps.lookup.result.email', but step 1 (contacts.find) never produces a result
+//! warning[unknown-daemon] daemons[1].name: Unknown daemon 'fax' - may not be available
+//! ```
+
+use colored::Colorize;
+use serde::Serialize;
+use std::fmt;
+
+/// How much a finding matters. Errors fail validation; warnings only do
+/// with `fgp skill validate --strict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// One finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// What the finding is about: a skill.yaml field such as
+    /// `daemons[0].name`, or a workflow step such as `step 2 (gmail.send)`
+    pub location: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(
+        rule: &'static str,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            rule,
+            severity: Severity::Error,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(
+        rule: &'static str,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            rule,
+            severity: Severity::Warning,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity.as_str(),
+            self.rule,
+            self.location,
+            self.message
+        )
+    }
+}
+
+/// Print findings one per line, with the label colored by severity.
+pub fn print(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let label = format!("{}[{}]", diagnostic.severity.as_str(), diagnostic.rule);
+        let label = match diagnostic.severity {
+            Severity::Error => label.red().bold(),
+            Severity::Warning => label.yellow().bold(),
+        };
+        println!("{} {}: {}", label, diagnostic.location, diagnostic.message);
+    }
+}
+
+/// How many findings are errors and how many are warnings.
+pub fn counts(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    (errors, diagnostics.len() - errors)
+}
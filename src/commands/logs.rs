@@ -2,19 +2,37 @@
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 
+use super::fgp_services_dir;
+
+/// Interval between checks for new log output when following.
+const FOLLOW_POLL_MS: u64 = 100;
+
 /// Get the log file path for a service.
-fn log_file_path(service: &str) -> PathBuf {
-    let base = shellexpand::tilde("~/.fgp/services");
-    PathBuf::from(base.as_ref())
-        .join(service)
-        .join("logs")
-        .join("daemon.log")
+///
+/// Uses the skill's `daemon.log_file` when configured, otherwise the first
+/// existing default location (`logs/daemon.log`, then `daemon.log`).
+pub(crate) fn log_file_path(service: &str) -> PathBuf {
+    if let Some(path) = super::skill::daemon_log_file(service) {
+        return path;
+    }
+
+    let service_dir = fgp_services_dir().join(service);
+    let candidates = [
+        service_dir.join("logs").join("daemon.log"),
+        service_dir.join("daemon.log"),
+    ];
+
+    candidates
+        .iter()
+        .find(|p| p.exists())
+        .unwrap_or(&candidates[0])
+        .clone()
 }
 
 /// Run the logs command.
@@ -29,17 +47,17 @@ pub fn run(service: &str, follow: bool, lines: usize) -> Result<()> {
         );
     }
 
+    tail_logs(&log_path, lines)?;
+
     if follow {
         follow_logs(&log_path)?;
-    } else {
-        tail_logs(&log_path, lines)?;
     }
 
     Ok(())
 }
 
 /// Display the last N lines of the log file.
-fn tail_logs(path: &PathBuf, lines: usize) -> Result<()> {
+fn tail_logs(path: &Path, lines: usize) -> Result<()> {
     let file = File::open(path).context("Failed to open log file")?;
     let reader = BufReader::new(file);
 
@@ -58,12 +76,16 @@ fn tail_logs(path: &PathBuf, lines: usize) -> Result<()> {
     Ok(())
 }
 
-/// Follow log output in real-time (like tail -f).
-fn follow_logs(path: &PathBuf) -> Result<()> {
+/// Follow log output in real-time (like tail -F).
+///
+/// If the file is truncated or replaced (log rotation), it is reopened and
+/// read from the start.
+fn follow_logs(path: &Path) -> Result<()> {
     let mut file = File::open(path).context("Failed to open log file")?;
 
     // Seek to end of file
-    file.seek(SeekFrom::End(0))?;
+    let mut position = file.seek(SeekFrom::End(0))?;
+    let mut identity = file_identity(path);
 
     println!(
         "{} Following logs... (press Ctrl+C to exit)",
@@ -77,12 +99,23 @@ fn follow_logs(path: &PathBuf) -> Result<()> {
         line.clear();
         match reader.read_line(&mut line) {
             Ok(0) => {
-                // No new data, wait and try again
-                thread::sleep(Duration::from_millis(100));
+                // No new data; check for rotation before waiting again
+                if let Some(reopened) = reopen_if_rotated(path, position, &mut identity) {
+                    println!("{} Log file rotated, reopening", "→".blue());
+                    reader = BufReader::new(reopened);
+                    position = 0;
+                    continue;
+                }
+                thread::sleep(Duration::from_millis(FOLLOW_POLL_MS));
             }
-            Ok(_) => {
-                // Got new data
-                print_log_line(line.trim_end());
+            Ok(n) => {
+                position += n as u64;
+                // Partial lines are completed on the next read
+                if line.ends_with('\n') {
+                    print_log_line(line.trim_end());
+                } else {
+                    print!("{}", line);
+                }
             }
             Err(e) => {
                 eprintln!("{} Read error: {}", "✗".red().bold(), e);
@@ -94,9 +127,35 @@ fn follow_logs(path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Identity of the file currently at `path` (device + inode on Unix).
+pub(crate) fn file_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+/// Reopen the log if it was replaced or truncated since we last read it.
+pub(crate) fn reopen_if_rotated(
+    path: &Path,
+    position: u64,
+    identity: &mut Option<(u64, u64)>,
+) -> Option<File> {
+    let metadata = fs::metadata(path).ok()?;
+    let current = file_identity(path);
+
+    let replaced = current != *identity;
+    let truncated = metadata.len() < position;
+    if !replaced && !truncated {
+        return None;
+    }
+
+    let file = File::open(path).ok()?;
+    *identity = current;
+    Some(file)
+}
+
 /// Detect log level from a line using case-insensitive search.
 /// Returns the detected level or None for INFO/unknown.
-fn detect_log_level(line: &str) -> Option<&'static str> {
+pub(crate) fn detect_log_level(line: &str) -> Option<&'static str> {
     // Use case-insensitive byte search to avoid allocation
     let bytes = line.as_bytes();
 
@@ -0,0 +1,133 @@
+//! Lightweight terminal rendering for markdown documents.
+//!
+//! Handles the subset skills actually use: headings, bold, inline code,
+//! fenced code blocks, lists, and YAML frontmatter (which is hidden).
+
+use colored::Colorize;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// Render markdown with terminal styling.
+pub fn render(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_code_block = false;
+    let mut lines = markdown.lines().peekable();
+
+    // Skip YAML frontmatter
+    if lines.peek().map(|l| l.trim() == "---").unwrap_or(false) {
+        lines.next();
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+        }
+    }
+
+    for line in lines {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            out.push_str(&format!("    {}\n", line.green()));
+            continue;
+        }
+
+        if let Some(heading) = trimmed.strip_prefix("# ") {
+            out.push_str(&format!("{}\n", heading.bold().underline().cyan()));
+        } else if let Some(heading) = trimmed.strip_prefix("## ") {
+            out.push_str(&format!("{}\n", heading.bold().cyan()));
+        } else if let Some(heading) = trimmed.strip_prefix("### ") {
+            out.push_str(&format!("{}\n", heading.bold()));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            out.push_str(&format!("{}  • {}\n", indent, render_inline(item)));
+        } else {
+            out.push_str(&render_inline(line));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Render inline `code` and **bold** spans.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let code = rest.find('`');
+        let bold = rest.find("**");
+
+        match (code, bold) {
+            (Some(c), b) if b.map(|b| c < b).unwrap_or(true) => {
+                if let Some(end) = rest[c + 1..].find('`') {
+                    out.push_str(&rest[..c]);
+                    out.push_str(&rest[c + 1..c + 1 + end].yellow().to_string());
+                    rest = &rest[c + end + 2..];
+                } else {
+                    out.push_str(rest);
+                    break;
+                }
+            }
+            (_, Some(b)) => {
+                if let Some(end) = rest[b + 2..].find("**") {
+                    out.push_str(&rest[..b]);
+                    out.push_str(&rest[b + 2..b + 2 + end].bold().to_string());
+                    rest = &rest[b + end + 4..];
+                } else {
+                    out.push_str(rest);
+                    break;
+                }
+            }
+            _ => {
+                out.push_str(rest);
+                break;
+            }
+        }
+    }
+
+    out
+}
+
+/// Print text, piping it through `$PAGER` when stdout is a terminal and the
+/// text is longer than one screen.
+pub fn page(text: &str) {
+    let stdout = std::io::stdout();
+    let screen_rows = crossterm::terminal::size()
+        .map(|(_, rows)| rows as usize)
+        .unwrap_or(usize::MAX);
+
+    if !stdout.is_terminal() || text.lines().count() < screen_rows {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+
+    let child = Command::new("sh")
+        .args(["-c", &pager])
+        .stdin(Stdio::piped())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // Ignore broken pipes if the user quits the pager early
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Err(_) => print!("{}", text),
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! Expose FGP daemons as MCP servers for compatibility with Claude Desktop,
 //! Cline, Continue, and other MCP-compatible tools.
+//!
+//! Methods a manifest marks destructive are listed with `destructiveHint` and
+//! only called as the guardrails policy allows; a denied call comes back as
+//! a tool error (`isError`) the agent can relay.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
@@ -9,6 +13,8 @@ use std::fs;
 use std::io::{self, BufRead, Write};
 
 // Use shared helpers from parent module
+use super::client::{self, Client};
+use super::guardrails::{self, Decision};
 use super::{fgp_services_dir, service_socket_path};
 
 /// Maximum retries when waiting for daemon to start.
@@ -32,7 +38,12 @@ fn is_valid_daemon_name(name: &str) -> bool {
 /// Start the MCP bridge in stdio mode.
 ///
 /// This runs an MCP server that translates MCP tool calls to FGP daemon calls.
-pub fn serve() -> Result<()> {
+/// With `group_by_daemon`, each daemon is listed as a single tool taking the
+/// method to call and its params, rather than one tool per method; calls in
+/// either form are understood whichever way the tools are listed. With
+/// `services`, only those daemons' tools are listed and called, so one
+/// client entry per daemon doesn't repeat every other daemon's tools.
+pub fn serve(group_by_daemon: bool, services: &[String]) -> Result<()> {
     // MCP uses JSON-RPC 2.0 over stdio
     let stdin = io::stdin();
     let mut stdout = io::stdout();
@@ -53,8 +64,8 @@ pub fn serve() -> Result<()> {
 
         let response = match method {
             "initialize" => handle_initialize(&request),
-            "tools/list" => handle_tools_list(id),
-            "tools/call" => handle_tools_call(&request),
+            "tools/list" => handle_tools_list(id, group_by_daemon, services),
+            "tools/call" => handle_tools_call(&request, services),
             _ => {
                 // Unknown method - return error
                 json_rpc_error(id, -32601, "Method not found")
@@ -106,62 +117,220 @@ fn decode_tool_name(tool_name: &str) -> Option<(String, String)> {
     Some((daemon, method))
 }
 
-/// Handle MCP tools/list request.
-fn handle_tools_list(id: Option<serde_json::Value>) -> String {
-    let mut tools = Vec::new();
+/// Whether `method` is a daemon's own plumbing rather than a tool.
+pub(crate) fn is_internal_method(method: &str) -> bool {
+    matches!(method, "health" | "stop" | "methods")
+}
 
-    // Scan installed daemons and collect their methods
-    let services_dir = fgp_services_dir();
-    if services_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&services_dir) {
-            for entry in entries.flatten() {
-                let name = entry.file_name().to_string_lossy().to_string();
-                let socket = service_socket_path(&name);
+/// Methods of every running daemon, by daemon name, without the internal ones.
+fn running_daemon_methods() -> Vec<(String, Vec<serde_json::Value>)> {
+    let mut daemons = Vec::new();
+    let Ok(entries) = fs::read_dir(fgp_services_dir()) else {
+        return daemons;
+    };
 
-                if socket.exists() {
-                    // Try to get methods from this daemon
-                    if let Ok(client) = fgp_daemon::FgpClient::new(&socket) {
-                        if let Ok(response) = client.methods() {
-                            if response.ok {
-                                if let Some(result) = response.result {
-                                    if let Some(methods) = result["methods"].as_array() {
-                                        for method in methods {
-                                            let method_name =
-                                                method["name"].as_str().unwrap_or("unknown");
-                                            let description = method["description"]
-                                                .as_str()
-                                                .unwrap_or("No description");
-
-                                            // Skip internal methods
-                                            if method_name == "health"
-                                                || method_name == "stop"
-                                                || method_name == "methods"
-                                            {
-                                                continue;
-                                            }
-
-                                            // Build input schema from method params
-                                            let input_schema = method
-                                                .get("params")
-                                                .cloned()
-                                                .unwrap_or(serde_json::json!({
-                                                    "type": "object",
-                                                    "properties": {}
-                                                }));
-
-                                            tools.push(serde_json::json!({
-                                                "name": encode_tool_name(&name, method_name),
-                                                "description": format!("[FGP:{}] {}", name, description),
-                                                "inputSchema": input_schema
-                                            }));
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let socket = service_socket_path(&name);
+        if !socket.exists() {
+            continue;
+        }
+
+        let Ok(response) = Client::at(&socket).methods() else {
+            continue;
+        };
+        if !response.ok {
+            continue;
+        }
+        let methods: Vec<serde_json::Value> = response
+            .result
+            .and_then(|result| result["methods"].as_array().cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|method| !is_internal_method(method["name"].as_str().unwrap_or("unknown")))
+            .collect();
+        daemons.push((name, methods));
+    }
+
+    daemons.sort_by(|a, b| a.0.cmp(&b.0));
+    daemons
+}
+
+/// One MCP tool per method of `daemon`.
+fn method_tools(daemon: &str, methods: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let declared = guardrails::declared_methods(daemon);
+
+    methods
+        .iter()
+        .map(|method| {
+            let method_name = method["name"].as_str().unwrap_or("unknown");
+            let description = method["description"].as_str().unwrap_or("No description");
+
+            // Build input schema from method params
+            let input_schema = method.get("params").cloned().unwrap_or(serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }));
+
+            let mut tool = serde_json::json!({
+                "name": encode_tool_name(daemon, method_name),
+                "description": format!("[FGP:{}] {}", daemon, description),
+                "inputSchema": input_schema
+            });
+            if guardrails::find_destructive(&declared, daemon, method_name).is_some() {
+                tool["annotations"] = serde_json::json!({
+                    "destructiveHint": true
+                });
+            }
+            tool
+        })
+        .collect()
+}
+
+/// A method offered through a grouped daemon tool.
+pub struct GroupedMethod<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    /// JSON schema of the method's params, if known
+    pub params: Option<&'a serde_json::Value>,
+    pub destructive: bool,
+}
+
+/// One MCP tool standing in for all of `daemon`'s methods: it takes the
+/// method's name as `method` and its arguments as `params`, and its
+/// description lists the methods with their params.
+pub fn grouped_tool(tool_name: &str, daemon: &str, methods: &[GroupedMethod]) -> serde_json::Value {
+    let mut description = format!(
+        "[FGP:{}] Call a method of the {} daemon. Pass the method name as `method` and its arguments as `params`.\n\nMethods:",
+        daemon, daemon
+    );
+    for method in methods {
+        description.push_str(&format!("\n- {}", method.name));
+        if method.destructive {
+            description.push_str(" (destructive)");
+        }
+        if let Some(text) = method.description {
+            description.push_str(&format!(": {}", text));
+        }
+        let params = param_summary(method.params);
+        if !params.is_empty() {
+            description.push_str(&format!("\n  Params: {}", params));
+        }
+    }
+
+    let mut tool = serde_json::json!({
+        "name": tool_name,
+        "description": description,
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "method": {
+                    "type": "string",
+                    "enum": methods.iter().map(|m| m.name).collect::<Vec<_>>(),
+                    "description": "Method to call"
+                },
+                "params": {
+                    "type": "object",
+                    "description": "The method's params, as listed in the tool description"
                 }
+            },
+            "required": ["method"]
+        }
+    });
+    if methods.iter().any(|m| m.destructive) {
+        tool["annotations"] = serde_json::json!({
+            "destructiveHint": true
+        });
+    }
+    tool
+}
+
+/// "a (string, required), b (integer)" for a params JSON schema.
+fn param_summary(schema: Option<&serde_json::Value>) -> String {
+    let Some(properties) = schema
+        .and_then(|s| s.get("properties"))
+        .and_then(|p| p.as_object())
+    else {
+        return String::new();
+    };
+    let required: Vec<&str> = schema
+        .and_then(|s| s.get("required"))
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let kind = property["type"].as_str().unwrap_or("any");
+            if required.contains(&name.as_str()) {
+                format!("{} ({}, required)", name, kind)
+            } else {
+                format!("{} ({})", name, kind)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The grouped tool for a running daemon's methods.
+fn daemon_tool(daemon: &str, methods: &[serde_json::Value]) -> serde_json::Value {
+    let declared = guardrails::declared_methods(daemon);
+    let grouped: Vec<GroupedMethod> = methods
+        .iter()
+        .map(|method| {
+            let name = method["name"].as_str().unwrap_or("unknown");
+            GroupedMethod {
+                name,
+                description: method["description"].as_str(),
+                params: method.get("params"),
+                destructive: guardrails::find_destructive(&declared, daemon, name).is_some(),
+            }
+        })
+        .collect();
+    grouped_tool(&format!("fgp__{}", daemon), daemon, &grouped)
+}
+
+/// A warning when `count` tools on one MCP server is more than some clients
+/// handle well, per the `[mcp.tool_limits]` config.
+pub fn tool_count_warning(count: usize) -> Option<String> {
+    let exceeded = crate::config::load()
+        .unwrap_or_default()
+        .mcp
+        .exceeded_limits(count);
+    if exceeded.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} tools is more than some MCP clients handle well: {}",
+        count,
+        exceeded.join(", ")
+    ))
+}
+
+/// Whether a bridge limited to `services` (none: all) serves `daemon`.
+fn serves(services: &[String], daemon: &str) -> bool {
+    services.is_empty() || services.iter().any(|service| service == daemon)
+}
+
+/// Handle MCP tools/list request.
+fn handle_tools_list(
+    id: Option<serde_json::Value>,
+    group_by_daemon: bool,
+    services: &[String],
+) -> String {
+    let mut tools = Vec::new();
+
+    for (daemon, methods) in running_daemon_methods() {
+        if !serves(services, &daemon) {
+            continue;
+        }
+        if group_by_daemon {
+            if !methods.is_empty() {
+                tools.push(daemon_tool(&daemon, &methods));
             }
+        } else {
+            tools.extend(method_tools(&daemon, &methods));
         }
     }
 
@@ -205,6 +374,16 @@ fn handle_tools_list(id: Option<serde_json::Value>) -> String {
         }
     }));
 
+    // stdout carries the protocol, so the client's log gets the warning
+    if !group_by_daemon {
+        if let Some(warning) = tool_count_warning(tools.len()) {
+            eprintln!(
+                "fgp mcp serve: {}; run with --group-by-daemon to list one tool per daemon",
+                warning
+            );
+        }
+    }
+
     let result = serde_json::json!({
         "tools": tools
     });
@@ -212,8 +391,45 @@ fn handle_tools_list(id: Option<serde_json::Value>) -> String {
     json_rpc_response(id, result)
 }
 
+/// The daemon, method and params a tools/call asks for.
+///
+/// A per-method tool (`fgp__<daemon>__<method>`) takes the method's params
+/// as its arguments; a grouped daemon tool (`fgp__<daemon>`) takes
+/// `{"method": ..., "params": {...}}`.
+fn resolve_call(
+    tool_name: &str,
+    arguments: serde_json::Value,
+) -> std::result::Result<(String, String, serde_json::Value), String> {
+    if let Some((daemon, method)) = decode_tool_name(tool_name) {
+        return Ok((daemon, method, arguments));
+    }
+
+    let daemon = tool_name
+        .strip_prefix("fgp__")
+        .filter(|rest| !rest.contains("__"))
+        .ok_or_else(|| {
+            "Invalid tool name format. Expected fgp__<daemon>__<method> or fgp__<daemon>"
+                .to_string()
+        })?;
+    let method = arguments["method"]
+        .as_str()
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "{} needs the name of the method to call as `method`",
+                tool_name
+            )
+        })?;
+    let params = match arguments.get("params") {
+        None | Some(serde_json::Value::Null) => serde_json::json!({}),
+        Some(params) if params.is_object() => params.clone(),
+        Some(_) => return Err("`params` must be an object".to_string()),
+    };
+    Ok((daemon.to_string(), method.to_string(), params))
+}
+
 /// Handle MCP tools/call request.
-fn handle_tools_call(request: &serde_json::Value) -> String {
+fn handle_tools_call(request: &serde_json::Value, services: &[String]) -> String {
     let id = request.get("id").cloned();
     let params = &request["params"];
     let tool_name = params["name"].as_str().unwrap_or("");
@@ -234,28 +450,52 @@ fn handle_tools_call(request: &serde_json::Value) -> String {
     }
 
     // Parse tool name to extract daemon and method
-    let (daemon, method) = match decode_tool_name(tool_name) {
-        Some((d, m)) => (d, m),
-        None => {
-            return json_rpc_error(
-                id,
-                -32602,
-                "Invalid tool name format. Expected fgp__<daemon>__<method>",
-            )
-        }
+    let (daemon, method, arguments) = match resolve_call(tool_name, arguments) {
+        Ok(call) => call,
+        Err(message) => return json_rpc_error(id, -32602, &message),
     };
 
     // Validate daemon name to prevent path traversal
     if !is_valid_daemon_name(&daemon) {
         return json_rpc_error(id, -32602, "Invalid daemon name");
     }
+    if !serves(services, &daemon) {
+        return json_rpc_error(
+            id,
+            -32602,
+            &format!("{} is not served by this MCP server", daemon),
+        );
+    }
+
+    // Nobody can confirm a destructive method here, so the guardrails
+    // policy decides
+    let declared = guardrails::declared_methods(&daemon);
+    let mut preview = false;
+    let arguments = match guardrails::find_destructive(&declared, &daemon, &method) {
+        None => arguments,
+        Some(definition) => match guardrails::check(&daemon, definition) {
+            Ok(Decision::Proceed) => arguments,
+            Ok(Decision::Preview) => {
+                preview = true;
+                guardrails::with_dry_run(arguments)
+            }
+            Ok(Decision::Deny(reason)) => return denied_tool_result(id, &daemon, &method, &reason),
+            Err(e) => {
+                return json_rpc_error(
+                    id,
+                    -32603,
+                    &format!("Failed to read guardrails policy: {:#}", e),
+                )
+            }
+        },
+    };
 
     // Call the daemon
     let socket = service_socket_path(&daemon);
 
     // Auto-start if needed with health polling
     if !socket.exists() {
-        if let Err(e) = fgp_daemon::lifecycle::start_service(&daemon) {
+        if let Err(e) = super::daemon_command::start_service(&daemon) {
             return json_rpc_error(id, -32603, &format!("Failed to start daemon: {}", e));
         }
         // Poll for daemon readiness instead of fixed sleep
@@ -264,28 +504,63 @@ fn handle_tools_call(request: &serde_json::Value) -> String {
         }
     }
 
-    match fgp_daemon::FgpClient::new(&socket) {
-        Ok(client) => match client.call(&method, arguments) {
-            Ok(response) if response.ok => {
-                let result = serde_json::json!({
-                    "content": [{
-                        "type": "text",
-                        "text": serde_json::to_string_pretty(&response.result).unwrap_or_default()
-                    }]
-                });
-                json_rpc_response(id, result)
+    match Client::at(&socket).call(&method, arguments) {
+        Ok(response) if response.ok => {
+            let mut text = serde_json::to_string_pretty(&response.result).unwrap_or_default();
+            if preview {
+                text = format!(
+                    "Dry run only: {} is destructive and the guardrails policy allows previews, so nothing was changed.\n{}",
+                    method, text
+                );
             }
-            Ok(response) => {
-                let error_msg = response
-                    .error
-                    .map(|e| e.message)
-                    .unwrap_or_else(|| "Unknown error".to_string());
-                json_rpc_error(id, -32603, &error_msg)
+            let result = serde_json::json!({
+                "content": [{
+                    "type": "text",
+                    "text": text
+                }]
+            });
+            json_rpc_response(id, result)
+        }
+        Ok(response) => {
+            let error_msg = response
+                .error
+                .map(|e| e.message)
+                .unwrap_or_else(|| "Unknown error".to_string());
+            json_rpc_error(id, -32603, &error_msg)
+        }
+        Err(client::Error::Connect { source, .. }) => json_rpc_error(
+            id,
+            -32603,
+            &format!("Failed to connect to daemon: {}", source),
+        ),
+        Err(e) => json_rpc_error(id, -32603, &format!("Call failed: {}", e)),
+    }
+}
+
+/// A tool error for a call the guardrails policy refused, with the details
+/// in `structuredContent` so the agent can tell the user how to approve it.
+fn denied_tool_result(
+    id: Option<serde_json::Value>,
+    daemon: &str,
+    method: &str,
+    reason: &str,
+) -> String {
+    let result = serde_json::json!({
+        "content": [{
+            "type": "text",
+            "text": format!("Call not made: {}", reason)
+        }],
+        "structuredContent": {
+            "error": {
+                "code": "destructive_method_denied",
+                "daemon": daemon,
+                "method": method,
+                "message": reason
             }
-            Err(e) => json_rpc_error(id, -32603, &format!("Call failed: {}", e)),
         },
-        Err(e) => json_rpc_error(id, -32603, &format!("Failed to connect to daemon: {}", e)),
-    }
+        "isError": true
+    });
+    json_rpc_response(id, result)
 }
 
 /// Wait for a daemon to become ready by polling its health endpoint.
@@ -293,12 +568,8 @@ fn wait_for_daemon_ready(daemon: &str) -> bool {
     let socket = service_socket_path(daemon);
     for _ in 0..MAX_START_RETRIES {
         std::thread::sleep(std::time::Duration::from_millis(RETRY_DELAY_MS));
-        if socket.exists() {
-            if let Ok(client) = fgp_daemon::FgpClient::new(&socket) {
-                if client.health().is_ok() {
-                    return true;
-                }
-            }
+        if socket.exists() && Client::at(&socket).health().is_ok() {
+            return true;
         }
     }
     false
@@ -316,12 +587,8 @@ fn handle_list_daemons(id: Option<serde_json::Value>) -> String {
                 let socket = service_socket_path(&name);
 
                 let status = if socket.exists() {
-                    if let Ok(client) = fgp_daemon::FgpClient::new(&socket) {
-                        if client.health().is_ok() {
-                            "running"
-                        } else {
-                            "error"
-                        }
+                    if Client::at(&socket).health().is_ok() {
+                        "running"
                     } else {
                         "error"
                     }
@@ -353,7 +620,7 @@ fn handle_start_daemon(id: Option<serde_json::Value>, name: &str) -> String {
         return json_rpc_error(id, -32602, "Invalid daemon name");
     }
 
-    match fgp_daemon::lifecycle::start_service(name) {
+    match super::daemon_command::start_service(name) {
         Ok(()) => {
             let result = serde_json::json!({
                 "content": [{
@@ -373,7 +640,7 @@ fn handle_stop_daemon(id: Option<serde_json::Value>, name: &str) -> String {
         return json_rpc_error(id, -32602, "Invalid daemon name");
     }
 
-    match fgp_daemon::lifecycle::stop_service(name) {
+    match super::daemon_command::stop_service(name) {
         Ok(()) => {
             let result = serde_json::json!({
                 "content": [{
@@ -411,12 +678,16 @@ fn json_rpc_error(id: Option<serde_json::Value>, code: i32, message: &str) -> St
 }
 
 /// Register FGP with Claude Code.
-pub fn install() -> Result<()> {
+pub fn install(group_by_daemon: bool) -> Result<()> {
     println!("{} Registering FGP with Claude Code...", "→".blue().bold());
 
-    // Run: claude mcp add fgp -- fgp mcp serve
+    // Run: claude mcp add fgp -- fgp mcp serve [--group-by-daemon]
+    let mut args = vec!["mcp", "add", "fgp", "--", "fgp", "mcp", "serve"];
+    if group_by_daemon {
+        args.push("--group-by-daemon");
+    }
     let status = std::process::Command::new("claude")
-        .args(["mcp", "add", "fgp", "--", "fgp", "mcp", "serve"])
+        .args(&args)
         .status()
         .context("Failed to run 'claude mcp add'. Is Claude Code installed?")?;
 
@@ -458,41 +729,36 @@ pub fn tools() -> Result<()> {
             continue;
         }
 
-        match fgp_daemon::FgpClient::new(&socket) {
-            Ok(client) => match client.methods() {
-                Ok(response) if response.ok => {
-                    if let Some(result) = response.result {
-                        if let Some(methods) = result["methods"].as_array() {
-                            for method in methods {
-                                let method_name = method["name"].as_str().unwrap_or("unknown");
-                                let description =
-                                    method["description"].as_str().unwrap_or("No description");
-
-                                // Skip internal methods
-                                if method_name == "health"
-                                    || method_name == "stop"
-                                    || method_name == "methods"
-                                {
-                                    continue;
-                                }
-
-                                println!(
-                                    "  {} - {}",
-                                    encode_tool_name(&name, method_name).green(),
-                                    description.dimmed()
-                                );
-                                total_tools += 1;
+        match Client::at(&socket).methods() {
+            Ok(response) if response.ok => {
+                if let Some(result) = response.result {
+                    if let Some(methods) = result["methods"].as_array() {
+                        for method in methods {
+                            let method_name = method["name"].as_str().unwrap_or("unknown");
+                            let description =
+                                method["description"].as_str().unwrap_or("No description");
+
+                            // Skip internal methods
+                            if is_internal_method(method_name) {
+                                continue;
                             }
+
+                            println!(
+                                "  {} - {}",
+                                encode_tool_name(&name, method_name).green(),
+                                description.dimmed()
+                            );
+                            total_tools += 1;
                         }
                     }
                 }
-                _ => {
-                    println!("  {} Error fetching methods", "✗".red());
-                }
-            },
-            Err(_) => {
+            }
+            Err(client::Error::Connect { .. }) => {
                 println!("  {} Connection error", "✗".red());
             }
+            _ => {
+                println!("  {} Error fetching methods", "✗".red());
+            }
         }
 
         println!();
@@ -510,5 +776,109 @@ pub fn tools() -> Result<()> {
     println!();
     println!("Total: {} tools available", total_tools + 3);
 
+    if let Some(warning) = tool_count_warning(total_tools + 3) {
+        println!();
+        println!("{} {}", "!".yellow().bold(), warning);
+        println!(
+            "  Register the bridge with {} to list one tool per daemon.",
+            "fgp mcp install --group-by-daemon".cyan()
+        );
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_call() {
+        let params = serde_json::json!({"limit": 5});
+        assert_eq!(
+            resolve_call("fgp__gmail__list", params.clone()).unwrap(),
+            ("gmail".to_string(), "list".to_string(), params.clone())
+        );
+
+        // Grouped form
+        let arguments = serde_json::json!({"method": "gmail.list", "params": {"limit": 5}});
+        assert_eq!(
+            resolve_call("fgp__gmail", arguments).unwrap(),
+            ("gmail".to_string(), "gmail.list".to_string(), params)
+        );
+        let arguments = serde_json::json!({"method": "my_svc.ping"});
+        assert_eq!(
+            resolve_call("fgp__my_svc", arguments).unwrap().2,
+            serde_json::json!({})
+        );
+
+        assert!(resolve_call("fgp__gmail", serde_json::json!({}))
+            .unwrap_err()
+            .contains("`method`"));
+        assert!(resolve_call(
+            "fgp__gmail",
+            serde_json::json!({"method": "gmail.list", "params": [5]})
+        )
+        .is_err());
+        assert!(resolve_call("gmail_list", serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_serves() {
+        assert!(serves(&[], "gmail"));
+        let services = ["gmail".to_string()];
+        assert!(serves(&services, "gmail"));
+        assert!(!serves(&services, "calendar"));
+
+        let request = serde_json::json!({
+            "id": 1,
+            "params": {"name": "fgp__calendar__list_events", "arguments": {}}
+        });
+        let response = handle_tools_call(&request, &services);
+        assert!(
+            response.contains("calendar is not served by this MCP server"),
+            "{}",
+            response
+        );
+    }
+
+    #[test]
+    fn test_grouped_tool() {
+        let send_params = serde_json::json!({
+            "type": "object",
+            "properties": {"to": {"type": "string"}, "cc": {"type": "string"}},
+            "required": ["to"]
+        });
+        let methods = [
+            GroupedMethod {
+                name: "gmail.list",
+                description: Some("List messages"),
+                params: None,
+                destructive: false,
+            },
+            GroupedMethod {
+                name: "gmail.send",
+                description: Some("Send a message"),
+                params: Some(&send_params),
+                destructive: true,
+            },
+        ];
+        let tool = grouped_tool("fgp__gmail", "gmail", &methods);
+
+        assert_eq!(tool["name"], "fgp__gmail");
+        assert_eq!(tool["annotations"]["destructiveHint"], true);
+        assert_eq!(
+            tool["inputSchema"]["properties"]["method"]["enum"],
+            serde_json::json!(["gmail.list", "gmail.send"])
+        );
+        assert_eq!(
+            tool["inputSchema"]["required"],
+            serde_json::json!(["method"])
+        );
+        let description = tool["description"].as_str().unwrap();
+        assert!(description.contains("\n- gmail.list: List messages\n"));
+        assert!(description.ends_with(
+            "- gmail.send (destructive): Send a message\n  Params: cc (string), to (string, required)"
+        ));
+    }
+}
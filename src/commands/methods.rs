@@ -1,59 +1,417 @@
-//! List available methods for a service.
+//! List available methods for a service, with their params.
+//!
+//! Method definitions come from the running daemon's `methods` response,
+//! which may describe params either as a list (the manifest.json form) or as
+//! a JSON Schema object. Anything the daemon leaves out is filled in from the
+//! service's installed manifest.json, which is also used on its own when the
+//! daemon isn't running.
+//!
+//! Methods marked `destructive` are flagged, since `fgp call` and the MCP
+//! bridge hold calls to them for confirmation (see `guardrails`).
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use serde_json::Value;
+use std::path::PathBuf;
 use tabled::{Table, Tabled};
 
-use super::service_socket_path;
+use super::client::{self, Client};
+use super::fgp_services_dir;
+use super::skill_import::{ManifestMethod, ManifestParam};
+
+/// Where the method definitions were read from.
+enum Source {
+    /// The running daemon, plus the manifest if one filled in gaps
+    Daemon {
+        manifest: Option<PathBuf>,
+    },
+    Manifest(PathBuf),
+}
 
 #[derive(Tabled)]
-struct MethodInfo {
-    #[tabled(rename = "Method")]
+struct ParamRow {
+    #[tabled(rename = "Param")]
     name: String,
+    #[tabled(rename = "Type")]
+    param_type: String,
+    #[tabled(rename = "Required")]
+    required: String,
+    #[tabled(rename = "Default")]
+    default: String,
     #[tabled(rename = "Description")]
     description: String,
 }
 
-pub fn run(service: &str) -> Result<()> {
-    let socket_path = service_socket_path(service);
+impl From<&ManifestParam> for ParamRow {
+    fn from(param: &ManifestParam) -> Self {
+        Self {
+            name: param.name.clone(),
+            param_type: param.param_type.as_deref().unwrap_or("any").to_string(),
+            required: if param.required { "yes" } else { "no" }.to_string(),
+            default: param
+                .default
+                .as_ref()
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            description: param.description.clone().unwrap_or_default(),
+        }
+    }
+}
+
+pub fn run(service: &str, method: Option<&str>, json: bool) -> Result<()> {
+    let (methods, source) = load_methods(service)?;
+
+    let methods = match method {
+        Some(name) => match find_method(&methods, service, name) {
+            Some(found) => vec![found.clone()],
+            None => bail!(
+                "Service '{}' has no method '{}'. Run 'fgp methods {}' to list them.",
+                service,
+                name,
+                service
+            ),
+        },
+        None => methods,
+    };
+
+    if json {
+        let output = match (method, methods.first()) {
+            (Some(_), Some(single)) => serde_json::to_string_pretty(single)?,
+            _ => serde_json::to_string_pretty(&methods)?,
+        };
+        println!("{}", output);
+        return Ok(());
+    }
+
+    println!("{} methods:", service.bold());
+    let origin = match source {
+        Source::Daemon { manifest: None } => "(reported by the running daemon)".to_string(),
+        Source::Daemon {
+            manifest: Some(path),
+        } => format!(
+            "(reported by the running daemon, details from {})",
+            path.display()
+        ),
+        Source::Manifest(path) => format!("(from {})", path.display()),
+    };
+    println!("{}", origin.dimmed());
+    println!();
+
+    if methods.is_empty() {
+        println!("  The daemon reported no methods.");
+        return Ok(());
+    }
+
+    for (i, method) in methods.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        if method.destructive {
+            let dry_run = if method.supports_dry_run {
+                " (supports dry run)"
+            } else {
+                ""
+            };
+            println!(
+                "  {} {}{}",
+                method.name.bold(),
+                "destructive".red(),
+                dry_run.dimmed()
+            );
+        } else {
+            println!("  {}", method.name.bold());
+        }
+        if let Some(ref description) = method.description {
+            println!("    {}", description);
+        }
+        if method.params.is_empty() {
+            println!("    {}", "No params".dimmed());
+            continue;
+        }
+        let rows: Vec<ParamRow> = method.params.iter().map(ParamRow::from).collect();
+        for line in Table::new(rows).to_string().lines() {
+            println!("    {}", line);
+        }
+    }
 
-    if !socket_path.exists() {
-        bail!(
-            "Service '{}' is not running. Run 'fgp start {}' first.",
+    Ok(())
+}
+
+/// Method definitions for `service`, wherever they were read from.
+pub(crate) fn service_methods(service: &str) -> Result<Vec<ManifestMethod>> {
+    load_methods(service).map(|(methods, _)| methods)
+}
+
+/// Method definitions from the daemon, completed from manifest.json, or
+/// from manifest.json alone when the daemon isn't running.
+fn load_methods(service: &str) -> Result<(Vec<ManifestMethod>, Source)> {
+    let manifest_path = fgp_services_dir().join(service).join("manifest.json");
+    let manifest = if manifest_path.exists() {
+        Some(
+            super::skill_import::load_manifest(&manifest_path)
+                .map_err(|e| anyhow::anyhow!("Invalid manifest {}", e))?,
+        )
+    } else {
+        None
+    };
+
+    let daemon = daemon_methods(service);
+
+    if let Ok(Some(mut methods)) = daemon {
+        return Ok(match manifest {
+            None => (methods, Source::Daemon { manifest: None }),
+            // A daemon that reports nothing is less useful than its manifest
+            Some(manifest) if methods.is_empty() => {
+                (manifest.methods, Source::Manifest(manifest_path))
+            }
+            Some(manifest) => {
+                fill_from_manifest(&mut methods, &manifest.methods, service);
+                let source = Source::Daemon {
+                    manifest: Some(manifest_path),
+                };
+                (methods, source)
+            }
+        });
+    }
+
+    match (daemon, manifest) {
+        (_, Some(manifest)) => Ok((manifest.methods, Source::Manifest(manifest_path))),
+        (Err(e), None) => bail!(
+            "No method information for '{}': the daemon did not answer ({:#}) and there is no manifest at {}.",
             service,
+            e,
+            manifest_path.display()
+        ),
+        (_, None) => bail!(
+            "No method information for '{}': the daemon is not running and there is no manifest at {}. Run 'fgp start {}' first.",
+            service,
+            manifest_path.display(),
             service
-        );
+        ),
     }
+}
 
-    let client = fgp_daemon::FgpClient::new(&socket_path).context("Failed to connect to daemon")?;
-
+/// Ask the running daemon for its methods. `None` if it isn't running.
+fn daemon_methods(service: &str) -> Result<Option<Vec<ManifestMethod>>> {
+    let client = match Client::connect(service) {
+        Ok(client) => client,
+        Err(client::Error::NotRunning { .. }) => return Ok(None),
+        Err(e) => return Err(e).context("Failed to connect to daemon"),
+    };
     let response = client.methods().context("Failed to get methods")?;
 
-    if !response.ok {
-        let error = response.error.unwrap_or_default();
+    if let Some(error) = response.error() {
         bail!("Error ({}): {}", error.code, error.message);
     }
 
     let result = response.result.unwrap_or_default();
-    let methods_array = result["methods"].as_array().cloned().unwrap_or_default();
+    Ok(Some(
+        result["methods"]
+            .as_array()
+            .map(|methods| methods.iter().map(parse_daemon_method).collect())
+            .unwrap_or_default(),
+    ))
+}
 
-    println!("{} methods:", service.bold());
-    println!();
+/// Read one entry of a `methods` response. Params may be a manifest-style
+/// list or a JSON Schema object; anything else is ignored.
+fn parse_daemon_method(method: &Value) -> ManifestMethod {
+    let params = match &method["params"] {
+        Value::Array(_) => serde_json::from_value(method["params"].clone()).unwrap_or_default(),
+        Value::Object(schema) if schema.contains_key("properties") => {
+            super::skill_import::params_from_schema(schema)
+        }
+        _ => Vec::new(),
+    };
+
+    ManifestMethod {
+        name: method["name"].as_str().unwrap_or("?").to_string(),
+        description: method["description"].as_str().map(|s| s.to_string()),
+        params,
+        destructive: method["destructive"].as_bool().unwrap_or(false),
+        supports_dry_run: method["supports_dry_run"].as_bool().unwrap_or(false),
+        cost: method["cost"].as_str().map(|s| s.to_string()),
+        rate_limit: method["rate_limit"].as_str().map(|s| s.to_string()),
+    }
+}
+
+/// Fill in descriptions and params the daemon didn't report. A method is
+/// destructive if either the daemon or the manifest says so.
+fn fill_from_manifest(methods: &mut [ManifestMethod], manifest: &[ManifestMethod], service: &str) {
+    for method in methods {
+        let Some(declared) = find_method(manifest, service, &method.name) else {
+            continue;
+        };
+        if method.description.is_none() {
+            method.description = declared.description.clone();
+        }
+        if method.params.is_empty() {
+            method.params = declared.params.clone();
+        }
+        method.destructive |= declared.destructive;
+        method.supports_dry_run |= declared.supports_dry_run;
+    }
+}
 
-    let methods: Vec<MethodInfo> = methods_array
+/// Find a method by its full (`gmail.send`) or short (`send`) name.
+pub(crate) fn find_method<'a>(
+    methods: &'a [ManifestMethod],
+    service: &str,
+    name: &str,
+) -> Option<&'a ManifestMethod> {
+    let short = name
+        .strip_prefix(service)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .unwrap_or(name);
+    methods
         .iter()
-        .map(|m| MethodInfo {
-            name: m["name"].as_str().unwrap_or("?").to_string(),
-            description: m["description"].as_str().unwrap_or("").to_string(),
-        })
-        .collect();
+        .find(|m| m.name == name || m.name == short || m.name == format!("{}.{}", service, short))
+}
 
-    if methods.is_empty() {
-        println!("  No methods available.");
-    } else {
-        let table = Table::new(&methods).to_string();
-        println!("{}", table);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str) -> ManifestMethod {
+        ManifestMethod {
+            name: name.to_string(),
+            description: None,
+            params: Vec::new(),
+            destructive: false,
+            supports_dry_run: false,
+            cost: None,
+            rate_limit: None,
+        }
     }
 
-    Ok(())
+    #[test]
+    fn test_find_method_by_full_or_short_name() {
+        let qualified = [method("gmail.send"), method("gmail.list")];
+        assert_eq!(
+            find_method(&qualified, "gmail", "send").unwrap().name,
+            "gmail.send"
+        );
+        assert_eq!(
+            find_method(&qualified, "gmail", "gmail.list").unwrap().name,
+            "gmail.list"
+        );
+
+        let short = [method("send")];
+        assert_eq!(
+            find_method(&short, "gmail", "gmail.send").unwrap().name,
+            "send"
+        );
+        assert!(find_method(&short, "gmail", "gmail.delete").is_none());
+    }
+
+    #[test]
+    fn test_param_row() {
+        let param = ManifestParam {
+            name: "limit".to_string(),
+            param_type: Some("integer".to_string()),
+            required: false,
+            default: Some(serde_json::json!(10)),
+            description: Some("Max results".to_string()),
+            items: None,
+            properties: None,
+            schema: None,
+        };
+        let row = ParamRow::from(&param);
+        assert_eq!(
+            (
+                row.param_type.as_str(),
+                row.required.as_str(),
+                row.default.as_str()
+            ),
+            ("integer", "no", "10")
+        );
+        assert_eq!(row.description, "Max results");
+
+        let param = ManifestParam {
+            name: "to".to_string(),
+            param_type: None,
+            required: true,
+            default: None,
+            description: None,
+            items: None,
+            properties: None,
+            schema: None,
+        };
+        let row = ParamRow::from(&param);
+        assert_eq!(
+            (
+                row.param_type.as_str(),
+                row.required.as_str(),
+                row.default.as_str()
+            ),
+            ("any", "yes", "-")
+        );
+    }
+
+    #[test]
+    fn test_daemon_methods_are_completed_from_manifest() {
+        let reported = serde_json::json!([
+            {"name": "gmail.send", "params": {
+                "type": "object",
+                "properties": {
+                    "to": {"type": "string", "description": "Recipient"},
+                    "cc": {"type": "array", "default": []},
+                },
+                "required": ["to"],
+            }},
+            {"name": "gmail.list", "params": [{"name": "limit", "type": "integer"}]},
+            {"name": "gmail.unread"},
+        ]);
+        let mut methods: Vec<ManifestMethod> = reported
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(parse_daemon_method)
+            .collect();
+
+        let send = &methods[0];
+        let to = send.params.iter().find(|p| p.name == "to").unwrap();
+        assert!(to.required);
+        assert_eq!(to.description.as_deref(), Some("Recipient"));
+        let cc = send.params.iter().find(|p| p.name == "cc").unwrap();
+        assert!(!cc.required);
+        assert_eq!(cc.default, Some(serde_json::json!([])));
+        assert_eq!(methods[1].params[0].param_type.as_deref(), Some("integer"));
+        assert!(methods[2].params.is_empty());
+
+        let mut unread = method("unread");
+        unread.description = Some("Unread messages".to_string());
+        unread.params = vec![ManifestParam {
+            name: "limit".to_string(),
+            param_type: Some("integer".to_string()),
+            required: false,
+            default: None,
+            description: None,
+            items: None,
+            properties: None,
+            schema: None,
+        }];
+        fill_from_manifest(&mut methods, &[unread], "gmail");
+        assert_eq!(methods[2].description.as_deref(), Some("Unread messages"));
+        assert_eq!(methods[2].params.len(), 1);
+        // Params the daemon reported win over the manifest
+        assert_eq!(methods[1].params.len(), 1);
+    }
+
+    #[test]
+    fn test_destructive_flag() {
+        let reported = serde_json::json!({"name": "gmail.send", "destructive": true});
+        let mut methods = vec![parse_daemon_method(&reported), method("gmail.trash")];
+        assert!(methods[0].destructive);
+        assert!(!methods[0].supports_dry_run);
+
+        let mut trash = method("trash");
+        trash.destructive = true;
+        trash.supports_dry_run = true;
+        fill_from_manifest(&mut methods, &[trash], "gmail");
+        assert!(methods[1].destructive && methods[1].supports_dry_run);
+        // The manifest can't clear a flag the daemon reported
+        fill_from_manifest(&mut methods, &[method("send")], "gmail");
+        assert!(methods[0].destructive);
+    }
 }
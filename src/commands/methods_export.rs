@@ -0,0 +1,408 @@
+//! Function-calling tool definitions for daemon methods.
+//!
+//! `fgp methods <service> --export openai|anthropic` (or `--all` for every
+//! installed service) turns method definitions into the tool shapes the
+//! model APIs accept: OpenAI's `tools` array of functions with a JSON Schema
+//! `parameters`, or Anthropic's tools with an `input_schema`.
+//!
+//! Neither API accepts dots in tool names and both cap their length, so each
+//! method gets a sanitized name (`gmail.send` becomes `gmail_send`; a name
+//! that is too long or already taken is cut short and given a hash suffix).
+//! The export carries a `mapping` from each tool name back to the service and
+//! method, so a tool call can be routed to `fgp call`.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::mcp_bridge::is_internal_method;
+use super::methods;
+use super::skill_import::{ManifestMethod, ManifestParam};
+
+/// API whose tool format to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    OpenAi,
+    Anthropic,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format {
+            "openai" => Ok(Self::OpenAi),
+            "anthropic" => Ok(Self::Anthropic),
+            _ => bail!(
+                "Unknown export format '{}' (expected openai or anthropic)",
+                format
+            ),
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Anthropic => "anthropic",
+        }
+    }
+
+    /// Longest tool name the API accepts
+    fn max_name_len(self) -> usize {
+        match self {
+            // Both currently allow ^[a-zA-Z0-9_-]{1,64}$
+            Self::OpenAi | Self::Anthropic => 64,
+        }
+    }
+
+    /// Whether the API accepts `c` in a tool name
+    fn allows_in_name(self, c: char) -> bool {
+        match self {
+            Self::OpenAi | Self::Anthropic => c.is_ascii_alphanumeric() || c == '_' || c == '-',
+        }
+    }
+}
+
+/// Tools for one API, with the method each one calls
+#[derive(Debug, Serialize)]
+pub struct ToolExport {
+    pub format: &'static str,
+    pub tools: Vec<Value>,
+    /// By tool name
+    pub mapping: BTreeMap<String, MappedMethod>,
+}
+
+/// The daemon method behind a tool
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct MappedMethod {
+    pub service: String,
+    /// Full method name, as `fgp call` takes it
+    pub method: String,
+}
+
+pub fn run(
+    service: Option<&str>,
+    method: Option<&str>,
+    format: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let format = ExportFormat::parse(format)?;
+    let services = match service {
+        Some(service) => vec![service.to_string()],
+        None => super::restart::installed_services()?,
+    };
+    if services.is_empty() {
+        bail!("No services installed. Install one with 'fgp install <source>' first.");
+    }
+
+    let mut loaded = Vec::new();
+    for name in &services {
+        let methods = match methods::service_methods(name) {
+            Ok(methods) => methods,
+            // With --all, one broken service shouldn't stop the rest
+            Err(e) if service.is_none() => {
+                eprintln!("{} Skipped {}: {:#}", "!".yellow().bold(), name, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let methods = match method {
+            Some(wanted) => match methods::find_method(&methods, name, wanted) {
+                Some(found) => vec![found.clone()],
+                None => bail!(
+                    "Service '{}' has no method '{}'. Run 'fgp methods {}' to list them.",
+                    name,
+                    wanted,
+                    name
+                ),
+            },
+            None => methods,
+        };
+        loaded.push((name.clone(), methods));
+    }
+
+    let export = build(format, &loaded);
+    let content = serde_json::to_string_pretty(&export)? + "\n";
+    match output {
+        Some(path) => {
+            let path = Path::new(path);
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{} Wrote {} {} tool{} to {}",
+                "✓".green(),
+                export.tools.len(),
+                format.key(),
+                if export.tools.len() == 1 { "" } else { "s" },
+                path.display()
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Tool definitions for the methods of each service, skipping the daemons'
+/// own `health`, `stop` and `methods`.
+pub fn build(format: ExportFormat, services: &[(String, Vec<ManifestMethod>)]) -> ToolExport {
+    let mut export = ToolExport {
+        format: format.key(),
+        tools: Vec::new(),
+        mapping: BTreeMap::new(),
+    };
+
+    for (service, methods) in services {
+        for method in methods {
+            let qualified = qualified_name(service, &method.name);
+            let short = qualified
+                .strip_prefix(&format!("{}.", service))
+                .unwrap_or(&qualified);
+            if is_internal_method(short) {
+                continue;
+            }
+
+            let name = tool_name(format, &qualified, &export.mapping);
+            let description = tool_description(method, &qualified);
+            let schema = params_schema(&method.params);
+            export.tools.push(match format {
+                ExportFormat::OpenAi => json!({
+                    "type": "function",
+                    "function": {
+                        "name": name,
+                        "description": description,
+                        "parameters": schema,
+                    },
+                }),
+                ExportFormat::Anthropic => json!({
+                    "name": name,
+                    "description": description,
+                    "input_schema": schema,
+                }),
+            });
+            export.mapping.insert(
+                name,
+                MappedMethod {
+                    service: service.clone(),
+                    method: qualified,
+                },
+            );
+        }
+    }
+
+    export
+}
+
+/// `service.method`, whether the manifest names methods in full or not
+fn qualified_name(service: &str, method: &str) -> String {
+    if method.starts_with(&format!("{}.", service)) {
+        method.to_string()
+    } else {
+        format!("{}.{}", service, method)
+    }
+}
+
+/// The method's name with characters the API rejects replaced by `_`. A
+/// name that is too long or already `taken` keeps as much as fits and ends
+/// in a hash of the method name, so it's the same on every export.
+fn tool_name(
+    format: ExportFormat,
+    qualified: &str,
+    taken: &BTreeMap<String, MappedMethod>,
+) -> String {
+    let mut name: String = qualified
+        .chars()
+        .map(|c| if format.allows_in_name(c) { c } else { '_' })
+        .collect();
+
+    if name.len() > format.max_name_len() || taken.contains_key(&name) {
+        let hash = Sha256::digest(qualified.as_bytes());
+        let suffix: String = std::iter::once("_".to_string())
+            .chain(hash.iter().take(4).map(|b| format!("{:02x}", b)))
+            .collect();
+        // Only ASCII is left, so any length is a char boundary
+        name.truncate(format.max_name_len() - suffix.len());
+        name.push_str(&suffix);
+    }
+    name
+}
+
+fn tool_description(method: &ManifestMethod, qualified: &str) -> String {
+    let mut description = method
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Call {}", qualified));
+    if method.destructive {
+        if !description.ends_with(['.', '!', '?']) {
+            description.push('.');
+        }
+        description.push_str(" This changes or deletes data; confirm with the user first.");
+    }
+    description
+}
+
+/// JSON Schema for a method's params
+pub fn params_schema(params: &[ManifestParam]) -> Value {
+    let properties: Map<String, Value> = params
+        .iter()
+        .map(|param| (param.name.clone(), param_schema(param)))
+        .collect();
+    let required: Vec<&str> = params
+        .iter()
+        .filter(|param| param.required)
+        .map(|param| param.name.as_str())
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// A param's full schema when it has one, with the shorthand fields filling
+/// in what it leaves out.
+fn param_schema(param: &ManifestParam) -> Value {
+    let mut schema = match param.schema {
+        Some(Value::Object(ref schema)) => schema.clone(),
+        _ => Map::new(),
+    };
+    let schema_type = match schema.get("type") {
+        Some(declared) => declared.as_str().and_then(json_schema_type),
+        None => {
+            let schema_type = param.param_type.as_deref().and_then(json_schema_type);
+            if let Some(schema_type) = schema_type {
+                schema.insert("type".to_string(), schema_type.into());
+            }
+            schema_type
+        }
+    };
+    if let Some(ref description) = param.description {
+        schema
+            .entry("description")
+            .or_insert_with(|| description.clone().into());
+    }
+    match param.items {
+        Some(ref items) => {
+            schema.entry("items").or_insert_with(|| items.clone());
+        }
+        // OpenAI rejects an array schema without items
+        None if schema_type == Some("array") => {
+            schema.entry("items").or_insert_with(|| json!({}));
+        }
+        None => {}
+    }
+    if let Some(ref properties) = param.properties {
+        schema
+            .entry("properties")
+            .or_insert_with(|| properties.clone());
+    }
+    if let Some(ref default) = param.default {
+        schema.entry("default").or_insert_with(|| default.clone());
+    }
+    Value::Object(schema)
+}
+
+/// JSON Schema type for a manifest param type; `None` accepts anything.
+fn json_schema_type(param_type: &str) -> Option<&'static str> {
+    match param_type.to_ascii_lowercase().as_str() {
+        "string" | "str" => Some("string"),
+        "integer" | "int" => Some("integer"),
+        "number" | "float" | "double" => Some("number"),
+        "boolean" | "bool" => Some("boolean"),
+        "array" | "list" => Some("array"),
+        "object" | "map" | "dict" => Some("object"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn method(name: &str) -> ManifestMethod {
+        ManifestMethod {
+            name: name.to_string(),
+            description: None,
+            params: Vec::new(),
+            destructive: false,
+            supports_dry_run: false,
+            cost: None,
+            rate_limit: None,
+        }
+    }
+
+    #[test]
+    fn test_tool_names() {
+        let long = format!("files.{}", "x".repeat(80));
+        let services = vec![(
+            "files".to_string(),
+            vec![
+                method("read"),
+                method("files.list@v2"),
+                method("list.v2"),
+                method("health"),
+                method(&long),
+            ],
+        )];
+        let export = build(ExportFormat::OpenAi, &services);
+        let names: Vec<&str> = export.mapping.keys().map(String::as_str).collect();
+        assert_eq!(names.len(), 4);
+        assert!(names.contains(&"files_read"));
+        assert!(names.contains(&"files_list_v2"));
+        assert!(names.iter().all(|name| name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')));
+
+        // The second method sanitizing to files_list_v2 gets a suffix
+        let clash = names
+            .iter()
+            .find(|name| name.starts_with("files_list_v2_"))
+            .unwrap();
+        assert_eq!(export.mapping[*clash].method, "files.list.v2");
+        assert_eq!(export.mapping["files_list_v2"].method, "files.list@v2");
+        let truncated = names.iter().find(|name| name.len() == 64).unwrap();
+        assert_eq!(export.mapping[*truncated].method, long);
+    }
+
+    /// The fixture manifest in tests/fixtures/methods-export exports to the
+    /// golden openai.json and anthropic.json next to it. After an intended
+    /// change, regenerate them with
+    /// `UPDATE_GOLDEN=1 cargo test test_export_fixtures` and review the diff.
+    #[test]
+    fn test_export_fixtures() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/methods-export");
+        let manifest = super::super::skill_import::load_manifest(&root.join("manifest.json"))
+            .unwrap_or_else(|e| panic!("{}", e));
+        let services = vec![(manifest.name.clone(), manifest.methods)];
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+
+        for format in [ExportFormat::OpenAi, ExportFormat::Anthropic] {
+            let export = build(format, &services);
+            let golden = root.join(format!("{}.json", format.key()));
+            if update {
+                // As `fgp methods --export` prints it
+                fs::write(
+                    &golden,
+                    serde_json::to_string_pretty(&export).unwrap() + "\n",
+                )
+                .unwrap();
+                continue;
+            }
+            let actual = serde_json::to_value(&export).unwrap();
+            let expected: Value = fs::read_to_string(&golden)
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or(Value::Null);
+            assert_eq!(
+                actual,
+                expected,
+                "{} differs from its golden output (rerun with UPDATE_GOLDEN=1 if the change is intended)",
+                golden.display()
+            );
+        }
+    }
+}
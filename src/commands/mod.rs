@@ -1,43 +1,115 @@
 //! CLI command implementations.
 
 pub mod agents;
+pub mod autostart;
 pub mod call;
+pub mod completions;
+pub mod daemon_command;
+pub mod daemon_proxy;
 pub mod dashboard;
+pub mod dev;
+pub mod diff;
+pub mod doctor;
 pub mod generate;
+pub mod guardrails;
 pub mod health;
+pub mod history;
 pub mod install;
+pub mod json_schema;
 pub mod license;
+pub mod lint_report;
 pub mod logs;
+pub mod markdown;
 pub mod mcp_bridge;
 pub mod methods;
+pub mod methods_export;
 pub mod monitor;
+pub mod network;
 pub mod new;
+pub mod orphans;
+pub mod registry;
+pub mod restart;
 pub mod skill;
+pub mod skill_diff;
 pub mod skill_export;
 pub mod skill_import;
+pub mod skill_legacy;
+pub mod skill_lock;
+pub mod skill_manifest;
+pub mod skill_migrate;
 pub mod skill_tap;
+pub mod skill_triggers;
+pub mod skill_trust;
 pub mod skill_validate;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod text;
 pub mod tui;
+pub mod uninstall;
 pub mod workflow;
+pub mod workflow_engine;
+pub mod workflow_lint;
 
+/// The daemon socket client, shared with external tools as the `fgp-client` crate.
+pub use fgp_client as client;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::io::{BufRead, Write};
 use std::path::PathBuf;
 
+/// Get the FGP home directory (`~/.fgp`).
+pub fn fgp_home() -> PathBuf {
+    dirs::home_dir().unwrap_or_default().join(".fgp")
+}
+
 /// Get the FGP services directory.
 pub fn fgp_services_dir() -> PathBuf {
-    let base = shellexpand::tilde("~/.fgp/services");
-    PathBuf::from(base.as_ref())
+    client::services_dir()
 }
 
 /// Get the socket path for a service.
 pub fn service_socket_path(service: &str) -> PathBuf {
-    fgp_services_dir().join(service).join("daemon.sock")
+    client::socket_path(service)
 }
 
 /// Get the PID file path for a service.
-#[allow(dead_code)]
 pub fn service_pid_path(service: &str) -> PathBuf {
     fgp_services_dir().join(service).join("daemon.pid")
 }
+
+/// Parse a skill or daemon version as semver. Versions are often written
+/// `v1.2` or `1.2`, so a leading `v` is dropped and missing parts are 0.
+/// `None` for anything else, which sorts before every version.
+pub fn parse_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    if let Ok(version) = semver::Version::parse(version) {
+        return Some(version);
+    }
+    let split = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, suffix) = version.split_at(split);
+    let padding = match core.split('.').count() {
+        1 => ".0.0",
+        2 => ".0",
+        _ => return None,
+    };
+    semver::Version::parse(&format!("{}{}{}", core, padding, suffix)).ok()
+}
+
+/// Ask a yes/no question on stderr; anything but "y" or "yes" is no.
+///
+/// Callers check that stdin is a terminal first, since nobody can answer
+/// otherwise.
+pub fn confirm(question: &str) -> Result<bool> {
+    eprint!("{} {} [y/N] ", "?".yellow().bold(), question);
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
@@ -15,6 +15,7 @@ use std::time::Duration;
 use crate::notifications;
 
 // Use shared helpers from parent module
+use super::client::Client;
 use super::{fgp_services_dir, service_socket_path};
 
 /// Service state for tracking changes.
@@ -141,21 +142,18 @@ fn get_service_state(socket: &PathBuf) -> ServiceState {
         return ServiceState::Stopped;
     }
 
-    match fgp_daemon::FgpClient::new(socket) {
-        Ok(client) => match client.health() {
-            Ok(response) if response.ok => {
-                let result = response.result.unwrap_or_default();
-                let status = result["status"].as_str().unwrap_or("running");
+    match Client::at(socket).health() {
+        Ok(response) if response.ok => {
+            let result = response.result.unwrap_or_default();
+            let status = result["status"].as_str().unwrap_or("running");
 
-                match status {
-                    "healthy" | "running" => ServiceState::Running,
-                    "degraded" | "unhealthy" => ServiceState::Unhealthy,
-                    _ => ServiceState::Running,
-                }
+            match status {
+                "healthy" | "running" => ServiceState::Running,
+                "degraded" | "unhealthy" => ServiceState::Unhealthy,
+                _ => ServiceState::Running,
             }
-            _ => ServiceState::Error,
-        },
-        Err(_) => ServiceState::Error,
+        }
+        _ => ServiceState::Error,
     }
 }
 
@@ -277,7 +275,7 @@ fn attempt_restart(name: &str, watchdog: &WatchdogConfig, restart_tracker: &mut
     thread::sleep(watchdog.restart_delay);
 
     // Attempt to start the service
-    match fgp_daemon::lifecycle::start_service(name) {
+    match super::daemon_command::start_service(name) {
         Ok(()) => {
             println!(
                 "[{}] {} {} restart initiated",
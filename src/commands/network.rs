@@ -0,0 +1,102 @@
+//! Whether a command may use the network.
+//!
+//! `fgp --offline` (or `FGP_OFFLINE=1`) guarantees no network requests and
+//! no git operations against remotes. Installs use the tap and marketplace
+//! clones already on disk, the marketplace pricing check and tap or
+//! marketplace updates are skipped with a notice, and anything that can't
+//! work without the network (adding a tap, installing from a git URL) fails
+//! straight away instead of waiting on a timeout. Repositories on this
+//! machine (`file://` URLs and paths) stay reachable.
+//!
+//! Commands that would touch the network take a [`Network`] rather than
+//! reading a global, so tests can run them both ways.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+/// Environment variable that turns offline mode on, like `--offline`.
+pub const OFFLINE_ENV: &str = "FGP_OFFLINE";
+
+/// Network access for one command run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Network {
+    pub offline: bool,
+}
+
+impl Network {
+    pub fn online() -> Self {
+        Self { offline: false }
+    }
+
+    pub fn offline() -> Self {
+        Self { offline: true }
+    }
+
+    /// Fail when offline; `what` is the operation that needs the network.
+    pub fn require(&self, what: &str) -> Result<()> {
+        if self.offline {
+            bail!(
+                "{} needs the network, but fgp is offline (--offline or {}=1)",
+                what,
+                OFFLINE_ENV
+            );
+        }
+        Ok(())
+    }
+
+    /// Like [`Network::require`], for an operation on the git repository at
+    /// `url`, which is fine offline when the repository is local.
+    pub fn require_repo(&self, url: &str, what: &str) -> Result<()> {
+        if self.reaches(url) {
+            Ok(())
+        } else {
+            self.require(what)
+        }
+    }
+
+    /// Whether the git repository at `url` can be reached.
+    pub fn reaches(&self, url: &str) -> bool {
+        !self.offline || is_local(url)
+    }
+
+    /// Whether to go ahead with an optional network check; when offline,
+    /// says that `what` was skipped.
+    pub fn allows(&self, what: &str) -> bool {
+        if self.offline {
+            println!("  {} Offline: skipped {}", "○".dimmed(), what);
+        }
+        !self.offline
+    }
+}
+
+/// Whether a git URL names a repository on this machine.
+fn is_local(url: &str) -> bool {
+    url.starts_with("file://")
+        || url.starts_with('/')
+        || url.starts_with('.')
+        || url.starts_with('~')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline() {
+        let remote = "https://github.com/acme/skills.git";
+        let local = "file:///srv/git/skills.git";
+
+        let online = Network::online();
+        assert!(online.require("Cloning").is_ok());
+        assert!(online.reaches(remote));
+        assert!(online.allows("the update check"));
+
+        let offline = Network::offline();
+        let err = offline.require_repo(remote, "Cloning it").unwrap_err();
+        assert!(err.to_string().contains("Cloning it needs the network"));
+        assert!(offline.require_repo(local, "Cloning it").is_ok());
+        assert!(offline.reaches("/srv/git/skills"));
+        assert!(!offline.reaches("git@github.com:acme/skills.git"));
+        assert!(!offline.allows("the update check"));
+    }
+}
@@ -8,8 +8,11 @@ use std::process::Command;
 
 // Template file contents embedded at compile time
 const TEMPLATE_MANIFEST: &str = include_str!("../templates/manifest.json.tmpl");
+const TEMPLATE_SKILL_JSON: &str = include_str!("../templates/skill.json.tmpl");
 const TEMPLATE_CARGO: &str = include_str!("../templates/Cargo.toml.tmpl");
 const TEMPLATE_MAIN: &str = include_str!("../templates/main.rs.tmpl");
+const TEMPLATE_PYPROJECT: &str = include_str!("../templates/pyproject.toml.tmpl");
+const TEMPLATE_DAEMON_PY: &str = include_str!("../templates/daemon.py.tmpl");
 const TEMPLATE_GITIGNORE: &str = include_str!("../templates/gitignore.tmpl");
 const TEMPLATE_README: &str = include_str!("../templates/README.md.tmpl");
 const TEMPLATE_SKILL: &str = include_str!("../templates/skill.md.tmpl");
@@ -25,6 +28,72 @@ const AGENT_CONFIGS: &[(&str, &str, &str)] = &[
     ("continue", "~/.continue/rules", "Continue"),
 ];
 
+/// Languages a new package's daemon can be written in.
+pub const SUPPORTED_LANGUAGES: &[&str] = &["rust", "python"];
+
+/// Implementation language of a new package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    /// fgp-daemon SDK, built with cargo
+    Rust,
+    /// Standard-library daemon script
+    Python,
+}
+
+impl Language {
+    fn from_name(language: &str) -> Result<Self> {
+        match language.to_lowercase().as_str() {
+            "rust" => Ok(Language::Rust),
+            "python" => Ok(Language::Python),
+            _ => bail!(
+                "Unsupported language '{}'. Supported languages: {}",
+                language,
+                SUPPORTED_LANGUAGES.join(", ")
+            ),
+        }
+    }
+
+    /// `daemon.entrypoint` in manifest.json, relative to the service directory.
+    fn entrypoint(self, name: &str) -> String {
+        match self {
+            Language::Rust => format!("./target/release/fgp-{}", name),
+            Language::Python => "./daemon.py".to_string(),
+        }
+    }
+
+    /// `daemon.start_command` in manifest.json, as a JSON array. It uses
+    /// `{service_dir}` (see [`super::daemon_command`]) rather than an absolute
+    /// path, so the package works wherever it's installed.
+    fn start_command(self, name: &str) -> String {
+        match self {
+            Language::Rust => format!(r#"["{{service_dir}}/target/release/fgp-{}"]"#, name),
+            Language::Python => r#"["python3", "{service_dir}/daemon.py"]"#.to_string(),
+        }
+    }
+
+    /// File holding the method handlers.
+    fn source_file(self) -> &'static str {
+        match self {
+            Language::Rust => "src/main.rs",
+            Language::Python => "daemon.py",
+        }
+    }
+
+    fn build_command(self) -> &'static str {
+        match self {
+            Language::Rust => "cargo build --release",
+            Language::Python => "python3 -m pip install -e .",
+        }
+    }
+
+    fn run_command(self) -> &'static str {
+        match self {
+            Language::Rust => "cargo run --release",
+            Language::Python => "python3 daemon.py",
+        }
+    }
+}
+
 pub fn run(name: &str, description: Option<&str>, language: &str, no_git: bool) -> Result<()> {
     // Validate name
     if !is_valid_name(name) {
@@ -38,10 +107,7 @@ pub fn run(name: &str, description: Option<&str>, language: &str, no_git: bool)
     let default_desc = format!("{} service", to_title_case(name));
     let description = description.unwrap_or(&default_desc);
 
-    // Only Rust is supported for now
-    if language != "rust" {
-        bail!("Only 'rust' language is currently supported");
-    }
+    let language = Language::from_name(language)?;
 
     println!();
     println!(
@@ -56,11 +122,56 @@ pub fn run(name: &str, description: Option<&str>, language: &str, no_git: bool)
         bail!("Directory '{}' already exists", name);
     }
 
+    generate(package_dir, name, description, language)?;
+
+    // Git initialization
+    if !no_git {
+        let git_init = Command::new("git")
+            .arg("init")
+            .current_dir(package_dir)
+            .output();
+
+        if git_init.is_ok() {
+            println!("  {} Initialized git repository", "✓".green());
+        }
+    }
+
+    // Summary
+    println!();
+    println!(
+        "{} Package {} created successfully!",
+        "✓".green().bold(),
+        name.bold()
+    );
+    println!();
+    println!("{}", "Next steps:".bold());
+    println!("  1. cd {}", name.cyan());
+    println!("  2. Edit manifest.json to add your methods");
+    println!("  3. Implement methods in {}", language.source_file());
+    match language {
+        Language::Rust => println!("  4. {}", language.build_command().cyan()),
+        Language::Python => println!(
+            "  4. {} {}",
+            language.run_command().cyan(),
+            "(try the daemon locally)".dimmed()
+        ),
+    }
+    println!("  5. {}", "fgp install .".cyan());
+    println!();
+
+    Ok(())
+}
+
+/// Write a new package's files into `package_dir`.
+fn generate(package_dir: &Path, name: &str, description: &str, language: Language) -> Result<()> {
     fs::create_dir_all(package_dir).context("Failed to create package directory")?;
     println!("  {} Created ./{}/", "✓".green(), name);
 
     // Create directory structure
-    fs::create_dir_all(package_dir.join("src"))?;
+    if language == Language::Rust {
+        fs::create_dir_all(package_dir.join("src"))?;
+    }
+    fs::create_dir_all(package_dir.join(".fgp"))?;
     fs::create_dir_all(package_dir.join("skills/claude-code"))?;
     fs::create_dir_all(package_dir.join("skills/cursor"))?;
     fs::create_dir_all(package_dir.join("skills/windsurf"))?;
@@ -70,59 +181,73 @@ pub fn run(name: &str, description: Option<&str>, language: &str, no_git: bool)
     let name_pascal = to_pascal_case(name);
     let name_title = to_title_case(name);
     let description_lower = description.to_lowercase();
+    let render = |template: &str| {
+        substitute_template(
+            template,
+            name,
+            description,
+            &name_pascal,
+            &name_title,
+            &description_lower,
+        )
+        .replace("{{ENTRYPOINT}}", &language.entrypoint(name))
+        .replace("{{START_COMMAND}}", &language.start_command(name))
+        .replace("{{BUILD_COMMAND}}", language.build_command())
+        .replace("{{RUN_COMMAND}}", language.run_command())
+    };
 
     // manifest.json
-    let manifest = substitute_template(
-        TEMPLATE_MANIFEST,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
-    fs::write(package_dir.join("manifest.json"), manifest)?;
+    fs::write(package_dir.join("manifest.json"), render(TEMPLATE_MANIFEST))?;
     println!("  {} Generated manifest.json", "✓".green());
 
-    // Cargo.toml
-    let cargo = substitute_template(
-        TEMPLATE_CARGO,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
-    fs::write(package_dir.join("Cargo.toml"), cargo)?;
-    println!("  {} Generated Cargo.toml", "✓".green());
-
-    // src/main.rs
-    let main_rs = substitute_template(
-        TEMPLATE_MAIN,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
-    fs::write(package_dir.join("src/main.rs"), main_rs)?;
-    println!(
-        "  {} Generated src/main.rs (Rust daemon skeleton)",
-        "✓".green()
-    );
+    // .fgp/skill.json
+    fs::write(
+        package_dir.join(".fgp/skill.json"),
+        render(TEMPLATE_SKILL_JSON),
+    )?;
+    println!("  {} Generated .fgp/skill.json", "✓".green());
+
+    match language {
+        Language::Rust => {
+            // Cargo.toml
+            fs::write(package_dir.join("Cargo.toml"), render(TEMPLATE_CARGO))?;
+            println!("  {} Generated Cargo.toml", "✓".green());
+
+            // src/main.rs
+            fs::write(package_dir.join("src/main.rs"), render(TEMPLATE_MAIN))?;
+            println!(
+                "  {} Generated src/main.rs (Rust daemon skeleton)",
+                "✓".green()
+            );
+        }
+        Language::Python => {
+            // pyproject.toml
+            fs::write(
+                package_dir.join("pyproject.toml"),
+                render(TEMPLATE_PYPROJECT),
+            )?;
+            println!("  {} Generated pyproject.toml", "✓".green());
+
+            // daemon.py (the manifest's entrypoint, so it must be executable)
+            let daemon_py = package_dir.join("daemon.py");
+            fs::write(&daemon_py, render(TEMPLATE_DAEMON_PY))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&daemon_py, fs::Permissions::from_mode(0o755))?;
+            }
+            println!(
+                "  {} Generated daemon.py (Python daemon skeleton)",
+                "✓".green()
+            );
+        }
+    }
 
     // .gitignore
     fs::write(package_dir.join(".gitignore"), TEMPLATE_GITIGNORE)?;
 
     // README.md
-    let readme = substitute_template(
-        TEMPLATE_README,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
-    fs::write(package_dir.join("README.md"), readme)?;
+    fs::write(package_dir.join("README.md"), render(TEMPLATE_README))?;
     println!("  {} Generated README.md", "✓".green());
 
     // Detect agents and generate skill files
@@ -140,90 +265,33 @@ pub fn run(name: &str, description: Option<&str>, language: &str, no_git: bool)
     }
 
     // Claude Code skill
-    let skill = substitute_template(
-        TEMPLATE_SKILL,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
-    fs::write(package_dir.join("skills/claude-code/SKILL.md"), skill)?;
+    fs::write(
+        package_dir.join("skills/claude-code/SKILL.md"),
+        render(TEMPLATE_SKILL),
+    )?;
     println!("  {} Generated skills/claude-code/SKILL.md", "✓".green());
 
     // Cursor skill
-    let cursor = substitute_template(
-        TEMPLATE_CURSOR,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
     fs::write(
         package_dir.join(format!("skills/cursor/{}.mdc", name)),
-        cursor,
+        render(TEMPLATE_CURSOR),
     )?;
     println!("  {} Generated skills/cursor/{}.mdc", "✓".green(), name);
 
     // Windsurf skill
-    let windsurf = substitute_template(
-        TEMPLATE_WINDSURF,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
     fs::write(
         package_dir.join(format!("skills/windsurf/{}.md", name)),
-        windsurf,
+        render(TEMPLATE_WINDSURF),
     )?;
     println!("  {} Generated skills/windsurf/{}.md", "✓".green(), name);
 
     // Continue skill
-    let continue_skill = substitute_template(
-        TEMPLATE_CONTINUE,
-        name,
-        description,
-        &name_pascal,
-        &name_title,
-        &description_lower,
-    );
     fs::write(
         package_dir.join(format!("skills/continue/{}.yaml", name)),
-        continue_skill,
+        render(TEMPLATE_CONTINUE),
     )?;
     println!("  {} Generated skills/continue/{}.yaml", "✓".green(), name);
 
-    // Git initialization
-    if !no_git {
-        let git_init = Command::new("git")
-            .arg("init")
-            .current_dir(package_dir)
-            .output();
-
-        if git_init.is_ok() {
-            println!("  {} Initialized git repository", "✓".green());
-        }
-    }
-
-    // Summary
-    println!();
-    println!(
-        "{} Package {} created successfully!",
-        "✓".green().bold(),
-        name.bold()
-    );
-    println!();
-    println!("{}", "Next steps:".bold());
-    println!("  1. cd {}", name.cyan());
-    println!("  2. Edit manifest.json to add your methods");
-    println!("  3. Implement methods in src/main.rs");
-    println!("  4. {}", "cargo build --release".cyan());
-    println!("  5. {}", "fgp install .".cyan());
-    println!();
-
     Ok(())
 }
 
@@ -262,7 +330,7 @@ fn to_pascal_case(name: &str) -> String {
 }
 
 /// Convert to Title Case.
-fn to_title_case(name: &str) -> String {
+pub(crate) fn to_title_case(name: &str) -> String {
     name.split('-')
         .map(|word| {
             let mut chars = word.chars();
@@ -307,3 +375,60 @@ fn detect_agents() -> Vec<(&'static str, &'static str)> {
 
     agents
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language() {
+        assert_eq!(Language::from_name("python").unwrap(), Language::Python);
+        assert_eq!(Language::from_name("Rust").unwrap(), Language::Rust);
+        let err = Language::from_name("go").unwrap_err().to_string();
+        assert!(err.contains("rust, python"), "{}", err);
+    }
+
+    #[test]
+    fn test_generate_python() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("my-svc");
+        generate(&package_dir, "my-svc", "My service", Language::Python).unwrap();
+
+        for file in [
+            "pyproject.toml",
+            "daemon.py",
+            "manifest.json",
+            ".fgp/skill.json",
+            "README.md",
+            "skills/claude-code/SKILL.md",
+            "skills/cursor/my-svc.mdc",
+        ] {
+            assert!(package_dir.join(file).is_file(), "missing {}", file);
+        }
+        assert!(!package_dir.join("Cargo.toml").exists());
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(package_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        assert_eq!(manifest["daemon"]["entrypoint"], "./daemon.py");
+        assert!(package_dir
+            .join(manifest["daemon"]["entrypoint"].as_str().unwrap())
+            .is_file());
+        let start_command =
+            crate::commands::daemon_command::from_manifest(&manifest, "start_command")
+                .unwrap()
+                .unwrap();
+        assert_eq!(start_command, vec!["python3", "{service_dir}/daemon.py"]);
+        assert!(crate::commands::daemon_command::check(&start_command).is_empty());
+
+        let skill: crate::commands::skill::SkillManifest =
+            serde_json::from_str(&fs::read_to_string(package_dir.join(".fgp/skill.json")).unwrap())
+                .unwrap();
+        assert_eq!(skill.name, "my-svc");
+
+        let daemon = fs::read_to_string(package_dir.join("daemon.py")).unwrap();
+        assert!(daemon.starts_with("#!/usr/bin/env python3"));
+        assert!(daemon.contains("class MySvcService:"));
+        assert!(!daemon.contains("{{"));
+    }
+}
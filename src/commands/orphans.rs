@@ -0,0 +1,425 @@
+//! Find daemon processes that fgp has lost track of.
+//!
+//! A daemon keeps running when its socket or PID file is deleted out from
+//! under it (usually by a botched manual cleanup), but then nothing fgp
+//! manages knows about it, and it keeps holding resources and API sessions.
+//! `fgp status --verbose` and `fgp doctor` list these orphans, and
+//! `fgp stop --orphan <pid>` terminates one.
+//!
+//! A process is taken for a daemon if its command line runs a service's
+//! manifest entrypoint, or if it holds a socket bound under
+//! ~/.fgp/services. It is an orphan if no PID file or health response
+//! accounts for it.
+
+use chrono::{Local, TimeZone};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+
+use super::{fgp_services_dir, service_pid_path};
+use super::{restart, status};
+
+/// How long a terminated orphan gets to exit.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A daemon process that no socket or PID file accounts for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orphan {
+    pub pid: u64,
+    /// The service the process most likely belongs to
+    pub service: String,
+    /// Local start time, when the platform reports one
+    pub started: Option<String>,
+    pub command: String,
+}
+
+/// A process that looks like a daemon for `service`.
+#[derive(Debug, Clone)]
+struct Candidate {
+    pid: u64,
+    parent: Option<u64>,
+    service: String,
+    /// Seconds since the epoch (0 if unknown)
+    started: u64,
+    command: String,
+}
+
+/// What fgp knows about running daemons.
+#[derive(Debug, Default)]
+struct Tracked {
+    /// PIDs from PID files and health responses
+    pids: HashSet<u64>,
+    /// Services whose daemon answers without saying which process it is
+    unidentified: HashSet<String>,
+}
+
+/// Scan running processes for orphaned daemons, ordered by PID.
+pub fn scan() -> Vec<Orphan> {
+    let services_dir = fgp_services_dir();
+    if !services_dir.is_dir() {
+        return Vec::new();
+    }
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_cmd(UpdateKind::Always),
+    );
+
+    let services = restart::installed_services().unwrap_or_default();
+    let candidates = candidates(&system, &services_dir, &services);
+    find_orphans(candidates, &tracked(&services))
+}
+
+/// Whether a process with this PID exists.
+pub fn is_running(pid: u64) -> bool {
+    let pid = Pid::from_u32(pid as u32);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
+
+/// Send SIGTERM to `pid` and wait for it to exit; `false` if it is still
+/// running after [`EXIT_TIMEOUT`].
+pub fn terminate(pid: u64) -> anyhow::Result<bool> {
+    let pid = Pid::from_u32(pid as u32);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+
+    let Some(process) = system.process(pid) else {
+        // Already gone
+        return Ok(true);
+    };
+    match process.kill_with(Signal::Term) {
+        Some(true) => {}
+        Some(false) => anyhow::bail!("Failed to signal PID {}", pid),
+        None => anyhow::bail!("SIGTERM is not supported on this platform"),
+    }
+
+    let start = std::time::Instant::now();
+    while start.elapsed() < EXIT_TIMEOUT {
+        std::thread::sleep(Duration::from_millis(100));
+        system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        if system.process(pid).is_none() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Installed service names.
+/// Processes that run a manifest entrypoint or hold an FGP socket.
+fn candidates(system: &System, services_dir: &Path, services: &[String]) -> Vec<Candidate> {
+    let entrypoints = manifest_entrypoints(services_dir, services);
+    let processes: Vec<_> = system
+        .processes()
+        .values()
+        // Linux lists threads as processes too
+        .filter(|p| p.thread_kind().is_none())
+        .filter(|p| p.pid().as_u32() != std::process::id())
+        .collect();
+    let sockets = socket_owners(
+        services_dir,
+        processes.iter().map(|p| p.pid().as_u32() as u64),
+    );
+
+    processes
+        .into_iter()
+        .filter_map(|process| {
+            let pid = process.pid().as_u32() as u64;
+            let args: Vec<String> = process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect();
+            let service = sockets
+                .get(&pid)
+                .cloned()
+                .or_else(|| service_for_command(&args, &entrypoints))?;
+            Some(Candidate {
+                pid,
+                parent: process.parent().map(|p| p.as_u32() as u64),
+                service,
+                started: process.start_time(),
+                command: args.join(" "),
+            })
+        })
+        .collect()
+}
+
+/// Each service's daemon entrypoint, as `fgp start` runs it.
+fn manifest_entrypoints(services_dir: &Path, services: &[String]) -> Vec<(PathBuf, String)> {
+    services
+        .iter()
+        .filter_map(|name| {
+            let dir = services_dir.join(name);
+            let content = fs::read_to_string(dir.join("manifest.json")).ok()?;
+            let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let entrypoint = manifest["daemon"]["entrypoint"].as_str()?;
+            Some((dir.join(entrypoint), name.clone()))
+        })
+        .collect()
+}
+
+/// The service whose entrypoint a command line runs, directly or through an
+/// interpreter.
+fn service_for_command(args: &[String], entrypoints: &[(PathBuf, String)]) -> Option<String> {
+    args.iter().find_map(|arg| {
+        entrypoints
+            .iter()
+            .find(|(path, _)| Path::new(arg) == path)
+            .map(|(_, service)| service.clone())
+    })
+}
+
+/// The service a socket path under `services_dir` belongs to.
+fn service_for_socket(services_dir: &Path, socket: &Path) -> Option<String> {
+    let mut components = socket.strip_prefix(services_dir).ok()?.components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(service)), Some(_)) => service.to_str().map(String::from),
+        _ => None,
+    }
+}
+
+/// Which of `pids` hold a socket bound under `services_dir`, and for which
+/// service.
+///
+/// Sockets whose file was deleted still count: that is how most orphans
+/// look. Processes whose descriptors can't be read are skipped.
+#[cfg(target_os = "linux")]
+fn socket_owners(services_dir: &Path, pids: impl Iterator<Item = u64>) -> HashMap<u64, String> {
+    let Ok(table) = fs::read_to_string("/proc/net/unix") else {
+        return HashMap::new();
+    };
+    let inodes = parse_unix_sockets(&table, services_dir);
+    if inodes.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut owners = HashMap::new();
+    for pid in pids {
+        let Ok(fds) = fs::read_dir(format!("/proc/{}/fd", pid)) else {
+            continue;
+        };
+        let service = fds.flatten().find_map(|fd| {
+            let target = fs::read_link(fd.path()).ok()?;
+            let inode: u64 = target
+                .to_str()?
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()?;
+            inodes.get(&inode).cloned()
+        });
+        if let Some(service) = service {
+            owners.insert(pid, service);
+        }
+    }
+    owners
+}
+
+/// Socket inodes bound under `services_dir` in /proc/net/unix, by service.
+///
+/// Columns: `Num RefCount Protocol Flags Type St Inode Path`.
+#[cfg(target_os = "linux")]
+fn parse_unix_sockets(table: &str, services_dir: &Path) -> HashMap<u64, String> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let inode = fields.get(6)?.parse().ok()?;
+            let service = service_for_socket(services_dir, Path::new(fields.get(7)?))?;
+            Some((inode, service))
+        })
+        .collect()
+}
+
+/// Which of `pids` hold a socket bound under `services_dir`, from `lsof`.
+#[cfg(not(target_os = "linux"))]
+fn socket_owners(services_dir: &Path, pids: impl Iterator<Item = u64>) -> HashMap<u64, String> {
+    let Ok(output) = std::process::Command::new("lsof")
+        .args(["-U", "-F", "pn"])
+        .stderr(std::process::Stdio::null())
+        .output()
+    else {
+        return HashMap::new();
+    };
+
+    let wanted: HashSet<u64> = pids.collect();
+    let mut owners = HashMap::new();
+    let mut pid = None;
+    // Fields come one per line: `p<pid>` starts a process, `n<name>` names a file
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(value) = line.strip_prefix('p') {
+            pid = value.parse::<u64>().ok().filter(|p| wanted.contains(p));
+        } else if let (Some(pid), Some(name)) = (pid, line.strip_prefix('n')) {
+            if let Some(service) = service_for_socket(services_dir, Path::new(name)) {
+                owners.entry(pid).or_insert(service);
+            }
+        }
+    }
+    owners
+}
+
+/// PIDs that PID files and health responses account for.
+fn tracked(services: &[String]) -> Tracked {
+    let mut tracked = Tracked::default();
+    for service in services {
+        let pid_file = fs::read_to_string(service_pid_path(service))
+            .ok()
+            .and_then(|content| content.trim().parse().ok());
+        if let Some(pid) = pid_file {
+            tracked.pids.insert(pid);
+        }
+
+        let snapshot = status::snapshot_service(service);
+        match snapshot.pid {
+            Some(pid) => {
+                tracked.pids.insert(pid);
+            }
+            None if snapshot.running && pid_file.is_none() => {
+                tracked.unidentified.insert(service.clone());
+            }
+            None => {}
+        }
+    }
+    tracked
+}
+
+/// Candidates that nothing accounts for.
+///
+/// Processes forked by another candidate (worker pools and the like) belong
+/// to it. When a daemon answers without reporting its PID, the newest process
+/// for its service is taken to be that daemon, since an orphan's socket has
+/// to be gone before a replacement can bind it.
+fn find_orphans(mut candidates: Vec<Candidate>, tracked: &Tracked) -> Vec<Orphan> {
+    let pids: HashSet<u64> = candidates.iter().map(|c| c.pid).collect();
+    candidates.retain(|c| {
+        !c.parent
+            .is_some_and(|parent| pids.contains(&parent) || tracked.pids.contains(&parent))
+    });
+    candidates.retain(|c| !tracked.pids.contains(&c.pid));
+
+    for service in &tracked.unidentified {
+        let newest = candidates
+            .iter()
+            .filter(|c| &c.service == service)
+            .max_by_key(|c| c.started)
+            .map(|c| c.pid);
+        candidates.retain(|c| Some(c.pid) != newest);
+    }
+
+    candidates.sort_by_key(|c| c.pid);
+    candidates
+        .into_iter()
+        .map(|c| Orphan {
+            pid: c.pid,
+            service: c.service,
+            started: format_start(c.started),
+            command: c.command,
+        })
+        .collect()
+}
+
+fn format_start(epoch_secs: u64) -> Option<String> {
+    if epoch_secs == 0 {
+        return None;
+    }
+    Local
+        .timestamp_opt(epoch_secs as i64, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(pid: u64, parent: u64, service: &str, started: u64) -> Candidate {
+        Candidate {
+            pid,
+            parent: Some(parent),
+            service: service.to_string(),
+            started,
+            command: format!("/fgp/services/{}/daemon", service),
+        }
+    }
+
+    fn orphan_pids(candidates: Vec<Candidate>, tracked: &Tracked) -> Vec<u64> {
+        find_orphans(candidates, tracked)
+            .iter()
+            .map(|o| o.pid)
+            .collect()
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let mut tracked = Tracked::default();
+        tracked.pids.insert(100);
+
+        let candidates = vec![
+            // Tracked, with a worker
+            candidate(100, 1, "gmail", 50),
+            candidate(101, 100, "gmail", 51),
+            // Untracked, with a worker
+            candidate(300, 1, "gmail", 10),
+            candidate(301, 300, "gmail", 11),
+            candidate(200, 1, "slack", 20),
+        ];
+        assert_eq!(orphan_pids(candidates.clone(), &tracked), vec![200, 300]);
+
+        // Slack answers without a PID: its newest process is the live daemon
+        tracked.unidentified.insert("slack".to_string());
+        let mut with_replacement = candidates;
+        with_replacement.push(candidate(400, 1, "slack", 90));
+        assert_eq!(orphan_pids(with_replacement, &tracked), vec![200, 300]);
+    }
+
+    #[test]
+    fn test_service_matching() {
+        let services = Path::new("/home/u/.fgp/services");
+        assert_eq!(
+            service_for_socket(services, &services.join("gmail/daemon.sock")).as_deref(),
+            Some("gmail")
+        );
+        assert_eq!(
+            service_for_socket(services, &services.join("stray.sock")),
+            None
+        );
+        assert_eq!(
+            service_for_socket(services, Path::new("/tmp/gmail.sock")),
+            None
+        );
+
+        let entrypoints = vec![(services.join("gmail/daemon.py"), "gmail".to_string())];
+        let command = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            service_for_command(&args, &entrypoints)
+        };
+        assert_eq!(
+            command(&["python3", "/home/u/.fgp/services/gmail/daemon.py"]).as_deref(),
+            Some("gmail")
+        );
+        assert_eq!(command(&["tail", "/home/u/.fgp/services/gmail/logs"]), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_unix_sockets() {
+        let table = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 41234 /home/u/.fgp/services/gmail/daemon.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 41240 /home/u/.fgp/services/gmail/daemon.sock
+0000000000000000: 00000002 00000000 00010000 0001 01 50001 /run/user/1000/bus
+0000000000000000: 00000003 00000000 00000000 0001 03 50002
+";
+        let inodes = parse_unix_sockets(table, Path::new("/home/u/.fgp/services"));
+        assert_eq!(inodes.len(), 2);
+        assert_eq!(inodes[&41234], "gmail");
+        assert_eq!(inodes[&41240], "gmail");
+    }
+}
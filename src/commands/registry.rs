@@ -0,0 +1,86 @@
+//! Inspect the daemon registry used for skill import enrichment.
+
+use anyhow::Result;
+use colored::Colorize;
+use tabled::{Table, Tabled};
+
+use super::skill_import::{DaemonRegistry, ManifestErrorKind};
+
+#[derive(Tabled)]
+struct RegistryRow {
+    #[tabled(rename = "Daemon")]
+    name: String,
+    #[tabled(rename = "Version")]
+    version: String,
+    #[tabled(rename = "Methods")]
+    methods: usize,
+    #[tabled(rename = "Source")]
+    source: String,
+}
+
+/// List known daemons, or the manifests that failed to load with `errors`.
+pub fn list(errors: bool) -> Result<()> {
+    let registry = DaemonRegistry::load_default()?;
+
+    if errors {
+        print_errors(&registry);
+        return Ok(());
+    }
+
+    if registry.daemon_count() == 0 {
+        println!("{} No daemon manifests found.", "!".yellow().bold());
+    } else {
+        let rows: Vec<RegistryRow> = registry
+            .daemon_names()
+            .into_iter()
+            .filter_map(|name| registry.get_daemon(name))
+            .map(|daemon| RegistryRow {
+                name: daemon.name.clone(),
+                version: daemon.version.clone(),
+                methods: daemon.methods.len(),
+                source: registry
+                    .source_path(&daemon.name)
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            })
+            .collect();
+
+        println!("{}", "Daemon Registry".bold());
+        println!();
+        println!("{}", Table::new(rows));
+    }
+
+    if !registry.errors().is_empty() {
+        println!();
+        println!("{} {}", "!".yellow().bold(), registry.load_summary());
+    }
+
+    Ok(())
+}
+
+fn print_errors(registry: &DaemonRegistry) {
+    if registry.errors().is_empty() {
+        println!(
+            "{} All daemon manifests loaded cleanly.",
+            "✓".green().bold()
+        );
+        return;
+    }
+
+    println!(
+        "{} {} manifest(s) skipped:",
+        "✗".red().bold(),
+        registry.errors().len()
+    );
+    println!();
+
+    for error in registry.errors() {
+        let label = match error.kind {
+            ManifestErrorKind::Io => "io error",
+            ManifestErrorKind::Syntax { .. } => "parse error",
+            ManifestErrorKind::Invalid => "invalid manifest",
+        };
+        println!("  {} {}", label.red(), error.path.display());
+        println!("    {}", error.message.dimmed());
+    }
+}
@@ -0,0 +1,180 @@
+//! Restart a daemon and wait for it to report healthy.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use super::client::Client;
+use super::{fgp_services_dir, service_socket_path};
+
+/// Interval between socket/health polls.
+const POLL_INTERVAL_MS: u64 = 100;
+
+pub fn run(service: Option<&str>, all: bool, wait_timeout: u64) -> Result<()> {
+    let services: Vec<String> = if all {
+        installed_services()?
+    } else {
+        match service {
+            Some(s) => vec![s.to_string()],
+            None => bail!("Specify a service name or use --all"),
+        }
+    };
+
+    if services.is_empty() {
+        println!("{} No services installed.", "!".yellow().bold());
+        return Ok(());
+    }
+
+    let timeout = Duration::from_secs(wait_timeout);
+    let mut failed = Vec::new();
+
+    for service in &services {
+        if let Err(e) = restart_one(service, timeout) {
+            eprintln!("{} {}: {}", "✗".red().bold(), service.bold(), e);
+            failed.push(service.clone());
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} service(s) failed to restart: {}",
+            failed.len(),
+            services.len(),
+            failed.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Restart a single service, returning an error if it does not come back healthy.
+fn restart_one(service: &str, timeout: Duration) -> Result<()> {
+    let manifest_path = fgp_services_dir().join(service).join("manifest.json");
+    if !manifest_path.exists() {
+        bail!(
+            "Service '{}' is not installed. Run 'fgp install <path>' first.",
+            service
+        );
+    }
+
+    let socket_path = service_socket_path(service);
+    let old_pid = current_pid(service);
+
+    println!("{} Restarting {}...", "→".blue().bold(), service.bold());
+
+    if socket_path.exists() {
+        if let Err(e) = super::daemon_command::stop_service(service) {
+            println!(
+                "  {} Stop failed ({}), removing stale socket",
+                "!".yellow().bold(),
+                e
+            );
+            let _ = fs::remove_file(&socket_path);
+        }
+
+        // Wait for the socket to go away before starting again
+        let deadline = Instant::now() + timeout;
+        while socket_path.exists() {
+            if Instant::now() >= deadline {
+                bail!(
+                    "Daemon did not stop within {}s (socket still present)",
+                    timeout.as_secs()
+                );
+            }
+            std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+        }
+    }
+
+    if let Err(e) = super::daemon_command::start_service(service) {
+        bail!("Failed to start: {}", e);
+    }
+
+    let new_pid = match wait_for_healthy(service, timeout) {
+        Some(pid) => pid,
+        None => bail!(
+            "Daemon did not become healthy within {}s. Check 'fgp logs {}'.",
+            timeout.as_secs(),
+            service
+        ),
+    };
+
+    let old = old_pid
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    println!(
+        "{} {} restarted (PID: {} → {})",
+        "✓".green().bold(),
+        service.bold(),
+        old.dimmed(),
+        new_pid.to_string().green()
+    );
+
+    if old_pid == Some(new_pid) {
+        println!(
+            "  {} PID unchanged - the process may not have cycled",
+            "⚠".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// PID reported by a running daemon, if it answers health at all.
+fn current_pid(service: &str) -> Option<u64> {
+    let response = Client::at(service_socket_path(service)).health().ok()?;
+    response.result?["pid"].as_u64()
+}
+
+/// Poll health until the daemon reports healthy, returning its PID.
+///
+/// Returns `None` if the timeout expires first.
+pub fn wait_for_healthy(service: &str, timeout: Duration) -> Option<u64> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(pid) = healthy_pid(service) {
+            return Some(pid);
+        }
+        if Instant::now() >= deadline {
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    }
+}
+
+/// PID reported by the daemon once it answers health with status "healthy".
+fn healthy_pid(service: &str) -> Option<u64> {
+    let socket_path = service_socket_path(service);
+    if !socket_path.exists() {
+        return None;
+    }
+
+    let response = Client::at(socket_path).health().ok()?;
+    if !response.ok {
+        return None;
+    }
+
+    let result = response.result.unwrap_or_default();
+    match result["status"].as_str() {
+        Some("healthy") | None => Some(result["pid"].as_u64().unwrap_or(0)),
+        _ => None,
+    }
+}
+
+/// Names of all installed services (directories with a manifest.json).
+pub(crate) fn installed_services() -> Result<Vec<String>> {
+    let services_dir = fgp_services_dir();
+    if !services_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut services: Vec<String> = fs::read_dir(&services_dir)?
+        .flatten()
+        .filter(|e| e.path().join("manifest.json").exists())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    services.sort();
+
+    Ok(services)
+}
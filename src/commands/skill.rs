@@ -9,6 +9,7 @@
 //! ~/.fgp/
 //! ├── skills/
 //! │   ├── installed_skills.json    # Track installed skills + versions
+//! │   ├── skills.lock              # Exact versions and upgrade constraints
 //! │   ├── known_marketplaces.json  # Track marketplace sources
 //! │   ├── cache/                   # Installed skill files
 //! │   │   └── <marketplace>/<skill>/<version>/
@@ -20,13 +21,20 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use super::license::{check_skill_pricing, format_price, validate_license};
+use super::network::Network;
+use super::skill_lock::{self, VersionReq};
 use super::skill_tap;
+use super::skill_trust::{self, TrustSummary};
+use super::skill_validate::DaemonDependency;
+use super::text;
+use super::{fgp_home, parse_version};
 
 /// Skill manifest format (skill.json)
 #[derive(Debug, Serialize, Deserialize)]
@@ -228,6 +236,12 @@ pub struct MethodDef {
     pub description: Option<String>,
     #[serde(default)]
     pub params: HashMap<String, ParamDef>,
+    /// What a call costs (e.g. "$0.01 per call"); copied to manifest.json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<String>,
+    /// How often the method may be called (e.g. "60 per minute")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -238,6 +252,9 @@ pub struct ParamDef {
     pub required: bool,
     #[serde(default)]
     pub description: Option<String>,
+    /// Value the daemon uses when the param is left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -315,6 +332,21 @@ pub struct InstalledSkill {
     pub git_commit_sha: Option<String>,
     #[serde(rename = "binaryPath")]
     pub binary_path: Option<String>,
+    /// What installing and registering the skill wrote for agents, removed
+    /// again by `fgp skill remove`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<ExportArtifact>,
+}
+
+/// Something written outside the skill's cache so an agent can use it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportArtifact {
+    /// A file of its own, such as a SKILL.md or a service manifest
+    File { path: String },
+    /// One `mcpServers` entry in a shared config, such as Cursor's mcp.json
+    /// or Continue's config.yaml
+    McpServer { config: String, server: String },
 }
 
 /// Known marketplaces tracking
@@ -340,12 +372,8 @@ pub struct MarketplaceSource {
     pub repo: String,
 }
 
-/// Get the FGP home directory
-fn fgp_home() -> PathBuf {
-    dirs::home_dir()
-        .expect("Could not find home directory")
-        .join(".fgp")
-}
+/// What builds a Rust skill whose manifest has no `build_command`
+const DEFAULT_BUILD_COMMAND: &str = "cargo build --release";
 
 /// Get the skills directory
 fn skills_dir() -> PathBuf {
@@ -385,6 +413,163 @@ fn load_installed_skills() -> Result<InstalledSkills> {
     Ok(serde_json::from_str(&content)?)
 }
 
+/// Daemon config for `daemon_name` from an installed skill (its `daemon`
+/// section)
+pub fn daemon_config(daemon_name: &str) -> Option<DaemonConfig> {
+    let installed = load_installed_skills().ok()?;
+
+    installed
+        .skills
+        .values()
+        .filter_map(|entries| entries.first())
+        .filter_map(|entry| {
+            super::skill_manifest::load_legacy(&Path::new(&entry.install_path).join("source")).ok()
+        })
+        .filter_map(|manifest| manifest.daemon)
+        .find(|daemon| daemon.name == daemon_name)
+}
+
+/// Log file configured for a daemon by an installed skill (`daemon.log_file`)
+pub fn daemon_log_file(daemon_name: &str) -> Option<PathBuf> {
+    daemon_config(daemon_name)
+        .and_then(|daemon| daemon.log_file)
+        .map(|log_file| PathBuf::from(shellexpand::tilde(&log_file).as_ref()))
+}
+
+/// Installed skills that provide `daemon_name`, with their cache directories.
+pub fn installed_for_daemon(daemon_name: &str) -> Result<Vec<(String, PathBuf)>> {
+    let installed = load_installed_skills()?;
+    let mut found = Vec::new();
+
+    for (key, entries) in &installed.skills {
+        let Some(entry) = entries.first() else {
+            continue;
+        };
+        let skill_name = key.split('@').next().unwrap_or(key);
+        let daemon =
+            super::skill_manifest::load_legacy(&Path::new(&entry.install_path).join("source"))
+                .ok()
+                .and_then(|manifest| manifest.daemon)
+                .map(|daemon| daemon.name)
+                .unwrap_or_else(|| skill_name.replace("-gateway", ""));
+        if daemon == daemon_name {
+            found.push((key.clone(), PathBuf::from(&entry.install_path)));
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// Drop skills from installed_skills.json, leaving their files alone.
+pub fn forget_installed(keys: &[String]) -> Result<()> {
+    let mut installed = load_installed_skills()?;
+    let before = installed.skills.len();
+    installed.skills.retain(|key, _| !keys.contains(key));
+    if installed.skills.len() != before {
+        save_installed_skills(&installed)?;
+    }
+    skill_lock::forget(keys)
+}
+
+/// An installed skill whose `source` symlink points at a missing directory.
+pub struct BrokenSource {
+    pub skill: String,
+    pub link: PathBuf,
+    /// Where the skill's source can be found now, if anywhere
+    pub relink_to: Option<PathBuf>,
+}
+
+/// Find installed skills with dangling `source` symlinks.
+pub fn broken_sources() -> Result<Vec<BrokenSource>> {
+    let installed = load_installed_skills()?;
+    let mut broken = Vec::new();
+
+    for (key, entries) in &installed.skills {
+        let (name, origin) = key.split_once('@').unwrap_or((key.as_str(), ""));
+        for entry in entries {
+            let link = Path::new(&entry.install_path).join("source");
+            let is_symlink = fs::symlink_metadata(&link)
+                .map(|m| m.file_type().is_symlink())
+                .unwrap_or(false);
+            if !is_symlink || link.exists() {
+                continue;
+            }
+
+            let relink_to = if entry.scope == "tap" {
+                skill_tap::find_skill(name)
+                    .ok()
+                    .flatten()
+                    .map(|(_, path, _)| path)
+            } else {
+                marketplace_skill_source(origin, name)
+            };
+
+            broken.push(BrokenSource {
+                skill: name.to_string(),
+                link,
+                relink_to: relink_to.filter(|p| p.exists()),
+            });
+        }
+    }
+
+    broken.sort_by(|a, b| a.skill.cmp(&b.skill));
+    Ok(broken)
+}
+
+/// Source directory of a skill in a cloned marketplace.
+fn marketplace_skill_source(marketplace: &str, name: &str) -> Option<PathBuf> {
+    let marketplaces = load_known_marketplaces().ok()?;
+    let location = marketplaces
+        .marketplaces
+        .get(marketplace)?
+        .install_location
+        .clone()?;
+    let content =
+        fs::read_to_string(Path::new(&location).join(".fgp").join("marketplace.json")).ok()?;
+    let manifest: MarketplaceManifest = serde_json::from_str(&content).ok()?;
+
+    manifest
+        .skills
+        .into_iter()
+        .find(|skill| skill.name == name)
+        .map(|skill| Path::new(&location).join(skill.source))
+}
+
+/// Names and source directories of installed skills, sorted by name.
+///
+/// A skill installed in several scopes is listed once.
+pub fn installed_skill_sources() -> Result<Vec<(String, PathBuf)>> {
+    let installed = load_installed_skills()?;
+    let mut sources: Vec<(String, PathBuf)> = installed
+        .skills
+        .iter()
+        .filter_map(|(key, entries)| {
+            let name = key.split('@').next().unwrap_or(key);
+            let entry = entries.first()?;
+            Some((
+                name.to_string(),
+                Path::new(&entry.install_path).join("source"),
+            ))
+        })
+        .collect();
+
+    sources.sort();
+    sources.dedup_by(|a, b| a.0 == b.0);
+    Ok(sources)
+}
+
+/// The install record for a skill, with the tap or marketplace it came from.
+pub fn find_installed(name: &str) -> Result<Option<(String, InstalledSkill)>> {
+    let installed = load_installed_skills()?;
+    let prefix = format!("{}@", name);
+
+    Ok(installed.skills.iter().find_map(|(key, entries)| {
+        let origin = key.strip_prefix(&prefix)?;
+        Some((origin.to_string(), entries.first()?.clone()))
+    }))
+}
+
 /// Save installed skills
 fn save_installed_skills(skills: &InstalledSkills) -> Result<()> {
     let path = installed_skills_path();
@@ -395,7 +580,7 @@ fn save_installed_skills(skills: &InstalledSkills) -> Result<()> {
 }
 
 /// Load known marketplaces
-fn load_known_marketplaces() -> Result<KnownMarketplaces> {
+pub fn load_known_marketplaces() -> Result<KnownMarketplaces> {
     let path = known_marketplaces_path();
     if !path.exists() {
         return Ok(KnownMarketplaces {
@@ -407,7 +592,7 @@ fn load_known_marketplaces() -> Result<KnownMarketplaces> {
 }
 
 /// Save known marketplaces
-fn save_known_marketplaces(marketplaces: &KnownMarketplaces) -> Result<()> {
+pub fn save_known_marketplaces(marketplaces: &KnownMarketplaces) -> Result<()> {
     let path = known_marketplaces_path();
     fs::create_dir_all(path.parent().unwrap())?;
     let content = serde_json::to_string_pretty(marketplaces)?;
@@ -441,12 +626,19 @@ pub fn list() -> Result<()> {
                 "○ stopped".dimmed()
             };
 
+            let (skill_name, origin) = skill_key.split_once('@').unwrap_or((skill_key, ""));
+            let behind = available_version(skill_name, origin)
+                .filter(|available| parse_version(available) > parse_version(&entry.version))
+                .map(|available| format!(" {} available", available).yellow().to_string())
+                .unwrap_or_default();
+
             println!(
-                "  {} {} {} {}",
+                "  {} {} {} {}{}",
                 skill_key.cyan(),
                 format!("v{}", entry.version).dimmed(),
                 status,
-                format!("({})", entry.scope).dimmed()
+                format!("({})", entry.scope).dimmed(),
+                behind
             );
         }
     }
@@ -463,84 +655,166 @@ fn check_daemon_running(service: &str) -> bool {
     socket_path.exists()
 }
 
-/// Search for skills in taps and marketplaces
-pub fn search(query: &str) -> Result<()> {
-    println!("{} {}", "Searching for:".bold(), query.cyan());
-    println!();
+/// A skill found by `fgp skill search`
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    /// Tap or marketplace the skill comes from
+    pub source: String,
+    /// `tap` or `marketplace`
+    pub source_kind: &'static str,
+    /// skill.yaml keywords, or marketplace tags
+    pub keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub daemons: Vec<String>,
+    pub installed: bool,
+    /// How well the skill matches the query; higher is better
+    pub score: u32,
+}
 
-    let mut found = false;
+/// How well a skill matches `query` (already [`text::fold`]ed): an exact
+/// name beats a name prefix, which beats a name containing it, then a
+/// keyword and then the description. `None` if nothing matches.
+fn relevance(query: &str, name: &str, keywords: &[String], description: &str) -> Option<u32> {
+    let name = text::fold(name);
+    if name == query {
+        Some(100)
+    } else if name.starts_with(query) {
+        Some(80)
+    } else if name.contains(query) {
+        Some(60)
+    } else if keywords.iter().any(|k| text::fold(k) == query) {
+        Some(50)
+    } else if keywords.iter().any(|k| text::contains_folded(k, query)) {
+        Some(40)
+    } else if text::contains_folded(description, query) {
+        Some(20)
+    } else {
+        None
+    }
+}
+
+/// Order results best match first, keeping one per skill name: the tap's
+/// where a tap and a legacy marketplace both have it.
+fn rank(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    results.sort_by(|a, b| {
+        (&a.name, a.source_kind != "tap", &a.source).cmp(&(
+            &b.name,
+            b.source_kind != "tap",
+            &b.source,
+        ))
+    });
+    results.dedup_by(|later, first| later.name == first.name);
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    results
+}
+
+/// Search taps and legacy marketplaces for skills matching `query`, best
+/// match first; `auto_update` fetches stale taps first, as `[taps]
+/// auto_update` does.
+pub fn search(
+    query: &str,
+    limit: usize,
+    installed_only: bool,
+    json: bool,
+    auto_update: bool,
+    network: Network,
+) -> Result<()> {
+    if query.trim().is_empty() {
+        bail!(
+            "Search query is empty. Search for a name, keyword or word from a description \
+             (e.g. 'fgp skill search gmail'), or list installed skills with 'fgp skill list'."
+        );
+    }
+    if auto_update || crate::config::load().unwrap_or_default().taps.auto_update {
+        skill_tap::auto_update(network, json)?;
+    }
+    let folded = text::fold(query.trim());
+    let installed = load_installed_skills()?;
+    let is_installed = |name: &str| {
+        let prefix = format!("{}@", name);
+        installed.skills.keys().any(|key| key.starts_with(&prefix))
+    };
+
+    let mut results = Vec::new();
 
-    // First search taps (new skill.yaml format)
     // Ignore tap search errors, continue with marketplaces
-    if let Ok(results) = skill_tap::search_taps(query) {
-        if !results.is_empty() {
-            println!("{}", "From taps:".bold().underline());
-            for (tap_name, _path, manifest) in &results {
-                found = true;
-                println!(
-                    "  {} v{} (from {})",
-                    manifest.name.cyan().bold(),
-                    manifest.version.dimmed(),
-                    tap_name.dimmed()
-                );
-                println!("    {}", manifest.description);
-                if !manifest.keywords.is_empty() {
-                    println!("    Keywords: {}", manifest.keywords.join(", ").dimmed());
-                }
-                if !manifest.daemons.is_empty() {
-                    let daemon_names: Vec<_> =
-                        manifest.daemons.iter().map(|d| d.name.as_str()).collect();
-                    println!("    Daemons: {}", daemon_names.join(", ").dimmed());
-                }
-                println!();
-            }
+    if let Ok(found) = skill_tap::search_taps(query.trim()) {
+        for (tap_name, _path, manifest) in found {
+            let Some(score) = relevance(
+                &folded,
+                &manifest.name,
+                &manifest.keywords,
+                &manifest.description,
+            ) else {
+                continue;
+            };
+            results.push(SearchResult {
+                installed: is_installed(&manifest.name),
+                name: manifest.name,
+                version: manifest.version,
+                description: manifest.description,
+                source: tap_name,
+                source_kind: "tap",
+                keywords: manifest.keywords,
+                daemons: manifest.daemons.into_iter().map(|d| d.name).collect(),
+                score,
+            });
         }
     }
 
-    // Also search legacy marketplaces
     let marketplaces = load_known_marketplaces()?;
-    if !marketplaces.marketplaces.is_empty() {
-        let mut marketplace_found = false;
-        for (name, entry) in &marketplaces.marketplaces {
-            if let Some(ref location) = entry.install_location {
-                let manifest_path = Path::new(location).join(".fgp").join("marketplace.json");
-                if manifest_path.exists() {
-                    let content = fs::read_to_string(&manifest_path)?;
-                    let manifest: MarketplaceManifest = serde_json::from_str(&content)?;
-
-                    for skill in &manifest.skills {
-                        let query_lower = query.to_lowercase();
-                        if skill.name.to_lowercase().contains(&query_lower)
-                            || skill.description.to_lowercase().contains(&query_lower)
-                            || skill
-                                .tags
-                                .iter()
-                                .any(|t| t.to_lowercase().contains(&query_lower))
-                        {
-                            if !marketplace_found {
-                                println!("{}", "From marketplaces (legacy):".bold().underline());
-                                marketplace_found = true;
-                            }
-                            found = true;
-                            println!(
-                                "  {} {} (from {})",
-                                skill.name.cyan().bold(),
-                                format!("v{}", skill.version).dimmed(),
-                                name.dimmed()
-                            );
-                            println!("    {}", skill.description);
-                            if !skill.tags.is_empty() {
-                                println!("    Tags: {}", skill.tags.join(", ").dimmed());
-                            }
-                            println!();
-                        }
-                    }
-                }
-            }
+    for (mp_name, entry) in &marketplaces.marketplaces {
+        let Some(ref location) = entry.install_location else {
+            continue;
+        };
+        let manifest_path = Path::new(location).join(".fgp").join("marketplace.json");
+        if !manifest_path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&manifest_path)?;
+        let manifest: MarketplaceManifest = serde_json::from_str(&content)?;
+        for skill in manifest.skills {
+            let Some(score) = relevance(&folded, &skill.name, &skill.tags, &skill.description)
+            else {
+                continue;
+            };
+            results.push(SearchResult {
+                installed: is_installed(&skill.name),
+                name: skill.name,
+                version: skill.version,
+                description: skill.description,
+                source: mp_name.clone(),
+                source_kind: "marketplace",
+                keywords: skill.tags,
+                daemons: Vec::new(),
+                score,
+            });
         }
     }
 
-    if !found {
+    let mut results = rank(results);
+    if installed_only {
+        results.retain(|result| result.installed);
+    }
+    let total = results.len();
+    results.truncate(limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    println!("{} {}", "Searching for:".bold(), query.cyan());
+    println!();
+
+    if results.is_empty() {
+        if installed_only {
+            println!("{}", "No installed skills match your query.".yellow());
+            return Ok(());
+        }
         println!("{}", "No skills found matching your query.".yellow());
         println!();
         println!("Add a tap to search more skills:");
@@ -548,17 +822,76 @@ pub fn search(query: &str) -> Result<()> {
             "  {}",
             "fgp skill tap add fast-gateway-protocol/official-skills".cyan()
         );
+        skill_tap::nudge_if_stale(None);
+        return Ok(());
+    }
+
+    for result in &results {
+        let installed = if result.installed { " installed" } else { "" };
+        println!(
+            "  {} {} (from {} {}){}",
+            result.name.cyan().bold(),
+            format!("v{}", result.version).dimmed(),
+            result.source_kind.dimmed(),
+            result.source.dimmed(),
+            installed.green()
+        );
+        println!("    {}", result.description);
+        if !result.keywords.is_empty() {
+            println!("    Keywords: {}", result.keywords.join(", ").dimmed());
+        }
+        if !result.daemons.is_empty() {
+            println!("    Daemons: {}", result.daemons.join(", ").dimmed());
+        }
+        println!();
+    }
+
+    if total > results.len() {
+        println!(
+            "{}",
+            format!(
+                "Showing {} of {} results; use --limit to see more.",
+                results.len(),
+                total
+            )
+            .dimmed()
+        );
+    }
+
+    let mut taps: Vec<String> = results
+        .iter()
+        .filter(|result| result.source_kind == "tap")
+        .map(|result| result.source.clone())
+        .collect();
+    taps.sort();
+    taps.dedup();
+    if let Some(footer) = skill_tap::search_footer(&taps) {
+        println!("{}", footer.dimmed());
     }
+    skill_tap::nudge_if_stale(None);
 
     Ok(())
 }
 
-/// Install a skill
-pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&str>) -> Result<()> {
+/// Install a skill; `trust` accepts its source without asking
+pub fn install(
+    name: &str,
+    from_marketplace: Option<&str>,
+    license_key: Option<&str>,
+    trust: bool,
+    with_deps: bool,
+    network: Network,
+) -> Result<()> {
     println!("{} {}...", "Installing skill:".bold(), name.cyan());
 
-    // Check if skill is paid and requires a license
-    if let Ok(Some(pricing)) = check_skill_pricing(name) {
+    // Check if skill is paid and requires a license. Offline, it installs as
+    // free, as it does when the marketplace API can't be reached.
+    let pricing = if network.allows("the marketplace pricing check") {
+        check_skill_pricing(name)
+    } else {
+        Ok(None)
+    };
+    if let Ok(Some(pricing)) = pricing {
         let price_str = format_price(pricing.price_cents, &pricing.currency);
         println!(
             "  {} This is a paid skill ({} - {})",
@@ -615,7 +948,9 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
     // First, try to find the skill in taps (new skill.yaml format)
     if from_marketplace.is_none() {
         if let Ok(Some((tap_name, skill_path, manifest))) = skill_tap::find_skill(name) {
-            return install_from_tap(&tap_name, &skill_path, &manifest);
+            skill_tap::nudge_if_stale(Some(std::slice::from_ref(&tap_name)));
+            install_from_tap(&tap_name, &skill_path, &manifest, trust)?;
+            return install_dependencies(&manifest.daemons, trust, with_deps, network);
         }
     }
 
@@ -655,6 +990,7 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
     let (marketplace_name, skill, source_path) = match skill_info {
         Some(info) => info,
         None => {
+            skill_tap::nudge_if_stale(None);
             bail!(
                 "Skill '{}' not found. Add a tap first:\n  fgp skill tap add fast-gateway-protocol/official-skills",
                 name
@@ -666,17 +1002,49 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
     println!("  Version: {}", skill.version);
     println!("  Source: {}", source_path.display());
 
-    // Check for skill.json in the source
-    let skill_manifest_path = source_path.join(".fgp").join("skill.json");
-    if !skill_manifest_path.exists() {
-        bail!(
-            "Skill manifest not found at {}",
-            skill_manifest_path.display()
-        );
-    }
-
-    let skill_content = fs::read_to_string(&skill_manifest_path)?;
-    let skill_manifest: SkillManifest = serde_json::from_str(&skill_content)?;
+    // Load the skill manifest (skill.json or skill.yaml) from the source
+    let skill_manifest = super::skill_manifest::load_legacy(&source_path)?;
+
+    // Building runs a command from the manifest, so check the source is trusted
+    let repo = marketplaces
+        .marketplaces
+        .get(&marketplace_name)
+        .map(|entry| entry.source.repo.clone())
+        .unwrap_or_else(|| marketplace_name.clone());
+    skill_trust::confirm(
+        &TrustSummary {
+            source: &marketplace_name,
+            repo,
+            skill: &skill.name,
+            build_command: skill_manifest
+                .binary
+                .as_ref()
+                .filter(|binary| binary.binary_type == "rust")
+                .map(|binary| {
+                    binary
+                        .build_command
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_BUILD_COMMAND.to_string())
+                }),
+            requirements: skill_manifest
+                .requirements
+                .iter()
+                .map(|(name, req)| match req.min_version {
+                    Some(ref min) => format!("{} {} >= {}", req.req_type, name, min),
+                    None => format!("{} {}", req.req_type, name),
+                })
+                .collect(),
+            daemons: skill_manifest
+                .daemon
+                .iter()
+                .map(|daemon| {
+                    let methods = skill_manifest.methods.iter().map(|m| m.name.clone());
+                    (daemon.name.clone(), methods.collect())
+                })
+                .collect(),
+        },
+        trust,
+    )?;
 
     // Create cache directory for this skill
     let cache_path = cache_dir()
@@ -706,7 +1074,7 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
             let build_cmd = binary
                 .build_command
                 .as_deref()
-                .unwrap_or("cargo build --release");
+                .unwrap_or(DEFAULT_BUILD_COMMAND);
 
             let status = Command::new("sh")
                 .arg("-c")
@@ -771,15 +1139,18 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
         last_updated: now,
         git_commit_sha: git_sha,
         binary_path,
+        exports: previous_exports(&installed, &skill_key),
     };
 
     installed
         .skills
         .insert(skill_key.clone(), vec![entry.clone()]);
     save_installed_skills(&installed)?;
+    skill_lock::record(&skill_key, &entry.version, entry.git_commit_sha.as_deref())?;
 
     // Auto-register with ecosystems based on exports config
     println!("  Registering with ecosystems...");
+    let mut written = Vec::new();
     let daemon_name = skill_manifest
         .daemon
         .as_ref()
@@ -795,6 +1166,9 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
         let manifest_json = serde_json::to_string_pretty(&manifest)?;
         fs::write(&manifest_path, &manifest_json)?;
         println!("    {} MCP: {}", "✓".green(), manifest_path.display());
+        written.push(ExportArtifact::File {
+            path: manifest_path.to_string_lossy().to_string(),
+        });
     }
 
     // Auto-register with other ecosystems based on exports config
@@ -802,7 +1176,7 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
         // Claude Code
         if exports.claude.as_ref().map(|c| c.enabled).unwrap_or(false) {
             match export_to_claude(&skill_manifest) {
-                Ok(()) => {}
+                Ok(artifact) => written.extend(artifact),
                 Err(e) => println!("    {} Claude: {}", "✗".red(), e),
             }
         }
@@ -810,7 +1184,7 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
         // Cursor
         if exports.cursor.as_ref().map(|c| c.enabled).unwrap_or(false) {
             match export_to_cursor(&skill_manifest) {
-                Ok(()) => {}
+                Ok(artifact) => written.extend(artifact),
                 Err(e) => println!("    {} Cursor: {}", "✗".red(), e),
             }
         }
@@ -823,11 +1197,12 @@ pub fn install(name: &str, from_marketplace: Option<&str>, license_key: Option<&
             .unwrap_or(false)
         {
             match export_to_windsurf(&skill_manifest) {
-                Ok(()) => {}
+                Ok(artifact) => written.extend(artifact),
                 Err(e) => println!("    {} Windsurf: {}", "✗".red(), e),
             }
         }
     }
+    remember_exports(&skill_key, written)?;
 
     println!();
     println!(
@@ -897,11 +1272,9 @@ fn install_paid_package(
 
     println!("  {} Package extracted to: {}", "✓".green(), extract_dir.display());
 
-    // Look for skill.json in the extracted package
-    let skill_manifest_path = extract_dir.join(".fgp").join("skill.json");
-    if skill_manifest_path.exists() {
-        let skill_content = fs::read_to_string(&skill_manifest_path)?;
-        let skill_manifest: SkillManifest = serde_json::from_str(&skill_content)?;
+    // Look for a skill manifest in the extracted package
+    if super::skill_manifest::find_manifest(&extract_dir).is_some() {
+        let skill_manifest = super::skill_manifest::load_legacy(&extract_dir)?;
 
         let daemon_name = skill_manifest
             .daemon
@@ -922,6 +1295,7 @@ fn install_paid_package(
             last_updated: now,
             git_commit_sha: None,
             binary_path: None,
+            exports: Vec::new(),
         };
 
         installed.skills.insert(skill_key.clone(), vec![entry]);
@@ -950,6 +1324,7 @@ fn install_paid_package(
             last_updated: now,
             git_commit_sha: None,
             binary_path: None,
+            exports: Vec::new(),
         };
 
         installed.skills.insert(skill_key.clone(), vec![entry]);
@@ -987,25 +1362,207 @@ fn extract_tarball(tarball_path: &Path, dest_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Options for `fgp skill install --all-from <tap>`.
+#[derive(Debug, Default)]
+pub struct InstallAllOptions<'a> {
+    /// Only skills with this keyword
+    pub category: Option<&'a str>,
+    /// Only skills whose name matches this glob
+    pub pattern: Option<&'a str>,
+    /// Install without asking to confirm the list
+    pub yes: bool,
+    /// Trust the tap without asking
+    pub trust: bool,
+    /// Install missing daemons without asking
+    pub with_deps: bool,
+    /// Stop at the first skill that fails to install
+    pub fail_fast: bool,
+}
+
+/// Install every skill in a tap that matches the filters, one after the
+/// other, skipping the ones already installed. The daemons the skills need
+/// are checked once at the end, so a daemon several of them share is only
+/// installed once.
+pub fn install_all_from(tap: &str, options: &InstallAllOptions, network: Network) -> Result<()> {
+    let (tap_name, skills) = skill_tap::tap_skills(tap)?;
+    let skills: Vec<_> = skills
+        .into_iter()
+        .filter(|(_, manifest)| matches_filters(manifest, options.category, options.pattern))
+        .collect();
+    if skills.is_empty() {
+        bail!("No skills in tap '{}' match", tap_name);
+    }
+
+    let installed = load_installed_skills()?;
+    let is_installed = |name: &str| {
+        installed
+            .skills
+            .contains_key(&format!("{}@{}", name, tap_name))
+    };
+
+    println!("{} {}:", "Skills in".bold(), tap_name.cyan());
+    for (_, manifest) in &skills {
+        let note = if is_installed(&manifest.name) {
+            " (already installed)"
+        } else {
+            ""
+        };
+        println!(
+            "  {:<24} {}{}",
+            manifest.name,
+            manifest.version.dimmed(),
+            note.dimmed()
+        );
+    }
+    println!();
+
+    let (skipped, to_install): (Vec<_>, Vec<_>) = skills
+        .iter()
+        .partition(|(_, manifest)| is_installed(&manifest.name));
+    if to_install.is_empty() {
+        println!(
+            "{} All {} skill(s) are already installed",
+            "✓".green(),
+            skipped.len()
+        );
+        return Ok(());
+    }
+    if !options.yes && !confirm_install_all(&tap_name, to_install.len())? {
+        bail!("Install from {} cancelled", tap_name);
+    }
+
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+    for (i, (path, manifest)) in to_install.iter().enumerate() {
+        println!();
+        println!(
+            "{} [{}/{}] {}",
+            "→".blue().bold(),
+            i + 1,
+            to_install.len(),
+            manifest.name.cyan()
+        );
+        match install_from_tap(&tap_name, path, manifest, options.trust) {
+            Ok(()) => succeeded.push(manifest),
+            Err(e) => {
+                println!(
+                    "  {} Failed to install {}: {:#}",
+                    "✗".red(),
+                    manifest.name,
+                    e
+                );
+                failed.push((manifest.name.as_str(), e));
+                if options.fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    let daemons: Vec<DaemonDependency> = succeeded
+        .iter()
+        .flat_map(|manifest| manifest.daemons.iter().cloned())
+        .collect();
+    install_dependencies(&daemons, options.trust, options.with_deps, network)?;
+
+    let not_attempted = to_install.len() - succeeded.len() - failed.len();
+    println!();
+    println!(
+        "{} {} installed, {} failed, {} already installed",
+        "Summary:".bold(),
+        succeeded.len().to_string().green(),
+        failed.len().to_string().red(),
+        skipped.len()
+    );
+    if not_attempted > 0 {
+        println!(
+            "  {} {} skill(s) not attempted after --fail-fast",
+            "○".dimmed(),
+            not_attempted
+        );
+    }
+    for (name, e) in &failed {
+        println!("  {} {}: {:#}", "✗".red(), name, e);
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} skill(s) failed to install",
+            failed.len(),
+            to_install.len()
+        );
+    }
+    Ok(())
+}
+
+/// Whether a tap skill passes the `--category` and `--match` filters
+fn matches_filters(
+    manifest: &super::skill_validate::SkillManifest,
+    category: Option<&str>,
+    pattern: Option<&str>,
+) -> bool {
+    let in_category = category.is_none_or(|category| {
+        manifest
+            .keywords
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(category))
+    });
+    in_category && pattern.is_none_or(|pattern| glob_match(pattern, &manifest.name))
+}
+
+/// Ask whether to install `count` skills from `tap`; a non-interactive stdin
+/// needs --yes.
+fn confirm_install_all(tap: &str, count: usize) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        println!(
+            "  {} Installing {} skill(s) from {} needs confirmation; rerun with --yes",
+            "!".yellow().bold(),
+            count,
+            tap
+        );
+        return Ok(false);
+    }
+    super::confirm(&format!("Install {} skill(s) from {}?", count, tap))
+}
+
 /// Install a skill from a tap (skill.yaml format)
 fn install_from_tap(
     tap_name: &str,
     skill_path: &Path,
     manifest: &super::skill_validate::SkillManifest,
+    trust: bool,
 ) -> Result<()> {
     println!("  Found in tap: {}", tap_name.green());
     println!("  Version: {}", manifest.version);
     println!("  Path: {}", skill_path.display());
 
-    // Check daemon dependencies
-    if !manifest.daemons.is_empty() {
-        println!();
-        println!("  {}:", "Required daemons".bold());
-        for daemon in &manifest.daemons {
-            let optional = if daemon.optional { " (optional)" } else { "" };
-            println!("    - {}{}", daemon.name.cyan(), optional.dimmed());
+    let mut requirements = Vec::new();
+    if let Some(ref permissions) = manifest.permissions {
+        if permissions.network {
+            requirements.push("network access".to_string());
+        }
+        if permissions.subprocess {
+            requirements.push("subprocesses".to_string());
+        }
+        for var in &permissions.env_vars {
+            requirements.push(format!("${}", var));
         }
     }
+    skill_trust::confirm(
+        &TrustSummary {
+            source: tap_name,
+            repo: skill_tap::tap_url(tap_name).unwrap_or_else(|| tap_name.to_string()),
+            skill: &manifest.name,
+            build_command: None,
+            requirements,
+            daemons: manifest
+                .daemons
+                .iter()
+                .map(|daemon| (daemon.name.clone(), daemon.methods.clone()))
+                .collect(),
+        },
+        trust,
+    )?;
 
     // Create skills directory
     let skills_install_dir = skills_dir().join("installed").join(&manifest.name);
@@ -1043,12 +1600,14 @@ fn install_from_tap(
         version: manifest.version.clone(),
         installed_at: now.clone(),
         last_updated: now,
-        git_commit_sha: git_sha,
+        git_commit_sha: git_sha.clone(),
         binary_path: None, // skill.yaml packages typically don't have binaries
+        exports: previous_exports(&installed, &skill_key),
     };
 
     installed.skills.insert(skill_key.clone(), vec![entry]);
     save_installed_skills(&installed)?;
+    skill_lock::record(&skill_key, &manifest.version, git_sha.as_deref())?;
 
     // Export to agents if instructions are available
     println!();
@@ -1057,7 +1616,8 @@ fn install_from_tap(
     if let Some(ref instructions) = manifest.instructions {
         // Claude Code
         if instructions.claude_code.is_some() || instructions.core.is_some() {
-            export_tap_skill_to_claude(skill_path, manifest)?;
+            let artifact = export_tap_skill_to_claude(skill_path, manifest)?;
+            remember_exports(&skill_key, [artifact])?;
         }
 
         // Cursor
@@ -1101,35 +1661,244 @@ fn install_from_tap(
     Ok(())
 }
 
-/// Export a tap skill to Claude Code
-fn export_tap_skill_to_claude(
-    skill_path: &Path,
-    manifest: &super::skill_validate::SkillManifest,
-) -> Result<()> {
-    let claude_skills_dir = dirs::home_dir()
-        .context("Could not find home directory")?
-        .join(".claude")
-        .join("skills")
-        .join(format!("{}-fgp", manifest.name));
+/// Where one of a skill's daemon dependencies stands
+#[derive(Debug, PartialEq, Eq)]
+enum DependencyStatus {
+    /// Installed as a service, or provided by an installed skill
+    Available,
+    /// Missing, but optional
+    OptionalMissing,
+    /// Missing, and provided by `skill` in `marketplace`
+    Installable { marketplace: String, skill: String },
+    /// Missing, and nothing in the marketplaces provides it
+    Unavailable,
+}
 
-    fs::create_dir_all(&claude_skills_dir)?;
-    let skill_md_path = claude_skills_dir.join("SKILL.md");
+/// The status of each daemon in `daemons`, listing a daemon named more than
+/// once only once (required if any entry requires it).
+fn check_dependencies(
+    daemons: &[DaemonDependency],
+    available: impl Fn(&str) -> bool,
+    provider: impl Fn(&str) -> Option<(String, String)>,
+) -> Vec<(String, DependencyStatus)> {
+    let mut names: Vec<&str> = Vec::new();
+    for daemon in daemons {
+        if !names.contains(&daemon.name.as_str()) {
+            names.push(&daemon.name);
+        }
+    }
 
-    // Try to read instruction file or generate from manifest
-    let content = if let Some(ref instructions) = manifest.instructions {
-        if let Some(ref claude_file) = instructions.claude_code {
-            let src_path = skill_path.join(claude_file);
-            if src_path.exists() {
-                fs::read_to_string(&src_path)?
-            } else if let Some(ref core_file) = instructions.core {
-                let core_path = skill_path.join(core_file);
-                if core_path.exists() {
-                    fs::read_to_string(&core_path)?
-                } else {
-                    generate_skill_md_from_manifest(manifest)
-                }
+    names
+        .into_iter()
+        .map(|name| {
+            let optional = daemons
+                .iter()
+                .filter(|daemon| daemon.name == name)
+                .all(|daemon| daemon.optional);
+            let status = if available(name) {
+                DependencyStatus::Available
+            } else if optional {
+                DependencyStatus::OptionalMissing
             } else {
-                generate_skill_md_from_manifest(manifest)
+                match provider(name) {
+                    Some((marketplace, skill)) => {
+                        DependencyStatus::Installable { marketplace, skill }
+                    }
+                    None => DependencyStatus::Unavailable,
+                }
+            };
+            (name.to_string(), status)
+        })
+        .collect()
+}
+
+/// Whether `daemon` is installed as a service (`~/.fgp/services/<daemon>`)
+/// or provided by an installed marketplace skill (tap skills only use
+/// daemons).
+fn daemon_available(daemon: &str) -> bool {
+    if super::fgp_services_dir()
+        .join(daemon)
+        .join("manifest.json")
+        .exists()
+    {
+        return true;
+    }
+    let (Ok(providers), Ok(installed)) = (installed_for_daemon(daemon), load_installed_skills())
+    else {
+        return false;
+    };
+    providers.iter().any(|(key, _)| {
+        installed
+            .skills
+            .get(key)
+            .and_then(|entries| entries.first())
+            .is_some_and(|entry| entry.scope != "tap")
+    })
+}
+
+/// The marketplace skill that provides `daemon`, as (marketplace, skill),
+/// looking through the marketplaces in name order.
+fn find_daemon_provider(daemon: &str) -> Option<(String, String)> {
+    let marketplaces = load_known_marketplaces().ok()?;
+    let mut names: Vec<&String> = marketplaces.marketplaces.keys().collect();
+    names.sort();
+
+    for mp_name in names {
+        let Some(ref location) = marketplaces.marketplaces[mp_name].install_location else {
+            continue;
+        };
+        let manifest_path = Path::new(location).join(".fgp").join("marketplace.json");
+        let Some(manifest) = fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<MarketplaceManifest>(&content).ok())
+        else {
+            continue;
+        };
+
+        for skill in manifest.skills {
+            let provides =
+                super::skill_manifest::load_legacy(&Path::new(location).join(&skill.source))
+                    .ok()
+                    .and_then(|manifest| manifest.daemon)
+                    .map(|daemon| daemon.name)
+                    .unwrap_or_else(|| skill.name.replace("-gateway", ""));
+            if provides == daemon {
+                return Some((mp_name.clone(), skill.name));
+            }
+        }
+    }
+    None
+}
+
+/// Check the daemons an installed tap skill needs, and install the missing
+/// required ones from the marketplace skills that provide them: straight
+/// away with `with_deps`, otherwise after asking. Optional daemons are only
+/// reported. Each providing skill is installed once, and marketplace skills
+/// have no daemon dependencies of their own, so repeated or circular
+/// dependencies can't recurse.
+fn install_dependencies(
+    daemons: &[DaemonDependency],
+    trust: bool,
+    with_deps: bool,
+    network: Network,
+) -> Result<()> {
+    if daemons.is_empty() {
+        return Ok(());
+    }
+
+    let statuses = check_dependencies(daemons, daemon_available, find_daemon_provider);
+    println!();
+    println!("  {}:", "Daemons".bold());
+    for (daemon, status) in &statuses {
+        match status {
+            DependencyStatus::Available => println!("    {} {}", "✓".green(), daemon),
+            DependencyStatus::OptionalMissing => println!(
+                "    {} {} {}",
+                "○".dimmed(),
+                daemon,
+                "(optional, not installed)".dimmed()
+            ),
+            DependencyStatus::Installable { marketplace, skill } => println!(
+                "    {} {} (missing; provided by {} in {})",
+                "✗".red(),
+                daemon,
+                skill.cyan(),
+                marketplace
+            ),
+            DependencyStatus::Unavailable => println!(
+                "    {} {} (missing; no marketplace provides it)",
+                "✗".red(),
+                daemon
+            ),
+        }
+    }
+
+    let mut installed = HashSet::new();
+    let mut missing = Vec::new();
+    for (daemon, status) in &statuses {
+        let (marketplace, skill) = match status {
+            DependencyStatus::Installable { marketplace, skill } => (marketplace, skill),
+            DependencyStatus::Unavailable => {
+                missing.push(daemon.as_str());
+                continue;
+            }
+            _ => continue,
+        };
+        if installed.contains(skill) {
+            continue;
+        }
+        if !with_deps && !confirm_dependency(skill, daemon)? {
+            missing.push(daemon.as_str());
+            continue;
+        }
+
+        println!();
+        match install(skill, Some(marketplace), None, trust, false, network) {
+            Ok(()) => {
+                installed.insert(skill.clone());
+            }
+            Err(e) => {
+                println!("  {} Failed to install {}: {}", "✗".red(), skill, e);
+                missing.push(daemon.as_str());
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        println!();
+        println!(
+            "  {} The skill won't work until these daemons are installed: {}",
+            "!".yellow().bold(),
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Ask whether to install `skill` for `daemon`; anything but "y" (or a
+/// non-interactive stdin) is no.
+fn confirm_dependency(skill: &str, daemon: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        println!(
+            "  {} Installing {} needs confirmation; rerun with --with-deps",
+            "!".yellow().bold(),
+            skill
+        );
+        return Ok(false);
+    }
+
+    super::confirm(&format!("Install {} to provide {}?", skill, daemon))
+}
+
+/// Export a tap skill to Claude Code
+fn export_tap_skill_to_claude(
+    skill_path: &Path,
+    manifest: &super::skill_validate::SkillManifest,
+) -> Result<ExportArtifact> {
+    let claude_skills_dir = dirs::home_dir()
+        .context("Could not find home directory")?
+        .join(".claude")
+        .join("skills")
+        .join(format!("{}-fgp", manifest.name));
+
+    fs::create_dir_all(&claude_skills_dir)?;
+    let skill_md_path = claude_skills_dir.join("SKILL.md");
+
+    // Try to read instruction file or generate from manifest
+    let content = if let Some(ref instructions) = manifest.instructions {
+        if let Some(ref claude_file) = instructions.claude_code {
+            let src_path = skill_path.join(claude_file);
+            if src_path.exists() {
+                fs::read_to_string(&src_path)?
+            } else if let Some(ref core_file) = instructions.core {
+                let core_path = skill_path.join(core_file);
+                if core_path.exists() {
+                    fs::read_to_string(&core_path)?
+                } else {
+                    generate_skill_md_from_manifest(manifest)
+                }
+            } else {
+                generate_skill_md_from_manifest(manifest)
             }
         } else if let Some(ref core_file) = instructions.core {
             let core_path = skill_path.join(core_file);
@@ -1148,7 +1917,9 @@ fn export_tap_skill_to_claude(
     fs::write(&skill_md_path, &content)?;
     println!("    {} Claude: {}", "✓".green(), skill_md_path.display());
 
-    Ok(())
+    Ok(ExportArtifact::File {
+        path: skill_md_path.to_string_lossy().to_string(),
+    })
 }
 
 /// Export a tap skill to Cursor
@@ -1241,7 +2012,8 @@ fn generate_skill_md_from_manifest(manifest: &super::skill_validate::SkillManife
 }
 
 /// Update marketplaces (git pull)
-pub fn marketplace_update() -> Result<()> {
+pub fn marketplace_update(network: Network) -> Result<()> {
+    super::skill_legacy::notice("update", None, network)?;
     let mut marketplaces = load_known_marketplaces()?;
 
     if marketplaces.marketplaces.is_empty() {
@@ -1258,7 +2030,9 @@ pub fn marketplace_update() -> Result<()> {
     for (name, entry) in marketplaces.marketplaces.iter_mut() {
         print!("  {} ", name.cyan());
 
-        if let Some(ref location) = entry.install_location {
+        if network.offline {
+            println!("{}", "skipped (offline)".dimmed());
+        } else if let Some(ref location) = entry.install_location {
             // Git pull
             let output = Command::new("git")
                 .args(["pull", "--quiet"])
@@ -1298,7 +2072,9 @@ pub fn marketplace_update() -> Result<()> {
 }
 
 /// Add a marketplace
-pub fn marketplace_add(url: &str) -> Result<()> {
+pub fn marketplace_add(url: &str, network: Network) -> Result<()> {
+    super::skill_legacy::notice("add", Some(url), network)?;
+    network.require_repo(url, &format!("Cloning {}", url))?;
     println!("{} {}", "Adding marketplace:".bold(), url.cyan());
 
     // Parse URL to get repo name
@@ -1381,7 +2157,8 @@ pub fn marketplace_add(url: &str) -> Result<()> {
 }
 
 /// List marketplaces
-pub fn marketplace_list() -> Result<()> {
+pub fn marketplace_list(network: Network) -> Result<()> {
+    super::skill_legacy::notice("list", None, network)?;
     let marketplaces = load_known_marketplaces()?;
 
     if marketplaces.marketplaces.is_empty() {
@@ -1494,8 +2271,81 @@ pub fn check_updates() -> Result<()> {
     Ok(())
 }
 
-/// Upgrade all skills
-pub fn upgrade(skill_name: Option<&str>) -> Result<()> {
+/// What `fgp skill upgrade` would do with one installed skill
+#[derive(Debug, PartialEq, Eq)]
+enum UpgradePlan {
+    /// Install this newer version
+    To(String),
+    /// The source has nothing newer
+    UpToDate,
+    /// A newer version is available, but outside the skill's constraint
+    Held {
+        available: String,
+        constraint: String,
+    },
+    /// The source no longer lists the skill
+    Missing,
+}
+
+/// Version of a skill that its tap or marketplace offers now
+fn available_version(skill_name: &str, origin: &str) -> Option<String> {
+    if let Ok(Some((tap_name, _, manifest))) = skill_tap::find_skill(skill_name) {
+        if tap_name == origin {
+            return Some(manifest.version);
+        }
+    }
+
+    let marketplaces = load_known_marketplaces().ok()?;
+    let location = marketplaces
+        .marketplaces
+        .get(origin)?
+        .install_location
+        .as_ref()?;
+    let content =
+        fs::read_to_string(Path::new(location).join(".fgp").join("marketplace.json")).ok()?;
+    let manifest: MarketplaceManifest = serde_json::from_str(&content).ok()?;
+    manifest
+        .skills
+        .into_iter()
+        .find(|skill| skill.name == skill_name)
+        .map(|skill| skill.version)
+}
+
+/// Decide whether to move a skill from `current` to `available`, within
+/// `constraint` unless `latest`
+fn plan_upgrade(
+    current: &str,
+    constraint: &VersionReq,
+    available: Option<String>,
+    latest: bool,
+) -> UpgradePlan {
+    let Some(available) = available else {
+        return UpgradePlan::Missing;
+    };
+    if parse_version(&available) <= parse_version(current) {
+        UpgradePlan::UpToDate
+    } else if latest || constraint.matches(&available) {
+        UpgradePlan::To(available)
+    } else {
+        UpgradePlan::Held {
+            available,
+            constraint: constraint.to_string(),
+        }
+    }
+}
+
+/// Upgrade installed skills to the newest version their tap or marketplace
+/// has within the constraint in skills.lock (any newer version with
+/// `latest`).
+///
+/// With `dry_run`, shows what each upgrade would change instead.
+pub fn upgrade(
+    skill_name: Option<&str>,
+    dry_run: bool,
+    latest: bool,
+    trust: bool,
+    network: Network,
+) -> Result<()> {
     let installed = load_installed_skills()?;
 
     if installed.skills.is_empty() {
@@ -1503,7 +2353,7 @@ pub fn upgrade(skill_name: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
-    let skills_to_upgrade: Vec<_> = if let Some(name) = skill_name {
+    let mut skills_to_upgrade: Vec<_> = if let Some(name) = skill_name {
         installed
             .skills
             .keys()
@@ -1513,6 +2363,7 @@ pub fn upgrade(skill_name: Option<&str>) -> Result<()> {
     } else {
         installed.skills.keys().cloned().collect()
     };
+    skills_to_upgrade.sort();
 
     if skills_to_upgrade.is_empty() {
         println!(
@@ -1522,32 +2373,233 @@ pub fn upgrade(skill_name: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
-    println!("{}", "Upgrading skills...".bold());
+    let lock = skill_lock::load()?;
+
+    if dry_run {
+        println!("{}", "Changes an upgrade would apply:".bold());
+    } else {
+        println!("{}", "Upgrading skills...".bold());
+    }
     println!();
 
+    let mut held = false;
     for skill_key in skills_to_upgrade {
-        let parts: Vec<&str> = skill_key.split('@').collect();
-        if parts.len() != 2 {
+        let Some((skill_name, origin)) = skill_key.split_once('@') else {
+            continue;
+        };
+        let Some(entry) = installed.skills[&skill_key].first() else {
+            continue;
+        };
+        let locked = lock.skills.get(&skill_key);
+        let current = locked.map_or(entry.version.as_str(), |l| l.version.as_str());
+        let constraint = locked
+            .and_then(|l| VersionReq::parse(&l.constraint).ok())
+            .unwrap_or_else(|| VersionReq::default_for(current));
+
+        let version = match plan_upgrade(
+            current,
+            &constraint,
+            available_version(skill_name, origin),
+            latest,
+        ) {
+            UpgradePlan::To(version) => version,
+            UpgradePlan::UpToDate => {
+                println!(
+                    "  {} {} {}",
+                    skill_name.cyan(),
+                    current.dimmed(),
+                    "up to date".dimmed()
+                );
+                continue;
+            }
+            UpgradePlan::Held {
+                available,
+                constraint,
+            } => {
+                held = true;
+                println!(
+                    "  {} {} held by {} ({} available)",
+                    skill_name.cyan(),
+                    current.dimmed(),
+                    constraint,
+                    available.yellow()
+                );
+                continue;
+            }
+            UpgradePlan::Missing => {
+                println!(
+                    "  {} {} no longer in {}",
+                    "!".yellow().bold(),
+                    skill_name.cyan(),
+                    origin
+                );
+                continue;
+            }
+        };
+
+        if dry_run {
+            println!(
+                "  {} {} → {}",
+                skill_name.cyan(),
+                current.dimmed(),
+                version.green()
+            );
+            if let Err(e) = super::skill_diff::preview(skill_name) {
+                println!("{} {}: {:#}", "!".yellow().bold(), skill_name, e);
+            }
+            println!();
             continue;
         }
-        let skill_name = parts[0];
-        let marketplace_name = parts[1];
 
-        print!("  {} ", skill_name.cyan());
+        print!(
+            "  {} {} → {} ",
+            skill_name.cyan(),
+            current.dimmed(),
+            version.green()
+        );
 
-        // Re-install the skill
+        // Re-install the skill, from its tap or marketplace
         // For upgrades, we don't need a license (user already purchased)
-        match install(skill_name, Some(marketplace_name), None) {
+        let from = skill_tap::tap_url(origin).is_none().then_some(origin);
+        match install(skill_name, from, None, trust, false, network) {
             Ok(()) => println!("{}", "✓ upgraded".green()),
             Err(e) => println!("{} {}", "✗ failed:".red(), e),
         }
     }
 
+    if held {
+        println!();
+        println!(
+            "Run {} to upgrade past a skill's constraint.",
+            "fgp skill upgrade --latest".cyan()
+        );
+    }
+    if dry_run {
+        println!();
+        println!(
+            "Review a skill in full with {}",
+            "fgp skill diff <name>".cyan()
+        );
+    }
+
     Ok(())
 }
 
-/// Remove a skill
-pub fn remove(name: &str) -> Result<()> {
+/// Exports recorded for `skill_key` by an earlier install, kept when it's
+/// reinstalled or upgraded
+fn previous_exports(installed: &InstalledSkills, skill_key: &str) -> Vec<ExportArtifact> {
+    installed
+        .skills
+        .get(skill_key)
+        .and_then(|entries| entries.first())
+        .map(|entry| entry.exports.clone())
+        .unwrap_or_default()
+}
+
+/// Record exports written for the installed skill `skill_key`
+fn remember_exports(
+    skill_key: &str,
+    exports: impl IntoIterator<Item = ExportArtifact>,
+) -> Result<()> {
+    let mut installed = load_installed_skills()?;
+    let Some(entry) = installed
+        .skills
+        .get_mut(skill_key)
+        .and_then(|entries| entries.first_mut())
+    else {
+        return Ok(());
+    };
+    let before = entry.exports.len();
+    for artifact in exports {
+        if !entry.exports.contains(&artifact) {
+            entry.exports.push(artifact);
+        }
+    }
+    if entry.exports.len() != before {
+        save_installed_skills(&installed)?;
+    }
+    Ok(())
+}
+
+/// Undo an export, returning what was removed (`None` if it was already
+/// gone). A file's directory goes too once it's empty; an `mcpServers` entry
+/// is taken out of its config leaving everything else as it was.
+fn remove_export(artifact: &ExportArtifact) -> Result<Option<String>> {
+    match artifact {
+        ExportArtifact::File { path } => {
+            let path = Path::new(path);
+            if !path.exists() {
+                return Ok(None);
+            }
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            if let Some(dir) = path.parent() {
+                // Only succeeds if nothing else is in it
+                let _ = fs::remove_dir(dir);
+            }
+            Ok(Some(path.display().to_string()))
+        }
+        ExportArtifact::McpServer { config, server } => {
+            let path = Path::new(config);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let content = fs::read_to_string(path)?;
+            let updated = if is_yaml(path) {
+                without_continue_server(&content, server)
+            } else {
+                without_json_server(&content, server)
+            }
+            .with_context(|| format!("Invalid {}", path.display()))?;
+            let Some(updated) = updated else {
+                return Ok(None);
+            };
+            fs::write(path, updated)?;
+            Ok(Some(format!("{} from {}", server, path.display())))
+        }
+    }
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+/// A JSON config without `server` in its `mcpServers` object, or `None` if
+/// it isn't there.
+fn without_json_server(content: &str, server: &str) -> Result<Option<String>> {
+    let mut value: serde_json::Value = serde_json::from_str(content)?;
+    let removed = value
+        .get_mut("mcpServers")
+        .and_then(|servers| servers.as_object_mut())
+        .and_then(|servers| servers.remove(server))
+        .is_some();
+    Ok(removed
+        .then(|| serde_json::to_string_pretty(&value))
+        .transpose()?)
+}
+
+/// Continue's config.yaml without the `mcpServers` entry named `server`, or
+/// `None` if it isn't there.
+fn without_continue_server(content: &str, server: &str) -> Result<Option<String>> {
+    let mut value: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let Some(servers) = value
+        .get_mut("mcpServers")
+        .and_then(|servers| servers.as_sequence_mut())
+    else {
+        return Ok(None);
+    };
+    let before = servers.len();
+    servers.retain(|entry| entry.get("name").and_then(|name| name.as_str()) != Some(server));
+    if servers.len() == before {
+        return Ok(None);
+    }
+    Ok(Some(serde_yaml::to_string(&value)?))
+}
+
+/// Remove a skill, along with what it exported for agents unless
+/// `keep_exports`
+pub fn remove(name: &str, keep_exports: bool) -> Result<()> {
     let mut installed = load_installed_skills()?;
 
     // Find the skill key
@@ -1566,10 +2618,29 @@ pub fn remove(name: &str) -> Result<()> {
                     if cache_path.exists() {
                         fs::remove_dir_all(cache_path)?;
                     }
+
+                    if keep_exports && !entry.exports.is_empty() {
+                        println!(
+                            "  {} Kept {} agent export(s) (--keep-exports)",
+                            "○".dimmed(),
+                            entry.exports.len()
+                        );
+                    } else if !keep_exports {
+                        for artifact in &entry.exports {
+                            match remove_export(artifact) {
+                                Ok(Some(removed)) => {
+                                    println!("  {} Removed {}", "✓".green(), removed)
+                                }
+                                Ok(None) => {}
+                                Err(e) => println!("  {} {:#}", "✗".red(), e),
+                            }
+                        }
+                    }
                 }
             }
 
             save_installed_skills(&installed)?;
+            skill_lock::forget(&[key])?;
 
             println!(
                 "{} Skill '{}' removed successfully.",
@@ -1586,7 +2657,11 @@ pub fn remove(name: &str) -> Result<()> {
 }
 
 /// Show skill info
-pub fn info(name: &str) -> Result<()> {
+pub fn info(name: &str, instructions: bool, raw: bool) -> Result<()> {
+    if instructions {
+        return readme(name, raw);
+    }
+
     let installed = load_installed_skills()?;
     let marketplaces = load_known_marketplaces()?;
 
@@ -1614,22 +2689,38 @@ pub fn info(name: &str) -> Result<()> {
                 println!("  Updated:   {}", entry.last_updated.dimmed());
 
                 // Try to load skill manifest for more info
-                let manifest_path = Path::new(&entry.install_path)
-                    .join("source")
-                    .join(".fgp")
-                    .join("skill.json");
-                if manifest_path.exists() {
-                    let content = fs::read_to_string(&manifest_path)?;
-                    let manifest: SkillManifest = serde_json::from_str(&content)?;
-                    println!();
-                    println!("  Description:");
-                    println!("    {}", manifest.description.dimmed());
-                    println!();
-                    println!("  Methods: {}", manifest.methods.len());
-                    for method in &manifest.methods {
-                        println!("    - {}", method.name);
+                let source = Path::new(&entry.install_path).join("source");
+                let daemon_name = match super::skill_manifest::find_manifest(&source) {
+                    // Tap layout: source/skill.yaml
+                    Some((_, super::skill_manifest::ManifestFormat::Yaml)) => {
+                        let skill = super::skill_manifest::load(&source)?;
+                        print_skill_yaml_details(&skill.root, &skill.manifest);
+                        legacy_daemon_name(&super::skill_manifest::to_legacy(skill.manifest))
                     }
-                }
+                    Some(_) => {
+                        let manifest = super::skill_manifest::load_legacy(&source)?;
+                        println!();
+                        println!("  Description:");
+                        println!("    {}", manifest.description.dimmed());
+                        println!();
+                        println!("  Methods: {}", manifest.methods.len());
+                        for method in &manifest.methods {
+                            println!("    - {}", method.name);
+                        }
+                        legacy_daemon_name(&manifest)
+                    }
+                    None => return Ok(()),
+                };
+
+                let registered: Vec<String> = registrations(&daemon_name, name)
+                    .into_iter()
+                    .map(|(agent, location)| match location {
+                        Some(_) => format!("{} {}", agent, "✓".green()),
+                        None => format!("{} {}", agent, "○".dimmed()),
+                    })
+                    .collect();
+                println!();
+                println!("  Registered: {}", registered.join("  "));
 
                 return Ok(());
             }
@@ -1673,6 +2764,180 @@ pub fn info(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Print what a skill.yaml declares: its daemons, workflows, trigger
+/// keywords and instruction files, marking files missing under `root`.
+fn print_skill_yaml_details(root: &Path, manifest: &super::skill_validate::SkillManifest) {
+    // ✓ or ✗, and a note for a file that isn't there
+    let file_status = |file: &str| {
+        if root.join(file).exists() {
+            ("✓".green(), "".normal())
+        } else {
+            ("✗".red(), " (missing)".red())
+        }
+    };
+
+    println!();
+    println!("  Description:");
+    println!("    {}", manifest.description.dimmed());
+
+    if !manifest.daemons.is_empty() {
+        println!();
+        println!("  Daemons:");
+        for daemon in &manifest.daemons {
+            let mut line = daemon.name.clone();
+            if let Some(ref version) = daemon.version {
+                line.push_str(&format!(" {}", version));
+            }
+            let optional = if daemon.optional { " (optional)" } else { "" };
+            println!("    - {}{}", line, optional.dimmed());
+            if !daemon.methods.is_empty() {
+                println!("      Methods: {}", daemon.methods.join(", ").dimmed());
+            }
+        }
+    }
+
+    if !manifest.workflows.is_empty() {
+        println!();
+        println!("  Workflows:");
+        let mut names: Vec<&String> = manifest.workflows.keys().collect();
+        names.sort();
+        for name in names {
+            let workflow = &manifest.workflows[name];
+            let default = if workflow.default { " (default)" } else { "" };
+            let (status, missing) = file_status(&workflow.file);
+            println!(
+                "    {} {}: {}{}{}",
+                status,
+                name,
+                workflow.file.dimmed(),
+                default,
+                missing
+            );
+            if let Some(ref description) = workflow.description {
+                println!("        {}", description.dimmed());
+            }
+        }
+    }
+
+    if let Some(ref triggers) = manifest.triggers {
+        if !triggers.keywords.is_empty() {
+            println!();
+            println!("  Triggers: {}", triggers.keywords.join(", ").dimmed());
+        }
+    }
+
+    if let Some(ref instructions) = manifest.instructions {
+        let files: Vec<(&str, &String)> = instructions
+            .files()
+            .into_iter()
+            .filter_map(|(key, _, path)| path.as_ref().map(|path| (key, path)))
+            .collect();
+        if !files.is_empty() {
+            println!();
+            println!("  Instructions:");
+            for (key, path) in files {
+                let (status, missing) = file_status(path);
+                println!("    {} {:<12} {}{}", status, key, path.dimmed(), missing);
+            }
+        }
+    }
+}
+
+/// Show a skill's instructions, rendered for the terminal unless `raw` is set
+pub fn readme(name: &str, raw: bool) -> Result<()> {
+    let markdown = match find_instructions(name)? {
+        Some(markdown) => markdown,
+        None => bail!(
+            "Skill '{}' not found. Run 'fgp skill search {}' to find it.",
+            name,
+            name
+        ),
+    };
+
+    if raw {
+        print!("{}", markdown);
+    } else {
+        super::markdown::page(&super::markdown::render(&markdown));
+    }
+
+    Ok(())
+}
+
+/// Locate a skill's instruction markdown.
+///
+/// Checks installed skills (tap layout, then legacy layout) before falling back
+/// to taps. When no instruction file exists, documentation is generated from
+/// the manifest instead.
+fn find_instructions(name: &str) -> Result<Option<String>> {
+    let installed = load_installed_skills()?;
+
+    for (skill_key, entries) in &installed.skills {
+        if !skill_key.starts_with(&format!("{}@", name)) {
+            continue;
+        }
+        let Some(entry) = entries.first() else {
+            continue;
+        };
+        let source = Path::new(&entry.install_path).join("source");
+
+        // Tap layout: source/skill.yaml
+        for manifest_name in ["skill.yaml", "skill.yml"] {
+            let manifest_path = source.join(manifest_name);
+            if manifest_path.exists() {
+                let content = fs::read_to_string(&manifest_path)?;
+                let manifest: super::skill_validate::SkillManifest = serde_yaml::from_str(&content)
+                    .with_context(|| format!("Invalid {}", manifest_path.display()))?;
+                return Ok(Some(tap_instructions(&source, &manifest)?));
+            }
+        }
+
+        // Legacy layout: source/.fgp/skill.json
+        let manifest_path = source.join(".fgp").join("skill.json");
+        if manifest_path.exists() {
+            for doc in ["SKILL.md", "README.md"] {
+                let doc_path = source.join(doc);
+                if doc_path.exists() {
+                    return Ok(Some(fs::read_to_string(&doc_path)?));
+                }
+            }
+
+            let content = fs::read_to_string(&manifest_path)?;
+            let manifest: SkillManifest = serde_json::from_str(&content)?;
+            return Ok(Some(generate_claude_skill_md(
+                &manifest,
+                &format!("{}-fgp", manifest.name),
+                &[],
+                &["Bash".to_string()],
+            )));
+        }
+    }
+
+    match skill_tap::find_skill(name)? {
+        Some((_, skill_path, manifest)) => Ok(Some(tap_instructions(&skill_path, &manifest)?)),
+        None => Ok(None),
+    }
+}
+
+/// Read a tap skill's core (or Claude Code) instructions, or generate them.
+fn tap_instructions(
+    skill_path: &Path,
+    manifest: &super::skill_validate::SkillManifest,
+) -> Result<String> {
+    if let Some(ref instructions) = manifest.instructions {
+        for file in [&instructions.core, &instructions.claude_code]
+            .into_iter()
+            .flatten()
+        {
+            let path = skill_path.join(file);
+            if path.exists() {
+                return Ok(fs::read_to_string(&path)?);
+            }
+        }
+    }
+
+    Ok(generate_skill_md_from_manifest(manifest))
+}
+
 // ============================================================================
 // MCP Bridge Registration
 // ============================================================================
@@ -1709,6 +2974,10 @@ fn default_protocol() -> String {
 #[derive(Debug, Serialize, Deserialize)]
 struct DaemonManifestConfig {
     entrypoint: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    start_command: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    stop_command: Vec<String>,
     socket: String,
     #[serde(default)]
     dependencies: Vec<String>,
@@ -1720,6 +2989,10 @@ struct DaemonManifestMethod {
     description: String,
     #[serde(default)]
     params: Vec<DaemonManifestParam>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    cost: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    rate_limit: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1728,11 +3001,18 @@ struct DaemonManifestParam {
     #[serde(rename = "type")]
     param_type: String,
     required: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    description: Option<String>,
 }
 
-/// Convert skill.json to manifest.json format for MCP server
+/// Convert skill.json to manifest.json format for MCP server.
+///
+/// A binary under the FGP home (where `fgp skill install` builds it) gets an
+/// entrypoint relative to the service directory and a `{service_dir}`-based
+/// start command, so the manifest keeps working if the home moves. The
+/// skill's own start and stop commands are used when it has them.
 fn skill_to_daemon_manifest(skill: &SkillManifest, binary_path: &str) -> DaemonManifest {
     let daemon_name = skill
         .daemon
@@ -1740,25 +3020,50 @@ fn skill_to_daemon_manifest(skill: &SkillManifest, binary_path: &str) -> DaemonM
         .map(|d| d.name.clone())
         .unwrap_or_else(|| skill.name.replace("-gateway", ""));
 
+    // The service directory is <home>/services/<name>
+    let (entrypoint, program) = match Path::new(binary_path).strip_prefix(fgp_home()) {
+        Ok(rest) => {
+            let entrypoint = format!("../../{}", rest.to_string_lossy());
+            let program = format!("{{service_dir}}/{}", entrypoint);
+            (entrypoint, program)
+        }
+        Err(_) => (
+            binary_path.to_string(),
+            binary_path.replace('{', "{{").replace('}', "}}"),
+        ),
+    };
+    let (start_command, stop_command) = match &skill.daemon {
+        Some(daemon) if !daemon.start_command.is_empty() => {
+            (daemon.start_command.clone(), daemon.stop_command.clone())
+        }
+        Some(daemon) => (vec![program], daemon.stop_command.clone()),
+        None => (vec![program], vec![]),
+    };
+
     let methods: Vec<DaemonManifestMethod> = skill
         .methods
         .iter()
         .map(|m| {
-            let params: Vec<DaemonManifestParam> = m
+            let mut params: Vec<DaemonManifestParam> = m
                 .params
                 .iter()
                 .map(|(name, def)| DaemonManifestParam {
                     name: name.clone(),
                     param_type: def.param_type.clone(),
                     required: def.required,
-                    default: None,
+                    default: def.default.clone(),
+                    description: def.description.clone(),
                 })
                 .collect();
+            // Stable order so re-registering produces identical manifests
+            params.sort_by(|a, b| a.name.cmp(&b.name));
 
             DaemonManifestMethod {
                 name: m.name.clone(),
                 description: m.description.clone().unwrap_or_default(),
                 params,
+                cost: m.cost.clone(),
+                rate_limit: m.rate_limit.clone(),
             }
         })
         .collect();
@@ -1772,7 +3077,9 @@ fn skill_to_daemon_manifest(skill: &SkillManifest, binary_path: &str) -> DaemonM
         license: skill.license.clone(),
         repository: skill.repository.clone(),
         daemon: DaemonManifestConfig {
-            entrypoint: binary_path.to_string(),
+            entrypoint,
+            start_command,
+            stop_command,
             socket: format!("{}/daemon.sock", daemon_name),
             dependencies: vec![],
         },
@@ -1782,8 +3089,58 @@ fn skill_to_daemon_manifest(skill: &SkillManifest, binary_path: &str) -> DaemonM
     }
 }
 
-/// Register an installed skill with the MCP server by creating manifest.json
-pub fn mcp_register(name: &str) -> Result<()> {
+/// What registering a skill with the MCP server would write.
+pub struct McpRegistration {
+    pub daemon_name: String,
+    pub manifest_path: PathBuf,
+    pub content: String,
+    pub tool_count: usize,
+    /// What's wrong with the skill's method definitions; `mcp register-all`
+    /// skips skills with any, `mcp register` writes them anyway
+    pub problems: Vec<String>,
+}
+
+/// Parameter types accepted in skill method definitions.
+const VALID_PARAM_TYPES: &[&str] = &[
+    "string", "number", "integer", "boolean", "array", "object", "any",
+];
+
+/// Installed skills with a binary whose MCP manifest is missing or differs
+/// from what registering them would write now.
+///
+/// The flag is `true` when a manifest exists and would be overwritten.
+pub fn stale_registrations() -> Result<Vec<(String, McpRegistration, bool)>> {
+    let installed = load_installed_skills()?;
+
+    let mut names: Vec<&str> = installed
+        .skills
+        .iter()
+        .filter(|(_, entries)| entries.iter().any(|e| e.binary_path.is_some()))
+        .filter_map(|(key, _)| key.split('@').next())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut stale = Vec::new();
+    for name in names {
+        // Skills that can't be registered are reported by `mcp register-all`
+        let registration = match plan_mcp_registration(name) {
+            Ok(registration) if registration.problems.is_empty() => registration,
+            _ => continue,
+        };
+        match fs::read_to_string(&registration.manifest_path) {
+            Ok(existing) if existing == registration.content => {}
+            Ok(_) => stale.push((name.to_string(), registration, true)),
+            Err(_) => stale.push((name.to_string(), registration, false)),
+        }
+    }
+
+    Ok(stale)
+}
+
+/// Build the manifest.json an installed skill would register, and check its
+/// method definitions.
+fn plan_mcp_registration(name: &str) -> Result<McpRegistration> {
     let installed = load_installed_skills()?;
 
     // Find the installed skill
@@ -1793,11 +3150,13 @@ pub fn mcp_register(name: &str) -> Result<()> {
         .find(|k| k.starts_with(&format!("{}@", name)))
         .cloned();
 
-    let (_key, entry) = match skill_key {
+    let entry = match skill_key {
         Some(k) => {
             let entries = installed.skills.get(&k).unwrap();
-            let entry = entries.first().context("No installation entry found")?;
-            (k, entry)
+            entries
+                .first()
+                .context("No installation entry found")?
+                .clone()
         }
         None => {
             bail!(
@@ -1808,21 +3167,9 @@ pub fn mcp_register(name: &str) -> Result<()> {
         }
     };
 
-    // Load skill.json
-    let skill_manifest_path = Path::new(&entry.install_path)
-        .join("source")
-        .join(".fgp")
-        .join("skill.json");
-
-    if !skill_manifest_path.exists() {
-        bail!(
-            "Skill manifest not found at {}",
-            skill_manifest_path.display()
-        );
-    }
-
-    let skill_content = fs::read_to_string(&skill_manifest_path)?;
-    let skill_manifest: SkillManifest = serde_json::from_str(&skill_content)?;
+    // Load skill manifest (skill.json or skill.yaml)
+    let skill_manifest =
+        super::skill_manifest::load_legacy(&Path::new(&entry.install_path).join("source"))?;
 
     // Get the daemon name
     let daemon_name = skill_manifest
@@ -1837,16 +3184,73 @@ pub fn mcp_register(name: &str) -> Result<()> {
         .as_ref()
         .context("No binary path found. Was the skill built correctly?")?;
 
+    let problems = validate_skill_methods(&skill_manifest, &daemon_name);
+
     // Create manifest.json for MCP server
     let daemon_manifest = skill_to_daemon_manifest(&skill_manifest, binary_path);
 
-    // Write to services directory
-    let services_dir = fgp_home().join("services").join(&daemon_name);
-    fs::create_dir_all(&services_dir)?;
+    Ok(McpRegistration {
+        manifest_path: fgp_home()
+            .join("services")
+            .join(&daemon_name)
+            .join("manifest.json"),
+        content: serde_json::to_string_pretty(&daemon_manifest)?,
+        tool_count: daemon_manifest.methods.len(),
+        daemon_name,
+        problems,
+    })
+}
 
-    let manifest_path = services_dir.join("manifest.json");
-    let manifest_content = serde_json::to_string_pretty(&daemon_manifest)?;
-    fs::write(&manifest_path, &manifest_content)?;
+/// Check that a skill's methods can be exposed as MCP tools.
+fn validate_skill_methods(skill: &SkillManifest, daemon_name: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if skill.methods.is_empty() {
+        problems.push("no methods defined".to_string());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for method in &skill.methods {
+        if method.name.trim().is_empty() {
+            problems.push("method with empty name".to_string());
+            continue;
+        }
+        if !seen.insert(method.name.as_str()) {
+            problems.push(format!("duplicate method '{}'", method.name));
+        }
+        if !method.name.starts_with(&format!("{}.", daemon_name)) {
+            problems.push(format!(
+                "method '{}' is not namespaced under '{}.'",
+                method.name, daemon_name
+            ));
+        }
+
+        let mut params: Vec<(&String, &ParamDef)> = method.params.iter().collect();
+        params.sort_by_key(|(name, _)| *name);
+        for (param, def) in params {
+            if !VALID_PARAM_TYPES.contains(&def.param_type.as_str()) {
+                problems.push(format!(
+                    "param '{}.{}' has unknown type '{}'",
+                    method.name, param, def.param_type
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Register an installed skill with the MCP server by creating manifest.json
+pub fn mcp_register(name: &str) -> Result<()> {
+    let registration = plan_mcp_registration(name)?;
+    let daemon_name = registration.daemon_name;
+
+    // Write to services directory
+    if let Some(services_dir) = registration.manifest_path.parent() {
+        fs::create_dir_all(services_dir)?;
+    }
+    let manifest_path = registration.manifest_path;
+    fs::write(&manifest_path, &registration.content)?;
 
     println!(
         "{} Registered '{}' with MCP server",
@@ -1864,35 +3268,153 @@ pub fn mcp_register(name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Register all installed skills with MCP server
-pub fn mcp_register_all() -> Result<()> {
+/// Register all installed skills with MCP server.
+///
+/// Every skill is validated before anything is written; invalid skills are
+/// listed and skipped, and manifests whose content is unchanged are left alone.
+pub fn mcp_register_all(dry_run: bool, only: Option<&str>) -> Result<()> {
     let installed = load_installed_skills()?;
 
-    if installed.skills.is_empty() {
-        println!("{}", "No skills installed.".yellow());
+    let mut skill_names: Vec<&str> = installed
+        .skills
+        .keys()
+        .filter_map(|key| key.split('@').next())
+        .filter(|name| {
+            only.map(|pattern| glob_match(pattern, name))
+                .unwrap_or(true)
+        })
+        .collect();
+    skill_names.sort();
+    skill_names.dedup();
+
+    if skill_names.is_empty() {
+        match only {
+            Some(pattern) => println!(
+                "{}",
+                format!("No installed skills match '{}'.", pattern).yellow()
+            ),
+            None => println!("{}", "No skills installed.".yellow()),
+        }
         return Ok(());
     }
 
-    println!("{}", "Registering all skills with MCP server...".bold());
+    println!(
+        "{}",
+        if dry_run {
+            "Previewing MCP registration (dry run)..."
+        } else {
+            "Registering all skills with MCP server..."
+        }
+        .bold()
+    );
     println!();
 
-    for skill_key in installed.skills.keys() {
-        let parts: Vec<&str> = skill_key.split('@').collect();
-        if parts.is_empty() {
-            continue;
+    // Plan everything up front so invalid skills never cause partial writes
+    let mut planned = Vec::new();
+    let mut invalid = Vec::new();
+    for skill_name in skill_names {
+        match plan_mcp_registration(skill_name) {
+            Ok(registration) if !registration.problems.is_empty() => invalid.push((
+                skill_name,
+                anyhow::anyhow!(
+                    "invalid method definitions: {}",
+                    registration.problems.join("; ")
+                ),
+            )),
+            Ok(registration) => planned.push((skill_name, registration)),
+            Err(e) => invalid.push((skill_name, e)),
         }
-        let skill_name = parts[0];
+    }
 
-        print!("  {} ", skill_name.cyan());
-        match mcp_register(skill_name) {
-            Ok(()) => {} // Already prints success
-            Err(e) => println!("{} {}", "✗ failed:".red(), e),
-        }
+    let mut written = 0;
+    let mut unchanged = 0;
+
+    for (skill_name, registration) in &planned {
+        let is_unchanged = fs::read_to_string(&registration.manifest_path)
+            .map(|existing| existing == registration.content)
+            .unwrap_or(false);
+
+        let status = if is_unchanged {
+            unchanged += 1;
+            "unchanged".dimmed()
+        } else if dry_run {
+            written += 1;
+            "would write".cyan()
+        } else {
+            if let Some(dir) = registration.manifest_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&registration.manifest_path, &registration.content)?;
+            written += 1;
+            "written".green()
+        };
+
+        println!(
+            "  {} {} → {} ({} tools) [{}]",
+            "✓".green(),
+            skill_name.cyan(),
+            registration.daemon_name,
+            registration.tool_count,
+            status
+        );
+        println!(
+            "      {}",
+            registration.manifest_path.display().to_string().dimmed()
+        );
+    }
+
+    for (skill_name, error) in &invalid {
+        println!("  {} {} skipped: {}", "✗".red(), skill_name.cyan(), error);
     }
 
+    println!();
+    println!(
+        "{} {}, {} skipped (invalid), {} unchanged",
+        if invalid.is_empty() {
+            "✓".green().bold()
+        } else {
+            "!".yellow().bold()
+        },
+        if dry_run {
+            format!("{} would be written", written)
+        } else {
+            format!("{} written", written)
+        },
+        invalid.len(),
+        unchanged
+    );
+
     Ok(())
 }
 
+/// Match a name against a shell-style glob (`*` and `?`).
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Iterative matcher with single-star backtracking
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 /// List MCP-registered skills
 pub fn mcp_list() -> Result<()> {
     let services_dir = fgp_home().join("services");
@@ -1965,10 +3487,11 @@ pub fn export_skill(name: &str, targets: &[ExportTarget], binary_path: Option<&s
         .find(|k| k.starts_with(&format!("{}@", name)))
         .cloned();
 
-    let entry = match skill_key {
+    let (skill_key, entry) = match skill_key {
         Some(k) => {
             let entries = installed.skills.get(&k).unwrap();
-            entries.first().context("No installation entry found")?
+            let entry = entries.first().context("No installation entry found")?;
+            (k, entry)
         }
         None => {
             bail!(
@@ -1979,21 +3502,8 @@ pub fn export_skill(name: &str, targets: &[ExportTarget], binary_path: Option<&s
         }
     };
 
-    // Load skill.json
-    let skill_manifest_path = Path::new(&entry.install_path)
-        .join("source")
-        .join(".fgp")
-        .join("skill.json");
-
-    if !skill_manifest_path.exists() {
-        bail!(
-            "Skill manifest not found at {}",
-            skill_manifest_path.display()
-        );
-    }
-
-    let skill_content = fs::read_to_string(&skill_manifest_path)?;
-    let skill: SkillManifest = serde_json::from_str(&skill_content)?;
+    // Load skill manifest (skill.json or skill.yaml)
+    let skill = super::skill_manifest::load_legacy(&Path::new(&entry.install_path).join("source"))?;
 
     let bin_path = binary_path
         .map(|s| s.to_string())
@@ -2006,26 +3516,36 @@ pub fn export_skill(name: &str, targets: &[ExportTarget], binary_path: Option<&s
         targets.to_vec()
     };
 
+    // Remember what was written even if a later target fails
+    let mut exports = Vec::new();
+    let mut result = Ok(());
     for target in actual_targets {
-        match target {
-            ExportTarget::Mcp => {
-                if let Some(ref bp) = bin_path {
-                    export_to_mcp(&skill, bp)?;
-                }
+        let artifact = match target {
+            ExportTarget::Mcp => match bin_path {
+                Some(ref bp) => export_to_mcp(&skill, bp),
+                None => Ok(None),
+            },
+            ExportTarget::Claude => export_to_claude(&skill),
+            ExportTarget::Cursor => export_to_cursor(&skill),
+            ExportTarget::ContinueDev => export_to_continue(&skill),
+            ExportTarget::Windsurf => export_to_windsurf(&skill),
+            ExportTarget::All => Ok(None), // Already expanded
+        };
+        match artifact {
+            Ok(artifact) => exports.extend(artifact),
+            Err(e) => {
+                result = Err(e);
+                break;
             }
-            ExportTarget::Claude => export_to_claude(&skill)?,
-            ExportTarget::Cursor => export_to_cursor(&skill)?,
-            ExportTarget::ContinueDev => export_to_continue(&skill)?,
-            ExportTarget::Windsurf => export_to_windsurf(&skill)?,
-            ExportTarget::All => {} // Already expanded
         }
     }
+    remember_exports(&skill_key, exports)?;
 
-    Ok(())
+    result
 }
 
 /// Export to MCP (FGP daemon manifest)
-fn export_to_mcp(skill: &SkillManifest, binary_path: &str) -> Result<()> {
+fn export_to_mcp(skill: &SkillManifest, binary_path: &str) -> Result<Option<ExportArtifact>> {
     let daemon_name = skill
         .daemon
         .as_ref()
@@ -2040,11 +3560,13 @@ fn export_to_mcp(skill: &SkillManifest, binary_path: &str) -> Result<()> {
     fs::write(&manifest_path, &manifest_json)?;
 
     println!("  {} MCP: {}", "✓".green(), manifest_path.display());
-    Ok(())
+    Ok(Some(ExportArtifact::File {
+        path: manifest_path.to_string_lossy().to_string(),
+    }))
 }
 
 /// Export to Claude Code (SKILL.md)
-fn export_to_claude(skill: &SkillManifest) -> Result<()> {
+fn export_to_claude(skill: &SkillManifest) -> Result<Option<ExportArtifact>> {
     let daemon_name = skill
         .daemon
         .as_ref()
@@ -2056,7 +3578,7 @@ fn export_to_claude(skill: &SkillManifest) -> Result<()> {
         if let Some(ref claude) = exports.claude {
             if !claude.enabled {
                 println!("  {} Claude: disabled in skill.json", "○".dimmed());
-                return Ok(());
+                return Ok(None);
             }
             (
                 claude
@@ -2096,7 +3618,9 @@ fn export_to_claude(skill: &SkillManifest) -> Result<()> {
     fs::write(&skill_md_path, &skill_md)?;
 
     println!("  {} Claude: {}", "✓".green(), skill_md_path.display());
-    Ok(())
+    Ok(Some(ExportArtifact::File {
+        path: skill_md_path.to_string_lossy().to_string(),
+    }))
 }
 
 /// Generate Claude Code SKILL.md content
@@ -2171,12 +3695,16 @@ fn generate_claude_skill_md(
         let desc = method.description.as_deref().unwrap_or("");
         md.push_str(&format!("### {} - {}\n\n", method.name, desc));
 
+        // Params in name order, so the document is the same on every run
+        let mut params: Vec<_> = method.params.iter().collect();
+        params.sort_by_key(|(name, _)| *name);
+
         // Parameters table
-        if !method.params.is_empty() {
+        if !params.is_empty() {
             md.push_str("**Parameters:**\n");
             md.push_str("| Parameter | Type | Required | Description |\n");
             md.push_str("|-----------|------|----------|-------------|\n");
-            for (name, param) in &method.params {
+            for (name, param) in &params {
                 let param_desc = param.description.as_deref().unwrap_or("-");
                 md.push_str(&format!(
                     "| `{}` | {} | {} | {} |\n",
@@ -2195,17 +3723,13 @@ fn generate_claude_skill_md(
             md.push_str(&format!("fgp call {}\n", method.name));
         } else {
             // Build example params
-            let example_params: Vec<String> = method
-                .params
+            let example_params: Vec<String> = params
                 .iter()
                 .filter(|(_, p)| p.required)
                 .map(|(name, p)| {
-                    let val = match p.param_type.as_str() {
-                        "string" => format!("\"<{}>\"", name),
-                        "integer" | "number" => "0".to_string(),
-                        "boolean" => "true".to_string(),
-                        _ => "null".to_string(),
-                    };
+                    let val = p.default.clone().unwrap_or_else(|| {
+                        super::skill_export::example_value(0, &method.name, name, &p.param_type)
+                    });
                     format!("\"{}\": {}", name, val)
                 })
                 .collect();
@@ -2231,8 +3755,18 @@ fn generate_claude_skill_md(
     md
 }
 
+/// The `mcpServers` entry that serves `daemon` through FGP, as Cursor and
+/// Claude Desktop expect it
+pub(crate) fn fgp_mcp_server(daemon: &str) -> serde_json::Value {
+    serde_json::json!({
+        "command": "fgp",
+        "args": ["mcp", "serve", "--service", daemon],
+        "env": {}
+    })
+}
+
 /// Export to Cursor (mcp.json entry)
-fn export_to_cursor(skill: &SkillManifest) -> Result<()> {
+fn export_to_cursor(skill: &SkillManifest) -> Result<Option<ExportArtifact>> {
     let daemon_name = skill
         .daemon
         .as_ref()
@@ -2244,7 +3778,7 @@ fn export_to_cursor(skill: &SkillManifest) -> Result<()> {
         if let Some(ref cursor) = exports.cursor {
             if !cursor.enabled {
                 println!("  {} Cursor: disabled in skill.json", "○".dimmed());
-                return Ok(());
+                return Ok(None);
             }
             cursor
                 .server_name
@@ -2273,15 +3807,9 @@ fn export_to_cursor(skill: &SkillManifest) -> Result<()> {
     };
 
     // Add FGP server entry
-    let server_entry = serde_json::json!({
-        "command": "fgp",
-        "args": ["mcp", "--service", &daemon_name],
-        "env": {}
-    });
-
     if let Some(servers) = mcp_config.get_mut("mcpServers") {
         if let Some(obj) = servers.as_object_mut() {
-            obj.insert(server_name.clone(), server_entry);
+            obj.insert(server_name.clone(), fgp_mcp_server(&daemon_name));
         }
     }
 
@@ -2295,58 +3823,160 @@ fn export_to_cursor(skill: &SkillManifest) -> Result<()> {
         server_name,
         mcp_json_path.display()
     );
-    Ok(())
+    Ok(Some(ExportArtifact::McpServer {
+        config: mcp_json_path.to_string_lossy().to_string(),
+        server: server_name,
+    }))
 }
 
 /// Export to Continue.dev (config.yaml provider)
-fn export_to_continue(skill: &SkillManifest) -> Result<()> {
+fn export_to_continue(skill: &SkillManifest) -> Result<Option<ExportArtifact>> {
     // Check if enabled
     if let Some(ref exports) = skill.exports {
         if let Some(ref continue_cfg) = exports.continue_dev {
             if !continue_cfg.enabled {
                 println!("  {} Continue: disabled in skill.json", "○".dimmed());
-                return Ok(());
+                return Ok(None);
             }
         } else {
             println!("  {} Continue: not configured in skill.json", "○".dimmed());
-            return Ok(());
+            return Ok(None);
         }
     } else {
         println!("  {} Continue: not configured in skill.json", "○".dimmed());
-        return Ok(());
+        return Ok(None);
     }
 
-    let daemon_name = skill
-        .daemon
-        .as_ref()
-        .map(|d| d.name.clone())
-        .unwrap_or_else(|| skill.name.replace("-gateway", ""));
+    let daemon_name = legacy_daemon_name(skill);
+    let server_name = format!("fgp-{}", daemon_name);
+
+    let config_path = continue_config_path()?;
+    let config = load_continue_config(&config_path, false)?;
+    let config = merge_continue_config(config, &[daemon_name])
+        .with_context(|| format!("Can't update {}", config_path.display()))?;
+    if let Some(dir) = config_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&config_path, config)?;
 
-    // Continue.dev doesn't have a stable format yet - log as TODO
     println!(
-        "  {} Continue: format TBD (daemon: {})",
-        "⚠".yellow(),
-        daemon_name
+        "  {} Continue: {} in {}",
+        "✓".green(),
+        server_name,
+        config_path.display()
     );
-    Ok(())
+    Ok(Some(ExportArtifact::McpServer {
+        config: config_path.to_string_lossy().to_string(),
+        server: server_name,
+    }))
+}
+
+/// Continue's config file, `~/.continue/config.yaml`.
+pub(crate) fn continue_config_path() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
+        .context("Could not find home directory")?
+        .join(".continue")
+        .join("config.yaml"))
+}
+
+/// Continue's current config, or `None` to start a new one: when there's no
+/// config yet, or it isn't one Continue could read either. Unless `dry_run`,
+/// an unreadable config is saved to `config.yaml.bak` first.
+pub(crate) fn load_continue_config(
+    path: &Path,
+    dry_run: bool,
+) -> Result<Option<serde_yaml::Value>> {
+    let existing = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let config = parse_continue_config(Some(&existing));
+    if config.is_none() && !existing.trim().is_empty() {
+        let backup = path.with_extension("yaml.bak");
+        if !dry_run {
+            fs::copy(path, &backup)
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+        }
+        println!(
+            "  {} {} isn't a valid Continue config; starting a new one (the old one goes to {})",
+            "!".yellow(),
+            path.display(),
+            backup.display()
+        );
+    }
+    Ok(config)
+}
+
+/// The content of Continue's config.yaml, if it's a YAML mapping as Continue
+/// expects.
+fn parse_continue_config(content: Option<&str>) -> Option<serde_yaml::Value> {
+    content
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(content).ok())
+        .filter(serde_yaml::Value::is_mapping)
+}
+
+/// Continue's config (a new one if `config` is `None`) with an `mcpServers`
+/// entry named `fgp-<daemon>` for each of `daemons`, replacing any of the same
+/// name. Other servers and settings are left as they were.
+pub(crate) fn merge_continue_config(
+    config: Option<serde_yaml::Value>,
+    daemons: &[String],
+) -> Result<String> {
+    let mut config = config.unwrap_or_else(|| {
+        let mut fresh = serde_yaml::Mapping::new();
+        fresh.insert("name".into(), "Local Assistant".into());
+        fresh.insert("version".into(), "1.0.0".into());
+        fresh.insert("schema".into(), "v1".into());
+        serde_yaml::Value::Mapping(fresh)
+    });
+    let Some(config_map) = config.as_mapping_mut() else {
+        bail!("Expected a YAML mapping");
+    };
+    let servers = config_map
+        .entry("mcpServers".into())
+        .or_insert_with(|| serde_yaml::Value::Sequence(Vec::new()))
+        .as_sequence_mut()
+        .context("Expected mcpServers to be a list")?;
+
+    for daemon in daemons {
+        let server = format!("fgp-{}", daemon);
+        let mut entry = serde_yaml::Mapping::new();
+        entry.insert("name".into(), server.as_str().into());
+        entry.insert("command".into(), "fgp".into());
+        // The same command line as the Claude Desktop and Cursor entries
+        entry.insert(
+            "args".into(),
+            serde_yaml::to_value(&fgp_mcp_server(daemon)["args"])?,
+        );
+        let entry = serde_yaml::Value::Mapping(entry);
+        match servers.iter_mut().find(|existing| {
+            existing.get("name").and_then(|name| name.as_str()) == Some(server.as_str())
+        }) {
+            Some(existing) => *existing = entry,
+            None => servers.push(entry),
+        }
+    }
+
+    Ok(serde_yaml::to_string(&config)?)
 }
 
 /// Export to Windsurf (markdown skill)
-fn export_to_windsurf(skill: &SkillManifest) -> Result<()> {
+fn export_to_windsurf(skill: &SkillManifest) -> Result<Option<ExportArtifact>> {
     // Check if enabled
     if let Some(ref exports) = skill.exports {
         if let Some(ref windsurf) = exports.windsurf {
             if !windsurf.enabled {
                 println!("  {} Windsurf: disabled in skill.json", "○".dimmed());
-                return Ok(());
+                return Ok(None);
             }
         } else {
             println!("  {} Windsurf: not configured in skill.json", "○".dimmed());
-            return Ok(());
+            return Ok(None);
         }
     } else {
         println!("  {} Windsurf: not configured in skill.json", "○".dimmed());
-        return Ok(());
+        return Ok(None);
     }
 
     let daemon_name = skill
@@ -2374,7 +4004,9 @@ fn export_to_windsurf(skill: &SkillManifest) -> Result<()> {
     fs::write(&skill_md_path, &skill_md)?;
 
     println!("  {} Windsurf: {}", "✓".green(), skill_md_path.display());
-    Ok(())
+    Ok(Some(ExportArtifact::File {
+        path: skill_md_path.to_string_lossy().to_string(),
+    }))
 }
 
 /// Register skill with multiple targets (CLI entry point)
@@ -2425,85 +4057,446 @@ pub fn registration_status(name: &str) -> Result<()> {
         }
     };
 
-    // Load skill.json
-    let skill_manifest_path = Path::new(&entry.install_path)
-        .join("source")
-        .join(".fgp")
-        .join("skill.json");
+    // Load skill manifest (skill.json or skill.yaml)
+    let skill = super::skill_manifest::load_legacy(&Path::new(&entry.install_path).join("source"))?;
 
-    let skill: SkillManifest = if skill_manifest_path.exists() {
-        let content = fs::read_to_string(&skill_manifest_path)?;
-        serde_json::from_str(&content)?
-    } else {
-        bail!("Skill manifest not found");
+    let daemon_name = legacy_daemon_name(&skill);
+
+    println!("{} v{}", name.cyan().bold(), skill.version);
+    println!();
+
+    let [mcp, claude, cursor, continue_dev, windsurf] = registrations(&daemon_name, &skill.name);
+    let line = |branch: &str, agent: &str, location: Option<String>| match location {
+        Some(location) => println!(
+            "  {} {:<9} {} {}",
+            branch,
+            format!("{}:", agent),
+            "✓".green(),
+            location
+        ),
+        None => println!(
+            "  {} {:<9} {} not registered",
+            branch,
+            format!("{}:", agent),
+            "○".dimmed()
+        ),
     };
+    line("├─", mcp.0, mcp.1);
+    line("├─", claude.0, claude.1);
+    line("├─", cursor.0, cursor.1);
+    line("├─", continue_dev.0, continue_dev.1);
+    line("└─", windsurf.0, windsurf.1);
 
-    let daemon_name = skill
+    Ok(())
+}
+
+/// The daemon a legacy manifest registers, named after the skill if it
+/// doesn't say
+fn legacy_daemon_name(skill: &SkillManifest) -> String {
+    skill
         .daemon
         .as_ref()
         .map(|d| d.name.clone())
-        .unwrap_or_else(|| skill.name.replace("-gateway", ""));
+        .unwrap_or_else(|| skill.name.replace("-gateway", ""))
+}
 
-    println!("{} v{}", name.cyan().bold(), skill.version);
-    println!();
+/// Where a skill's daemon is registered with each agent, as (agent,
+/// location), with no location where it isn't. A SKILL.md export is named
+/// after the daemon, or after the skill for tap skills.
+fn registrations(daemon_name: &str, skill_name: &str) -> [(&'static str, Option<String>); 5] {
+    let home = dirs::home_dir().unwrap();
+    let skill_dirs = [
+        format!("{}-fgp", daemon_name),
+        format!("{}-fgp", skill_name),
+    ];
+    let skill_md = |agent_dir: &str| {
+        skill_dirs
+            .iter()
+            .map(|dir| {
+                home.join(agent_dir)
+                    .join("skills")
+                    .join(dir)
+                    .join("SKILL.md")
+            })
+            .find(|path| path.exists())
+            .map(|path| path.display().to_string())
+    };
 
-    // Check MCP
     let mcp_manifest = fgp_home()
         .join("services")
-        .join(&daemon_name)
+        .join(daemon_name)
         .join("manifest.json");
-    if mcp_manifest.exists() {
-        println!("  ├─ mcp:      {} {}", "✓".green(), mcp_manifest.display());
-    } else {
-        println!("  ├─ mcp:      {} not registered", "○".dimmed());
+    let server = format!("fgp-{}", daemon_name);
+    let cursor = fs::read_to_string(home.join(".cursor").join("mcp.json"))
+        .ok()
+        .filter(|content| content.contains(&server))
+        .map(|_| server.clone());
+    let continue_dev = fs::read_to_string(home.join(".continue").join("config.yaml"))
+        .ok()
+        .filter(|content| content.contains(&server))
+        .map(|_| server.clone());
+
+    [
+        (
+            "mcp",
+            mcp_manifest
+                .exists()
+                .then(|| mcp_manifest.display().to_string()),
+        ),
+        ("claude", skill_md(".claude")),
+        ("cursor", cursor),
+        ("continue", continue_dev),
+        ("windsurf", skill_md(".windsurf")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("gmail*", "gmail"));
+        assert!(glob_match("gmail*", "gmail-gateway"));
+        assert!(glob_match("*-gateway", "slack-gateway"));
+        assert!(glob_match("g?ail", "gmail"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(!glob_match("gmail*", "slack"));
+        assert!(!glob_match("g?ail", "gmmail"));
     }
 
-    // Check Claude
-    let claude_skill = dirs::home_dir()
-        .unwrap()
-        .join(".claude")
-        .join("skills")
-        .join(format!("{}-fgp", daemon_name))
-        .join("SKILL.md");
-    if claude_skill.exists() {
-        println!("  ├─ claude:   {} {}", "✓".green(), claude_skill.display());
-    } else {
-        println!("  ├─ claude:   {} not registered", "○".dimmed());
+    #[test]
+    fn test_search_ranking() {
+        let keywords = ["email".to_string(), "gmail-api".to_string()];
+        let score = |query: &str, name: &str| relevance(query, name, &keywords, "Reads your inbox");
+        assert_eq!(score("gmail", "gmail"), Some(100));
+        assert_eq!(score("gmail", "Gmail-Triage"), Some(80));
+        assert_eq!(score("triage", "gmail-triage"), Some(60));
+        assert_eq!(score("email", "inbox-zero"), Some(50));
+        assert_eq!(score("api", "inbox-zero"), Some(40));
+        assert_eq!(score("inbox", "zero"), Some(20));
+        assert_eq!(score("slack", "gmail"), None);
+
+        let result = |name: &str, source_kind: &'static str, score: u32| SearchResult {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            source: format!("acme-{}", source_kind),
+            source_kind,
+            keywords: Vec::new(),
+            daemons: Vec::new(),
+            installed: false,
+            score,
+        };
+        let ranked = rank(vec![
+            result("gmail-send", "marketplace", 80),
+            result("gmail", "marketplace", 100),
+            result("gmail-triage", "tap", 80),
+            result("gmail", "tap", 100),
+        ]);
+        let order: Vec<(&str, &str)> = ranked
+            .iter()
+            .map(|r| (r.name.as_str(), r.source_kind))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("gmail", "tap"),
+                ("gmail-send", "marketplace"),
+                ("gmail-triage", "tap")
+            ]
+        );
     }
 
-    // Check Cursor
-    let cursor_mcp = dirs::home_dir().unwrap().join(".cursor").join("mcp.json");
-    let cursor_registered = if cursor_mcp.exists() {
-        let content = fs::read_to_string(&cursor_mcp).unwrap_or_default();
-        content.contains(&format!("fgp-{}", daemon_name))
-    } else {
-        false
-    };
-    if cursor_registered {
-        println!("  ├─ cursor:   {} fgp-{}", "✓".green(), daemon_name);
-    } else {
-        println!("  ├─ cursor:   {} not registered", "○".dimmed());
+    #[test]
+    fn test_install_all_filters() {
+        let manifest: super::super::skill_validate::SkillManifest = serde_yaml::from_str(
+            "name: gmail-triage\nversion: 1.0.0\ndescription: Triage\nauthor: Jane\nkeywords: [Email, productivity]\n",
+        )
+        .unwrap();
+        assert!(matches_filters(&manifest, None, None));
+        assert!(matches_filters(&manifest, Some("email"), Some("gmail-*")));
+        assert!(!matches_filters(&manifest, Some("calendar"), None));
+        assert!(!matches_filters(&manifest, None, Some("slack-*")));
     }
 
-    // Check Continue
-    println!("  ├─ continue: {} not supported yet", "○".dimmed());
+    #[test]
+    fn test_validate_skill_methods() {
+        let skill: SkillManifest = serde_json::from_value(serde_json::json!({
+            "name": "gmail-gateway",
+            "version": "1.0.0",
+            "description": "Gmail",
+            "author": { "name": "Jane" },
+            "methods": [
+                { "name": "gmail.inbox", "params": { "limit": { "type": "integer" } } },
+                { "name": "gmail.inbox" },
+                { "name": "send" },
+                { "name": "gmail.search", "params": { "q": { "type": "text" } } }
+            ]
+        }))
+        .unwrap();
+
+        let problems = validate_skill_methods(&skill, "gmail");
+        assert_eq!(
+            problems,
+            vec![
+                "duplicate method 'gmail.inbox'",
+                "method 'send' is not namespaced under 'gmail.'",
+                "param 'gmail.search.q' has unknown type 'text'",
+            ]
+        );
+    }
 
-    // Check Windsurf
-    let windsurf_skill = dirs::home_dir()
-        .unwrap()
-        .join(".windsurf")
-        .join("skills")
-        .join(format!("{}-fgp", daemon_name))
-        .join("SKILL.md");
-    if windsurf_skill.exists() {
-        println!(
-            "  └─ windsurf: {} {}",
-            "✓".green(),
-            windsurf_skill.display()
+    #[test]
+    fn test_plan_upgrade() {
+        let constraint = VersionReq::default_for("1.2.0");
+        let plan = |available: &str, latest| {
+            plan_upgrade("1.2.0", &constraint, Some(available.to_string()), latest)
+        };
+
+        assert_eq!(plan("1.4.1", false), UpgradePlan::To("1.4.1".to_string()));
+        assert_eq!(plan("1.2", false), UpgradePlan::UpToDate);
+        assert_eq!(plan("1.1.0", true), UpgradePlan::UpToDate);
+        assert_eq!(
+            plan("2.0.0", false),
+            UpgradePlan::Held {
+                available: "2.0.0".to_string(),
+                constraint: "^1.2".to_string(),
+            }
+        );
+        assert_eq!(plan("2.0.0", true), UpgradePlan::To("2.0.0".to_string()));
+        assert_eq!(
+            plan_upgrade("1.2.0", &constraint, None, true),
+            UpgradePlan::Missing
         );
-    } else {
-        println!("  └─ windsurf: {} not registered", "○".dimmed());
     }
 
-    Ok(())
+    #[test]
+    fn test_check_dependencies() {
+        let daemons: Vec<DaemonDependency> = serde_yaml::from_str(
+            "- name: browser\n\
+             - name: gmail\n\
+             - name: calendar\n  optional: true\n\
+             - name: gmail\n  optional: true\n\
+             - name: slack\n",
+        )
+        .unwrap();
+        let statuses = check_dependencies(
+            &daemons,
+            |daemon| daemon == "browser",
+            |daemon| {
+                (daemon == "gmail").then(|| ("official".to_string(), "gmail-gateway".to_string()))
+            },
+        );
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("browser".to_string(), DependencyStatus::Available),
+                (
+                    "gmail".to_string(),
+                    DependencyStatus::Installable {
+                        marketplace: "official".to_string(),
+                        skill: "gmail-gateway".to_string(),
+                    }
+                ),
+                ("calendar".to_string(), DependencyStatus::OptionalMissing),
+                ("slack".to_string(), DependencyStatus::Unavailable),
+            ]
+        );
+    }
+    #[test]
+    fn test_skill_to_daemon_manifest() {
+        let skill: SkillManifest = serde_json::from_str(
+            r#"{
+                "name": "gmail-gateway",
+                "version": "1.0.0",
+                "description": "Gmail",
+                "author": { "name": "Jane" },
+                "methods": [{
+                    "name": "gmail.inbox",
+                    "description": "List inbox messages",
+                    "params": {
+                        "limit": { "type": "integer", "description": "Most to return", "default": 10 },
+                        "query": { "type": "string", "required": true }
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let manifest = skill_to_daemon_manifest(&skill, "/usr/local/bin/gmail-daemon");
+        assert_eq!(
+            serde_json::to_string_pretty(&manifest).unwrap(),
+            r#"{
+  "name": "gmail",
+  "version": "1.0.0",
+  "description": "Gmail",
+  "protocol": "fgp@1",
+  "author": "Jane",
+  "license": null,
+  "repository": null,
+  "daemon": {
+    "entrypoint": "/usr/local/bin/gmail-daemon",
+    "start_command": [
+      "/usr/local/bin/gmail-daemon"
+    ],
+    "socket": "gmail/daemon.sock",
+    "dependencies": []
+  },
+  "methods": [
+    {
+      "name": "gmail.inbox",
+      "description": "List inbox messages",
+      "params": [
+        {
+          "name": "limit",
+          "type": "integer",
+          "required": false,
+          "default": 10,
+          "description": "Most to return"
+        },
+        {
+          "name": "query",
+          "type": "string",
+          "required": true
+        }
+      ]
+    }
+  ],
+  "platforms": [
+    "darwin",
+    "linux"
+  ]
+}"#
+        );
+
+        // A binary fgp built under its home is found from the service directory
+        let binary = fgp_home().join("skills/cache/official/gmail-gateway/1.0.0/gmail-gateway");
+        let manifest = skill_to_daemon_manifest(&skill, &binary.to_string_lossy());
+        assert_eq!(
+            manifest.daemon.entrypoint,
+            "../../skills/cache/official/gmail-gateway/1.0.0/gmail-gateway"
+        );
+        assert_eq!(
+            manifest.daemon.start_command,
+            vec!["{service_dir}/../../skills/cache/official/gmail-gateway/1.0.0/gmail-gateway"]
+        );
+        assert!(super::super::daemon_command::check(&manifest.daemon.start_command).is_empty());
+
+        // Manifests written before params carried descriptions still load
+        let old: DaemonManifest = serde_json::from_str(
+            r#"{"name": "gmail", "daemon": {"entrypoint": "gmail", "socket": "gmail/daemon.sock"},
+                "methods": [{"name": "gmail.inbox", "description": "",
+                             "params": [{"name": "limit", "type": "integer", "required": false}]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(old.methods[0].params[0].description, None);
+        assert_eq!(old.methods[0].params[0].default, None);
+    }
+
+    #[test]
+    fn test_remove_export() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = |name: &str| tmp.path().join(name).to_string_lossy().to_string();
+
+        // A SKILL.md goes with its directory, unless something else is in it
+        fs::create_dir_all(tmp.path().join("gmail-fgp")).unwrap();
+        fs::write(path("gmail-fgp/SKILL.md"), "# Gmail").unwrap();
+        let skill_md = ExportArtifact::File {
+            path: path("gmail-fgp/SKILL.md"),
+        };
+        assert_eq!(
+            remove_export(&skill_md).unwrap(),
+            Some(path("gmail-fgp/SKILL.md"))
+        );
+        assert!(!tmp.path().join("gmail-fgp").exists());
+        assert_eq!(remove_export(&skill_md).unwrap(), None);
+
+        fs::create_dir_all(tmp.path().join("gmail")).unwrap();
+        fs::write(path("gmail/manifest.json"), "{}").unwrap();
+        fs::write(path("gmail/config.toml"), "").unwrap();
+        let manifest = ExportArtifact::File {
+            path: path("gmail/manifest.json"),
+        };
+        assert!(remove_export(&manifest).unwrap().is_some());
+        assert!(tmp.path().join("gmail/config.toml").exists());
+
+        // Only the skill's own server leaves mcp.json
+        fs::write(
+            path("mcp.json"),
+            r#"{"mcpServers": {"fgp-gmail": {"command": "fgp"}, "github": {"command": "gh-mcp"}}, "theme": "dark"}"#,
+        )
+        .unwrap();
+        let server = ExportArtifact::McpServer {
+            config: path("mcp.json"),
+            server: "fgp-gmail".to_string(),
+        };
+        assert_eq!(
+            remove_export(&server).unwrap(),
+            Some(format!("fgp-gmail from {}", path("mcp.json")))
+        );
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path("mcp.json")).unwrap()).unwrap();
+        assert_eq!(
+            config,
+            serde_json::json!({"mcpServers": {"github": {"command": "gh-mcp"}}, "theme": "dark"})
+        );
+        assert_eq!(remove_export(&server).unwrap(), None);
+
+        // Entries written before exports were tracked still load
+        let entry: InstalledSkill = serde_json::from_str(
+            r#"{"scope": "user", "installPath": "/tmp/x", "version": "1.0.0",
+                "installedAt": "", "lastUpdated": "", "gitCommitSha": null, "binaryPath": null}"#,
+        )
+        .unwrap();
+        assert!(entry.exports.is_empty());
+    }
+
+    #[test]
+    fn test_continue_config() {
+        let gmail = ["gmail".to_string()];
+        let fresh: serde_yaml::Value =
+            serde_yaml::from_str(&merge_continue_config(None, &gmail).unwrap()).unwrap();
+        assert_eq!(fresh["schema"], "v1");
+        assert_eq!(fresh["mcpServers"][0]["name"], "fgp-gmail");
+        assert_eq!(fresh["mcpServers"][0]["command"], "fgp");
+        assert_eq!(
+            fresh["mcpServers"][0]["args"],
+            serde_yaml::from_str::<serde_yaml::Value>("[mcp, serve, --service, gmail]").unwrap()
+        );
+        assert_eq!(
+            serde_yaml::to_value(&fgp_mcp_server("gmail")["args"]).unwrap(),
+            fresh["mcpServers"][0]["args"]
+        );
+
+        // Other servers and settings stay; a server of the same name is replaced
+        let existing = "name: Mine\nmodels:\n  - name: gpt\n    provider: openai\n\
+                        mcpServers:\n  - name: fgp-gmail\n    command: old\n  - name: sqlite\n    command: npx\n";
+        let merged = merge_continue_config(parse_continue_config(Some(existing)), &gmail).unwrap();
+        assert!(merged.starts_with("name: Mine\nmodels:\n"), "{}", merged);
+        let merged_value: serde_yaml::Value = serde_yaml::from_str(&merged).unwrap();
+        let servers = merged_value["mcpServers"].as_sequence().unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0]["command"], "fgp");
+        assert_eq!(servers[1]["name"], "sqlite");
+
+        // Removing the export takes only that server out
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+        fs::write(&path, &merged).unwrap();
+        let server = ExportArtifact::McpServer {
+            config: path.to_string_lossy().to_string(),
+            server: "fgp-gmail".to_string(),
+        };
+        assert!(remove_export(&server).unwrap().is_some());
+        let left: serde_yaml::Value =
+            serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(left["mcpServers"].as_sequence().unwrap().len(), 1);
+        assert_eq!(left["models"][0]["name"], "gpt");
+        assert_eq!(remove_export(&server).unwrap(), None);
+
+        assert!(parse_continue_config(Some("mcpServers: [unclosed")).is_none());
+        assert!(parse_continue_config(Some("- a list")).is_none());
+    }
 }
@@ -0,0 +1,752 @@
+//! Compare an installed skill with another version of it from its tap.
+//!
+//! Tap skills are installed as a symlink into the tap's clone, and the commit
+//! checked out at install time is recorded in installed_skills.json.
+//! `fgp skill diff <skill>` compares the skill as it was at that commit with
+//! the tap's current checkout (or with `<version>`, resolved to a tag), so an
+//! upgrade can be reviewed before it is applied:
+//!
+//! - manifest changes (version, daemons, methods, trigger keywords) come from
+//!   [`compare_skills`], the comparison used by import sync analysis;
+//! - every changed file in the skill directory gets a unified diff.
+//!
+//! `fgp skill diff <source> <canonical>` instead compares a source agent
+//! file (SKILL.md, .cursorrules, ...) with a canonical skill.yaml, showing
+//! the manifest changes re-importing the file would make, so hand edits to
+//! skill.yaml can be checked before an import overwrites them.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::diff::DiffStyle;
+use super::skill_import::{self, compare_skills, from_canonical, FieldDiff};
+use super::skill_validate::SkillManifest;
+
+/// Lines of context around each change in unified diffs.
+const CONTEXT_LINES: usize = 3;
+
+/// A skill's files at one revision.
+struct Snapshot {
+    /// Commit the files were read from (HEAD for the working tree)
+    sha: String,
+    manifest: SkillManifest,
+    /// File contents keyed by path relative to the skill directory
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl Snapshot {
+    fn new(sha: String, files: BTreeMap<String, Vec<u8>>) -> Result<Self> {
+        let content = files
+            .get("skill.yaml")
+            .or_else(|| files.get("skill.yml"))
+            .context("No skill.yaml (only skill.yaml skills can be compared)")?;
+        let manifest = serde_yaml::from_slice(content).context("Invalid skill.yaml")?;
+        Ok(Self {
+            sha,
+            manifest,
+            files,
+        })
+    }
+
+    /// Content of the core (or Claude Code) instruction file.
+    fn instructions(&self) -> String {
+        self.manifest
+            .instructions
+            .as_ref()
+            .and_then(|i| i.core.as_ref().or(i.claude_code.as_ref()))
+            .and_then(|path| self.files.get(path.trim_start_matches("./")))
+            .map(|content| String::from_utf8_lossy(content).to_string())
+            .unwrap_or_default()
+    }
+
+    fn revision(&self) -> Revision {
+        Revision {
+            version: self.manifest.version.clone(),
+            sha: self.sha.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Revision {
+    version: String,
+    sha: String,
+}
+
+impl Revision {
+    fn label(&self) -> String {
+        format!("{} ({})", self.version, &self.sha[..self.sha.len().min(7)])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Changes to one file in the skill directory.
+#[derive(Debug, Serialize)]
+struct FileDiff {
+    path: String,
+    status: FileStatus,
+    insertions: usize,
+    deletions: usize,
+    binary: bool,
+    /// Unified diff (not produced for binary files)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patch: Option<String>,
+    /// Old and new content of a text file, for rendering
+    #[serde(skip)]
+    text: Option<(String, String)>,
+}
+
+/// Everything that differs between two versions of a skill.
+#[derive(Debug, Serialize)]
+struct SkillDiff {
+    skill: String,
+    from: Revision,
+    to: Revision,
+    fields: Vec<FieldDiff>,
+    files: Vec<FileDiff>,
+}
+
+impl SkillDiff {
+    fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.files.is_empty()
+    }
+}
+
+/// Show what changed between the installed copy of a skill and `version`
+/// (the tap's current checkout if not given).
+///
+/// When `name` is a file, it is a source agent file to compare with the
+/// canonical skill at `version` instead (see [`run_import`]).
+pub fn run(
+    name: &str,
+    version: Option<&str>,
+    json: bool,
+    stat: bool,
+    style: &DiffStyle,
+) -> Result<()> {
+    if Path::new(name).is_file() {
+        let Some(canonical) = version else {
+            bail!(
+                "Comparing {} needs the canonical skill.yaml (or its directory) to compare it with",
+                name
+            );
+        };
+        return run_import(Path::new(name), Path::new(canonical), json);
+    }
+
+    let diff = diff_installed(name, version)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    let mut out = header(&diff);
+    if diff.is_empty() {
+        print!("{}", out);
+        println!("{} No changes.", "✓".green().bold());
+        return Ok(());
+    }
+
+    if !stat {
+        out.push_str(&render_fields(&diff.fields));
+        for file in &diff.files {
+            out.push('\n');
+            out.push_str(&render_patch(file, style));
+        }
+        out.push('\n');
+    }
+    out.push_str(&render_stat(&diff.files));
+
+    super::markdown::page(&out);
+    Ok(())
+}
+
+/// What re-importing a source agent file would change in a canonical skill.
+#[derive(Debug, Serialize)]
+struct ImportDiff {
+    source: PathBuf,
+    format: skill_import::ImportFormat,
+    canonical: PathBuf,
+    fields: Vec<FieldDiff>,
+}
+
+/// Show the manifest changes re-importing `source` would make to the
+/// canonical skill at `canonical` (a skill.yaml or its directory).
+pub fn run_import(source: &Path, canonical: &Path, json: bool) -> Result<()> {
+    let diff = diff_import(source, canonical)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    print!(
+        "{} {} → {} ({})\n\n",
+        "→".blue().bold(),
+        diff.canonical.display().to_string().bold(),
+        diff.source.display(),
+        diff.format.name()
+    );
+    if diff.fields.is_empty() {
+        println!("{} No changes.", "✓".green().bold());
+    } else {
+        print!("{}", render_fields(&diff.fields));
+    }
+    Ok(())
+}
+
+fn diff_import(source: &Path, canonical: &Path) -> Result<ImportDiff> {
+    let imported = skill_import::parse_file(source, None)?;
+    let loaded = super::skill_manifest::load(canonical)?;
+    let instructions = loaded
+        .manifest
+        .instructions
+        .as_ref()
+        .and_then(|i| i.core.as_ref().or(i.claude_code.as_ref()))
+        .and_then(|path| fs::read_to_string(loaded.root.join(path.trim_start_matches("./"))).ok())
+        .unwrap_or_default();
+
+    Ok(ImportDiff {
+        source: source.to_path_buf(),
+        format: imported.source_format,
+        canonical: loaded.path.clone(),
+        fields: compare_skills(
+            &from_canonical(&loaded.manifest, &instructions, &loaded.root),
+            &imported,
+        ),
+    })
+}
+
+/// Print a files-changed summary of what upgrading `name` would change.
+pub fn preview(name: &str) -> Result<()> {
+    let diff = diff_installed(name, None)?;
+    print!("{}", header(&diff));
+    if diff.is_empty() {
+        println!("  {}", "No changes".dimmed());
+    } else {
+        print!("{}", render_stat(&diff.files));
+    }
+    Ok(())
+}
+
+fn diff_installed(name: &str, version: Option<&str>) -> Result<SkillDiff> {
+    let Some((_, entry)) = super::skill::find_installed(name)? else {
+        bail!(
+            "Skill '{}' is not installed. Use 'fgp skill list' to see installed skills.",
+            name
+        );
+    };
+
+    let link = Path::new(&entry.install_path).join("source");
+    let skill_dir = fs::canonicalize(&link).with_context(|| {
+        format!(
+            "Source of '{}' is missing ({}); run 'fgp doctor --fix'",
+            name,
+            link.display()
+        )
+    })?;
+
+    let Some(ref installed_sha) = entry.git_commit_sha else {
+        bail!(
+            "'{}' was installed without a recorded commit, so there is nothing to compare against. Reinstall it with 'fgp skill install {}'.",
+            name,
+            name
+        );
+    };
+
+    let repo = TapRepo::open(&skill_dir)?;
+    if !repo.has_commit(installed_sha) {
+        bail!(
+            "Commit {} recorded for '{}' is no longer in {} (was the tap re-cloned?). Reinstall it with 'fgp skill install {}'.",
+            installed_sha,
+            name,
+            repo.root.display(),
+            name
+        );
+    }
+
+    let from = repo
+        .snapshot(installed_sha)
+        .with_context(|| format!("Failed to read '{}' at {}", name, installed_sha))?;
+    let to = match version {
+        Some(version) => {
+            let rev = repo.resolve_version(name, version)?;
+            repo.snapshot(&rev)
+                .with_context(|| format!("Failed to read '{}' at {}", name, version))?
+        }
+        None => repo
+            .working_tree()
+            .with_context(|| format!("Failed to read '{}' from the tap", name))?,
+    };
+
+    Ok(compare(name, &from, &to))
+}
+
+fn compare(name: &str, from: &Snapshot, to: &Snapshot) -> SkillDiff {
+    let fields = compare_skills(
+        &from_canonical(&from.manifest, &from.instructions(), Path::new(name)),
+        &from_canonical(&to.manifest, &to.instructions(), Path::new(name)),
+    );
+
+    SkillDiff {
+        skill: name.to_string(),
+        from: from.revision(),
+        to: to.revision(),
+        fields,
+        files: diff_files(&from.files, &to.files),
+    }
+}
+
+/// Line diffs for every file that differs between two snapshots.
+fn diff_files(old: &BTreeMap<String, Vec<u8>>, new: &BTreeMap<String, Vec<u8>>) -> Vec<FileDiff> {
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut diffs = Vec::new();
+
+    for path in paths {
+        let (before, after) = (old.get(path), new.get(path));
+        if before == after {
+            continue;
+        }
+        let status = match (before, after) {
+            (None, _) => FileStatus::Added,
+            (_, None) => FileStatus::Removed,
+            _ => FileStatus::Modified,
+        };
+
+        let (Some(before), Some(after)) = (as_text(before), as_text(after)) else {
+            diffs.push(FileDiff {
+                path: path.clone(),
+                status,
+                insertions: 0,
+                deletions: 0,
+                binary: true,
+                patch: None,
+                text: None,
+            });
+            continue;
+        };
+
+        let diff = TextDiff::from_lines(before, after);
+        let count = |tag| diff.iter_all_changes().filter(|c| c.tag() == tag).count();
+        let patch = diff
+            .unified_diff()
+            .context_radius(CONTEXT_LINES)
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+            .to_string();
+
+        diffs.push(FileDiff {
+            path: path.clone(),
+            status,
+            insertions: count(ChangeTag::Insert),
+            deletions: count(ChangeTag::Delete),
+            binary: false,
+            patch: Some(patch),
+            text: Some((before.to_string(), after.to_string())),
+        });
+    }
+
+    diffs
+}
+
+/// File content as text; `None` for binary content. A missing file is empty.
+fn as_text(content: Option<&Vec<u8>>) -> Option<&str> {
+    match content {
+        None => Some(""),
+        Some(bytes) if bytes.contains(&0) => None,
+        Some(bytes) => std::str::from_utf8(bytes).ok(),
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn header(diff: &SkillDiff) -> String {
+    format!(
+        "{} {}: {} → {}\n\n",
+        "→".blue().bold(),
+        diff.skill.bold(),
+        diff.from.label(),
+        diff.to.label()
+    )
+}
+
+fn render_fields(fields: &[FieldDiff]) -> String {
+    if fields.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("{}\n", "Manifest:".bold());
+    for field in fields {
+        let details = match (&field.original_value, &field.current_value) {
+            (Some(orig), Some(curr)) => format!("{} → {}", orig, curr),
+            (None, Some(curr)) => format!("→ {}", curr),
+            (Some(orig), None) => format!("{} →", orig),
+            (None, None) => String::new(),
+        };
+        out.push_str(&format!(
+            "  {} {} {}: {}\n",
+            field.significance.emoji(),
+            field.change_type.emoji(),
+            field.field,
+            details.dimmed()
+        ));
+    }
+    out
+}
+
+fn render_patch(file: &FileDiff, style: &DiffStyle) -> String {
+    let Some((ref before, ref after)) = file.text else {
+        return format!("{} {}\n", "Binary file changed:".bold(), file.path);
+    };
+
+    super::diff::render(
+        &TextDiff::from_lines(before, after),
+        &format!("a/{}", file.path),
+        &format!("b/{}", file.path),
+        style,
+    )
+}
+
+fn render_stat(files: &[FileDiff]) -> String {
+    let width = files.iter().map(|f| f.path.len()).max().unwrap_or(0);
+    let mut out = String::new();
+
+    for file in files {
+        let change = if file.binary {
+            "binary".dimmed().to_string()
+        } else {
+            format!(
+                "{} {}",
+                format!("+{}", file.insertions).green(),
+                format!("-{}", file.deletions).red()
+            )
+        };
+        let status = match file.status {
+            FileStatus::Added => " (new)".dimmed().to_string(),
+            FileStatus::Removed => " (deleted)".dimmed().to_string(),
+            FileStatus::Modified => String::new(),
+        };
+        out.push_str(&format!(
+            "  {:width$}  {}{}\n",
+            file.path,
+            change,
+            status,
+            width = width
+        ));
+    }
+
+    out.push_str(&format!(
+        "  {} file(s) changed, {} insertion(s), {} deletion(s)\n",
+        files.len(),
+        files.iter().map(|f| f.insertions).sum::<usize>(),
+        files.iter().map(|f| f.deletions).sum::<usize>()
+    ));
+    out
+}
+
+// ============================================================================
+// Git
+// ============================================================================
+
+/// The git checkout a tap skill lives in.
+struct TapRepo {
+    root: PathBuf,
+    /// Path of the skill directory within the repository ("" or ending in '/')
+    prefix: String,
+}
+
+impl TapRepo {
+    fn open(skill_dir: &Path) -> Result<Self> {
+        let root = git(skill_dir, &["rev-parse", "--show-toplevel"]).with_context(|| {
+            format!(
+                "{} is not in a git checkout, so its versions can't be compared",
+                skill_dir.display()
+            )
+        })?;
+        let prefix = git(skill_dir, &["rev-parse", "--show-prefix"])?;
+
+        Ok(Self {
+            root: PathBuf::from(String::from_utf8_lossy(&root).trim()),
+            prefix: String::from_utf8_lossy(&prefix).trim().to_string(),
+        })
+    }
+
+    fn skill_dir(&self) -> PathBuf {
+        self.root.join(&self.prefix)
+    }
+
+    fn has_commit(&self, rev: &str) -> bool {
+        git(
+            &self.root,
+            &["cat-file", "-e", &format!("{}^{{commit}}", rev)],
+        )
+        .is_ok()
+    }
+
+    /// Find the commit for a version: a `<skill>-v1.2.0`, `<skill>@1.2.0`, or
+    /// `v1.2.0` tag, or any other ref git understands.
+    fn resolve_version(&self, name: &str, version: &str) -> Result<String> {
+        let bare = version.trim_start_matches('v');
+        let candidates = [
+            format!("{}-v{}", name, bare),
+            format!("{}@{}", name, bare),
+            format!("v{}", bare),
+            version.to_string(),
+        ];
+
+        for candidate in &candidates {
+            let spec = format!("{}^{{commit}}", candidate);
+            if let Ok(sha) = git(&self.root, &["rev-parse", "--verify", "--quiet", &spec]) {
+                return Ok(String::from_utf8_lossy(&sha).trim().to_string());
+            }
+        }
+
+        bail!(
+            "No version '{}' of '{}' in {} (tried {}). Run 'fgp skill tap update' to fetch new versions.",
+            version,
+            name,
+            self.root.display(),
+            candidates.join(", ")
+        )
+    }
+
+    /// The skill's committed files at `rev`.
+    fn snapshot(&self, rev: &str) -> Result<Snapshot> {
+        let pathspec = if self.prefix.is_empty() {
+            "."
+        } else {
+            self.prefix.as_str()
+        };
+        let listing = git(
+            &self.root,
+            &["ls-tree", "-r", "-z", "--name-only", rev, "--", pathspec],
+        )?;
+
+        let mut files = BTreeMap::new();
+        for path in split_nul(&listing) {
+            let content = git(
+                &self.root,
+                &["cat-file", "blob", &format!("{}:{}", rev, path)],
+            )?;
+            let relative = path.strip_prefix(&self.prefix).unwrap_or(&path);
+            files.insert(relative.to_string(), content);
+        }
+
+        if files.is_empty() {
+            bail!("The skill does not exist at {}", rev);
+        }
+
+        let sha = git(&self.root, &["rev-parse", &format!("{}^{{commit}}", rev)])?;
+        Snapshot::new(String::from_utf8_lossy(&sha).trim().to_string(), files)
+    }
+
+    /// The skill's files as currently checked out (ignored files excluded).
+    fn working_tree(&self) -> Result<Snapshot> {
+        let dir = self.skill_dir();
+        let listing = git(
+            &dir,
+            &[
+                "ls-files",
+                "-z",
+                "--cached",
+                "--others",
+                "--exclude-standard",
+            ],
+        )?;
+
+        let mut files = BTreeMap::new();
+        for path in split_nul(&listing) {
+            // Tracked files deleted from the checkout are simply absent
+            if let Ok(content) = fs::read(dir.join(&path)) {
+                files.insert(path, content);
+            }
+        }
+
+        let sha = git(&self.root, &["rev-parse", "HEAD"])?;
+        Snapshot::new(String::from_utf8_lossy(&sha).trim().to_string(), files)
+    }
+}
+
+fn split_nul(output: &[u8]) -> Vec<String> {
+    output
+        .split(|b| *b == 0)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| String::from_utf8_lossy(entry).to_string())
+        .collect()
+}
+
+/// Run git in `dir` and return its stdout.
+fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::skill_import::ChangeType;
+
+    fn files(entries: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
+        entries
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_files() {
+        let mut old = files(&[
+            ("instructions/core.md", "# Gmail\n\nSend mail.\n"),
+            ("workflows/old.yaml", "steps: []\n"),
+            ("skill.yaml", "name: gmail\n"),
+        ]);
+        let mut new = files(&[
+            (
+                "instructions/core.md",
+                "# Gmail\n\nSend and read mail.\nBe brief.\n",
+            ),
+            ("workflows/triage.yaml", "steps:\n  - call: gmail.list\n"),
+            ("skill.yaml", "name: gmail\n"),
+        ]);
+        old.insert("icon.png".to_string(), vec![0x89, 0x50, 0x00]);
+        new.insert("icon.png".to_string(), vec![0x89, 0x51, 0x00]);
+
+        let diffs = diff_files(&old, &new);
+        let summary: Vec<_> = diffs
+            .iter()
+            .map(|d| {
+                (
+                    d.path.as_str(),
+                    d.status,
+                    d.insertions,
+                    d.deletions,
+                    d.binary,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("icon.png", FileStatus::Modified, 0, 0, true),
+                ("instructions/core.md", FileStatus::Modified, 2, 1, false),
+                ("workflows/old.yaml", FileStatus::Removed, 0, 1, false),
+                ("workflows/triage.yaml", FileStatus::Added, 2, 0, false),
+            ]
+        );
+
+        let patch = diffs[1].patch.as_deref().unwrap();
+        assert!(patch.contains("--- a/instructions/core.md"));
+        assert!(patch.contains("-Send mail.\n"));
+        assert!(patch.contains("+Be brief.\n"));
+        assert!(diffs[0].patch.is_none());
+    }
+
+    #[test]
+    fn test_compare_manifests() {
+        let from = Snapshot::new(
+            "a".repeat(40),
+            files(&[
+                (
+                    "skill.yaml",
+                    "name: gmail\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+                     instructions:\n  core: ./core.md\n\
+                     daemons:\n  - name: gmail\n    methods: [gmail.send, gmail.list]\n",
+                ),
+                ("core.md", "Send mail.\n"),
+            ]),
+        )
+        .unwrap();
+        let to = Snapshot::new(
+            "b".repeat(40),
+            files(&[
+                (
+                    "skill.yaml",
+                    "name: gmail\nversion: 1.1.0\ndescription: Mail\nauthor: fgp\n\
+                     instructions:\n  core: ./core.md\n\
+                     daemons:\n  - name: gmail\n    methods: [gmail.send]\n  - name: calendar\n",
+                ),
+                ("core.md", "Send mail.\n"),
+            ]),
+        )
+        .unwrap();
+
+        let diff = compare("gmail", &from, &to);
+        assert_eq!(diff.from.label(), "1.0.0 (aaaaaaa)");
+        let fields: Vec<_> = diff
+            .fields
+            .iter()
+            .map(|f| (f.field.as_str(), f.change_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("version", ChangeType::Modified),
+                ("daemon.calendar", ChangeType::Added),
+                ("gmail.gmail.list", ChangeType::Removed),
+            ]
+        );
+        // Unchanged instructions aren't reported
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!(diff.files[0].path, "skill.yaml");
+    }
+
+    #[test]
+    fn test_diff_import() {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/import/claude-code/gmail-triage/SKILL.md");
+        let imported = skill_import::parse_file(&source, None).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("skill.yaml"),
+            "name: gmail-triage\nversion: 1.3.0\ndescription: Hand-written\nauthor: fgp\n\
+             instructions:\n  core: ./core.md\n\
+             daemons:\n  - name: gmail\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("core.md"),
+            &imported.instructions_content.value,
+        )
+        .unwrap();
+
+        let diff = diff_import(&source, dir.path()).unwrap();
+        assert_eq!(diff.canonical, dir.path().join("skill.yaml"));
+        let fields: Vec<_> = diff
+            .fields
+            .iter()
+            .map(|f| (f.field.as_str(), f.original_value.as_deref()))
+            .collect();
+        // The canonical skill is the original; the import would replace it
+        assert!(fields.contains(&("version", Some("1.3.0"))));
+        assert!(fields.contains(&("description", Some("Hand-written"))));
+        assert!(fields.contains(&("daemon.calendar", None)));
+        assert!(!fields.iter().any(|(field, _)| *field == "instructions"));
+    }
+}
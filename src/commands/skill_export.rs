@@ -2,93 +2,1077 @@
 //!
 //! Supported targets:
 //! - claude-code: Generates SKILL.md for ~/.claude/skills/
-//! - cursor: Generates .cursorrules and commands
+//! - cursor: Generates .cursorrules (or a .mdc rule with `--format mdc`)
+//!   and commands
 //! - codex: Generates tool spec and prompts
 //! - mcp: Generates MCP tool schema
 //! - windsurf: Generates cascade rules
 //! - zed: Generates .rules file for Zed's AI assistant
+//! - claude-desktop: Adds an MCP server per daemon to Claude Desktop's
+//!   `claude_desktop_config.json` (backing the old file up first)
+//! - continue: Adds an MCP server per daemon to Continue's
+//!   `~/.continue/config.yaml`
+//! - all: Every target above except claude-desktop and continue, each in a subdirectory
+//!   of the output directory named after it (`claude-code/`, `cursor/`, ...),
+//!   (with `--layout project`, in the agents' own directories under it),
+//!   skipping files whose content is unchanged and ending with a summary
+//!
+//! With `--layout project`, cursor and claude-code write into the directory
+//! structure those agents read from a project root (`.cursor/rules/<name>.mdc`
+//! and `.claude/skills/<name>/SKILL.md`). Adding `--gitignore add` also lists
+//! the generated files in the project's `.gitignore`.
+//!
+//! `--merge-into <dir>` adds a claude-code export to an existing Claude Code
+//! plugin instead: SKILL.md goes into the plugin's skills folder, and into
+//! the `"skills"` list of its `plugin.json` if it keeps one.
+//!
+//! `--tools-scope <glob>` limits the shell access an exported skill grants to
+//! the `fgp call` commands for its daemon methods that match the glob. Only
+//! Claude Code skills can declare tool permissions (`allowed-tools`); other
+//! targets are exported unchanged with a warning.
+//!
+//! `--since-version <version>` adds a "What's New" section, built from the
+//! skill's CHANGELOG.md entries newer than that version, to the Markdown
+//! documents agents read (SKILL.md, Cursor/Windsurf/Zed rules, GEMINI.md).
+//! Skills without a changelog are exported without one.
+//!
+//! `--annotate-confidence` checks a skill created by `fgp skill import`
+//! against the import metadata in its `.sync.json`, and warns about fields
+//! the import could only guess at (low or unknown confidence) that still have
+//! their imported value. `--annotate-confidence=deny` refuses to export them
+//! instead.
+//!
+//! `--daemons gmail,calendar` exports a focused skill: only the listed
+//! daemons' dependencies, methods and auth requirements make it into the
+//! generated files. Each name must be a daemon the skill declares.
+//!
+//! `--include-cost` adds what the skill's daemons declare about their methods'
+//! cost and rate limits (`"cost"` and `"rate_limit"` in the installed
+//! manifest.json) as a "Usage Considerations" section, or for codex and mcp,
+//! to the tool descriptions, so agents know which calls to ration.
+//!
+//! The mcp target lists one tool per daemon method, with an input schema and
+//! description built from what the method declares in its daemon's
+//! installed manifest.json (skill.yaml only names methods), and warns when
+//! that's more than an MCP client is known to handle (see
+//! `[mcp.tool_limits]` in the config). `--group-by-daemon` lists one tool per daemon instead, taking
+//! the method to call and its params; the bridge (`fgp mcp serve`) accepts
+//! calls in either form.
+//!
+//! The `fgp call` examples in the generated docs fill in each method's
+//! required params (as declared in its daemon's installed manifest.json)
+//! with values derived from the method and param names, so exporting the
+//! same skill twice gives the same files and `--check` stays meaningful.
+//! `--example-seed <n>` picks a different set of values.
+//!
+//! Exported files are UTF-8 without a byte order mark, with `\n` line
+//! endings unless `--line-endings crlf` (or `native`, which is `crlf` on
+//! Windows) asks for `\r\n`. Instruction files are converted too, whatever
+//! they were saved with.
+//!
+//! After exporting, each target lists the parts of the skill its files have
+//! no place for (workflows, config options, auth requirements, and for codex,
+//! mcp, claude-desktop and continue, instructions and triggers), followed by the
+//! format's limitations as `fgp skill import` reports them, so it's clear
+//! what an agent reading the export won't know.
+//!
+//! `--with-commands` also writes a slash command per daemon method, such as
+//! `/gmail-send` for `gmail.send`, for the agents that have them (Claude Code
+//! reads `.claude/commands/`, Cursor `.cursor/commands/`). A skill can curate
+//! the set by listing the commands it wants in `triggers.commands`.
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::methods::find_method;
+use super::methods_export;
+use super::skill_import::ManifestMethod;
 use super::skill_validate::SkillManifest;
+use super::{guardrails, mcp_bridge, parse_version};
+
+/// Targets whose exported files can restrict tool permissions.
+const SCOPED_TOOL_TARGETS: &[&str] = &["claude-code", "claude"];
+
+/// Targets whose exported documents get a "What's New" section.
+const CHANGELOG_TARGETS: &[&str] = &[
+    "claude-code",
+    "claude",
+    "cursor",
+    "windsurf",
+    "zed",
+    "gemini",
+];
+
+/// Targets whose exported documents carry the skill's instructions and
+/// triggers.
+const DOCUMENT_TARGETS: &[&str] = &[
+    "claude-code",
+    "claude",
+    "cursor",
+    "windsurf",
+    "zed",
+    "gemini",
+    "aider",
+];
+
+/// Targets whose exported documents list the skill's workflows.
+const WORKFLOW_TARGETS: &[&str] = &["claude-code", "claude", "zed", "aider"];
+
+/// Targets that can be given slash commands (`--with-commands`).
+const COMMAND_TARGETS: &[&str] = &["claude-code", "claude", "cursor"];
+
+/// Targets generated by `fgp skill export all`.
+const ALL_TARGETS: &[&str] = &[
+    "claude-code",
+    "cursor",
+    "codex",
+    "mcp",
+    "windsurf",
+    "zed",
+    "gemini",
+    "aider",
+];
+
+/// How generated files are written to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriteMode {
+    /// Always write (single-target export)
+    Write,
+    /// Write only files whose content differs from what's on disk
+    SkipUnchanged,
+    /// Never write; report files that would change (--check)
+    Check,
+    /// Write only files whose content differs, without reporting each one
+    /// (`fgp dev` prints its own status line)
+    Quiet,
+}
+
+/// Where exported files are placed relative to the output directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportLayout {
+    /// Standalone files in the output directory (default)
+    Standalone,
+    /// The per-agent directory structure of a project root
+    Project,
+    /// The skills folder of a Claude Code plugin (`--merge-into`)
+    Plugin,
+}
+
+impl ExportLayout {
+    fn parse(layout: &str) -> Result<Self> {
+        match layout {
+            "standalone" => Ok(Self::Standalone),
+            "project" => Ok(Self::Project),
+            _ => bail!(
+                "Unknown layout: {}\nValid layouts: standalone, project",
+                layout
+            ),
+        }
+    }
+}
+
+/// File format of the cursor target (`--format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CursorFormat {
+    /// A single legacy `.cursorrules` file
+    Rules,
+    /// A `.mdc` project rule with frontmatter
+    Mdc,
+}
+
+impl CursorFormat {
+    /// The requested format, or the layout's default: `.cursorrules` for
+    /// standalone files, `.mdc` for a project (`.cursor/rules/`).
+    fn parse(format: Option<&str>, layout: ExportLayout) -> Result<Self> {
+        match (format, layout) {
+            (Some("mdc"), _) | (None, ExportLayout::Project) => Ok(Self::Mdc),
+            (Some("cursorrules"), ExportLayout::Project) => bail!(
+                "--format cursorrules can't be used with --layout project (project rules live in .cursor/rules/*.mdc)"
+            ),
+            (Some("cursorrules"), _) | (None, _) => Ok(Self::Rules),
+            (Some(format), _) => bail!(
+                "Unknown format: {}\nValid formats: cursorrules, mdc",
+                format
+            ),
+        }
+    }
+}
+
+/// Line endings of exported files (`--line-endings`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineEndings {
+    Lf,
+    Crlf,
+}
+
+impl LineEndings {
+    fn parse(line_endings: &str) -> Result<Self> {
+        match line_endings {
+            "lf" => Ok(Self::Lf),
+            "crlf" => Ok(Self::Crlf),
+            "native" if cfg!(windows) => Ok(Self::Crlf),
+            "native" => Ok(Self::Lf),
+            _ => bail!(
+                "Unknown line endings: {}\nValid line endings: lf, crlf, native",
+                line_endings
+            ),
+        }
+    }
+
+    /// `content` with these line endings and no byte order marks, including
+    /// any carried over from an instruction file
+    fn apply(self, content: &str) -> String {
+        let content = content.replace('\u{feff}', "").replace("\r\n", "\n");
+        match self {
+            Self::Lf => content,
+            Self::Crlf => content.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Outcome for a single generated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileStatus {
+    Written,
+    Unchanged,
+    Stale,
+}
+
+/// Writes exported files and records what happened to each one.
+struct ExportWriter {
+    mode: WriteMode,
+    line_endings: LineEndings,
+    results: Vec<(PathBuf, FileStatus)>,
+    /// Written files that didn't exist before
+    created: Vec<PathBuf>,
+}
+
+impl ExportWriter {
+    fn new(mode: WriteMode, line_endings: LineEndings) -> Self {
+        Self {
+            mode,
+            line_endings,
+            results: Vec::new(),
+            created: Vec::new(),
+        }
+    }
+
+    /// Write `content` to `path` according to the write mode, as UTF-8
+    /// with the writer's line endings.
+    fn write(&mut self, path: &Path, content: &str) -> Result<()> {
+        let content = &self.line_endings.apply(content);
+        let unchanged = is_up_to_date(path, content);
+
+        let status = match self.mode {
+            WriteMode::Check if unchanged => FileStatus::Unchanged,
+            WriteMode::Check => FileStatus::Stale,
+            WriteMode::SkipUnchanged | WriteMode::Quiet if unchanged => FileStatus::Unchanged,
+            _ => {
+                if !path.exists() {
+                    self.created.push(path.to_path_buf());
+                }
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                FileStatus::Written
+            }
+        };
+
+        if matches!(self.mode, WriteMode::SkipUnchanged | WriteMode::Check) {
+            let label = match status {
+                FileStatus::Written => "written".green(),
+                FileStatus::Unchanged => "unchanged".dimmed(),
+                FileStatus::Stale => "out of date".yellow(),
+            };
+            println!("  {} ({})", path.display(), label);
+        }
+
+        self.results.push((path.to_path_buf(), status));
+        Ok(())
+    }
+
+    /// Whether per-target success messages and install hints should be printed.
+    fn announce(&self) -> bool {
+        self.mode == WriteMode::Write
+    }
+
+    fn count(&self, status: FileStatus) -> usize {
+        self.results.iter().filter(|(_, s)| *s == status).count()
+    }
+}
+
+/// "2 written, 1 unchanged" for the files that had each status
+fn status_counts(results: &[(PathBuf, FileStatus)]) -> String {
+    let counts: Vec<String> = [
+        (FileStatus::Written, "written"),
+        (FileStatus::Unchanged, "unchanged"),
+        (FileStatus::Stale, "out of date"),
+    ]
+    .into_iter()
+    .filter_map(|(status, label)| {
+        let count = results.iter().filter(|(_, s)| *s == status).count();
+        (count > 0).then(|| format!("{} {}", count, label))
+    })
+    .collect();
+    if counts.is_empty() {
+        "no files".to_string()
+    } else {
+        counts.join(", ")
+    }
+}
+
+/// Check whether the file at `path` already has exactly `content`.
+fn is_up_to_date(path: &Path, content: &str) -> bool {
+    fs::read_to_string(path)
+        .map(|existing| existing == content)
+        .unwrap_or(false)
+}
+
+/// Options for `fgp skill export`.
+#[derive(Debug, Default)]
+pub struct ExportOptions<'a> {
+    /// Output directory (default: current directory)
+    pub output: Option<&'a str>,
+    /// Don't write; fail if any generated file differs from what's on disk
+    pub check: bool,
+    /// `standalone` or `project`
+    pub layout: &'a str,
+    /// Add the generated files to the project's .gitignore
+    pub gitignore: bool,
+    /// Restrict the commands exported skills may run (see the module docs)
+    pub tools_scope: Option<&'a str>,
+    /// Add a "What's New" section with changelog entries after this version
+    pub since_version: Option<&'a str>,
+    /// `warn` or `deny` about unreviewed low-confidence imported fields
+    pub annotate_confidence: Option<&'a str>,
+    /// Only export these daemons (all of them when empty)
+    pub daemons: &'a [String],
+    /// List one MCP tool per daemon rather than per method
+    pub group_by_daemon: bool,
+    /// Include the methods' cost and rate-limit hints
+    pub include_cost: bool,
+    /// Varies the example param values in `fgp call` examples
+    pub example_seed: u64,
+    /// Write a slash command per method for agents that support them
+    pub with_commands: bool,
+    /// `lf`, `crlf` or `native`
+    pub line_endings: &'a str,
+    /// `cursorrules` or `mdc` for the cursor target (default: by layout)
+    pub format: Option<&'a str>,
+    /// Claude Code plugin directory to add the skill to (claude-code only)
+    pub merge_into: Option<&'a str>,
+}
+
+/// Per-target settings resolved from [`ExportOptions`].
+struct TargetOptions<'a> {
+    layout: ExportLayout,
+    cursor_format: CursorFormat,
+    tools_scope: Option<&'a str>,
+    /// Rendered "What's New" section, if there is anything to show
+    whats_new: Option<String>,
+    group_by_daemon: bool,
+    /// Cost and rate-limit notes by method (`--include-cost`)
+    usage: MethodUsage,
+    examples: Examples,
+    /// Slash commands to write (`--with-commands`)
+    commands: Option<Vec<SlashCommand>>,
+}
+
+/// Export a skill for a specific agent (or `all`).
+pub fn export(target: &str, skill: &str, options: &ExportOptions) -> Result<()> {
+    let ExportOptions {
+        output,
+        check,
+        layout,
+        gitignore,
+        tools_scope,
+        since_version,
+        annotate_confidence,
+        daemons,
+        group_by_daemon,
+        include_cost,
+        example_seed,
+        with_commands,
+        line_endings,
+        format,
+        merge_into,
+    } = *options;
+
+    let plugin = match merge_into {
+        Some(_) if !matches!(target, "claude-code" | "claude") => bail!(
+            "--merge-into only works with the claude-code target (got {})",
+            target
+        ),
+        Some(dir) => Some(find_plugin(Path::new(dir))?),
+        None => None,
+    };
+    let layout = match plugin {
+        Some(_) => ExportLayout::Plugin,
+        None => ExportLayout::parse(layout)?,
+    };
+    let cursor_format = CursorFormat::parse(format, layout)?;
+    let line_endings = LineEndings::parse(line_endings)?;
+    if gitignore && layout != ExportLayout::Project {
+        bail!("--gitignore add requires --layout project (the output directory must be a project root)");
+    }
 
-/// Export a skill for a specific agent.
-pub fn export(target: &str, skill: &str, output: Option<&str>) -> Result<()> {
     println!(
-        "{} Exporting skill for {}...",
+        "{} {} skill for {}...",
         "→".blue().bold(),
+        if check { "Checking" } else { "Exporting" },
         target.cyan()
     );
 
-    // Load the skill manifest
+    // Load the skill manifest (skill.yaml, or a legacy skill.json)
     let skill_path = Path::new(skill);
-    let (skill_dir, manifest_path) = if skill_path.is_dir() {
-        (skill_path.to_path_buf(), skill_path.join("skill.yaml"))
-    } else if skill_path
-        .extension()
-        .map(|e| e == "yaml" || e == "yml")
-        .unwrap_or(false)
-    {
-        (
-            skill_path.parent().unwrap_or(Path::new(".")).to_path_buf(),
-            skill_path.to_path_buf(),
-        )
+    let lookup_path = if skill_path.exists() {
+        skill_path.to_path_buf()
     } else {
         // Assume it's a skill name, look in installed skills
         let installed_path = shellexpand::tilde("~/.fgp/skills").to_string();
-        let installed_skill_dir = Path::new(&installed_path).join(skill);
-        (
-            installed_skill_dir.clone(),
-            installed_skill_dir.join("skill.yaml"),
-        )
+        Path::new(&installed_path).join(skill)
     };
 
-    if !manifest_path.exists() {
+    if super::skill_manifest::find_manifest(&lookup_path).is_none() {
         bail!(
-            "Skill manifest not found: {}\n\
-             Provide a path to a skill directory or skill.yaml file.",
-            manifest_path.display()
+            "Skill manifest not found in {}\n\
+             Provide a path to a skill directory, skill.yaml, or skill.json file.",
+            lookup_path.display()
         );
     }
 
-    let content = fs::read_to_string(&manifest_path)
-        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let loaded = super::skill_manifest::load(&lookup_path)?;
+    if loaded.format == super::skill_manifest::ManifestFormat::Json {
+        println!(
+            "  {} Using legacy manifest {} (run 'fgp skill migrate-manifest' to upgrade)",
+            "!".yellow(),
+            loaded.path.display()
+        );
+    }
+    let skill_dir = loaded.root;
+    let mut manifest = loaded.manifest;
 
-    let manifest: SkillManifest =
-        serde_yaml::from_str(&content).with_context(|| "Invalid skill.yaml")?;
+    if let Some(mode) = annotate_confidence {
+        check_reviewed(&skill_dir, &manifest, mode == "deny")?;
+    }
+
+    if !daemons.is_empty() {
+        let total = manifest.daemons.len();
+        select_daemons(&mut manifest, daemons)?;
+        println!(
+            "  {} Exporting {} of {} daemon(s): {}",
+            "→".blue(),
+            manifest.daemons.len(),
+            total,
+            daemons.join(", ")
+        );
+    }
+
+    let whats_new = match since_version {
+        Some(since) => whats_new(&skill_dir, since)?,
+        None => None,
+    };
+    let usage = if include_cost {
+        method_usage(&manifest)
+    } else {
+        MethodUsage::new()
+    };
+    let commands = if with_commands {
+        let (commands, unmatched) = slash_commands(&manifest);
+        for command in unmatched {
+            println!(
+                "  {} {} in triggers.commands matches none of the skill's daemon methods",
+                "!".yellow(),
+                command
+            );
+        }
+        if commands.is_empty() {
+            println!(
+                "  {} {} lists no daemon methods; no slash commands to write",
+                "!".yellow(),
+                manifest.name
+            );
+        }
+        Some(commands)
+    } else {
+        None
+    };
+    let target_options = TargetOptions {
+        layout,
+        cursor_format,
+        tools_scope,
+        whats_new,
+        group_by_daemon,
+        usage,
+        examples: Examples::new(&manifest, example_seed),
+        commands,
+    };
 
     // Determine output directory
-    let output_dir = match output {
-        Some(dir) => Path::new(dir).to_path_buf(),
-        None => std::env::current_dir()?,
+    let output_dir = match (&plugin, output) {
+        (Some(plugin), _) => plugin.skills_dir.clone(),
+        (None, Some(dir)) => Path::new(dir).to_path_buf(),
+        (None, None) => std::env::current_dir()?,
     };
 
-    // Export based on target
+    let mode = if check {
+        WriteMode::Check
+    } else if target == "all" {
+        WriteMode::SkipUnchanged
+    } else {
+        WriteMode::Write
+    };
+    let mut writer = ExportWriter::new(mode, line_endings);
+
+    if target == "all" {
+        let mut written = Vec::new();
+        for t in ALL_TARGETS {
+            println!("{} {}", "→".blue(), t.bold());
+            // The project layout already keeps each agent's files apart
+            let target_dir = match layout {
+                ExportLayout::Project => output_dir.clone(),
+                ExportLayout::Standalone | ExportLayout::Plugin => output_dir.join(t),
+            };
+            let first = writer.results.len();
+            export_target(
+                t,
+                &manifest,
+                &skill_dir,
+                &target_dir,
+                &target_options,
+                &mut writer,
+            )?;
+            if !check {
+                print_unrepresented(t, &manifest);
+            }
+            written.push((t, target_dir, first..writer.results.len()));
+        }
+
+        println!();
+        println!("{}", "Summary:".bold());
+        for (t, target_dir, files) in written {
+            println!(
+                "  {:<12} {} ({})",
+                t,
+                target_dir.display(),
+                status_counts(&writer.results[files])
+            );
+        }
+    } else {
+        export_target(
+            target,
+            &manifest,
+            &skill_dir,
+            &output_dir,
+            &target_options,
+            &mut writer,
+        )?;
+        if let Some(plugin) = &plugin {
+            register_plugin_skill(plugin, &manifest.name, &mut writer)?;
+        }
+        if !check {
+            print_unrepresented(target, &manifest);
+        }
+    }
+
+    if mode != WriteMode::Write {
+        println!();
+        println!(
+            "{} {} written, {} unchanged, {} out of date",
+            "✓".green().bold(),
+            writer.count(FileStatus::Written),
+            writer.count(FileStatus::Unchanged),
+            writer.count(FileStatus::Stale)
+        );
+    }
+
+    if gitignore && mode != WriteMode::Check {
+        let paths: Vec<&Path> = writer.results.iter().map(|(p, _)| p.as_path()).collect();
+        let added = update_gitignore(&output_dir, &paths)?;
+        if added > 0 {
+            println!(
+                "{} Added {} path(s) to {}",
+                "✓".green().bold(),
+                added,
+                output_dir.join(".gitignore").display()
+            );
+        }
+    }
+
+    let stale = writer.count(FileStatus::Stale);
+    if stale > 0 {
+        bail!(
+            "{} exported file(s) are out of date. Re-run without --check to update them.",
+            stale
+        );
+    }
+
+    Ok(())
+}
+
+/// Targets that can be exported straight into an agent's directory: the
+/// project layout rooted at the home directory is where the agent looks.
+pub const INSTALLABLE_TARGETS: &[&str] = &["claude-code", "cursor"];
+
+/// What [`export_quietly`] did to a generated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportChange {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// Export the skill at `skill` for one of [`INSTALLABLE_TARGETS`] in the
+/// project layout under `root`, writing only files that changed and printing
+/// nothing.
+pub(crate) fn export_quietly(
+    target: &str,
+    skill: &Path,
+    root: &Path,
+) -> Result<Vec<(PathBuf, ExportChange)>> {
+    if !INSTALLABLE_TARGETS.contains(&target) {
+        bail!(
+            "Can't install {} exports\nValid targets: {}",
+            target,
+            INSTALLABLE_TARGETS.join(", ")
+        );
+    }
+    let loaded = super::skill_manifest::load(skill)?;
+    let options = TargetOptions {
+        layout: ExportLayout::Project,
+        cursor_format: CursorFormat::Mdc,
+        tools_scope: None,
+        whats_new: None,
+        group_by_daemon: false,
+        usage: MethodUsage::new(),
+        examples: Examples::new(&loaded.manifest, 0),
+        commands: None,
+    };
+    let mut writer = ExportWriter::new(WriteMode::Quiet, LineEndings::Lf);
+    export_target(
+        target,
+        &loaded.manifest,
+        &loaded.root,
+        root,
+        &options,
+        &mut writer,
+    )?;
+    let ExportWriter {
+        results, created, ..
+    } = writer;
+    Ok(results
+        .into_iter()
+        .map(|(path, status)| {
+            let change = if status != FileStatus::Written {
+                ExportChange::Unchanged
+            } else if created.contains(&path) {
+                ExportChange::Created
+            } else {
+                ExportChange::Updated
+            };
+            (path, change)
+        })
+        .collect())
+}
+
+/// Drop every daemon not in `names` from `manifest`, along with its auth
+/// requirement. Fails if a name isn't one of the skill's daemons.
+fn select_daemons(manifest: &mut SkillManifest, names: &[String]) -> Result<()> {
+    let unknown: Vec<&str> = names
+        .iter()
+        .filter(|name| !manifest.daemons.iter().any(|d| &d.name == *name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        let declared: Vec<&str> = manifest.daemons.iter().map(|d| d.name.as_str()).collect();
+        bail!(
+            "Skill '{}' has no daemon named {}\nIts daemons: {}",
+            manifest.name,
+            unknown.join(", "),
+            if declared.is_empty() {
+                "none".to_string()
+            } else {
+                declared.join(", ")
+            }
+        );
+    }
+
+    manifest.daemons.retain(|d| names.contains(&d.name));
+    if let Some(auth) = manifest.auth.as_mut() {
+        auth.daemons.retain(|daemon, _| names.contains(daemon));
+    }
+    Ok(())
+}
+
+/// Warn about imported fields nobody has reviewed, or with `deny`, refuse to
+/// export them.
+fn check_reviewed(skill_dir: &Path, manifest: &SkillManifest, deny: bool) -> Result<()> {
+    let unreviewed = super::skill_import::unreviewed_fields(skill_dir, manifest);
+    if unreviewed.is_empty() {
+        return Ok(());
+    }
+
+    let fields = unreviewed
+        .iter()
+        .map(|f| format!("{} ({:?})", f.field, f.confidence).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let hint = format!(
+        "Check them in skill.yaml (instructions in instructions/core.md): editing a field marks it reviewed, \
+         and a correct one can be removed from \"unreviewed\" in {}.",
+        skill_dir.join(".sync.json").display()
+    );
+
+    if deny {
+        bail!(
+            "{} field(s) were imported with low confidence and never reviewed: {}\n{}",
+            unreviewed.len(),
+            fields,
+            hint
+        );
+    }
+    println!(
+        "  {} {} field(s) were imported with low confidence and never reviewed: {}",
+        "!".yellow(),
+        unreviewed.len(),
+        fields
+    );
+    println!("    {}", hint.dimmed());
+    Ok(())
+}
+
+/// Header written above the entries `update_gitignore` adds.
+const GITIGNORE_HEADER: &str = "# Generated by fgp skill export";
+
+/// Add exported files to `<project_dir>/.gitignore`, returning how many
+/// entries were added. Entries already present are left alone.
+fn update_gitignore(project_dir: &Path, paths: &[&Path]) -> Result<usize> {
+    let gitignore_path = project_dir.join(".gitignore");
+    let existing = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    let present: std::collections::HashSet<&str> = existing.lines().map(|l| l.trim()).collect();
+
+    let mut entries: Vec<String> = paths
+        .iter()
+        .filter_map(|p| p.strip_prefix(project_dir).ok())
+        .map(|p| format!("/{}", p.display()))
+        .filter(|entry| !present.contains(entry.as_str()))
+        .collect();
+    entries.sort();
+    entries.dedup();
+
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let mut content = existing.clone();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !present.contains(GITIGNORE_HEADER) {
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(GITIGNORE_HEADER);
+        content.push('\n');
+    }
+    for entry in &entries {
+        content.push_str(entry);
+        content.push('\n');
+    }
+
+    fs::write(&gitignore_path, content)
+        .with_context(|| format!("Failed to write {}", gitignore_path.display()))?;
+
+    Ok(entries.len())
+}
+
+/// Dispatch a single export target.
+fn export_target(
+    target: &str,
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    options: &TargetOptions,
+    writer: &mut ExportWriter,
+) -> Result<()> {
+    let TargetOptions {
+        layout,
+        cursor_format,
+        tools_scope,
+        ref whats_new,
+        group_by_daemon,
+        ref usage,
+        ref examples,
+        ref commands,
+    } = *options;
+    let whats_new = whats_new.as_deref();
+    let sections = [usage_section(usage), whats_new.map(str::to_string)]
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| format!("{}\n{}", a, b));
+    let sections = sections.as_deref();
+
+    if tools_scope.is_some() && !SCOPED_TOOL_TARGETS.contains(&target) {
+        println!(
+            "  {} {} has no per-skill tool permissions; --tools-scope was not enforced",
+            "!".yellow(),
+            target
+        );
+    }
+    if whats_new.is_some() && !CHANGELOG_TARGETS.contains(&target) {
+        println!(
+            "  {} {} output has no What's New section; --since-version was not applied",
+            "!".yellow(),
+            target
+        );
+    }
+
+    if group_by_daemon && target != "mcp" {
+        println!(
+            "  {} {} output has no MCP tools; --group-by-daemon was not applied",
+            "!".yellow(),
+            target
+        );
+    }
     match target {
-        "claude-code" | "claude" => export_claude_code(&manifest, &skill_dir, &output_dir),
-        "cursor" => export_cursor(&manifest, &skill_dir, &output_dir),
-        "codex" => export_codex(&manifest, &skill_dir, &output_dir),
-        "mcp" => export_mcp(&manifest, &skill_dir, &output_dir),
-        "windsurf" => export_windsurf(&manifest, &skill_dir, &output_dir),
-        "zed" => export_zed(&manifest, &skill_dir, &output_dir),
-        "gemini" => export_gemini(&manifest, &skill_dir, &output_dir),
-        "aider" => export_aider(&manifest, &skill_dir, &output_dir),
+        "claude-code" | "claude" => {
+            let allowed_tools = match tools_scope {
+                Some(scope) => scoped_tools(manifest, scope)?,
+                None => Vec::new(),
+            };
+            export_claude_code(
+                manifest,
+                skill_dir,
+                output_dir,
+                layout,
+                &allowed_tools,
+                sections,
+                writer,
+            )
+        }
+        "cursor" => export_cursor(
+            manifest,
+            skill_dir,
+            &cursor_rules_path(manifest, output_dir, layout, cursor_format),
+            cursor_format,
+            sections,
+            examples,
+            writer,
+        ),
+        "codex" => export_codex(manifest, skill_dir, output_dir, usage, examples, writer),
+        "mcp" => export_mcp(
+            manifest,
+            skill_dir,
+            output_dir,
+            group_by_daemon,
+            usage,
+            examples,
+            writer,
+        ),
+        "windsurf" => export_windsurf(manifest, skill_dir, output_dir, sections, writer),
+        "zed" => export_zed(manifest, skill_dir, output_dir, sections, examples, writer),
+        "gemini" => export_gemini(manifest, skill_dir, output_dir, sections, examples, writer),
+        "aider" => export_aider(manifest, skill_dir, output_dir, usage, examples, writer),
+        "claude-desktop" => export_claude_desktop(manifest, writer),
+        "continue" => export_continue(manifest, writer),
         _ => bail!(
             "Unknown export target: {}\n\
-             Valid targets: claude-code, cursor, codex, mcp, windsurf, zed, gemini, aider",
+             Valid targets: claude-code, claude-desktop, continue, cursor, codex, mcp, windsurf, zed, gemini, aider, all",
             target
         ),
+    }?;
+
+    if let Some(commands) = commands {
+        if COMMAND_TARGETS.contains(&target) {
+            export_commands(target, commands, output_dir, layout, examples, writer)?;
+        } else {
+            println!(
+                "  {} {} has no slash commands; --with-commands was not applied",
+                "!".yellow(),
+                target
+            );
+        }
     }
+
+    Ok(())
+}
+
+/// Parts of the skill that `target`'s files have no place for, such as
+/// `workflows (digest, triage)`. No target carries config options or auth
+/// requirements.
+fn unrepresented(target: &str, manifest: &SkillManifest) -> Vec<String> {
+    let sorted = |names: Vec<&String>| {
+        let mut names: Vec<&str> = names.into_iter().map(String::as_str).collect();
+        names.sort_unstable();
+        names.join(", ")
+    };
+    let mut missing = Vec::new();
+
+    if !DOCUMENT_TARGETS.contains(&target) {
+        if manifest
+            .instructions
+            .as_ref()
+            .is_some_and(|i| i.core.is_some())
+        {
+            missing.push("instructions".to_string());
+        }
+        if let Some(ref triggers) = manifest.triggers {
+            if !triggers.keywords.is_empty() || !triggers.patterns.is_empty() {
+                missing.push("triggers".to_string());
+            }
+        }
+    }
+    if !manifest.workflows.is_empty() && !WORKFLOW_TARGETS.contains(&target) {
+        missing.push(format!(
+            "workflows ({})",
+            sorted(manifest.workflows.keys().collect())
+        ));
+    }
+    if !manifest.config.is_empty() {
+        missing.push(format!(
+            "config options ({})",
+            sorted(manifest.config.keys().collect())
+        ));
+    }
+    if let Some(ref auth) = manifest.auth {
+        let mut requirements: Vec<String> = auth
+            .daemons
+            .iter()
+            .map(|(daemon, kind)| format!("{}: {}", daemon, kind))
+            .collect();
+        requirements.sort();
+        requirements.extend(auth.secrets.iter().map(|secret| secret.name.clone()));
+        if !requirements.is_empty() {
+            missing.push(format!("auth requirements ({})", requirements.join(", ")));
+        }
+    }
+    missing
+}
+
+/// Warn about the parts of the skill agents reading `target`'s files won't
+/// see, with what `fgp skill import` knows the format leaves out.
+fn print_unrepresented(target: &str, manifest: &SkillManifest) {
+    let missing = unrepresented(target, manifest);
+    if missing.is_empty() {
+        return;
+    }
+
+    println!(
+        "  {} {} can't represent all of {}; agents using it won't see:",
+        "!".yellow(),
+        target,
+        manifest.name
+    );
+    for part in &missing {
+        println!("      - {}", part);
+    }
+    // claude-desktop and continue only register MCP servers, so they have no
+    // format of their own
+    if let Ok(format) = super::skill_import::parse_format_arg(target) {
+        println!("    {} format limitations:", format.name());
+        for limitation in super::skill_import::get_format_limitations(format) {
+            println!("      {}", format!("- {}", limitation).dimmed());
+        }
+    }
+}
+
+/// `fgp call` commands a skill runs: one per declared daemon method, or the
+/// `fgp call <daemon>.` prefix for daemons that don't list their methods.
+fn required_commands(manifest: &SkillManifest) -> Vec<String> {
+    manifest
+        .daemons
+        .iter()
+        .flat_map(|daemon| {
+            if daemon.methods.is_empty() {
+                vec![format!("fgp call {}.", daemon.name)]
+            } else {
+                daemon
+                    .methods
+                    .iter()
+                    .map(|method| {
+                        // Methods may be listed with or without the daemon prefix
+                        let method = method
+                            .strip_prefix(&format!("{}.", daemon.name))
+                            .unwrap_or(method);
+                        format!("fgp call {}.{}", daemon.name, method)
+                    })
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+/// Claude Code `allowed-tools` entries for the commands a skill needs that
+/// fall inside `scope`.
+///
+/// Commands outside the scope are dropped with a warning; a scope that
+/// leaves nothing is an error, since the skill couldn't do anything.
+fn scoped_tools(manifest: &SkillManifest, scope: &str) -> Result<Vec<String>> {
+    let commands = required_commands(manifest);
+    if commands.is_empty() {
+        println!(
+            "  {} {} uses no daemon methods; no tool permissions to scope",
+            "!".yellow(),
+            manifest.name
+        );
+        return Ok(Vec::new());
+    }
+
+    let (inside, outside): (Vec<String>, Vec<String>) = commands
+        .into_iter()
+        .partition(|command| super::skill::glob_match(scope, command));
+
+    if inside.is_empty() {
+        bail!(
+            "--tools-scope '{}' excludes every command {} needs: {}",
+            scope,
+            manifest.name,
+            outside.join(", ")
+        );
+    }
+    for command in &outside {
+        println!(
+            "  {} '{}' is outside --tools-scope '{}' and won't be allowed",
+            "!".yellow(),
+            command,
+            scope
+        );
+    }
+
+    // Claude Code matches `:*` rules by prefix, so arguments are allowed
+    Ok(inside
+        .into_iter()
+        .map(|command| format!("Bash({}:*)", command))
+        .collect())
 }
 
 /// Export for Claude Code (generates SKILL.md).
-fn export_claude_code(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+///
+/// Non-empty `allowed_tools` are written as the skill's `allowed-tools`.
+fn export_claude_code(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    layout: ExportLayout,
+    allowed_tools: &[String],
+    sections: Option<&str>,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     // Create output directory
-    let skill_output_dir = output_dir.join(&manifest.name);
-    fs::create_dir_all(&skill_output_dir)?;
+    let skill_output_dir = match layout {
+        ExportLayout::Standalone | ExportLayout::Plugin => output_dir.join(&manifest.name),
+        ExportLayout::Project => output_dir
+            .join(".claude")
+            .join("skills")
+            .join(&manifest.name),
+    };
 
     // Build SKILL.md content
     let mut skill_md = String::new();
@@ -98,6 +1082,9 @@ fn export_claude_code(manifest: &SkillManifest, skill_dir: &Path, output_dir: &P
     skill_md.push_str(&format!("name: {}\n", manifest.name));
     skill_md.push_str(&format!("description: {}\n", manifest.description));
     skill_md.push_str(&format!("version: {}\n", manifest.version));
+    if !allowed_tools.is_empty() {
+        skill_md.push_str(&format!("allowed-tools: {}\n", allowed_tools.join(", ")));
+    }
 
     // Add triggers
     if let Some(ref triggers) = manifest.triggers {
@@ -163,26 +1150,137 @@ fn export_claude_code(manifest: &SkillManifest, skill_dir: &Path, output_dir: &P
         }
     }
 
+    push_sections(&mut skill_md, sections);
+
     // Write SKILL.md
     let skill_md_path = skill_output_dir.join("SKILL.md");
-    fs::write(&skill_md_path, &skill_md)?;
+    writer.write(&skill_md_path, &skill_md)?;
 
-    println!(
-        "{} Exported Claude Code skill to: {}",
-        "✓".green().bold(),
-        skill_md_path.display()
-    );
+    if writer.announce() {
+        println!(
+            "{} Exported Claude Code skill to: {}",
+            "✓".green().bold(),
+            skill_md_path.display()
+        );
+
+        // Provide install hint (project layout is already where Claude Code looks)
+        if layout == ExportLayout::Standalone {
+            println!();
+            println!("{}:", "Install".cyan().bold());
+            println!("  cp -r {} ~/.claude/skills/", skill_output_dir.display());
+        }
+    }
 
-    // Provide install hint
-    println!();
-    println!("{}:", "Install".cyan().bold());
-    println!("  cp -r {} ~/.claude/skills/", skill_output_dir.display());
+    Ok(())
+}
+
+/// Export for Cursor (generates .cursorrules, or a .mdc rule).
+fn export_cursor(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    rules_path: &Path,
+    format: CursorFormat,
+    sections: Option<&str>,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
+    let mut rules = cursor_rules(manifest, skill_dir, examples)?;
+    push_sections(&mut rules, sections);
+
+    let content = match format {
+        CursorFormat::Rules => rules,
+        CursorFormat::Mdc => format!("{}{}", mdc_frontmatter(manifest), rules),
+    };
+
+    writer.write(rules_path, &content)?;
+
+    if writer.announce() {
+        println!(
+            "{} Exported Cursor rules to: {}",
+            "✓".green().bold(),
+            rules_path.display()
+        );
+    }
 
     Ok(())
 }
 
-/// Export for Cursor (generates .cursorrules).
-fn export_cursor(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+/// Where the Cursor rules go: `<name>.cursorrules` or `<name>.mdc`, the
+/// latter in `.cursor/rules/` for a project.
+fn cursor_rules_path(
+    manifest: &SkillManifest,
+    output_dir: &Path,
+    layout: ExportLayout,
+    format: CursorFormat,
+) -> PathBuf {
+    match (layout, format) {
+        (_, CursorFormat::Rules) => output_dir.join(format!("{}.cursorrules", manifest.name)),
+        (ExportLayout::Standalone | ExportLayout::Plugin, CursorFormat::Mdc) => {
+            output_dir.join(format!("{}.mdc", manifest.name))
+        }
+        (ExportLayout::Project, CursorFormat::Mdc) => output_dir
+            .join(".cursor")
+            .join("rules")
+            .join(format!("{}.mdc", manifest.name)),
+    }
+}
+
+/// MDC frontmatter for a Cursor project rule.
+///
+/// Trigger keywords are folded into the description (Cursor uses it to decide
+/// when to attach the rule), trigger patterns that look like file globs become
+/// `globs`, and the rule is only always applied when there are no triggers.
+fn mdc_frontmatter(manifest: &SkillManifest) -> String {
+    let keywords: &[String] = manifest
+        .triggers
+        .as_ref()
+        .map(|t| t.keywords.as_slice())
+        .unwrap_or_default();
+
+    let globs: Vec<&str> = manifest
+        .triggers
+        .as_ref()
+        .map(|t| {
+            t.patterns
+                .iter()
+                .map(|p| p.as_str())
+                .filter(|p| is_file_glob(p))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut description = manifest.description.trim().to_string();
+    if !keywords.is_empty() {
+        if !description.ends_with(['.', '!', '?']) {
+            description.push('.');
+        }
+        description.push_str(&format!(
+            " Use when the user mentions: {}.",
+            keywords.join(", ")
+        ));
+    }
+
+    let always_apply = keywords.is_empty() && globs.is_empty();
+
+    let mut md = String::from("---\n");
+    // JSON strings are valid YAML scalars and handle quoting/escaping
+    md.push_str(&format!(
+        "description: {}\n",
+        serde_json::to_string(&description).unwrap_or_default()
+    ));
+    md.push_str(&format!("globs: {}\n", globs.join(",")));
+    md.push_str(&format!("alwaysApply: {}\n", always_apply));
+    md.push_str("---\n\n");
+    md
+}
+
+/// Whether a trigger pattern is a file glob (e.g. `**/*.rs`) rather than prose.
+fn is_file_glob(pattern: &str) -> bool {
+    !pattern.contains(char::is_whitespace) && (pattern.contains('*') || pattern.contains('/'))
+}
+
+/// Body of the Cursor rules file.
+fn cursor_rules(manifest: &SkillManifest, skill_dir: &Path, examples: &Examples) -> Result<String> {
     let mut rules = String::new();
 
     rules.push_str(&format!("# {} - FGP Skill\n\n", manifest.name));
@@ -218,29 +1316,28 @@ fn export_cursor(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path)
         for daemon in &manifest.daemons {
             for method in &daemon.methods {
                 rules.push_str(&format!(
-                    "fgp call {}.{} -p '{{\"param\": \"value\"}}'\n",
-                    daemon.name, method
+                    "fgp call {}.{} -p '{}'\n",
+                    daemon.name,
+                    method,
+                    examples.params_or(&daemon.name, method, r#"{"param": "value"}"#)
                 ));
             }
         }
         rules.push_str("```\n");
     }
 
-    // Write file
-    let rules_path = output_dir.join(format!("{}.cursorrules", manifest.name));
-    fs::write(&rules_path, &rules)?;
-
-    println!(
-        "{} Exported Cursor rules to: {}",
-        "✓".green().bold(),
-        rules_path.display()
-    );
-
-    Ok(())
+    Ok(rules)
 }
 
 /// Export for Codex (generates tool spec).
-fn export_codex(manifest: &SkillManifest, _skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_codex(
+    manifest: &SkillManifest,
+    _skill_dir: &Path,
+    output_dir: &Path,
+    usage: &MethodUsage,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     // Generate a simple tool specification for Codex
     let mut spec = serde_json::json!({
         "name": manifest.name,
@@ -253,29 +1350,48 @@ fn export_codex(manifest: &SkillManifest, _skill_dir: &Path, output_dir: &Path)
     let tools = spec["tools"].as_array_mut().unwrap();
     for daemon in &manifest.daemons {
         for method in &daemon.methods {
+            let mut description = format!("{} {} operation", daemon.name, method);
+            if let Some(note) = usage.get(&(daemon.name.clone(), method.clone())) {
+                description.push_str(&format!(" ({})", note));
+            }
             tools.push(serde_json::json!({
                 "name": format!("{}.{}", daemon.name, method),
-                "description": format!("{} {} operation", daemon.name, method),
-                "invocation": format!("fgp call {}.{} -p '{{...}}'", daemon.name, method)
+                "description": description,
+                "invocation": format!(
+                    "fgp call {}.{} -p '{}'",
+                    daemon.name,
+                    method,
+                    examples.params_or(&daemon.name, method, "{...}")
+                )
             }));
         }
     }
 
     // Write file
     let spec_path = output_dir.join(format!("{}.codex.json", manifest.name));
-    fs::write(&spec_path, serde_json::to_string_pretty(&spec)?)?;
+    writer.write(&spec_path, &serde_json::to_string_pretty(&spec)?)?;
 
-    println!(
-        "{} Exported Codex spec to: {}",
-        "✓".green().bold(),
-        spec_path.display()
-    );
+    if writer.announce() {
+        println!(
+            "{} Exported Codex spec to: {}",
+            "✓".green().bold(),
+            spec_path.display()
+        );
+    }
 
     Ok(())
 }
 
 /// Export for MCP (generates tool schema).
-fn export_mcp(manifest: &SkillManifest, _skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_mcp(
+    manifest: &SkillManifest,
+    _skill_dir: &Path,
+    output_dir: &Path,
+    group_by_daemon: bool,
+    usage: &MethodUsage,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     let prefix = manifest
         .exports
         .as_ref()
@@ -284,44 +1400,397 @@ fn export_mcp(manifest: &SkillManifest, _skill_dir: &Path, output_dir: &Path) ->
         .map(|s| s.as_str())
         .unwrap_or(&manifest.name);
 
-    let mut mcp_tools = Vec::new();
+    for daemon in &manifest.daemons {
+        if !daemon.methods.is_empty() && !examples.declared.contains_key(&daemon.name) {
+            println!(
+                "  {} {} has no installed manifest; its tools take any params",
+                "!".yellow(),
+                daemon.name
+            );
+        }
+    }
+    let mcp_tools = mcp_tools(manifest, prefix, group_by_daemon, usage, examples);
+
+    if !group_by_daemon {
+        if let Some(warning) = mcp_bridge::tool_count_warning(mcp_tools.len()) {
+            println!("  {} {}", "!".yellow(), warning);
+            println!("    Export with --group-by-daemon to list one tool per daemon.");
+        }
+    }
+
+    let mcp_spec = serde_json::json!({
+        "name": manifest.name,
+        "version": manifest.version,
+        "description": manifest.description,
+        "tools": mcp_tools
+    });
+
+    // Write file
+    let mcp_path = output_dir.join(format!("{}.mcp.json", manifest.name));
+    writer.write(&mcp_path, &serde_json::to_string_pretty(&mcp_spec)?)?;
+
+    if writer.announce() {
+        println!(
+            "{} Exported MCP schema to: {}",
+            "✓".green().bold(),
+            mcp_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Export for Claude Desktop: an `fgp-<daemon>` MCP server for each of the
+/// skill's daemons in `claude_desktop_config.json`. Claude Desktop runs MCP
+/// servers rather than reading skills, so the config is updated in place,
+/// wherever the output directory is.
+fn export_claude_desktop(manifest: &SkillManifest, writer: &mut ExportWriter) -> Result<()> {
+    if manifest.daemons.is_empty() {
+        println!(
+            "  {} {} has no daemons; there are no MCP servers to add to Claude Desktop",
+            "!".yellow(),
+            manifest.name
+        );
+        return Ok(());
+    }
+
+    let config_path = claude_desktop_config_path()?;
+    let existing = match fs::read_to_string(&config_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", config_path.display()))
+        }
+    };
+    let config = merge_desktop_config(existing.as_deref(), manifest)
+        .with_context(|| format!("Can't update {}", config_path.display()))?;
+
+    let backup = config_path.with_extension("json.bak");
+    let changed = existing.as_deref().is_some_and(|old| old != config);
+    if changed && writer.mode != WriteMode::Check {
+        fs::copy(&config_path, &backup)
+            .with_context(|| format!("Failed to back up {}", config_path.display()))?;
+    }
+    writer.write(&config_path, &config)?;
+
+    if writer.announce() {
+        let servers: Vec<String> = manifest
+            .daemons
+            .iter()
+            .map(|daemon| format!("fgp-{}", daemon.name))
+            .collect();
+        println!(
+            "{} Added {} to Claude Desktop config: {}",
+            "✓".green().bold(),
+            servers.join(", "),
+            config_path.display()
+        );
+        if changed {
+            println!("  Previous config saved to {}", backup.display());
+        }
+        println!();
+        println!("{}:", "Usage".cyan().bold());
+        println!("  Restart Claude Desktop to load the servers.");
+    }
+
+    Ok(())
+}
+
+/// A Claude Code plugin that `--merge-into` adds a skill to.
+#[derive(Debug)]
+struct Plugin {
+    /// `.claude-plugin/plugin.json` (or a `plugin.json` at the root), if the
+    /// plugin has one
+    manifest_path: Option<PathBuf>,
+    manifest: Option<Value>,
+    /// Where the plugin's skills live: `skills/`, or the directory its
+    /// manifest names in `"skills"`
+    skills_dir: PathBuf,
+    /// `skills_dir` as the manifest writes paths (`./skills`)
+    skills_path: String,
+}
+
+/// The plugin at `root`, with its manifest if it has one.
+fn find_plugin(root: &Path) -> Result<Plugin> {
+    if !root.is_dir() {
+        bail!("Plugin directory not found: {}", root.display());
+    }
+
+    let manifest_path = [
+        root.join(".claude-plugin").join("plugin.json"),
+        root.join("plugin.json"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file());
+    let manifest = match &manifest_path {
+        Some(path) => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let manifest: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            if !manifest.is_object() {
+                bail!("Expected a JSON object in {}", path.display());
+            }
+            Some(manifest)
+        }
+        None => None,
+    };
+
+    let skills_path = match manifest.as_ref().map(|manifest| &manifest["skills"]) {
+        Some(Value::String(path)) => format!("./{}", normalize_plugin_path(path)),
+        _ => "./skills".to_string(),
+    };
+    Ok(Plugin {
+        skills_dir: root.join(normalize_plugin_path(&skills_path)),
+        manifest_path,
+        manifest,
+        skills_path,
+    })
+}
+
+/// A plugin-relative path without its leading `./` or trailing `/`.
+fn normalize_plugin_path(path: &str) -> &str {
+    path.trim_start_matches("./").trim_end_matches('/')
+}
+
+/// The plugin manifest with `./skills/<name>` added to its `"skills"` list,
+/// or `None` if there's no list to add to or it already covers the skill.
+fn add_plugin_skill(manifest: &Value, skills_path: &str, name: &str) -> Option<Value> {
+    let Value::Array(skills) = &manifest["skills"] else {
+        return None;
+    };
+    let entry = format!("{}/{}", skills_path, name);
+    let listed = skills.iter().filter_map(Value::as_str).any(|path| {
+        let path = normalize_plugin_path(path);
+        path == normalize_plugin_path(skills_path) || path == normalize_plugin_path(&entry)
+    });
+    if listed {
+        return None;
+    }
+
+    let mut manifest = manifest.clone();
+    if let Some(skills) = manifest["skills"].as_array_mut() {
+        skills.push(Value::String(entry));
+    }
+    Some(manifest)
+}
+
+/// List the exported skill in the plugin's manifest, if it keeps a list.
+/// Without a manifest, or without a `"skills"` list in it, Claude Code finds
+/// the skill in the plugin's skills folder by itself.
+fn register_plugin_skill(plugin: &Plugin, name: &str, writer: &mut ExportWriter) -> Result<()> {
+    let (Some(manifest_path), Some(manifest)) = (&plugin.manifest_path, &plugin.manifest) else {
+        if writer.announce() {
+            println!(
+                "  {} No plugin.json found; wrote the skill without updating a plugin manifest",
+                "!".yellow()
+            );
+        }
+        return Ok(());
+    };
+
+    match add_plugin_skill(manifest, &plugin.skills_path, name) {
+        Some(updated) => {
+            writer.write(
+                manifest_path,
+                &(serde_json::to_string_pretty(&updated)? + "\n"),
+            )?;
+            if writer.announce() {
+                println!(
+                    "{} Added {}/{} to {}",
+                    "✓".green().bold(),
+                    plugin.skills_path,
+                    name,
+                    manifest_path.display()
+                );
+            }
+        }
+        None if writer.announce() && manifest["skills"].is_array() => {
+            println!(
+                "  {} {} already lists {}",
+                "○".dimmed(),
+                manifest_path.display(),
+                name
+            );
+        }
+        None if writer.announce() => {
+            println!(
+                "  {} {} has no skills list; Claude Code finds the skill in {}/",
+                "○".dimmed(),
+                manifest_path.display(),
+                plugin.skills_path
+            );
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Add an `fgp-<daemon>` MCP server to Continue's config.yaml for each of the
+/// skill's daemons. Like Claude Desktop, Continue reads MCP servers rather
+/// than skills, so its config is updated in place.
+fn export_continue(manifest: &SkillManifest, writer: &mut ExportWriter) -> Result<()> {
+    if manifest.daemons.is_empty() {
+        println!(
+            "  {} {} has no daemons; there are no MCP servers to add to Continue",
+            "!".yellow(),
+            manifest.name
+        );
+        return Ok(());
+    }
+
+    let config_path = super::skill::continue_config_path()?;
+    let config = super::skill::load_continue_config(&config_path, writer.mode == WriteMode::Check)?;
+    let daemons: Vec<String> = manifest.daemons.iter().map(|d| d.name.clone()).collect();
+    let config = super::skill::merge_continue_config(config, &daemons)
+        .with_context(|| format!("Can't update {}", config_path.display()))?;
+    writer.write(&config_path, &config)?;
+
+    if writer.announce() {
+        let servers: Vec<String> = daemons
+            .iter()
+            .map(|daemon| format!("fgp-{}", daemon))
+            .collect();
+        println!(
+            "{} Added {} to Continue config: {}",
+            "✓".green().bold(),
+            servers.join(", "),
+            config_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Claude Desktop's config file: under `~/Library/Application Support/Claude`
+/// on macOS, `%APPDATA%\Claude` on Windows and `~/.config/Claude` elsewhere.
+fn claude_desktop_config_path() -> Result<PathBuf> {
+    Ok(dirs::config_dir()
+        .context("Could not find the config directory")?
+        .join("Claude")
+        .join("claude_desktop_config.json"))
+}
+
+/// Claude Desktop's config (`existing`, if there is one) with an MCP server
+/// for each of the skill's daemons added to `mcpServers`. Other servers and
+/// settings are left as they were; a config that isn't a JSON object is an
+/// error rather than something to overwrite.
+fn merge_desktop_config(existing: Option<&str>, manifest: &SkillManifest) -> Result<String> {
+    let mut config: Value = match existing {
+        Some(content) if !content.trim().is_empty() => {
+            serde_json::from_str(content).context("Invalid JSON")?
+        }
+        _ => serde_json::json!({}),
+    };
+    let Some(config_map) = config.as_object_mut() else {
+        bail!("Expected a JSON object");
+    };
+    let servers = config_map
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .context("Expected mcpServers to be a JSON object")?;
+
+    for daemon in &manifest.daemons {
+        servers.insert(
+            format!("fgp-{}", daemon.name),
+            super::skill::fgp_mcp_server(&daemon.name),
+        );
+    }
+
+    Ok(serde_json::to_string_pretty(&config)? + "\n")
+}
+
+/// MCP tools for the skill's daemon methods: one per method, or with
+/// `group_by_daemon` one per daemon taking `method` and `params`. Methods'
+/// `usage` notes go in the descriptions.
+fn mcp_tools(
+    manifest: &SkillManifest,
+    prefix: &str,
+    group_by_daemon: bool,
+    usage: &MethodUsage,
+    examples: &Examples,
+) -> Vec<serde_json::Value> {
+    let mut tools = Vec::new();
 
     for daemon in &manifest.daemons {
-        for method in &daemon.methods {
-            mcp_tools.push(serde_json::json!({
-                "name": format!("{}_{}", prefix, method),
-                "description": format!("{} via FGP {} daemon", method, daemon.name),
-                "inputSchema": {
+        // Params and descriptions come from the daemon's installed manifest;
+        // skill.yaml only names the methods
+        let declared: Vec<Option<&ManifestMethod>> = daemon
+            .methods
+            .iter()
+            .map(|method| examples.method(&daemon.name, method))
+            .collect();
+        let schemas: Vec<Value> = declared
+            .iter()
+            .map(|found| match found {
+                Some(found) => methods_export::params_schema(&found.params),
+                None => serde_json::json!({
                     "type": "object",
                     "properties": {},
                     "required": []
-                }
-            }));
-        }
-    }
-
-    let mcp_spec = serde_json::json!({
-        "name": manifest.name,
-        "version": manifest.version,
-        "description": manifest.description,
-        "tools": mcp_tools
-    });
+                }),
+            })
+            .collect();
 
-    // Write file
-    let mcp_path = output_dir.join(format!("{}.mcp.json", manifest.name));
-    fs::write(&mcp_path, serde_json::to_string_pretty(&mcp_spec)?)?;
+        if group_by_daemon {
+            if daemon.methods.is_empty() {
+                continue;
+            }
+            let methods: Vec<mcp_bridge::GroupedMethod> = daemon
+                .methods
+                .iter()
+                .zip(&declared)
+                .zip(&schemas)
+                .map(|((method, found), schema)| mcp_bridge::GroupedMethod {
+                    name: method,
+                    description: usage
+                        .get(&(daemon.name.clone(), method.clone()))
+                        .map(String::as_str)
+                        .or_else(|| found.and_then(|found| found.description.as_deref())),
+                    params: found.map(|_| schema),
+                    destructive: found.is_some_and(|found| found.destructive),
+                })
+                .collect();
+            tools.push(mcp_bridge::grouped_tool(
+                &format!("{}_{}", prefix, daemon.name),
+                &daemon.name,
+                &methods,
+            ));
+            continue;
+        }
 
-    println!(
-        "{} Exported MCP schema to: {}",
-        "✓".green().bold(),
-        mcp_path.display()
-    );
+        for ((method, found), schema) in daemon.methods.iter().zip(&declared).zip(schemas) {
+            let mut description = match found.and_then(|found| found.description.as_deref()) {
+                Some(text) => format!("{} (via FGP {} daemon)", text, daemon.name),
+                None => format!("{} via FGP {} daemon", method, daemon.name),
+            };
+            if let Some(note) = usage.get(&(daemon.name.clone(), method.clone())) {
+                description.push_str(&format!(" ({})", note));
+            }
+            let mut tool = serde_json::json!({
+                "name": format!("{}_{}", prefix, method),
+                "description": description,
+                "inputSchema": schema
+            });
+            if found.is_some_and(|found| found.destructive) {
+                tool["annotations"] = serde_json::json!({ "destructiveHint": true });
+            }
+            tools.push(tool);
+        }
+    }
 
-    Ok(())
+    tools
 }
 
 /// Export for Windsurf (generates cascade rules).
-fn export_windsurf(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_windsurf(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    sections: Option<&str>,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     let mut rules = String::new();
 
     rules.push_str(&format!("# {} - FGP Skill for Windsurf\n\n", manifest.name));
@@ -355,21 +1824,32 @@ fn export_windsurf(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path
         }
     }
 
+    push_sections(&mut rules, sections);
+
     // Write file
     let rules_path = output_dir.join(format!("{}.windsurf.md", manifest.name));
-    fs::write(&rules_path, &rules)?;
+    writer.write(&rules_path, &rules)?;
 
-    println!(
-        "{} Exported Windsurf rules to: {}",
-        "✓".green().bold(),
-        rules_path.display()
-    );
+    if writer.announce() {
+        println!(
+            "{} Exported Windsurf rules to: {}",
+            "✓".green().bold(),
+            rules_path.display()
+        );
+    }
 
     Ok(())
 }
 
 /// Export for Zed (generates .rules file for Zed's AI assistant).
-fn export_zed(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_zed(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    sections: Option<&str>,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     let mut rules = String::new();
 
     // Zed rules format - plain text instructions for the AI assistant
@@ -413,8 +1893,10 @@ fn export_zed(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) ->
             for daemon in &manifest.daemons {
                 for method in &daemon.methods {
                     rules.push_str(&format!(
-                        "fgp call {}.{} -p '{{\"param\": \"value\"}}'\n",
-                        daemon.name, method
+                        "fgp call {}.{} -p '{}'\n",
+                        daemon.name,
+                        method,
+                        examples.params_or(&daemon.name, method, r#"{"param": "value"}"#)
                     ));
                 }
             }
@@ -443,30 +1925,40 @@ fn export_zed(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) ->
         }
     }
 
+    push_sections(&mut rules, sections);
+
     // Write .rules file (Zed's native format)
     let rules_path = output_dir.join(format!("{}.rules", manifest.name));
-    fs::write(&rules_path, &rules)?;
+    writer.write(&rules_path, &rules)?;
 
-    println!(
-        "{} Exported Zed rules to: {}",
-        "✓".green().bold(),
-        rules_path.display()
-    );
+    if writer.announce() {
+        println!(
+            "{} Exported Zed rules to: {}",
+            "✓".green().bold(),
+            rules_path.display()
+        );
 
-    // Provide usage hints
-    println!();
-    println!("{}:", "Usage".cyan().bold());
-    println!("  1. Copy to project root as .rules");
-    println!("  2. Or add to Zed's Rules Library (Cmd+Alt+L)");
+        // Provide usage hints
+        println!();
+        println!("{}:", "Usage".cyan().bold());
+        println!("  1. Copy to project root as .rules");
+        println!("  2. Or add to Zed's Rules Library (Cmd+Alt+L)");
+    }
 
     Ok(())
 }
 
 /// Export for Gemini CLI (generates extension directory with gemini-extension.json + GEMINI.md).
-fn export_gemini(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_gemini(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    sections: Option<&str>,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
     // Create extension directory
     let ext_dir = output_dir.join(&manifest.name);
-    fs::create_dir_all(&ext_dir)?;
 
     // Generate gemini-extension.json manifest
     let extension_json = serde_json::json!({
@@ -475,9 +1967,9 @@ fn export_gemini(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path)
         "contextFileName": "GEMINI.md"
     });
     let manifest_path = ext_dir.join("gemini-extension.json");
-    fs::write(
+    writer.write(
         &manifest_path,
-        serde_json::to_string_pretty(&extension_json)?,
+        &serde_json::to_string_pretty(&extension_json)?,
     )?;
 
     // Generate GEMINI.md context file
@@ -515,108 +2007,1209 @@ fn export_gemini(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path)
             gemini_md.push_str("```bash\n");
             for daemon in &manifest.daemons {
                 for method in &daemon.methods {
-                    gemini_md.push_str(&format!("fgp call {}.{} -p '{{}}'\n", daemon.name, method));
+                    gemini_md.push_str(&format!(
+                        "fgp call {}.{} -p '{}'\n",
+                        daemon.name,
+                        method,
+                        examples.params_or(&daemon.name, method, "{}")
+                    ));
                 }
             }
             gemini_md.push_str("```\n");
         }
     }
 
+    push_sections(&mut gemini_md, sections);
+
     let gemini_md_path = ext_dir.join("GEMINI.md");
-    fs::write(&gemini_md_path, &gemini_md)?;
+    writer.write(&gemini_md_path, &gemini_md)?;
 
-    println!(
-        "{} Exported Gemini extension to: {}",
-        "✓".green().bold(),
-        ext_dir.display()
-    );
+    if writer.announce() {
+        println!(
+            "{} Exported Gemini extension to: {}",
+            "✓".green().bold(),
+            ext_dir.display()
+        );
 
-    // Provide usage hints
-    println!();
-    println!("{}:", "Usage".cyan().bold());
-    println!("  1. Copy directory to ~/.gemini/extensions/");
-    println!(
-        "  2. Or run: gemini extensions install {}",
-        ext_dir.display()
-    );
+        // Provide usage hints
+        println!();
+        println!("{}:", "Usage".cyan().bold());
+        println!("  1. Copy directory to ~/.gemini/extensions/");
+        println!(
+            "  2. Or run: gemini extensions install {}",
+            ext_dir.display()
+        );
+    }
 
     Ok(())
 }
 
 /// Export for Aider (generates CONVENTIONS.md).
-fn export_aider(manifest: &SkillManifest, skill_dir: &Path, output_dir: &Path) -> Result<()> {
+fn export_aider(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    output_dir: &Path,
+    usage: &MethodUsage,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
+    let mut conventions = aider_conventions(manifest, skill_dir, examples)?;
+    push_sections(&mut conventions, usage_section(usage).as_deref());
+
+    // Write CONVENTIONS.md
+    let conventions_path = output_dir.join(format!("{}.CONVENTIONS.md", manifest.name));
+    writer.write(&conventions_path, &conventions)?;
+
+    if writer.announce() {
+        println!(
+            "{} Exported Aider conventions to: {}",
+            "✓".green().bold(),
+            conventions_path.display()
+        );
+
+        // Provide usage hints
+        println!();
+        println!("{}:", "Usage".cyan().bold());
+        println!("  1. Rename to CONVENTIONS.md in project root");
+        println!("  2. Run: aider --read CONVENTIONS.md");
+        println!("  3. Or add to .aider.conf.yml: read: CONVENTIONS.md");
+    }
+
+    Ok(())
+}
+
+/// Render an Aider CONVENTIONS.md.
+///
+/// The section names (Overview, When to apply, Commands, Usage notes) are the
+/// ones `fgp skill import` looks for, so the file round-trips.
+pub fn aider_conventions(
+    manifest: &SkillManifest,
+    skill_dir: &Path,
+    examples: &Examples,
+) -> Result<String> {
     let mut conventions = String::new();
 
     conventions.push_str(&format!("# {} Conventions\n\n", manifest.name));
+
+    conventions.push_str("## Overview\n\n");
     conventions.push_str(&format!("{}\n\n", manifest.description));
 
-    // Read aider-specific or core instructions
-    let aider_instructions = manifest.instructions.as_ref().and_then(|i| i.core.as_ref());
+    if let Some(ref triggers) = manifest.triggers {
+        if !triggers.keywords.is_empty() {
+            conventions.push_str("## When to apply\n\n");
+            for keyword in &triggers.keywords {
+                conventions.push_str(&format!("- {}\n", keyword));
+            }
+            conventions.push('\n');
+        }
+    }
+
+    if !manifest.daemons.is_empty() {
+        conventions.push_str("## Commands\n\n");
+        conventions.push_str("Use Fast Gateway Protocol daemons for these operations:\n\n");
+        for daemon in &manifest.daemons {
+            let optional = if daemon.optional { " (optional)" } else { "" };
+            for method in &daemon.methods {
+                conventions.push_str(&format!(
+                    "- `fgp call {}.{} -p '{}'`{}\n",
+                    daemon.name,
+                    method,
+                    examples.params_or(&daemon.name, method, "{}"),
+                    optional
+                ));
+            }
+        }
+        conventions.push('\n');
+    }
 
-    if let Some(instruction_path) = aider_instructions {
-        let full_path = skill_dir.join(instruction_path);
-        if full_path.exists() {
-            let instructions = fs::read_to_string(&full_path)?;
-            conventions.push_str(&instructions);
+    conventions.push_str("## Usage notes\n\n");
+    let core = manifest
+        .instructions
+        .as_ref()
+        .and_then(|i| i.core.as_ref())
+        .map(|path| skill_dir.join(path))
+        .filter(|path| path.exists());
+    match core {
+        Some(path) => {
+            let instructions = fs::read_to_string(&path)?;
+            conventions.push_str(instructions.trim_end());
+            conventions.push_str("\n\n");
         }
-    } else {
-        // Generate default conventions
-        conventions.push_str("## Guidelines\n\n");
+        None => {
+            conventions.push_str(
+                "Prefer the `fgp call` commands above over ad-hoc scripts or direct API calls.\n",
+            );
+            conventions.push_str("Pass parameters as JSON with `-p`.\n\n");
+        }
+    }
 
-        if let Some(ref triggers) = manifest.triggers {
-            conventions.push_str("When working with this skill:\n");
-            for keyword in &triggers.keywords {
-                conventions.push_str(&format!("- Use when dealing with \"{}\"\n", keyword));
+    if !manifest.workflows.is_empty() {
+        conventions.push_str("## Workflows\n\n");
+        let mut workflows: Vec<_> = manifest.workflows.iter().collect();
+        workflows.sort_by_key(|(name, _)| *name);
+        for (name, workflow) in workflows {
+            let desc = workflow.description.as_deref().unwrap_or("");
+            conventions.push_str(&format!("- **{}**: {}\n", name, desc));
+        }
+        conventions.push('\n');
+    }
+
+    Ok(conventions)
+}
+
+// ============================================================================
+// Example params (--example-seed)
+// ============================================================================
+
+/// Words example string values are picked from
+const EXAMPLE_WORDS: &[&str] = &[
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel",
+];
+
+/// An example value for `param` of `method`, of `param_type` (a JSON Schema
+/// type name; anything else is treated as a string). The value is derived
+/// from a hash of the names, so it's the same on every run; `seed` picks a
+/// different one.
+pub(crate) fn example_value(seed: u64, method: &str, param: &str, param_type: &str) -> Value {
+    let digest = Sha256::digest(format!("{}:{}:{}", seed, method, param).as_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let hash = u64::from_le_bytes(bytes);
+    let word = EXAMPLE_WORDS[(hash % EXAMPLE_WORDS.len() as u64) as usize];
+
+    match param_type {
+        "integer" => Value::from(1 + hash % 100),
+        "number" => Value::from((hash % 1000) as f64 / 10.0),
+        "boolean" => Value::from(hash % 2 == 0),
+        "array" => Value::from(vec![word]),
+        "object" => Value::Object(Default::default()),
+        _ => {
+            let name = param.to_lowercase();
+            if name.contains("email") {
+                Value::from(format!("{}@example.com", word))
+            } else if name.contains("url") {
+                Value::from(format!("https://example.com/{}", word))
+            } else if param == "id" || param.ends_with("_id") || param.ends_with("Id") {
+                Value::from(format!("{}-{}", word, hash % 1000))
+            } else {
+                Value::from(word)
             }
-            conventions.push('\n');
         }
+    }
+}
 
-        // Add FGP daemon usage
-        if !manifest.daemons.is_empty() {
-            conventions.push_str("## FGP Integration\n\n");
-            conventions
-                .push_str("This project uses Fast Gateway Protocol daemons for performance.\n\n");
-            conventions.push_str("### Available Commands\n\n");
-            for daemon in &manifest.daemons {
-                let optional = if daemon.optional { " (optional)" } else { "" };
-                conventions.push_str(&format!("**{}**{}:\n", daemon.name, optional));
-                for method in &daemon.methods {
-                    conventions.push_str(&format!(
-                        "- `fgp call {}.{} -p '{{\"param\": \"value\"}}'`\n",
-                        daemon.name, method
-                    ));
+/// Example `-p` params for the `fgp call` lines in exported docs: the
+/// skill's own `examples` where it has them (an import keeps the ones its
+/// source documented), and otherwise built from the params the skill's
+/// daemons declare in their installed manifest.json.
+#[derive(Debug, Default)]
+pub struct Examples {
+    seed: u64,
+    /// Declared methods by daemon
+    declared: BTreeMap<String, Vec<ManifestMethod>>,
+    /// The skill's example params by daemon, then method
+    documented: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Examples {
+    /// Examples for `manifest`'s daemons, varied by `seed`.
+    pub fn new(manifest: &SkillManifest, seed: u64) -> Self {
+        let declared = manifest
+            .daemons
+            .iter()
+            .map(|daemon| {
+                (
+                    daemon.name.clone(),
+                    guardrails::declared_methods(&daemon.name),
+                )
+            })
+            .filter(|(_, methods)| !methods.is_empty())
+            .collect();
+        let documented = manifest
+            .daemons
+            .iter()
+            .filter(|daemon| !daemon.examples.is_empty())
+            .map(|daemon| (daemon.name.clone(), daemon.examples.clone()))
+            .collect();
+        Self {
+            seed,
+            declared,
+            documented,
+        }
+    }
+
+    /// The skill's example params for `method`, or else the JSON object of
+    /// example values for its required params (a declared default where
+    /// there is one); `None` if neither the skill nor its daemon's manifest
+    /// has the method.
+    fn params(&self, daemon: &str, method: &str) -> Option<String> {
+        if let Some(params) = self.documented(daemon, method) {
+            return Some(params.to_string());
+        }
+        let found = self.method(daemon, method)?;
+        let mut required: Vec<_> = found.params.iter().filter(|p| p.required).collect();
+        required.sort_by(|a, b| a.name.cmp(&b.name));
+        let fields: Vec<String> = required
+            .into_iter()
+            .map(|param| {
+                let value = param.default.clone().unwrap_or_else(|| {
+                    example_value(
+                        self.seed,
+                        &found.name,
+                        &param.name,
+                        param.param_type.as_deref().unwrap_or("string"),
+                    )
+                });
+                format!("{}: {}", Value::from(param.name.as_str()), value)
+            })
+            .collect();
+        Some(format!("{{{}}}", fields.join(", ")))
+    }
+
+    /// `method`'s params in the skill's `examples`, listed with or without
+    /// the daemon prefix
+    fn documented(&self, daemon: &str, method: &str) -> Option<&str> {
+        let examples = self.documented.get(daemon)?;
+        let short = method
+            .strip_prefix(&format!("{}.", daemon))
+            .unwrap_or(method);
+        examples
+            .get(short)
+            .or_else(|| examples.get(&format!("{}.{}", daemon, short)))
+            .map(String::as_str)
+    }
+
+    /// `method` as declared in its daemon's manifest.
+    fn method(&self, daemon: &str, method: &str) -> Option<&ManifestMethod> {
+        find_method(self.declared.get(daemon)?, daemon, method)
+    }
+
+    /// [`Examples::params`], or `fallback` for methods without declared params.
+    fn params_or(&self, daemon: &str, method: &str, fallback: &str) -> String {
+        self.params(daemon, method)
+            .unwrap_or_else(|| fallback.to_string())
+    }
+}
+
+// ============================================================================
+// Slash commands (--with-commands)
+// ============================================================================
+
+/// A slash command that calls one daemon method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlashCommand {
+    /// Without the leading `/`, e.g. `gmail-send`
+    name: String,
+    daemon: String,
+    /// Without the daemon prefix, as the skill lists it
+    method: String,
+}
+
+impl SlashCommand {
+    fn new(daemon: &str, method: &str) -> Self {
+        let method = method
+            .strip_prefix(&format!("{}.", daemon))
+            .unwrap_or(method);
+        Self {
+            name: format!("{}-{}", daemon, method.replace(['.', '_'], "-")).to_lowercase(),
+            daemon: daemon.to_string(),
+            method: method.to_string(),
+        }
+    }
+}
+
+/// The slash commands for a skill: one per method its daemons list, or if
+/// `triggers.commands` names any, just those. Also returns the listed
+/// commands that match no method.
+fn slash_commands(manifest: &SkillManifest) -> (Vec<SlashCommand>, Vec<String>) {
+    let all: Vec<SlashCommand> = manifest
+        .daemons
+        .iter()
+        .flat_map(|daemon| {
+            daemon
+                .methods
+                .iter()
+                .map(|method| SlashCommand::new(&daemon.name, method))
+        })
+        .collect();
+
+    let curated = manifest
+        .triggers
+        .as_ref()
+        .map(|t| t.commands.as_slice())
+        .unwrap_or_default();
+    if curated.is_empty() {
+        return (all, Vec::new());
+    }
+
+    let mut commands = Vec::new();
+    let mut unmatched = Vec::new();
+    for listed in curated {
+        let name = listed.trim().trim_start_matches('/').to_lowercase();
+        match all.iter().find(|command| command.name == name) {
+            Some(command) if !commands.contains(command) => commands.push(command.clone()),
+            Some(_) => {}
+            None => unmatched.push(listed.clone()),
+        }
+    }
+    (commands, unmatched)
+}
+
+/// Write `commands` where `target` reads them: `.claude/commands/` or
+/// `.cursor/commands/` in the project layout, otherwise a `commands/`
+/// directory next to the exported skill.
+fn export_commands(
+    target: &str,
+    commands: &[SlashCommand],
+    output_dir: &Path,
+    layout: ExportLayout,
+    examples: &Examples,
+    writer: &mut ExportWriter,
+) -> Result<()> {
+    let cursor = target == "cursor";
+    let agent_dir = if cursor { ".cursor" } else { ".claude" };
+    let commands_dir = match layout {
+        ExportLayout::Project => output_dir.join(agent_dir).join("commands"),
+        ExportLayout::Standalone if cursor => output_dir.join("cursor-commands"),
+        ExportLayout::Standalone | ExportLayout::Plugin => output_dir.join("commands"),
+    };
+
+    for command in commands {
+        let body = command_body(command, examples);
+        // Cursor commands are plain Markdown; Claude Code reads frontmatter
+        let content = if cursor {
+            body
+        } else {
+            format!("{}{}", command_frontmatter(command, examples), body)
+        };
+        writer.write(&commands_dir.join(format!("{}.md", command.name)), &content)?;
+    }
+
+    if writer.announce() && !commands.is_empty() {
+        println!(
+            "{} Exported {} slash command(s) to: {}",
+            "✓".green().bold(),
+            commands.len(),
+            commands_dir.display()
+        );
+        if layout == ExportLayout::Standalone {
+            println!();
+            println!("{}:", "Install".cyan().bold());
+            println!(
+                "  cp {}/*.md ~/{}/commands/",
+                commands_dir.display(),
+                agent_dir
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Claude Code frontmatter for a slash command, limiting it to the one
+/// `fgp call` it makes.
+fn command_frontmatter(command: &SlashCommand, examples: &Examples) -> String {
+    let declared = examples.method(&command.daemon, &command.method);
+    let description = declared
+        .and_then(|m| m.description.as_deref())
+        .filter(|d| !d.trim().is_empty())
+        .map(|d| d.trim().to_string())
+        .unwrap_or_else(|| format!("Call {}.{}", command.daemon, command.method));
+
+    let mut md = String::from("---\n");
+    // JSON strings are valid YAML scalars and handle quoting/escaping
+    md.push_str(&format!(
+        "description: {}\n",
+        serde_json::to_string(&description).unwrap_or_default()
+    ));
+    if let Some(method) = declared {
+        let mut params: Vec<&str> = method.params.iter().map(|p| p.name.as_str()).collect();
+        params.sort();
+        if !params.is_empty() {
+            md.push_str(&format!("argument-hint: \"[{}]\"\n", params.join("] [")));
+        }
+    }
+    md.push_str(&format!(
+        "allowed-tools: Bash(fgp call {}.{}:*)\n",
+        command.daemon, command.method
+    ));
+    md.push_str("---\n\n");
+    md
+}
+
+/// What the agent is asked to do when the command runs.
+fn command_body(command: &SlashCommand, examples: &Examples) -> String {
+    let full = format!("{}.{}", command.daemon, command.method);
+    let mut md = format!(
+        "Call `{}` on the FGP {} daemon with params built from: $ARGUMENTS\n\n",
+        full, command.daemon
+    );
+
+    if let Some(method) = examples.method(&command.daemon, &command.method) {
+        let mut params: Vec<_> = method.params.iter().collect();
+        params.sort_by(|a, b| a.name.cmp(&b.name));
+        if !params.is_empty() {
+            md.push_str("Params:\n\n");
+            for param in params {
+                md.push_str(&format!(
+                    "- `{}` ({}{})",
+                    param.name,
+                    param.param_type.as_deref().unwrap_or("string"),
+                    if param.required { ", required" } else { "" }
+                ));
+                if let Some(ref description) = param.description {
+                    md.push_str(&format!(": {}", description));
                 }
-                conventions.push('\n');
+                md.push('\n');
             }
+            md.push('\n');
         }
+    }
 
-        // Add workflow info
-        if !manifest.workflows.is_empty() {
-            conventions.push_str("## Workflows\n\n");
-            for (name, workflow) in &manifest.workflows {
-                let desc = workflow.description.as_deref().unwrap_or("");
-                conventions.push_str(&format!("- **{}**: {}\n", name, desc));
+    md.push_str("```bash\n");
+    md.push_str(&format!(
+        "fgp call {} -p '{}'\n",
+        full,
+        examples.params_or(&command.daemon, &command.method, "{}")
+    ));
+    md.push_str("```\n\n");
+    md.push_str("Ask for any required param the arguments don't give, then report the result.\n");
+    md
+}
+
+// ============================================================================
+// Usage Considerations (--include-cost)
+// ============================================================================
+
+/// Cost and rate-limit notes, keyed by (daemon, method as the skill lists it).
+type MethodUsage = BTreeMap<(String, String), String>;
+
+/// Notes for the skill's methods whose daemon manifest declares a `cost` or
+/// `rate_limit`, e.g. "cost: $0.01 per call; rate limit: 60 per minute".
+fn method_usage(manifest: &SkillManifest) -> MethodUsage {
+    let mut usage = MethodUsage::new();
+    for daemon in &manifest.daemons {
+        let declared = guardrails::declared_methods(&daemon.name);
+        if declared.is_empty() {
+            println!(
+                "  {} {} has no installed manifest; its methods' cost and rate limits are unknown",
+                "!".yellow(),
+                daemon.name
+            );
+            continue;
+        }
+        for method in &daemon.methods {
+            let Some(found) = find_method(&declared, &daemon.name, method) else {
+                continue;
+            };
+            let note: Vec<String> = [("cost", &found.cost), ("rate limit", &found.rate_limit)]
+                .into_iter()
+                .filter_map(|(label, value)| value.as_ref().map(|v| format!("{}: {}", label, v)))
+                .collect();
+            if !note.is_empty() {
+                usage.insert((daemon.name.clone(), method.clone()), note.join("; "));
             }
-            conventions.push('\n');
         }
     }
+    usage
+}
 
-    // Write CONVENTIONS.md
-    let conventions_path = output_dir.join(format!("{}.CONVENTIONS.md", manifest.name));
-    fs::write(&conventions_path, &conventions)?;
-
-    println!(
-        "{} Exported Aider conventions to: {}",
-        "✓".green().bold(),
-        conventions_path.display()
+/// The "Usage Considerations" section, if any method has a note.
+fn usage_section(usage: &MethodUsage) -> Option<String> {
+    if usage.is_empty() {
+        return None;
+    }
+    let mut section = String::from("## Usage Considerations\n\n");
+    section.push_str(
+        "These methods cost money or are rate-limited. Call them only when the task needs them, \
+         and reuse results instead of repeating calls.\n\n",
     );
+    for ((daemon, method), note) in usage {
+        let name = if method.contains('.') {
+            method.clone()
+        } else {
+            format!("{}.{}", daemon, method)
+        };
+        section.push_str(&format!("- `{}`: {}\n", name, note));
+    }
+    Some(section)
+}
 
-    // Provide usage hints
-    println!();
-    println!("{}:", "Usage".cyan().bold());
-    println!("  1. Rename to CONVENTIONS.md in project root");
-    println!("  2. Run: aider --read CONVENTIONS.md");
-    println!("  3. Or add to .aider.conf.yml: read: CONVENTIONS.md");
+// ============================================================================
+// What's New (--since-version)
+// ============================================================================
 
-    Ok(())
+/// Changelog file names looked for in the skill directory.
+const CHANGELOG_FILES: &[&str] = &["CHANGELOG.md", "changelog.md"];
+
+/// One released version's entry in a changelog.
+#[derive(Debug, PartialEq)]
+struct ChangelogEntry {
+    version: String,
+    /// Text after the version in the heading, such as the release date
+    date: Option<String>,
+    body: String,
+}
+
+/// Render the "What's New" section for changes after `since`, or `None`
+/// (with a note) when there is no changelog or nothing newer.
+fn whats_new(skill_dir: &Path, since: &str) -> Result<Option<String>> {
+    let since_key = parse_version(since)
+        .with_context(|| format!("Invalid --since-version '{}' (expected e.g. 1.2.0)", since))?;
+
+    let Some(path) = CHANGELOG_FILES
+        .iter()
+        .map(|name| skill_dir.join(name))
+        .find(|path| path.is_file())
+    else {
+        println!(
+            "  {} No CHANGELOG.md in {}; exporting without What's New",
+            "!".yellow(),
+            skill_dir.display()
+        );
+        return Ok(None);
+    };
+
+    let changelog =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let entries: Vec<ChangelogEntry> = parse_changelog(&changelog)
+        .into_iter()
+        .filter(|entry| parse_version(&entry.version).is_some_and(|key| key > since_key))
+        .collect();
+
+    if entries.is_empty() {
+        println!(
+            "  {} No changelog entries after {}; exporting without What's New",
+            "!".yellow(),
+            since
+        );
+        return Ok(None);
+    }
+
+    let mut section = String::from("## What's New\n\n");
+    for entry in &entries {
+        match entry.date {
+            Some(ref date) => section.push_str(&format!("### {} ({})\n\n", entry.version, date)),
+            None => section.push_str(&format!("### {}\n\n", entry.version)),
+        }
+        if !entry.body.is_empty() {
+            section.push_str(&entry.body);
+            section.push_str("\n\n");
+        }
+    }
+    Ok(Some(section))
+}
+
+/// Released entries of a Keep a Changelog style file, newest first as
+/// written. Headings look like `## [1.2.0] - 2025-01-14`, `## v1.2.0` or
+/// `## 1.2.0`; `[Unreleased]` and link reference lines are skipped, and
+/// subheadings are demoted a level to nest under the version.
+fn parse_changelog(changelog: &str) -> Vec<ChangelogEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<ChangelogEntry> = None;
+
+    for line in changelog.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            entries.extend(current.take());
+            let heading = heading.trim();
+            let (version, rest) = heading
+                .split_once(char::is_whitespace)
+                .unwrap_or((heading, ""));
+            let version = version.trim_matches(['[', ']']);
+            let version = version.strip_prefix('v').unwrap_or(version);
+            if parse_version(version).is_some() {
+                let date = rest.trim().trim_start_matches(['-', '–']).trim();
+                current = Some(ChangelogEntry {
+                    version: version.to_string(),
+                    date: (!date.is_empty()).then(|| date.to_string()),
+                    body: String::new(),
+                });
+            }
+            continue;
+        }
+
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        // `[1.2.0]: https://...` link references belong to the whole file
+        if line.starts_with('[') && line.contains("]: ") {
+            continue;
+        }
+        let line = if line.starts_with("###") {
+            format!("#{}", line)
+        } else {
+            line.to_string()
+        };
+        entry.body.push_str(&line);
+        entry.body.push('\n');
+    }
+    entries.extend(current);
+
+    for entry in &mut entries {
+        entry.body = entry.body.trim().to_string();
+    }
+    entries
+}
+
+/// Append rendered sections (Usage Considerations, What's New) to a
+/// Markdown document.
+fn push_sections(document: &mut String, sections: Option<&str>) {
+    let Some(sections) = sections else {
+        return;
+    };
+    let trimmed = document.trim_end().len();
+    document.truncate(trimmed);
+    document.push_str("\n\n");
+    document.push_str(sections);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_tools() {
+        let manifest: SkillManifest = serde_yaml::from_str(
+            "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [gmail.send, list]\n  - name: calendar\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            required_commands(&manifest),
+            vec![
+                "fgp call gmail.send",
+                "fgp call gmail.list",
+                "fgp call calendar."
+            ]
+        );
+        assert_eq!(
+            scoped_tools(&manifest, "fgp *").unwrap(),
+            vec![
+                "Bash(fgp call gmail.send:*)",
+                "Bash(fgp call gmail.list:*)",
+                "Bash(fgp call calendar.:*)"
+            ]
+        );
+        assert_eq!(
+            scoped_tools(&manifest, "fgp call gmail.*").unwrap(),
+            vec!["Bash(fgp call gmail.send:*)", "Bash(fgp call gmail.list:*)"]
+        );
+        assert!(scoped_tools(&manifest, "git *").is_err());
+    }
+
+    #[test]
+    fn test_select_daemons() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [gmail.send]\n  - name: calendar\n  - name: slack\n\
+             auth:\n  daemons:\n    gmail: oauth\n    slack: token\n";
+        let mut manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+
+        select_daemons(&mut manifest, &["slack".to_string(), "gmail".to_string()]).unwrap();
+        let names: Vec<&str> = manifest.daemons.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["gmail", "slack"]);
+        assert_eq!(
+            required_commands(&manifest),
+            vec!["fgp call gmail.send", "fgp call slack."]
+        );
+        assert_eq!(manifest.auth.as_ref().unwrap().daemons.len(), 2);
+
+        select_daemons(&mut manifest, &["gmail".to_string()]).unwrap();
+        assert_eq!(manifest.daemons.len(), 1);
+        assert!(manifest.auth.unwrap().daemons.contains_key("gmail"));
+
+        let mut manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+        let err = select_daemons(&mut manifest, &["gmail".to_string(), "github".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no daemon named github"), "{}", err);
+        assert!(err.contains("gmail, calendar, slack"), "{}", err);
+        // Nothing is dropped when a name is wrong
+        assert_eq!(manifest.daemons.len(), 3);
+    }
+
+    #[test]
+    fn test_unrepresented() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [gmail.send]\n\
+             instructions:\n  core: instructions/core.md\n\
+             triggers:\n  keywords: [inbox]\n\
+             workflows:\n  triage:\n    file: workflows/triage.yaml\n  digest:\n    file: workflows/digest.yaml\n\
+             config:\n  max_results:\n    type: integer\n\
+             auth:\n  daemons:\n    gmail: oauth\n  secrets:\n    - name: SLACK_TOKEN\n";
+        let manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            unrepresented("claude-code", &manifest),
+            vec![
+                "config options (max_results)",
+                "auth requirements (gmail: oauth, SLACK_TOKEN)"
+            ]
+        );
+        assert_eq!(
+            unrepresented("cursor", &manifest)[0],
+            "workflows (digest, triage)"
+        );
+        let mcp = unrepresented("mcp", &manifest);
+        assert_eq!(&mcp[..2], ["instructions", "triggers"]);
+        assert_eq!(mcp.len(), 5);
+
+        let bare: SkillManifest =
+            serde_yaml::from_str("name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n")
+                .unwrap();
+        for target in ALL_TARGETS {
+            assert!(unrepresented(target, &bare).is_empty(), "{}", target);
+        }
+    }
+
+    #[test]
+    fn test_mcp_tools_grouped() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [gmail.send, gmail.list]\n  - name: calendar\n";
+        let manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+
+        let tools = mcp_tools(
+            &manifest,
+            "inbox",
+            false,
+            &MethodUsage::new(),
+            &Examples::default(),
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["inbox_gmail.send", "inbox_gmail.list"]);
+
+        // A daemon with no listed methods has nothing to group
+        let tools = mcp_tools(
+            &manifest,
+            "inbox",
+            true,
+            &MethodUsage::new(),
+            &Examples::default(),
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0]["name"], "inbox_gmail");
+        assert_eq!(
+            tools[0]["inputSchema"]["properties"]["method"]["enum"],
+            serde_json::json!(["gmail.send", "gmail.list"])
+        );
+    }
+
+    #[test]
+    fn test_mcp_tool_schemas() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [send, gmail.archive]\n";
+        let manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+        let methods: Vec<ManifestMethod> = serde_json::from_str(
+            r#"[{"name": "gmail.send", "description": "Send an email", "destructive": true, "params": [
+                {"name": "to", "type": "string", "required": true, "description": "Recipient"},
+                {"name": "cc", "type": "array", "items": {"type": "string"}},
+                {"name": "draft", "type": "bool", "default": false}
+            ]}]"#,
+        )
+        .unwrap();
+        let examples = Examples {
+            declared: BTreeMap::from([("gmail".to_string(), methods)]),
+            ..Default::default()
+        };
+
+        let tools = mcp_tools(&manifest, "inbox", false, &MethodUsage::new(), &examples);
+        assert_eq!(
+            tools[0]["description"],
+            "Send an email (via FGP gmail daemon)"
+        );
+        assert_eq!(
+            tools[0]["inputSchema"],
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "to": {"type": "string", "description": "Recipient"},
+                    "cc": {"type": "array", "items": {"type": "string"}},
+                    "draft": {"type": "boolean", "default": false}
+                },
+                "required": ["to"]
+            })
+        );
+        assert_eq!(tools[0]["annotations"]["destructiveHint"], true);
+        // Not in the daemon's manifest: no params known
+        assert_eq!(tools[1]["inputSchema"]["properties"], serde_json::json!({}));
+        assert!(tools[1].get("annotations").is_none());
+
+        let grouped = mcp_tools(&manifest, "inbox", true, &MethodUsage::new(), &examples);
+        let description = grouped[0]["description"].as_str().unwrap();
+        assert!(
+            description
+                .contains("- send (destructive): Send an email\n  Params: cc (array), draft (boolean), to (string, required)"),
+            "{}",
+            description
+        );
+    }
+
+    #[test]
+    fn test_usage_notes() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [send, list]\n";
+        let manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(usage_section(&MethodUsage::new()), None);
+
+        let mut usage = MethodUsage::new();
+        usage.insert(
+            ("gmail".to_string(), "send".to_string()),
+            "cost: $0.001 per message".to_string(),
+        );
+        let section = usage_section(&usage).unwrap();
+        assert!(section.starts_with("## Usage Considerations\n"));
+        assert!(section.contains("- `gmail.send`: cost: $0.001 per message\n"));
+
+        let tools = mcp_tools(&manifest, "inbox", false, &usage, &Examples::default());
+        assert_eq!(
+            tools[0]["description"],
+            "send via FGP gmail daemon (cost: $0.001 per message)"
+        );
+        assert_eq!(tools[1]["description"], "list via FGP gmail daemon");
+        let grouped = mcp_tools(&manifest, "inbox", true, &usage, &Examples::default());
+        let description = grouped[0]["description"].as_str().unwrap();
+        assert!(
+            description.contains("- send: cost: $0.001 per message"),
+            "{}",
+            description
+        );
+    }
+
+    #[test]
+    fn test_whats_new() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- Not yet\n\n\
+             ## [1.3.0] - 2025-03-01\n\n### Added\n- Search\n\n\
+             ## v1.2.1\n\n- Fix paging\n\n\
+             ## [1.2.0] - 2025-01-14\n\n- Initial\n\n\
+             [1.3.0]: https://example.com/compare\n";
+        let entries = parse_changelog(changelog);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].version, "1.3.0");
+        assert_eq!(entries[0].date.as_deref(), Some("2025-03-01"));
+        assert_eq!(entries[0].body, "#### Added\n- Search");
+        assert_eq!(entries[1].version, "1.2.1");
+        assert_eq!(entries[1].date, None);
+        assert_eq!(entries[2].body, "- Initial");
+
+        assert!(parse_version("1.2").unwrap() == parse_version("1.2.0").unwrap());
+        assert!(parse_version("1.10.0").unwrap() > parse_version("1.9.3").unwrap());
+        assert!(parse_version("v1.2.0-beta.1").unwrap() < parse_version("1.2").unwrap());
+        assert!(parse_version("Unreleased").is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(whats_new(dir.path(), "1.2.0").unwrap(), None);
+        fs::write(dir.path().join("CHANGELOG.md"), changelog).unwrap();
+        let section = whats_new(dir.path(), "1.2").unwrap().unwrap();
+        assert!(section.starts_with("## What's New\n\n### 1.3.0 (2025-03-01)\n\n"));
+        assert!(section.contains("### 1.2.1\n\n- Fix paging"));
+        assert!(!section.contains("Initial"));
+        assert!(!section.contains("Not yet"));
+        assert_eq!(whats_new(dir.path(), "1.3.0").unwrap(), None);
+        assert!(whats_new(dir.path(), "latest").is_err());
+
+        let mut md = String::from("# Skill\n\nBody\n");
+        push_sections(&mut md, Some(&section));
+        assert!(md.starts_with("# Skill\n\nBody\n\n## What's New"));
+    }
+
+    #[test]
+    fn test_merge_desktop_config() {
+        let manifest: SkillManifest = serde_yaml::from_str(
+            "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n  - name: calendar\n",
+        )
+        .unwrap();
+
+        let fresh: Value =
+            serde_json::from_str(&merge_desktop_config(None, &manifest).unwrap()).unwrap();
+        assert_eq!(
+            fresh["mcpServers"]["fgp-gmail"],
+            serde_json::json!({
+                "command": "fgp",
+                "args": ["mcp", "serve", "--service", "gmail"],
+                "env": {}
+            })
+        );
+        assert!(fresh["mcpServers"]["fgp-calendar"].is_object());
+
+        let existing = r#"{"globalShortcut": "Ctrl+Space",
+            "mcpServers": {"github": {"command": "gh-mcp"}, "fgp-gmail": {"command": "old"}}}"#;
+        let merged: Value =
+            serde_json::from_str(&merge_desktop_config(Some(existing), &manifest).unwrap())
+                .unwrap();
+        assert_eq!(merged["globalShortcut"], "Ctrl+Space");
+        assert_eq!(merged["mcpServers"]["github"]["command"], "gh-mcp");
+        assert_eq!(merged["mcpServers"]["fgp-gmail"]["command"], "fgp");
+
+        assert!(merge_desktop_config(Some("{not json"), &manifest).is_err());
+        assert!(merge_desktop_config(Some(r#"{"mcpServers": []}"#), &manifest).is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        let value = example_value(0, "gmail.send", "to_email", "string");
+        assert_eq!(value, example_value(0, "gmail.send", "to_email", "string"));
+        assert!(value.as_str().unwrap().ends_with("@example.com"));
+        let seeds: Vec<Value> = (0..8)
+            .map(|seed| example_value(seed, "gmail.list", "limit", "integer"))
+            .collect();
+        assert!(seeds.iter().any(|v| *v != seeds[0]));
+        assert!(seeds
+            .iter()
+            .all(|v| (1..=100).contains(&v.as_u64().unwrap())));
+        assert!(example_value(3, "gmail.get", "message_id", "string")
+            .as_str()
+            .unwrap()
+            .contains('-'));
+        assert!(example_value(0, "gmail.list", "unread", "boolean").is_boolean());
+
+        let methods: Vec<ManifestMethod> = serde_json::from_str(
+            r#"[{"name": "gmail.send", "params": [
+                {"name": "to", "type": "string", "required": true},
+                {"name": "cc", "type": "string"},
+                {"name": "body", "type": "string", "required": true, "default": "Hi"}
+            ]}]"#,
+        )
+        .unwrap();
+        let examples = Examples {
+            seed: 0,
+            declared: BTreeMap::from([("gmail".to_string(), methods)]),
+            ..Default::default()
+        };
+        let params = examples.params("gmail", "send").unwrap();
+        let to = example_value(0, "gmail.send", "to", "string");
+        assert_eq!(params, format!(r#"{{"body": "Hi", "to": {}}}"#, to));
+        assert_eq!(examples.params_or("gmail", "list", "{}"), "{}");
+        assert_eq!(examples.params_or("calendar", "today", "{}"), "{}");
+
+        // The skill's own examples win over generated ones
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [send]\n    examples:\n      send: '{\"to\": \"ann@example.com\"}'\n";
+        let manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+        let documented = Examples {
+            declared: examples.declared,
+            ..Examples::new(&manifest, 0)
+        };
+        assert_eq!(
+            documented.params("gmail", "gmail.send").unwrap(),
+            r#"{"to": "ann@example.com"}"#
+        );
+        assert!(documented.params("gmail", "list").is_none());
+    }
+
+    #[test]
+    fn test_slash_commands() {
+        let yaml = "name: inbox\nversion: 1.0.0\ndescription: Mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n    methods: [gmail.send, list_labels]\n  - name: calendar\n";
+        let mut manifest: SkillManifest = serde_yaml::from_str(yaml).unwrap();
+        let (commands, unmatched) = slash_commands(&manifest);
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["gmail-send", "gmail-list-labels"]);
+        assert_eq!(commands[0].method, "send");
+        assert!(unmatched.is_empty());
+
+        // triggers.commands picks and orders the set
+        manifest.triggers =
+            serde_yaml::from_str("commands: [/gmail-list-labels, /gmail-archive]").unwrap();
+        let (commands, unmatched) = slash_commands(&manifest);
+        assert_eq!(commands, vec![SlashCommand::new("gmail", "list_labels")]);
+        assert_eq!(unmatched, vec!["/gmail-archive"]);
+
+        let methods: Vec<ManifestMethod> = serde_json::from_str(
+            r#"[{"name": "gmail.send", "description": "Send an email", "params": [
+                {"name": "to", "type": "string", "required": true, "description": "Recipient"},
+                {"name": "cc", "type": "string"}
+            ]}]"#,
+        )
+        .unwrap();
+        let examples = Examples {
+            seed: 0,
+            declared: BTreeMap::from([("gmail".to_string(), methods)]),
+            ..Default::default()
+        };
+        let send = SlashCommand::new("gmail", "gmail.send");
+        assert_eq!(
+            command_frontmatter(&send, &examples),
+            "---\ndescription: \"Send an email\"\nargument-hint: \"[cc] [to]\"\n\
+             allowed-tools: Bash(fgp call gmail.send:*)\n---\n\n"
+        );
+        let body = command_body(&send, &examples);
+        assert!(body.starts_with(
+            "Call `gmail.send` on the FGP gmail daemon with params built from: $ARGUMENTS"
+        ));
+        assert!(
+            body.contains("- `cc` (string)\n- `to` (string, required): Recipient\n"),
+            "{}",
+            body
+        );
+        assert!(body.contains(&format!(
+            "fgp call gmail.send -p '{}'",
+            examples.params("gmail", "send").unwrap()
+        )));
+        assert_eq!(
+            command_frontmatter(&SlashCommand::new("gmail", "archive"), &examples),
+            "---\ndescription: \"Call gmail.archive\"\nallowed-tools: Bash(fgp call gmail.archive:*)\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let content = "\u{feff}# Gmail\r\n\nUse it.\n";
+        assert_eq!(LineEndings::Lf.apply(content), "# Gmail\n\nUse it.\n");
+        assert_eq!(
+            LineEndings::Crlf.apply(content),
+            "# Gmail\r\n\r\nUse it.\r\n"
+        );
+        assert!(LineEndings::parse("cr").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SKILL.md");
+        let mut writer = ExportWriter::new(WriteMode::Quiet, LineEndings::Crlf);
+        writer.write(&path, content).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"# Gmail\r\n\r\nUse it.\r\n");
+        // A file already converted is up to date
+        writer.write(&path, content).unwrap();
+        assert_eq!(writer.count(FileStatus::Unchanged), 1);
+    }
+
+    #[test]
+    fn test_export_all() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(
+            skill.join("skill.yaml"),
+            "name: gmail-triage\nversion: 1.0.0\ndescription: Triage the inbox\nauthor: Jane\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        let options = ExportOptions {
+            output: out.to_str(),
+            layout: "standalone",
+            line_endings: "lf",
+            ..Default::default()
+        };
+        export("all", skill.to_str().unwrap(), &options).unwrap();
+
+        for target in ALL_TARGETS {
+            assert!(out.join(target).is_dir(), "no {} directory", target);
+        }
+        assert!(out.join("cursor/gmail-triage.cursorrules").exists());
+        assert!(fs::read_dir(&out)
+            .unwrap()
+            .flatten()
+            .all(|e| e.path().is_dir()));
+
+        let results = [
+            (PathBuf::from("a"), FileStatus::Written),
+            (PathBuf::from("b"), FileStatus::Unchanged),
+            (PathBuf::from("c"), FileStatus::Written),
+        ];
+        assert_eq!(status_counts(&results), "2 written, 1 unchanged");
+        assert_eq!(status_counts(&[]), "no files");
+    }
+
+    #[test]
+    fn test_cursor_mdc() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(
+            skill.join("skill.yaml"),
+            "name: gmail-triage\nversion: 1.0.0\ndescription: Triage the inbox\nauthor: Jane\n\
+             triggers:\n  keywords: [inbox, unread email]\n",
+        )
+        .unwrap();
+        let out = dir.path().join("out");
+        let options = ExportOptions {
+            output: out.to_str(),
+            layout: "standalone",
+            line_endings: "lf",
+            format: Some("mdc"),
+            ..Default::default()
+        };
+        export("cursor", skill.to_str().unwrap(), &options).unwrap();
+
+        assert!(!out.join("gmail-triage.cursorrules").exists());
+        let rule = fs::read_to_string(out.join("gmail-triage.mdc")).unwrap();
+        assert!(rule.starts_with(
+            "---\ndescription: \"Triage the inbox. Use when the user mentions: inbox, unread email.\"\n\
+             globs: \nalwaysApply: false\n---\n\n"
+        ));
+
+        let format = |format, layout| CursorFormat::parse(format, layout).ok();
+        assert_eq!(
+            format(None, ExportLayout::Standalone),
+            Some(CursorFormat::Rules)
+        );
+        assert_eq!(format(None, ExportLayout::Project), Some(CursorFormat::Mdc));
+        assert_eq!(format(Some("cursorrules"), ExportLayout::Project), None);
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(
+            skill.join("skill.yaml"),
+            "name: gmail-triage\nversion: 1.0.0\ndescription: Triage the inbox\nauthor: Jane\n",
+        )
+        .unwrap();
+        let export_into = |plugin: &Path| {
+            let options = ExportOptions {
+                layout: "standalone",
+                line_endings: "lf",
+                merge_into: plugin.to_str(),
+                ..Default::default()
+            };
+            export("claude-code", skill.to_str().unwrap(), &options)
+        };
+
+        // A manifest with a skills list gets the new skill added, once
+        let listed = dir.path().join("listed");
+        fs::create_dir_all(listed.join(".claude-plugin")).unwrap();
+        let manifest_path = listed.join(".claude-plugin/plugin.json");
+        fs::write(
+            &manifest_path,
+            r#"{"name": "mail", "skills": ["./skills/inbox-zero"]}"#,
+        )
+        .unwrap();
+        export_into(&listed).unwrap();
+        export_into(&listed).unwrap();
+        assert!(listed.join("skills/gmail-triage/SKILL.md").exists());
+        let manifest: Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(
+            manifest["skills"],
+            serde_json::json!(["./skills/inbox-zero", "./skills/gmail-triage"])
+        );
+
+        // A skills directory named in the manifest is used, and left alone
+        let custom = dir.path().join("custom");
+        fs::create_dir_all(&custom).unwrap();
+        let manifest = r#"{"name": "mail", "skills": "./agent-skills/"}"#;
+        fs::write(custom.join("plugin.json"), manifest).unwrap();
+        export_into(&custom).unwrap();
+        assert!(custom.join("agent-skills/gmail-triage/SKILL.md").exists());
+        assert_eq!(
+            fs::read_to_string(custom.join("plugin.json")).unwrap(),
+            manifest
+        );
+
+        // No manifest: just the SKILL.md
+        let bare = dir.path().join("bare");
+        fs::create_dir_all(&bare).unwrap();
+        export_into(&bare).unwrap();
+        assert!(bare.join("skills/gmail-triage/SKILL.md").exists());
+        assert!(!bare.join(".claude-plugin").exists());
+
+        assert!(export_into(&dir.path().join("missing")).is_err());
+        let options = ExportOptions {
+            layout: "standalone",
+            line_endings: "lf",
+            merge_into: bare.to_str(),
+            ..Default::default()
+        };
+        assert!(export("cursor", skill.to_str().unwrap(), &options).is_err());
+    }
 }
@@ -10,6 +10,8 @@
 //! - **Cursor** (.cursorrules): ~50% fidelity - pure markdown
 //! - **Codex** (.codex.json): ~25% fidelity - minimal JSON schema
 //! - **MCP** (.mcp.json): ~30% fidelity - tool schema
+//! - **OpenAI Tools** (.openai.json): function-calling definitions with
+//!   JSON-schema parameters
 //! - **Gemini** (gemini-extension.json): ~75% fidelity - JSON manifest
 //!
 //! # Daemon Registry Enrichment
@@ -27,6 +29,7 @@
 //! fgp skill import ./rules.txt --format cursor
 //! fgp skill import ./SKILL.md --dry-run
 //! fgp skill import ./SKILL.md --enrich  # Enable daemon registry enrichment
+//! fgp skill import ./skills/ --output ./imported/ --continue-on-error
 //! ```
 
 use anyhow::{bail, Context, Result};
@@ -34,10 +37,13 @@ use colored::Colorize;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
+use super::parse_version;
+
 // ============================================================================
 // Unified Intermediate Representation (UIR)
 // ============================================================================
@@ -57,6 +63,16 @@ pub enum Confidence {
 }
 
 impl Confidence {
+    /// Higher is more certain
+    fn rank(&self) -> u8 {
+        match self {
+            Confidence::High => 3,
+            Confidence::Medium => 2,
+            Confidence::Low => 1,
+            Confidence::Unknown => 0,
+        }
+    }
+
     fn symbol(&self) -> &'static str {
         match self {
             Confidence::High => "✓",
@@ -94,6 +110,8 @@ pub enum FieldSource {
     UserInput,
     /// Default/placeholder value
     Default,
+    /// From the source file's git history
+    GitHistory,
 }
 
 /// An imported field with confidence metadata
@@ -156,6 +174,10 @@ pub struct ImportedDaemon {
     pub version: ImportedField<Option<String>>,
     pub optional: ImportedField<bool>,
     pub methods: Vec<ImportedField<String>>,
+    /// JSON params by method, from the `fgp call` examples in the source's
+    /// code blocks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, ImportedField<String>>,
 }
 
 /// Imported trigger configuration
@@ -166,6 +188,35 @@ pub struct ImportedTriggers {
     pub commands: Vec<ImportedField<String>>,
 }
 
+/// Config options recovered from the source's documentation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportedConfig {
+    pub options: Vec<ImportedField<ImportedConfigOption>>,
+}
+
+impl ImportedConfig {
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+}
+
+/// One config option, in skill.yaml's terms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedConfigOption {
+    pub name: String,
+    /// string, number, boolean, enum or array
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Default as written in the source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Allowed values of an enum
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
 /// Imported author information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportedAuthor {
@@ -182,6 +233,8 @@ pub enum ImportFormat {
     Cursor,
     Codex,
     Mcp,
+    #[serde(rename = "openai")]
+    OpenAiTools,
     Zed,
     Windsurf,
     Gemini,
@@ -200,12 +253,18 @@ impl ImportFormat {
         if filename.ends_with(".cursorrules") || filename == ".cursorrules" {
             return Some(ImportFormat::Cursor);
         }
+        if extension == Some("mdc") || (extension == Some("md") && in_cursor_rules(path)) {
+            return Some(ImportFormat::Cursor);
+        }
         if filename.ends_with(".codex.json") {
             return Some(ImportFormat::Codex);
         }
         if filename.ends_with(".mcp.json") {
             return Some(ImportFormat::Mcp);
         }
+        if filename.ends_with(".openai.json") {
+            return Some(ImportFormat::OpenAiTools);
+        }
         if filename.ends_with(".rules") {
             return Some(ImportFormat::Zed);
         }
@@ -221,12 +280,8 @@ impl ImportFormat {
 
         // Check by extension
         match extension {
-            Some("md") => {
-                // Could be Claude Code or other markdown
-                // Read first few lines to detect
-                None
-            }
-            Some("json") => None,
+            Some("md") => sniff_markdown(path),
+            Some("json") => sniff_json(path),
             _ => None,
         }
     }
@@ -237,6 +292,7 @@ impl ImportFormat {
             ImportFormat::Cursor => "Cursor",
             ImportFormat::Codex => "Codex",
             ImportFormat::Mcp => "MCP",
+            ImportFormat::OpenAiTools => "OpenAI Tools",
             ImportFormat::Zed => "Zed",
             ImportFormat::Windsurf => "Windsurf",
             ImportFormat::Gemini => "Gemini",
@@ -250,6 +306,7 @@ impl ImportFormat {
             ImportFormat::Cursor => "cursor",
             ImportFormat::Codex => "codex",
             ImportFormat::Mcp => "mcp",
+            ImportFormat::OpenAiTools => "openai",
             ImportFormat::Zed => "zed",
             ImportFormat::Windsurf => "windsurf",
             ImportFormat::Gemini => "gemini",
@@ -258,6 +315,65 @@ impl ImportFormat {
     }
 }
 
+/// Lines of a markdown file read when sniffing its format
+const SNIFF_LINES: usize = 40;
+
+/// Whether `path` is inside a `.cursor/rules` directory.
+fn in_cursor_rules(path: &Path) -> bool {
+    let dirs: Vec<&std::ffi::OsStr> = path
+        .parent()
+        .map(|p| p.components().map(|c| c.as_os_str()).collect())
+        .unwrap_or_default();
+    dirs.windows(2)
+        .any(|w| w[0] == ".cursor" && w[1] == "rules")
+}
+
+/// Guess the format of a generic markdown file from its first lines.
+///
+/// YAML frontmatter with skill keys (`name:`, `description:`, `tools:`) is a
+/// Claude Code skill. Anything else is left alone: plenty of markdown makes
+/// `fgp call`s without being Cursor rules.
+fn sniff_markdown(path: &Path) -> Option<ImportFormat> {
+    let file = fs::File::open(path).ok()?;
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .take(SNIFF_LINES)
+        .map_while(|l| l.ok())
+        .collect();
+
+    if lines.first().map(|l| l.trim()) != Some("---") {
+        return None;
+    }
+    lines[1..]
+        .iter()
+        .take_while(|l| l.trim() != "---")
+        .any(|l| {
+            ["name:", "description:", "tools:"]
+                .iter()
+                .any(|k| l.starts_with(k))
+        })
+        .then_some(ImportFormat::ClaudeCode)
+}
+
+/// Guess the format of a generic JSON file from its content.
+///
+/// A list of OpenAI tool definitions, bare or under a top-level `tools` key
+/// (as in an Assistants API export), is recognized when at least one entry
+/// has `"type": "function"`.
+fn sniff_json(path: &Path) -> Option<ImportFormat> {
+    let content = fs::read_to_string(path).ok()?;
+    let value: Value = serde_json::from_str(&content).ok()?;
+    let tools = match &value {
+        Value::Array(tools) => tools,
+        Value::Object(map) => map.get("tools")?.as_array()?,
+        _ => return None,
+    };
+    tools
+        .iter()
+        .any(|t| t.get("type").and_then(Value::as_str) == Some("function"))
+        .then_some(ImportFormat::OpenAiTools)
+}
+
 /// Unified Intermediate Representation for imported skills
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportedSkill {
@@ -276,6 +392,10 @@ pub struct ImportedSkill {
     // === TRIGGERS ===
     pub triggers: ImportedTriggers,
 
+    // === CONFIG ===
+    #[serde(default, skip_serializing_if = "ImportedConfig::is_empty")]
+    pub config: ImportedConfig,
+
     // === SOURCE METADATA ===
     pub source_format: ImportFormat,
     pub source_path: PathBuf,
@@ -321,6 +441,12 @@ impl ImportedSkill {
             count += 1;
         }
 
+        // Config
+        for option in &self.config.options {
+            total += conf_value(option.confidence);
+            count += 1;
+        }
+
         if count == 0 {
             return 0;
         }
@@ -354,13 +480,26 @@ pub struct DaemonManifest {
 }
 
 /// Method definition from manifest
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ManifestMethod {
     pub name: String,
     #[serde(default)]
     pub description: Option<String>,
-    #[serde(default)]
+    /// A list of params, or a JSON Schema object describing them
+    #[serde(default, deserialize_with = "deserialize_params")]
     pub params: Vec<ManifestParam>,
+    /// Changes or deletes data; calls are confirmed first (see `guardrails`)
+    #[serde(default)]
+    pub destructive: bool,
+    /// Accepts `dry_run: true` to preview its effect without applying it
+    #[serde(default)]
+    pub supports_dry_run: bool,
+    /// What a call costs, for methods backed by paid APIs (e.g. "$0.01 per call")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<String>,
+    /// How often the method may be called (e.g. "60 per minute")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<String>,
 }
 
 /// Parameter definition from manifest
@@ -375,6 +514,57 @@ pub struct ManifestParam {
     pub default: Option<Value>,
     #[serde(default)]
     pub description: Option<String>,
+    /// JSON Schema of an array param's elements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Value>,
+    /// JSON Schema properties of an object param's fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Value>,
+    /// Full JSON Schema of the param's value (`enum`, `minimum`, `pattern`,
+    /// ...), which `type`, `items` and `properties` are shorthand for parts of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+}
+
+fn deserialize_params<'de, D>(deserializer: D) -> std::result::Result<Vec<ManifestParam>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(Vec::new()),
+        Value::Object(schema) => Ok(params_from_schema(&schema)),
+        list => serde_json::from_value(list).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Params from a JSON Schema `{"properties": ..., "required": [...]}`, each
+/// keeping its property's schema.
+pub fn params_from_schema(schema: &serde_json::Map<String, Value>) -> Vec<ManifestParam> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|name| name.as_str()).collect())
+        .unwrap_or_default();
+
+    schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| ManifestParam {
+                    name: name.clone(),
+                    param_type: property["type"].as_str().map(|s| s.to_string()),
+                    required: required.contains(&name.as_str()),
+                    default: property.get("default").cloned(),
+                    description: property["description"].as_str().map(|s| s.to_string()),
+                    items: property.get("items").cloned(),
+                    properties: property.get("properties").cloned(),
+                    schema: Some(property.clone()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Authentication configuration from manifest
@@ -388,6 +578,9 @@ pub struct ManifestAuth {
     pub scopes: Vec<String>,
 }
 
+/// How many directory levels below an FGP checkout to look for daemons.
+const CHECKOUT_SCAN_DEPTH: usize = 3;
+
 /// Registry of known FGP daemons for enrichment
 #[derive(Debug, Default)]
 pub struct DaemonRegistry {
@@ -395,6 +588,83 @@ pub struct DaemonRegistry {
     daemons: HashMap<String, DaemonManifest>,
     /// Map of method name -> (daemon name, method info)
     methods: HashMap<String, (String, ManifestMethod)>,
+    /// Map of daemon name -> manifest.json the daemon was loaded from
+    sources: HashMap<String, PathBuf>,
+    /// Manifests that were skipped because they could not be loaded
+    errors: Vec<ManifestLoadError>,
+}
+
+/// Why a daemon manifest was skipped during registry loading
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestErrorKind {
+    /// The file could not be read
+    Io,
+    /// The file is not valid JSON
+    Syntax { line: usize, column: usize },
+    /// Valid JSON that doesn't describe a usable daemon
+    Invalid,
+}
+
+/// A manifest that failed to load
+#[derive(Debug, Clone)]
+pub struct ManifestLoadError {
+    pub path: PathBuf,
+    pub kind: ManifestErrorKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for ManifestLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ManifestErrorKind::Syntax { line, column } => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.path.display(),
+                line,
+                column,
+                self.message
+            ),
+            _ => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Read, parse, and sanity-check a single daemon manifest.json
+pub fn load_manifest(path: &Path) -> std::result::Result<DaemonManifest, ManifestLoadError> {
+    let error = |kind, message: String| ManifestLoadError {
+        path: path.to_path_buf(),
+        kind,
+        message,
+    };
+
+    let content = fs::read_to_string(path).map_err(|e| error(ManifestErrorKind::Io, e.to_string()))?;
+
+    let manifest: DaemonManifest = serde_json::from_str(&content).map_err(|e| {
+        let kind = match e.classify() {
+            serde_json::error::Category::Data => ManifestErrorKind::Invalid,
+            serde_json::error::Category::Io => ManifestErrorKind::Io,
+            _ => ManifestErrorKind::Syntax {
+                line: e.line(),
+                column: e.column(),
+            },
+        };
+        error(kind, e.to_string())
+    })?;
+
+    if manifest.name.trim().is_empty() {
+        return Err(error(ManifestErrorKind::Invalid, "daemon name is empty".into()));
+    }
+    if manifest.version.trim().is_empty() {
+        return Err(error(ManifestErrorKind::Invalid, "daemon version is empty".into()));
+    }
+    if let Some(index) = manifest.methods.iter().position(|m| m.name.trim().is_empty()) {
+        return Err(error(
+            ManifestErrorKind::Invalid,
+            format!("method #{} has an empty name", index + 1),
+        ));
+    }
+
+    Ok(manifest)
 }
 
 impl DaemonRegistry {
@@ -403,71 +673,103 @@ impl DaemonRegistry {
         Self::default()
     }
 
-    /// Load daemons from the FGP project directory
+    /// Load daemons from an FGP checkout, wherever their manifests sit in it
+    /// (`<dir>/gmail/manifest.json`, `<dir>/daemons/gmail/manifest.json`, ...)
     pub fn load_from_fgp_dir(fgp_dir: &Path) -> Result<Self> {
         let mut registry = Self::new();
+        registry.walk_dir(fgp_dir, CHECKOUT_SCAN_DEPTH);
+        Ok(registry)
+    }
 
-        // Known daemon directories
-        let daemon_dirs = [
-            "gmail", "calendar", "github", "browser", "imessage",
-            "vercel", "fly", "neon", "travel", "slack",
-        ];
+    /// Add every daemon directory under `dir`, up to `depth` levels down.
+    /// A directory with a manifest.json is a daemon and isn't searched
+    /// further; hidden directories and build output are skipped.
+    fn walk_dir(&mut self, dir: &Path, depth: usize) {
+        if depth == 0 {
+            return;
+        }
+        self.scan_dir(dir);
+
+        let mut subdirs: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir() && !p.join("manifest.json").exists())
+                .filter(|p| {
+                    let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    !name.starts_with('.') && !matches!(name, "target" | "node_modules")
+                })
+                .collect(),
+            Err(_) => return,
+        };
+        subdirs.sort();
+
+        for subdir in subdirs {
+            self.walk_dir(&subdir, depth - 1);
+        }
+    }
 
-        for daemon_name in daemon_dirs {
-            let manifest_path = fgp_dir.join(daemon_name).join("manifest.json");
+    /// Add every `<dir>/*/manifest.json` found directly under `dir`
+    fn scan_dir(&mut self, dir: &Path) {
+        let mut daemon_dirs: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect(),
+            Err(_) => return,
+        };
+        daemon_dirs.sort();
+
+        for daemon_dir in daemon_dirs {
+            let manifest_path = daemon_dir.join("manifest.json");
             if manifest_path.exists() {
-                match fs::read_to_string(&manifest_path) {
-                    Ok(content) => {
-                        match serde_json::from_str::<DaemonManifest>(&content) {
-                            Ok(manifest) => {
-                                registry.add_daemon(manifest);
-                            }
-                            Err(e) => {
-                                eprintln!(
-                                    "Warning: Failed to parse {}: {}",
-                                    manifest_path.display(),
-                                    e
-                                );
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to read {}: {}",
-                            manifest_path.display(),
-                            e
-                        );
-                    }
+                match load_manifest(&manifest_path) {
+                    Ok(manifest) => self.add_daemon(manifest, &manifest_path),
+                    Err(e) => self.errors.push(e),
                 }
             }
         }
-
-        Ok(registry)
     }
 
-    /// Load from default FGP directory (~/.fgp or ~/Projects/fgp)
+    /// Load from every FGP checkout that exists (~/Projects/fgp, ~/.fgp/src),
+    /// plus any installed services under ~/.fgp/services
     pub fn load_default() -> Result<Self> {
-        // Try common FGP project locations
         let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
 
         let possible_paths = [
             home.join("Projects").join("fgp"),
             home.join("projects").join("fgp"),
-            home.join(".fgp").join("src"),
+            super::fgp_home().join("src"),
         ];
 
-        for path in &possible_paths {
-            if path.exists() {
-                return Self::load_from_fgp_dir(path);
-            }
+        let mut checkouts = possible_paths.iter().filter(|p| p.exists());
+        let mut registry = match checkouts.next() {
+            Some(path) => Self::load_from_fgp_dir(path)?,
+            None => Self::new(),
+        };
+        for path in checkouts {
+            registry.walk_dir(path, CHECKOUT_SCAN_DEPTH);
         }
 
-        // Return empty registry if no FGP directory found
-        Ok(Self::new())
+        // Installed services (including manifests written by `mcp_register`)
+        registry.scan_dir(&super::fgp_services_dir());
+
+        Ok(registry)
     }
 
-    /// Add a daemon manifest to the registry
-    fn add_daemon(&mut self, manifest: DaemonManifest) {
+    /// Add a daemon manifest to the registry.
+    ///
+    /// If the daemon is already known, the manifest with the newer version wins.
+    fn add_daemon(&mut self, manifest: DaemonManifest, source: &Path) {
+        if let Some(existing) = self.daemons.get(&manifest.name) {
+            if parse_version(&existing.version) >= parse_version(&manifest.version) {
+                return;
+            }
+            let name = manifest.name.clone();
+            self.methods.retain(|_, (daemon, _)| *daemon != name);
+        }
+
         // Index all methods
         for method in &manifest.methods {
             self.methods.insert(
@@ -484,9 +786,16 @@ impl DaemonRegistry {
             }
         }
 
+        self.sources
+            .insert(manifest.name.clone(), source.to_path_buf());
         self.daemons.insert(manifest.name.clone(), manifest);
     }
 
+    /// Get the manifest.json path a daemon was loaded from
+    pub fn source_path(&self, name: &str) -> Option<&Path> {
+        self.sources.get(name).map(|p| p.as_path())
+    }
+
     /// Get daemon manifest by name
     pub fn get_daemon(&self, name: &str) -> Option<&DaemonManifest> {
         self.daemons.get(name)
@@ -515,9 +824,29 @@ impl DaemonRegistry {
         self.daemons.len()
     }
 
+    /// Manifests that were skipped while loading
+    pub fn errors(&self) -> &[ManifestLoadError] {
+        &self.errors
+    }
+
+    /// One-line load summary, pointing at `fgp registry list --errors` when manifests were skipped
+    pub fn load_summary(&self) -> String {
+        let mut summary = format!("Loaded {} daemons", self.daemon_count());
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(
+                ", {} manifest{} skipped — run 'fgp registry list --errors' for details",
+                self.errors.len(),
+                if self.errors.len() == 1 { "" } else { "s" }
+            ));
+        }
+        summary
+    }
+
     /// Get all daemon names
     pub fn daemon_names(&self) -> Vec<&str> {
-        self.daemons.keys().map(|s| s.as_str()).collect()
+        let mut names: Vec<&str> = self.daemons.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
     }
 }
 
@@ -534,12 +863,24 @@ pub struct EnrichmentData {
     pub platform_support: HashMap<String, Vec<String>>,
     /// Daemons found in registry
     pub verified_daemons: Vec<String>,
+    /// Manifest path each verified daemon was found at
+    pub verified_sources: HashMap<String, PathBuf>,
     /// Daemons not found in registry
     pub unknown_daemons: Vec<String>,
+    /// Registry methods added to the skill (with `add_missing_methods`)
+    pub added_methods: Vec<String>,
 }
 
-/// Enrich an imported skill with data from the daemon registry
-pub fn enrich_skill(skill: &mut ImportedSkill, registry: &DaemonRegistry) -> EnrichmentData {
+/// Enrich an imported skill with data from the daemon registry.
+///
+/// Methods a verified daemon has but the import doesn't mention are only
+/// described, unless `add_missing_methods` is set: then they are added to
+/// the skill at medium confidence.
+pub fn enrich_skill(
+    skill: &mut ImportedSkill,
+    registry: &DaemonRegistry,
+    add_missing_methods: bool,
+) -> EnrichmentData {
     let mut enrichment = EnrichmentData::default();
 
     for daemon in &mut skill.daemons {
@@ -547,6 +888,11 @@ pub fn enrich_skill(skill: &mut ImportedSkill, registry: &DaemonRegistry) -> Enr
 
         if let Some(manifest) = registry.get_daemon(daemon_name) {
             enrichment.verified_daemons.push(daemon_name.clone());
+            if let Some(source) = registry.source_path(daemon_name) {
+                enrichment
+                    .verified_sources
+                    .insert(daemon_name.clone(), source.to_path_buf());
+            }
 
             // Upgrade confidence if verified
             if daemon.name.confidence == Confidence::Low {
@@ -608,10 +954,22 @@ pub fn enrich_skill(skill: &mut ImportedSkill, registry: &DaemonRegistry) -> Enr
                     .unwrap_or(&manifest_method.name);
 
                 if !known_methods.contains(&short_name.to_string()) {
-                    // Don't add missing methods automatically, but note them
+                    // Only noted unless asked for, so imports stay faithful
                     let full_name = format!("{}.{}", daemon_name, short_name);
                     if let Some(ref desc) = manifest_method.description {
-                        enrichment.method_descriptions.insert(full_name, desc.clone());
+                        enrichment.method_descriptions.insert(full_name.clone(), desc.clone());
+                    }
+                    if add_missing_methods {
+                        if !manifest_method.params.is_empty() {
+                            enrichment
+                                .method_params
+                                .insert(full_name.clone(), manifest_method.params.clone());
+                        }
+                        daemon.methods.push(
+                            ImportedField::medium(short_name.to_string(), FieldSource::Registry)
+                                .with_note("added from registry"),
+                        );
+                        enrichment.added_methods.push(full_name);
                     }
                 }
             }
@@ -638,6 +996,23 @@ pub enum QualityGrade {
 }
 
 impl QualityGrade {
+    /// Parse a grade letter, in either case
+    pub fn parse(grade: &str) -> Option<Self> {
+        match grade.to_ascii_uppercase().as_str() {
+            "A" => Some(QualityGrade::A),
+            "B" => Some(QualityGrade::B),
+            "C" => Some(QualityGrade::C),
+            "D" => Some(QualityGrade::D),
+            "F" => Some(QualityGrade::F),
+            _ => None,
+        }
+    }
+
+    /// Whether this grade is `min` or better
+    pub fn at_least(&self, min: QualityGrade) -> bool {
+        (*self as u8) <= (min as u8)
+    }
+
     pub fn from_score(score: u32) -> Self {
         match score {
             90..=100 => QualityGrade::A,
@@ -1029,6 +1404,11 @@ pub fn analyze_quality(
     // === CONFIG SCORING ===
     let mut config_score = 0u32;
 
+    // Options recovered from the documentation
+    if !skill.config.is_empty() {
+        config_score += 20;
+    }
+
     // Auth requirements from enrichment
     if let Some(e) = enrichment {
         if !e.auth_requirements.is_empty() {
@@ -1202,7 +1582,7 @@ pub fn analyze_quality(
 }
 
 /// Get known limitations for each import format
-fn get_format_limitations(format: ImportFormat) -> Vec<String> {
+pub fn get_format_limitations(format: ImportFormat) -> Vec<String> {
     match format {
         ImportFormat::ClaudeCode => vec![
             "Workflows not included in export format".to_string(),
@@ -1225,6 +1605,12 @@ fn get_format_limitations(format: ImportFormat) -> Vec<String> {
             "No workflow or trigger information".to_string(),
             "Method names may need translation".to_string(),
         ],
+        ImportFormat::OpenAiTools => vec![
+            "Function definitions only, no version or author".to_string(),
+            "Functions without a daemon prefix (daemon__method) are not mapped to daemons"
+                .to_string(),
+            "No workflow or trigger information".to_string(),
+        ],
         ImportFormat::Zed => vec![
             "Context-only format (~40% fidelity)".to_string(),
             "No structured daemon configuration".to_string(),
@@ -1239,9 +1625,11 @@ fn get_format_limitations(format: ImportFormat) -> Vec<String> {
             "Extension config not all recoverable".to_string(),
         ],
         ImportFormat::Aider => vec![
-            "Conventions format is minimal (~35% fidelity)".to_string(),
-            "No tool/daemon definitions".to_string(),
-            "Style preferences only".to_string(),
+            "Conventions markdown (~50% fidelity for fgp exports, less for hand-written files)"
+                .to_string(),
+            "Daemons and methods recovered from `fgp call` examples, without parameters"
+                .to_string(),
+            "No version, author, or workflow definitions".to_string(),
         ],
     }
 }
@@ -1332,6 +1720,19 @@ impl SkillFingerprint {
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
+
+    /// The hash of one field, by the names [`UnreviewedField`] uses.
+    pub fn field_hash(&self, field: &str) -> Option<u64> {
+        match field {
+            "name" => Some(self.name_hash),
+            "version" => Some(self.version_hash),
+            "description" => Some(self.description_hash),
+            "daemons" => Some(self.daemons_hash),
+            "instructions" => Some(self.instructions_hash),
+            "triggers" => Some(self.triggers_hash),
+            _ => None,
+        }
+    }
 }
 
 /// Type of change detected between two skill versions
@@ -1480,6 +1881,154 @@ pub struct SyncMetadata {
     pub last_sync: String,
     /// Sync direction (import or export)
     pub direction: String,
+    /// Fields imported with low confidence that nobody has reviewed yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreviewed: Vec<UnreviewedField>,
+}
+
+/// A field imported with low or unknown confidence.
+///
+/// It counts as reviewed once the canonical skill's value for it changes, or
+/// once the entry is removed from `.sync.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreviewedField {
+    /// `name`, `version`, `description`, `daemons`, `instructions` or `triggers`
+    pub field: String,
+    pub confidence: Confidence,
+    /// The field's hash in the canonical skill as imported
+    pub hash: u64,
+}
+
+/// Fingerprinted fields that were imported with low or unknown confidence.
+///
+/// A list field (daemons, triggers) counts if any of its entries does.
+fn low_confidence_fields(skill: &ImportedSkill) -> Vec<(&'static str, Confidence)> {
+    fn worst(confidences: impl Iterator<Item = Confidence>) -> Option<Confidence> {
+        confidences
+            .filter(|c| matches!(c, Confidence::Low | Confidence::Unknown))
+            .max_by_key(|c| *c == Confidence::Unknown)
+    }
+
+    let daemons = skill.daemons.iter().flat_map(|d| {
+        std::iter::once(d.name.confidence).chain(d.methods.iter().map(|m| m.confidence))
+    });
+    let triggers = skill
+        .triggers
+        .keywords
+        .iter()
+        .chain(&skill.triggers.patterns)
+        .map(|t| t.confidence);
+
+    [
+        ("name", worst(std::iter::once(skill.name.confidence))),
+        ("version", worst(std::iter::once(skill.version.confidence))),
+        ("description", worst(std::iter::once(skill.description.confidence))),
+        ("daemons", worst(daemons)),
+        ("instructions", worst(std::iter::once(skill.instructions_content.confidence))),
+        ("triggers", worst(triggers)),
+    ]
+    .into_iter()
+    .filter_map(|(field, confidence)| confidence.map(|c| (field, c)))
+    .collect()
+}
+
+/// Low-confidence fields recorded at import that still have their imported
+/// value in the skill at `skill_dir`.
+///
+/// Skills without import metadata have nothing to review.
+pub fn unreviewed_fields(
+    skill_dir: &Path,
+    manifest: &crate::commands::skill_validate::SkillManifest,
+) -> Vec<UnreviewedField> {
+    let Some(metadata) = fs::read_to_string(skill_dir.join(".sync.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SyncMetadata>(&content).ok())
+    else {
+        return Vec::new();
+    };
+
+    let instructions = manifest
+        .instructions
+        .as_ref()
+        .and_then(|i| i.core.as_ref())
+        .and_then(|path| fs::read_to_string(skill_dir.join(path.trim_start_matches("./"))).ok())
+        .unwrap_or_default();
+    let current =
+        SkillFingerprint::from_imported(&from_canonical(manifest, &instructions, skill_dir));
+
+    metadata
+        .unreviewed
+        .into_iter()
+        .filter(|field| current.field_hash(&field.field) == Some(field.hash))
+        .collect()
+}
+
+/// Represent a canonical skill.yaml manifest as an [`ImportedSkill`], so two
+/// versions of a skill can be compared with [`compare_skills`].
+///
+/// `instructions` is the content of the skill's core instruction file. Every
+/// field is high confidence since it comes straight from the manifest. There
+/// is no import format for skill.yaml itself; Claude Code (whose SKILL.md is
+/// generated from it) is recorded, and comparisons ignore it.
+pub fn from_canonical(
+    manifest: &crate::commands::skill_validate::SkillManifest,
+    instructions: &str,
+    path: &Path,
+) -> ImportedSkill {
+    use crate::commands::skill_validate::Author;
+
+    let high = |value: &str| ImportedField::high(value.to_string(), FieldSource::Frontmatter);
+
+    let author = match &manifest.author {
+        Author::String(name) => ImportedAuthor {
+            name: high(name),
+            email: ImportedField::high(None, FieldSource::Frontmatter),
+            url: ImportedField::high(None, FieldSource::Frontmatter),
+        },
+        Author::Object { name, email, url } => ImportedAuthor {
+            name: high(name),
+            email: ImportedField::high(email.clone(), FieldSource::Frontmatter),
+            url: ImportedField::high(url.clone(), FieldSource::Frontmatter),
+        },
+    };
+
+    let triggers = manifest
+        .triggers
+        .as_ref()
+        .map(|t| ImportedTriggers {
+            keywords: t.keywords.iter().map(|k| high(k)).collect(),
+            patterns: t.patterns.iter().map(|p| high(p)).collect(),
+            commands: t.commands.iter().map(|c| high(c)).collect(),
+        })
+        .unwrap_or_default();
+
+    ImportedSkill {
+        name: high(&manifest.name),
+        version: high(&manifest.version),
+        description: high(&manifest.description),
+        author: Some(author),
+        daemons: manifest
+            .daemons
+            .iter()
+            .map(|daemon| ImportedDaemon {
+                name: high(&daemon.name),
+                version: ImportedField::high(daemon.version.clone(), FieldSource::Frontmatter),
+                optional: ImportedField::high(daemon.optional, FieldSource::Frontmatter),
+                methods: daemon.methods.iter().map(|m| high(m)).collect(),
+                examples: daemon
+                    .examples
+                    .iter()
+                    .map(|(method, params)| (method.clone(), high(params)))
+                    .collect(),
+            })
+            .collect(),
+        instructions_content: high(instructions),
+        triggers,
+        config: ImportedConfig::default(),
+        source_format: ImportFormat::ClaudeCode,
+        source_path: path.to_path_buf(),
+        import_timestamp: chrono::Utc::now().to_rfc3339(),
+    }
 }
 
 /// Compare two imported skills and generate diffs
@@ -1799,13 +2348,38 @@ pub fn analyze_sync(
 }
 
 /// Generate sync metadata JSON for storage
-pub fn generate_sync_metadata(skill: &ImportedSkill) -> String {
+///
+/// `skill_yaml` is the canonical manifest written for the skill; the hashes
+/// of its low-confidence fields are recorded so an export can tell whether
+/// anyone has edited them since.
+pub fn generate_sync_metadata(skill: &ImportedSkill, skill_yaml: &str) -> String {
+    // Hash the fields as an export will read them back
+    let canonical = serde_yaml::from_str::<crate::commands::skill_validate::SkillManifest>(skill_yaml)
+        .map(|manifest| {
+            SkillFingerprint::from_imported(&from_canonical(
+                &manifest,
+                &skill.instructions_content.value,
+                &skill.source_path,
+            ))
+        })
+        .unwrap_or_else(|_| SkillFingerprint::from_imported(skill));
+
     let metadata = SyncMetadata {
         source_path: skill.source_path.display().to_string(),
         source_format: skill.source_format.to_key().to_string(),
         fingerprint: SkillFingerprint::from_imported(skill),
         last_sync: chrono::Utc::now().to_rfc3339(),
         direction: "import".to_string(),
+        unreviewed: low_confidence_fields(skill)
+            .into_iter()
+            .filter_map(|(field, confidence)| {
+                Some(UnreviewedField {
+                    field: field.to_string(),
+                    confidence,
+                    hash: canonical.field_hash(field)?,
+                })
+            })
+            .collect(),
     };
 
     serde_json::to_string_pretty(&metadata).unwrap_or_else(|_| "{}".to_string())
@@ -1938,6 +2512,224 @@ fn extract_list_items(content: &str) -> Vec<String> {
     items
 }
 
+/// Headers of sections that document a skill's settings.
+const CONFIG_SECTIONS: &[&str] = &["configuration", "options", "settings", "config"];
+
+/// Recover config options from a "Configuration", "Options" or "Settings"
+/// section, written as a table (a name column, plus any of type, default
+/// and description) or a list of `` `name` `` / `**name**` items.
+///
+/// Options are medium confidence: the section is documentation, so the
+/// types in particular are a reading of prose.
+fn extract_config_options(body: &str) -> ImportedConfig {
+    let Some(section) = extract_section_content(body, CONFIG_SECTIONS) else {
+        return ImportedConfig::default();
+    };
+
+    let mut options = config_options_from_table(&section);
+    if options.is_empty() {
+        options = config_options_from_list(&section);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    options.retain(|option| seen.insert(option.value.name.clone()));
+    ImportedConfig { options }
+}
+
+/// The first markdown table in `content`: its header cells and rows.
+fn markdown_table(content: &str) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    // `\|` is a pipe inside a cell
+    let cells = |line: &str| -> Vec<String> {
+        line.trim()
+            .trim_start_matches('|')
+            .trim_end_matches('|')
+            .replace("\\|", "\0")
+            .split('|')
+            .map(|cell| cell.trim().replace('\0', "|"))
+            .collect()
+    };
+    let is_separator = |line: &str| {
+        line.contains('-')
+            && line
+                .trim()
+                .chars()
+                .all(|c| matches!(c, '|' | '-' | ':' | ' '))
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines
+        .windows(2)
+        .position(|pair| pair[0].trim().starts_with('|') && is_separator(pair[1]))?;
+
+    let headers = cells(lines[start]);
+    let rows = lines[start + 2..]
+        .iter()
+        .take_while(|line| line.trim().starts_with('|'))
+        .map(|line| cells(line))
+        .collect();
+    Some((headers, rows))
+}
+
+fn config_options_from_table(section: &str) -> Vec<ImportedField<ImportedConfigOption>> {
+    let Some((headers, rows)) = markdown_table(section) else {
+        return Vec::new();
+    };
+    let column = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|h| names.contains(&h.to_lowercase().as_str()))
+    };
+    let Some(name_col) = column(&["option", "name", "setting", "key", "variable", "parameter"])
+    else {
+        return Vec::new();
+    };
+    let type_col = column(&["type"]);
+    let default_col = column(&["default", "default value"]);
+    let description_col = column(&["description", "meaning", "purpose", "notes"]);
+
+    let cell = |row: &[String], col: Option<usize>| {
+        col.and_then(|c| row.get(c))
+            .map(|v| strip_inline_markup(v))
+            .filter(|v| !v.is_empty() && v != "-")
+    };
+
+    rows.iter()
+        .filter_map(|row| {
+            let name = cell(row, Some(name_col))?;
+            config_option(
+                &name,
+                cell(row, type_col).as_deref(),
+                cell(row, default_col),
+                cell(row, description_col),
+            )
+        })
+        .collect()
+}
+
+fn config_options_from_list(section: &str) -> Vec<ImportedField<ImportedConfigOption>> {
+    // - `name` (type, default: x): description
+    // - **name** - description. Default: `x`
+    let item_re = Regex::new(
+        r"^[-*]\s+(?:`([^`]+)`|\*\*([^*]+)\*\*)\s*(?:\(([^)]*)\))?\s*(?:[:—–-]\s*)?(.*)$",
+    )
+    .unwrap();
+    let default_re =
+        Regex::new(r"(?i)\bdefault(?:s to|:)?\s*(?:is\s+)?`?([^`,;)]+?)`?\s*(?:[,;)]|\.?$)")
+            .unwrap();
+
+    section
+        .lines()
+        .filter_map(|line| item_re.captures(line.trim()))
+        .filter_map(|caps| {
+            let name = caps.get(1).or(caps.get(2))?.as_str().trim();
+            let details = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+            let description = caps[4].trim();
+
+            let mut kind = None;
+            let mut default = None;
+            for part in details.split(',').map(str::trim) {
+                if let Some(caps) = default_re.captures(part) {
+                    default = Some(strip_inline_markup(&caps[1]));
+                } else if !part.is_empty() && kind.is_none() {
+                    kind = Some(part.to_string());
+                }
+            }
+            if default.is_none() {
+                default = default_re
+                    .captures(description)
+                    .map(|caps| strip_inline_markup(&caps[1]));
+            }
+
+            config_option(
+                name,
+                kind.as_deref(),
+                default,
+                Some(strip_inline_markup(description)).filter(|d| !d.is_empty()),
+            )
+        })
+        .collect()
+}
+
+/// Build an option from what the documentation says about it, or `None`
+/// when the name isn't an identifier (the list or table is about something
+/// else).
+fn config_option(
+    name: &str,
+    kind: Option<&str>,
+    default: Option<String>,
+    description: Option<String>,
+) -> Option<ImportedField<ImportedConfigOption>> {
+    let name_re = Regex::new(r"^[A-Za-z_][A-Za-z0-9_.-]*$").unwrap();
+    if !name_re.is_match(name) {
+        return None;
+    }
+    let default = default.map(|d| d.trim_matches(['"', '\'']).to_string());
+
+    let mut options = Vec::new();
+    let (kind, note) = match kind.map(|k| k.trim().to_lowercase()) {
+        Some(k) if k.contains('|') || k.starts_with("enum") || k.starts_with("one of") => {
+            options = enum_values(&k);
+            ("enum", None)
+        }
+        Some(k) if ["bool", "boolean"].contains(&k.as_str()) => ("boolean", None),
+        Some(k) if ["int", "integer", "number", "float", "double"].contains(&k.as_str()) => {
+            ("number", None)
+        }
+        Some(k) if k.starts_with("array") || k.starts_with("list") || k.ends_with("[]") => {
+            ("array", None)
+        }
+        Some(k) if ["string", "str", "text", "path", "url"].contains(&k.as_str()) => {
+            ("string", None)
+        }
+        _ => match default.as_deref() {
+            Some("true" | "false") => ("boolean", Some("Type inferred from default")),
+            Some(d) if d.parse::<f64>().is_ok() => ("number", Some("Type inferred from default")),
+            _ => ("string", Some("Type not documented; assumed string")),
+        },
+    };
+    // An enum with no values listed can't be validated; keep it a string
+    let kind = if kind == "enum" && options.is_empty() {
+        "string"
+    } else {
+        kind
+    };
+
+    let field = ImportedField::medium(
+        ImportedConfigOption {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            description,
+            default,
+            options,
+        },
+        FieldSource::Content,
+    );
+    Some(match note {
+        Some(note) => field.with_note(note),
+        None => field.with_note("From the documentation's config section"),
+    })
+}
+
+/// Values of an enum type written as `"a" | "b"` or `enum (a, b)`.
+fn enum_values(kind: &str) -> Vec<String> {
+    let inner = kind
+        .trim_start_matches("one of")
+        .trim_start_matches("enum")
+        .trim()
+        .trim_start_matches([':', '(', '['])
+        .trim_end_matches([')', ']']);
+    inner
+        .split(['|', ','])
+        .map(|v| v.trim().trim_matches(['"', '\'', '`']).to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Text without backticks and bold/italic markers.
+fn strip_inline_markup(text: &str) -> String {
+    text.replace(['`', '*'], "").trim().to_string()
+}
+
 /// Extract skill name from path (directory name or filename without extension)
 fn extract_name_from_path(path: &Path) -> String {
     // Try parent directory first (common convention)
@@ -1961,6 +2753,7 @@ fn extract_name_from_path(path: &Path) -> String {
                 .trim_end_matches(".cursorrules")
                 .trim_end_matches(".rules")
                 .trim_end_matches(".windsurf")
+                .trim_end_matches(".openai")
                 .to_string()
         })
         .unwrap_or_else(|| "unknown-skill".to_string())
@@ -1976,13 +2769,88 @@ struct ClaudeCodeFrontmatter {
     name: Option<String>,
     description: Option<String>,
     version: Option<String>,
-    author: Option<String>,
+    /// A name, "Name <email>", or a mapping (see [`frontmatter_author`])
+    author: Option<serde_yaml::Value>,
+    maintainer: Option<serde_yaml::Value>,
     #[serde(default)]
     tools: Vec<ClaudeCodeTool>,
     #[serde(default)]
     triggers: ClaudeCodeTriggers,
 }
 
+/// Author from a frontmatter `author` or `maintainer` value: a name, a
+/// "Name <email>" string, or a mapping with `name` and optional `email` and
+/// `url`.
+fn frontmatter_author(value: &serde_yaml::Value) -> Option<ImportedAuthor> {
+    let optional = |value: Option<String>| match value {
+        Some(v) => ImportedField::high(Some(v), FieldSource::Frontmatter),
+        None => ImportedField::low(None, FieldSource::Default),
+    };
+
+    let (name, email, url) = match value {
+        serde_yaml::Value::String(s) => {
+            let s = s.trim();
+            match s.strip_suffix('>').and_then(|rest| rest.rsplit_once('<')) {
+                Some((name, email)) => (
+                    name.trim().to_string(),
+                    Some(email.trim().to_string()),
+                    None,
+                ),
+                None => (s.to_string(), None, None),
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            let field = |key: &str| map.get(key).and_then(|v| v.as_str()).map(String::from);
+            (field("name")?, field("email"), field("url"))
+        }
+        _ => return None,
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(ImportedAuthor {
+        name: ImportedField::high(name, FieldSource::Frontmatter),
+        email: optional(email),
+        url: optional(url),
+    })
+}
+
+/// Author of the last commit touching `path`, when it is tracked in a git
+/// repository.
+fn git_author(path: &Path) -> Option<ImportedAuthor> {
+    let dir = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let output = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%an,%ae", "--"])
+        .arg(path.file_name()?)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (name, email) = stdout.trim().rsplit_once(',')?;
+    if name.is_empty() {
+        return None;
+    }
+    let note = "Author of the last git commit to the source file";
+
+    Some(ImportedAuthor {
+        name: ImportedField::medium(name.to_string(), FieldSource::GitHistory).with_note(note),
+        email: if email.is_empty() {
+            ImportedField::low(None, FieldSource::Default)
+        } else {
+            ImportedField::medium(Some(email.to_string()), FieldSource::GitHistory).with_note(note)
+        },
+        url: ImportedField::low(None, FieldSource::Default),
+    })
+}
+
 /// Tool definition - supports both string format ("gmail.inbox") and structured format
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
@@ -2032,6 +2900,7 @@ fn parse_claude_code(path: &Path, content: &str) -> Result<ImportedSkill> {
             description: None,
             version: None,
             author: None,
+            maintainer: None,
             tools: vec![],
             triggers: ClaudeCodeTriggers::Simple(vec![]),
         })
@@ -2041,6 +2910,7 @@ fn parse_claude_code(path: &Path, content: &str) -> Result<ImportedSkill> {
             description: None,
             version: None,
             author: None,
+            maintainer: None,
             tools: vec![],
             triggers: ClaudeCodeTriggers::Simple(vec![]),
         }
@@ -2115,13 +2985,19 @@ fn parse_claude_code(path: &Path, content: &str) -> Result<ImportedSkill> {
     // Extract triggers
     let triggers = extract_triggers(&trigger_strings, &body);
 
-    // Extract author from frontmatter
-    let author = fm.author.map(|a| ImportedAuthor {
-        name: ImportedField::high(a, FieldSource::Frontmatter),
-        email: ImportedField::low(None, FieldSource::Default),
-        url: ImportedField::low(None, FieldSource::Default),
+    // Extract author from frontmatter, falling back to the maintainer
+    let author = fm.author.as_ref().and_then(frontmatter_author).or_else(|| {
+        fm.maintainer
+            .as_ref()
+            .and_then(frontmatter_author)
+            .map(|mut a| {
+                a.name = a.name.with_note("From maintainer");
+                a
+            })
     });
 
+    let config = extract_config_options(&body);
+
     Ok(ImportedSkill {
         name,
         version,
@@ -2130,6 +3006,7 @@ fn parse_claude_code(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content: ImportedField::high(body, FieldSource::Content),
         triggers,
+        config,
         source_format: ImportFormat::ClaudeCode,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -2332,6 +3209,7 @@ fn extract_daemons_from_tools(tools: &[String], body: &str) -> Vec<ImportedDaemo
     }
 
     // Convert to ImportedDaemon
+    let mut examples = extract_method_examples(body);
     daemons
         .into_iter()
         .map(|(name, methods)| {
@@ -2340,6 +3218,7 @@ fn extract_daemons_from_tools(tools: &[String], body: &str) -> Vec<ImportedDaemo
             } else {
                 Confidence::Medium
             };
+            let examples = examples.remove(&name).unwrap_or_default();
 
             ImportedDaemon {
                 name: ImportedField {
@@ -2355,6 +3234,7 @@ fn extract_daemons_from_tools(tools: &[String], body: &str) -> Vec<ImportedDaemo
                 .with_note("Default version constraint"),
                 optional: ImportedField::low(false, FieldSource::Default),
                 methods,
+                examples,
             }
         })
         .collect()
@@ -2381,6 +3261,46 @@ fn extract_methods_from_tables(body: &str) -> HashMap<String, Vec<String>> {
     result
 }
 
+/// Extract the params of `fgp call daemon.method -p '<json>'` examples in
+/// fenced code blocks, by daemon and then method. The first example of a
+/// method wins; params that aren't a JSON object are skipped.
+fn extract_method_examples(body: &str) -> HashMap<String, BTreeMap<String, ImportedField<String>>> {
+    let mut result: HashMap<String, BTreeMap<String, ImportedField<String>>> = HashMap::new();
+    let call_re =
+        Regex::new(r"fgp\s+call\s+(\w+)\.(\w+)\s+(?:-p|--params)(?:\s+|=)'([^']*)'").unwrap();
+
+    let mut fence: Option<&str> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            (Some(_), _) => {
+                for cap in call_re.captures_iter(line) {
+                    let params = cap[3].trim();
+                    if !matches!(serde_json::from_str(params), Ok(Value::Object(_))) {
+                        continue;
+                    }
+                    result
+                        .entry(cap[1].to_string())
+                        .or_default()
+                        .entry(cap[2].to_string())
+                        .or_insert_with(|| {
+                            ImportedField::medium(params.to_string(), FieldSource::MethodExtraction)
+                                .with_note("From an `fgp call` example")
+                        });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    result
+}
+
 /// Extract triggers from frontmatter and body
 fn extract_triggers(frontmatter_triggers: &[String], body: &str) -> ImportedTriggers {
     let mut triggers = ImportedTriggers::default();
@@ -2503,6 +3423,7 @@ fn parse_cursor(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content: ImportedField::high(content.to_string(), FieldSource::Content),
         triggers,
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Cursor,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -2616,6 +3537,7 @@ fn parse_zed(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content: ImportedField::high(content.to_string(), FieldSource::Content),
         triggers,
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Zed,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -2721,6 +3643,7 @@ fn parse_windsurf(path: &Path, content: &str) -> Result<ImportedSkill> {
 
     // Parse tools into daemon.method pairs
     if !all_tools.is_empty() {
+        let mut examples = extract_method_examples(&body);
         let mut daemon_methods: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
 
         for tool in &all_tools {
@@ -2733,6 +3656,7 @@ fn parse_windsurf(path: &Path, content: &str) -> Result<ImportedSkill> {
         }
 
         for (daemon_name, methods) in daemon_methods {
+            let examples = examples.remove(&daemon_name).unwrap_or_default();
             daemons.push(ImportedDaemon {
                 name: ImportedField::high(daemon_name, FieldSource::Frontmatter),
                 version: ImportedField::low(Some(">=1.0.0".to_string()), FieldSource::Default),
@@ -2740,6 +3664,7 @@ fn parse_windsurf(path: &Path, content: &str) -> Result<ImportedSkill> {
                 methods: methods.into_iter()
                     .map(|m| ImportedField::high(m, FieldSource::Frontmatter))
                     .collect(),
+                examples,
             });
         }
     }
@@ -2780,6 +3705,7 @@ fn parse_windsurf(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content: ImportedField::high(body, FieldSource::Content),
         triggers,
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Windsurf,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -2818,7 +3744,20 @@ fn parse_aider(path: &Path, content: &str) -> Result<ImportedSkill> {
     let name = if let Some(h1) = extract_first_h1(content) {
         // Often the H1 is just "Conventions" or similar, so check if it's generic
         let lower = h1.to_lowercase();
-        if lower.contains("convention") || lower.contains("rules") || lower.contains("guide") {
+        // Match the suffix on `h1` itself: lowercasing can change the byte
+        // length of what comes before it
+        const SUFFIX: &str = " conventions";
+        let prefix = h1
+            .len()
+            .checked_sub(SUFFIX.len())
+            .filter(|&at| h1.is_char_boundary(at) && h1[at..].eq_ignore_ascii_case(SUFFIX))
+            .map(|at| h1[..at].trim().to_string())
+            .filter(|p| !p.is_empty());
+        if let Some(prefix) = prefix {
+            // "<name> Conventions", as written by `fgp skill export aider`
+            ImportedField::medium(prefix, FieldSource::Content)
+                .with_note("Extracted from '<name> Conventions' header")
+        } else if lower.contains("convention") || lower.contains("rules") || lower.contains("guide") {
             // Try to extract a project name from description
             // Look for patterns like "for the X project" or "X assistant"
             if let Some(project_name) = extract_project_name_from_text(&first_para) {
@@ -2843,7 +3782,19 @@ fn parse_aider(path: &Path, content: &str) -> Result<ImportedSkill> {
     // Extract triggers - Aider often has "Commands" or "Usage" sections
     let mut triggers = extract_triggers(&[], content);
 
-    // Add daemon names as trigger keywords (like Zed parser)
+    // "When to apply" lists the trigger keywords
+    if let Some(apply_section) = extract_section_content(content, &["when to apply"]) {
+        for item in extract_list_items(&apply_section) {
+            if !triggers.keywords.iter().any(|k| k.value == item) {
+                triggers.keywords.push(
+                    ImportedField::medium(item, FieldSource::Content)
+                        .with_note("From When to apply section"),
+                );
+            }
+        }
+    }
+
+    // Add daemon names as trigger keywords (like Zed parser)
     for daemon in &daemons {
         let daemon_name = daemon.name.value.clone();
         if !triggers.keywords.iter().any(|k| k.value == daemon_name) {
@@ -2878,6 +3829,7 @@ fn parse_aider(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content: ImportedField::high(content.to_string(), FieldSource::Content),
         triggers,
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Aider,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -3114,6 +4066,7 @@ fn parse_gemini(path: &Path, content: &str) -> Result<ImportedSkill> {
             ),
             optional: ImportedField::low(false, FieldSource::Default),
             methods,
+            examples: BTreeMap::new(),
         })
         .collect();
 
@@ -3141,6 +4094,7 @@ fn parse_gemini(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons: daemons_vec,
         instructions_content,
         triggers,
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Gemini,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -3230,6 +4184,7 @@ fn parse_codex(path: &Path, content: &str) -> Result<ImportedSkill> {
         daemons,
         instructions_content,
         triggers: ImportedTriggers::default(),
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Codex,
         source_path: path.to_path_buf(),
         import_timestamp: now,
@@ -3255,6 +4210,23 @@ struct McpTool {
     description: Option<String>,
 }
 
+/// Split a tool name into daemon and method.
+///
+/// Tools are often formatted as "mcp__server__method", "daemon__method" or
+/// "daemon.method".
+fn split_tool_name(name: &str) -> Option<(&str, &str)> {
+    let parts: Vec<&str> = name.split("__").collect();
+    if parts.len() >= 2 {
+        if parts[0] == "mcp" && parts.len() >= 3 {
+            Some((parts[1], parts[2]))
+        } else {
+            Some((parts[0], parts[1]))
+        }
+    } else {
+        name.split_once('.')
+    }
+}
+
 /// Parse an MCP .mcp.json file
 fn parse_mcp(path: &Path, content: &str) -> Result<ImportedSkill> {
     let now = chrono::Utc::now().to_rfc3339();
@@ -3284,26 +4256,7 @@ fn parse_mcp(path: &Path, content: &str) -> Result<ImportedSkill> {
     // Extract daemons from tools (MCP tools are often "daemon__method" format)
     let mut daemons_map: HashMap<String, Vec<ImportedField<String>>> = HashMap::new();
     for tool in &config.tools {
-        // MCP tools are often formatted as "mcp__server__method" or "daemon.method"
-        let parts: Vec<&str> = tool.name.split("__").collect();
-        if parts.len() >= 2 {
-            // Format: mcp__daemon__method or daemon__method
-            let (daemon_name, method_name) = if parts[0] == "mcp" && parts.len() >= 3 {
-                (parts[1], parts[2])
-            } else {
-                (parts[0], parts[1])
-            };
-
-            if is_valid_daemon_name(daemon_name) {
-                daemons_map
-                    .entry(daemon_name.to_string())
-                    .or_default()
-                    .push(ImportedField::high(
-                        method_name.to_string(),
-                        FieldSource::Frontmatter,
-                    ));
-            }
-        } else if let Some((daemon_name, method_name)) = tool.name.split_once('.') {
+        if let Some((daemon_name, method_name)) = split_tool_name(&tool.name) {
             if is_valid_daemon_name(daemon_name) {
                 daemons_map
                     .entry(daemon_name.to_string())
@@ -3326,6 +4279,7 @@ fn parse_mcp(path: &Path, content: &str) -> Result<ImportedSkill> {
             ),
             optional: ImportedField::low(false, FieldSource::Default),
             methods,
+            examples: BTreeMap::new(),
         })
         .collect();
 
@@ -3350,12 +4304,235 @@ fn parse_mcp(path: &Path, content: &str) -> Result<ImportedSkill> {
         instructions_content: ImportedField::medium(instructions, FieldSource::Content)
             .with_note("Generated from tool list"),
         triggers: ImportedTriggers::default(),
+        config: ImportedConfig::default(),
         source_format: ImportFormat::Mcp,
         source_path: path.to_path_buf(),
         import_timestamp: now,
     })
 }
 
+// ============================================================================
+// OpenAI Tools (.openai.json) Parser
+// ============================================================================
+
+/// OpenAI function-calling definitions: a bare tool list, or an object with
+/// a `tools` list such as an Assistants API export
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OpenAiToolsFile {
+    Tools(Vec<OpenAiTool>),
+    Assistant(OpenAiAssistant),
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiAssistant {
+    name: Option<String>,
+    description: Option<String>,
+    instructions: Option<String>,
+    #[serde(default)]
+    tools: Vec<OpenAiTool>,
+}
+
+/// One entry of a tool list. Built-in tools such as `code_interpreter` have
+/// no `function` and are skipped.
+#[derive(Debug, Deserialize)]
+struct OpenAiTool {
+    #[serde(rename = "type")]
+    kind: String,
+    function: Option<OpenAiFunction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiFunction {
+    name: String,
+    description: Option<String>,
+    /// JSON schema of the arguments object
+    parameters: Option<Value>,
+}
+
+/// One property of a function's parameter schema
+struct OpenAiParam {
+    name: String,
+    kind: String,
+    required: bool,
+    description: Option<String>,
+}
+
+impl OpenAiParam {
+    /// "name (type, required)"
+    fn summary(&self) -> String {
+        if self.required {
+            format!("{} ({}, required)", self.name, self.kind)
+        } else {
+            format!("{} ({})", self.name, self.kind)
+        }
+    }
+}
+
+/// The properties of a JSON-schema `parameters` object, sorted by name
+fn openai_params(schema: Option<&Value>) -> Vec<OpenAiParam> {
+    let Some(schema) = schema else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    properties
+        .iter()
+        .map(|(name, prop)| {
+            let kind = match prop.get("type") {
+                Some(Value::String(t)) => t.clone(),
+                Some(Value::Array(types)) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                _ if prop.get("enum").is_some() => "enum".to_string(),
+                _ => "any".to_string(),
+            };
+            OpenAiParam {
+                name: name.clone(),
+                kind,
+                required: required.contains(&name.as_str()),
+                description: prop
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            }
+        })
+        .collect()
+}
+
+/// Parse an OpenAI tools / Assistants .openai.json file
+fn parse_openai(path: &Path, content: &str) -> Result<ImportedSkill> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let assistant = match serde_json::from_str(content)
+        .with_context(|| "Failed to parse OpenAI tools JSON")?
+    {
+        OpenAiToolsFile::Tools(tools) => OpenAiAssistant {
+            name: None,
+            description: None,
+            instructions: None,
+            tools,
+        },
+        OpenAiToolsFile::Assistant(assistant) => assistant,
+    };
+    let functions: Vec<&OpenAiFunction> = assistant
+        .tools
+        .iter()
+        .filter(|t| t.kind == "function")
+        .filter_map(|t| t.function.as_ref())
+        .collect();
+    if functions.is_empty() {
+        bail!("No function tools found in {}", path.display());
+    }
+
+    // Extract name
+    let name = if let Some(n) = assistant.name {
+        ImportedField::high(n, FieldSource::Frontmatter)
+    } else {
+        ImportedField::low(extract_name_from_path(path), FieldSource::Filename)
+            .with_note("Inferred from path")
+    };
+
+    // Extract description
+    let description = if let Some(d) = assistant.description {
+        ImportedField::high(d, FieldSource::Frontmatter)
+    } else {
+        ImportedField::low(
+            format!("{} skill", name.value),
+            FieldSource::Default,
+        )
+    };
+
+    // Extract daemons from function names; each method keeps its parameter
+    // list from the schema as a note
+    let mut daemons_map: HashMap<String, Vec<ImportedField<String>>> = HashMap::new();
+    for function in &functions {
+        if let Some((daemon_name, method_name)) = split_tool_name(&function.name) {
+            if is_valid_daemon_name(daemon_name) {
+                let params = openai_params(function.parameters.as_ref());
+                let note = if params.is_empty() {
+                    "No parameters".to_string()
+                } else {
+                    let summaries: Vec<String> = params.iter().map(|p| p.summary()).collect();
+                    format!("Params: {}", summaries.join(", "))
+                };
+                daemons_map
+                    .entry(daemon_name.to_string())
+                    .or_default()
+                    .push(
+                        ImportedField::high(method_name.to_string(), FieldSource::Frontmatter)
+                            .with_note(&note),
+                    );
+            }
+        }
+    }
+
+    let daemons: Vec<ImportedDaemon> = daemons_map
+        .into_iter()
+        .map(|(name, methods)| ImportedDaemon {
+            name: ImportedField::high(name, FieldSource::Frontmatter),
+            version: ImportedField::low(
+                Some(">=1.0.0".to_string()),
+                FieldSource::Default,
+            ),
+            optional: ImportedField::low(false, FieldSource::Default),
+            methods,
+            examples: BTreeMap::new(),
+        })
+        .collect();
+
+    // Instructions: the assistant's own, then the function reference
+    let mut instructions = format!("# {}\n\n", name.value);
+    if let Some(ref text) = assistant.instructions {
+        instructions.push_str(text.trim());
+        instructions.push_str("\n\n");
+    }
+    instructions.push_str("## Available Tools\n\n");
+    for function in &functions {
+        instructions.push_str(&format!(
+            "- **{}**: {}\n",
+            function.name,
+            function.description.as_deref().unwrap_or("No description")
+        ));
+        for param in openai_params(function.parameters.as_ref()) {
+            match param.description {
+                Some(ref d) => instructions.push_str(&format!("  - `{}`: {}\n", param.summary(), d)),
+                None => instructions.push_str(&format!("  - `{}`\n", param.summary())),
+            }
+        }
+    }
+    let instructions_content = if assistant.instructions.is_some() {
+        ImportedField::high(instructions, FieldSource::Content)
+    } else {
+        ImportedField::medium(instructions, FieldSource::Content)
+            .with_note("Generated from function definitions")
+    };
+
+    Ok(ImportedSkill {
+        name,
+        version: ImportedField::low("1.0.0".to_string(), FieldSource::Default)
+            .with_note("Default version - please update"),
+        description,
+        author: None,
+        daemons,
+        instructions_content,
+        triggers: ImportedTriggers::default(),
+        config: ImportedConfig::default(),
+        source_format: ImportFormat::OpenAiTools,
+        source_path: path.to_path_buf(),
+        import_timestamp: now,
+    })
+}
+
 // ============================================================================
 // Skill.yaml Generator
 // ============================================================================
@@ -3421,6 +4598,16 @@ fn generate_skill_yaml(skill: &ImportedSkill) -> String {
                     yaml.push_str(&format!("      - {}\n", method.value));
                 }
             }
+            if !daemon.examples.is_empty() {
+                yaml.push_str("    examples:\n");
+                for (method, params) in &daemon.examples {
+                    yaml.push_str(&format!(
+                        "      {}: {}\n",
+                        yaml_string(method),
+                        yaml_string(&params.value)
+                    ));
+                }
+            }
         }
     }
 
@@ -3470,12 +4657,37 @@ fn generate_skill_yaml(skill: &ImportedSkill) -> String {
     yaml.push_str("#     description: Main workflow\n");
     yaml.push_str("#     default: true\n");
 
-    yaml.push_str("\n# [*INCOMPLETE*] Config options not recoverable from export\n");
-    yaml.push_str("# config:\n");
-    yaml.push_str("#   option_name:\n");
-    yaml.push_str("#     type: string\n");
-    yaml.push_str("#     description: Option description\n");
-    yaml.push_str("#     default: \"value\"\n");
+    if skill.config.is_empty() {
+        yaml.push_str("\n# [*INCOMPLETE*] Config options not recoverable from export\n");
+        yaml.push_str("# config:\n");
+        yaml.push_str("#   option_name:\n");
+        yaml.push_str("#     type: string\n");
+        yaml.push_str("#     description: Option description\n");
+        yaml.push_str("#     default: \"value\"\n");
+    } else {
+        yaml.push_str(
+            "\n# [*LOW-CONFIDENCE*] Recovered from the documentation - verify types and defaults\n",
+        );
+        yaml.push_str("config:\n");
+        for option in &skill.config.options {
+            let option = &option.value;
+            yaml.push_str(&format!("  {}:\n", yaml_string(&option.name)));
+            yaml.push_str(&format!("    type: {}\n", option.kind));
+            if let Some(ref description) = option.description {
+                yaml.push_str(&format!("    description: {}\n", yaml_string(description)));
+            }
+            if let Some(ref default) = option.default {
+                yaml.push_str(&format!(
+                    "    default: {}\n",
+                    yaml_default(&option.kind, default)
+                ));
+            }
+            if !option.options.is_empty() {
+                let values: Vec<String> = option.options.iter().map(|v| yaml_string(v)).collect();
+                yaml.push_str(&format!("    options: [{}]\n", values.join(", ")));
+            }
+        }
+    }
 
     yaml.push_str("\n# [*INCOMPLETE*] Auth requirements - verify these\n");
     yaml.push_str("# auth:\n");
@@ -3485,21 +4697,51 @@ fn generate_skill_yaml(skill: &ImportedSkill) -> String {
     yaml
 }
 
+/// A string as a YAML scalar, quoted (JSON-style) unless it's a plain word.
+fn yaml_string(value: &str) -> String {
+    let plain = Regex::new(r"^[A-Za-z_][A-Za-z0-9_.-]*$").unwrap();
+    let reserved = ["true", "false", "yes", "no", "on", "off", "null", "~"];
+    if plain.is_match(value) && !reserved.contains(&value.to_lowercase().as_str()) {
+        value.to_string()
+    } else {
+        serde_json::to_string(value).unwrap_or_default()
+    }
+}
+
+/// A documented default as YAML of the option's type, or a string when it
+/// isn't one.
+fn yaml_default(kind: &str, default: &str) -> String {
+    match kind {
+        "boolean" if ["true", "false"].contains(&default) => default.to_string(),
+        "number" if default.parse::<f64>().is_ok() => default.to_string(),
+        "array" if default.starts_with('[') => default.to_string(),
+        _ => serde_json::to_string(default).unwrap_or_default(),
+    }
+}
+
 /// Generate import report markdown
 fn generate_import_report(
     skill: &ImportedSkill,
     enrichment: Option<&EnrichmentData>,
     quality: Option<&QualityAssessment>,
     sync: Option<&SyncAnalysis>,
+    merge: Option<&MergeSummary>,
 ) -> String {
     let mut report = String::new();
 
     report.push_str(&format!("# Import Report: {}\n\n", skill.name.value));
-    report.push_str(&format!(
-        "**Source:** {} ({} format)\n",
-        skill.source_path.display(),
-        skill.source_format.name()
-    ));
+    match merge {
+        Some(m) => report.push_str(&format!(
+            "**Source:** {} (merged from {} files)\n",
+            skill.source_path.display(),
+            m.sources.len()
+        )),
+        None => report.push_str(&format!(
+            "**Source:** {} ({} format)\n",
+            skill.source_path.display(),
+            skill.source_format.name()
+        )),
+    }
     report.push_str(&format!("**Imported:** {}\n", skill.import_timestamp));
 
     // Quality grade at the top if available
@@ -3611,7 +4853,49 @@ fn generate_import_report(
 
     // Always missing
     report.push_str("| workflows | ❌ None | N/A | Not in export format |\n");
-    report.push_str("| config | ❌ None | N/A | Not in export format |\n");
+    if skill.config.is_empty() {
+        report.push_str("| config | ❌ None | N/A | Not in export format |\n");
+    } else {
+        report.push_str(&format!(
+            "| config | ⚠️ Medium | Content | {} option(s) from the documentation |\n",
+            skill.config.options.len()
+        ));
+    }
+
+    // Merged sources and where they disagree
+    if let Some(m) = merge {
+        report.push_str("\n## Merged Sources\n\n");
+        report.push_str("| File | Format |\n");
+        report.push_str("|------|--------|\n");
+        for (path, format) in &m.sources {
+            report.push_str(&format!("| `{}` | {} |\n", path.display(), format.name()));
+        }
+
+        if !m.conflicts.is_empty() {
+            report.push_str("\n## Merge Conflicts\n\n");
+            report.push_str(
+                "The sources disagree on these fields. The most confident value was used; \
+                 edit skill.yaml if another one is right.\n",
+            );
+            for conflict in &m.conflicts {
+                report.push_str(&format!("\n### {}\n\n", conflict.field));
+                for (value, path, confidence) in &conflict.candidates {
+                    let mark = if *value == conflict.chosen {
+                        " ← used"
+                    } else {
+                        ""
+                    };
+                    report.push_str(&format!(
+                        "- {} `{}` ({}){}\n",
+                        conf_emoji(*confidence),
+                        value.replace('\n', " "),
+                        path.display(),
+                        mark
+                    ));
+                }
+            }
+        }
+    }
 
     // Enrichment section
     if let Some(e) = enrichment {
@@ -3620,7 +4904,17 @@ fn generate_import_report(
         if !e.verified_daemons.is_empty() {
             report.push_str("### Verified Daemons\n\n");
             for daemon in &e.verified_daemons {
-                report.push_str(&format!("- ✅ **{}** - Found in daemon registry\n", daemon));
+                match e.verified_sources.get(daemon) {
+                    Some(source) => report.push_str(&format!(
+                        "- ✅ **{}** - Found in daemon registry (`{}`)\n",
+                        daemon,
+                        source.display()
+                    )),
+                    None => report.push_str(&format!(
+                        "- ✅ **{}** - Found in daemon registry\n",
+                        daemon
+                    )),
+                }
             }
             report.push_str("\n");
         }
@@ -3887,91 +5181,637 @@ fn generate_import_report(
     report
 }
 
+/// An import's quality and sync status for tooling, written as
+/// import-result.json next to IMPORT_REPORT.md
+#[derive(Debug, Serialize)]
+pub struct ImportResult<'a> {
+    pub name: &'a str,
+    pub source: &'a Path,
+    pub format: ImportFormat,
+    pub quality: &'a QualityAssessment,
+    pub sync_status: SyncStatus,
+    /// `--min-grade`, if given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_grade: Option<QualityGrade>,
+    /// Whether the grade meets `min_grade` (always, without one)
+    pub passed: bool,
+}
+
+/// Write import-result.json into `dir`; returns its path.
+fn write_import_result(
+    dir: &Path,
+    skill: &ImportedSkill,
+    quality: &QualityAssessment,
+    sync: &SyncAnalysis,
+    options: &ImportOptions,
+) -> Result<PathBuf> {
+    let result = ImportResult {
+        name: &skill.name.value,
+        source: &skill.source_path,
+        format: skill.source_format,
+        quality,
+        sync_status: sync.status,
+        min_grade: options.min_grade,
+        passed: options
+            .min_grade
+            .is_none_or(|min_grade| quality.grade.at_least(min_grade)),
+    };
+    fs::create_dir_all(dir)?;
+    let path = dir.join("import-result.json");
+    fs::write(&path, serde_json::to_string_pretty(&result)? + "\n")?;
+    Ok(path)
+}
+
 // ============================================================================
 // Public API
 // ============================================================================
 
-/// Import a skill from a file
-pub fn import_skill(
-    path: &str,
-    format: Option<&str>,
-    output: Option<&str>,
-    dry_run: bool,
-    enrich: bool,
-) -> Result<()> {
+/// Result of importing a single file (used for batch summaries)
+#[derive(Debug, Clone)]
+pub struct ImportOutcome {
+    pub name: String,
+    pub format: ImportFormat,
+    pub grade: QualityGrade,
+    pub score: u32,
+}
+
+/// Options for `fgp skill import`
+#[derive(Debug, Default)]
+pub struct ImportOptions<'a> {
+    /// Source format (detected from each file when not given)
+    pub format: Option<&'a str>,
+    /// Output directory (a parent directory when importing a directory)
+    pub output: Option<&'a str>,
+    /// Show what would be imported without writing files
+    pub dry_run: bool,
+    /// Look up daemons in the registry
+    pub enrich: bool,
+    /// With `enrich`, add registry methods the source doesn't mention
+    pub add_missing_methods: bool,
+    /// Keep going when a file of a directory fails to import
+    pub continue_on_error: bool,
+    /// Fail when a skill's quality grade is below this
+    pub min_grade: Option<QualityGrade>,
+    /// Write import-result.json on a dry run too
+    pub emit_json: bool,
+}
+
+/// Where to write an imported skill
+enum OutputTarget<'a> {
+    /// Exact directory (from --output, or ./<skill-name>/ when not set)
+    Dir(Option<&'a str>),
+    /// Parent directory; the skill goes into <parent>/<skill-name>/
+    Parent(&'a Path),
+}
+
+/// Parse a user-supplied --format value
+pub fn parse_format_arg(fmt: &str) -> Result<ImportFormat> {
+    Ok(match fmt.to_lowercase().as_str() {
+        "claude-code" | "claude" => ImportFormat::ClaudeCode,
+        "cursor" => ImportFormat::Cursor,
+        "codex" => ImportFormat::Codex,
+        "mcp" => ImportFormat::Mcp,
+        "openai" | "openai-tools" => ImportFormat::OpenAiTools,
+        "zed" => ImportFormat::Zed,
+        "windsurf" => ImportFormat::Windsurf,
+        "gemini" => ImportFormat::Gemini,
+        "aider" => ImportFormat::Aider,
+        _ => bail!("Unknown format: {}", fmt),
+    })
+}
+
+/// Import a skill from a file, or every importable file under a directory
+pub fn import_skill(path: &str, options: &ImportOptions) -> Result<()> {
     let source_path = Path::new(path);
 
     if !source_path.exists() {
         bail!("File not found: {}", path);
     }
 
-    // Detect or use specified format
-    let import_format = if let Some(fmt) = format {
-        match fmt.to_lowercase().as_str() {
-            "claude-code" | "claude" => ImportFormat::ClaudeCode,
-            "cursor" => ImportFormat::Cursor,
-            "codex" => ImportFormat::Codex,
-            "mcp" => ImportFormat::Mcp,
-            "zed" => ImportFormat::Zed,
-            "windsurf" => ImportFormat::Windsurf,
-            "gemini" => ImportFormat::Gemini,
-            "aider" => ImportFormat::Aider,
-            _ => bail!("Unknown format: {}", fmt),
-        }
-    } else {
-        ImportFormat::detect(source_path).ok_or_else(|| {
-            anyhow::anyhow!(
-                "Could not detect format. Use --format to specify.\n\
-                 Valid formats: claude-code, cursor, codex, mcp, zed, windsurf, gemini, aider"
-            )
-        })?
+    if source_path.is_dir() {
+        return import_directory(source_path, options);
+    }
+
+    let outcome = import_file(source_path, OutputTarget::Dir(options.output), options)?;
+    check_min_grade(&[outcome], options.min_grade)
+}
+
+/// Fail if any import graded below `--min-grade`.
+fn check_min_grade(outcomes: &[ImportOutcome], min_grade: Option<QualityGrade>) -> Result<()> {
+    let Some(min_grade) = min_grade else {
+        return Ok(());
     };
+    let below: Vec<String> = outcomes
+        .iter()
+        .filter(|outcome| !outcome.grade.at_least(min_grade))
+        .map(|outcome| format!("{} ({:?}, {}%)", outcome.name, outcome.grade, outcome.score))
+        .collect();
+    if !below.is_empty() {
+        bail!(
+            "Quality below the minimum grade {:?}: {}",
+            min_grade,
+            below.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Directories never worth descending into when scanning for skills
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", ".venv", "__pycache__"];
+
+/// Recursively collect files under a directory, sorted for stable output
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if !SKIP_DIRS.contains(&name) {
+                collect_files(&path, files)?;
+            }
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Summary row for batch imports
+#[derive(tabled::Tabled)]
+struct BatchRow {
+    #[tabled(rename = "Skill")]
+    name: String,
+    #[tabled(rename = "Format")]
+    format: String,
+    #[tabled(rename = "Grade")]
+    grade: String,
+    #[tabled(rename = "Source")]
+    source: String,
+}
+
+/// Import every recognized file under a directory tree
+fn import_directory(dir: &Path, options: &ImportOptions) -> Result<()> {
+    let forced_format = options.format.map(parse_format_arg).transpose()?;
+
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+
+    let (importable, unrecognized): (Vec<PathBuf>, Vec<PathBuf>) = files
+        .into_iter()
+        .partition(|f| forced_format.is_some() || ImportFormat::detect(f).is_some());
+
+    if importable.is_empty() {
+        bail!(
+            "No importable files found under {} ({} files scanned)",
+            dir.display(),
+            unrecognized.len()
+        );
+    }
 
     println!(
-        "{} Importing from {} format...",
+        "{} Found {} importable file(s) under {}",
         "→".blue().bold(),
-        import_format.name().cyan()
+        importable.len(),
+        dir.display()
     );
 
-    // Read content
-    let content = fs::read_to_string(source_path)
-        .with_context(|| format!("Failed to read {}", source_path.display()))?;
-
-    // Parse based on format
-    let mut skill = match import_format {
-        ImportFormat::ClaudeCode => parse_claude_code(source_path, &content)?,
-        ImportFormat::Cursor => parse_cursor(source_path, &content)?,
-        ImportFormat::Zed => parse_zed(source_path, &content)?,
-        ImportFormat::Windsurf => parse_windsurf(source_path, &content)?,
-        ImportFormat::Aider => parse_aider(source_path, &content)?,
-        ImportFormat::Gemini => parse_gemini(source_path, &content)?,
-        ImportFormat::Codex => parse_codex(source_path, &content)?,
-        ImportFormat::Mcp => parse_mcp(source_path, &content)?,
+    let output_root = match options.output {
+        Some(o) => PathBuf::from(o),
+        None => std::env::current_dir()?,
     };
 
-    // Optionally enrich with daemon registry data
-    let enrichment = if enrich {
-        println!(
-            "{} Loading daemon registry...",
-            "→".blue().bold()
-        );
-        match DaemonRegistry::load_default() {
-            Ok(registry) => {
-                if registry.daemon_count() > 0 {
-                    println!(
-                        "  {} Loaded {} daemons: [{}]",
-                        "✓".green(),
-                        registry.daemon_count(),
-                        registry.daemon_names().join(", ")
+    let mut rows = Vec::new();
+    let mut outcomes = Vec::new();
+    let mut failures: Vec<(PathBuf, String)> = Vec::new();
+
+    for file in &importable {
+        println!();
+        println!("{} {}", "==>".bold(), file.display());
+
+        match import_file(file, OutputTarget::Parent(&output_root), options) {
+            Ok(outcome) => {
+                rows.push(BatchRow {
+                    name: outcome.name.clone(),
+                    format: outcome.format.name().to_string(),
+                    grade: format!("{} {:?} ({}%)", outcome.grade.emoji(), outcome.grade, outcome.score),
+                    source: file
+                        .strip_prefix(dir)
+                        .unwrap_or(file)
+                        .display()
+                        .to_string(),
+                });
+                outcomes.push(outcome);
+            }
+            Err(e) => {
+                eprintln!("{} {}: {}", "✗".red().bold(), file.display(), e);
+                if !options.continue_on_error {
+                    bail!(
+                        "Import of {} failed (use --continue-on-error to keep going)",
+                        file.display()
                     );
-                    let enrichment_data = enrich_skill(&mut skill, &registry);
+                }
+                failures.push((file.clone(), e.to_string()));
+            }
+        }
+    }
 
-                    if !enrichment_data.verified_daemons.is_empty() {
-                        println!(
-                            "  {} Verified daemons: [{}]",
-                            "✓".green(),
-                            enrichment_data.verified_daemons.join(", ")
-                        );
+    println!();
+    println!("{}", "Import Summary".bold());
+    println!();
+    if !rows.is_empty() {
+        println!("{}", tabled::Table::new(&rows));
+    }
+    println!();
+    println!(
+        "  {} imported, {} failed, {} unrecognized file(s) skipped",
+        rows.len().to_string().green(),
+        failures.len().to_string().red(),
+        unrecognized.len().to_string().dimmed()
+    );
+
+    for (file, err) in &failures {
+        println!("  {} {}: {}", "✗".red(), file.display(), err);
+    }
+
+    if !failures.is_empty() {
+        bail!("{} file(s) failed to import", failures.len());
+    }
+
+    check_min_grade(&outcomes, options.min_grade)
+}
+
+/// Import every agent file at the top of `dir` as a single skill
+/// (`--merge-dir`), merging what each format says about it
+pub fn import_merged(dir: &str, options: &ImportOptions) -> Result<()> {
+    let dir = Path::new(dir);
+    if !dir.is_dir() {
+        bail!("Not a directory: {}", dir.display());
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    let detected: Vec<(PathBuf, ImportFormat)> = files
+        .into_iter()
+        .filter_map(|f| ImportFormat::detect(&f).map(|format| (f, format)))
+        .collect();
+    if detected.is_empty() {
+        bail!("No importable files found in {}", dir.display());
+    }
+
+    println!(
+        "{} Merging {} file(s) from {}",
+        "→".blue().bold(),
+        detected.len(),
+        dir.display()
+    );
+
+    let mut skills = Vec::new();
+    let mut originals = Vec::new();
+    let mut sources = Vec::new();
+    for (path, format) in detected {
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        match read_source(&path, format) {
+            Ok((skill, content)) => {
+                println!("  {} {} ({})", "✓".green(), name, format.name());
+                skills.push(skill);
+                originals.push((format, content));
+                sources.push((path, format));
+            }
+            Err(e) => {
+                println!("  {} {}: {:#}", "✗".red(), name, e);
+                if !options.continue_on_error {
+                    bail!(
+                        "Import of {} failed (use --continue-on-error to leave it out)",
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+    if skills.is_empty() {
+        bail!("None of the files in {} could be imported", dir.display());
+    }
+
+    let (mut skill, conflicts) = merge_skills(skills, dir);
+    if skill.author.is_none() {
+        skill.author = git_author(&sources[0].0);
+    }
+    let summary = MergeSummary { sources, conflicts };
+
+    let outcome = finish_import(
+        skill,
+        &originals,
+        Some(&summary),
+        OutputTarget::Dir(options.output),
+        options,
+    )?;
+    check_min_grade(&[outcome], options.min_grade)
+}
+
+/// A field the merged sources disagree on
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    pub field: &'static str,
+    /// The value the merged skill uses
+    pub chosen: String,
+    /// Each distinct value, with the file it came from and its confidence
+    pub candidates: Vec<(String, PathBuf, Confidence)>,
+}
+
+/// How a `--merge-dir` import combined its sources
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    /// Each file merged, with its format
+    pub sources: Vec<(PathBuf, ImportFormat)>,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Merge skills parsed from the files of one directory.
+///
+/// Name, version, description and author take the most confident source's
+/// value (the first source's on ties); the instructions also prefer the
+/// longest. Daemons, methods, triggers and config options are unioned.
+/// Sources that give different names, versions, descriptions or authors are
+/// returned as conflicts; defaults and placeholders don't count.
+fn merge_skills(skills: Vec<ImportedSkill>, dir: &Path) -> (ImportedSkill, Vec<MergeConflict>) {
+    // Index of the source a field is taken from
+    let most_confident = |rank: &dyn Fn(&ImportedSkill) -> Option<(u8, usize)>| {
+        (0..skills.len())
+            .filter_map(|i| rank(&skills[i]).map(|r| (i, r)))
+            .min_by_key(|&(_, r)| std::cmp::Reverse(r))
+            .map(|(i, _)| i)
+    };
+    let field_rank = |field: fn(&ImportedSkill) -> &ImportedField<String>| {
+        most_confident(&|s| Some((field(s).confidence.rank(), 0))).unwrap_or(0)
+    };
+
+    let name = field_rank(|s| &s.name);
+    let version = field_rank(|s| &s.version);
+    let description = field_rank(|s| &s.description);
+    let instructions = most_confident(&|s| {
+        let content = &s.instructions_content;
+        Some((content.confidence.rank(), content.value.len()))
+    })
+    .unwrap_or(0);
+    let author = most_confident(&|s| s.author.as_ref().map(|a| (a.name.confidence.rank(), 0)));
+
+    let mut conflicts = Vec::new();
+    let mut check =
+        |field,
+         chosen: usize,
+         values: &dyn Fn(&ImportedSkill) -> Option<&ImportedField<String>>| {
+            let candidates: Vec<(&ImportedField<String>, &Path)> = skills
+                .iter()
+                .filter_map(|s| values(s).map(|v| (v, s.source_path.as_path())))
+                .collect();
+            let chosen = values(&skills[chosen])
+                .map(|v| v.value.as_str())
+                .unwrap_or_default();
+            conflicts.extend(field_conflict(field, chosen, &candidates));
+        };
+    check("name", name, &|s| Some(&s.name));
+    check("version", version, &|s| Some(&s.version));
+    check("description", description, &|s| Some(&s.description));
+    if let Some(author) = author {
+        check("author", author, &|s| s.author.as_ref().map(|a| &a.name));
+    }
+
+    let mut merged = ImportedSkill {
+        name: skills[name].name.clone(),
+        version: skills[version].version.clone(),
+        description: skills[description].description.clone(),
+        author: author.and_then(|i| skills[i].author.clone()),
+        daemons: Vec::new(),
+        instructions_content: skills[instructions].instructions_content.clone(),
+        triggers: ImportedTriggers::default(),
+        config: ImportedConfig::default(),
+        source_format: skills[instructions].source_format,
+        source_path: dir.to_path_buf(),
+        import_timestamp: skills[instructions].import_timestamp.clone(),
+    };
+
+    for skill in skills {
+        for daemon in skill.daemons {
+            match merged
+                .daemons
+                .iter_mut()
+                .find(|d| d.name.value == daemon.name.value)
+            {
+                Some(existing) => {
+                    if daemon.version.confidence.rank() > existing.version.confidence.rank() {
+                        existing.version = daemon.version;
+                    }
+                    if daemon.optional.confidence.rank() > existing.optional.confidence.rank() {
+                        existing.optional = daemon.optional;
+                    }
+                    union_fields(&mut existing.methods, daemon.methods, |a, b| a == b);
+                }
+                None => merged.daemons.push(daemon),
+            }
+        }
+        let triggers = &mut merged.triggers;
+        union_fields(&mut triggers.keywords, skill.triggers.keywords, |a, b| {
+            a == b
+        });
+        union_fields(&mut triggers.patterns, skill.triggers.patterns, |a, b| {
+            a == b
+        });
+        union_fields(&mut triggers.commands, skill.triggers.commands, |a, b| {
+            a == b
+        });
+        union_fields(&mut merged.config.options, skill.config.options, |a, b| {
+            a.name == b.name
+        });
+    }
+
+    (merged, conflicts)
+}
+
+/// A conflict, when the sources' values for `field` differ
+fn field_conflict(
+    field: &'static str,
+    chosen: &str,
+    values: &[(&ImportedField<String>, &Path)],
+) -> Option<MergeConflict> {
+    let mut candidates: Vec<(String, PathBuf, Confidence)> = Vec::new();
+    for (value, path) in values {
+        let placeholder =
+            value.confidence == Confidence::Unknown || matches!(value.source, FieldSource::Default);
+        if !placeholder && !candidates.iter().any(|(v, _, _)| *v == value.value) {
+            candidates.push((value.value.clone(), path.to_path_buf(), value.confidence));
+        }
+    }
+    (candidates.len() > 1).then(|| MergeConflict {
+        field,
+        chosen: chosen.to_string(),
+        candidates,
+    })
+}
+
+/// Add `fields` to `into`, skipping ones `same` as a field already there;
+/// of two such fields, the more confident one is kept.
+fn union_fields<T>(
+    into: &mut Vec<ImportedField<T>>,
+    fields: Vec<ImportedField<T>>,
+    same: impl Fn(&T, &T) -> bool,
+) {
+    for field in fields {
+        match into.iter_mut().find(|f| same(&f.value, &field.value)) {
+            Some(existing) if field.confidence.rank() > existing.confidence.rank() => {
+                *existing = field
+            }
+            Some(_) => {}
+            None => into.push(field),
+        }
+    }
+}
+
+/// Names under instructions/ for copies of the original files:
+/// `<format>.md`, numbered when several sources share a format
+fn original_file_names(originals: &[(ImportFormat, String)]) -> Vec<String> {
+    originals
+        .iter()
+        .enumerate()
+        .map(
+            |(i, (format, _))| match originals[..i].iter().filter(|(f, _)| f == format).count() {
+                0 => format!("{}.md", format.to_key()),
+                n => format!("{}-{}.md", format.to_key(), n + 1),
+            },
+        )
+        .collect()
+}
+
+/// Parse a source file's content with the parser for `format`
+fn parse_source(format: ImportFormat, path: &Path, content: &str) -> Result<ImportedSkill> {
+    match format {
+        ImportFormat::ClaudeCode => parse_claude_code(path, content),
+        ImportFormat::Cursor => parse_cursor(path, content),
+        ImportFormat::Zed => parse_zed(path, content),
+        ImportFormat::Windsurf => parse_windsurf(path, content),
+        ImportFormat::Aider => parse_aider(path, content),
+        ImportFormat::Gemini => parse_gemini(path, content),
+        ImportFormat::Codex => parse_codex(path, content),
+        ImportFormat::Mcp => parse_mcp(path, content),
+        ImportFormat::OpenAiTools => parse_openai(path, content),
+    }
+}
+
+/// Import a single file
+fn import_file(
+    source_path: &Path,
+    output: OutputTarget,
+    options: &ImportOptions,
+) -> Result<ImportOutcome> {
+    let import_format = source_format(source_path, options.format)?;
+
+    println!(
+        "{} Importing from {} format...",
+        "→".blue().bold(),
+        import_format.name().cyan()
+    );
+
+    let (mut skill, content) = read_source(source_path, import_format)?;
+
+    // Formats without author information fall back to git history
+    if skill.author.is_none() {
+        skill.author = git_author(source_path);
+    }
+
+    finish_import(skill, &[(import_format, content)], None, output, options)
+}
+
+/// The format given with `--format`, or else the one detected from the path
+fn source_format(path: &Path, format: Option<&str>) -> Result<ImportFormat> {
+    if let Some(fmt) = format {
+        return parse_format_arg(fmt);
+    }
+    ImportFormat::detect(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not detect format. Use --format to specify.\n\
+             Valid formats: claude-code, cursor, codex, mcp, openai, zed, windsurf, gemini, aider"
+        )
+    })
+}
+
+/// Parse a source agent file as an import would, without enriching or
+/// writing anything
+pub fn parse_file(path: &Path, format: Option<&str>) -> Result<ImportedSkill> {
+    let format = source_format(path, format)?;
+    Ok(read_source(path, format)?.0)
+}
+
+/// Read and parse one source file; returns the skill and the file's content
+fn read_source(path: &Path, format: ImportFormat) -> Result<(ImportedSkill, String)> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let skill = parse_source(format, path, &content)?;
+    Ok((skill, content))
+}
+
+/// Enrich, assess and write a parsed skill.
+///
+/// `originals` holds the content of each source file, copied to
+/// `instructions/<format>.md`; `merge` is set for `--merge-dir` imports.
+fn finish_import(
+    mut skill: ImportedSkill,
+    originals: &[(ImportFormat, String)],
+    merge: Option<&MergeSummary>,
+    output: OutputTarget,
+    options: &ImportOptions,
+) -> Result<ImportOutcome> {
+    let original_names = original_file_names(originals);
+
+    // Optionally enrich with daemon registry data
+    let enrichment = if options.enrich {
+        println!(
+            "{} Loading daemon registry...",
+            "→".blue().bold()
+        );
+        match DaemonRegistry::load_default() {
+            Ok(registry) => {
+                if !registry.errors().is_empty() {
+                    println!("  {} {}", "!".yellow(), registry.load_summary());
+                }
+                if registry.daemon_count() > 0 {
+                    if registry.errors().is_empty() {
+                        println!(
+                            "  {} Loaded {} daemons: [{}]",
+                            "✓".green(),
+                            registry.daemon_count(),
+                            registry.daemon_names().join(", ")
+                        );
+                    }
+                    let enrichment_data =
+                        enrich_skill(&mut skill, &registry, options.add_missing_methods);
+
+                    if !enrichment_data.verified_daemons.is_empty() {
+                        println!(
+                            "  {} Verified daemons: [{}]",
+                            "✓".green(),
+                            enrichment_data.verified_daemons.join(", ")
+                        );
+                    }
+                    if !enrichment_data.added_methods.is_empty() {
+                        println!(
+                            "  {} Added from registry: [{}]",
+                            "✓".green(),
+                            enrichment_data.added_methods.join(", ")
+                        );
                     }
                     if !enrichment_data.unknown_daemons.is_empty() {
                         println!(
@@ -3989,10 +5829,12 @@ pub fn import_skill(
                     }
                     Some(enrichment_data)
                 } else {
-                    println!(
-                        "  {} No daemon manifests found",
-                        "?".yellow()
-                    );
+                    if registry.errors().is_empty() {
+                        println!(
+                            "  {} No daemon manifests found",
+                            "?".yellow()
+                        );
+                    }
                     None
                 }
             }
@@ -4027,6 +5869,16 @@ pub fn import_skill(
         skill.version.confidence.symbol(),
         skill.version.value
     );
+    if let Some(ref author) = skill.author {
+        let mut line = author.name.value.clone();
+        if let Some(ref email) = author.email.value {
+            line.push_str(&format!(" <{}>", email));
+        }
+        if let Some(ref note) = author.name.notes {
+            line.push_str(&format!(" {}", format!("({})", note).dimmed()));
+        }
+        println!("  {} author: {}", author.name.confidence.symbol(), line);
+    }
 
     if !skill.daemons.is_empty() {
         let daemon_info: Vec<String> = skill
@@ -4053,6 +5905,22 @@ pub fn import_skill(
             keywords.join(", ")
         );
     }
+    if !skill.config.is_empty() {
+        let names: Vec<_> = skill.config.options.iter().map(|o| o.value.name.as_str()).collect();
+        println!(
+            "  {} config: [{}]",
+            Confidence::Medium.symbol(),
+            names.join(", ")
+        );
+    }
+    if let Some(m) = merge.filter(|m| !m.conflicts.is_empty()) {
+        let fields: Vec<_> = m.conflicts.iter().map(|c| c.field).collect();
+        println!(
+            "  {} conflicts: {} (see IMPORT_REPORT.md)",
+            "!".yellow(),
+            fields.join(", ")
+        );
+    }
 
     // Perform quality assessment
     let quality = analyze_quality(&skill, enrichment.as_ref());
@@ -4086,27 +5954,45 @@ pub fn import_skill(
         }
     }
 
-    if dry_run {
+    // Determine output directory
+    let output_dir = match output {
+        OutputTarget::Dir(Some(dir)) => PathBuf::from(dir),
+        OutputTarget::Dir(None) => std::env::current_dir()?.join(&skill.name.value),
+        OutputTarget::Parent(parent) => parent.join(&skill.name.value),
+    };
+
+    // Analyze sync status (check if output directory already has a skill)
+    let sync_analysis = analyze_sync(&skill, Some(&output_dir));
+
+    if options.dry_run {
         println!();
-        println!("{}", "Dry run - no files written.".yellow());
+        if options.emit_json {
+            let result_path =
+                write_import_result(&output_dir, &skill, &quality, &sync_analysis, options)?;
+            println!("{}", "Dry run - only import-result.json written.".yellow());
+            println!("{} {}", "→".blue(), result_path.display());
+        } else {
+            println!("{}", "Dry run - no files written.".yellow());
+        }
         println!();
         println!("Would generate:");
         println!("  → skill.yaml");
         println!("  → instructions/core.md");
-        println!(
-            "  → instructions/{}.md",
-            skill.source_format.to_key()
-        );
+        for name in &original_names {
+            println!("  → instructions/{}", name);
+        }
         println!("  → IMPORT_REPORT.md");
-        return Ok(());
+        if !options.emit_json {
+            println!("  → import-result.json");
+        }
+        return Ok(ImportOutcome {
+            name: skill.name.value.clone(),
+            format: skill.source_format,
+            grade: quality.grade,
+            score: quality.score,
+        });
     }
 
-    // Determine output directory
-    let output_dir = match output {
-        Some(dir) => PathBuf::from(dir),
-        None => std::env::current_dir()?.join(&skill.name.value),
-    };
-
     // Create directory structure
     fs::create_dir_all(&output_dir)?;
     fs::create_dir_all(output_dir.join("instructions"))?;
@@ -4124,24 +6010,31 @@ pub fn import_skill(
     fs::write(&core_md_path, &skill.instructions_content.value)?;
     println!("{} {}", "→".blue(), core_md_path.display());
 
-    // Write instructions/{agent}.md (copy of original)
-    let agent_md_path = output_dir
-        .join("instructions")
-        .join(format!("{}.md", skill.source_format.to_key()));
-    fs::write(&agent_md_path, &content)?;
-    println!("{} {}", "→".blue(), agent_md_path.display());
-
-    // Analyze sync status (check if output directory already has a skill)
-    let sync_analysis = analyze_sync(&skill, Some(&output_dir));
+    // Write instructions/{agent}.md (copy of each original)
+    for (name, (_, content)) in original_names.iter().zip(originals) {
+        let agent_md_path = output_dir.join("instructions").join(name);
+        fs::write(&agent_md_path, content)?;
+        println!("{} {}", "→".blue(), agent_md_path.display());
+    }
 
     // Write import report with quality assessment and sync status
-    let report = generate_import_report(&skill, enrichment.as_ref(), Some(&quality), Some(&sync_analysis));
+    let report = generate_import_report(
+        &skill,
+        enrichment.as_ref(),
+        Some(&quality),
+        Some(&sync_analysis),
+        merge,
+    );
     let report_path = output_dir.join("IMPORT_REPORT.md");
     fs::write(&report_path, &report)?;
     println!("{} {}", "→".blue(), report_path.display());
 
+    // And its findings for tooling
+    let result_path = write_import_result(&output_dir, &skill, &quality, &sync_analysis, options)?;
+    println!("{} {}", "→".blue(), result_path.display());
+
     // Write sync metadata for future comparisons
-    let sync_metadata = generate_sync_metadata(&skill);
+    let sync_metadata = generate_sync_metadata(&skill, &skill_yaml);
     let sync_path = output_dir.join(".sync.json");
     fs::write(&sync_path, &sync_metadata)?;
     println!("{} {} (sync tracking)", "→".blue(), sync_path.display());
@@ -4170,7 +6063,12 @@ pub fn import_skill(
         format!("(hash: {:016x})", sync_analysis.current_fingerprint.combined_hash).dimmed()
     );
 
-    Ok(())
+    Ok(ImportOutcome {
+        name: skill.name.value.clone(),
+        format: skill.source_format,
+        grade: quality.grade,
+        score: quality.score,
+    })
 }
 
 /// Truncate a string to a maximum length
@@ -4218,6 +6116,250 @@ This is the body.
         );
     }
 
+    #[test]
+    fn test_detect_markdown_by_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let detect = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            ImportFormat::detect(&path)
+        };
+
+        assert_eq!(
+            detect(
+                "gmail.md",
+                "---\nname: gmail\ntools:\n  - gmail.inbox\n---\n\n# Gmail\n"
+            ),
+            Some(ImportFormat::ClaudeCode)
+        );
+        // Frontmatter that isn't a skill's, plain docs, and docs that merely
+        // show `fgp call`s (such as a Codex AGENTS.md)
+        assert_eq!(
+            detect("post.md", "---\ntitle: Hello\n---\n\nfgp call notes.list\n"),
+            None
+        );
+        assert_eq!(
+            detect("README.md", "# Project\n\nRun `fgp status`.\n"),
+            None
+        );
+        assert_eq!(
+            detect(
+                "notes.md",
+                "# Notes\n\nList them with `fgp call notes.list`.\n"
+            ),
+            None
+        );
+
+        // Only the first lines are read
+        let late = format!("---\n{}name: late\n---\n", "\n".repeat(SNIFF_LINES));
+        assert_eq!(detect("late.md", &late), None);
+    }
+
+    #[test]
+    fn test_detect_cursor_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules = dir.path().join(".cursor").join("rules");
+        fs::create_dir_all(&rules).unwrap();
+        let notes = "# Notes\n\nList them with `fgp call notes.list`.\n";
+        for name in ["notes.md", "notes.mdc"] {
+            fs::write(rules.join(name), notes).unwrap();
+            assert_eq!(
+                ImportFormat::detect(&rules.join(name)),
+                Some(ImportFormat::Cursor)
+            );
+        }
+
+        assert_eq!(
+            ImportFormat::detect(Path::new("docs/notes.mdc")),
+            Some(ImportFormat::Cursor)
+        );
+        assert_eq!(ImportFormat::detect(Path::new("docs/rules/notes.md")), None);
+    }
+
+    #[test]
+    fn test_detect_openai_tools_by_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let detect = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            ImportFormat::detect(&path)
+        };
+
+        assert_eq!(
+            detect("tools.json", r#"[{"type": "function", "function": {"name": "gmail__send"}}]"#),
+            Some(ImportFormat::OpenAiTools)
+        );
+        assert_eq!(
+            detect(
+                "assistant.json",
+                r#"{"name": "helper", "tools": [{"type": "file_search"}, {"type": "function", "function": {"name": "notes.list"}}]}"#
+            ),
+            Some(ImportFormat::OpenAiTools)
+        );
+        // Other JSON with a tools list, and manifests
+        assert_eq!(detect("other.json", r#"{"tools": ["gmail.send"]}"#), None);
+        assert_eq!(detect("manifest.json", r#"{"name": "gmail", "methods": []}"#), None);
+        assert_eq!(detect("broken.json", "{"), None);
+    }
+
+    #[test]
+    fn test_claude_code_author() {
+        let author = |frontmatter: &str| {
+            let content = format!("---\nname: inbox\n{}\n---\n\n# Inbox\n", frontmatter);
+            parse_claude_code(Path::new("inbox/SKILL.md"), &content).unwrap().author
+        };
+
+        let plain = author("author: Ada Lovelace").unwrap();
+        assert_eq!(plain.name.value, "Ada Lovelace");
+        assert_eq!(plain.email.value, None);
+
+        let with_email = author("author: Ada Lovelace <ada@example.com>").unwrap();
+        assert_eq!(with_email.name.value, "Ada Lovelace");
+        assert_eq!(with_email.email.value.as_deref(), Some("ada@example.com"));
+        assert_eq!(with_email.email.confidence, Confidence::High);
+
+        let mapping = author("author:\n  name: Ada\n  url: https://example.com").unwrap();
+        assert_eq!(mapping.name.value, "Ada");
+        assert_eq!(mapping.url.value.as_deref(), Some("https://example.com"));
+
+        let maintainer = author("maintainer: Grace <grace@example.com>").unwrap();
+        assert_eq!(maintainer.name.value, "Grace");
+        assert_eq!(maintainer.name.notes.as_deref(), Some("From maintainer"));
+
+        assert!(author("description: No author").is_none());
+        // An unexpected shape doesn't lose the rest of the frontmatter
+        let content = "---\nname: inbox\nauthor: [a, b]\n---\n\n# Inbox\n";
+        let skill = parse_claude_code(Path::new("SKILL.md"), content).unwrap();
+        assert_eq!(skill.name.value, "inbox");
+        assert!(skill.author.is_none());
+    }
+
+    #[test]
+    fn test_claude_code_config() {
+        let config = |section: &str| {
+            let content = format!("---\nname: inbox\n---\n\n# Inbox\n\n{}\n", section);
+            parse_claude_code(Path::new("inbox/SKILL.md"), &content)
+                .unwrap()
+                .config
+        };
+
+        let table = config(
+            "## Configuration\n\n\
+             | Option | Type | Default | Description |\n\
+             |--------|------|---------|-------------|\n\
+             | `max_results` | integer | 20 | Messages per page |\n\
+             | `format` | \"full\" \\| \"minimal\" | full | Body format |\n\
+             | `archive` | | false | Archive after reading |\n",
+        );
+        let options: Vec<&ImportedConfigOption> = table.options.iter().map(|o| &o.value).collect();
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].name, "max_results");
+        assert_eq!(options[0].kind, "number");
+        assert_eq!(options[0].default.as_deref(), Some("20"));
+        assert_eq!(options[1].kind, "enum");
+        assert_eq!(options[1].options, vec!["full", "minimal"]);
+        assert_eq!(options[2].kind, "boolean");
+        assert_eq!(
+            table.options[2].notes.as_deref(),
+            Some("Type inferred from default")
+        );
+        assert!(table
+            .options
+            .iter()
+            .all(|o| o.confidence == Confidence::Medium));
+
+        let list = config(
+            "## Options\n\n\
+             - `label` (string, default: INBOX): Label to read from\n\
+             - **unread_only** - Only unread messages. Default: `true`\n\
+             - Any other note about options\n",
+        );
+        let options: Vec<&ImportedConfigOption> = list.options.iter().map(|o| &o.value).collect();
+        assert_eq!(options.len(), 2);
+        assert_eq!(options[0].name, "label");
+        assert_eq!(options[0].kind, "string");
+        assert_eq!(options[0].default.as_deref(), Some("INBOX"));
+        assert_eq!(
+            options[0].description.as_deref(),
+            Some("Label to read from")
+        );
+        assert_eq!(options[1].name, "unread_only");
+        assert_eq!(options[1].kind, "boolean");
+        assert_eq!(options[1].default.as_deref(), Some("true"));
+
+        assert!(config("## Usage\n\n- `fgp call gmail.list`\n").is_empty());
+
+        let content = "---\nname: inbox\n---\n\n# Inbox\n\n## Settings\n\n\
+                       - `max_results` (number, default: 20): Messages per page\n\
+                       - `label` (string, default: \"INBOX\"): Label to read\n";
+        let skill = parse_claude_code(Path::new("inbox/SKILL.md"), content).unwrap();
+        let yaml = generate_skill_yaml(&skill);
+        assert!(yaml.contains("\nconfig:\n  max_results:\n    type: number\n"));
+        assert!(yaml.contains("    default: 20\n"));
+        assert!(yaml.contains("    default: \"INBOX\"\n"));
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed["config"]["label"]["type"], "string");
+    }
+
+    #[test]
+    fn test_method_examples() {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/import/claude-code/calendar-examples/SKILL.md");
+        let content = fs::read_to_string(&source).unwrap();
+        let skill = parse_claude_code(&source, &content).unwrap();
+        let examples = &skill.daemons[0].examples;
+        // Fenced examples only; the first per method, and valid JSON objects
+        assert_eq!(examples["upcoming"].value, r#"{"days": 7}"#);
+        assert!(examples["create"]
+            .value
+            .starts_with(r#"{"title": "1:1 with Ana""#));
+        assert!(examples.contains_key("free_slots"));
+        assert!(!examples.contains_key("cancel"));
+
+        // skill.yaml keeps them for export
+        let yaml = generate_skill_yaml(&skill);
+        let manifest: crate::commands::skill_validate::SkillManifest =
+            serde_yaml::from_str(&yaml).unwrap();
+        let daemon = &manifest.daemons[0];
+        assert_eq!(daemon.examples.len(), 3);
+        assert_eq!(daemon.examples["upcoming"], r#"{"days": 7}"#);
+        assert_eq!(daemon.examples["create"], examples["create"].value);
+    }
+
+    #[test]
+    fn test_git_author() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("SKILL.md");
+        fs::write(&source, "# Inbox\n").unwrap();
+
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=Ada Lovelace", "-c", "user.email=ada@example.com"])
+                .args(["-c", "commit.gpgsign=false"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        // Untracked files have no history
+        assert!(git_author(&source).is_none());
+
+        git(&["add", "SKILL.md"]);
+        git(&["commit", "-q", "-m", "Add skill"]);
+        let author = git_author(&source).unwrap();
+        assert_eq!(author.name.value, "Ada Lovelace");
+        assert_eq!(author.name.confidence, Confidence::Medium);
+        assert_eq!(author.email.value.as_deref(), Some("ada@example.com"));
+
+        let elsewhere = tempfile::tempdir().unwrap();
+        let outside = elsewhere.path().join("SKILL.md");
+        fs::write(&outside, "# Inbox\n").unwrap();
+        assert!(git_author(&outside).is_none());
+    }
+
     #[test]
     fn test_extract_first_paragraph() {
         let body = r#"# Header
@@ -4229,4 +6371,425 @@ This is the second.
         let para = extract_first_paragraph(body);
         assert_eq!(para, "This is the first paragraph.");
     }
+
+    #[test]
+    fn test_aider_round_trip() {
+        let manifest: crate::commands::skill_validate::SkillManifest = serde_yaml::from_str(
+            r#"
+name: gmail
+version: 1.0.0
+description: Read and send Gmail messages via FGP
+author: Jane
+daemons:
+  - name: gmail
+    methods: [inbox, send]
+triggers:
+  keywords: [email, inbox]
+"#,
+        )
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let conventions = crate::commands::skill_export::aider_conventions(
+            &manifest,
+            dir.path(),
+            &Default::default(),
+        )
+        .unwrap();
+        let path = dir.path().join("gmail.CONVENTIONS.md");
+        let skill = parse_aider(&path, &conventions).unwrap();
+
+        let medium_or_better = |c: Confidence| matches!(c, Confidence::High | Confidence::Medium);
+
+        assert_eq!(skill.name.value, "gmail");
+        assert!(medium_or_better(skill.name.confidence));
+        assert_eq!(skill.description.value, "Read and send Gmail messages via FGP");
+        assert!(medium_or_better(skill.description.confidence));
+
+        assert_eq!(skill.daemons.len(), 1);
+        let daemon = &skill.daemons[0];
+        assert_eq!(daemon.name.value, "gmail");
+        let methods: Vec<&str> = daemon.methods.iter().map(|m| m.value.as_str()).collect();
+        assert_eq!(methods, vec!["inbox", "send"]);
+        assert!(daemon.methods.iter().all(|m| medium_or_better(m.confidence)));
+
+        for keyword in ["email", "inbox"] {
+            let found = skill
+                .triggers
+                .keywords
+                .iter()
+                .find(|k| k.value == keyword)
+                .unwrap_or_else(|| panic!("keyword '{}' not recovered", keyword));
+            assert!(medium_or_better(found.confidence));
+        }
+
+        // Lowercasing 'İ' takes more bytes, and the Kelvin sign fewer
+        for name in ["İİ", "é\u{212A}"] {
+            let content = format!("# {} Conventions\n\nLocal rules.\n", name);
+            assert_eq!(parse_aider(&path, &content).unwrap().name.value, name);
+        }
+    }
+
+    fn write_manifest(dir: &Path, daemon: &str, version: &str, methods: &[&str]) {
+        let daemon_dir = dir.join(daemon);
+        fs::create_dir_all(&daemon_dir).unwrap();
+        let methods: Vec<Value> = methods
+            .iter()
+            .map(|m| serde_json::json!({"name": format!("{}.{}", daemon, m), "description": m}))
+            .collect();
+        let manifest = serde_json::json!({
+            "name": daemon,
+            "version": version,
+            "description": format!("{} daemon", daemon),
+            "methods": methods,
+        });
+        fs::write(daemon_dir.join("manifest.json"), manifest.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_registry_discovers_custom_daemons() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_manifest(tmp.path(), "gmail", "1.0.0", &["inbox", "send"]);
+        write_manifest(tmp.path(), "my-custom", "0.1.0", &["frobnicate"]);
+        fs::create_dir_all(tmp.path().join("not-a-daemon")).unwrap();
+
+        let registry = DaemonRegistry::load_from_fgp_dir(tmp.path()).unwrap();
+        assert_eq!(registry.daemon_names(), vec!["gmail", "my-custom"]);
+        assert!(registry.get_method("my-custom.frobnicate").is_some());
+        assert_eq!(
+            registry.source_path("my-custom"),
+            Some(tmp.path().join("my-custom").join("manifest.json").as_path())
+        );
+    }
+
+    #[test]
+    fn test_registry_walks_nested_checkouts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write_manifest(root, "gmail", "1.0.0", &["inbox"]);
+        write_manifest(&root.join("daemons"), "linear", "0.2.0", &["issues"]);
+        write_manifest(&root.join("contrib/extra"), "jira", "0.1.0", &["search"]);
+        write_manifest(&root.join("target/debug"), "stale", "0.0.1", &["x"]);
+        write_manifest(&root.join(".git"), "hidden", "0.0.1", &["x"]);
+        // A daemon's own subdirectories aren't searched
+        write_manifest(&root.join("gmail"), "fixture", "0.0.1", &["x"]);
+        // Too deep to be a daemon of this checkout
+        write_manifest(&root.join("a/b/c"), "deep", "0.0.1", &["x"]);
+
+        let registry = DaemonRegistry::load_from_fgp_dir(root).unwrap();
+        assert_eq!(registry.daemon_names(), vec!["gmail", "jira", "linear"]);
+    }
+
+    #[test]
+    fn test_registry_prefers_newest_version() {
+        let project = tempfile::tempdir().unwrap();
+        let services = tempfile::tempdir().unwrap();
+        write_manifest(project.path(), "gmail", "1.2.0", &["inbox"]);
+        write_manifest(services.path(), "gmail", "1.10.0", &["inbox", "send"]);
+
+        let mut registry = DaemonRegistry::new();
+        registry.scan_dir(project.path());
+        registry.scan_dir(services.path());
+
+        assert_eq!(registry.daemon_count(), 1);
+        assert_eq!(registry.get_daemon("gmail").unwrap().version, "1.10.0");
+        assert!(registry.get_method("gmail.send").is_some());
+        assert_eq!(
+            registry.source_path("gmail"),
+            Some(services.path().join("gmail").join("manifest.json").as_path())
+        );
+
+        // An older manifest seen later doesn't replace the newer one
+        let older = tempfile::tempdir().unwrap();
+        write_manifest(older.path(), "gmail", "0.9.0", &["inbox"]);
+        registry.scan_dir(older.path());
+        assert_eq!(registry.get_daemon("gmail").unwrap().version, "1.10.0");
+    }
+
+    #[test]
+    fn test_enrich_add_missing_methods() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_manifest(tmp.path(), "gmail", "1.0.0", &["inbox", "send", "search"]);
+        let registry = DaemonRegistry::load_from_fgp_dir(tmp.path()).unwrap();
+        let content = "---\nname: mail\n---\n\n# Mail\n\nRun `fgp call gmail.inbox` first.\n";
+        let methods = |skill: &ImportedSkill| -> Vec<String> {
+            skill.daemons[0].methods.iter().map(|m| m.value.clone()).collect()
+        };
+
+        // Off by default: missing methods are only described
+        let mut skill = parse_claude_code(Path::new("SKILL.md"), content).unwrap();
+        let enrichment = enrich_skill(&mut skill, &registry, false);
+        assert_eq!(methods(&skill), vec!["inbox"]);
+        assert!(enrichment.added_methods.is_empty());
+        assert!(enrichment.method_descriptions.contains_key("gmail.send"));
+
+        let mut skill = parse_claude_code(Path::new("SKILL.md"), content).unwrap();
+        let enrichment = enrich_skill(&mut skill, &registry, true);
+        assert_eq!(methods(&skill), vec!["inbox", "send", "search"]);
+        assert_eq!(enrichment.added_methods, vec!["gmail.send", "gmail.search"]);
+        let added = &skill.daemons[0].methods[1];
+        assert_eq!(added.confidence, Confidence::Medium);
+        assert_eq!(added.notes.as_deref(), Some("added from registry"));
+    }
+
+    #[test]
+    fn test_min_grade() {
+        assert_eq!(QualityGrade::parse("c"), Some(QualityGrade::C));
+        assert_eq!(QualityGrade::parse("E"), None);
+        assert!(QualityGrade::B.at_least(QualityGrade::C));
+        assert!(QualityGrade::C.at_least(QualityGrade::C));
+        assert!(!QualityGrade::D.at_least(QualityGrade::C));
+
+        let outcome = |name: &str, grade, score| ImportOutcome {
+            name: name.to_string(),
+            format: ImportFormat::Cursor,
+            grade,
+            score,
+        };
+        let outcomes = [
+            outcome("gmail", QualityGrade::B, 87),
+            outcome("notes", QualityGrade::D, 62),
+        ];
+        assert!(check_min_grade(&outcomes, None).is_ok());
+        assert!(check_min_grade(&outcomes, Some(QualityGrade::D)).is_ok());
+        let err = check_min_grade(&outcomes, Some(QualityGrade::C)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Quality below the minimum grade C: notes (D, 62%)"
+        );
+    }
+
+    #[test]
+    fn test_merge_skills() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/import");
+        let sources = [
+            (
+                fixtures.join("claude-code/gmail-triage/SKILL.md"),
+                ImportFormat::ClaudeCode,
+            ),
+            (
+                fixtures.join("cursor/github-review/github.cursorrules"),
+                ImportFormat::Cursor,
+            ),
+            (fixtures.join("mcp/gmail/gmail.mcp.json"), ImportFormat::Mcp),
+        ];
+        let skills: Vec<ImportedSkill> = sources
+            .iter()
+            .map(|(path, format)| read_source(path, *format).unwrap().0)
+            .collect();
+        let gmail_methods = |skill: &ImportedSkill| -> Vec<String> {
+            let gmail = skill
+                .daemons
+                .iter()
+                .find(|d| d.name.value == "gmail")
+                .unwrap();
+            gmail.methods.iter().map(|m| m.value.clone()).collect()
+        };
+        let mut expected_methods = gmail_methods(&skills[0]);
+        for method in gmail_methods(&skills[2]) {
+            if !expected_methods.contains(&method) {
+                expected_methods.push(method);
+            }
+        }
+
+        let (merged, conflicts) = merge_skills(skills, Path::new("agents"));
+        // SKILL.md's frontmatter beats the other guesses; ties go to the first source
+        assert_eq!(merged.name.value, "gmail-triage");
+        assert_eq!(merged.version.value, "1.2.0");
+        assert_eq!(merged.source_format, ImportFormat::ClaudeCode);
+        assert_eq!(merged.source_path, Path::new("agents"));
+        assert!(merged.daemons.iter().any(|d| d.name.value == "github"));
+        assert_eq!(gmail_methods(&merged), expected_methods);
+
+        let fields: Vec<_> = conflicts.iter().map(|c| c.field).collect();
+        assert_eq!(fields, vec!["name", "description"]);
+        let name = &conflicts[0];
+        assert_eq!(name.chosen, "gmail-triage");
+        assert!(name.candidates.iter().any(|(v, _, _)| v == "gmail"));
+
+        assert_eq!(
+            original_file_names(&[
+                (ImportFormat::Cursor, String::new()),
+                (ImportFormat::Zed, String::new()),
+                (ImportFormat::Cursor, String::new()),
+            ]),
+            vec!["cursor.md", "zed.md", "cursor-2.md"]
+        );
+    }
+
+    #[test]
+    fn test_registry_skips_bad_manifests() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_manifest(tmp.path(), "gmail", "1.0.0", &["inbox"]);
+        write_manifest(tmp.path(), "slack", "0.2.0", &["post"]);
+
+        // Syntactically broken JSON
+        fs::create_dir_all(tmp.path().join("broken")).unwrap();
+        fs::write(
+            tmp.path().join("broken").join("manifest.json"),
+            "{\n  \"name\": \"broken\",\n  \"version\": \n}",
+        )
+        .unwrap();
+
+        // Valid JSON missing a required field
+        fs::create_dir_all(tmp.path().join("incomplete")).unwrap();
+        fs::write(
+            tmp.path().join("incomplete").join("manifest.json"),
+            r#"{"name": "incomplete", "description": "no version"}"#,
+        )
+        .unwrap();
+
+        // Valid schema but unusable
+        fs::create_dir_all(tmp.path().join("unnamed")).unwrap();
+        fs::write(
+            tmp.path().join("unnamed").join("manifest.json"),
+            r#"{"name": "", "version": "1.0.0", "description": "no name"}"#,
+        )
+        .unwrap();
+
+        let registry = DaemonRegistry::load_from_fgp_dir(tmp.path()).unwrap();
+
+        assert_eq!(registry.daemon_names(), vec!["gmail", "slack"]);
+        assert_eq!(registry.errors().len(), 3);
+
+        let broken = &registry.errors()[0];
+        assert!(broken.path.ends_with("broken/manifest.json"));
+        assert!(matches!(broken.kind, ManifestErrorKind::Syntax { line: 4, .. }));
+
+        let incomplete = &registry.errors()[1];
+        assert_eq!(incomplete.kind, ManifestErrorKind::Invalid);
+        assert!(incomplete.message.contains("version"));
+
+        let unnamed = &registry.errors()[2];
+        assert_eq!(unnamed.kind, ManifestErrorKind::Invalid);
+
+        assert_eq!(
+            registry.load_summary(),
+            "Loaded 2 daemons, 3 manifests skipped — run 'fgp registry list --errors' for details"
+        );
+    }
+
+    /// Subdirectories of `dir`, sorted.
+    fn subdirs(dir: &Path) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    /// A parsed skill as JSON, minus what changes between runs: the
+    /// timestamp, where the checkout lives, and daemon order (parsers collect
+    /// daemons in hash maps).
+    fn normalized(mut skill: ImportedSkill, root: &Path) -> Value {
+        skill.import_timestamp = String::new();
+        skill.source_path = skill.source_path.strip_prefix(root).unwrap().to_path_buf();
+        skill.daemons.sort_by(|a, b| a.name.value.cmp(&b.name.value));
+        serde_json::to_value(&skill).unwrap()
+    }
+
+    /// Every fixture under tests/fixtures/import/<format>/<case>/ parses to the
+    /// case's expected.json. After an intended parser change, regenerate them
+    /// with `UPDATE_GOLDEN=1 cargo test test_import_fixtures` and review the diff.
+    #[test]
+    fn test_import_fixtures() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/import");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let mut cases = 0;
+        let mut mismatches = Vec::new();
+
+        for format_dir in subdirs(&root) {
+            let format_key = format_dir.file_name().unwrap().to_str().unwrap();
+            let format_cases = subdirs(&format_dir);
+            assert!(
+                format_cases.len() >= 2,
+                "{} needs at least two fixtures",
+                format_dir.display()
+            );
+
+            for case in format_cases {
+                // Other files in a case are ones the source refers to, such as
+                // an instructions_file
+                let sources: Vec<PathBuf> = fs::read_dir(&case)
+                    .unwrap()
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| ImportFormat::detect(p).is_some())
+                    .collect();
+                assert_eq!(sources.len(), 1, "{} needs exactly one source file", case.display());
+                let source = &sources[0];
+
+                let format = ImportFormat::detect(source).unwrap();
+                assert_eq!(format.to_key(), format_key, "{} detected as the wrong format", source.display());
+
+                let content = fs::read_to_string(source).unwrap();
+                let skill = parse_source(format, source, &content)
+                    .unwrap_or_else(|e| panic!("{} failed to import: {}", source.display(), e));
+                let actual = normalized(skill, &root);
+
+                let golden = case.join("expected.json");
+                if update {
+                    fs::write(&golden, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+                } else {
+                    let expected: Value = fs::read_to_string(&golden)
+                        .ok()
+                        .and_then(|s| serde_json::from_str(&s).ok())
+                        .unwrap_or(Value::Null);
+                    if actual != expected {
+                        mismatches.push(format!(
+                            "{}\n--- expected\n{}\n+++ actual\n{}",
+                            golden.display(),
+                            serde_json::to_string_pretty(&expected).unwrap(),
+                            serde_json::to_string_pretty(&actual).unwrap()
+                        ));
+                    }
+                }
+                cases += 1;
+            }
+        }
+
+        assert!(cases > 0, "no fixtures found under {}", root.display());
+        assert!(
+            mismatches.is_empty(),
+            "{} of {} fixtures differ from their golden output (rerun with UPDATE_GOLDEN=1 if the change is intended):\n\n{}",
+            mismatches.len(),
+            cases,
+            mismatches.join("\n\n")
+        );
+    }
+
+    #[test]
+    fn test_unreviewed_fields() {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/import/cursor/browser-testing/.cursorrules");
+        let skill = parse_source(ImportFormat::Cursor, &source, &fs::read_to_string(&source).unwrap()).unwrap();
+
+        // Write the skill the way an import does
+        let dir = tempfile::tempdir().unwrap();
+        let skill_yaml = generate_skill_yaml(&skill);
+        fs::create_dir(dir.path().join("instructions")).unwrap();
+        fs::write(dir.path().join("skill.yaml"), &skill_yaml).unwrap();
+        fs::write(dir.path().join("instructions/core.md"), &skill.instructions_content.value).unwrap();
+        fs::write(dir.path().join(".sync.json"), generate_sync_metadata(&skill, &skill_yaml)).unwrap();
+
+        let unreviewed = |dir: &Path| -> Vec<String> {
+            let manifest = crate::commands::skill_manifest::load(dir).unwrap().manifest;
+            unreviewed_fields(dir, &manifest).into_iter().map(|f| f.field).collect()
+        };
+        // The version is a placeholder; everything else was found in the rules
+        assert_eq!(unreviewed(dir.path()), vec!["version"]);
+
+        // Editing the field reviews it
+        let edited = skill_yaml.replacen(&format!("version: {}", skill.version.value), "version: 2.0.0", 1);
+        assert_ne!(edited, skill_yaml);
+        fs::write(dir.path().join("skill.yaml"), edited).unwrap();
+        assert!(unreviewed(dir.path()).is_empty());
+
+        // Skills that weren't imported have nothing to review
+        fs::remove_file(dir.path().join(".sync.json")).unwrap();
+        assert!(unreviewed(dir.path()).is_empty());
+    }
 }
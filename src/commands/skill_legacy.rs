@@ -0,0 +1,324 @@
+//! Deprecation path for the legacy `fgp skill marketplace` commands.
+//!
+//! Skill taps replace marketplaces. Every marketplace command prints a notice
+//! pointing at the tap equivalent and offers to migrate marketplaces whose
+//! repository is also laid out as a tap. Set `FGP_LEGACY_MARKETPLACE=1` or
+//! `[legacy] marketplace = true` in `~/.fgp/config.toml` to silence it.
+//!
+//! Invocations are counted locally in `~/.fgp/skills/legacy_usage.json` (never
+//! sent anywhere) so a future release can decide when removal is safe. The
+//! same file remembers marketplaces the user declined to migrate, so each one
+//! is only offered once.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use super::network::Network;
+use super::skill::{load_known_marketplaces, save_known_marketplaces};
+use super::{fgp_home, skill_tap};
+
+/// Environment variable that silences the deprecation notice.
+pub const LEGACY_MARKETPLACE_ENV: &str = "FGP_LEGACY_MARKETPLACE";
+
+/// A configured marketplace and how it maps onto taps.
+#[derive(Debug)]
+pub struct LegacyMarketplace {
+    pub name: String,
+    /// GitHub `owner/repo`
+    pub repo: String,
+    /// Whether the marketplace's clone is also laid out as a tap
+    pub tap_compatible: bool,
+    /// Whether a tap for the same repository is already configured
+    pub tapped: bool,
+}
+
+/// Local record of legacy command usage.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LegacyUsage {
+    invocations: u64,
+    #[serde(default)]
+    first_used: Option<String>,
+    #[serde(default)]
+    last_used: Option<String>,
+    /// Invocations per subcommand
+    #[serde(default)]
+    commands: BTreeMap<String, u64>,
+    /// Marketplaces the user chose not to migrate when asked
+    #[serde(default)]
+    declined: BTreeSet<String>,
+}
+
+fn usage_path() -> PathBuf {
+    fgp_home().join("skills").join("legacy_usage.json")
+}
+
+/// Configured marketplaces, sorted by name.
+pub fn legacy_marketplaces() -> Result<Vec<LegacyMarketplace>> {
+    let known = load_known_marketplaces()?;
+
+    let mut marketplaces: Vec<LegacyMarketplace> = known
+        .marketplaces
+        .iter()
+        .map(|(name, entry)| LegacyMarketplace {
+            name: name.clone(),
+            repo: entry.source.repo.clone(),
+            tap_compatible: entry
+                .install_location
+                .as_deref()
+                .map(|location| skill_tap::has_tap_layout(Path::new(location)))
+                .unwrap_or(false),
+            tapped: skill_tap::is_tapped(&entry.source.repo),
+        })
+        .collect();
+
+    marketplaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(marketplaces)
+}
+
+/// Whether the user opted into legacy mode.
+fn silenced() -> bool {
+    let env = std::env::var(LEGACY_MARKETPLACE_ENV).unwrap_or_default();
+    if !env.is_empty() && env != "0" {
+        return true;
+    }
+    crate::config::load()
+        .map(|config| config.legacy.marketplace)
+        .unwrap_or(false)
+}
+
+/// Print the deprecation notice for `fgp skill marketplace <command>` and
+/// offer to migrate marketplaces that have a tap equivalent.
+///
+/// `url` is the repository passed to `marketplace add`, if any. Output goes
+/// to stderr so scripts reading stdout are unaffected.
+pub fn notice(command: &str, url: Option<&str>, network: Network) -> Result<()> {
+    // Best-effort; a read-only home directory shouldn't break the command
+    let usage_path = usage_path();
+    let _ = record_usage(&usage_path, command);
+
+    if silenced() {
+        return Ok(());
+    }
+
+    let marketplaces = legacy_marketplaces()?;
+
+    // Nothing left to migrate: nudge harder, nobody should be starting here
+    if marketplaces.is_empty() && url.is_none() {
+        eprintln!(
+            "{} `fgp skill marketplace` is deprecated and will be removed; no marketplaces are configured.",
+            "✗".red().bold()
+        );
+        eprintln!(
+            "  Use {} and {} instead.",
+            "fgp skill tap add <owner/repo>".cyan(),
+            "fgp skill search".cyan()
+        );
+        eprintln!();
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} `fgp skill marketplace` is deprecated; skill taps replace it.",
+        "!".yellow().bold()
+    );
+    let equivalent = url
+        .and_then(skill_tap::repo_slug)
+        .unwrap_or_else(|| "<owner/repo>".to_string());
+    eprintln!(
+        "  Equivalent: {}",
+        format!("fgp skill tap add {}", equivalent).cyan()
+    );
+    eprintln!(
+        "  {}",
+        format!(
+            "Silence with {}=1 or `[legacy] marketplace = true` in ~/.fgp/config.toml",
+            LEGACY_MARKETPLACE_ENV
+        )
+        .dimmed()
+    );
+
+    // Ask once per marketplace; after that (or without a terminal) just say
+    // how to switch
+    let declined = load_usage(&usage_path).declined;
+    let interactive = std::io::stdin().is_terminal();
+    let (ask, remind): (Vec<&LegacyMarketplace>, Vec<&LegacyMarketplace>) = marketplaces
+        .iter()
+        .filter(|m| m.tap_compatible)
+        .partition(|m| interactive && !declined.contains(&m.name));
+
+    for marketplace in ask {
+        if super::confirm(&format!(
+            "Migrate marketplace '{}' to the {} tap?",
+            marketplace.name, marketplace.repo
+        ))? {
+            migrate_one(marketplace, network)?;
+        } else {
+            let _ = record_declined(&usage_path, &marketplace.name);
+        }
+    }
+
+    if !remind.is_empty() {
+        let names: Vec<&str> = remind.iter().map(|m| m.name.as_str()).collect();
+        eprintln!(
+            "  {} also available as a tap; run {} to switch.",
+            names.join(", "),
+            "fgp skill marketplace migrate".cyan()
+        );
+    }
+
+    eprintln!();
+    Ok(())
+}
+
+/// Move marketplaces to taps (all of them, or just `name`).
+pub fn migrate(name: Option<&str>, network: Network) -> Result<()> {
+    let marketplaces = legacy_marketplaces()?;
+
+    if marketplaces.is_empty() {
+        println!("{} No legacy marketplaces configured.", "✓".green().bold());
+        return Ok(());
+    }
+
+    let selected: Vec<&LegacyMarketplace> = match name {
+        Some(name) => {
+            let Some(marketplace) = marketplaces.iter().find(|m| m.name == name) else {
+                bail!(
+                    "Marketplace '{}' not found. Use 'fgp skill marketplace list' to see configured marketplaces.",
+                    name
+                );
+            };
+            if !marketplace.tap_compatible {
+                bail!(
+                    "{} has no tap layout (skills/<name>/skill.yaml), so marketplace '{}' can't be migrated yet. Keep using it with {}=1.",
+                    marketplace.repo,
+                    name,
+                    LEGACY_MARKETPLACE_ENV
+                );
+            }
+            vec![marketplace]
+        }
+        None => marketplaces.iter().collect(),
+    };
+
+    for marketplace in selected {
+        if marketplace.tap_compatible {
+            migrate_one(marketplace, network)?;
+        } else {
+            println!(
+                "{} Skipping '{}': {} has no tap layout",
+                "!".yellow().bold(),
+                marketplace.name,
+                marketplace.repo
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the tap (unless present) and drop the marketplace entry.
+///
+/// The marketplace clone is left in place so skills installed from it keep
+/// working until they are reinstalled from the tap.
+fn migrate_one(marketplace: &LegacyMarketplace, network: Network) -> Result<()> {
+    if !marketplace.tapped {
+        skill_tap::add(&marketplace.repo, None, network)
+            .with_context(|| format!("Failed to add tap {}", marketplace.repo))?;
+    }
+
+    let mut known = load_known_marketplaces()?;
+    known.marketplaces.remove(&marketplace.name);
+    save_known_marketplaces(&known)?;
+
+    println!(
+        "{} Migrated marketplace '{}' to tap {}",
+        "✓".green().bold(),
+        marketplace.name,
+        marketplace.repo.cyan()
+    );
+    Ok(())
+}
+
+fn load_usage(path: &Path) -> LegacyUsage {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_usage(path: &Path, usage: &LegacyUsage) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(usage)?)?;
+    Ok(())
+}
+
+fn record_usage(path: &Path, command: &str) -> Result<()> {
+    let mut usage = load_usage(path);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    usage.invocations += 1;
+    usage.first_used.get_or_insert_with(|| now.clone());
+    usage.last_used = Some(now);
+    *usage.commands.entry(command.to_string()).or_default() += 1;
+
+    save_usage(path, &usage)
+}
+
+fn record_declined(path: &Path, marketplace: &str) -> Result<()> {
+    let mut usage = load_usage(path);
+    usage.declined.insert(marketplace.to_string());
+    save_usage(path, &usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_usage_counts_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skills").join("legacy_usage.json");
+
+        record_usage(&path, "list").unwrap();
+        record_usage(&path, "update").unwrap();
+        record_usage(&path, "list").unwrap();
+
+        let usage: LegacyUsage = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(usage.invocations, 3);
+        assert_eq!(usage.commands["list"], 2);
+        assert_eq!(usage.commands["update"], 1);
+        assert!(usage.first_used.is_some());
+    }
+
+    #[test]
+    fn test_record_declined_keeps_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skills").join("legacy_usage.json");
+
+        record_usage(&path, "list").unwrap();
+        record_declined(&path, "community").unwrap();
+        record_usage(&path, "list").unwrap();
+
+        let usage = load_usage(&path);
+        assert_eq!(usage.invocations, 2);
+        assert!(usage.declined.contains("community"));
+    }
+
+    #[test]
+    fn test_tap_layout_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!skill_tap::has_tap_layout(dir.path()));
+
+        let skill = dir.path().join("skills").join("triage");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("skill.yaml"), "name: triage\n").unwrap();
+        assert!(skill_tap::has_tap_layout(dir.path()));
+    }
+}
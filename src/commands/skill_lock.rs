@@ -0,0 +1,201 @@
+//! The skill lockfile, `~/.fgp/skills/skills.lock`.
+//!
+//! Records the exact version and git commit of every installed skill, plus
+//! the version constraint it may be upgraded within. The constraint is set
+//! when a skill is installed, as `^major.minor` of its version, and
+//! `fgp skill upgrade` only moves a skill to a newer version that satisfies
+//! it; `--latest` upgrades past it, and the constraint then follows the new
+//! version.
+//!
+//! Constraints use Cargo's syntax and matching rules (via the `semver`
+//! crate): `^1.2` (the default, `>=1.2.0, <2.0.0`), `~1.2.3`
+//! (`>=1.2.3, <1.3.0`), `=1.2.3`, `>=1.2` and `*`. A pre-release only
+//! matches a constraint that names a pre-release of the same version.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use super::{fgp_home, parse_version};
+
+/// skills.lock
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SkillsLock {
+    pub version: u32,
+    /// By installed skill key (`<skill>@<tap or marketplace>`)
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+impl Default for SkillsLock {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+/// One installed skill in skills.lock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Installed version
+    pub version: String,
+    /// Commit of the tap or marketplace the skill was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// Versions `fgp skill upgrade` may move to without `--latest`
+    pub constraint: String,
+    /// When the entry was last written
+    pub locked_at: String,
+}
+
+/// A version constraint such as `^1.2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq(semver::VersionReq);
+
+impl VersionReq {
+    /// Parse a constraint; a bare version means `^version`.
+    pub fn parse(constraint: &str) -> Result<Self> {
+        match semver::VersionReq::parse(constraint.trim()) {
+            Ok(req) => Ok(Self(req)),
+            Err(e) => bail!(
+                "Invalid version constraint '{}' (expected e.g. ^1.2, ~1.2.3 or =1.2.3): {}",
+                constraint,
+                e
+            ),
+        }
+    }
+
+    /// The default constraint for a skill installed at `version`:
+    /// `^major.minor`, or `*` for a version that isn't semver.
+    pub fn default_for(version: &str) -> Self {
+        match parse_version(version) {
+            Some(version) => Self::parse(&format!("^{}.{}", version.major, version.minor))
+                .expect("^major.minor is a valid constraint"),
+            None => Self(semver::VersionReq::STAR),
+        }
+    }
+
+    /// Whether `version` satisfies the constraint (a version that isn't
+    /// semver satisfies only `*`).
+    pub fn matches(&self, version: &str) -> bool {
+        if self.0 == semver::VersionReq::STAR {
+            return true;
+        }
+        parse_version(version).is_some_and(|version| self.0.matches(&version))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+fn lock_path() -> PathBuf {
+    fgp_home().join("skills").join("skills.lock")
+}
+
+/// Load skills.lock (empty if there isn't one yet)
+pub fn load() -> Result<SkillsLock> {
+    let path = lock_path();
+    if !path.exists() {
+        return Ok(SkillsLock::default());
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+}
+
+/// Save skills.lock, replacing the old file in one step so an interrupted
+/// install never leaves it half-written
+fn save(lock: &SkillsLock) -> Result<()> {
+    let path = lock_path();
+    fs::create_dir_all(path.parent().unwrap())?;
+    let temp = path.with_extension("lock.tmp");
+    fs::write(&temp, serde_json::to_string_pretty(lock)? + "\n")?;
+    fs::rename(&temp, &path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Record an installed skill. The constraint stays as it was while the new
+/// version satisfies it, and otherwise follows the new version.
+pub fn record(key: &str, version: &str, git_sha: Option<&str>) -> Result<()> {
+    let mut lock = load()?;
+    let constraint = lock
+        .skills
+        .get(key)
+        .and_then(|locked| VersionReq::parse(&locked.constraint).ok())
+        .filter(|req| req.matches(version))
+        .unwrap_or_else(|| VersionReq::default_for(version));
+    lock.skills.insert(
+        key.to_string(),
+        LockedSkill {
+            version: version.to_string(),
+            git_sha: git_sha.map(str::to_string),
+            constraint: constraint.to_string(),
+            locked_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    save(&lock)
+}
+
+/// Drop skills from skills.lock
+pub fn forget(keys: &[String]) -> Result<()> {
+    let mut lock = load()?;
+    let before = lock.skills.len();
+    lock.skills.retain(|key, _| !keys.contains(key));
+    if lock.skills.len() != before {
+        save(&lock)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_req() {
+        let caret = VersionReq::parse("^1.2").unwrap();
+        assert!(caret.matches("1.2.0"));
+        assert!(caret.matches("1.9.3"));
+        assert!(!caret.matches("1.1.9"));
+        assert!(!caret.matches("2.0.0"));
+        assert!(!caret.matches("latest"));
+
+        let zero = VersionReq::parse("0.2").unwrap();
+        assert!(zero.matches("0.2.5"));
+        assert!(!zero.matches("0.3.0"));
+        assert!(VersionReq::parse("^0.0.3").unwrap().matches("0.0.3"));
+        assert!(!VersionReq::parse("^0.0.3").unwrap().matches("0.0.4"));
+
+        let tilde = VersionReq::parse("~1.2.3").unwrap();
+        assert!(tilde.matches("1.2.9"));
+        assert!(!tilde.matches("1.3.0"));
+        assert!(VersionReq::parse("=1.2").unwrap().matches("v1.2.0"));
+        assert!(VersionReq::parse(">=1.2").unwrap().matches("3.0.0"));
+        assert!(VersionReq::parse("*").unwrap().matches("anything"));
+        assert!(VersionReq::parse("^1.x").unwrap().matches("1.4.0"));
+        assert!(VersionReq::parse("^one").is_err());
+
+        // Pre-releases only match constraints that name one of the same version
+        assert!(!caret.matches("1.5.0-beta.1"));
+        assert!(VersionReq::parse("^1.5.0-beta.1")
+            .unwrap()
+            .matches("1.5.0-beta.2"));
+        assert!(!VersionReq::parse("^1.5.0-beta.1")
+            .unwrap()
+            .matches("1.6.0-beta.1"));
+        // A 0.x caret with fewer parts still allows patch releases
+        assert!(VersionReq::parse("^0.0").unwrap().matches("0.0.7"));
+        assert!(!VersionReq::parse("^0.0").unwrap().matches("0.1.0"));
+
+        assert_eq!(VersionReq::default_for("1.4.2").to_string(), "^1.4");
+        assert_eq!(VersionReq::default_for("0.3.0-beta.1").to_string(), "^0.3");
+        assert_eq!(VersionReq::default_for("nightly").to_string(), "*");
+        assert_eq!(VersionReq::parse("~1.2.3").unwrap().to_string(), "~1.2.3");
+    }
+}
@@ -0,0 +1,292 @@
+//! Load skill manifests regardless of which format a skill ships.
+//!
+//! Skills may ship the composed `skill.yaml` format or the legacy
+//! `.fgp/skill.json` format. Commands should go through this module rather
+//! than reading either file directly, so a skill with only one of them works
+//! everywhere:
+//!
+//! - [`load`] returns the canonical skill.yaml representation
+//!   (legacy manifests are converted with `skill_migrate`).
+//! - [`load_legacy`] returns the skill.json representation used by MCP
+//!   registration (skill.yaml manifests are converted back).
+//!
+//! When both files exist, `skill.yaml` wins.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skill::{
+    Author as LegacyAuthor, ClaudeExportConfig, CursorExportConfig, DaemonConfig, ExportsConfig,
+    McpExportConfig, MethodDef, SkillManifest as LegacyManifest,
+};
+use super::skill_validate::{Author, SkillManifest};
+
+/// On-disk format of a skill manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// skill.yaml / skill.yml
+    Yaml,
+    /// .fgp/skill.json
+    Json,
+}
+
+/// A skill manifest normalized to the skill.yaml schema.
+#[derive(Debug)]
+pub struct LoadedSkill {
+    /// Skill root directory (instruction paths are relative to this)
+    pub root: PathBuf,
+    /// Manifest file that was read
+    pub path: PathBuf,
+    /// Format of the manifest file
+    pub format: ManifestFormat,
+    pub manifest: SkillManifest,
+}
+
+/// Find the manifest for a skill directory or manifest file.
+///
+/// Returns `None` if a directory contains no manifest at all.
+pub fn find_manifest(path: &Path) -> Option<(PathBuf, ManifestFormat)> {
+    if path.is_file() {
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => ManifestFormat::Json,
+            _ => ManifestFormat::Yaml,
+        };
+        return Some((path.to_path_buf(), format));
+    }
+
+    [
+        (path.join("skill.yaml"), ManifestFormat::Yaml),
+        (path.join("skill.yml"), ManifestFormat::Yaml),
+        (path.join(".fgp").join("skill.json"), ManifestFormat::Json),
+        (path.join("skill.json"), ManifestFormat::Json),
+    ]
+    .into_iter()
+    .find(|(candidate, _)| candidate.exists())
+}
+
+/// Load a skill as skill.yaml, converting a legacy skill.json if necessary.
+pub fn load(path: &Path) -> Result<LoadedSkill> {
+    let (manifest_path, format) = require_manifest(path)?;
+    let content = read(&manifest_path)?;
+
+    let manifest = match format {
+        ManifestFormat::Yaml => serde_yaml::from_str(&content)
+            .with_context(|| format!("Invalid skill.yaml: {}", manifest_path.display()))?,
+        ManifestFormat::Json => {
+            let legacy: LegacyManifest = serde_json::from_str(&content)
+                .with_context(|| format!("Invalid skill.json: {}", manifest_path.display()))?;
+            let instructions = super::skill_migrate::instructions_file(&skill_root(&manifest_path));
+            super::skill_migrate::migrate(legacy, instructions).manifest
+        }
+    };
+
+    Ok(LoadedSkill {
+        root: skill_root(&manifest_path),
+        path: manifest_path,
+        format,
+        manifest,
+    })
+}
+
+/// Load a skill as skill.json, converting a skill.yaml if necessary.
+pub fn load_legacy(path: &Path) -> Result<LegacyManifest> {
+    let (manifest_path, format) = require_manifest(path)?;
+    let content = read(&manifest_path)?;
+
+    match format {
+        ManifestFormat::Json => serde_json::from_str(&content)
+            .with_context(|| format!("Invalid skill.json: {}", manifest_path.display())),
+        ManifestFormat::Yaml => {
+            let manifest: SkillManifest = serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid skill.yaml: {}", manifest_path.display()))?;
+            Ok(to_legacy(manifest))
+        }
+    }
+}
+
+/// Convert a skill.yaml manifest to the legacy skill.json structure.
+///
+/// The first required daemon becomes the skill's daemon, and its methods are
+/// listed without descriptions or params (skill.yaml doesn't carry them).
+pub fn to_legacy(manifest: SkillManifest) -> LegacyManifest {
+    let author = match manifest.author {
+        Author::String(name) => LegacyAuthor { name, email: None },
+        Author::Object { name, email, .. } => LegacyAuthor { name, email },
+    };
+
+    let primary = manifest
+        .daemons
+        .iter()
+        .find(|d| !d.optional)
+        .or_else(|| manifest.daemons.first());
+
+    let daemon = primary.map(|d| DaemonConfig {
+        name: d.name.clone(),
+        socket_path: None,
+        pid_file: None,
+        log_file: None,
+        start_command: Vec::new(),
+        stop_command: Vec::new(),
+        health_method: None,
+    });
+
+    let methods = primary
+        .map(|d| {
+            d.methods
+                .iter()
+                .map(|m| MethodDef {
+                    name: if m.contains('.') {
+                        m.clone()
+                    } else {
+                        format!("{}.{}", d.name, m)
+                    },
+                    description: None,
+                    params: Default::default(),
+                    cost: None,
+                    rate_limit: None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let trigger_keywords = manifest.triggers.map(|t| t.keywords).unwrap_or_default();
+
+    let exports = manifest.exports.map(|e| ExportsConfig {
+        mcp: e.mcp.map(|m| McpExportConfig {
+            enabled: m.enabled,
+            tools_prefix: m.tools_prefix,
+        }),
+        claude: e.claude_code.map(|c| ClaudeExportConfig {
+            enabled: c.enabled,
+            skill_name: c.skill_name,
+            triggers: if c.triggers.is_empty() {
+                trigger_keywords.clone()
+            } else {
+                c.triggers
+            },
+            tools: vec!["Bash".to_string()],
+        }),
+        cursor: e.cursor.map(|c| CursorExportConfig {
+            enabled: c.enabled,
+            server_name: None,
+        }),
+        continue_dev: None,
+        windsurf: None,
+    });
+
+    LegacyManifest {
+        name: manifest.name,
+        version: manifest.version,
+        description: manifest.description,
+        author,
+        repository: manifest.repository,
+        homepage: manifest.homepage,
+        license: manifest.license,
+        keywords: manifest.keywords,
+        category: None,
+        binary: None,
+        distribution: None,
+        daemon,
+        methods,
+        mcp_bridge: None,
+        requirements: Default::default(),
+        exports,
+    }
+}
+
+fn require_manifest(path: &Path) -> Result<(PathBuf, ManifestFormat)> {
+    match find_manifest(path) {
+        Some(found) => Ok(found),
+        None => bail!(
+            "Skill manifest not found in {} (looked for skill.yaml, skill.yml, .fgp/skill.json)",
+            path.display()
+        ),
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// The skill's root directory (the parent of `.fgp/` for the legacy layout).
+pub fn skill_root(manifest_path: &Path) -> PathBuf {
+    let parent = manifest_path.parent().unwrap_or(Path::new("."));
+    if parent.file_name().map(|n| n == ".fgp").unwrap_or(false) {
+        parent.parent().unwrap_or(Path::new(".")).to_path_buf()
+    } else {
+        parent.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILL_YAML: &str = r#"
+name: gmail
+version: 1.0.0
+description: Gmail via FGP
+author: Jane
+daemons:
+  - name: gmail
+    methods: [inbox, send]
+triggers:
+  keywords: [email]
+exports:
+  claude-code:
+    enabled: true
+"#;
+
+    const SKILL_JSON: &str = r#"{
+        "name": "gmail-gateway",
+        "version": "1.0.0",
+        "description": "Gmail via FGP",
+        "author": { "name": "Jane" },
+        "daemon": { "name": "gmail" },
+        "methods": [{ "name": "gmail.inbox" }]
+    }"#;
+
+    #[test]
+    fn test_load_accepts_either_format() {
+        let yaml_dir = tempfile::tempdir().unwrap();
+        fs::write(yaml_dir.path().join("skill.yaml"), SKILL_YAML).unwrap();
+        let loaded = load(yaml_dir.path()).unwrap();
+        assert_eq!(loaded.format, ManifestFormat::Yaml);
+        assert_eq!(loaded.manifest.daemons[0].methods, vec!["inbox", "send"]);
+
+        let json_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(json_dir.path().join(".fgp")).unwrap();
+        fs::write(json_dir.path().join(".fgp").join("skill.json"), SKILL_JSON).unwrap();
+        let loaded = load(json_dir.path()).unwrap();
+        assert_eq!(loaded.format, ManifestFormat::Json);
+        assert_eq!(loaded.root, json_dir.path());
+        assert_eq!(loaded.manifest.daemons[0].name, "gmail");
+        assert_eq!(loaded.manifest.daemons[0].methods, vec!["inbox"]);
+    }
+
+    #[test]
+    fn test_load_legacy_converts_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("skill.yaml"), SKILL_YAML).unwrap();
+
+        let legacy = load_legacy(dir.path()).unwrap();
+        assert_eq!(legacy.daemon.unwrap().name, "gmail");
+        let methods: Vec<&str> = legacy.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(methods, vec!["gmail.inbox", "gmail.send"]);
+        let claude = legacy.exports.unwrap().claude.unwrap();
+        assert_eq!(claude.triggers, vec!["email"]);
+    }
+
+    #[test]
+    fn test_yaml_preferred_over_json() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("skill.yaml"), SKILL_YAML).unwrap();
+        fs::create_dir_all(dir.path().join(".fgp")).unwrap();
+        fs::write(dir.path().join(".fgp").join("skill.json"), SKILL_JSON).unwrap();
+
+        let loaded = load(dir.path()).unwrap();
+        assert_eq!(loaded.format, ManifestFormat::Yaml);
+        assert_eq!(loaded.manifest.name, "gmail");
+    }
+}
@@ -0,0 +1,481 @@
+//! Migrate legacy skill.json manifests to the skill.yaml format.
+//!
+//! The legacy format (`skill::SkillManifest`) describes a single daemon and its
+//! methods, while skill.yaml (`skill_validate::SkillManifest`) describes a
+//! composed skill with daemon dependencies, instructions, and triggers.
+//!
+//! # Usage
+//!
+//! ```bash
+//! fgp skill migrate-manifest ./my-skill/.fgp/skill.json
+//! fgp skill migrate-manifest ./my-skill --dry-run
+//! ```
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::skill::SkillManifest as LegacyManifest;
+use super::skill_manifest::skill_root;
+use super::skill_validate::{
+    Author, ClaudeExport, CursorExport, DaemonDependency, Exports, Instructions, McpExport,
+    SkillManifest, Triggers,
+};
+
+/// Instruction files picked up as `instructions.core`, in order of preference.
+const INSTRUCTION_FILES: &[&str] = &["SKILL.md", "README.md"];
+
+/// Result of mapping a legacy manifest onto the skill.yaml schema.
+pub struct Migration {
+    pub manifest: SkillManifest,
+    /// Fields that could not be carried over (or only partially)
+    pub warnings: Vec<String>,
+}
+
+/// Convert a skill.json into a skill.yaml alongside it (or at `output`).
+pub fn migrate_manifest(
+    path: &str,
+    output: Option<&str>,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    let manifest_path = find_legacy_manifest(Path::new(path))?;
+    let skill_root = skill_root(&manifest_path);
+
+    println!(
+        "{} Migrating {}...",
+        "→".blue().bold(),
+        manifest_path.display()
+    );
+
+    let content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let legacy: LegacyManifest = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid skill.json: {}", manifest_path.display()))?;
+
+    let migration = migrate(legacy, instructions_file(&skill_root));
+    let yaml = to_yaml(&migration.manifest)?;
+
+    if !migration.warnings.is_empty() {
+        println!();
+        println!("{}", "Fields that did not map cleanly:".yellow().bold());
+        for warning in &migration.warnings {
+            println!("  {} {}", "⚠".yellow(), warning);
+        }
+    }
+
+    if dry_run {
+        println!();
+        println!("{}", "Generated skill.yaml:".bold());
+        println!("{}", yaml);
+        return Ok(());
+    }
+
+    let output_path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| skill_root.join("skill.yaml"));
+
+    if output_path.exists() && !force {
+        bail!(
+            "{} already exists. Use --force to overwrite.",
+            output_path.display()
+        );
+    }
+
+    fs::write(&output_path, &yaml)
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+
+    println!();
+    println!("{} Wrote {}", "✓".green().bold(), output_path.display());
+    println!();
+
+    // Validate from the skill directory so instruction paths resolve
+    let validate_path = if output_path.ends_with("skill.yaml") {
+        output_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf()
+    } else {
+        output_path.clone()
+    };
+
+    super::skill_validate::validate(&validate_path.to_string_lossy(), false, false)
+}
+
+/// The instruction file a legacy skill ships alongside its manifest, if any.
+pub fn instructions_file(skill_root: &Path) -> Option<String> {
+    INSTRUCTION_FILES
+        .iter()
+        .find(|f| skill_root.join(f).exists())
+        .map(|f| f.to_string())
+}
+
+/// Map a legacy manifest onto the skill.yaml schema.
+pub fn migrate(legacy: LegacyManifest, instructions_file: Option<String>) -> Migration {
+    let mut warnings = Vec::new();
+
+    let daemon_name = legacy
+        .daemon
+        .as_ref()
+        .map(|d| d.name.clone())
+        .unwrap_or_else(|| legacy.name.replace("-gateway", ""));
+
+    if let Some(ref daemon) = legacy.daemon {
+        let runtime_fields: Vec<&str> = [
+            ("socket_path", daemon.socket_path.is_some()),
+            ("pid_file", daemon.pid_file.is_some()),
+            ("log_file", daemon.log_file.is_some()),
+            ("start_command", !daemon.start_command.is_empty()),
+            ("stop_command", !daemon.stop_command.is_empty()),
+            ("health_method", daemon.health_method.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(field, _)| field)
+        .collect();
+
+        if !runtime_fields.is_empty() {
+            warnings.push(format!(
+                "daemon.{{{}}} belong in the daemon's manifest.json, not the skill",
+                runtime_fields.join(", ")
+            ));
+        }
+    }
+
+    let daemons = if legacy.daemon.is_some() || !legacy.methods.is_empty() {
+        if legacy
+            .methods
+            .iter()
+            .any(|m| m.description.is_some() || !m.params.is_empty())
+        {
+            warnings.push(
+                "methods: descriptions and params are dropped (only method names are kept)"
+                    .to_string(),
+            );
+        }
+
+        vec![DaemonDependency {
+            name: daemon_name.clone(),
+            version: None,
+            optional: false,
+            // skill.yaml lists methods without the daemon namespace
+            methods: legacy
+                .methods
+                .iter()
+                .map(|m| {
+                    m.name
+                        .strip_prefix(&format!("{}.", daemon_name))
+                        .unwrap_or(&m.name)
+                        .to_string()
+                })
+                .collect(),
+            examples: Default::default(),
+        }]
+    } else {
+        Vec::new()
+    };
+
+    // Legacy Claude triggers become skill-level trigger keywords
+    let claude_triggers = legacy
+        .exports
+        .as_ref()
+        .and_then(|e| e.claude.as_ref())
+        .map(|c| c.triggers.clone())
+        .unwrap_or_default();
+
+    let triggers = (!claude_triggers.is_empty()).then(|| Triggers {
+        keywords: claude_triggers.clone(),
+        patterns: Vec::new(),
+        commands: Vec::new(),
+    });
+
+    let exports = migrate_exports(&legacy, claude_triggers, &mut warnings);
+
+    if let Some(ref category) = legacy.category {
+        warnings.push(format!(
+            "category '{}' has no skill.yaml equivalent (added to keywords)",
+            category
+        ));
+    }
+    if legacy.binary.is_some() {
+        warnings.push("binary: build configuration belongs in the daemon package".to_string());
+    }
+    if legacy.distribution.is_some() {
+        warnings.push("distribution: prebuilt/homebrew sources are not carried over".to_string());
+    }
+    if !legacy.requirements.is_empty() {
+        let mut names: Vec<&String> = legacy.requirements.keys().collect();
+        names.sort();
+        warnings.push(format!(
+            "requirements ({}) have no skill.yaml equivalent",
+            names
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let mut keywords = legacy.keywords;
+    if let Some(category) = legacy.category {
+        if !keywords.contains(&category) {
+            keywords.push(category);
+        }
+    }
+
+    let manifest = SkillManifest {
+        name: legacy.name,
+        version: legacy.version,
+        description: legacy.description,
+        author: Author::Object {
+            name: legacy.author.name,
+            email: legacy.author.email,
+            url: None,
+        },
+        license: legacy.license,
+        repository: legacy.repository,
+        homepage: legacy.homepage,
+        keywords,
+        daemons,
+        instructions: instructions_file.map(|file| Instructions {
+            core: Some(file),
+            claude_code: None,
+            cursor: None,
+            codex: None,
+            windsurf: None,
+            mcp: None,
+            zed: None,
+        }),
+        triggers,
+        workflows: Default::default(),
+        config: Default::default(),
+        auth: None,
+        permissions: None,
+        exports,
+    };
+
+    Migration { manifest, warnings }
+}
+
+/// Map the legacy `exports` and `mcp_bridge` sections.
+fn migrate_exports(
+    legacy: &LegacyManifest,
+    claude_triggers: Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Option<Exports> {
+    let legacy_exports = legacy.exports.as_ref();
+
+    let claude_code = legacy_exports.and_then(|e| e.claude.as_ref()).map(|c| {
+        if c.tools != ["Bash"] {
+            warnings.push(format!(
+                "exports.claude.tools ({}) are not configurable in skill.yaml",
+                c.tools.join(", ")
+            ));
+        }
+        ClaudeExport {
+            enabled: c.enabled,
+            skill_name: c.skill_name.clone(),
+            triggers: claude_triggers,
+        }
+    });
+
+    let cursor = legacy_exports.and_then(|e| e.cursor.as_ref()).map(|c| {
+        if c.server_name.is_some() {
+            warnings.push(
+                "exports.cursor.server_name has no equivalent (skill.yaml exports rules files)"
+                    .to_string(),
+            );
+        }
+        CursorExport {
+            enabled: c.enabled,
+            rules_file: None,
+        }
+    });
+
+    let mcp = match (
+        legacy_exports.and_then(|e| e.mcp.as_ref()),
+        &legacy.mcp_bridge,
+    ) {
+        (Some(m), _) => Some(McpExport {
+            enabled: m.enabled,
+            tools_prefix: m.tools_prefix.clone(),
+        }),
+        (None, Some(bridge)) => {
+            if bridge.command.is_some() || !bridge.args.is_empty() {
+                warnings.push(
+                    "mcp_bridge.command/args are dropped (the MCP bridge is provided by fgp)"
+                        .to_string(),
+                );
+            }
+            Some(McpExport {
+                enabled: bridge.enabled,
+                tools_prefix: bridge.tools_prefix.clone(),
+            })
+        }
+        (None, None) => None,
+    };
+
+    if legacy_exports
+        .and_then(|e| e.continue_dev.as_ref())
+        .is_some()
+    {
+        warnings.push("exports.continue_dev has no skill.yaml equivalent".to_string());
+    }
+    if legacy_exports.and_then(|e| e.windsurf.as_ref()).is_some() {
+        warnings.push(
+            "exports.windsurf has no skill.yaml equivalent (use instructions.windsurf)".to_string(),
+        );
+    }
+
+    if claude_code.is_none() && cursor.is_none() && mcp.is_none() {
+        return None;
+    }
+
+    Some(Exports {
+        claude_code,
+        cursor,
+        mcp,
+    })
+}
+
+/// Serialize a manifest to YAML, omitting empty and unset fields.
+fn to_yaml(manifest: &SkillManifest) -> Result<String> {
+    let mut value = serde_yaml::to_value(manifest)?;
+    prune(&mut value);
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Remove nulls and empty collections so the output reads like a hand-written manifest.
+fn prune(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            for (_, v) in map.iter_mut() {
+                prune(v);
+            }
+            map.retain(|_, v| !is_empty(v));
+        }
+        serde_yaml::Value::Sequence(seq) => seq.iter_mut().for_each(prune),
+        _ => {}
+    }
+}
+
+fn is_empty(value: &serde_yaml::Value) -> bool {
+    match value {
+        serde_yaml::Value::Null => true,
+        serde_yaml::Value::Mapping(map) => map.is_empty(),
+        serde_yaml::Value::Sequence(seq) => seq.is_empty(),
+        _ => false,
+    }
+}
+
+/// Resolve a path argument to a skill.json file.
+fn find_legacy_manifest(path: &Path) -> Result<PathBuf> {
+    if !path.exists() {
+        bail!("Path not found: {}", path.display());
+    }
+
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    for candidate in [
+        path.join(".fgp").join("skill.json"),
+        path.join("skill.json"),
+    ] {
+        if candidate.exists() {
+            return Ok(candidate);
+        }
+    }
+
+    bail!(
+        "No skill.json found in {} (looked in .fgp/skill.json and skill.json)",
+        path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy(json: serde_json::Value) -> LegacyManifest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_maps_daemon_methods_and_exports() {
+        let manifest = legacy(serde_json::json!({
+            "name": "gmail-gateway",
+            "version": "1.2.0",
+            "description": "Gmail via FGP",
+            "author": { "name": "Jane", "email": "jane@example.com" },
+            "keywords": ["email"],
+            "daemon": { "name": "gmail" },
+            "methods": [{ "name": "gmail.inbox" }, { "name": "gmail.send" }],
+            "exports": {
+                "claude": { "enabled": true, "triggers": ["check email"] },
+                "mcp": { "tools_prefix": "gmail" }
+            }
+        }));
+
+        let migration = migrate(manifest, Some("SKILL.md".to_string()));
+        let m = migration.manifest;
+
+        assert!(migration.warnings.is_empty(), "{:?}", migration.warnings);
+        assert_eq!(m.daemons.len(), 1);
+        assert_eq!(m.daemons[0].name, "gmail");
+        assert_eq!(m.daemons[0].methods, vec!["inbox", "send"]);
+        assert_eq!(m.triggers.unwrap().keywords, vec!["check email"]);
+        let exports = m.exports.unwrap();
+        assert_eq!(exports.claude_code.unwrap().triggers, vec!["check email"]);
+        assert_eq!(exports.mcp.unwrap().tools_prefix.as_deref(), Some("gmail"));
+        assert_eq!(m.instructions.unwrap().core.as_deref(), Some("SKILL.md"));
+    }
+
+    #[test]
+    fn test_migrate_flags_unmapped_fields() {
+        let manifest = legacy(serde_json::json!({
+            "name": "browser-gateway",
+            "version": "0.1.0",
+            "description": "Browser automation",
+            "author": { "name": "Jane" },
+            "category": "automation",
+            "daemon": { "name": "browser", "start_command": ["browser-gateway", "start"] },
+            "methods": [{ "name": "browser.open", "params": { "url": { "type": "string" } } }],
+            "requirements": { "chrome": { "type": "binary" } },
+            "exports": { "windsurf": { "enabled": true } }
+        }));
+
+        let migration = migrate(manifest, None);
+        let joined = migration.warnings.join("\n");
+
+        assert!(joined.contains("start_command"));
+        assert!(joined.contains("params are dropped"));
+        assert!(joined.contains("requirements (chrome)"));
+        assert!(joined.contains("exports.windsurf"));
+        assert!(joined.contains("category 'automation'"));
+        assert!(migration
+            .manifest
+            .keywords
+            .contains(&"automation".to_string()));
+    }
+
+    #[test]
+    fn test_yaml_output_omits_empty_fields() {
+        let manifest = legacy(serde_json::json!({
+            "name": "tiny",
+            "version": "0.1.0",
+            "description": "A tiny skill for testing",
+            "author": { "name": "Jane" }
+        }));
+
+        let yaml = to_yaml(&migrate(manifest, None).manifest).unwrap();
+
+        assert!(!yaml.contains("null"));
+        assert!(!yaml.contains("workflows"));
+        assert!(yaml.contains("name: tiny"));
+        let reparsed: SkillManifest = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(reparsed.name, "tiny");
+    }
+}
@@ -3,6 +3,12 @@
 //! Taps are Git repositories containing skill.yaml packages.
 //! Similar to Homebrew taps, they enable community distribution.
 //!
+//! A tap is added as `owner/repo` on GitHub, or as any git URL, such as
+//! `git@github.com:acme/skills.git` for a private repo or
+//! `https://gitlab.internal/acme/skills.git` for another host. Cloning and
+//! pulling go through git, so access uses the user's SSH keys and credential
+//! helpers.
+//!
 //! # Directory Structure
 //!
 //! ```text
@@ -18,19 +24,39 @@
 //!         │       │   └── email-triage/
 //!         │       │       └── skill.yaml
 //!         │       └── tap.yaml          # Tap metadata
-//!         └── user/
-//!             └── my-skills/
+//!         ├── user/
+//!         │   └── my-skills/
+//!         └── gitlab.internal/          # Taps from other hosts
+//!             └── acme/
+//!                 └── skills/
 //! ```
-
-use anyhow::{bail, Result};
+//!
+//! The name and version of each skill in a tap are cached in
+//! `~/.fgp/skills/taps/<tap>/.index.json`, so `fgp skill tap update` can
+//! report what a pull added, removed or upgraded.
+//!
+//! # Stale taps
+//!
+//! Each tap records when it was last fetched. `fgp skill tap list` shows how
+//! long ago that was, search results say how old the taps they came from
+//! are, and search and install suggest `fgp skill tap update` once a tap is
+//! older than `[taps] stale_after_days` in ~/.fgp/config.toml (14 by
+//! default). With `[taps] auto_update = true`, or `fgp skill search
+//! --auto-update`, stale taps are fetched before searching, for at most a
+//! few seconds; offline, they are searched as they are.
+
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
+use super::network::Network;
 use super::skill_validate::SkillManifest;
+use super::{fgp_home, parse_version, skill, text};
 
 /// Tap configuration stored in taps.json
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,9 +77,9 @@ impl Default for TapsConfig {
 /// Individual tap entry
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TapEntry {
-    /// GitHub owner/repo format
+    /// GitHub owner/repo format, or host/path for other hosts
     pub repo: String,
-    /// Full GitHub URL
+    /// URL the tap was cloned from, as given to `fgp skill tap add`
     pub url: String,
     /// Local path to cloned repo
     pub path: String,
@@ -61,10 +87,32 @@ pub struct TapEntry {
     pub added_at: String,
     /// Last update timestamp
     pub updated_at: Option<String>,
+    /// Last successful fetch, whether or not it brought changes
+    #[serde(default)]
+    pub fetched_at: Option<String>,
     /// Number of skills in this tap
     pub skill_count: usize,
 }
 
+impl TapEntry {
+    /// When the tap was last fetched; for taps recorded before fetches
+    /// were, when it was last updated or else added.
+    fn last_fetched(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.fetched_at
+            .as_deref()
+            .or(self.updated_at.as_deref())
+            .or(Some(&self.added_at))
+            .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+            .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+    }
+
+    /// Whole days between the last fetch and `now`.
+    fn age_days(&self, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+        self.last_fetched()
+            .map(|fetched| now.signed_duration_since(fetched).num_days().max(0))
+    }
+}
+
 /// Tap metadata (tap.yaml in the repo root)
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(dead_code)]
@@ -80,10 +128,7 @@ pub struct TapMetadata {
 
 /// Get the taps directory
 fn taps_dir() -> PathBuf {
-    dirs::home_dir()
-        .expect("Could not find home directory")
-        .join(".fgp")
-        .join("taps")
+    fgp_home().join("taps")
 }
 
 /// Get the taps config file path
@@ -120,11 +165,20 @@ fn repo_to_tap_name(repo: &str) -> String {
     repo.replace('/', "-")
 }
 
-/// Add a new tap
-pub fn add(repo: &str) -> Result<()> {
+/// Add a new tap, named `name` or after the repository
+pub fn add(repo: &str, name: Option<&str>, network: Network) -> Result<()> {
     // Parse repo format (owner/repo or full URL)
-    let (owner, repo_name, url) = parse_repo_input(repo)?;
-    let tap_name = format!("{}-{}", owner, repo_name);
+    let source = TapSource::parse(repo)?;
+    network.require_repo(&source.url, &format!("Cloning {}", source.url))?;
+    let tap_name = match name {
+        Some(name) => {
+            if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+                bail!("Invalid tap name '{}'", name);
+            }
+            name.to_string()
+        }
+        None => source.name.clone(),
+    };
 
     println!("{} Adding tap {}...", "→".blue().bold(), tap_name.cyan());
 
@@ -140,35 +194,48 @@ pub fn add(repo: &str) -> Result<()> {
     }
 
     // Create directory structure
-    let tap_path = repos_dir().join(&owner).join(&repo_name);
+    let tap_path = repos_dir().join(&source.repo);
+    if tap_path.exists() {
+        bail!(
+            "{} is already cloned at {} (by another tap?)",
+            source.url,
+            tap_path.display()
+        );
+    }
     fs::create_dir_all(tap_path.parent().unwrap())?;
 
-    // Clone the repository
-    println!("  Cloning {}...", url);
-    let status = Command::new("git")
-        .args(["clone", "--depth", "1", &url])
+    // Clone the repository. Password and passphrase prompts go to the
+    // terminal, so only git's error messages are captured.
+    println!("  Cloning {}...", source.url);
+    let output = Command::new("git")
+        .args(["clone", "--depth", "1", &source.url])
         .arg(&tap_path)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::inherit())
-        .status()?;
+        .stdin(std::process::Stdio::inherit())
+        .output()?;
 
-    if !status.success() {
-        bail!("Failed to clone repository: {}", url);
+    if !output.status.success() {
+        prune_empty_parents(&tap_path);
+        bail!(
+            "clone failed: {}",
+            git_failure(&String::from_utf8_lossy(&output.stderr), &source.url)
+        );
     }
 
     // Count skills in the tap
     let skill_count = count_skills(&tap_path)?;
+    save_index(&tap_name, &scan_index(&tap_path))?;
 
     // Add to config
     let now = chrono::Utc::now().to_rfc3339();
     config.taps.insert(
         tap_name.clone(),
         TapEntry {
-            repo: format!("{}/{}", owner, repo_name),
-            url: url.clone(),
+            repo: source.repo,
+            url: source.url,
             path: tap_path.to_string_lossy().to_string(),
             added_at: now.clone(),
-            updated_at: Some(now),
+            updated_at: Some(now.clone()),
+            fetched_at: Some(now),
             skill_count,
         },
     );
@@ -209,11 +276,11 @@ pub fn remove(name: &str) -> Result<()> {
         fs::remove_dir_all(&tap_path)?;
 
         // Clean up empty parent directories
-        if let Some(parent) = tap_path.parent() {
-            if parent.read_dir()?.next().is_none() {
-                let _ = fs::remove_dir(parent);
-            }
-        }
+        prune_empty_parents(&tap_path);
+    }
+
+    if let Some(index_dir) = index_path(&tap_name).parent() {
+        let _ = fs::remove_dir_all(index_dir);
     }
 
     // Remove from config
@@ -243,12 +310,28 @@ pub fn list() -> Result<()> {
     println!("{}", "Configured Taps".bold());
     println!();
 
+    let stale_after_days = crate::config::load()
+        .unwrap_or_default()
+        .taps
+        .stale_after_days;
+    let now = chrono::Utc::now();
+
     for (name, entry) in &config.taps {
         let updated = entry
             .updated_at
             .as_ref()
             .map(|s| format_relative_time(s))
             .unwrap_or_else(|| "never".to_string());
+        let age = entry.age_days(now);
+        let fetched = match age {
+            Some(days) => format_age(days),
+            None => "never".to_string(),
+        };
+        let fetched = if is_stale(age, stale_after_days) {
+            format!("{} {}", fetched.yellow(), "(stale)".yellow())
+        } else {
+            fetched
+        };
 
         println!(
             "  {} {}",
@@ -257,13 +340,14 @@ pub fn list() -> Result<()> {
         );
         println!("    {} {}", "repo:".dimmed(), entry.repo);
         println!("    {} {}", "updated:".dimmed(), updated);
+        println!("    {} {}", "fetched:".dimmed(), fetched);
     }
 
     Ok(())
 }
 
-/// Update all taps (git pull)
-pub fn update() -> Result<()> {
+/// Update all taps (git pull); offline, only taps cloned from this machine
+pub fn update(network: Network) -> Result<()> {
     let mut config = load_taps_config()?;
 
     if config.taps.is_empty() {
@@ -274,6 +358,9 @@ pub fn update() -> Result<()> {
     println!("{}", "Updating taps...".bold());
     println!();
 
+    // Installed skills a tap now has a newer version of
+    let mut upgradable = Vec::new();
+
     for (name, entry) in config.taps.iter_mut() {
         let tap_path = PathBuf::from(&entry.path);
 
@@ -282,40 +369,82 @@ pub fn update() -> Result<()> {
                 "  {} {} (path missing, re-add with 'fgp skill tap add {}')",
                 "✗".red(),
                 name,
-                entry.repo
+                entry.url
             );
             continue;
         }
 
         print!("  {} {}... ", "→".blue(), name);
+        if !network.reaches(&entry.url) {
+            println!("{}", "skipped (offline)".dimmed());
+            continue;
+        }
+        let before = load_index(name).unwrap_or_else(|| scan_index(&tap_path));
 
+        // From the URL the tap was added with; git prompts can't be
+        // answered mid-line, so credentials must come from a helper or agent
         let output = Command::new("git")
-            .args(["pull", "--ff-only"])
+            .args(["pull", "--ff-only", &entry.url, "HEAD"])
+            .env("GIT_TERMINAL_PROMPT", "0")
             .current_dir(&tap_path)
             .output()?;
 
         if output.status.success() {
+            entry.fetched_at = Some(chrono::Utc::now().to_rfc3339());
+            let after = scan_index(&tap_path);
+            save_index(name, &after)?;
+            let changes = IndexChanges::between(&before, &after);
+
             let stdout = String::from_utf8_lossy(&output.stdout);
-            if stdout.contains("Already up to date") {
+            if stdout.contains("Already up to date") && changes.is_empty() {
                 println!("{}", "up to date".dimmed());
             } else {
                 // Recount skills
                 let skill_count = count_skills(&tap_path)?;
                 entry.skill_count = skill_count;
                 entry.updated_at = Some(chrono::Utc::now().to_rfc3339());
-                println!("{} ({} skills)", "updated".green(), skill_count);
+                if changes.is_empty() {
+                    println!("{} ({} skills)", "updated".green(), skill_count);
+                } else {
+                    println!("{}: {}", "updated".green(), changes);
+                }
             }
-        } else {
-            println!("{}", "failed".red());
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            if !stderr.is_empty() {
-                println!("    {}", stderr.trim().dimmed());
+
+            for (skill_name, version) in &after {
+                if let Ok(Some((origin, installed))) = skill::find_installed(skill_name) {
+                    if origin == *name && parse_version(version) > parse_version(&installed.version)
+                    {
+                        upgradable.push((skill_name.clone(), installed.version, version.clone()));
+                    }
+                }
             }
+        } else {
+            println!(
+                "{}: {}",
+                "failed".red(),
+                git_failure(&String::from_utf8_lossy(&output.stderr), &entry.url)
+            );
         }
     }
 
     save_taps_config(&config)?;
 
+    if !upgradable.is_empty() {
+        upgradable.sort();
+        println!();
+        println!("{}", "Installed skills with a newer version:".bold());
+        for (skill_name, installed, latest) in &upgradable {
+            println!(
+                "  {} {} → {}",
+                skill_name.cyan(),
+                installed.dimmed(),
+                latest.green()
+            );
+        }
+        println!();
+        println!("Run {} to install them.", "fgp skill upgrade".cyan());
+    }
+
     Ok(())
 }
 
@@ -329,12 +458,13 @@ pub fn show(name: &str) -> Result<()> {
     if !tap_path.exists() {
         bail!(
             "Tap directory not found. Re-add with 'fgp skill tap add {}'",
-            entry.repo
+            entry.url
         );
     }
 
     println!("{} {}", "Tap:".bold(), tap_name.cyan());
     println!("  {} {}", "repo:".dimmed(), entry.repo);
+    println!("  {} {}", "url:".dimmed(), entry.url);
     println!("  {} {}", "path:".dimmed(), entry.path);
     println!();
     println!("{}:", "Skills".bold());
@@ -348,58 +478,130 @@ pub fn show(name: &str) -> Result<()> {
 // Helper functions
 // ============================================================================
 
-/// Parse repo input (owner/repo or full URL)
-fn parse_repo_input(input: &str) -> Result<(String, String, String)> {
-    let input = input.trim();
-
-    // Handle full GitHub URL
-    if input.starts_with("https://") || input.starts_with("git@") {
-        let cleaned = input.trim_end_matches('/').trim_end_matches(".git");
-
-        // Extract owner/repo from URL
-        let parts: Vec<&str> = if cleaned.contains("github.com/") {
-            cleaned
-                .split("github.com/")
-                .last()
-                .unwrap_or("")
-                .split('/')
-                .collect()
-        } else if cleaned.contains("github.com:") {
-            cleaned
-                .split("github.com:")
-                .last()
-                .unwrap_or("")
-                .split('/')
-                .collect()
+/// Where a tap comes from
+#[derive(Debug, PartialEq)]
+struct TapSource {
+    /// `owner/repo` on GitHub, `host/path` elsewhere; also where it's cloned
+    /// under the repos directory
+    repo: String,
+    /// URL to clone and pull
+    url: String,
+    /// Tap name unless `--name` is given
+    name: String,
+}
+
+impl TapSource {
+    /// Parse `owner/repo` (on GitHub) or a git URL: `https://`, `ssh://`,
+    /// `git://`, `file://` or scp-like `user@host:path`. URLs are kept as
+    /// given, so private repos clone over SSH when the URL says so.
+    fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+
+        let (host, path) = if let Some((scheme, rest)) = input.split_once("://") {
+            if !matches!(scheme, "https" | "http" | "ssh" | "git" | "file") {
+                bail!("Unsupported URL scheme '{}' in {}", scheme, input);
+            }
+            let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+            // Drop user@ and :port
+            let host = authority.rsplit('@').next().unwrap_or(authority);
+            (host.split(':').next().unwrap_or(host).to_string(), path)
+        } else if let Some((user_host, path)) =
+            input.split_once(':').filter(|(h, _)| h.contains('@'))
+        {
+            let host = user_host.rsplit('@').next().unwrap_or(user_host);
+            (host.to_string(), path)
         } else {
-            bail!("Could not parse GitHub URL: {}", input);
+            // Handle owner/repo format
+            let parts: Vec<&str> = input.split('/').collect();
+            if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+                bail!(
+                    "Invalid tap format '{}'. Use 'owner/repo' format (e.g., 'fast-gateway-protocol/official-skills') or a git URL",
+                    input
+                );
+            }
+            return Ok(Self {
+                repo: input.to_string(),
+                url: format!("https://github.com/{}.git", input),
+                name: format!("{}-{}", parts[0], parts[1]),
+            });
         };
 
-        if parts.len() < 2 {
-            bail!("Invalid GitHub URL format: {}", input);
+        let segments: Vec<&str> = path
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        let Some(last) = segments.last() else {
+            bail!("No repository path in URL: {}", input);
+        };
+        if segments.iter().any(|s| *s == "." || *s == "..") {
+            bail!("Invalid repository path in URL: {}", input);
         }
 
-        let owner = parts[0].to_string();
-        let repo = parts[1].to_string();
-        let url = format!("https://github.com/{}/{}.git", owner, repo);
-
-        return Ok((owner, repo, url));
-    }
-
-    // Handle owner/repo format
-    let parts: Vec<&str> = input.split('/').collect();
-    if parts.len() != 2 {
-        bail!(
-            "Invalid tap format '{}'. Use 'owner/repo' format (e.g., 'fast-gateway-protocol/official-skills')",
-            input
-        );
+        let repo = if host == "github.com" && segments.len() == 2 {
+            segments.join("/")
+        } else if host.is_empty() {
+            // file:///path/to/repo
+            format!("local/{}", segments.join("/"))
+        } else {
+            format!("{}/{}", host, segments.join("/"))
+        };
+        Ok(Self {
+            repo,
+            url: input.to_string(),
+            name: last.to_string(),
+        })
     }
+}
 
-    let owner = parts[0].to_string();
-    let repo = parts[1].to_string();
-    let url = format!("https://github.com/{}/{}.git", owner, repo);
+/// `owner/repo` for a GitHub URL or `owner/repo` input.
+pub fn repo_slug(input: &str) -> Option<String> {
+    TapSource::parse(input)
+        .ok()
+        .filter(|source| source.url.contains("github.com"))
+        .map(|source| source.repo)
+        .filter(|repo| repo.matches('/').count() == 1)
+}
 
-    Ok((owner, repo, url))
+/// What went wrong with a `git clone` or `git pull`, and what to check,
+/// from git's error output.
+fn git_failure(stderr: &str, url: &str) -> String {
+    let lower = stderr.to_lowercase();
+    let ssh = !url.contains("://") || url.starts_with("ssh://");
+
+    if lower.contains("host key verification failed") {
+        "host key verification failed — connect once with ssh to accept the host key".to_string()
+    } else if lower.contains("permission denied") {
+        "permission denied — check SSH keys (is the key added to your account and ssh-agent?)"
+            .to_string()
+    } else if lower.contains("authentication failed")
+        || lower.contains("could not read username")
+        || lower.contains("terminal prompts disabled")
+        || lower.contains("403")
+    {
+        if ssh {
+            "authentication failed — check SSH keys".to_string()
+        } else {
+            "authentication failed — set up a git credential helper or token for this host"
+                .to_string()
+        }
+    } else if lower.contains("repository not found")
+        || lower.contains("does not appear to be a git repository")
+        || lower.contains("not found")
+    {
+        "repository not found — check the URL, and your access if the repo is private".to_string()
+    } else if lower.contains("could not resolve host") {
+        "could not resolve host — check the URL and your network or VPN".to_string()
+    } else {
+        // Git's last line is usually the most specific
+        stderr
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .map(|line| line.trim_start_matches("fatal: ").to_string())
+            .unwrap_or_else(|| "git exited with an error".to_string())
+    }
 }
 
 /// Find tap name with partial matching
@@ -436,6 +638,176 @@ fn find_tap_name(config: &TapsConfig, partial: &str) -> Result<String> {
     }
 }
 
+/// The URL the tap `name` was cloned from, if it is configured.
+pub fn tap_url(name: &str) -> Option<String> {
+    load_taps_config()
+        .ok()
+        .and_then(|mut config| config.taps.remove(name))
+        .map(|tap| tap.url)
+}
+
+/// Whether a tap for `owner/repo` is configured.
+pub fn is_tapped(repo: &str) -> bool {
+    load_taps_config()
+        .map(|config| {
+            config
+                .taps
+                .values()
+                .any(|tap| tap.repo.eq_ignore_ascii_case(repo))
+        })
+        .unwrap_or(false)
+}
+
+/// Whether a cloned repository is laid out as a tap.
+pub fn has_tap_layout(path: &Path) -> bool {
+    path.join("tap.yaml").exists() || count_skills(path).unwrap_or(0) > 0
+}
+
+// ============================================================================
+// Tap index
+// ============================================================================
+
+/// Versions of the skills in a tap, by name.
+type TapIndex = BTreeMap<String, String>;
+
+/// Where the index of a tap's skills is cached.
+fn index_path(tap_name: &str) -> PathBuf {
+    fgp_home()
+        .join("skills")
+        .join("taps")
+        .join(tap_name)
+        .join(".index.json")
+}
+
+/// The cached index of a tap, if there is one.
+fn load_index(tap_name: &str) -> Option<TapIndex> {
+    let content = fs::read_to_string(index_path(tap_name)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_index(tap_name: &str, index: &TapIndex) -> Result<()> {
+    let path = index_path(tap_name);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(index)?)?;
+    Ok(())
+}
+
+/// Read the name and version of every skill in a cloned tap.
+fn scan_index(tap_path: &Path) -> TapIndex {
+    /// Just the fields the index needs, so a skill.yaml that fails full
+    /// validation is still listed
+    #[derive(Deserialize)]
+    struct Entry {
+        name: String,
+        version: String,
+    }
+
+    let skills_dir = tap_path.join("skills");
+    let search_dir = if skills_dir.exists() {
+        skills_dir
+    } else {
+        tap_path.to_path_buf()
+    };
+
+    let mut index = TapIndex::new();
+    for entry in fs::read_dir(&search_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let manifest = ["skill.yaml", "skill.yml"]
+            .iter()
+            .map(|file| path.join(file))
+            .find(|file| file.exists());
+        let Some(content) = manifest.and_then(|file| fs::read_to_string(file).ok()) else {
+            continue;
+        };
+        if let Ok(entry) = serde_yaml::from_str::<Entry>(&content) {
+            index.insert(entry.name, entry.version);
+        }
+    }
+    index
+}
+
+/// How a tap's skills changed between two indexes.
+#[derive(Debug, Default, PartialEq)]
+struct IndexChanges {
+    added: Vec<String>,
+    removed: Vec<String>,
+    /// `(name, old version, new version)`
+    updated: Vec<(String, String, String)>,
+}
+
+impl IndexChanges {
+    fn between(before: &TapIndex, after: &TapIndex) -> Self {
+        let mut changes = Self::default();
+        for (name, version) in after {
+            match before.get(name) {
+                None => changes.added.push(name.clone()),
+                Some(old) if old != version => {
+                    changes
+                        .updated
+                        .push((name.clone(), old.clone(), version.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = before
+            .keys()
+            .filter(|name| !after.contains_key(*name))
+            .cloned()
+            .collect();
+        changes
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// `+2 new (a, b), 1 updated (c 1.2.0 → 1.3.0), 1 removed (d)`
+impl std::fmt::Display for IndexChanges {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            parts.push(format!(
+                "+{} new ({})",
+                self.added.len(),
+                self.added.join(", ")
+            ));
+        }
+        if !self.updated.is_empty() {
+            let updated: Vec<String> = self
+                .updated
+                .iter()
+                .map(|(name, old, new)| format!("{} {} → {}", name, old, new))
+                .collect();
+            parts.push(format!(
+                "{} updated ({})",
+                self.updated.len(),
+                updated.join(", ")
+            ));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!(
+                "{} removed ({})",
+                self.removed.len(),
+                self.removed.join(", ")
+            ));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Remove the empty directories between a tap's clone and the repos directory.
+fn prune_empty_parents(tap_path: &Path) {
+    let repos = repos_dir();
+    let mut parent = tap_path.parent();
+    while let Some(dir) = parent.filter(|dir| *dir != repos && dir.starts_with(&repos)) {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+}
+
 /// Count skills in a tap directory
 fn count_skills(tap_path: &Path) -> Result<usize> {
     let skills_dir = tap_path.join("skills");
@@ -552,6 +924,260 @@ fn format_relative_time(timestamp: &str) -> String {
     }
 }
 
+// ============================================================================
+// Staleness
+// ============================================================================
+
+/// How long `fgp skill search` waits for stale taps to be fetched before it
+/// searches them as they are.
+const AUTO_UPDATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether a tap last fetched `age` days ago (`None` if unknown) is stale;
+/// with `stale_after_days` at 0, none are.
+fn is_stale(age: Option<i64>, stale_after_days: u32) -> bool {
+    match age {
+        _ if stale_after_days == 0 => false,
+        Some(days) => days >= i64::from(stale_after_days),
+        None => true,
+    }
+}
+
+/// `today`, `1 day ago`, `23 days ago`
+fn format_age(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "1 day ago".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
+/// The stale taps among `taps` (all of them if `None`) with their age in
+/// days, oldest first.
+fn stale_taps(
+    config: &TapsConfig,
+    taps: Option<&[String]>,
+    stale_after_days: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<(String, Option<i64>)> {
+    let mut stale: Vec<(String, Option<i64>)> = config
+        .taps
+        .iter()
+        .filter(|(name, _)| match taps {
+            Some(taps) => taps.contains(name),
+            None => true,
+        })
+        .map(|(name, entry)| (name.clone(), entry.age_days(now)))
+        .filter(|(_, age)| is_stale(*age, stale_after_days))
+        .collect();
+    stale.sort_by(|a, b| {
+        let age = |tap: &(String, Option<i64>)| tap.1.unwrap_or(i64::MAX);
+        age(b).cmp(&age(a)).then_with(|| a.0.cmp(&b.0))
+    });
+    stale
+}
+
+/// How old the taps behind search results are: the age of the one fetched
+/// longest ago.
+fn results_age(
+    config: &TapsConfig,
+    taps: &[String],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<String> {
+    let oldest = taps
+        .iter()
+        .filter_map(|name| config.taps.get(name))
+        .filter_map(|entry| entry.age_days(now))
+        .max()?;
+    let from = match taps {
+        [tap] => format!("tap {},", tap),
+        _ => "taps".to_string(),
+    };
+    Some(format!(
+        "Results from {} last updated {}.",
+        from,
+        format_age(oldest)
+    ))
+}
+
+/// Footer for search results that came from `taps`, such as "Results from
+/// taps last updated 23 days ago."
+pub fn search_footer(taps: &[String]) -> Option<String> {
+    let config = load_taps_config().ok()?;
+    results_age(&config, taps, chrono::Utc::now())
+}
+
+/// Suggest `fgp skill tap update` when any of `taps` (all of them if
+/// `None`) hasn't been fetched in `[taps] stale_after_days` days.
+pub fn nudge_if_stale(taps: Option<&[String]>) {
+    let Ok(config) = load_taps_config() else {
+        return;
+    };
+    let stale_after_days = crate::config::load()
+        .unwrap_or_default()
+        .taps
+        .stale_after_days;
+    let stale = stale_taps(&config, taps, stale_after_days, chrono::Utc::now());
+    if stale.is_empty() {
+        return;
+    }
+
+    let names: Vec<String> = stale
+        .iter()
+        .map(|(name, age)| match age {
+            Some(days) => format!("{} ({})", name, format_age(*days)),
+            None => name.clone(),
+        })
+        .collect();
+    println!(
+        "{} {} not updated in {}+ days: {}. Run {} for the latest skills.",
+        "!".yellow(),
+        if stale.len() == 1 { "Tap" } else { "Taps" },
+        stale_after_days,
+        names.join(", "),
+        "fgp skill tap update".cyan()
+    );
+}
+
+/// Fetch stale taps before a search, all at once, stopping any fetch still
+/// running after [`AUTO_UPDATE_TIMEOUT`] so a dead network can't hang the
+/// search. Offline, nothing is fetched, not even from taps on this machine.
+/// `quiet` keeps stdout clean for JSON output.
+pub fn auto_update(network: Network, quiet: bool) -> Result<()> {
+    let mut config = load_taps_config()?;
+    let stale_after_days = crate::config::load()
+        .unwrap_or_default()
+        .taps
+        .stale_after_days;
+    let stale = stale_taps(&config, None, stale_after_days, chrono::Utc::now());
+    if stale.is_empty() {
+        return Ok(());
+    }
+    let allowed = if quiet {
+        !network.offline
+    } else {
+        network.allows("refreshing stale taps")
+    };
+    if !allowed {
+        return Ok(());
+    }
+
+    if !quiet {
+        println!(
+            "{} Refreshing {} stale tap(s)...",
+            "→".blue().bold(),
+            stale.len()
+        );
+    }
+
+    // (tap, clone, running fetch)
+    let mut fetches = Vec::new();
+    let mut failed = Vec::new();
+    for (name, _) in stale {
+        let entry = &config.taps[&name];
+        let tap_path = PathBuf::from(&entry.path);
+        if !tap_path.exists() {
+            failed.push(name);
+            continue;
+        }
+        let child = Command::new("git")
+            .args(["fetch", "--quiet", &entry.url, "HEAD"])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .current_dir(&tap_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(child) => fetches.push((name, tap_path, child)),
+            Err(_) => failed.push(name),
+        }
+    }
+
+    // (tap, clone) of the fetches that succeeded
+    let mut fetched = Vec::new();
+    let start = Instant::now();
+    loop {
+        fetches.retain_mut(|(name, tap_path, child)| match child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) if status.success() => {
+                fetched.push((name.clone(), tap_path.clone()));
+                false
+            }
+            _ => {
+                failed.push(name.clone());
+                false
+            }
+        });
+        if fetches.is_empty() || start.elapsed() >= AUTO_UPDATE_TIMEOUT {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let mut timed_out = Vec::new();
+    for (name, _, mut child) in fetches {
+        let _ = child.kill();
+        let _ = child.wait();
+        timed_out.push(name);
+    }
+
+    // Fast-forwarding is local, so it isn't timed
+    let mut refreshed = Vec::new();
+    for (name, tap_path) in fetched {
+        let before = load_index(&name).unwrap_or_else(|| scan_index(&tap_path));
+        let merged = Command::new("git")
+            .args(["merge", "--ff-only", "--quiet", "FETCH_HEAD"])
+            .current_dir(&tap_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !merged {
+            failed.push(name);
+            continue;
+        }
+
+        let after = scan_index(&tap_path);
+        save_index(&name, &after)?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let entry = config.taps.get_mut(&name).unwrap();
+        if !IndexChanges::between(&before, &after).is_empty() {
+            entry.skill_count = count_skills(&tap_path)?;
+            entry.updated_at = Some(now.clone());
+        }
+        entry.fetched_at = Some(now);
+        refreshed.push(name);
+    }
+    save_taps_config(&config)?;
+
+    if quiet {
+        return Ok(());
+    }
+    if !refreshed.is_empty() {
+        println!("  {} Refreshed {}", "✓".green(), refreshed.join(", "));
+    }
+    if !timed_out.is_empty() {
+        println!(
+            "  {} Gave up on {} after {}s; searching what is on disk",
+            "!".yellow(),
+            timed_out.join(", "),
+            AUTO_UPDATE_TIMEOUT.as_secs()
+        );
+    }
+    if !failed.is_empty() {
+        failed.sort();
+        println!(
+            "  {} Couldn't refresh {}; see {}",
+            "!".yellow(),
+            failed.join(", "),
+            "fgp skill tap update".cyan()
+        );
+    }
+    println!();
+    Ok(())
+}
+
 // ============================================================================
 // Public functions for skill search/install integration
 // ============================================================================
@@ -559,7 +1185,7 @@ fn format_relative_time(timestamp: &str) -> String {
 /// Search all taps for a skill by name
 pub fn search_taps(query: &str) -> Result<Vec<(String, PathBuf, SkillManifest)>> {
     let config = load_taps_config()?;
-    let query_lower = query.to_lowercase();
+    let query = text::fold(query);
     let mut results = Vec::new();
 
     for (tap_name, entry) in &config.taps {
@@ -596,12 +1222,12 @@ pub fn search_taps(query: &str) -> Result<Vec<(String, PathBuf, SkillManifest)>>
             if let Ok(content) = fs::read_to_string(&manifest_path) {
                 if let Ok(manifest) = serde_yaml::from_str::<SkillManifest>(&content) {
                     // Match against name, description, or keywords
-                    let matches = manifest.name.to_lowercase().contains(&query_lower)
-                        || manifest.description.to_lowercase().contains(&query_lower)
+                    let matches = text::contains_folded(&manifest.name, &query)
+                        || text::contains_folded(&manifest.description, &query)
                         || manifest
                             .keywords
                             .iter()
-                            .any(|k| k.to_lowercase().contains(&query_lower));
+                            .any(|k| text::contains_folded(k, &query));
 
                     if matches {
                         results.push((tap_name.clone(), path, manifest));
@@ -614,6 +1240,40 @@ pub fn search_taps(query: &str) -> Result<Vec<(String, PathBuf, SkillManifest)>>
     Ok(results)
 }
 
+/// Every skill in the tap `name` (or a unique part of its name), in name
+/// order, with the tap's full name. Skills whose skill.yaml doesn't parse are
+/// left out.
+pub fn tap_skills(name: &str) -> Result<(String, Vec<(PathBuf, SkillManifest)>)> {
+    let config = load_taps_config()?;
+    let tap_name = find_tap_name(&config, name)?;
+    let tap_path = PathBuf::from(&config.taps[&tap_name].path);
+    let skills_dir = tap_path.join("skills");
+    let search_dir = if skills_dir.exists() {
+        skills_dir
+    } else {
+        tap_path
+    };
+
+    let mut skills = Vec::new();
+    for entry in fs::read_dir(&search_dir)
+        .with_context(|| format!("Failed to read tap {}", search_dir.display()))?
+        .flatten()
+    {
+        let path = entry.path();
+        let manifest = ["skill.yaml", "skill.yml"]
+            .iter()
+            .map(|file| path.join(file))
+            .find(|file| file.exists())
+            .and_then(|file| fs::read_to_string(file).ok())
+            .and_then(|content| serde_yaml::from_str::<SkillManifest>(&content).ok());
+        if let Some(manifest) = manifest {
+            skills.push((path, manifest));
+        }
+    }
+    skills.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    Ok((tap_name, skills))
+}
+
 /// Find a skill by exact name across all taps
 pub fn find_skill(name: &str) -> Result<Option<(String, PathBuf, SkillManifest)>> {
     let config = load_taps_config()?;
@@ -682,3 +1342,172 @@ pub fn find_skill(name: &str) -> Result<Option<(String, PathBuf, SkillManifest)>
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tap_source() {
+        let source = |input: &str| {
+            let source = TapSource::parse(input).unwrap();
+            (source.repo, source.url, source.name)
+        };
+        assert_eq!(
+            source("acme/skills"),
+            (
+                "acme/skills".to_string(),
+                "https://github.com/acme/skills.git".to_string(),
+                "acme-skills".to_string()
+            )
+        );
+        // URLs are cloned as given, so SSH stays SSH
+        assert_eq!(
+            source("git@github.com:acme/skills.git"),
+            (
+                "acme/skills".to_string(),
+                "git@github.com:acme/skills.git".to_string(),
+                "skills".to_string()
+            )
+        );
+        assert_eq!(
+            source("https://gitlab.internal/acme/team/skills.git"),
+            (
+                "gitlab.internal/acme/team/skills".to_string(),
+                "https://gitlab.internal/acme/team/skills.git".to_string(),
+                "skills".to_string()
+            )
+        );
+        assert_eq!(
+            source("ssh://git@gitlab.internal:2222/acme/skills").0,
+            "gitlab.internal/acme/skills"
+        );
+
+        assert!(TapSource::parse("skills").is_err());
+        assert!(TapSource::parse("https://gitlab.internal/").is_err());
+        assert!(TapSource::parse("ftp://example.com/skills").is_err());
+        assert!(TapSource::parse("https://example.com/../skills").is_err());
+
+        assert_eq!(
+            repo_slug("git@github.com:acme/skills.git").as_deref(),
+            Some("acme/skills")
+        );
+        assert_eq!(repo_slug("https://gitlab.internal/acme/skills.git"), None);
+    }
+
+    #[test]
+    fn test_index_changes() {
+        let tap = tempfile::tempdir().unwrap();
+        let write = |name: &str, version: &str| {
+            let dir = tap.path().join("skills").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("skill.yaml"),
+                format!("name: {}\nversion: {}\n", name, version),
+            )
+            .unwrap();
+        };
+        write("browser-gateway", "1.2.0");
+        write("email-triage", "0.1.0");
+        let before = scan_index(tap.path());
+        assert_eq!(before.len(), 2);
+        assert!(IndexChanges::between(&before, &before).is_empty());
+
+        write("browser-gateway", "1.3.0");
+        write("research-assistant", "1.0.0");
+        write("calendar-sync", "0.2.0");
+        fs::remove_dir_all(tap.path().join("skills/email-triage")).unwrap();
+        let changes = IndexChanges::between(&before, &scan_index(tap.path()));
+        assert_eq!(
+            changes.to_string(),
+            "+2 new (calendar-sync, research-assistant), \
+             1 updated (browser-gateway 1.2.0 → 1.3.0), 1 removed (email-triage)"
+        );
+    }
+
+    #[test]
+    fn test_staleness() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-24T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        // Entries from before fetches were recorded have no fetched_at
+        let config: TapsConfig = serde_json::from_str(
+            r#"{"version": 1, "taps": {
+                "fresh": {"repo": "acme/fresh", "url": "", "path": "", "skill_count": 1,
+                    "added_at": "2026-01-01T00:00:00Z",
+                    "updated_at": "2026-01-01T00:00:00Z",
+                    "fetched_at": "2026-03-23T09:00:00Z"},
+                "old": {"repo": "acme/old", "url": "", "path": "", "skill_count": 1,
+                    "added_at": "2026-01-01T00:00:00Z",
+                    "updated_at": "2026-03-01T12:00:00Z"},
+                "older": {"repo": "acme/older", "url": "", "path": "", "skill_count": 1,
+                    "added_at": "2026-02-01T12:00:00Z", "updated_at": null}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(config.taps["fresh"].age_days(now), Some(1));
+        assert_eq!(config.taps["old"].age_days(now), Some(23));
+        assert_eq!(config.taps["older"].age_days(now), Some(51));
+
+        assert_eq!(
+            stale_taps(&config, None, 14, now),
+            vec![
+                ("older".to_string(), Some(51)),
+                ("old".to_string(), Some(23))
+            ]
+        );
+        assert_eq!(
+            stale_taps(&config, Some(&["fresh".to_string()]), 1, now),
+            vec![("fresh".to_string(), Some(1))]
+        );
+        assert!(stale_taps(&config, None, 0, now).is_empty());
+        assert!(is_stale(None, 14));
+
+        assert_eq!(
+            results_age(&config, &["fresh".to_string(), "old".to_string()], now).unwrap(),
+            "Results from taps last updated 23 days ago."
+        );
+        assert_eq!(
+            results_age(&config, &["fresh".to_string()], now).unwrap(),
+            "Results from tap fresh, last updated 1 day ago."
+        );
+        assert_eq!(results_age(&config, &[], now), None);
+        assert_eq!(format_age(0), "today");
+    }
+
+    #[test]
+    fn test_add_offline() {
+        // Fails before anything is read or written under ~/.fgp
+        let err = add("acme/skills", None, Network::offline()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cloning https://github.com/acme/skills.git needs the network, \
+             but fgp is offline (--offline or FGP_OFFLINE=1)"
+        );
+    }
+
+    #[test]
+    fn test_git_failure() {
+        assert_eq!(
+            git_failure(
+                "git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository.",
+                "git@github.com:acme/skills.git"
+            ),
+            "permission denied — check SSH keys (is the key added to your account and ssh-agent?)"
+        );
+        assert!(git_failure(
+            "fatal: Authentication failed for 'https://gitlab.internal/acme/skills.git/'",
+            "https://gitlab.internal/acme/skills.git"
+        )
+        .contains("credential helper"));
+        assert!(git_failure(
+            "fatal: unable to access 'https://gitlab.internal/x.git/': Could not resolve host: gitlab.internal",
+            "https://gitlab.internal/x.git"
+        )
+        .starts_with("could not resolve host"));
+        assert_eq!(
+            git_failure("warning: x\nfatal: something else\n", "acme/skills"),
+            "something else"
+        );
+    }
+}
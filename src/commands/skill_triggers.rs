@@ -0,0 +1,442 @@
+//! Inspect skill triggers and find collisions between skills.
+//!
+//! When several skills are exported to the same agent, overlapping trigger
+//! keywords make the agent pick the wrong one. `fgp skill triggers --conflicts`
+//! compares the triggers declared in each skill's manifest:
+//!
+//! - the same keyword, pattern, or command in two skills is a conflict;
+//! - a keyword contained in another skill's keyword (`email` vs
+//!   `send email`), or a pattern that matches another skill's pattern
+//!   (`*.pdf` vs `invoices/*.pdf`), is an overlap worth narrowing.
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::skill::glob_match;
+use super::skill_manifest;
+use super::text;
+
+/// Triggers declared by one skill.
+#[derive(Debug)]
+struct SkillTriggers {
+    name: String,
+    keywords: Vec<String>,
+    patterns: Vec<String>,
+    commands: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TriggerKind {
+    Keyword,
+    Pattern,
+    Command,
+}
+
+impl TriggerKind {
+    fn label(&self) -> &'static str {
+        match self {
+            TriggerKind::Keyword => "keyword",
+            TriggerKind::Pattern => "pattern",
+            TriggerKind::Command => "command",
+        }
+    }
+}
+
+/// Triggers that can fire for more than one skill.
+#[derive(Debug, PartialEq)]
+enum Conflict {
+    /// The same trigger, declared by every listed skill
+    Same {
+        kind: TriggerKind,
+        trigger: String,
+        skills: Vec<String>,
+    },
+    /// `broad` (from `skill`) also fires wherever `narrow` (from `other`) does
+    Overlap {
+        kind: TriggerKind,
+        broad: String,
+        skill: String,
+        narrow: String,
+        other: String,
+    },
+}
+
+/// List installed skills' triggers, or only the conflicts between them.
+///
+/// `paths` adds skill directories that aren't installed, such as skills being
+/// developed alongside installed ones.
+pub fn run(conflicts: bool, paths: &[String]) -> Result<()> {
+    let skills = load_triggers(paths)?;
+
+    if skills.is_empty() {
+        println!("{}", "No skills found.".yellow());
+        println!("Install one with: {}", "fgp skill install <name>".cyan());
+        return Ok(());
+    }
+
+    if !conflicts {
+        for skill in &skills {
+            println!("{}", skill.name.bold());
+            if skill.keywords.is_empty() && skill.patterns.is_empty() && skill.commands.is_empty() {
+                println!("  {}", "No triggers".dimmed());
+            }
+            if !skill.keywords.is_empty() {
+                println!("  Keywords: {}", skill.keywords.join(", ").cyan());
+            }
+            if !skill.patterns.is_empty() {
+                println!("  Patterns: {}", skill.patterns.join(", ").cyan());
+            }
+            if !skill.commands.is_empty() {
+                println!("  Commands: {}", skill.commands.join(", ").cyan());
+            }
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{} Checking triggers across {} skill(s)...",
+        "→".blue().bold(),
+        skills.len()
+    );
+    println!();
+
+    let found = find_conflicts(&skills);
+    if found.is_empty() {
+        println!("{} No trigger conflicts.", "✓".green().bold());
+        return Ok(());
+    }
+
+    let mut same = 0;
+    for conflict in &found {
+        match conflict {
+            Conflict::Same {
+                kind,
+                trigger,
+                skills,
+            } => {
+                same += 1;
+                println!(
+                    "{} {} '{}' is declared by {}",
+                    "✗".red().bold(),
+                    kind.label(),
+                    trigger,
+                    skills.join(", ").bold()
+                );
+                let hint = match kind {
+                    TriggerKind::Keyword => format!(
+                        "Make it specific to each skill, e.g. {}",
+                        skills
+                            .iter()
+                            .map(|skill| format!("'{} {}'", skill, trigger))
+                            .collect::<Vec<_>>()
+                            .join(" / ")
+                    ),
+                    TriggerKind::Pattern => {
+                        "Both skills match the same files; narrow the glob in all but one"
+                            .to_string()
+                    }
+                    TriggerKind::Command => {
+                        "Only one skill can own a command; rename it in the others".to_string()
+                    }
+                };
+                println!("    {}", hint.dimmed());
+            }
+            Conflict::Overlap {
+                kind,
+                broad,
+                skill,
+                narrow,
+                other,
+            } => {
+                println!(
+                    "{} {} '{}' ({}) also fires for '{}' ({})",
+                    "!".yellow().bold(),
+                    kind.label(),
+                    broad,
+                    skill.bold(),
+                    narrow,
+                    other.bold()
+                );
+                println!(
+                    "    {}",
+                    format!(
+                        "Narrow '{}' in {} to something only it handles",
+                        broad, skill
+                    )
+                    .dimmed()
+                );
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} {} conflict(s), {} overlap(s)",
+        if same > 0 {
+            "✗".red().bold()
+        } else {
+            "!".yellow().bold()
+        },
+        same,
+        found.len() - same
+    );
+
+    if same > 0 {
+        bail!("{} trigger conflict(s) found", same);
+    }
+    Ok(())
+}
+
+/// Triggers of installed skills plus the skills in `paths`, sorted by name.
+fn load_triggers(paths: &[String]) -> Result<Vec<SkillTriggers>> {
+    let mut sources = super::skill::installed_skill_sources()?;
+    for path in paths {
+        let path = PathBuf::from(shellexpand::tilde(path).to_string());
+        let loaded = skill_manifest::load(&path)?;
+        sources.retain(|(name, _)| *name != loaded.manifest.name);
+        sources.push((loaded.manifest.name, path));
+    }
+
+    let mut skills = Vec::new();
+    for (name, source) in sources {
+        match read_triggers(&source) {
+            Ok(triggers) => skills.push(triggers),
+            Err(e) => eprintln!("{} Skipping {}: {:#}", "!".yellow().bold(), name, e),
+        }
+    }
+
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(skills)
+}
+
+fn read_triggers(source: &Path) -> Result<SkillTriggers> {
+    let manifest = skill_manifest::load(source)?.manifest;
+    let triggers = manifest.triggers.unwrap_or_default();
+
+    Ok(SkillTriggers {
+        name: manifest.name,
+        keywords: triggers.keywords,
+        patterns: triggers.patterns,
+        commands: triggers.commands,
+    })
+}
+
+/// Keywords compare case-insensitively (with Unicode case folding) and with
+/// whitespace collapsed.
+fn normalize_keyword(keyword: &str) -> String {
+    text::fold(&keyword.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn normalize_command(command: &str) -> String {
+    text::fold(command.trim().trim_start_matches('/'))
+}
+
+/// Whether `part`'s words appear, in order and adjacent, in `whole`.
+///
+/// Scripts written without spaces, such as Japanese, have no word boundaries
+/// to find, so any occurrence of `part` counts.
+fn contains_words(whole: &str, part: &str) -> bool {
+    if text::has_unspaced_script(whole) || text::has_unspaced_script(part) {
+        return whole.len() > part.len() && whole.contains(part);
+    }
+    let whole: Vec<&str> = whole.split(' ').collect();
+    let part: Vec<&str> = part.split(' ').collect();
+    part.len() < whole.len() && whole.windows(part.len()).any(|window| window == part)
+}
+
+fn find_conflicts(skills: &[SkillTriggers]) -> Vec<Conflict> {
+    // (kind, normalized trigger) -> (skill, trigger as written)
+    let mut index: BTreeMap<(TriggerKind, String), Vec<(&str, &str)>> = BTreeMap::new();
+    for skill in skills {
+        let all = skill
+            .keywords
+            .iter()
+            .map(|k| (TriggerKind::Keyword, normalize_keyword(k), k))
+            .chain(
+                skill
+                    .patterns
+                    .iter()
+                    .map(|p| (TriggerKind::Pattern, p.trim().to_string(), p)),
+            )
+            .chain(
+                skill
+                    .commands
+                    .iter()
+                    .map(|c| (TriggerKind::Command, normalize_command(c), c)),
+            );
+        for (kind, key, written) in all {
+            let owners = index.entry((kind, key)).or_default();
+            // A skill repeating its own trigger is not a conflict
+            if !owners.iter().any(|(name, _)| *name == skill.name) {
+                owners.push((&skill.name, written));
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for ((kind, _), owners) in &index {
+        if owners.len() > 1 {
+            conflicts.push(Conflict::Same {
+                kind: *kind,
+                trigger: owners[0].1.to_string(),
+                skills: owners.iter().map(|(name, _)| name.to_string()).collect(),
+            });
+        }
+    }
+
+    for ((kind, broad), broad_owners) in &index {
+        for ((other_kind, narrow), narrow_owners) in &index {
+            if kind != other_kind || broad == narrow {
+                continue;
+            }
+            let overlaps = match kind {
+                TriggerKind::Keyword => contains_words(narrow, broad),
+                TriggerKind::Pattern => glob_match(broad, narrow),
+                TriggerKind::Command => false,
+            };
+            if !overlaps {
+                continue;
+            }
+
+            for (skill, broad_written) in broad_owners {
+                for (other, narrow_written) in narrow_owners {
+                    if skill != other {
+                        conflicts.push(Conflict::Overlap {
+                            kind: *kind,
+                            broad: broad_written.to_string(),
+                            skill: skill.to_string(),
+                            narrow: narrow_written.to_string(),
+                            other: other.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(name: &str, keywords: &[&str], patterns: &[&str], commands: &[&str]) -> SkillTriggers {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        SkillTriggers {
+            name: name.to_string(),
+            keywords: strings(keywords),
+            patterns: strings(patterns),
+            commands: strings(commands),
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let skills = [
+            skill(
+                "gmail",
+                &["Email", "inbox", "inbox"],
+                &["*.eml"],
+                &["/mail"],
+            ),
+            skill("outlook", &["email "], &["*.msg"], &["mail"]),
+            skill("mailer", &["send email", "mailbox"], &["outbox/*.eml"], &[]),
+        ];
+
+        let conflicts = find_conflicts(&skills);
+        assert_eq!(
+            conflicts,
+            vec![
+                Conflict::Same {
+                    kind: TriggerKind::Keyword,
+                    trigger: "Email".to_string(),
+                    skills: vec!["gmail".to_string(), "outlook".to_string()],
+                },
+                Conflict::Same {
+                    kind: TriggerKind::Command,
+                    trigger: "/mail".to_string(),
+                    skills: vec!["gmail".to_string(), "outlook".to_string()],
+                },
+                Conflict::Overlap {
+                    kind: TriggerKind::Keyword,
+                    broad: "Email".to_string(),
+                    skill: "gmail".to_string(),
+                    narrow: "send email".to_string(),
+                    other: "mailer".to_string(),
+                },
+                Conflict::Overlap {
+                    kind: TriggerKind::Keyword,
+                    broad: "email ".to_string(),
+                    skill: "outlook".to_string(),
+                    narrow: "send email".to_string(),
+                    other: "mailer".to_string(),
+                },
+                Conflict::Overlap {
+                    kind: TriggerKind::Pattern,
+                    broad: "*.eml".to_string(),
+                    skill: "gmail".to_string(),
+                    narrow: "outbox/*.eml".to_string(),
+                    other: "mailer".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contains_words() {
+        assert!(contains_words("send email now", "send email"));
+        assert!(!contains_words("send email", "send email"));
+        // Whole words only: "mail" is not part of "mailbox"
+        assert!(!contains_words("check mailbox", "mail"));
+        // No spaces between Japanese words
+        assert!(contains_words("メールを送信", "メール"));
+        assert!(contains_words("gmail メール", "メール"));
+        assert!(!contains_words("メール", "メール"));
+    }
+
+    #[test]
+    fn test_find_conflicts_non_english() {
+        let skills = [
+            skill("kalender", &["Termin ändern"], &[], &["/Ändern"]),
+            skill("planer", &["TERMIN ÄNDERN"], &[], &["ändern"]),
+            skill("hava", &["İzmir"], &[], &[]),
+            skill("rota", &["IZMIR trafik"], &[], &[]),
+            skill("mail-ja", &["メール"], &[], &[]),
+            skill("send-ja", &["メールを送信"], &[], &[]),
+        ];
+
+        let conflicts = find_conflicts(&skills);
+        assert_eq!(
+            conflicts,
+            vec![
+                Conflict::Same {
+                    kind: TriggerKind::Keyword,
+                    trigger: "Termin ändern".to_string(),
+                    skills: vec!["kalender".to_string(), "planer".to_string()],
+                },
+                Conflict::Same {
+                    kind: TriggerKind::Command,
+                    trigger: "/Ändern".to_string(),
+                    skills: vec!["kalender".to_string(), "planer".to_string()],
+                },
+                Conflict::Overlap {
+                    kind: TriggerKind::Keyword,
+                    broad: "İzmir".to_string(),
+                    skill: "hava".to_string(),
+                    narrow: "IZMIR trafik".to_string(),
+                    other: "rota".to_string(),
+                },
+                Conflict::Overlap {
+                    kind: TriggerKind::Keyword,
+                    broad: "メール".to_string(),
+                    skill: "mail-ja".to_string(),
+                    narrow: "メールを送信".to_string(),
+                    other: "send-ja".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Trust decisions for skill sources.
+//!
+//! Installing a marketplace skill runs the `build_command` from its manifest
+//! through `sh -c`, so installing from a tap or marketplace means running
+//! code from whoever controls it. Before the first install from a source,
+//! fgp shows a trust summary (the source repository, whether it belongs to an
+//! official org, the build command, the skill's requirements and the daemons
+//! and methods it exposes) and asks to go ahead; `--trust` answers for
+//! scripts.
+//!
+//! Accepted sources are remembered in `~/.fgp/trusted_sources.json`, along
+//! with the repository they came from and the build command each skill last
+//! ran (or that it had none). An upgrade that changes or adds a command asks
+//! again and shows how it changed, and so does a source that now points at a
+//! different repository.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use super::{fgp_home, skill_tap};
+
+/// GitHub orgs whose taps and marketplaces are maintained by the FGP project
+pub const OFFICIAL_ORGS: &[&str] = &["fast-gateway-protocol"];
+
+/// Sources the user has accepted (trusted_sources.json)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrustedSources {
+    pub version: u32,
+    /// By tap or marketplace name
+    pub sources: BTreeMap<String, TrustedSource>,
+}
+
+impl Default for TrustedSources {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+/// One accepted tap or marketplace
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrustedSource {
+    /// Repository the source was cloned from when it was accepted
+    pub repo: String,
+    /// When it was accepted
+    pub trusted_at: String,
+    /// Build command each skill last ran, by skill name; `None` for a skill
+    /// installed without one
+    #[serde(default)]
+    pub build_commands: BTreeMap<String, Option<String>>,
+}
+
+/// What installing a skill will do, shown before asking to trust its source
+#[derive(Debug)]
+pub struct TrustSummary<'a> {
+    /// Tap or marketplace name
+    pub source: &'a str,
+    /// Repository the source was cloned from
+    pub repo: String,
+    pub skill: &'a str,
+    /// Command run through `sh -c` to build the skill, if any
+    pub build_command: Option<String>,
+    /// Tools, permissions and environment the skill asks for
+    pub requirements: Vec<String>,
+    /// Daemons the skill exposes, with their methods
+    pub daemons: Vec<(String, Vec<String>)>,
+}
+
+/// Why an install needs confirming
+#[derive(Debug, PartialEq, Eq)]
+enum Review {
+    /// The source is trusted and the build command is one it ran before
+    Trusted,
+    /// Nothing from this source has been installed yet, or it now comes from
+    /// a different repository
+    NewSource,
+    /// The skill's build command differs from the one it last ran (`None`:
+    /// it had none)
+    BuildCommandChanged(Option<String>),
+}
+
+fn trusted_sources_path() -> PathBuf {
+    fgp_home().join("trusted_sources.json")
+}
+
+fn load_trusted_sources() -> Result<TrustedSources> {
+    let path = trusted_sources_path();
+    if !path.exists() {
+        return Ok(TrustedSources::default());
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+}
+
+fn save_trusted_sources(trusted: &TrustedSources) -> Result<()> {
+    let path = trusted_sources_path();
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(trusted)?)?;
+    Ok(())
+}
+
+/// Whether `repo` (`owner/repo` or a git URL) is on GitHub under an official org.
+pub fn is_official(repo: &str) -> bool {
+    skill_tap::repo_slug(repo)
+        .and_then(|slug| slug.split('/').next().map(str::to_lowercase))
+        .is_some_and(|owner| OFFICIAL_ORGS.contains(&owner.as_str()))
+}
+
+/// Whether two spellings of a repository (`owner/repo`, a GitHub URL, any
+/// git URL) name the same one.
+fn same_repo(a: &str, b: &str) -> bool {
+    match (skill_tap::repo_slug(a), skill_tap::repo_slug(b)) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(&b),
+        _ => a.trim_end_matches('/') == b.trim_end_matches('/'),
+    }
+}
+
+fn review(trusted: &TrustedSources, summary: &TrustSummary) -> Review {
+    let Some(source) = trusted.sources.get(summary.source) else {
+        return Review::NewSource;
+    };
+    if !same_repo(&source.repo, &summary.repo) {
+        return Review::NewSource;
+    }
+    match (
+        source.build_commands.get(summary.skill),
+        &summary.build_command,
+    ) {
+        (Some(old), Some(new)) if old.as_ref() != Some(new) => {
+            Review::BuildCommandChanged(old.clone())
+        }
+        _ => Review::Trusted,
+    }
+}
+
+/// Make sure the user trusts what installing `summary.skill` will do,
+/// asking the first time its source is used and whenever its build command
+/// changes; `trust` accepts without asking. Remembers the answer.
+pub fn confirm(summary: &TrustSummary, trust: bool) -> Result<()> {
+    let mut trusted = load_trusted_sources()?;
+
+    match review(&trusted, summary) {
+        Review::Trusted => {}
+        Review::NewSource => {
+            println!();
+            match trusted.sources.get(summary.source) {
+                Some(previous) => println!(
+                    "  {} {} now comes from {} (it was trusted from {}).",
+                    "!".yellow().bold(),
+                    summary.source.cyan(),
+                    summary.repo,
+                    previous.repo
+                ),
+                None => println!(
+                    "  {} {} hasn't been installed from before.",
+                    "!".yellow().bold(),
+                    summary.source.cyan()
+                ),
+            }
+            print_summary(summary, None);
+            ask(summary, trust)?;
+            // Nothing accepted from the old repository carries over
+            trusted.sources.insert(
+                summary.source.to_string(),
+                TrustedSource {
+                    repo: summary.repo.clone(),
+                    trusted_at: chrono::Utc::now().to_rfc3339(),
+                    build_commands: BTreeMap::new(),
+                },
+            );
+        }
+        Review::BuildCommandChanged(old) => {
+            println!();
+            println!(
+                "  {} {}'s build command has changed since it was last installed.",
+                "!".yellow().bold(),
+                summary.skill.cyan()
+            );
+            print_summary(summary, Some(old.as_deref()));
+            ask(summary, trust)?;
+        }
+    }
+
+    if let Some(source) = trusted.sources.get_mut(summary.source) {
+        source
+            .build_commands
+            .insert(summary.skill.to_string(), summary.build_command.clone());
+    }
+    save_trusted_sources(&trusted)
+}
+
+/// Print what installing the skill will do; `old_command` is the build
+/// command it last ran (`Some(None)`: none), to show as a diff.
+fn print_summary(summary: &TrustSummary, old_command: Option<Option<&str>>) {
+    println!();
+    println!("  {}:", "Trust summary".bold());
+    let official = if is_official(&summary.repo) {
+        "official".green()
+    } else {
+        "third party".yellow()
+    };
+    println!("    Source:       {} ({})", summary.repo, official);
+
+    match (&summary.build_command, old_command) {
+        (Some(command), Some(old)) => {
+            println!("    Build command (run with sh -c):");
+            match old {
+                Some(old) => {
+                    for line in old.lines() {
+                        println!("      {}", format!("- {}", line).red());
+                    }
+                }
+                None => println!("      {}", "- (none)".red()),
+            }
+            for line in command.lines() {
+                println!("      {}", format!("+ {}", line).green());
+            }
+        }
+        (Some(command), None) => {
+            println!("    Build command (run with sh -c): {}", command.bold())
+        }
+        (None, _) => println!("    Build command: {}", "none".dimmed()),
+    }
+
+    if !summary.requirements.is_empty() {
+        println!("    Requires:     {}", summary.requirements.join(", "));
+    }
+    for (daemon, methods) in &summary.daemons {
+        if methods.is_empty() {
+            println!("    Exposes:      {}", daemon.cyan());
+        } else {
+            println!(
+                "    Exposes:      {} ({})",
+                daemon.cyan(),
+                methods.join(", ")
+            );
+        }
+    }
+    println!();
+}
+
+fn ask(summary: &TrustSummary, trust: bool) -> Result<()> {
+    if trust {
+        println!("  {} Trusted with --trust", "✓".green());
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!(
+            "Installing {} from {} needs confirmation; rerun with --trust",
+            summary.skill,
+            summary.source
+        );
+    }
+    if !super::confirm(&format!("Trust {} and install?", summary.source))? {
+        bail!("Install of {} cancelled", summary.skill);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review() {
+        let summary = |command: Option<&str>| TrustSummary {
+            source: "acme-skills",
+            repo: "acme/skills".to_string(),
+            skill: "gmail-gateway",
+            build_command: command.map(str::to_string),
+            requirements: Vec::new(),
+            daemons: Vec::new(),
+        };
+
+        let mut trusted = TrustedSources::default();
+        assert_eq!(review(&trusted, &summary(None)), Review::NewSource);
+
+        trusted.sources.insert(
+            "acme-skills".to_string(),
+            TrustedSource {
+                repo: "acme/skills".to_string(),
+                trusted_at: "2026-01-01T00:00:00Z".to_string(),
+                build_commands: BTreeMap::from([
+                    (
+                        "gmail-gateway".to_string(),
+                        Some("cargo build --release".to_string()),
+                    ),
+                    ("notes".to_string(), None),
+                ]),
+            },
+        );
+        let build = Some("cargo build --release");
+        assert_eq!(review(&trusted, &summary(build)), Review::Trusted);
+        assert_eq!(
+            review(&trusted, &summary(Some("curl -s x.sh | sh"))),
+            Review::BuildCommandChanged(Some("cargo build --release".to_string()))
+        );
+
+        // A skill installed without a command that gains one asks again
+        let notes = |command: Option<&str>| TrustSummary {
+            skill: "notes",
+            ..summary(command)
+        };
+        assert_eq!(review(&trusted, &notes(None)), Review::Trusted);
+        assert_eq!(
+            review(&trusted, &notes(Some("curl -s x.sh | sh"))),
+            Review::BuildCommandChanged(None)
+        );
+
+        // The same tap name re-added from another repository is a new source
+        let moved = TrustSummary {
+            repo: "mallory/skills".to_string(),
+            ..summary(build)
+        };
+        assert_eq!(review(&trusted, &moved), Review::NewSource);
+        let same = TrustSummary {
+            repo: "https://github.com/Acme/skills.git".to_string(),
+            ..summary(build)
+        };
+        assert_eq!(review(&trusted, &same), Review::Trusted);
+
+        assert!(is_official("fast-gateway-protocol/official-skills"));
+        assert!(is_official(
+            "https://github.com/Fast-Gateway-Protocol/skills.git"
+        ));
+        assert!(!is_official("acme/skills"));
+        assert!(!is_official(
+            "https://gitlab.com/fast-gateway-protocol/skills.git"
+        ));
+    }
+}
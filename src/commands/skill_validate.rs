@@ -2,14 +2,32 @@
 //!
 //! This module validates the composed skill package format,
 //! which bundles daemon dependencies, instructions, and triggers.
+//!
+//! Findings are reported with [`lint_report`](super::lint_report), under
+//! these rules:
+//!
+//! | Rule | Severity | Finding |
+//! |------|----------|---------|
+//! | `invalid-manifest` | error | Not YAML, or not shaped like a skill.yaml |
+//! | `missing-field` | error | `name`, `version`, `description` or `author` is missing |
+//! | `invalid-name` | error | The name isn't 2-64 lowercase letters, digits and hyphens |
+//! | `invalid-version` | error | The version isn't semver |
+//! | `invalid-description` | error | The description isn't 10-500 characters |
+//! | `empty-daemon` | error | A daemon dependency has no name |
+//! | `unknown-daemon` | warning | A daemon is neither well known nor installed |
+//! | `missing-file` | warning | An instruction or workflow file doesn't exist |
+//! | `invalid-config` | error | A config option has an unknown type, or an enum has no options |
+//! | `invalid-auth` | error | An auth value or secret name is malformed |
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
+use super::lint_report::{self, Diagnostic};
+
 /// Skill manifest (skill.yaml) - the composed skill format.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SkillManifest {
@@ -74,7 +92,7 @@ pub enum Author {
 }
 
 /// Daemon dependency.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonDependency {
     /// Daemon name (e.g., "browser", "gmail")
     pub name: String,
@@ -87,6 +105,10 @@ pub struct DaemonDependency {
     /// Specific methods used (for permissions)
     #[serde(default)]
     pub methods: Vec<String>,
+    /// Example JSON params by method, used for the `fgp call` examples in
+    /// exported docs
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, String>,
 }
 
 /// Agent-specific instruction files.
@@ -108,8 +130,23 @@ pub struct Instructions {
     pub zed: Option<String>,
 }
 
+impl Instructions {
+    /// Each agent's instruction file, as (key, agent name, path)
+    pub fn files(&self) -> [(&'static str, &'static str, &Option<String>); 7] {
+        [
+            ("core", "Core", &self.core),
+            ("claude-code", "Claude Code", &self.claude_code),
+            ("cursor", "Cursor", &self.cursor),
+            ("codex", "Codex", &self.codex),
+            ("windsurf", "Windsurf", &self.windsurf),
+            ("mcp", "MCP", &self.mcp),
+            ("zed", "Zed", &self.zed),
+        ]
+    }
+}
+
 /// Trigger conditions.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Triggers {
     #[serde(default)]
     pub keywords: Vec<String>,
@@ -225,9 +262,27 @@ fn default_true() -> bool {
     true
 }
 
-/// Validate a skill manifest.
-pub fn validate(path: &str) -> Result<()> {
-    println!("{} Validating skill manifest...", "→".blue().bold());
+/// Daemons that are always worth depending on (see `validate_daemons`)
+const KNOWN_DAEMONS: &[&str] = &[
+    "browser", "gmail", "calendar", "github", "imessage", "fly", "neon", "vercel", "slack",
+    "travel",
+];
+
+/// `fgp skill validate --json` output.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    manifest: String,
+    valid: bool,
+    strict: bool,
+    findings: &'a [Diagnostic],
+}
+
+/// Validate a skill manifest. Fails if it has errors, or with `strict`,
+/// warnings; `json` prints the findings as JSON instead of a summary.
+pub fn validate(path: &str, strict: bool, json: bool) -> Result<()> {
+    if !json {
+        println!("{} Validating skill manifest...", "→".blue().bold());
+    }
 
     let skill_path = Path::new(path);
 
@@ -236,74 +291,131 @@ pub fn validate(path: &str) -> Result<()> {
         bail!("Path not found: {}", path);
     }
 
-    // Find skill.yaml
-    let manifest_path = if skill_path.is_dir() {
-        skill_path.join("skill.yaml")
+    // Find skill.yaml (or skill.yml)
+    let candidates = if skill_path.is_dir() {
+        vec![skill_path.join("skill.yaml"), skill_path.join("skill.yml")]
     } else {
-        skill_path.to_path_buf()
+        vec![skill_path.to_path_buf(), skill_path.with_extension("yml")]
     };
-
-    if !manifest_path.exists() {
-        // Also check for skill.yml
-        let alt_path = if skill_path.is_dir() {
-            skill_path.join("skill.yml")
-        } else {
-            skill_path.with_extension("yml")
-        };
-
-        if alt_path.exists() {
-            return validate_manifest(&alt_path, skill_path);
-        }
-
+    let Some(manifest_path) = candidates.iter().find(|p| p.exists()) else {
         bail!(
             "Skill manifest not found. Expected: {}\n\
              Create a skill.yaml file with name, version, description, and author.",
-            manifest_path.display()
+            candidates[0].display()
         );
-    }
-
-    validate_manifest(&manifest_path, skill_path)
-}
+    };
+    // Instruction and workflow paths are relative to the manifest
+    let skill_dir = manifest_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
 
-fn validate_manifest(manifest_path: &Path, skill_dir: &Path) -> Result<()> {
-    // Read and parse
     let content = fs::read_to_string(manifest_path)
         .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let (skill, findings) = check_manifest(&content, skill_dir);
 
-    let skill: SkillManifest =
-        serde_yaml::from_str(&content).with_context(|| "Invalid YAML or schema mismatch")?;
-
-    // Validation checks
-    validate_name(&skill.name)?;
-    validate_version(&skill.version)?;
-    validate_description(&skill.description)?;
+    let (errors, warnings) = lint_report::counts(&findings);
+    let valid = errors == 0 && !(strict && warnings > 0);
 
-    let mut warnings = Vec::new();
-
-    // Validate daemon dependencies
-    validate_daemons(&skill.daemons)?;
+    if json {
+        let report = Report {
+            manifest: manifest_path.display().to_string(),
+            valid,
+            strict,
+            findings: &findings,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        if let (Some(skill), 0) = (&skill, errors) {
+            print_summary(skill);
+        }
+        if !findings.is_empty() {
+            println!();
+            lint_report::print(&findings);
+        }
+    }
 
-    // Validate instruction files exist
-    if let Some(ref instructions) = skill.instructions {
-        validate_instructions(instructions, skill_dir, &mut warnings)?;
+    if errors > 0 {
+        bail!("Skill manifest is invalid: {} error(s)", errors);
+    }
+    if !valid {
+        bail!(
+            "Skill manifest has {} warning(s), which --strict treats as errors",
+            warnings
+        );
     }
+    Ok(())
+}
 
-    // Validate workflow files exist
-    validate_workflows(&skill.workflows, skill_dir, &mut warnings)?;
+/// Check a manifest's content, returning it (if it parses) and everything
+/// wrong with it. Paths it references are resolved against `skill_dir`.
+pub fn check_manifest(content: &str, skill_dir: &Path) -> (Option<SkillManifest>, Vec<Diagnostic>) {
+    let mut findings = Vec::new();
+
+    // Look for missing fields first, so each one is reported by name rather
+    // than as whichever one serde trips over
+    let mut value: serde_yaml::Value = match serde_yaml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            findings.push(Diagnostic::error(
+                "invalid-manifest",
+                "manifest",
+                format!("Invalid YAML: {}", e),
+            ));
+            return (None, findings);
+        }
+    };
+    for field in ["name", "version", "description", "author"] {
+        if value.get(field).is_none_or(|v| v.is_null()) {
+            findings.push(Diagnostic::error(
+                "missing-field",
+                field,
+                "Required field is missing",
+            ));
+        }
+    }
+    if !findings.is_empty() {
+        return (None, findings);
+    }
+    // `version: 1.0` is a YAML number; check it as the version it was meant as
+    if let Some(version) = value.get_mut("version") {
+        if let serde_yaml::Value::Number(number) = version {
+            *version = serde_yaml::Value::String(number.to_string());
+        }
+    }
 
-    // Validate config options
-    validate_config(&skill.config)?;
+    let skill: SkillManifest = match serde_yaml::from_value(value) {
+        Ok(skill) => skill,
+        Err(e) => {
+            findings.push(Diagnostic::error(
+                "invalid-manifest",
+                "manifest",
+                format!("Schema mismatch: {}", e),
+            ));
+            return (None, findings);
+        }
+    };
 
-    // Validate auth config
+    validate_name(&skill.name, &mut findings);
+    validate_version(&skill.version, &mut findings);
+    validate_description(&skill.description, &mut findings);
+    validate_daemons(&skill.daemons, &mut findings);
+    if let Some(ref instructions) = skill.instructions {
+        validate_instructions(instructions, skill_dir, &mut findings);
+    }
+    validate_workflows(&skill.workflows, skill_dir, &mut findings);
+    validate_config(&skill.config, &mut findings);
     if let Some(ref auth) = skill.auth {
-        validate_auth(auth)?;
+        validate_auth(auth, &mut findings);
     }
 
-    // Success output
+    (Some(skill), findings)
+}
+
+fn print_summary(skill: &SkillManifest) {
     println!("{} Skill manifest is valid!", "✓".green().bold());
     println!();
 
-    // Print summary
     println!("{}:", "Skill Info".cyan().bold());
     println!("  Name:        {}", skill.name.white().bold());
     println!("  Version:     {}", skill.version);
@@ -367,177 +479,274 @@ fn validate_manifest(manifest_path: &Path, skill_dir: &Path) -> Result<()> {
             println!("  - MCP");
         }
     }
-
-    // Print warnings
-    if !warnings.is_empty() {
-        println!();
-        println!("{}:", "Warnings".yellow().bold());
-        for warning in warnings {
-            println!("  {} {}", "⚠".yellow(), warning);
-        }
-    }
-
-    Ok(())
 }
 
-fn validate_name(name: &str) -> Result<()> {
-    if name.len() < 2 {
-        bail!("Skill name must be at least 2 characters");
-    }
-    if name.len() > 64 {
-        bail!("Skill name must be at most 64 characters");
-    }
-    if !name
+fn validate_name(name: &str, findings: &mut Vec<Diagnostic>) {
+    let problem = if name.len() < 2 {
+        "Skill name must be at least 2 characters"
+    } else if name.len() > 64 {
+        "Skill name must be at most 64 characters"
+    } else if !name
         .chars()
         .next()
         .map(|c| c.is_ascii_lowercase())
         .unwrap_or(false)
     {
-        bail!("Skill name must start with a lowercase letter");
-    }
-    if !name
+        "Skill name must start with a lowercase letter"
+    } else if !name
         .chars()
         .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
     {
-        bail!("Skill name must contain only lowercase letters, numbers, and hyphens");
-    }
-    Ok(())
+        "Skill name must contain only lowercase letters, numbers, and hyphens"
+    } else {
+        return;
+    };
+    findings.push(Diagnostic::error("invalid-name", "name", problem));
 }
 
-fn validate_version(version: &str) -> Result<()> {
-    // Simple semver check
-    let parts: Vec<&str> = version
-        .split('-')
-        .next()
-        .unwrap_or(version)
-        .split('.')
-        .collect();
-    if parts.len() != 3 {
-        bail!("Version must be semver format (e.g., 1.0.0)");
-    }
-    for part in parts {
-        if part.is_empty() {
-            bail!("Version components cannot be empty (e.g., '1..0' or '1.2.' are invalid)");
-        }
-        if part.parse::<u32>().is_err() {
-            bail!("Version components must be numbers");
-        }
-    }
-    Ok(())
+fn validate_version(version: &str, findings: &mut Vec<Diagnostic>) {
+    // major.minor.patch, optionally followed by -prerelease and/or +build
+    let core = version.split(['-', '+']).next().unwrap_or(version);
+    let parts: Vec<&str> = core.split('.').collect();
+    let problem = if parts.len() != 3 {
+        "Version must be semver format (e.g., 1.0.0)"
+    } else if parts.iter().any(|part| part.is_empty()) {
+        "Version components cannot be empty (e.g., '1..0' or '1.2.' are invalid)"
+    } else if parts.iter().any(|part| part.parse::<u32>().is_err()) {
+        "Version components must be numbers"
+    } else if parts
+        .iter()
+        .any(|part| part.len() > 1 && part.starts_with('0'))
+    {
+        "Version components cannot have leading zeros"
+    } else {
+        return;
+    };
+    findings.push(Diagnostic::error(
+        "invalid-version",
+        "version",
+        format!("{} (got '{}')", problem, version),
+    ));
 }
 
-fn validate_description(description: &str) -> Result<()> {
+fn validate_description(description: &str, findings: &mut Vec<Diagnostic>) {
     if description.len() < 10 {
-        bail!("Description must be at least 10 characters");
-    }
-    if description.len() > 500 {
-        bail!("Description must be at most 500 characters");
+        findings.push(Diagnostic::error(
+            "invalid-description",
+            "description",
+            "Description must be at least 10 characters",
+        ));
+    } else if description.len() > 500 {
+        findings.push(Diagnostic::error(
+            "invalid-description",
+            "description",
+            "Description must be at most 500 characters",
+        ));
     }
-    Ok(())
 }
 
-fn validate_daemons(daemons: &[DaemonDependency]) -> Result<()> {
-    for daemon in daemons {
-        if daemon.name.is_empty() {
-            bail!("Daemon name cannot be empty");
+fn validate_daemons(daemons: &[DaemonDependency], findings: &mut Vec<Diagnostic>) {
+    for (i, daemon) in daemons.iter().enumerate() {
+        let field = format!("daemons[{}].name", i);
+        if daemon.name.trim().is_empty() {
+            findings.push(Diagnostic::error(
+                "empty-daemon",
+                field,
+                "Daemon name cannot be empty",
+            ));
+            continue;
         }
-        // Known daemons (could be expanded or loaded from registry)
-        let known_daemons = [
-            "browser", "gmail", "calendar", "github", "imessage", "fly", "neon", "vercel", "slack",
-            "travel",
-        ];
-        if !known_daemons.contains(&daemon.name.as_str()) {
-            eprintln!(
-                "  {} Unknown daemon '{}' - may not be available",
-                "⚠".yellow(),
-                daemon.name
-            );
+        // Known daemons, plus any installed on this machine
+        let installed = super::fgp_services_dir()
+            .join(&daemon.name)
+            .join("manifest.json")
+            .exists();
+        if !KNOWN_DAEMONS.contains(&daemon.name.as_str()) && !installed {
+            findings.push(Diagnostic::warning(
+                "unknown-daemon",
+                field,
+                format!("Unknown daemon '{}' - may not be available", daemon.name),
+            ));
         }
     }
-    Ok(())
 }
 
 fn validate_instructions(
     instructions: &Instructions,
     skill_dir: &Path,
-    warnings: &mut Vec<String>,
-) -> Result<()> {
-    let mut check_file = |path: &Option<String>, name: &str| {
+    findings: &mut Vec<Diagnostic>,
+) {
+    for (key, name, path) in instructions.files() {
         if let Some(ref p) = path {
-            let full_path = skill_dir.join(p);
-            if !full_path.exists() {
-                warnings.push(format!("{} instruction file not found: {}", name, p));
+            if !skill_dir.join(p).exists() {
+                findings.push(Diagnostic::warning(
+                    "missing-file",
+                    format!("instructions.{}", key),
+                    format!("{} instruction file not found: {}", name, p),
+                ));
             }
         }
-    };
-
-    check_file(&instructions.core, "Core");
-    check_file(&instructions.claude_code, "Claude Code");
-    check_file(&instructions.cursor, "Cursor");
-    check_file(&instructions.codex, "Codex");
-    check_file(&instructions.windsurf, "Windsurf");
-    check_file(&instructions.mcp, "MCP");
-
-    Ok(())
+    }
 }
 
 fn validate_workflows(
     workflows: &HashMap<String, WorkflowRef>,
     skill_dir: &Path,
-    warnings: &mut Vec<String>,
-) -> Result<()> {
-    for (name, workflow) in workflows {
-        let workflow_path = skill_dir.join(&workflow.file);
-        if !workflow_path.exists() {
-            warnings.push(format!(
-                "Workflow '{}' file not found: {}",
-                name, workflow.file
+    findings: &mut Vec<Diagnostic>,
+) {
+    let mut names: Vec<&String> = workflows.keys().collect();
+    names.sort();
+    for name in names {
+        let workflow = &workflows[name];
+        if !skill_dir.join(&workflow.file).exists() {
+            findings.push(Diagnostic::warning(
+                "missing-file",
+                format!("workflows.{}.file", name),
+                format!("Workflow '{}' file not found: {}", name, workflow.file),
             ));
         }
     }
-    Ok(())
 }
 
-fn validate_config(config: &HashMap<String, ConfigOption>) -> Result<()> {
+fn validate_config(config: &HashMap<String, ConfigOption>, findings: &mut Vec<Diagnostic>) {
     let valid_types = ["string", "number", "boolean", "enum", "array"];
-    for (name, opt) in config {
+    let mut names: Vec<&String> = config.keys().collect();
+    names.sort();
+    for name in names {
+        let opt = &config[name];
         if !valid_types.contains(&opt.config_type.as_str()) {
-            bail!(
-                "Invalid config type '{}' for '{}'. Valid types: {:?}",
-                opt.config_type,
-                name,
-                valid_types
-            );
-        }
-        if opt.config_type == "enum" && opt.options.is_empty() {
-            bail!("Enum config '{}' must have options", name);
+            findings.push(Diagnostic::error(
+                "invalid-config",
+                format!("config.{}.type", name),
+                format!(
+                    "Invalid config type '{}' for '{}'. Valid types: {:?}",
+                    opt.config_type, name, valid_types
+                ),
+            ));
+        } else if opt.config_type == "enum" && opt.options.is_empty() {
+            findings.push(Diagnostic::error(
+                "invalid-config",
+                format!("config.{}.options", name),
+                format!("Enum config '{}' must have options", name),
+            ));
         }
     }
-    Ok(())
 }
 
-fn validate_auth(auth: &AuthConfig) -> Result<()> {
+fn validate_auth(auth: &AuthConfig, findings: &mut Vec<Diagnostic>) {
     let valid_auth_values = ["required", "optional"];
-    for (daemon, value) in &auth.daemons {
+    let mut daemons: Vec<(&String, &String)> = auth.daemons.iter().collect();
+    daemons.sort();
+    for (daemon, value) in daemons {
         if !valid_auth_values.contains(&value.as_str()) {
-            bail!(
-                "Invalid auth value '{}' for daemon '{}'. Use 'required' or 'optional'",
-                value,
-                daemon
-            );
+            findings.push(Diagnostic::error(
+                "invalid-auth",
+                format!("auth.daemons.{}", daemon),
+                format!(
+                    "Invalid auth value '{}' for daemon '{}'. Use 'required' or 'optional'",
+                    value, daemon
+                ),
+            ));
         }
     }
 
-    for secret in &auth.secrets {
+    for (i, secret) in auth.secrets.iter().enumerate() {
         // Validate secret name format (UPPER_SNAKE_CASE)
         if !secret
             .name
             .chars()
             .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
         {
-            bail!("Secret name '{}' must be UPPER_SNAKE_CASE", secret.name);
+            findings.push(Diagnostic::error(
+                "invalid-auth",
+                format!("auth.secrets[{}].name", i),
+                format!("Secret name '{}' must be UPPER_SNAKE_CASE", secret.name),
+            ));
         }
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::lint_report::Severity;
+
+    fn fields(findings: &[Diagnostic]) -> Vec<(&str, Severity)> {
+        findings
+            .iter()
+            .map(|f| (f.location.as_str(), f.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("instructions")).unwrap();
+        fs::write(tmp.path().join("instructions/core.md"), "# Mail").unwrap();
+
+        let (skill, findings) = check_manifest(
+            "name: inbox\nversion: 1.2.0-beta.1\ndescription: Reads your mail\nauthor: fgp\n\
+             daemons:\n  - name: gmail\n\
+             instructions:\n  core: instructions/core.md\n",
+            tmp.path(),
+        );
+        assert!(skill.is_some());
+        assert!(findings.is_empty(), "{:?}", findings);
+
+        // Every missing required field is named
+        let (skill, findings) = check_manifest("name: inbox\nversion: 1.0.0\n", tmp.path());
+        assert!(skill.is_none());
+        assert_eq!(
+            fields(&findings),
+            vec![
+                ("description", Severity::Error),
+                ("author", Severity::Error)
+            ]
+        );
+
+        let (_, findings) = check_manifest(
+            "name: inbox\nversion: 1.0\ndescription: Reads your mail\nauthor: fgp\n\
+             daemons:\n  - name: ''\n  - name: no-such-daemon-here\n\
+             instructions:\n  cursor: instructions/cursor.md\n\
+             workflows:\n  triage:\n    file: workflows/triage.yaml\n\
+             auth:\n  secrets:\n    - name: api_key\n",
+            tmp.path(),
+        );
+        assert_eq!(
+            fields(&findings),
+            vec![
+                ("version", Severity::Error),
+                ("daemons[0].name", Severity::Error),
+                ("daemons[1].name", Severity::Warning),
+                ("instructions.cursor", Severity::Warning),
+                ("workflows.triage.file", Severity::Warning),
+                ("auth.secrets[0].name", Severity::Error),
+            ]
+        );
+        assert_eq!(
+            findings[0].message,
+            "Version must be semver format (e.g., 1.0.0) (got '1.0')"
+        );
+
+        let (_, findings) = check_manifest("name: [unclosed", tmp.path());
+        assert_eq!(fields(&findings), vec![("manifest", Severity::Error)]);
+    }
+
+    #[test]
+    fn test_finding_json() {
+        let finding = Diagnostic::warning(
+            "missing-file",
+            "workflows.triage.file",
+            "Workflow file not found",
+        );
+        assert_eq!(
+            serde_json::to_value(&finding).unwrap(),
+            serde_json::json!({
+                "rule": "missing-file",
+                "location": "workflows.triage.file",
+                "severity": "warning",
+                "message": "Workflow file not found"
+            })
+        );
+    }
 }
@@ -1,4 +1,7 @@
 //! Start a daemon service.
+//!
+//! Runs the daemon's entrypoint from its manifest.json, or its
+//! `start_command` if it has one (see [`super::daemon_command`]).
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
@@ -6,6 +9,8 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use super::client::Client;
+use super::daemon_command::{self, CommandPaths};
 use super::{fgp_services_dir, service_socket_path};
 
 pub fn run(service: &str, foreground: bool) -> Result<()> {
@@ -24,10 +29,7 @@ pub fn run(service: &str, foreground: bool) -> Result<()> {
     let socket_path = service_socket_path(service);
     if socket_path.exists() {
         // Try to connect to see if it's actually running
-        if fgp_daemon::FgpClient::new(&socket_path)
-            .map(|c| c.is_running())
-            .unwrap_or(false)
-        {
+        if Client::at(&socket_path).health().is_ok() {
             println!(
                 "{} Service '{}' is already running.",
                 "!".yellow().bold(),
@@ -40,39 +42,46 @@ pub fn run(service: &str, foreground: bool) -> Result<()> {
         }
     }
 
-    // Read manifest to get entrypoint
-    let manifest_content =
-        fs::read_to_string(&manifest_path).context("Failed to read manifest.json")?;
-    let manifest: serde_json::Value =
-        serde_json::from_str(&manifest_content).context("Failed to parse manifest.json")?;
+    let mut command = match daemon_command::configured(service, "start_command")? {
+        Some(start_command) => {
+            let args = daemon_command::expand(&start_command, &CommandPaths::for_service(service))
+                .context("Invalid daemon.start_command")?;
+            let mut command = Command::new(&args[0]);
+            command.args(&args[1..]);
+            command
+        }
+        None => {
+            // Read manifest to get entrypoint
+            let manifest_content =
+                fs::read_to_string(&manifest_path).context("Failed to read manifest.json")?;
+            let manifest: serde_json::Value =
+                serde_json::from_str(&manifest_content).context("Failed to parse manifest.json")?;
 
-    let entrypoint = manifest["daemon"]["entrypoint"]
-        .as_str()
-        .context("manifest.json missing daemon.entrypoint")?;
+            let entrypoint = manifest["daemon"]["entrypoint"]
+                .as_str()
+                .context("manifest.json missing daemon.entrypoint")?;
 
-    let entrypoint_path = service_dir.join(entrypoint);
-    if !entrypoint_path.exists() {
-        bail!("Daemon entrypoint not found: {}", entrypoint_path.display());
-    }
+            let entrypoint_path = service_dir.join(entrypoint);
+            if !entrypoint_path.exists() {
+                bail!("Daemon entrypoint not found: {}", entrypoint_path.display());
+            }
+            Command::new(entrypoint_path)
+        }
+    };
+    command.current_dir(&service_dir);
 
     println!("{} Starting {}...", "→".blue().bold(), service.bold());
 
     if foreground {
         // Run in foreground (blocking)
-        let status = Command::new(&entrypoint_path)
-            .current_dir(&service_dir)
-            .status()
-            .context("Failed to start daemon")?;
+        let status = command.status().context("Failed to start daemon")?;
 
         if !status.success() {
             bail!("Daemon exited with status: {}", status);
         }
     } else {
         // Start as background process
-        let child = Command::new(&entrypoint_path)
-            .current_dir(&service_dir)
-            .spawn()
-            .context("Failed to start daemon")?;
+        let child = command.spawn().context("Failed to start daemon")?;
 
         // Wait a moment for socket to appear
         std::thread::sleep(std::time::Duration::from_millis(500));
@@ -2,10 +2,17 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tabled::settings::{object::Columns, Disable};
 use tabled::{Table, Tabled};
 
-use super::{fgp_services_dir, service_socket_path};
+use super::client::{self, Client};
+use super::{fgp_services_dir, service_pid_path, service_socket_path};
 
 #[derive(Tabled)]
 struct ServiceStatus {
@@ -17,9 +24,194 @@ struct ServiceStatus {
     version: String,
     #[tabled(rename = "Uptime")]
     uptime: String,
+    #[tabled(rename = "Memory")]
+    memory: String,
+    #[tabled(rename = "CPU")]
+    cpu: String,
 }
 
-pub fn run(verbose: bool) -> Result<()> {
+/// Resource usage of a daemon process.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessStats {
+    /// Resident set size in megabytes
+    pub rss_mb: f64,
+    /// CPU usage in percent of one core
+    pub cpu_percent: f32,
+}
+
+/// Machine-readable snapshot of a single service (used by `--json` and the
+/// TUI's saved snapshots).
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceSnapshot {
+    pub service: String,
+    pub running: bool,
+    pub pid: Option<u64>,
+    pub socket_path: String,
+    pub uptime_seconds: Option<u64>,
+    /// Round trip of the health check, when the daemon answered
+    pub latency_ms: Option<f64>,
+    pub last_health: Option<serde_json::Value>,
+}
+
+/// Collect snapshots for every service under ~/.fgp/services.
+pub fn collect_snapshots() -> Result<Vec<ServiceSnapshot>> {
+    let services_dir = fgp_services_dir();
+    if !services_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(&services_dir)?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    names.sort();
+
+    Ok(names.iter().map(|name| snapshot_service(name)).collect())
+}
+
+/// Query a single service's health and build its snapshot.
+pub fn snapshot_service(service: &str) -> ServiceSnapshot {
+    let socket_path = service_socket_path(service);
+    let started = Instant::now();
+
+    let health = if socket_path.exists() {
+        Client::at(&socket_path)
+            .health()
+            .ok()
+            .filter(|response| response.ok)
+            .map(|response| response.result.unwrap_or_default())
+    } else {
+        None
+    };
+    let latency_ms = health
+        .as_ref()
+        .map(|_| started.elapsed().as_secs_f64() * 1000.0);
+
+    let (running, pid, uptime_seconds) = match &health {
+        Some(result) => (
+            true,
+            result["pid"].as_u64(),
+            result["uptime_seconds"]
+                .as_u64()
+                .or_else(|| socket_age_secs(&socket_path)),
+        ),
+        None => (false, None, None),
+    };
+
+    ServiceSnapshot {
+        service: service.to_string(),
+        running,
+        pid,
+        socket_path: socket_path.display().to_string(),
+        uptime_seconds,
+        latency_ms,
+        last_health: health,
+    }
+}
+
+/// Render snapshots as a markdown table, for pasting into bug reports.
+pub fn snapshot_markdown(snapshots: &[ServiceSnapshot], taken_at: &str) -> String {
+    let mut out = format!("# FGP service status\n\nTaken {}\n\n", taken_at);
+
+    if snapshots.is_empty() {
+        out.push_str("No services installed.\n");
+        return out;
+    }
+
+    out.push_str("| Service | State | Version | Uptime | PID | Latency |\n");
+    out.push_str("|---------|-------|---------|--------|-----|---------|\n");
+    for snapshot in snapshots {
+        let health = snapshot.last_health.as_ref();
+        let state = match health.and_then(|h| h["status"].as_str()) {
+            Some(status) => status.to_string(),
+            None if snapshot.running => "running".to_string(),
+            None => "stopped".to_string(),
+        };
+        let version = health
+            .and_then(|h| h["version"].as_str())
+            .unwrap_or("-")
+            .to_string();
+        let uptime = snapshot
+            .uptime_seconds
+            .map(format_uptime)
+            .unwrap_or_else(|| "-".to_string());
+        let pid = snapshot
+            .pid
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let latency = snapshot
+            .latency_ms
+            .map(|ms| format!("{:.1} ms", ms))
+            .unwrap_or_else(|| "-".to_string());
+
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            snapshot.service, state, version, uptime, pid, latency
+        ));
+    }
+
+    out
+}
+
+/// Approximate uptime from the socket file's modification time.
+fn socket_age_secs(socket_path: &Path) -> Option<u64> {
+    let modified = fs::metadata(socket_path).ok()?.modified().ok()?;
+    modified.elapsed().ok().map(|d| d.as_secs())
+}
+
+pub fn run(verbose: bool, json: bool, exit_code: bool) -> Result<()> {
+    if json {
+        let snapshots = collect_snapshots()?;
+        println!("{}", serde_json::to_string_pretty(&snapshots)?);
+    } else {
+        print_table(verbose)?;
+    }
+
+    if exit_code {
+        check_autostart(json)?;
+    }
+
+    Ok(())
+}
+
+/// Exit non-zero if any autostart service is not running.
+///
+/// Falls back to every installed service when no autostart list is configured.
+fn check_autostart(quiet: bool) -> Result<()> {
+    let config = crate::config::load()?;
+    let expected: Vec<String> = if config.autostart.services.is_empty() {
+        collect_snapshots()?
+            .into_iter()
+            .map(|s| s.service)
+            .collect()
+    } else {
+        config.autostart.services
+    };
+
+    let down: Vec<&String> = expected
+        .iter()
+        .filter(|name| !snapshot_service(name).running)
+        .collect();
+
+    if !down.is_empty() {
+        if !quiet {
+            eprintln!(
+                "{} Not running: {}",
+                "✗".red().bold(),
+                down.iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn print_table(verbose: bool) -> Result<()> {
     let services_dir = fgp_services_dir();
 
     if !services_dir.exists() {
@@ -34,6 +226,7 @@ pub fn run(verbose: bool) -> Result<()> {
 
     let entries = fs::read_dir(&services_dir)?;
     let mut statuses: Vec<ServiceStatus> = Vec::new();
+    let mut pids: Vec<Option<u64>> = Vec::new();
     let mut any_service = false;
 
     for entry in entries {
@@ -51,37 +244,37 @@ pub fn run(verbose: bool) -> Result<()> {
 
         any_service = true;
         let socket_path = service_socket_path(service_name);
+        let mut health_pid = None;
 
         let (status, version, uptime) = if socket_path.exists() {
             // Try to get health info
-            match fgp_daemon::FgpClient::new(&socket_path) {
-                Ok(client) => match client.health() {
-                    Ok(response) if response.ok => {
-                        let result = response.result.unwrap_or_default();
-                        let version = result["version"].as_str().unwrap_or("?").to_string();
-                        let uptime_secs = result["uptime_seconds"].as_u64().unwrap_or(0);
-                        let uptime = format_uptime(uptime_secs);
-                        let status_str = result["status"].as_str().unwrap_or("running");
-
-                        let status_colored = match status_str {
-                            "healthy" => "● running".green().to_string(),
-                            "degraded" => "◐ degraded".yellow().to_string(),
-                            _ => format!("● {}", status_str).green().to_string(),
-                        };
-
-                        (status_colored, version, uptime)
-                    }
-                    _ => (
-                        "○ not responding".red().to_string(),
-                        "-".to_string(),
-                        "-".to_string(),
-                    ),
-                },
-                Err(_) => (
+            match Client::at(&socket_path).health() {
+                Ok(response) if response.ok => {
+                    let result = response.result.unwrap_or_default();
+                    health_pid = result["pid"].as_u64();
+                    let version = result["version"].as_str().unwrap_or("?").to_string();
+                    let uptime_secs = result["uptime_seconds"].as_u64().unwrap_or(0);
+                    let uptime = format_uptime(uptime_secs);
+                    let status_str = result["status"].as_str().unwrap_or("running");
+
+                    let status_colored = match status_str {
+                        "healthy" => "● running".green().to_string(),
+                        "degraded" => "◐ degraded".yellow().to_string(),
+                        _ => format!("● {}", status_str).green().to_string(),
+                    };
+
+                    (status_colored, version, uptime)
+                }
+                Err(client::Error::Connect { .. }) => (
                     "○ socket error".red().to_string(),
                     "-".to_string(),
                     "-".to_string(),
                 ),
+                _ => (
+                    "○ not responding".red().to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                ),
             }
         } else {
             (
@@ -91,27 +284,28 @@ pub fn run(verbose: bool) -> Result<()> {
             )
         };
 
+        pids.push(read_pid_file(service_name).or(health_pid));
         statuses.push(ServiceStatus {
             name: service_name.to_string(),
             status,
             version,
             uptime,
+            memory: "-".to_string(),
+            cpu: "-".to_string(),
         });
 
         if verbose && socket_path.exists() {
             // Print detailed health info
-            if let Ok(client) = fgp_daemon::FgpClient::new(&socket_path) {
-                if let Ok(response) = client.health() {
-                    if response.ok {
-                        if let Some(result) = response.result {
-                            println!("\n{} {} health details:", "→".blue(), service_name.bold());
-                            println!(
-                                "{}",
-                                serde_json::to_string_pretty(&result)
-                                    .unwrap_or_default()
-                                    .dimmed()
-                            );
-                        }
+            if let Ok(response) = Client::at(&socket_path).health() {
+                if response.ok {
+                    if let Some(result) = response.result {
+                        println!("\n{} {} health details:", "→".blue(), service_name.bold());
+                        println!(
+                            "{}",
+                            serde_json::to_string_pretty(&result)
+                                .unwrap_or_default()
+                                .dimmed()
+                        );
                     }
                 }
             }
@@ -124,15 +318,72 @@ pub fn run(verbose: bool) -> Result<()> {
         return Ok(());
     }
 
+    if verbose {
+        // Sample every daemon at once so CPU usage is measured only once
+        let stats = process_stats(&pids.iter().flatten().copied().collect::<Vec<_>>());
+        for (status, pid) in statuses.iter_mut().zip(&pids) {
+            if let Some(stats) = pid.and_then(|pid| stats.get(&pid)) {
+                status.memory = format!("{:.1} MB", stats.rss_mb);
+                status.cpu = format!("{:.1}%", stats.cpu_percent);
+            }
+        }
+    }
+
     println!("{}", "FGP Services".bold());
     println!();
 
-    let table = Table::new(&statuses).to_string();
+    let mut table = Table::new(&statuses);
+    if !verbose {
+        // Memory/CPU columns are only populated in verbose mode
+        table.with(Disable::column(Columns::new(4..)));
+    }
     println!("{}", table);
 
+    if verbose {
+        print_orphans();
+    }
+
     Ok(())
 }
 
+/// List daemon processes that no socket or PID file accounts for.
+fn print_orphans() {
+    let orphans = super::orphans::scan();
+    println!();
+    if orphans.is_empty() {
+        println!("{}", "No orphaned daemon processes.".dimmed());
+        return;
+    }
+
+    println!("{}", "Orphaned daemons".bold());
+    let stats = process_stats(&orphans.iter().map(|orphan| orphan.pid).collect::<Vec<_>>());
+    for orphan in &orphans {
+        let started = orphan
+            .started
+            .as_deref()
+            .map(|t| format!(", started {}", t))
+            .unwrap_or_default();
+        let memory = stats
+            .get(&orphan.pid)
+            .map(|stats| format!(", {:.1} MB", stats.rss_mb))
+            .unwrap_or_default();
+        println!(
+            "  {} PID {} ({}{}{})",
+            "!".yellow().bold(),
+            orphan.pid,
+            orphan.service.bold(),
+            started,
+            memory
+        );
+        println!("    {}", orphan.command.dimmed());
+    }
+    println!();
+    println!(
+        "  These keep running without a socket or PID file. Stop one with {}.",
+        "fgp stop --orphan <pid>".cyan()
+    );
+}
+
 /// Format uptime seconds into human-readable string.
 fn format_uptime(secs: u64) -> String {
     if secs < 60 {
@@ -145,3 +396,78 @@ fn format_uptime(secs: u64) -> String {
         format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
     }
 }
+
+/// Read a daemon's PID from its pid file.
+pub(crate) fn read_pid_file(service: &str) -> Option<u64> {
+    fs::read_to_string(service_pid_path(service))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Read RSS and CPU usage for a set of processes. Processes that aren't
+/// running are left out.
+///
+/// CPU usage is measured between two refreshes, so this waits sysinfo's
+/// minimum update interval once for the whole set.
+pub fn process_stats(pids: &[u64]) -> HashMap<u64, ProcessStats> {
+    if pids.is_empty() {
+        return HashMap::new();
+    }
+
+    let pids: Vec<Pid> = pids.iter().map(|&pid| Pid::from_u32(pid as u32)).collect();
+    let refresh = ProcessRefreshKind::new().with_cpu().with_memory();
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh);
+
+    pids.iter()
+        .filter_map(|&pid| {
+            let process = system.process(pid)?;
+            let stats = ProcessStats {
+                rss_mb: process.memory() as f64 / (1024.0 * 1024.0),
+                cpu_percent: process.cpu_usage(),
+            };
+            Some((pid.as_u32() as u64, stats))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snapshot_markdown() {
+        let snapshots = vec![
+            ServiceSnapshot {
+                service: "gmail".to_string(),
+                running: true,
+                pid: Some(4242),
+                socket_path: "/tmp/gmail.sock".to_string(),
+                uptime_seconds: Some(3720),
+                latency_ms: Some(1.34),
+                last_health: Some(json!({"status": "healthy", "version": "1.2.0"})),
+            },
+            ServiceSnapshot {
+                service: "slack".to_string(),
+                running: false,
+                pid: None,
+                socket_path: "/tmp/slack.sock".to_string(),
+                uptime_seconds: None,
+                latency_ms: None,
+                last_health: None,
+            },
+        ];
+
+        let markdown = snapshot_markdown(&snapshots, "2026-01-02 03:04:05");
+        assert!(markdown.contains("Taken 2026-01-02 03:04:05"));
+        assert!(markdown.contains("| gmail | healthy | 1.2.0 | 1h 2m | 4242 | 1.3 ms |"));
+        assert!(markdown.contains("| slack | stopped | - | - | - | - |"));
+
+        assert!(snapshot_markdown(&[], "now").contains("No services installed."));
+    }
+}
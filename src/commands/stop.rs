@@ -1,11 +1,23 @@
 //! Stop a running daemon.
+//!
+//! Sends the daemon the protocol's `stop` call, or runs its `stop_command`
+//! if it has one (see [`super::daemon_command`]).
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::io::IsTerminal;
+use std::process::Command;
 
+use super::client::{self, Client};
+use super::daemon_command::{self, CommandPaths};
+use super::orphans;
 use super::service_socket_path;
 
 pub fn run(service: &str) -> Result<()> {
+    if let Some(stop_command) = daemon_command::configured(service, "stop_command")? {
+        return run_stop_command(service, &stop_command);
+    }
+
     let socket_path = service_socket_path(service);
 
     if !socket_path.exists() {
@@ -20,18 +32,17 @@ pub fn run(service: &str) -> Result<()> {
     println!("{} Stopping {}...", "→".blue().bold(), service.bold());
 
     // Connect and send stop command
-    let client = match fgp_daemon::FgpClient::new(&socket_path) {
-        Ok(c) => c,
-        Err(e) => {
+    match Client::at(&socket_path).call("stop", serde_json::json!({})) {
+        Err(client::Error::Connect { source, .. }) => {
             // Socket exists but can't connect - probably stale
-            println!("{} Could not connect to daemon: {}", "!".yellow().bold(), e);
+            println!(
+                "{} Could not connect to daemon: {}",
+                "!".yellow().bold(),
+                source
+            );
             println!("  Removing stale socket...");
             let _ = std::fs::remove_file(&socket_path);
-            return Ok(());
         }
-    };
-
-    match client.stop() {
         Ok(response) => {
             if response.ok {
                 println!("{} {} stopped.", "✓".green().bold(), service.bold());
@@ -54,3 +65,74 @@ pub fn run(service: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Stop `service` with its configured `stop_command`.
+fn run_stop_command(service: &str, stop_command: &[String]) -> Result<()> {
+    let paths = CommandPaths::for_service(service);
+    let args =
+        daemon_command::expand(stop_command, &paths).context("Invalid daemon.stop_command")?;
+
+    println!("{} Stopping {}...", "→".blue().bold(), service.bold());
+
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+    // A daemon set up by a skill alone may have no service directory
+    if paths.service_dir.is_dir() {
+        command.current_dir(&paths.service_dir);
+    }
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run stop command '{}'", args[0]))?;
+    if !status.success() {
+        bail!("Stop command exited with status: {}", status);
+    }
+
+    println!("{} {} stopped.", "✓".green().bold(), service.bold());
+    Ok(())
+}
+
+/// Terminate an orphaned daemon process (one `fgp status --verbose` lists),
+/// after confirming unless `yes` is set.
+pub fn orphan(pid: u64, yes: bool) -> Result<()> {
+    let Some(orphan) = orphans::scan().into_iter().find(|o| o.pid == pid) else {
+        bail!(
+            "PID {} is not an orphaned FGP daemon (see 'fgp status --verbose')",
+            pid
+        );
+    };
+
+    println!(
+        "{} PID {} looks like a {} daemon{}:",
+        "→".blue().bold(),
+        pid,
+        orphan.service.bold(),
+        orphan
+            .started
+            .as_deref()
+            .map(|t| format!(", started {}", t))
+            .unwrap_or_default()
+    );
+    println!("  {}", orphan.command.dimmed());
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("Stopping PID {} needs confirmation; rerun with --yes", pid);
+        }
+        if !super::confirm("Send it SIGTERM?")? {
+            println!("{} Left running.", "○".dimmed());
+            return Ok(());
+        }
+    }
+
+    if orphans::terminate(pid)? {
+        println!("{} PID {} stopped.", "✓".green().bold(), pid);
+    } else {
+        bail!(
+            "PID {} is still running after SIGTERM; it may need `kill -9 {}`",
+            pid,
+            pid
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! Language-independent text comparison for skill search and triggers.
+//!
+//! Skill names, descriptions and trigger keywords can be in any language, so
+//! lowercasing isn't enough to compare them. Text is compared after full
+//! Unicode case folding ("Straße" and "STRASSE" match) and normalization
+//! (a precomposed "ä" and "a" plus a combining diaeresis match).
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Combining dot above, left behind when "İ" is case folded.
+const COMBINING_DOT_ABOVE: char = '\u{307}';
+
+/// Case fold and NFC-normalize `text` for comparison.
+///
+/// Dotted and dotless i (Turkish "İ" and "ı") both fold to "i". Which one an
+/// uppercase "I" stands for depends on the text's language, which a skill
+/// doesn't declare, so the two are treated as the same letter.
+pub fn fold(text: &str) -> String {
+    let decomposed: String = text.nfd().collect();
+    let folded = caseless::default_case_fold_str(&decomposed);
+
+    let mut dotless = String::with_capacity(folded.len());
+    let mut chars = folded.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            'ı' => dotless.push('i'),
+            'i' => {
+                dotless.push('i');
+                chars.next_if_eq(&COMBINING_DOT_ABOVE);
+            }
+            _ => dotless.push(c),
+        }
+    }
+    dotless.nfc().collect()
+}
+
+/// Whether `haystack` contains `query`, which is already [`fold`]ed.
+pub fn contains_folded(haystack: &str, query: &str) -> bool {
+    fold(haystack).contains(query)
+}
+
+/// Whether `text` is in a script written without spaces between words
+/// (Chinese, Japanese, Thai and others), where word boundaries can't be
+/// found by splitting on whitespace.
+pub fn has_unspaced_script(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c,
+            '\u{0E00}'..='\u{0EFF}'     // Thai, Lao
+            | '\u{1000}'..='\u{109F}'   // Myanmar
+            | '\u{1780}'..='\u{17FF}'   // Khmer
+            | '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+            | '\u{31F0}'..='\u{31FF}'   // Katakana extensions
+            | '\u{3400}'..='\u{4DBF}'   // CJK extension A
+            | '\u{4E00}'..='\u{9FFF}'   // CJK unified ideographs
+            | '\u{F900}'..='\u{FAFF}'   // CJK compatibility ideographs
+            | '\u{FF66}'..='\u{FF9F}'   // Halfwidth Katakana
+            | '\u{20000}'..='\u{2FA1F}' // CJK extensions B and later
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_german() {
+        assert_eq!(fold("Ändern"), fold("ändern"));
+        assert_eq!(fold("STRASSE"), fold("Straße"));
+        // Combining diaeresis
+        assert_eq!(fold("a\u{308}ndern"), fold("ändern"));
+        assert!(contains_folded("Termine ÄNDERN", &fold("ändern")));
+    }
+
+    #[test]
+    fn test_fold_turkish() {
+        assert_eq!(fold("İstanbul"), "istanbul");
+        assert_eq!(fold("ISPARTA"), fold("ısparta"));
+        assert!(contains_folded("İZMİR hava durumu", &fold("izmir")));
+        assert!(contains_folded("Kış lastiği", &fold("KIŞ")));
+    }
+
+    #[test]
+    fn test_fold_japanese() {
+        // No case, so folding leaves the text as is
+        assert_eq!(fold("メールを送信"), "メールを送信");
+        assert!(contains_folded("Gmailでメールを送信", &fold("gmail")));
+        assert!(has_unspaced_script("メール"));
+        assert!(has_unspaced_script("send 邮件"));
+        assert!(!has_unspaced_script("send email"));
+        assert!(!has_unspaced_script("İzmir"));
+    }
+}
@@ -0,0 +1,413 @@
+//! Remove an installed daemon and everything FGP set up for it.
+//!
+//! `fgp uninstall <service>` stops the daemon if it is running, then removes:
+//!
+//! - `~/.fgp/services/<service>/` (manifest, binary, socket, PID file, logs)
+//!   and a log file the daemon's skill configured elsewhere
+//! - skills `fgp skill install` cached for the daemon, and their entries in
+//!   `installed_skills.json`
+//! - skill files `fgp install` copied into agent directories
+//! - agent registrations: `<service>-fgp` skill directories (Claude Code,
+//!   Codex, Windsurf, ...) and server entries in MCP configs (Cursor, Cline,
+//!   Zed)
+//!
+//! `--keep-config` leaves the service's config files (see [`is_config_file`])
+//! in its directory. `--dry-run` lists what would be removed. Uninstalling
+//! something that is already gone removes nothing and succeeds.
+//!
+//! If the daemon can't be stopped, nothing is removed unless `--force` is
+//! given, so a running daemon never loses its files.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::agents::{self, Registration};
+use super::client::Client;
+use super::{fgp_services_dir, install, service_socket_path, skill};
+
+/// How long to wait for a stopped daemon's socket to go away.
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One thing `fgp uninstall` does.
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// Stop the running daemon
+    Stop,
+    /// Delete a file or directory
+    Remove { path: PathBuf, what: String },
+    /// Delete everything in the service directory but its config files
+    Clear { dir: PathBuf, kept: Vec<String> },
+    /// Remove an agent's MCP server entry
+    Unregister {
+        agent: &'static str,
+        file: PathBuf,
+        key: &'static str,
+        name: String,
+    },
+    /// Drop skills from installed_skills.json
+    Forget { keys: Vec<String> },
+    /// Remove directories emptied by the steps before, up to `root`
+    Prune { dirs: Vec<PathBuf>, root: PathBuf },
+}
+
+impl Step {
+    fn describe(&self) -> String {
+        match self {
+            Step::Stop => "Stop the running daemon".to_string(),
+            Step::Remove { path, what } => format!("Remove {} {}", what, path.display()),
+            Step::Clear { dir, kept } => {
+                format!("Remove {} except {}", dir.display(), kept.join(", "))
+            }
+            Step::Unregister {
+                agent, file, name, ..
+            } => format!("Remove {} server '{}' from {}", agent, name, file.display()),
+            Step::Forget { keys } => format!(
+                "Forget installed skill(s) {} in installed_skills.json",
+                keys.join(", ")
+            ),
+            Step::Prune { root, .. } => format!("Remove {} if left empty", root.display()),
+        }
+    }
+
+    fn run(&self, service: &str) -> Result<()> {
+        match self {
+            Step::Stop => stop(service),
+            Step::Remove { path, .. } => remove_path(path),
+            Step::Clear { dir, .. } => {
+                for entry in fs::read_dir(dir)?.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if !is_config_file(&name) {
+                        remove_path(&entry.path())?;
+                    }
+                }
+                Ok(())
+            }
+            Step::Unregister {
+                file, key, name, ..
+            } => agents::remove_server(file, key, name),
+            Step::Forget { keys } => skill::forget_installed(keys),
+            Step::Prune { dirs, root } => {
+                for dir in dirs {
+                    let mut current = dir.as_path();
+                    // Fails (and stops) at the first directory that isn't empty
+                    while current.starts_with(root) && fs::remove_dir(current).is_ok() {
+                        match current.parent() {
+                            Some(parent) => current = parent,
+                            None => break,
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn run(service: &str, keep_config: bool, dry_run: bool, force: bool) -> Result<()> {
+    if service.is_empty() || service.contains(['/', '\\']) || service == "." || service == ".." {
+        bail!("Invalid service name '{}'", service);
+    }
+
+    let steps = plan(service, keep_config)?;
+    if steps.is_empty() {
+        println!(
+            "{} {} is not installed; nothing to remove",
+            "○".dimmed(),
+            service.bold()
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{} Would uninstall {}:", "→".blue().bold(), service.bold());
+        for step in &steps {
+            println!("  {}", step.describe());
+        }
+        return Ok(());
+    }
+
+    println!("{} Uninstalling {}...", "→".blue().bold(), service.bold());
+    let mut failed = 0;
+    for step in &steps {
+        match step.run(service) {
+            Ok(()) => {
+                if !matches!(step, Step::Prune { .. }) {
+                    println!("  {} {}", "✓".green(), step.describe());
+                }
+            }
+            Err(e) if *step == Step::Stop && force => {
+                println!(
+                    "  {} {}: {:#}; removing it anyway (--force)",
+                    "!".yellow().bold(),
+                    step.describe(),
+                    e
+                );
+            }
+            Err(e) if *step == Step::Stop => {
+                println!("  {} {}: {:#}", "✗".red(), step.describe(), e);
+                bail!(
+                    "Nothing was removed while {} is still running; stop it with 'fgp stop {}', or pass --force to remove it anyway",
+                    service,
+                    service
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {:#}", "✗".red(), step.describe(), e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!(
+            "{} step(s) failed; run 'fgp uninstall {}' again once fixed",
+            failed,
+            service
+        );
+    }
+    println!("{} {} uninstalled", "✓".green().bold(), service.bold());
+    Ok(())
+}
+
+/// Everything there is to undo for `service`, in the order to do it.
+fn plan(service: &str, keep_config: bool) -> Result<Vec<Step>> {
+    let mut steps = Vec::new();
+    let service_dir = fgp_services_dir().join(service);
+
+    if service_socket_path(service).exists() {
+        steps.push(Step::Stop);
+    }
+
+    // Read before the service directory (and the manifest in it) goes
+    let skill_files = install::installed_skill_files(&service_dir);
+    let log_file = skill::daemon_log_file(service).filter(|path| path.exists());
+    let cached = skill::installed_for_daemon(service)?;
+
+    if service_dir.exists() {
+        steps.extend(service_dir_step(&service_dir, keep_config)?);
+    }
+    if let Some(path) = log_file.filter(|path| !path.starts_with(&service_dir)) {
+        steps.push(Step::Remove {
+            path,
+            what: "log file".to_string(),
+        });
+    }
+
+    for (key, cache_dir) in &cached {
+        if cache_dir.exists() {
+            steps.push(Step::Remove {
+                path: cache_dir.clone(),
+                what: format!("cached skill {}", key),
+            });
+            // <marketplace>/<skill>/, once no other version is left in it
+            if let Some(skill_dir) = cache_dir.parent() {
+                steps.push(Step::Prune {
+                    dirs: vec![skill_dir.to_path_buf()],
+                    root: skill_dir.to_path_buf(),
+                });
+            }
+        }
+    }
+    if !cached.is_empty() {
+        steps.push(Step::Forget {
+            keys: cached.into_iter().map(|(key, _)| key).collect(),
+        });
+    }
+
+    let registrations = agents::registrations_of(service);
+    let registered_dirs: Vec<&PathBuf> = registrations
+        .iter()
+        .filter_map(|(_, registration)| match registration {
+            Registration::SkillDir(dir) => Some(dir),
+            Registration::Server { .. } => None,
+        })
+        .collect();
+
+    for (target, files) in skill_files {
+        // A file in a registered skill directory goes with the directory
+        let files: Vec<PathBuf> = files
+            .into_iter()
+            .filter(|file| !registered_dirs.iter().any(|dir| file.starts_with(dir)))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        let mut dirs: Vec<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .collect();
+        dirs.sort();
+        dirs.dedup();
+        for path in files {
+            steps.push(Step::Remove {
+                path,
+                what: "skill file".to_string(),
+            });
+        }
+        steps.push(Step::Prune { dirs, root: target });
+    }
+
+    for (agent, registration) in registrations {
+        steps.push(match registration {
+            Registration::SkillDir(path) => Step::Remove {
+                path,
+                what: format!("{} skill", agent),
+            },
+            Registration::Server { file, key, name } => Step::Unregister {
+                agent,
+                file,
+                key,
+                name,
+            },
+        });
+    }
+
+    Ok(steps)
+}
+
+/// Remove the service directory, or with `keep_config` everything in it
+/// but config files (nothing, if that's all there is).
+fn service_dir_step(service_dir: &Path, keep_config: bool) -> Result<Option<Step>> {
+    let remove_all = Step::Remove {
+        path: service_dir.to_path_buf(),
+        what: "service directory".to_string(),
+    };
+    if !keep_config {
+        return Ok(Some(remove_all));
+    }
+
+    let (mut kept, removed): (Vec<String>, Vec<String>) = fs::read_dir(service_dir)
+        .with_context(|| format!("Failed to read {}", service_dir.display()))?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .partition(|name| is_config_file(name));
+    if kept.is_empty() {
+        return Ok(Some(remove_all));
+    }
+    if removed.is_empty() {
+        return Ok(None);
+    }
+    kept.sort();
+    Ok(Some(Step::Clear {
+        dir: service_dir.to_path_buf(),
+        kept,
+    }))
+}
+
+/// Files in a service directory that hold the user's settings rather than
+/// anything the package or the daemon's runtime put there: `config.*`,
+/// `settings.*`, `*.toml`, `.env` and a `config/` directory.
+fn is_config_file(name: &str) -> bool {
+    name == "config"
+        || name == ".env"
+        || name.starts_with("config.")
+        || name.starts_with("settings.")
+        || name.ends_with(".toml")
+}
+
+/// Stop the daemon and wait for its socket to go away.
+fn stop(service: &str) -> Result<()> {
+    let socket_path = service_socket_path(service);
+    if let Err(e) = super::daemon_command::stop_service(service) {
+        // A socket nothing answers on is stale; it goes with the service
+        // directory
+        if Client::at(&socket_path).health().is_err() {
+            return Ok(());
+        }
+        bail!("the daemon is still running ({})", e);
+    }
+
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while socket_path.exists() {
+        if Instant::now() >= deadline {
+            bail!(
+                "daemon did not stop within {}s; stop it with 'fgp stop {}'",
+                STOP_TIMEOUT.as_secs(),
+                service
+            );
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+/// Delete a file, symlink or directory; a missing path is not an error.
+fn remove_path(path: &Path) -> Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+    .with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_config() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["manifest.json", "daemon.pid", "config.toml", ".env"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        fs::create_dir(dir.path().join("logs")).unwrap();
+        fs::write(dir.path().join("logs/daemon.log"), "").unwrap();
+
+        let step = service_dir_step(dir.path(), true).unwrap().unwrap();
+        assert_eq!(
+            step,
+            Step::Clear {
+                dir: dir.path().to_path_buf(),
+                kept: vec![".env".to_string(), "config.toml".to_string()],
+            }
+        );
+        step.run("test").unwrap();
+        let mut left: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec![".env", "config.toml"]);
+        // Only config left: nothing more to do
+        assert_eq!(service_dir_step(dir.path(), true).unwrap(), None);
+
+        // Nothing to keep: the whole directory goes
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("manifest.json"), "").unwrap();
+        assert!(matches!(
+            service_dir_step(dir.path(), true).unwrap(),
+            Some(Step::Remove { .. })
+        ));
+    }
+
+    #[test]
+    fn test_prune_and_remove() {
+        let root = tempfile::tempdir().unwrap();
+        let target = root.path().join("rules");
+        fs::create_dir_all(target.join("gmail")).unwrap();
+        fs::write(target.join("gmail/gmail.mdc"), "").unwrap();
+        fs::write(target.join("other.mdc"), "").unwrap();
+
+        remove_path(&target.join("gmail/gmail.mdc")).unwrap();
+        // Already gone
+        remove_path(&target.join("gmail/gmail.mdc")).unwrap();
+
+        Step::Prune {
+            dirs: vec![target.join("gmail")],
+            root: target.clone(),
+        }
+        .run("gmail")
+        .unwrap();
+        // The emptied directory goes; the target, which still has a file, stays
+        assert!(!target.join("gmail").exists());
+        assert!(target.join("other.mdc").exists());
+    }
+}
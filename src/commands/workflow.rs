@@ -2,8 +2,17 @@
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::lint_report;
+use super::workflow_engine::{
+    self, CancelReason, Cancellation, RunEvent, RunStatus, SaveSpec, StepStatus, Workflow,
+};
+use super::workflow_lint;
+use crate::tui::workflow::Outcome;
 
 /// Built-in workflow templates.
 static TEMPLATES: &[(&str, &str, &str)] = &[
@@ -47,12 +56,23 @@ steps:
     ),
 ];
 
-/// Run a workflow from a YAML file.
-pub fn run(file: &str, verbose: bool) -> Result<()> {
+/// Exit status when a run is cancelled with Ctrl-C (conventional for SIGINT).
+const EXIT_CANCELLED: i32 = 130;
+
+/// Exit status when a run exceeds its `timeout:` (matches `timeout(1)`).
+const EXIT_TIMED_OUT: i32 = 124;
+
+/// Run a workflow from a YAML file, following it in the terminal UI if `tui`.
+pub fn run(file: &str, verbose: bool, dry_run: bool, tui: bool) -> Result<()> {
     println!("{} Loading workflow from {}...", "→".blue().bold(), file);
 
     // Load and parse the workflow
-    let workflow = fgp_workflow::yaml::load_file(file).context("Failed to load workflow")?;
+    let workflow =
+        workflow_engine::load_file(Path::new(file)).context("Failed to load workflow")?;
+
+    if dry_run {
+        return print_dry_run(&workflow);
+    }
 
     println!(
         "{} Running workflow: {}",
@@ -65,49 +85,313 @@ pub fn run(file: &str, verbose: bool) -> Result<()> {
     }
 
     println!("  Steps: {}", workflow.steps.len());
+    if let Some(ref timeout) = workflow.timeout {
+        println!("  Timeout: {}", timeout);
+    }
     println!();
 
-    // Execute the workflow
-    let result = fgp_workflow::execute(&workflow)?;
+    // First Ctrl-C cancels gracefully, a second one force-quits
+    let cancel = Cancellation::new();
+    {
+        let cancel = cancel.clone();
+        let presses = AtomicUsize::new(0);
+        ctrlc::set_handler(move || {
+            if presses.fetch_add(1, Ordering::SeqCst) > 0 {
+                std::process::exit(EXIT_CANCELLED);
+            }
+            eprintln!(
+                "\n{} Cancelling workflow (press Ctrl-C again to force quit)...",
+                "!".yellow().bold()
+            );
+            cancel.cancel(CancelReason::Interrupted);
+        })
+        .context("Failed to install Ctrl-C handler")?;
+    }
+    let _timer = workflow.timeout()?.map(|t| cancel.start_timer(t));
 
-    // Print results
-    println!("{} Workflow completed!", "✓".green().bold());
-    println!();
+    let mut run = if tui {
+        // The view handles c and Ctrl-C itself while the terminal is in raw mode
+        match crate::tui::workflow::run(&workflow, &cancel)? {
+            Outcome::Finished(run) => *run,
+            Outcome::ForceQuit => std::process::exit(EXIT_CANCELLED),
+        }
+    } else {
+        // Execute the workflow; parallel steps report from their own threads,
+        // so verbose progress lines interleave
+        let run = workflow_engine::execute(&workflow, &cancel, |event| {
+            if verbose {
+                print_event(event);
+            }
+        });
+        if verbose {
+            println!();
+        }
+        run
+    };
+    run.file = Some(file.to_string());
+
+    if let Err(e) = workflow_engine::record_run(&run) {
+        eprintln!(
+            "{} Failed to record run history: {:#}",
+            "!".yellow().bold(),
+            e
+        );
+    }
 
-    if verbose {
+    if verbose || run.status != RunStatus::Completed {
         println!("Step Results:");
-        for step_result in &result.step_results {
+        for step in &run.steps {
+            let icon = match step.status {
+                StepStatus::Ok => "✓".green().bold(),
+                StepStatus::Failed => "✗".red().bold(),
+                StepStatus::Cancelled => "!".yellow().bold(),
+                StepStatus::Pending | StepStatus::Skipped => "○".dimmed(),
+            };
+            let detail = match step.status {
+                StepStatus::Pending => "not started".to_string(),
+                StepStatus::Skipped => "skipped: condition is false".to_string(),
+                _ if step.attempts > 1 => format!(
+                    "{:.1}ms, retried {} time(s)",
+                    step.duration_ms,
+                    step.attempts - 1
+                ),
+                _ => format!("{:.1}ms", step.duration_ms),
+            };
             println!(
-                "  {}. {} ({:.1}ms)",
-                step_result.index + 1,
-                format!("{}.{}", step_result.step.service, step_result.step.method).bold(),
-                step_result.duration_ms
+                "  {} {}. {}{} ({})",
+                icon,
+                step.index + 1,
+                format!("{}.{}", step.service, step.method).bold(),
+                if step.group.is_some() {
+                    " [parallel]".dimmed().to_string()
+                } else {
+                    String::new()
+                },
+                detail
             );
 
+            if step.status == StepStatus::Failed {
+                println!(
+                    "     {}",
+                    step.error.as_deref().unwrap_or("unknown error").red()
+                );
+                if run.status == RunStatus::Completed {
+                    println!("     {}", "continued (continue_on_error)".dimmed());
+                }
+            }
+
             // Print output variable if set
-            if let Some(ref output) = step_result.step.output {
+            if let (Some(ref output), StepStatus::Ok) = (&step.output, step.status) {
                 println!("     → {}", output.cyan());
             }
+            if let Some(ref saved) = step.saved {
+                println!("     {} {}", "saved".dimmed(), saved.cyan());
+            }
         }
         println!();
     }
 
-    println!("Total time: {:.1}ms", result.total_ms);
+    let finished = run
+        .steps
+        .iter()
+        .filter(|s| s.status == StepStatus::Ok)
+        .count();
+
+    match run.status {
+        RunStatus::Completed => {}
+        RunStatus::Failed => {
+            let step = run.failed_step().expect("failed run has a failed step");
+            bail!(
+                "Step {} ({}) failed: {}",
+                step.index + 1,
+                step.method,
+                step.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        RunStatus::Cancelled | RunStatus::TimedOut => {
+            let (what, code) = if run.status == RunStatus::TimedOut {
+                (
+                    format!(
+                        "timed out after {}",
+                        workflow.timeout.as_deref().unwrap_or("?")
+                    ),
+                    EXIT_TIMED_OUT,
+                )
+            } else {
+                ("cancelled".to_string(), EXIT_CANCELLED)
+            };
+            eprintln!(
+                "{} Workflow {} ({} of {} steps finished)",
+                "✗".red().bold(),
+                what,
+                finished,
+                run.steps.len()
+            );
+            std::process::exit(code);
+        }
+    }
+
+    // Print results
+    println!("{} Workflow completed!", "✓".green().bold());
+    println!();
+    println!("Total time: {:.1}ms", run.total_ms);
+
+    let saved: Vec<&str> = run
+        .steps
+        .iter()
+        .filter_map(|s| s.saved.as_deref())
+        .collect();
+    if !saved.is_empty() {
+        println!("Saved:");
+        for path in saved {
+            println!("  {}", path.cyan());
+        }
+    }
 
     // Print final result
     println!();
     println!("Result:");
-    println!("{}", serde_json::to_string_pretty(&result.result)?);
+    println!("{}", serde_json::to_string_pretty(&run.result())?);
 
     Ok(())
 }
 
-/// Validate a workflow file without running it.
-pub fn validate(file: &str) -> Result<()> {
+/// Print a progress line for a verbose run.
+fn print_event(event: &RunEvent) {
+    match event {
+        RunEvent::StepStarted {
+            index,
+            step,
+            attempt,
+        } => {
+            let name = format!("{}.{}", step.service, step.method);
+            if *attempt > 1 {
+                println!(
+                    "  {} {}. {} retrying (attempt {})",
+                    "▸".blue(),
+                    index + 1,
+                    name.bold(),
+                    attempt
+                );
+            } else {
+                println!("  {} {}. {} started", "▸".blue(), index + 1, name.bold());
+            }
+        }
+        RunEvent::StepFinished(record) => {
+            let name = format!("{}.{}", record.service, record.method).bold();
+            match record.status {
+                StepStatus::Ok => println!(
+                    "  {} {}. {} finished ({:.1}ms)",
+                    "✓".green(),
+                    record.index + 1,
+                    name,
+                    record.duration_ms
+                ),
+                StepStatus::Failed => println!(
+                    "  {} {}. {} failed ({:.1}ms)",
+                    "✗".red(),
+                    record.index + 1,
+                    name,
+                    record.duration_ms
+                ),
+                StepStatus::Cancelled => println!(
+                    "  {} {}. {} cancelled ({:.1}ms)",
+                    "!".yellow(),
+                    record.index + 1,
+                    name,
+                    record.duration_ms
+                ),
+                StepStatus::Skipped => {
+                    println!("  {} {}. {} skipped", "○".dimmed(), record.index + 1, name)
+                }
+                StepStatus::Pending => {}
+            }
+        }
+    }
+}
+
+/// `save: <path> (<format>[, overwrite])`, with the path under artifacts_dir.
+fn describe_save(workflow: &Workflow, save: &SaveSpec) -> String {
+    let path = match workflow.artifacts_dir {
+        Some(ref dir) => Path::new(dir).join(&save.path).display().to_string(),
+        None => save.path.clone(),
+    };
+    format!(
+        "save: {} ({}{})",
+        path,
+        save.format.as_str(),
+        if save.overwrite { ", overwrite" } else { "" }
+    )
+}
+
+/// Header shown before the first step of a parallel group.
+fn group_header(workflow: &Workflow, index: usize) -> Option<String> {
+    let group = &workflow.groups[workflow.steps[index].group?];
+    (group.first == index).then(|| {
+        format!(
+            "parallel ({} steps, max {} at a time):",
+            group.len, group.max_parallel
+        )
+    })
+}
+
+/// Print each step's params as they would be sent, without calling any daemon.
+///
+/// References to earlier step results can't be resolved without running
+/// those steps, so they are shown as written.
+fn print_dry_run(workflow: &Workflow) -> Result<()> {
+    println!(
+        "{} Dry run of workflow: {}",
+        "→".blue().bold(),
+        workflow.name.bold()
+    );
+    println!();
+
+    for (i, step) in workflow.steps.iter().enumerate() {
+        if let Some(header) = group_header(workflow, i) {
+            println!("  {}", header.dimmed());
+        }
+        let params = workflow_engine::interpolate(&step.params, &HashMap::new())?;
+        let params = if params.is_null() {
+            serde_json::json!({})
+        } else {
+            params
+        };
+
+        print!(
+            "  {}. {}",
+            i + 1,
+            format!("{}.{}", step.service, step.method).bold()
+        );
+        if let Some(ref id) = step.id {
+            print!(" {}", format!("[{}]", id).cyan());
+        }
+        println!();
+        if let Some(ref condition) = step.condition {
+            println!("     {}", format!("if: {}", condition).dimmed());
+        }
+        if let Some(ref save) = step.save {
+            println!("     {}", describe_save(workflow, save).dimmed());
+        }
+        for line in serde_json::to_string_pretty(&params)?.lines() {
+            println!("     {}", line);
+        }
+    }
+
+    println!();
+    println!("{}", "No daemons were called.".dimmed());
+
+    Ok(())
+}
+
+/// Validate a workflow file without running it, and with `lint` check it
+/// for mistakes that parse fine (see [`workflow_lint`]).
+pub fn validate(file: &str, lint: bool) -> Result<()> {
     println!("{} Validating workflow {}...", "→".blue().bold(), file);
 
     // Load and parse the workflow
-    let workflow = fgp_workflow::yaml::load_file(file).context("Failed to load workflow")?;
+    let workflow =
+        workflow_engine::load_file(Path::new(file)).context("Failed to load workflow")?;
 
     println!("{} Workflow is valid!", "✓".green().bold());
     println!();
@@ -117,17 +401,94 @@ pub fn validate(file: &str) -> Result<()> {
         println!("Description: {}", desc);
     }
 
+    if let Some(ref timeout) = workflow.timeout {
+        println!("Timeout: {}", timeout);
+    }
+
+    if let Some(ref dir) = workflow.artifacts_dir {
+        println!("Artifacts: {}", dir);
+    }
+
     println!("Steps: {}", workflow.steps.len());
 
     for (i, step) in workflow.steps.iter().enumerate() {
+        if let Some(header) = group_header(&workflow, i) {
+            println!("  {}", header.dimmed());
+        }
         println!(
             "  {}. {} → {}",
             i + 1,
             format!("{}.{}", step.service, step.method).bold(),
             step.output.as_deref().unwrap_or("-").cyan()
         );
+
+        let mut uses: Vec<String> = step.refs()?.into_iter().map(|r| r.step).collect();
+        uses.sort();
+        uses.dedup();
+        if let Some(ref condition) = step.condition {
+            println!("     {}", format!("if: {}", condition).dimmed());
+        }
+        if let Some(ref retry) = step.retry {
+            println!(
+                "     {}",
+                format!(
+                    "retry: {} attempts, {}ms apart{}",
+                    retry.attempts,
+                    retry.delay_ms,
+                    if step.continue_on_error {
+                        ", continue on error"
+                    } else {
+                        ""
+                    }
+                )
+                .dimmed()
+            );
+        } else if step.continue_on_error {
+            println!("     {}", "continue on error".dimmed());
+        }
+        if let Some(ref save) = step.save {
+            println!("     {}", describe_save(&workflow, save).dimmed());
+        }
+        if step.id.is_some() || !uses.is_empty() {
+            println!(
+                "     {}",
+                format!(
+                    "id: {}  uses: {}",
+                    step.id.as_deref().unwrap_or("-"),
+                    if uses.is_empty() {
+                        "-".to_string()
+                    } else {
+                        uses.join(", ")
+                    }
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    if lint {
+        print_lint(&workflow)?;
+    }
+
+    Ok(())
+}
+
+/// Print lint findings, failing if any is an error.
+fn print_lint(workflow: &Workflow) -> Result<()> {
+    println!();
+    let diagnostics = workflow_lint::lint(workflow);
+    if diagnostics.is_empty() {
+        println!("{} No lint findings", "✓".green().bold());
+        return Ok(());
     }
 
+    lint_report::print(&diagnostics);
+    let (errors, warnings) = lint_report::counts(&diagnostics);
+    println!();
+    if errors > 0 {
+        bail!("Lint found {} error(s) and {} warning(s)", errors, warnings);
+    }
+    println!("{} {} warning(s)", "!".yellow().bold(), warnings);
     Ok(())
 }
 
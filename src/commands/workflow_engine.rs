@@ -0,0 +1,1922 @@
+//! Workflow schema and step runner.
+//!
+//! Workflows are YAML files with a list of daemon calls executed in order,
+//! except that the children of a `parallel:` group run concurrently. The
+//! runner opens one connection per call and keeps a handle on each, so that
+//! a run can be cancelled (Ctrl-C or the workflow `timeout:`) by closing the
+//! in-flight connections, and every run leaves a record in
+//! `~/.fgp/workflow-runs.jsonl`.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::client::{self, Client, Request, Response, ShutdownHandle};
+
+// ============================================================================
+// Schema
+// ============================================================================
+
+/// Children of a `parallel:` group run at most this many at a time by default.
+pub const DEFAULT_MAX_PARALLEL: usize = 4;
+
+/// A workflow file.
+///
+/// `steps` is flat: the children of `parallel:` groups appear in file order
+/// with [`Step::group`] pointing into `groups`.
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    pub name: String,
+    pub description: Option<String>,
+    /// Whole-run time limit, e.g. `30s` or `5m`
+    pub timeout: Option<String>,
+    /// Directory `save:` paths are relative to (default: the current directory)
+    pub artifacts_dir: Option<String>,
+    pub steps: Vec<Step>,
+    pub groups: Vec<ParallelGroup>,
+}
+
+/// Steps `first..first + len` run concurrently.
+#[derive(Debug, Clone)]
+pub struct ParallelGroup {
+    pub first: usize,
+    pub len: usize,
+    pub max_parallel: usize,
+}
+
+/// The file format; entries are either steps or `parallel:` groups.
+#[derive(Deserialize)]
+struct RawWorkflow {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    timeout: Option<String>,
+    #[serde(default)]
+    artifacts_dir: Option<String>,
+    steps: Vec<serde_yaml::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawGroup {
+    parallel: Vec<serde_yaml::Value>,
+    #[serde(default = "default_max_parallel")]
+    max_parallel: usize,
+}
+
+fn default_max_parallel() -> usize {
+    DEFAULT_MAX_PARALLEL
+}
+
+/// A single daemon call.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Step {
+    /// Identifier later steps use to reference this step's result
+    #[serde(default)]
+    pub id: Option<String>,
+    pub service: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    /// Name the step's result is stored under
+    #[serde(default)]
+    pub output: Option<String>,
+    /// Condition that must hold for the step to run
+    #[serde(default, rename = "if")]
+    pub condition: Option<String>,
+    /// Retry policy for failed calls
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    /// Keep going if the step still fails after its retries
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// Write the step's result to a file
+    #[serde(default)]
+    pub save: Option<SaveSpec>,
+    /// Index into [`Workflow::groups`] for children of a `parallel:` group
+    #[serde(skip)]
+    pub group: Option<usize>,
+}
+
+/// How often to retry a failing step.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first
+    pub attempts: u32,
+    /// Pause between attempts
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// Where and how to write a step's result.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveSpec {
+    /// Destination file; may use `${{ steps.<id>.result... }}` templates
+    pub path: String,
+    #[serde(default)]
+    pub format: SaveFormat,
+    /// Replace the file if it already exists
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// File format for a saved result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SaveFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    Yaml,
+    /// Strings as-is, anything else as compact JSON
+    Raw,
+}
+
+impl SaveFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SaveFormat::Json => "json",
+            SaveFormat::Yaml => "yaml",
+            SaveFormat::Raw => "raw",
+        }
+    }
+}
+
+impl Step {
+    /// The parsed `if:` condition, if any.
+    pub fn condition(&self) -> Result<Option<Condition>> {
+        self.condition
+            .as_deref()
+            .map(|c| Condition::parse(c).with_context(|| format!("Invalid condition '{}'", c)))
+            .transpose()
+    }
+
+    /// Number of attempts the retry policy allows.
+    fn attempts(&self) -> u32 {
+        self.retry.as_ref().map(|r| r.attempts).unwrap_or(1)
+    }
+
+    /// Every step reference in the step's params, condition, and save path.
+    pub fn refs(&self) -> Result<Vec<StepRef>> {
+        let mut refs = step_refs(&self.params)?;
+        if let Some(condition) = self.condition()? {
+            refs.extend(condition.refs());
+        }
+        if let Some(ref save) = self.save {
+            refs.extend(step_refs(&Value::String(save.path.clone()))?);
+        }
+        Ok(refs)
+    }
+}
+
+impl Workflow {
+    /// The parsed `timeout:` value, if any.
+    pub fn timeout(&self) -> Result<Option<Duration>> {
+        self.timeout
+            .as_deref()
+            .map(|t| {
+                let duration = super::history::parse_duration(t)
+                    .with_context(|| format!("Invalid workflow timeout '{}'", t))?;
+                duration
+                    .to_std()
+                    .ok()
+                    .filter(|d| !d.is_zero())
+                    .with_context(|| format!("Workflow timeout '{}' must be positive", t))
+            })
+            .transpose()
+    }
+}
+
+/// Load and check a workflow file.
+pub fn load_file(path: &Path) -> Result<Workflow> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&content)
+}
+
+/// Parse and check a workflow from YAML.
+pub fn parse(content: &str) -> Result<Workflow> {
+    let raw: RawWorkflow = serde_yaml::from_str(content).context("Invalid workflow YAML")?;
+    let workflow = flatten(raw)?;
+
+    if workflow.name.trim().is_empty() {
+        bail!("Workflow name is empty");
+    }
+    if workflow.steps.is_empty() {
+        bail!("Workflow '{}' has no steps", workflow.name);
+    }
+    for (i, step) in workflow.steps.iter().enumerate() {
+        if step.service.trim().is_empty() || step.method.trim().is_empty() {
+            bail!("Step {} needs both a service and a method", i + 1);
+        }
+        if !(step.params.is_null() || step.params.is_object()) {
+            bail!("Step {} ({}): params must be a mapping", i + 1, step.method);
+        }
+        if step
+            .retry
+            .as_ref()
+            .map(|r| r.attempts == 0)
+            .unwrap_or(false)
+        {
+            bail!(
+                "Step {} ({}): retry.attempts must be at least 1",
+                i + 1,
+                step.method
+            );
+        }
+        step.condition()
+            .with_context(|| format!("Step {} ({})", i + 1, step.method))?;
+        if let Some(ref save) = step.save {
+            check_save_path(&save.path, workflow.artifacts_dir.is_some())
+                .with_context(|| format!("Step {} ({})", i + 1, step.method))?;
+        }
+    }
+    workflow.timeout()?;
+    check_references(&workflow)?;
+
+    Ok(workflow)
+}
+
+/// Expand `parallel:` groups into the flat step list.
+fn flatten(raw: RawWorkflow) -> Result<Workflow> {
+    let mut steps = Vec::new();
+    let mut groups = Vec::new();
+
+    let parse_step = |value: serde_yaml::Value, number: usize| -> Result<Step> {
+        serde_yaml::from_value(value).with_context(|| format!("Invalid step {}", number))
+    };
+
+    for entry in raw.steps {
+        let is_group = entry
+            .as_mapping()
+            .map(|m| m.contains_key("parallel"))
+            .unwrap_or(false);
+        if !is_group {
+            steps.push(parse_step(entry, steps.len() + 1)?);
+            continue;
+        }
+
+        let first = steps.len();
+        let group: RawGroup = serde_yaml::from_value(entry)
+            .with_context(|| format!("Invalid parallel group at step {}", first + 1))?;
+        if group.parallel.is_empty() {
+            bail!("Parallel group at step {} has no steps", first + 1);
+        }
+        if group.max_parallel == 0 {
+            bail!(
+                "Parallel group at step {}: max_parallel must be at least 1",
+                first + 1
+            );
+        }
+
+        for child in group.parallel {
+            if child
+                .as_mapping()
+                .map(|m| m.contains_key("parallel"))
+                .unwrap_or(false)
+            {
+                bail!("Parallel groups can't be nested (step {})", steps.len() + 1);
+            }
+            let mut step = parse_step(child, steps.len() + 1)?;
+            step.group = Some(groups.len());
+            steps.push(step);
+        }
+        groups.push(ParallelGroup {
+            first,
+            len: steps.len() - first,
+            max_parallel: group.max_parallel,
+        });
+    }
+
+    Ok(Workflow {
+        name: raw.name,
+        description: raw.description,
+        timeout: raw.timeout,
+        artifacts_dir: raw.artifacts_dir,
+        steps,
+        groups,
+    })
+}
+
+/// Check step ids and make sure every `${{ steps.<id>... }}` reference points
+/// at an earlier step outside the referencing step's parallel group.
+fn check_references(workflow: &Workflow) -> Result<()> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (i, step) in workflow.steps.iter().enumerate() {
+        if let Some(ref id) = step.id {
+            if id.is_empty()
+                || !id
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            {
+                bail!(
+                    "Step {} has an invalid id '{}' (use letters, digits, '_' or '-')",
+                    i + 1,
+                    id
+                );
+            }
+            if let Some(first) = seen.insert(id, i) {
+                bail!(
+                    "Duplicate step id '{}' (steps {} and {})",
+                    id,
+                    first + 1,
+                    i + 1
+                );
+            }
+        }
+    }
+
+    for (i, step) in workflow.steps.iter().enumerate() {
+        let refs = step
+            .refs()
+            .with_context(|| format!("Step {} ({})", i + 1, step.method))?;
+        for reference in refs {
+            match seen.get(reference.step.as_str()) {
+                Some(&j)
+                    if j != i && step.group.is_some() && workflow.steps[j].group == step.group =>
+                {
+                    bail!(
+                        "Step {} ({}) references step '{}' in the same parallel group; move it before the group",
+                        i + 1,
+                        step.method,
+                        reference.step
+                    )
+                }
+                Some(&j) if j < i => {}
+                Some(&j) if j == i => bail!(
+                    "Step {} ({}) references its own result '{}'",
+                    i + 1,
+                    step.method,
+                    reference.step
+                ),
+                Some(&j) => bail!(
+                    "Step {} ({}) references step '{}', which runs later (step {})",
+                    i + 1,
+                    step.method,
+                    reference.step,
+                    j + 1
+                ),
+                None => bail!(
+                    "Step {} ({}) references unknown step '{}'",
+                    i + 1,
+                    step.method,
+                    reference.step
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Templates
+// ============================================================================
+
+/// A `${{ steps.<id>.result.<path> }}` reference to an earlier step's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepRef {
+    pub step: String,
+    /// Path into the result; numeric segments index arrays
+    pub path: Vec<String>,
+}
+
+impl StepRef {
+    fn parse(expr: &str) -> Result<Self> {
+        let mut parts = expr.split('.');
+        let (Some("steps"), Some(step), Some("result")) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            bail!(
+                "Unsupported expression '{}' (expected steps.<id>.result[.<path>])",
+                expr
+            );
+        };
+        let path: Vec<String> = parts.map(|p| p.to_string()).collect();
+        if step.is_empty() || path.iter().any(|p| p.is_empty()) {
+            bail!("Invalid expression '{}': empty path segment", expr);
+        }
+        Ok(Self {
+            step: step.to_string(),
+            path,
+        })
+    }
+
+    /// Look the reference up in a step's result.
+    fn lookup<'a>(&self, result: &'a Value) -> Option<&'a Value> {
+        lookup_path(result, &self.path)
+    }
+}
+
+/// Follow a dotted path into a value; numeric segments index arrays.
+pub fn lookup_path<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(segment),
+    })
+}
+
+impl std::fmt::Display for StepRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "steps.{}.result", self.step)?;
+        for segment in &self.path {
+            write!(f, ".{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// A template expression found in a string, with its byte range.
+struct Placeholder {
+    start: usize,
+    end: usize,
+    reference: StepRef,
+}
+
+/// Find every `${{ ... }}` expression in a string.
+fn placeholders(text: &str) -> Result<Vec<Placeholder>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = text[offset..].find("${{") {
+        let start = offset + open;
+        let Some(close) = text[start..].find("}}") else {
+            bail!("Unterminated '${{{{' in '{}'", text);
+        };
+        let end = start + close + 2;
+        let reference = StepRef::parse(text[start + 3..end - 2].trim())?;
+        found.push(Placeholder {
+            start,
+            end,
+            reference,
+        });
+        offset = end;
+    }
+
+    Ok(found)
+}
+
+/// All step references in a params value.
+pub fn step_refs(value: &Value) -> Result<Vec<StepRef>> {
+    let mut refs = Vec::new();
+    collect_refs(value, &mut refs)?;
+    Ok(refs)
+}
+
+fn collect_refs(value: &Value, refs: &mut Vec<StepRef>) -> Result<()> {
+    match value {
+        Value::String(text) => {
+            refs.extend(placeholders(text)?.into_iter().map(|p| p.reference));
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, refs)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values() {
+                collect_refs(item, refs)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Substitute step results into a params value.
+///
+/// A string that is exactly one expression is replaced by the referenced
+/// JSON value (keeping its type); expressions embedded in longer strings are
+/// replaced by their text. References to steps missing from `results` are
+/// left as-is, which is what `--dry-run` shows.
+pub fn interpolate(value: &Value, results: &HashMap<String, Value>) -> Result<Value> {
+    Ok(match value {
+        Value::String(text) => interpolate_str(text, results)?,
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| interpolate(item, results))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), interpolate(v, results)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+fn interpolate_str(text: &str, results: &HashMap<String, Value>) -> Result<Value> {
+    let found = placeholders(text)?;
+    if found.is_empty() {
+        return Ok(Value::String(text.to_string()));
+    }
+
+    let resolve = |reference: &StepRef| -> Result<Option<Value>> {
+        let Some(result) = results.get(&reference.step) else {
+            return Ok(None);
+        };
+        match reference.lookup(result) {
+            Some(value) => Ok(Some(value.clone())),
+            None => bail!(
+                "'{}' not found in the result of step '{}'",
+                reference,
+                reference.step
+            ),
+        }
+    };
+
+    // Whole-string expression: keep the JSON type
+    if found.len() == 1 && found[0].start == 0 && found[0].end == text.len() {
+        return Ok(resolve(&found[0].reference)?.unwrap_or_else(|| Value::String(text.to_string())));
+    }
+
+    let mut out = String::new();
+    let mut last = 0;
+    for placeholder in &found {
+        out.push_str(&text[last..placeholder.start]);
+        match resolve(&placeholder.reference)? {
+            Some(Value::String(s)) => out.push_str(&s),
+            Some(value) => out.push_str(&value.to_string()),
+            None => out.push_str(&text[placeholder.start..placeholder.end]),
+        }
+        last = placeholder.end;
+    }
+    out.push_str(&text[last..]);
+
+    Ok(Value::String(out))
+}
+
+// ============================================================================
+// Conditions
+// ============================================================================
+
+/// An `if:` condition: one operand tested for truthiness, or a comparison
+/// such as `${{ steps.list.result.count }} > 0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    left: Operand,
+    comparison: Option<(CompareOp, Operand)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Ref(StepRef),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    fn is_ordering(self) -> bool {
+        !matches!(self, CompareOp::Eq | CompareOp::Ne)
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    Operand(Operand),
+    Op(CompareOp),
+}
+
+impl Condition {
+    /// Parse and type-check a condition.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut tokens = tokenize(text)?.into_iter();
+
+        let left = match tokens.next() {
+            Some(Token::Operand(operand)) => operand,
+            Some(Token::Op(_)) => bail!("expected a value before the operator"),
+            None => bail!("condition is empty"),
+        };
+
+        let comparison = match tokens.next() {
+            None => None,
+            Some(Token::Op(op)) => match tokens.next() {
+                Some(Token::Operand(right)) => Some((op, right)),
+                _ => bail!("expected a value after the operator"),
+            },
+            Some(Token::Operand(_)) => {
+                bail!("expected an operator (==, !=, >, >=, <, <=) between values")
+            }
+        };
+
+        if tokens.next().is_some() {
+            bail!("only a single comparison is supported");
+        }
+
+        if let Some((op, ref right)) = comparison {
+            if op.is_ordering() {
+                for operand in [&left, right] {
+                    if let Operand::Literal(value) = operand {
+                        if !(value.is_number() || value.is_string()) {
+                            bail!("cannot order-compare {}", value);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self { left, comparison })
+    }
+
+    /// Step references used by the condition.
+    pub fn refs(&self) -> Vec<StepRef> {
+        let mut operands = vec![&self.left];
+        if let Some((_, ref right)) = self.comparison {
+            operands.push(right);
+        }
+        operands
+            .into_iter()
+            .filter_map(|operand| match operand {
+                Operand::Ref(reference) => Some(reference.clone()),
+                Operand::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Evaluate against the results so far. Missing results and paths are
+    /// `null`, and comparisons between mismatched types are false.
+    pub fn evaluate(&self, results: &HashMap<String, Value>) -> bool {
+        let resolve = |operand: &Operand| match operand {
+            Operand::Literal(value) => value.clone(),
+            Operand::Ref(reference) => results
+                .get(&reference.step)
+                .and_then(|result| reference.lookup(result))
+                .cloned()
+                .unwrap_or(Value::Null),
+        };
+
+        let left = resolve(&self.left);
+        let Some((op, ref right)) = self.comparison else {
+            return truthy(&left);
+        };
+        let right = resolve(right);
+
+        let ordering = match (&left, &right) {
+            (Value::Number(a), Value::Number(b)) => a
+                .as_f64()
+                .zip(b.as_f64())
+                .and_then(|(a, b)| a.partial_cmp(&b)),
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+
+        match op {
+            CompareOp::Eq => ordering.map(|o| o.is_eq()).unwrap_or(left == right),
+            CompareOp::Ne => !ordering.map(|o| o.is_eq()).unwrap_or(left == right),
+            CompareOp::Gt => ordering.map(|o| o.is_gt()).unwrap_or(false),
+            CompareOp::Ge => ordering.map(|o| o.is_ge()).unwrap_or(false),
+            CompareOp::Lt => ordering.map(|o| o.is_lt()).unwrap_or(false),
+            CompareOp::Le => ordering.map(|o| o.is_le()).unwrap_or(false),
+        }
+    }
+}
+
+/// `null`, `false`, `0`, and empty strings, arrays, and objects are false.
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().map(|n| n != 0.0).unwrap_or(true),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+
+    while !rest.is_empty() {
+        if rest.starts_with("${{") {
+            let Some(close) = rest.find("}}") else {
+                bail!("unterminated '${{{{'");
+            };
+            let reference = StepRef::parse(rest[3..close].trim())?;
+            tokens.push(Token::Operand(Operand::Ref(reference)));
+            rest = &rest[close + 2..];
+        } else if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let Some(end) = rest[1..].find(quote) else {
+                bail!("unterminated string");
+            };
+            let literal = rest[1..1 + end].to_string();
+            tokens.push(Token::Operand(Operand::Literal(Value::String(literal))));
+            rest = &rest[end + 2..];
+        } else if let Some((op, len)) = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            (">=", CompareOp::Ge),
+            ("<=", CompareOp::Le),
+            (">", CompareOp::Gt),
+            ("<", CompareOp::Lt),
+        ]
+        .iter()
+        .find(|(symbol, _)| rest.starts_with(symbol))
+        .map(|(symbol, op)| (*op, symbol.len()))
+        {
+            tokens.push(Token::Op(op));
+            rest = &rest[len..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "=!<>".contains(c))
+                .unwrap_or(rest.len())
+                .max(rest.chars().next().map(|c| c.len_utf8()).unwrap_or(0));
+            let word = &rest[..end];
+            let value = match word {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                _ => match serde_json::from_str::<serde_json::Number>(word) {
+                    Ok(number) => Value::Number(number),
+                    Err(_) => bail!("unexpected '{}' (quote string values)", word),
+                },
+            };
+            tokens.push(Token::Operand(Operand::Literal(value)));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+// ============================================================================
+// Cancellation
+// ============================================================================
+
+/// Why a run was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// Ctrl-C
+    Interrupted,
+    /// The workflow `timeout:` elapsed
+    TimedOut,
+}
+
+/// Shared cancellation state for a run.
+///
+/// Cancelling stops new steps from starting and shuts down the sockets of
+/// calls in flight, which unblocks their reads and tells the daemon the
+/// client has gone away.
+#[derive(Clone, Default)]
+pub struct Cancellation {
+    inner: Arc<CancelState>,
+}
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    reason: Mutex<Option<CancelReason>>,
+    next_call: AtomicU64,
+    in_flight: Mutex<HashMap<u64, ShutdownHandle>>,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the run. Only the first reason is kept.
+    pub fn cancel(&self, reason: CancelReason) {
+        {
+            let mut current = self.inner.reason.lock().unwrap();
+            if current.is_none() {
+                *current = Some(reason);
+            }
+        }
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+
+        for (_, handle) in self.inner.in_flight.lock().unwrap().drain() {
+            handle.shutdown();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn reason(&self) -> Option<CancelReason> {
+        *self.inner.reason.lock().unwrap()
+    }
+
+    /// Track an in-flight connection until [`untrack`](Self::untrack) is
+    /// called with the returned id. If the run was already cancelled the
+    /// connection is shut down immediately.
+    fn track(&self, handle: ShutdownHandle) -> u64 {
+        let id = self.inner.next_call.fetch_add(1, Ordering::SeqCst);
+        let mut in_flight = self.inner.in_flight.lock().unwrap();
+        if self.is_cancelled() {
+            handle.shutdown();
+        } else {
+            in_flight.insert(id, handle);
+        }
+        id
+    }
+
+    fn untrack(&self, id: u64) {
+        self.inner.in_flight.lock().unwrap().remove(&id);
+    }
+
+    /// Cancel with [`CancelReason::TimedOut`] once `timeout` elapses, unless
+    /// the returned guard is dropped first.
+    pub fn start_timer(&self, timeout: Duration) -> TimerGuard {
+        let (tx, rx) = mpsc::channel::<()>();
+        let cancel = self.clone();
+        std::thread::spawn(move || {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = rx.recv_timeout(timeout) {
+                cancel.cancel(CancelReason::TimedOut);
+            }
+        });
+        TimerGuard { _stop: tx }
+    }
+}
+
+/// Stops the timeout timer when dropped.
+pub struct TimerGuard {
+    _stop: mpsc::Sender<()>,
+}
+
+// ============================================================================
+// Run records
+// ============================================================================
+
+/// Final state of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Completed,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+/// State of a step when the run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    /// Never started
+    Pending,
+    Ok,
+    Failed,
+    /// In flight when the run was cancelled
+    Cancelled,
+    /// Not run because its `if:` condition was false
+    Skipped,
+}
+
+/// Outcome of one step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub index: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub service: String,
+    pub method: String,
+    pub status: StepStatus,
+    pub duration_ms: f64,
+    /// Calls made, including retries
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Parallel group the step belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<usize>,
+    /// File the result was written to (`save:`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved: Option<String>,
+    #[serde(skip)]
+    pub result: Value,
+}
+
+/// A workflow run, as written to the run history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub workflow: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// RFC 3339 timestamps (UTC)
+    pub started_at: String,
+    pub finished_at: String,
+    pub status: RunStatus,
+    pub total_ms: f64,
+    pub steps: Vec<StepRecord>,
+}
+
+impl WorkflowRun {
+    /// Result of the last step that completed.
+    pub fn result(&self) -> Value {
+        self.steps
+            .iter()
+            .rev()
+            .find(|s| s.status == StepStatus::Ok)
+            .map(|s| s.result.clone())
+            .unwrap_or(Value::Null)
+    }
+
+    /// The last step that failed, if any.
+    pub fn failed_step(&self) -> Option<&StepRecord> {
+        self.steps
+            .iter()
+            .rev()
+            .find(|s| s.status == StepStatus::Failed)
+    }
+}
+
+/// Path of the workflow run history.
+pub fn runs_path() -> PathBuf {
+    super::fgp_home().join("workflow-runs.jsonl")
+}
+
+/// Append a run to the run history.
+pub fn record_run(run: &WorkflowRun) -> Result<()> {
+    let path = runs_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    Ok(())
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Progress reported while a workflow runs.
+///
+/// Children of a parallel group report from their own threads, so events for
+/// different steps interleave.
+#[derive(Debug)]
+pub enum RunEvent<'a> {
+    /// A call is about to be made (`attempt` counts from 1)
+    StepStarted {
+        index: usize,
+        step: &'a Step,
+        attempt: u32,
+    },
+    /// A step reached its final state
+    StepFinished(&'a StepRecord),
+}
+
+/// A [`RunEvent`] that owns its data, for reporting progress to another
+/// thread, followed by the finished run.
+#[derive(Debug, Clone)]
+pub enum RunUpdate {
+    StepStarted {
+        index: usize,
+        attempt: u32,
+    },
+    StepFinished(Box<StepRecord>),
+    /// The run is over; nothing is sent after this
+    Finished(Box<WorkflowRun>),
+}
+
+impl From<&RunEvent<'_>> for RunUpdate {
+    fn from(event: &RunEvent) -> Self {
+        match *event {
+            RunEvent::StepStarted { index, attempt, .. } => Self::StepStarted { index, attempt },
+            RunEvent::StepFinished(record) => Self::StepFinished(Box::new(record.clone())),
+        }
+    }
+}
+
+/// Run a workflow (as [`execute`] does) on its own thread, sending progress
+/// and then the finished run on `updates` instead of printing anything.
+pub fn spawn<T>(
+    workflow: Workflow,
+    cancel: Cancellation,
+    updates: mpsc::Sender<T>,
+) -> std::thread::JoinHandle<()>
+where
+    T: From<RunUpdate> + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let run = execute(&workflow, &cancel, |event| {
+            let _ = updates.send(RunUpdate::from(event).into());
+        });
+        let _ = updates.send(RunUpdate::Finished(Box::new(run)).into());
+    })
+}
+
+/// Run a workflow against the installed daemons, starting them as needed.
+pub fn execute<E>(workflow: &Workflow, cancel: &Cancellation, on_event: E) -> WorkflowRun
+where
+    E: Fn(&RunEvent) + Sync,
+{
+    execute_with(
+        workflow,
+        cancel,
+        |service| {
+            let socket_path = super::service_socket_path(service);
+            super::call::ensure_running(service, &socket_path, false)?;
+            Ok(socket_path)
+        },
+        on_event,
+    )
+}
+
+/// Run a workflow, resolving each service to a socket with `connect`.
+pub fn execute_with<F, E>(
+    workflow: &Workflow,
+    cancel: &Cancellation,
+    connect: F,
+    on_event: E,
+) -> WorkflowRun
+where
+    F: Fn(&str) -> Result<PathBuf> + Sync,
+    E: Fn(&RunEvent) + Sync,
+{
+    let started_at = Utc::now().to_rfc3339();
+    let start = Instant::now();
+
+    let mut steps: Vec<StepRecord> = workflow
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| StepRecord {
+            index,
+            id: step.id.clone(),
+            service: step.service.clone(),
+            method: step.method.clone(),
+            status: StepStatus::Pending,
+            duration_ms: 0.0,
+            attempts: 0,
+            output: step.output.clone(),
+            error: None,
+            group: step.group,
+            saved: None,
+            result: Value::Null,
+        })
+        .collect();
+
+    let runner = Runner {
+        cancel,
+        connect,
+        on_event,
+        artifacts_dir: workflow
+            .artifacts_dir
+            .as_deref()
+            .map(|dir| PathBuf::from(shellexpand::tilde(dir).as_ref())),
+    };
+    let mut status = RunStatus::Completed;
+    let mut results: HashMap<String, Value> = HashMap::new();
+
+    let mut i = 0;
+    while i < workflow.steps.len() && !cancel.is_cancelled() {
+        // A unit is either one step or a whole parallel group
+        let (len, max_parallel) = match workflow.steps[i].group {
+            Some(group) => (
+                workflow.groups[group].len,
+                workflow.groups[group].max_parallel,
+            ),
+            None => (1, 1),
+        };
+        let unit = &workflow.steps[i..i + len];
+        let records = &mut steps[i..i + len];
+        i += len;
+
+        if len == 1 {
+            runner.run_step(&unit[0], &mut records[0], &results);
+        } else {
+            runner.run_group(unit, records, max_parallel, &results);
+        }
+
+        let mut stop = false;
+        for (step, record) in unit.iter().zip(records.iter_mut()) {
+            match record.status {
+                StepStatus::Ok => {
+                    if let Some(ref id) = step.id {
+                        results.insert(id.clone(), record.result.clone());
+                    }
+                }
+                StepStatus::Cancelled => stop = true,
+                StepStatus::Failed if !step.continue_on_error => {
+                    status = RunStatus::Failed;
+                    stop = true;
+                }
+                _ => {}
+            }
+        }
+        if stop {
+            break;
+        }
+    }
+
+    if status == RunStatus::Completed {
+        status = match cancel.reason() {
+            Some(CancelReason::Interrupted) => RunStatus::Cancelled,
+            Some(CancelReason::TimedOut) => RunStatus::TimedOut,
+            None => RunStatus::Completed,
+        };
+    }
+
+    WorkflowRun {
+        workflow: workflow.name.clone(),
+        file: None,
+        started_at,
+        finished_at: Utc::now().to_rfc3339(),
+        status,
+        total_ms: start.elapsed().as_secs_f64() * 1000.0,
+        steps,
+    }
+}
+
+/// What every step of a run shares.
+struct Runner<'a, F, E> {
+    cancel: &'a Cancellation,
+    connect: F,
+    on_event: E,
+    /// Resolved `artifacts_dir`, if the workflow sets one
+    artifacts_dir: Option<PathBuf>,
+}
+
+impl<F, E> Runner<'_, F, E>
+where
+    F: Fn(&str) -> Result<PathBuf> + Sync,
+    E: Fn(&RunEvent) + Sync,
+{
+    /// Run the children of a parallel group, at most `max_parallel` at a time.
+    ///
+    /// Children only see results from before the group. Once a child fails
+    /// (without `continue_on_error`) or the run is cancelled, children that
+    /// haven't started stay pending.
+    fn run_group(
+        &self,
+        steps: &[Step],
+        records: &mut [StepRecord],
+        max_parallel: usize,
+        results: &HashMap<String, Value>,
+    ) {
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let slots: Vec<Mutex<&mut StepRecord>> = records.iter_mut().map(Mutex::new).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..max_parallel.min(steps.len()) {
+                scope.spawn(|| loop {
+                    if failed.load(Ordering::SeqCst) || self.cancel.is_cancelled() {
+                        break;
+                    }
+                    let n = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(step) = steps.get(n) else {
+                        break;
+                    };
+
+                    let mut record = slots[n].lock().unwrap();
+                    self.run_step(step, &mut record, results);
+                    if record.status == StepStatus::Failed && !step.continue_on_error {
+                        failed.store(true, Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Run one step: check its condition, call it (retrying as configured),
+    /// then save its result if asked to.
+    fn run_step(&self, step: &Step, record: &mut StepRecord, results: &HashMap<String, Value>) {
+        let cancel = self.cancel;
+        if cancel.is_cancelled() {
+            return;
+        }
+
+        match step.condition() {
+            Ok(Some(condition)) if !condition.evaluate(results) => {
+                record.status = StepStatus::Skipped;
+                (self.on_event)(&RunEvent::StepFinished(record));
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                record.status = StepStatus::Failed;
+                record.error = Some(format!("{:#}", e));
+                (self.on_event)(&RunEvent::StepFinished(record));
+                return;
+            }
+        }
+
+        let step_start = Instant::now();
+        let mut outcome = Err(anyhow::anyhow!("step did not run"));
+        while record.attempts < step.attempts() {
+            if record.attempts > 0 {
+                let delay = step.retry.as_ref().map(|r| r.delay_ms).unwrap_or(0);
+                sleep_unless_cancelled(cancel, Duration::from_millis(delay));
+                if cancel.is_cancelled() {
+                    break;
+                }
+            }
+            record.attempts += 1;
+            (self.on_event)(&RunEvent::StepStarted {
+                index: record.index,
+                step,
+                attempt: record.attempts,
+            });
+
+            // Params are resolved only once the steps they reference have finished
+            outcome = resolve_params(step, results).and_then(|params| {
+                let socket_path = (self.connect)(&step.service)?;
+                call_step(&socket_path, &step.method, params, cancel)
+            });
+            if outcome.is_ok() || cancel.is_cancelled() {
+                break;
+            }
+        }
+
+        // Saving is part of the step, but a failed write isn't worth a retry
+        let outcome = outcome.and_then(|result| {
+            if let Some(ref save) = step.save {
+                let path = save_result(save, self.artifacts_dir.as_deref(), &result, results)
+                    .context("Failed to save result")?;
+                record.saved = Some(path.display().to_string());
+            }
+            Ok(result)
+        });
+        record.duration_ms = step_start.elapsed().as_secs_f64() * 1000.0;
+
+        match outcome {
+            Ok(result) => {
+                record.status = StepStatus::Ok;
+                record.result = result;
+            }
+            Err(_) if cancel.is_cancelled() => {
+                record.status = StepStatus::Cancelled;
+                record.error = Some("cancelled".to_string());
+            }
+            Err(e) => {
+                record.status = StepStatus::Failed;
+                record.error = Some(format!("{:#}", e));
+            }
+        }
+        (self.on_event)(&RunEvent::StepFinished(record));
+    }
+}
+
+/// Interpolate a step's params, failing if a referenced step has no result
+/// (because it was skipped or failed).
+fn resolve_params(step: &Step, results: &HashMap<String, Value>) -> Result<Value> {
+    for reference in step_refs(&step.params)? {
+        if !results.contains_key(&reference.step) {
+            bail!(
+                "'{}' is unavailable: step '{}' did not produce a result",
+                reference,
+                reference.step
+            );
+        }
+    }
+    interpolate(&step.params, results)
+}
+
+/// Sleep for `duration`, waking early if the run is cancelled.
+fn sleep_unless_cancelled(cancel: &Cancellation, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while !cancel.is_cancelled() {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        std::thread::sleep((deadline - now).min(Duration::from_millis(50)));
+    }
+}
+
+/// Check a `save:` path template. With an `artifacts_dir`, the path must be
+/// relative and stay inside it.
+fn check_save_path(path: &str, confined: bool) -> Result<()> {
+    if path.trim().is_empty() {
+        bail!("save.path is empty");
+    }
+    step_refs(&Value::String(path.to_string())).context("Invalid save.path")?;
+
+    if confined && !stays_inside(Path::new(path)) {
+        bail!(
+            "save.path '{}' must be a relative path inside artifacts_dir",
+            path
+        );
+    }
+    Ok(())
+}
+
+/// Whether a path is relative and never climbs above its base with `..`.
+fn stays_inside(path: &Path) -> bool {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+/// Write a step's result as `save` describes, returning the path written.
+///
+/// Paths are relative to `artifacts_dir` when set, otherwise to the current
+/// directory.
+fn save_result(
+    save: &SaveSpec,
+    artifacts_dir: Option<&Path>,
+    result: &Value,
+    results: &HashMap<String, Value>,
+) -> Result<PathBuf> {
+    let path = match interpolate(&Value::String(save.path.clone()), results)? {
+        Value::String(path) => path,
+        other => other.to_string(),
+    };
+
+    let path = match artifacts_dir {
+        Some(dir) => {
+            // Checked again: templates can expand to anything
+            if !stays_inside(Path::new(&path)) {
+                bail!("'{}' is outside artifacts_dir {}", path, dir.display());
+            }
+            dir.join(path)
+        }
+        None => PathBuf::from(shellexpand::tilde(&path).as_ref()),
+    };
+
+    if path.exists() && !save.overwrite {
+        bail!(
+            "{} already exists (set save.overwrite: true to replace it)",
+            path.display()
+        );
+    }
+
+    let content = match save.format {
+        SaveFormat::Json => format!("{}\n", serde_json::to_string_pretty(result)?),
+        SaveFormat::Yaml => serde_yaml::to_string(result)?,
+        SaveFormat::Raw => match result {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        },
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Send one request and wait for its response.
+///
+/// The call goes through [`Client::stream`] rather than [`Client::call`] so
+/// that cancelling the run can shut the connection down mid-read.
+fn call_step(
+    socket_path: &Path,
+    method: &str,
+    params: Value,
+    cancel: &Cancellation,
+) -> Result<Value> {
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params
+    };
+
+    let mut stream = Client::at(socket_path).stream(&Request::new(method, params))?;
+    let call = cancel.track(stream.shutdown_handle()?);
+    let frame = stream.next().unwrap_or(Err(client::Error::Closed));
+    cancel.untrack(call);
+
+    Ok(Response::from_value(frame?)?.into_result()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixListener;
+
+    /// A daemon that echoes the method and params, answering `*.slow`
+    /// methods after `slow` and everything else immediately.
+    fn fake_daemon(slow: Duration) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                std::thread::spawn(move || {
+                    let mut line = String::new();
+                    BufReader::new(&stream).read_line(&mut line).unwrap();
+                    let request: Value = serde_json::from_str(&line).unwrap();
+                    let method = request["method"].as_str().unwrap_or("");
+                    if method.ends_with(".slow") {
+                        std::thread::sleep(slow);
+                    }
+                    let response = serde_json::json!({
+                        "ok": true,
+                        "result": {"method": method, "params": request["params"]},
+                    });
+                    let _ = writeln!(stream, "{}", response);
+                });
+            }
+        });
+
+        (dir, socket_path)
+    }
+
+    fn workflow(yaml: &str) -> Workflow {
+        parse(yaml).unwrap()
+    }
+
+    const SLOW_WORKFLOW: &str = r#"
+name: slow
+timeout: 200ms
+steps:
+  - service: fake
+    method: fake.fast
+  - service: fake
+    method: fake.slow
+  - service: fake
+    method: fake.fast
+"#;
+
+    #[test]
+    fn test_parse_checks_steps_and_timeout() {
+        let wf = workflow("name: ok\ntimeout: 1m\nsteps:\n  - service: a\n    method: a.b\n");
+        assert_eq!(wf.timeout().unwrap(), Some(Duration::from_secs(60)));
+
+        assert!(parse("name: empty\nsteps: []\n").is_err());
+        assert!(
+            parse("name: bad\ntimeout: soon\nsteps:\n  - service: a\n    method: a.b\n").is_err()
+        );
+        assert!(
+            parse("name: bad\nsteps:\n  - service: a\n    method: a.b\n    params: [1]\n").is_err()
+        );
+    }
+
+    #[test]
+    fn test_completed_run() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let wf = workflow("name: quick\nsteps:\n  - service: fake\n    method: fake.one\n  - service: fake\n    method: fake.two\n");
+
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        assert!(run.steps.iter().all(|s| s.status == StepStatus::Ok));
+        assert_eq!(run.result()["method"], "fake.two");
+    }
+
+    #[test]
+    fn test_interrupt_cancels_in_flight_step() {
+        let (_dir, socket) = fake_daemon(Duration::from_secs(30));
+        let wf = workflow(SLOW_WORKFLOW);
+        let cancel = Cancellation::new();
+
+        let trigger = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            trigger.cancel(CancelReason::Interrupted);
+        });
+
+        let started = Instant::now();
+        let run = execute_with(&wf, &cancel, |_| Ok(socket.clone()), |_| {});
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        assert_eq!(run.status, RunStatus::Cancelled);
+        let statuses: Vec<StepStatus> = run.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![StepStatus::Ok, StepStatus::Cancelled, StepStatus::Pending]
+        );
+
+        // The record keeps the per-step statuses
+        let line = serde_json::to_string(&run).unwrap();
+        assert!(line.contains(r#""status":"cancelled""#));
+        assert!(line.contains(r#""status":"pending""#));
+    }
+
+    #[test]
+    fn test_timeout_cancels_run() {
+        let (_dir, socket) = fake_daemon(Duration::from_secs(30));
+        let wf = workflow(SLOW_WORKFLOW);
+        let cancel = Cancellation::new();
+
+        let _timer = cancel.start_timer(wf.timeout().unwrap().unwrap());
+        let run = execute_with(&wf, &cancel, |_| Ok(socket.clone()), |_| {});
+
+        assert_eq!(run.status, RunStatus::TimedOut);
+        assert_eq!(run.steps[0].status, StepStatus::Ok);
+        assert_eq!(run.steps[1].status, StepStatus::Cancelled);
+        assert_eq!(run.steps[2].status, StepStatus::Pending);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut results = HashMap::new();
+        results.insert(
+            "list".to_string(),
+            serde_json::json!({"count": 2, "items": [{"subject": "Hi"}, {"subject": "Re: Hi"}]}),
+        );
+
+        let params = serde_json::json!({
+            "count": "${{ steps.list.result.count }}",
+            "first": "${{steps.list.result.items.0.subject}}",
+            "summary": "${{ steps.list.result.count }} new: ${{ steps.list.result.items.1.subject }}",
+            "all": ["${{ steps.list.result.items }}"],
+            "later": "${{ steps.other.result }}",
+        });
+        let resolved = interpolate(&params, &results).unwrap();
+        assert_eq!(resolved["count"], 2);
+        assert_eq!(resolved["first"], "Hi");
+        assert_eq!(resolved["summary"], "2 new: Re: Hi");
+        assert_eq!(resolved["all"][0][1]["subject"], "Re: Hi");
+        // Unknown results are left for --dry-run to show
+        assert_eq!(resolved["later"], "${{ steps.other.result }}");
+
+        let missing = serde_json::json!({"x": "${{ steps.list.result.items.5 }}"});
+        assert!(interpolate(&missing, &results).is_err());
+        assert!(interpolate(&serde_json::json!("${{ steps.list"), &results).is_err());
+        assert!(interpolate(&serde_json::json!("${{ env.HOME }}"), &results).is_err());
+    }
+
+    #[test]
+    fn test_validate_references() {
+        let base = "name: refs\nsteps:\n  - id: list\n    service: a\n    method: a.list\n";
+        let with = |step: &str| format!("{}{}", base, step);
+
+        assert!(parse(&with(
+            "  - service: a\n    method: a.send\n    params:\n      n: ${{ steps.list.result.count }}\n"
+        ))
+        .is_ok());
+
+        let unknown = parse(&with(
+            "  - service: a\n    method: a.send\n    params:\n      n: ${{ steps.nope.result }}\n",
+        ));
+        assert!(unknown
+            .unwrap_err()
+            .to_string()
+            .contains("unknown step 'nope'"));
+
+        let later = parse(
+            "name: refs\nsteps:\n  - service: a\n    method: a.send\n    params:\n      n: ${{ steps.list.result }}\n  - id: list\n    service: a\n    method: a.list\n",
+        );
+        assert!(later.unwrap_err().to_string().contains("runs later"));
+
+        let duplicate = parse(&with("  - id: list\n    service: a\n    method: a.again\n"));
+        assert!(duplicate
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate step id"));
+    }
+
+    #[test]
+    fn test_step_results_flow_into_params() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let wf = workflow(
+            r#"
+name: pipe
+steps:
+  - id: first
+    service: fake
+    method: fake.one
+    params:
+      labels: [inbox, work]
+  - service: fake
+    method: fake.two
+    params:
+      from: ${{ steps.first.result.method }}
+      label: ${{ steps.first.result.params.labels.1 }}
+"#,
+        );
+
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        assert_eq!(
+            run.result()["params"],
+            serde_json::json!({"from": "fake.one", "label": "work"})
+        );
+    }
+
+    #[test]
+    fn test_condition_parse_and_evaluate() {
+        let mut results = HashMap::new();
+        results.insert(
+            "list".to_string(),
+            serde_json::json!({"count": 2, "label": "inbox", "empty": []}),
+        );
+        let eval = |text: &str| Condition::parse(text).unwrap().evaluate(&results);
+
+        assert!(eval("${{ steps.list.result.count }} > 0"));
+        assert!(eval("${{ steps.list.result.count }} <= 2"));
+        assert!(!eval("${{ steps.list.result.count }} != 2"));
+        assert!(eval("${{ steps.list.result.label }} == 'inbox'"));
+        assert!(eval("${{ steps.list.result.count }}"));
+        assert!(!eval("${{ steps.list.result.empty }}"));
+        // Missing values are null; mismatched types never compare
+        assert!(eval("${{ steps.list.result.missing }} == null"));
+        assert!(!eval("${{ steps.list.result.label }} > 1"));
+
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("> 1").is_err());
+        assert!(Condition::parse("${{ steps.list.result }} > true").is_err());
+        assert!(Condition::parse("${{ steps.list.result }} == inbox").is_err());
+        assert!(Condition::parse("${{ steps.list.result }} == 1 2").is_err());
+
+        let invalid = parse("name: c\nsteps:\n  - service: a\n    method: a.b\n    if: 1 >\n");
+        assert!(format!("{:#}", invalid.unwrap_err()).contains("Invalid condition"));
+    }
+
+    #[test]
+    fn test_false_condition_skips_step() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let wf = workflow(
+            r#"
+name: cond
+steps:
+  - id: first
+    service: fake
+    method: fake.one
+  - service: fake
+    method: fake.two
+    if: ${{ steps.first.result.method }} == 'fake.other'
+  - service: fake
+    method: fake.three
+    if: ${{ steps.first.result.method }} == 'fake.one'
+"#,
+        );
+
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        let statuses: Vec<StepStatus> = run.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![StepStatus::Ok, StepStatus::Skipped, StepStatus::Ok]
+        );
+        assert_eq!(run.steps[1].attempts, 0);
+    }
+
+    #[test]
+    fn test_retry_and_continue_on_error() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let retried = "name: retry\nsteps:\n  - service: fake\n    method: fake.one\n    retry:\n      attempts: 3\n      delay_ms: 1\n";
+
+        // Fails twice, then succeeds on the last attempt
+        let failures = AtomicUsize::new(2);
+        let flaky = |_: &str| {
+            if failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                bail!("connection refused");
+            }
+            Ok(socket.clone())
+        };
+        let run = execute_with(&workflow(retried), &Cancellation::new(), flaky, |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        assert_eq!(run.steps[0].attempts, 3);
+
+        // Exhausted retries fail the run...
+        let down = |_: &str| -> Result<PathBuf> { bail!("connection refused") };
+        let run = execute_with(&workflow(retried), &Cancellation::new(), down, |_| {});
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.steps[0].status, StepStatus::Failed);
+        assert_eq!(run.steps[0].attempts, 3);
+
+        // ...unless the step opts out
+        let tolerant = "name: tolerant\nsteps:\n  - service: down\n    method: down.one\n    continue_on_error: true\n  - service: fake\n    method: fake.two\n";
+        let connect = |service: &str| {
+            if service == "down" {
+                bail!("connection refused");
+            }
+            Ok(socket.clone())
+        };
+        let run = execute_with(&workflow(tolerant), &Cancellation::new(), connect, |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        assert_eq!(run.steps[0].status, StepStatus::Failed);
+        assert_eq!(run.steps[0].attempts, 1);
+        assert_eq!(run.steps[1].status, StepStatus::Ok);
+
+        assert!(parse(
+            "name: r\nsteps:\n  - service: a\n    method: a.b\n    retry:\n      attempts: 0\n"
+        )
+        .is_err());
+    }
+
+    const PARALLEL_WORKFLOW: &str = r#"
+name: fan-out
+steps:
+  - id: first
+    service: fake
+    method: fake.fast
+  - parallel:
+      - id: a
+        service: fake
+        method: fake.slow
+      - id: b
+        service: fake
+        method: fake.slow
+        params:
+          from: ${{ steps.first.result.method }}
+      - id: c
+        service: fake
+        method: fake.slow
+  - service: fake
+    method: fake.fast
+    params:
+      joined: ${{ steps.a.result.method }}+${{ steps.b.result.params.from }}
+"#;
+
+    #[test]
+    fn test_parse_parallel_groups() {
+        let wf = workflow(PARALLEL_WORKFLOW);
+        assert_eq!(wf.steps.len(), 5);
+        assert_eq!(wf.groups.len(), 1);
+        assert_eq!(wf.groups[0].first, 1);
+        assert_eq!(wf.groups[0].len, 3);
+        assert_eq!(wf.groups[0].max_parallel, DEFAULT_MAX_PARALLEL);
+        assert_eq!(wf.steps[2].group, Some(0));
+        assert_eq!(wf.steps[4].group, None);
+
+        let group = |body: &str| format!("name: g\nsteps:\n  - parallel:\n{}", body);
+        let child = "      - id: x\n        service: a\n        method: a.x\n";
+        assert!(parse(&group("    []\n")).is_err());
+        assert!(parse(&format!("{}    max_parallel: 0\n", group(child))).is_err());
+        assert!(parse(&format!("{}    fail_fast: true\n", group(child))).is_err());
+        assert!(parse(&group(
+            "      - parallel:\n          - service: a\n            method: a.x\n"
+        ))
+        .is_err());
+
+        // Siblings can't see each other's results, in either direction
+        let sibling = "      - service: a\n        method: a.y\n        params:\n          n: ${{ steps.x.result }}\n";
+        for body in [
+            format!("{}{}", child, sibling),
+            format!("{}{}", sibling, child),
+        ] {
+            let err = parse(&group(&body)).unwrap_err().to_string();
+            assert!(err.contains("same parallel group"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parallel_group_runs_concurrently() {
+        let (_dir, socket) = fake_daemon(Duration::from_millis(300));
+        let wf = workflow(PARALLEL_WORKFLOW);
+
+        let events = Mutex::new(Vec::new());
+        let started = Instant::now();
+        let run = execute_with(
+            &wf,
+            &Cancellation::new(),
+            |_| Ok(socket.clone()),
+            |event| {
+                let event = match event {
+                    RunEvent::StepStarted { index, .. } => ("start", *index),
+                    RunEvent::StepFinished(record) => ("finish", record.index),
+                };
+                events.lock().unwrap().push(event);
+            },
+        );
+        let elapsed = started.elapsed();
+
+        assert_eq!(run.status, RunStatus::Completed);
+        assert!(run.steps.iter().all(|s| s.status == StepStatus::Ok));
+        assert!(elapsed < Duration::from_millis(800), "{:?}", elapsed);
+        assert_eq!(run.result()["params"]["joined"], "fake.slow+fake.fast");
+        assert_eq!(run.steps[1].group, Some(0));
+
+        // All three children start before any of them finishes
+        let events = events.into_inner().unwrap();
+        let first_finish = events
+            .iter()
+            .position(|&(kind, index)| kind == "finish" && index > 0)
+            .unwrap();
+        let child_starts = events[..first_finish]
+            .iter()
+            .filter(|&&(kind, index)| kind == "start" && index > 0)
+            .count();
+        assert_eq!(child_starts, 3);
+    }
+
+    #[test]
+    fn test_max_parallel_limits_concurrency() {
+        let (_dir, socket) = fake_daemon(Duration::from_millis(150));
+        let wf = workflow(
+            &PARALLEL_WORKFLOW.replace("  - parallel:\n", "  - max_parallel: 1\n    parallel:\n"),
+        );
+        assert_eq!(wf.groups[0].max_parallel, 1);
+
+        let started = Instant::now();
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+        assert!(started.elapsed() >= Duration::from_millis(450));
+    }
+
+    #[test]
+    fn test_save_writes_results() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let out = tempfile::tempdir().unwrap();
+        let wf = workflow(&format!(
+            r#"
+name: save
+artifacts_dir: {}
+steps:
+  - id: list
+    service: fake
+    method: fake.list
+    save:
+      path: raw/list.json
+  - service: fake
+    method: fake.echo
+    params:
+      text: hello
+    save:
+      path: ${{{{ steps.list.result.method }}}}/echo.yaml
+      format: yaml
+"#,
+            out.path().display()
+        ));
+
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Completed);
+
+        let list = out.path().join("raw").join("list.json");
+        assert_eq!(run.steps[0].saved.as_deref(), Some(list.to_str().unwrap()));
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&list).unwrap()).unwrap();
+        assert_eq!(saved["method"], "fake.list");
+        let echo = fs::read_to_string(out.path().join("fake.list").join("echo.yaml")).unwrap();
+        assert!(echo.contains("text: hello"), "{}", echo);
+
+        // A second run refuses to overwrite the files
+        let run = execute_with(&wf, &Cancellation::new(), |_| Ok(socket.clone()), |_| {});
+        assert_eq!(run.status, RunStatus::Failed);
+        assert!(run.steps[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("already exists"));
+        assert!(run.steps[0].saved.is_none());
+    }
+
+    #[test]
+    fn test_save_paths_stay_in_artifacts_dir() {
+        let with_save = |artifacts: &str, path: &str| {
+            parse(&format!(
+                "name: s\n{}steps:\n  - service: a\n    method: a.b\n    save:\n      path: {}\n",
+                artifacts, path
+            ))
+        };
+
+        assert!(with_save("", "/tmp/out.json").is_ok());
+        assert!(with_save("", "../out.json").is_ok());
+        assert!(with_save("artifacts_dir: out\n", "day/./out.json").is_ok());
+        assert!(with_save("artifacts_dir: out\n", "a/../b.json").is_ok());
+        assert!(with_save("artifacts_dir: out\n", "/tmp/out.json").is_err());
+        assert!(with_save("artifacts_dir: out\n", "a/../../b.json").is_err());
+        assert!(with_save("", "'${{ steps.a'").is_err());
+        assert!(with_save("", "'${{ steps.later.result }}'").is_err());
+    }
+
+    #[test]
+    fn test_failed_child_stops_group() {
+        let (_dir, socket) = fake_daemon(Duration::ZERO);
+        let wf = workflow("name: g\nsteps:\n  - max_parallel: 1\n    parallel:\n      - service: down\n        method: down.x\n      - service: fake\n        method: fake.y\n  - service: fake\n    method: fake.z\n");
+        let connect = |service: &str| {
+            if service == "down" {
+                bail!("connection refused");
+            }
+            Ok(socket.clone())
+        };
+
+        let run = execute_with(&wf, &Cancellation::new(), connect, |_| {});
+        assert_eq!(run.status, RunStatus::Failed);
+        let statuses: Vec<StepStatus> = run.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![StepStatus::Failed, StepStatus::Pending, StepStatus::Pending]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Static checks for workflows that parse but won't do what they say.
+//!
+//! `fgp workflow validate --lint` runs these after the checks parsing already
+//! makes (schema, duplicate ids, references to unknown or later steps). Each
+//! finding has a rule id and a severity, and is printed the way
+//! [`lint_report`](super::lint_report) prints every finding:
+//!
+//! | Rule | Severity | Finding |
+//! |------|----------|---------|
+//! | `never-runs` | warning | The step's `if:` is always false |
+//! | `constant-condition` | warning | The step's `if:` is always true |
+//! | `always-fails` | error | The step's params use a result that is never produced |
+//! | `unreachable-step` | warning | An earlier step always fails and stops the run |
+//! | `unused-id` | warning | No later step uses the step's result |
+//! | `duplicate-output` | warning | Two steps store their results under one `output:` name |
+//!
+//! A condition is constant when it only compares literals, or only uses
+//! results of steps that never produce one (a missing result is `null`).
+
+use std::collections::{HashMap, HashSet};
+
+use super::lint_report::Diagnostic;
+use super::workflow_engine::{step_refs, Workflow};
+
+/// Lint a parsed workflow. Findings are in step order.
+pub fn lint(workflow: &Workflow) -> Vec<Diagnostic> {
+    let steps = &workflow.steps;
+    let describe = |i: usize| format!("step {} ({})", i + 1, steps[i].method);
+    // Each finding with the index of its step, to sort them by
+    let mut diagnostics: Vec<(usize, Diagnostic)> = Vec::new();
+    let mut warn = |rule, step, message: String| {
+        diagnostics.push((step, Diagnostic::warning(rule, describe(step), message)))
+    };
+
+    let ids: HashMap<&str, usize> = steps
+        .iter()
+        .enumerate()
+        .filter_map(|(i, step)| step.id.as_deref().map(|id| (id, i)))
+        .collect();
+
+    // Steps that never produce a result, so references to them never resolve
+    let mut no_result: HashSet<usize> = HashSet::new();
+    // The first step that always fails and stops the run
+    let mut halted_by: Option<usize> = None;
+    let mut always_fails = Vec::new();
+
+    for (i, step) in steps.iter().enumerate() {
+        if let Some(h) = halted_by {
+            // Siblings in the failing step's group may still start
+            if step.group.is_none() || step.group != steps[h].group {
+                warn(
+                    "unreachable-step",
+                    i,
+                    format!("never runs: {} always fails first", describe(h)),
+                );
+                no_result.insert(i);
+                continue;
+            }
+        }
+
+        if let Ok(Some(condition)) = step.condition() {
+            let constant = condition.refs().iter().all(|r| {
+                ids.get(r.step.as_str())
+                    .is_some_and(|j| no_result.contains(j))
+            });
+            let text = step.condition.as_deref().unwrap_or_default();
+            if constant && condition.evaluate(&HashMap::new()) {
+                warn(
+                    "constant-condition",
+                    i,
+                    format!("`if: {}` is always true", text),
+                );
+            } else if constant {
+                warn(
+                    "never-runs",
+                    i,
+                    format!("`if: {}` is always false, so the step never runs", text),
+                );
+                no_result.insert(i);
+                continue;
+            }
+        }
+
+        let missing = step_refs(&step.params)
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|r| {
+                ids.get(r.step.as_str())
+                    .filter(|j| no_result.contains(j))
+                    .map(|&j| (r, j))
+            });
+        if let Some((reference, j)) = missing {
+            always_fails.push((
+                i,
+                Diagnostic::error(
+                    "always-fails",
+                    describe(i),
+                    format!(
+                        "uses '{}', but {} never produces a result",
+                        reference,
+                        describe(j)
+                    ),
+                ),
+            ));
+            no_result.insert(i);
+            if !step.continue_on_error && halted_by.is_none() {
+                halted_by = Some(i);
+            }
+        }
+    }
+
+    for (i, step) in steps.iter().enumerate() {
+        let Some(ref id) = step.id else {
+            continue;
+        };
+        let used = steps[i + 1..].iter().any(|later| {
+            later
+                .refs()
+                .unwrap_or_default()
+                .iter()
+                .any(|r| &r.step == id)
+        });
+        if !used {
+            warn(
+                "unused-id",
+                i,
+                format!("no later step uses the result of '{}'", id),
+            );
+        }
+    }
+
+    let mut outputs: HashMap<&str, usize> = HashMap::new();
+    for (i, step) in steps.iter().enumerate() {
+        let Some(ref output) = step.output else {
+            continue;
+        };
+        if let Some(&first) = outputs.get(output.as_str()) {
+            warn(
+                "duplicate-output",
+                i,
+                format!("output '{}' is also used by {}", output, describe(first)),
+            );
+        } else {
+            outputs.insert(output, i);
+        }
+    }
+
+    diagnostics.extend(always_fails);
+    diagnostics.sort_by_key(|(step, _)| *step);
+    diagnostics
+        .into_iter()
+        .map(|(_, diagnostic)| diagnostic)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::workflow_engine;
+    use std::fs;
+    use std::path::Path;
+
+    /// Each workflow in tests/fixtures/workflow-lint has its findings pinned
+    /// in a `.expected` file next to it (regenerate with UPDATE_GOLDEN=1).
+    #[test]
+    fn test_lint_fixtures() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workflow-lint");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let mut cases: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "yaml"))
+            .collect();
+        cases.sort();
+        assert!(
+            !cases.is_empty(),
+            "no fixtures found under {}",
+            root.display()
+        );
+
+        let mut mismatches = Vec::new();
+        for case in &cases {
+            let workflow = workflow_engine::load_file(case)
+                .unwrap_or_else(|e| panic!("{} failed to parse: {:#}", case.display(), e));
+            let actual: String = lint(&workflow).iter().map(|d| format!("{}\n", d)).collect();
+
+            let golden = case.with_extension("expected");
+            if update {
+                fs::write(&golden, &actual).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&golden).unwrap_or_default();
+            if actual != expected {
+                mismatches.push(format!(
+                    "{}\n--- expected\n{}+++ actual\n{}",
+                    golden.display(),
+                    expected,
+                    actual
+                ));
+            }
+        }
+        assert!(
+            mismatches.is_empty(),
+            "lint findings differ (rerun with UPDATE_GOLDEN=1 if the change is intended):\n\n{}",
+            mismatches.join("\n")
+        );
+    }
+}
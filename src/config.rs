@@ -0,0 +1,200 @@
+//! User configuration (~/.fgp/config.toml).
+//!
+//! ```toml
+//! [autostart]
+//! services = ["gmail", "calendar"]
+//!
+//! [legacy]
+//! marketplace = true  # keep using `fgp skill marketplace` without notices
+//!
+//! [guardrails]
+//! default = "deny"  # destructive methods nobody can confirm: allow, dry-run, deny
+//!
+//! [guardrails.services]
+//! calendar = "allow"
+//!
+//! [taps]
+//! stale_after_days = 14  # nudge to run `fgp skill tap update` after this; 0 turns it off
+//! auto_update = true     # refresh stale taps before `fgp skill search`
+//!
+//! [mcp.tool_limits]
+//! cursor = 40      # warn when one MCP server offers more tools than this
+//! windsurf = 0     # 0 turns a client's warning off
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// Top-level FGP configuration.
+#[derive(Debug, Default, Deserialize)]
+pub struct FgpConfig {
+    #[serde(default)]
+    pub autostart: AutostartConfig,
+    #[serde(default)]
+    pub legacy: LegacyConfig,
+    #[serde(default)]
+    pub guardrails: GuardrailsConfig,
+    #[serde(default)]
+    pub mcp: McpConfig,
+    #[serde(default)]
+    pub taps: TapsConfig,
+}
+
+/// Services that make up the user's declared daemon stack.
+#[derive(Debug, Default, Deserialize)]
+pub struct AutostartConfig {
+    #[serde(default)]
+    pub services: Vec<String>,
+}
+
+/// Opt-ins for deprecated features.
+#[derive(Debug, Default, Deserialize)]
+pub struct LegacyConfig {
+    /// Silence the `fgp skill marketplace` deprecation notice
+    #[serde(default)]
+    pub marketplace: bool,
+}
+
+/// How calls to destructive methods are handled when there is no terminal
+/// to confirm them on, as when an agent calls through the MCP bridge.
+#[derive(Debug, Default, Deserialize)]
+pub struct GuardrailsConfig {
+    /// Policy for services without their own entry
+    #[serde(default)]
+    pub default: Policy,
+    /// Per-service policies, keyed by service name
+    #[serde(default)]
+    pub services: HashMap<String, Policy>,
+}
+
+impl GuardrailsConfig {
+    /// The policy that applies to `service`.
+    pub fn policy(&self, service: &str) -> Policy {
+        self.services.get(service).copied().unwrap_or(self.default)
+    }
+}
+
+/// What to do with an unconfirmed call to a destructive method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Policy {
+    /// Blanket approval
+    Allow,
+    /// Only preview the call, for methods that support dry runs
+    DryRun,
+    /// Refuse the call
+    #[default]
+    Deny,
+}
+
+/// When skill taps count as stale, and what happens to stale ones.
+#[derive(Debug, Deserialize)]
+pub struct TapsConfig {
+    /// Days without a fetch after which search and install suggest
+    /// `fgp skill tap update`; 0 turns the nudge off
+    #[serde(default = "default_stale_after_days")]
+    pub stale_after_days: u32,
+    /// Refresh stale taps before `fgp skill search`, as `--auto-update` does
+    #[serde(default)]
+    pub auto_update: bool,
+}
+
+impl Default for TapsConfig {
+    fn default() -> Self {
+        Self {
+            stale_after_days: default_stale_after_days(),
+            auto_update: false,
+        }
+    }
+}
+
+fn default_stale_after_days() -> u32 {
+    14
+}
+
+/// Tool limits of known MCP clients, which start dropping or refusing tools
+/// past them.
+const DEFAULT_TOOL_LIMITS: &[(&str, usize)] = &[("cursor", 40), ("windsurf", 100)];
+
+/// Settings for the MCP bridge and MCP exports.
+#[derive(Debug, Default, Deserialize)]
+pub struct McpConfig {
+    /// Tool counts to warn past, keyed by client, over the built-in ones
+    #[serde(default)]
+    pub tool_limits: BTreeMap<String, usize>,
+}
+
+impl McpConfig {
+    /// The tool limit per client, with the user's entries over the built-in
+    /// ones and clients set to 0 left out.
+    pub fn tool_limits(&self) -> BTreeMap<String, usize> {
+        let mut limits: BTreeMap<String, usize> = DEFAULT_TOOL_LIMITS
+            .iter()
+            .map(|(client, limit)| (client.to_string(), *limit))
+            .collect();
+        limits.extend(self.tool_limits.clone());
+        limits.retain(|_, limit| *limit > 0);
+        limits
+    }
+
+    /// The clients whose limit `count` tools exceeds, as "cursor (40)".
+    pub fn exceeded_limits(&self, count: usize) -> Vec<String> {
+        self.tool_limits()
+            .into_iter()
+            .filter(|(_, limit)| count > *limit)
+            .map(|(client, limit)| format!("{} ({})", client, limit))
+            .collect()
+    }
+}
+
+/// Path to the config file.
+pub fn config_path() -> PathBuf {
+    let base = shellexpand::tilde("~/.fgp/config.toml");
+    PathBuf::from(base.as_ref())
+}
+
+/// Load the config file, returning defaults if it does not exist.
+pub fn load() -> Result<FgpConfig> {
+    let path = config_path();
+    if !path.exists() {
+        return Ok(FgpConfig::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mcp_tool_limits() {
+        let config: FgpConfig = toml::from_str("").unwrap();
+        assert_eq!(config.mcp.exceeded_limits(40), Vec::<String>::new());
+        assert_eq!(config.mcp.exceeded_limits(41), vec!["cursor (40)"]);
+        assert_eq!(
+            config.mcp.exceeded_limits(150),
+            vec!["cursor (40)", "windsurf (100)"]
+        );
+
+        let config: FgpConfig =
+            toml::from_str("[mcp.tool_limits]\ncursor = 0\nzed = 20\n").unwrap();
+        assert_eq!(config.mcp.exceeded_limits(50), vec!["zed (20)".to_string()]);
+    }
+
+    #[test]
+    fn test_taps_config() {
+        let config: FgpConfig = toml::from_str("").unwrap();
+        assert_eq!(config.taps.stale_after_days, 14);
+        assert!(!config.taps.auto_update);
+
+        let config: FgpConfig = toml::from_str("[taps]\nauto_update = true\n").unwrap();
+        assert_eq!(config.taps.stale_after_days, 14);
+        assert!(config.taps.auto_update);
+    }
+}
@@ -8,20 +8,29 @@
 //! fgp new <name>          # Create a new FGP package from template
 //! fgp start <service>     # Start a daemon
 //! fgp stop <service>      # Stop a daemon
+//! fgp restart <service>   # Restart a daemon and wait for health
 //! fgp status              # Show running daemons
+//! fgp doctor [--fix]      # Diagnose and repair common problems
+//! fgp up / fgp down       # Start/stop the autostart services
 //! fgp call <method>       # Call a method
+//! fgp history             # Show recent method calls
+//! fgp daemon-proxy        # Reuse daemon connections across calls
 //! fgp install <package>   # Install from local path
+//! fgp uninstall <service> # Remove a daemon and its agent registrations
 //! fgp logs <service>      # View daemon logs
 //! fgp mcp serve           # Start MCP bridge
 //! fgp monitor             # Health monitor with notifications
+//! fgp completions <shell> # Print a shell completion script
 //! ```
 
 mod commands;
+mod config;
 mod notifications;
 mod tui;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
 
 /// Fast Gateway Protocol CLI
 ///
@@ -33,12 +42,26 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Never use the network: installs use local tap clones, update checks
+    /// are skipped, and anything that needs a remote fails straight away
+    #[arg(
+        long,
+        global = true,
+        env = "FGP_OFFLINE",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    offline: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Detect installed AI agents on this machine
-    Agents,
+    Agents {
+        /// Output every known agent as JSON, detected or not
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Generate a new daemon from template (67 service presets available)
     Generate {
@@ -64,9 +87,24 @@ enum Commands {
         no_git: bool,
     },
 
+    /// Develop a skill: install its exports into your agents and reinstall on every change
+    Dev {
+        /// Skill directory or skill.yaml
+        skill: String,
+
+        /// Agents to install for: claude-code (or claude), cursor (default: detected ones)
+        #[arg(long, value_delimiter = ',')]
+        targets: Vec<String>,
+
+        /// Remove the exports this run installed when stopping with Ctrl-C
+        #[arg(long)]
+        clean: bool,
+    },
+
     /// Start a daemon service
     Start {
         /// Service name (e.g., "gmail", "imessage")
+        #[arg(add = ArgValueCandidates::new(commands::completions::services))]
         service: String,
 
         /// Run in foreground (don't daemonize)
@@ -77,50 +115,271 @@ enum Commands {
     /// Stop a running daemon
     Stop {
         /// Service name to stop
-        service: String,
+        #[arg(required_unless_present = "orphan", add = ArgValueCandidates::new(commands::completions::services))]
+        service: Option<String>,
+
+        /// Terminate an orphaned daemon process by PID (see `fgp status --verbose`)
+        #[arg(long, value_name = "PID", conflicts_with = "service")]
+        orphan: Option<u64>,
+
+        /// Don't ask for confirmation before terminating an orphan
+        #[arg(short, long, requires = "orphan")]
+        yes: bool,
+    },
+
+    /// Restart a daemon and wait until it reports healthy
+    Restart {
+        /// Service name to restart
+        #[arg(required_unless_present = "all", add = ArgValueCandidates::new(commands::completions::services))]
+        service: Option<String>,
+
+        /// Restart all installed services
+        #[arg(long, conflicts_with = "service")]
+        all: bool,
+
+        /// Seconds to wait for the daemon to stop and become healthy again
+        #[arg(long, default_value = "10")]
+        wait_timeout: u64,
     },
 
     /// Show status of all running daemons
     Status {
-        /// Show detailed health information
+        /// Show detailed health information, resource usage and orphaned daemons
         #[arg(short, long)]
         verbose: bool,
+
+        /// Output machine-readable JSON (no decorative output)
+        #[arg(long)]
+        json: bool,
+
+        /// Exit non-zero if any autostart service is not running
+        #[arg(long)]
+        exit_code: bool,
+    },
+
+    /// Diagnose common problems (stale sockets, broken links, missing registrations)
+    Doctor {
+        /// Apply safe fixes automatically (asks before overwriting files)
+        #[arg(long)]
+        fix: bool,
+
+        /// Don't ask for confirmation before overwriting files
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
+
+    /// Start all autostart services (from ~/.fgp/config.toml) and wait for health
+    Up {
+        /// Seconds to wait for each daemon to become healthy
+        #[arg(long, default_value = "10")]
+        wait_timeout: u64,
     },
 
+    /// Stop all autostart services
+    Down,
+
     /// Call a method on a daemon
     Call {
         /// Method name (e.g., "gmail.list", "imessage.send")
+        #[arg(add = ArgValueCandidates::new(commands::completions::methods))]
         method: String,
 
         /// JSON parameters (e.g., '{"limit": 10}')
         #[arg(short, long, default_value = "{}")]
         params: String,
 
+        /// Parameters as key=value (string) or key:=value (raw JSON) pairs;
+        /// dotted keys nest (filter.label=work), repeated keys build arrays
+        pairs: Vec<String>,
+
         /// Service name (inferred from method if not provided)
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(commands::completions::services))]
         service: Option<String>,
 
         /// Disable auto-start (fail if daemon is not running)
         #[arg(long)]
         no_auto_start: bool,
+
+        /// Print the resolved method and params without calling the daemon
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Keep reading newline-delimited JSON frames until the daemon finishes
+        #[arg(long)]
+        stream: bool,
+
+        /// Output format for --stream: text (frame by frame) or json (one array)
+        #[arg(long, value_parser = ["text", "json"], default_value = "text", requires = "stream")]
+        output: String,
+
+        /// Don't record this call in ~/.fgp/history.jsonl
+        #[arg(long)]
+        no_history: bool,
+
+        /// Skip checking params against the service's manifest.json
+        #[arg(long)]
+        skip_validation: bool,
+
+        /// Check params against the full JSON Schema the service's
+        /// manifest.json gives for them (enums, patterns, ranges, ...)
+        #[arg(long, conflicts_with = "skip_validation")]
+        validate: bool,
+
+        /// If the method is misspelled and has exactly one close match that
+        /// isn't destructive, call that instead; call destructive methods
+        /// without asking (without a terminal, the [guardrails] policy still
+        /// decides)
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Send this file (or - for stdin) as the request's context, e.g. a
+        /// prior conversation; JSON objects and arrays are sent as JSON
+        #[arg(long, value_name = "FILE")]
+        context: Option<String>,
+
+        /// Fill a param from another call's result first, e.g.
+        /// id=contacts.search:results.0.id (PATH defaults to PARAM)
+        #[arg(long, value_name = "PARAM=METHOD[:PATH]")]
+        param_from_call: Option<String>,
+
+        /// JSON parameters for the --param-from-call lookup
+        #[arg(
+            long,
+            value_name = "JSON",
+            default_value = "{}",
+            requires = "param_from_call"
+        )]
+        from_call_params: String,
+
+        /// Print the --param-from-call lookup's result
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Print only the part of the result at this JSON pointer, e.g.
+        /// /messages/0/id (--expect-contains then checks just that)
+        #[arg(long, value_name = "POINTER", conflicts_with = "stream")]
+        select: Option<String>,
+
+        /// Succeed only if the call exits with this status (0 for a result,
+        /// 1 for an error response); for smoke tests
+        #[arg(long, value_name = "CODE", conflicts_with_all = ["stream", "dry_run"])]
+        expect_exit: Option<i32>,
+
+        /// Succeed only if the output contains this text, e.g. '"ok"'
+        #[arg(long, value_name = "TEXT", conflicts_with_all = ["stream", "dry_run"])]
+        expect_contains: Option<String>,
+    },
+
+    /// Show recent method calls recorded by `fgp call`
+    History {
+        /// Only show calls to this service
+        #[arg(short, long)]
+        service: Option<String>,
+
+        /// Only show this method (supports globs, e.g. "gmail.*")
+        #[arg(short, long)]
+        method: Option<String>,
+
+        /// Only show calls newer than this (e.g. 30m, 2h, 7d)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of calls to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Keep daemon connections open so repeated `fgp call`s skip connection setup
+    DaemonProxy {
+        #[command(subcommand)]
+        action: Option<DaemonProxyAction>,
     },
 
     /// Install a package from local path
     Install {
-        /// Path to package directory or manifest
+        /// Package directory or manifest, tarball (.tar.gz, .tgz), or git URL
+        /// (append #<tag> to pin a version)
         path: String,
+
+        /// Tag or branch to install from a git URL
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
     },
 
-    /// List available methods for a service
-    Methods {
+    /// Stop and remove an installed daemon, its cached skills and its
+    /// registrations with AI agents
+    Uninstall {
         /// Service name
+        #[arg(add = ArgValueCandidates::new(commands::completions::services))]
         service: String,
+
+        /// Leave the service's config files (config.*, *.toml, .env) in place
+        #[arg(long)]
+        keep_config: bool,
+
+        /// List what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Remove the service even if its daemon can't be stopped
+        #[arg(long)]
+        force: bool,
     },
 
-    /// Check health of a specific service
+    /// List available methods for a service, with their params
+    Methods {
+        /// Service name
+        #[arg(required_unless_present = "all", add = ArgValueCandidates::new(commands::completions::services))]
+        service: Option<String>,
+
+        /// Only show this method (e.g. "send" or "gmail.send")
+        #[arg(short, long)]
+        method: Option<String>,
+
+        /// Output the full method definitions as JSON
+        #[arg(long, conflicts_with = "export")]
+        json: bool,
+
+        /// Print the methods as function-calling tools for an API, with a
+        /// mapping from tool names back to methods
+        #[arg(long, value_name = "FORMAT", value_parser = ["openai", "anthropic"])]
+        export: Option<String>,
+
+        /// Export the methods of every installed service
+        #[arg(long, requires = "export", conflicts_with_all = ["service", "method"])]
+        all: bool,
+
+        /// Write the export to this file instead of stdout
+        #[arg(short, long, requires = "export")]
+        output: Option<String>,
+    },
+
+    /// Check health of a service, or of all services with --all
     Health {
         /// Service name
-        service: String,
+        #[arg(required_unless_present = "all", add = ArgValueCandidates::new(commands::completions::services))]
+        service: Option<String>,
+
+        /// Check every installed service and print a table (exit 0 healthy,
+        /// 1 unhealthy, 2 unreachable)
+        #[arg(long, conflicts_with = "service")]
+        all: bool,
+
+        /// Redraw the table every --interval seconds until interrupted
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between refreshes with --watch
+        #[arg(long, default_value = "2", requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Seconds each daemon has to answer its health call
+        #[arg(long, default_value = "2", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: u64,
     },
 
     /// Open the web dashboard
@@ -144,6 +403,7 @@ enum Commands {
     /// View daemon logs
     Logs {
         /// Service name
+        #[arg(add = ArgValueCandidates::new(commands::completions::services))]
         service: String,
 
         /// Follow log output (like tail -f)
@@ -195,6 +455,37 @@ enum Commands {
         #[command(subcommand)]
         action: SkillAction,
     },
+
+    /// Inspect the daemon registry used for skill enrichment
+    Registry {
+        #[command(subcommand)]
+        action: RegistryAction,
+    },
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+
+        /// Complete service, skill, and method names by asking fgp on each
+        /// <Tab> instead of only subcommands and flags
+        #[arg(long)]
+        dynamic: bool,
+
+        /// Write the script to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum RegistryAction {
+    /// List daemons discovered from manifest.json files
+    List {
+        /// Show manifests that failed to load instead
+        #[arg(long)]
+        errors: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -207,12 +498,25 @@ enum WorkflowAction {
         /// Show verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// Print the resolved params for each step without calling any daemon
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Follow the run in a terminal UI with each step's status and output
+        #[arg(long, conflicts_with = "dry_run")]
+        tui: bool,
     },
 
     /// Validate a workflow file without running it
     Validate {
         /// Path to workflow YAML file
         file: String,
+
+        /// Also check for steps that never run or always fail, unused ids and
+        /// other mistakes that parse fine (fails on lint errors)
+        #[arg(long)]
+        lint: bool,
     },
 
     /// List available workflow templates
@@ -229,13 +533,41 @@ enum WorkflowAction {
     },
 }
 
+#[derive(Subcommand)]
+enum DaemonProxyAction {
+    /// Start the proxy in the background (the default)
+    Start {
+        /// Run in the foreground instead of detaching
+        #[arg(short, long)]
+        foreground: bool,
+    },
+
+    /// Stop the running proxy
+    Stop,
+
+    /// Show whether the proxy is running and its pooled connections
+    Status,
+}
+
 #[derive(Subcommand)]
 enum McpBridgeAction {
     /// Start MCP bridge server (stdio mode)
-    Serve,
+    Serve {
+        /// List one tool per daemon (taking `method` and `params`) instead of one per method
+        #[arg(long)]
+        group_by_daemon: bool,
+
+        /// Only serve this daemon's tools (repeatable)
+        #[arg(long = "service", value_name = "DAEMON")]
+        services: Vec<String>,
+    },
 
     /// Register FGP with Claude Code
-    Install,
+    Install {
+        /// Register the bridge in --group-by-daemon mode
+        #[arg(long)]
+        group_by_daemon: bool,
+    },
 
     /// List available MCP tools from daemons
     Tools,
@@ -246,16 +578,54 @@ enum SkillAction {
     /// List installed skills
     List,
 
-    /// Search for skills in marketplaces
+    /// Search for skills in taps and marketplaces, best match first
     Search {
         /// Search query
         query: String,
+
+        /// Show at most this many results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Only show skills that are already installed
+        #[arg(long)]
+        installed: bool,
+
+        /// Output the results as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Fetch stale taps before searching (always, with `[taps]
+        /// auto_update = true` in ~/.fgp/config.toml)
+        #[arg(long)]
+        auto_update: bool,
     },
 
     /// Install a skill from marketplace
     Install {
         /// Skill name (e.g., "browser-gateway")
-        name: String,
+        #[arg(required_unless_present = "all_from")]
+        name: Option<String>,
+
+        /// Install every skill in this tap (skipping installed ones)
+        #[arg(long, value_name = "TAP", conflicts_with_all = ["name", "from", "license"])]
+        all_from: Option<String>,
+
+        /// With --all-from: only skills with this keyword
+        #[arg(long, requires = "all_from")]
+        category: Option<String>,
+
+        /// With --all-from: only skills whose name matches this glob
+        #[arg(long = "match", value_name = "GLOB", requires = "all_from")]
+        pattern: Option<String>,
+
+        /// With --all-from: install the listed skills without asking
+        #[arg(short, long, requires = "all_from")]
+        yes: bool,
+
+        /// With --all-from: stop at the first skill that fails to install
+        #[arg(long, requires = "all_from")]
+        fail_fast: bool,
 
         /// Specific marketplace to install from
         #[arg(short, long)]
@@ -264,38 +634,154 @@ enum SkillAction {
         /// License key for paid skills (e.g., "sk_live_xxx")
         #[arg(short, long)]
         license: Option<String>,
+
+        /// Trust the skill's tap or marketplace without asking (for scripts)
+        #[arg(long)]
+        trust: bool,
+
+        /// Install missing daemons the skill needs without asking
+        #[arg(long)]
+        with_deps: bool,
     },
 
     /// Check for skill updates
     Update,
 
-    /// Upgrade installed skills
+    /// Upgrade installed skills, within the version constraints in skills.lock
     Upgrade {
         /// Specific skill to upgrade (all if not specified)
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
         skill: Option<String>,
+
+        /// Show what would change for each skill without upgrading
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Upgrade to the newest version, past the constraint in skills.lock
+        #[arg(long)]
+        latest: bool,
+
+        /// Accept changed build commands without asking (for scripts)
+        #[arg(long)]
+        trust: bool,
+    },
+
+    /// Show what changed between an installed skill and its tap
+    Diff {
+        /// Installed skill name, or a source agent file (SKILL.md,
+        /// .cursorrules, ...) to compare with a canonical skill
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
+        name: String,
+
+        /// Version to compare with (default: the tap's current checkout); for
+        /// a source file, the canonical skill.yaml or its directory
+        #[arg(value_name = "VERSION")]
+        to: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only list changed files
+        #[arg(long, conflicts_with = "json")]
+        stat: bool,
+
+        /// Show old and new side by side (when the terminal is wide enough)
+        #[arg(long, conflicts_with_all = ["json", "stat"])]
+        side_by_side: bool,
+
+        /// Show every line of long diffs instead of cutting them short
+        #[arg(long, conflicts_with_all = ["json", "stat"])]
+        full_diff: bool,
     },
 
-    /// Remove an installed skill
+    /// Remove an installed skill and the files it exported for agents
     Remove {
         /// Skill name to remove
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
         name: String,
+
+        /// Leave the skill's agent exports (SKILL.md files, mcp.json
+        /// entries) in place
+        #[arg(long)]
+        keep_exports: bool,
     },
 
     /// Show detailed info about a skill
     Info {
         /// Skill name
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
         name: String,
+
+        /// Show the skill's instructions instead of its metadata
+        #[arg(long)]
+        instructions: bool,
+
+        /// Print instructions as unrendered markdown (for piping)
+        #[arg(long, requires = "instructions")]
+        raw: bool,
+    },
+
+    /// Show a skill's instructions (alias for `info --instructions`)
+    Readme {
+        /// Skill name
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
+        name: String,
+
+        /// Print unrendered markdown (for piping)
+        #[arg(long)]
+        raw: bool,
     },
 
     /// Validate a skill manifest (skill.yaml)
     Validate {
         /// Path to skill directory or skill.yaml file
         path: String,
+
+        /// Treat warnings (missing instruction or workflow files, unknown
+        /// daemons) as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Print the findings as JSON (field, severity, message)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show installed skills' triggers, or find triggers that collide
+    Triggers {
+        /// Only report triggers shared or overlapping between skills
+        #[arg(long)]
+        conflicts: bool,
+
+        /// Also check a skill directory that isn't installed (repeatable)
+        #[arg(long)]
+        path: Vec<String>,
+    },
+
+    /// Convert a legacy skill.json manifest to skill.yaml
+    MigrateManifest {
+        /// Path to skill.json or a skill directory containing one
+        path: String,
+
+        /// Output path (default: skill.yaml in the skill directory)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Print the generated skill.yaml without writing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Overwrite an existing skill.yaml
+        #[arg(long)]
+        force: bool,
     },
 
-    /// Export skill for a specific agent (claude-code, cursor, codex, mcp, windsurf, zed, gemini, aider)
+    /// Export skill for a specific agent (claude-code, cursor, codex, mcp, windsurf, zed, gemini, aider, all)
     Export {
-        /// Target agent: claude-code, cursor, codex, mcp, windsurf, zed, gemini, aider
+        /// Target agent: claude-code, claude-desktop, continue, cursor, codex, mcp, windsurf, zed,
+        /// gemini, aider, or all (every target but claude-desktop and continue, each in its own
+        /// subdirectory)
         target: String,
 
         /// Skill name or path to skill directory
@@ -304,12 +790,87 @@ enum SkillAction {
         /// Output directory (default: current directory)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Don't write anything; fail if any exported file is out of date
+        #[arg(long)]
+        check: bool,
+
+        /// Output layout: standalone files, or project layout
+        /// (.cursor/rules/<name>.mdc, .claude/skills/<name>/SKILL.md)
+        #[arg(long, alias = "scope", value_parser = ["standalone", "project"], default_value = "standalone")]
+        layout: String,
+
+        /// Cursor rules format: a legacy .cursorrules file, or a .mdc rule
+        /// with frontmatter (default: cursorrules, or mdc for --layout project)
+        #[arg(long, value_parser = ["cursorrules", "mdc"])]
+        format: Option<String>,
+
+        /// With --layout project: add the generated files to the project's
+        /// .gitignore (add) or leave it untouched (skip)
+        #[arg(long, value_parser = ["add", "skip"], default_value = "skip")]
+        gitignore: String,
+
+        /// Add the skill to an existing Claude Code plugin: write SKILL.md
+        /// into the plugin's skills folder and list it in its plugin.json
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "layout", "gitignore", "with_commands"])]
+        merge_into: Option<String>,
+
+        /// Only allow the skill to run the `fgp call` commands for its daemon
+        /// methods that match this glob (e.g. "fgp *"); Claude Code only
+        #[arg(long, value_name = "GLOB")]
+        tools_scope: Option<String>,
+
+        /// Add a "What's New" section with the skill's CHANGELOG.md entries
+        /// after this version
+        #[arg(long, value_name = "VERSION")]
+        since_version: Option<String>,
+
+        /// Warn about fields an import filled in with low confidence that
+        /// nobody has reviewed since; `=deny` refuses to export them
+        #[arg(long, value_name = "MODE", num_args = 0..=1, require_equals = true, default_missing_value = "warn", value_parser = ["warn", "deny"])]
+        annotate_confidence: Option<String>,
+
+        /// Only export these daemons' methods and dependencies
+        /// (comma-separated, e.g. gmail,calendar)
+        #[arg(long, value_name = "DAEMONS", value_delimiter = ',')]
+        daemons: Vec<String>,
+
+        /// For the mcp target, list one tool per daemon (taking `method` and
+        /// `params`) instead of one per method
+        #[arg(long)]
+        group_by_daemon: bool,
+
+        /// Add a "Usage Considerations" section for methods whose daemon
+        /// manifest declares a cost or rate limit
+        #[arg(long)]
+        include_cost: bool,
+
+        /// Vary the example param values in the generated `fgp call`
+        /// examples (the same seed always gives the same values)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        example_seed: u64,
+
+        /// Also write a slash command per daemon method (or per entry in the
+        /// skill's triggers.commands); claude-code and cursor only
+        #[arg(long)]
+        with_commands: bool,
+
+        /// Line endings of the exported files (native is crlf on Windows)
+        #[arg(long, value_parser = ["lf", "crlf", "native"], default_value = "lf")]
+        line_endings: String,
     },
 
     /// Import a skill from agent-specific format to canonical FGP format
     Import {
-        /// Path to the skill file (e.g., SKILL.md, .cursorrules)
-        path: String,
+        /// Path to the skill file (e.g., SKILL.md, .cursorrules) or a directory to scan
+        #[arg(required_unless_present = "merge_dir")]
+        path: Option<String>,
+
+        /// Import every agent file in a directory (SKILL.md, .cursorrules,
+        /// .mcp.json, ...) as one skill, merging what each says; conflicts
+        /// are listed in IMPORT_REPORT.md
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["path", "format"])]
+        merge_dir: Option<String>,
 
         /// Source format (auto-detected if not specified)
         #[arg(short, long)]
@@ -326,6 +887,24 @@ enum SkillAction {
         /// Enrich with metadata from daemon registry (method descriptions, auth, etc.)
         #[arg(long)]
         enrich: bool,
+
+        /// Also add methods of verified daemons that the import doesn't
+        /// mention (off by default, so the import stays faithful)
+        #[arg(long, requires = "enrich")]
+        add_missing_methods: bool,
+
+        /// When importing a directory, keep going if a file fails to import
+        /// (with --merge-dir, leave it out of the merge)
+        #[arg(long)]
+        continue_on_error: bool,
+
+        /// Exit non-zero if a skill's quality grade is below this (A-F)
+        #[arg(long, value_name = "GRADE", ignore_case = true, value_parser = ["A", "B", "C", "D", "F"])]
+        min_grade: Option<String>,
+
+        /// With --dry-run, still write import-result.json
+        #[arg(long)]
+        emit_json: bool,
     },
 
     /// Manage skill taps (GitHub-based skill repositories)
@@ -334,7 +913,7 @@ enum SkillAction {
         action: TapAction,
     },
 
-    /// Manage skill marketplaces (legacy)
+    /// Manage skill marketplaces (deprecated: use `fgp skill tap`)
     Marketplace {
         #[command(subcommand)]
         action: MarketplaceAction,
@@ -349,10 +928,15 @@ enum SkillAction {
 
 #[derive(Subcommand)]
 enum TapAction {
-    /// Add a GitHub tap (e.g., fast-gateway-protocol/official-skills)
+    /// Add a tap from GitHub (e.g., fast-gateway-protocol/official-skills) or any git URL
     Add {
-        /// GitHub owner/repo (e.g., "fast-gateway-protocol/official-skills")
+        /// GitHub owner/repo (e.g., "fast-gateway-protocol/official-skills"), or
+        /// a git URL such as git@github.com:acme/skills.git
         repo: String,
+
+        /// Tap name (default: owner-repo, or the URL's last path component)
+        #[arg(long)]
+        name: Option<String>,
     },
 
     /// Remove a tap
@@ -379,6 +963,7 @@ enum McpAction {
     /// Register an installed skill with MCP server (and optionally other ecosystems)
     Register {
         /// Skill name to register
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
         name: String,
 
         /// Target ecosystems (comma-separated): mcp, claude, cursor, continue, windsurf, all
@@ -387,7 +972,15 @@ enum McpAction {
     },
 
     /// Register all installed skills with MCP server
-    RegisterAll,
+    RegisterAll {
+        /// Show what would be registered without writing any manifests
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only register skills whose name matches this glob (e.g. "gmail*")
+        #[arg(long)]
+        only: Option<String>,
+    },
 
     /// List MCP-registered skills
     List,
@@ -395,6 +988,7 @@ enum McpAction {
     /// Show registration status for a skill across all ecosystems
     Status {
         /// Skill name to check
+        #[arg(add = ArgValueCandidates::new(commands::completions::skills))]
         name: String,
     },
 }
@@ -412,13 +1006,27 @@ enum MarketplaceAction {
 
     /// Update all marketplaces (git pull)
     Update,
+
+    /// Replace marketplaces with the equivalent taps
+    Migrate {
+        /// Marketplace to migrate (default: all that have a tap layout)
+        name: Option<String>,
+    },
 }
 
+// Parsed once, so the size of NewDaemon doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum GenerateAction {
     /// List all available service presets
     List,
 
+    /// Show a preset's API URL, token variable and scaffolded methods
+    Show {
+        /// Preset name (e.g., "slack", "linear", "notion")
+        service: String,
+    },
+
     /// Create a new daemon from a service preset
     #[command(name = "new")]
     NewDaemon {
@@ -426,7 +1034,7 @@ enum GenerateAction {
         service: String,
 
         /// Use preset configuration for known services
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         preset: bool,
 
         /// Human-readable display name
@@ -448,16 +1056,47 @@ enum GenerateAction {
         /// Author name for changelog entries
         #[arg(long, default_value = "Claude")]
         author: String,
+
+        /// Render this directory of template files instead of the generator
+        /// ({{service}}, {{display_name}}, {{api_url}}, {{env_token}},
+        /// {{author}}, {{auth_style}}, {{auth_manifest}}, {{auth_client}},
+        /// {{env_config}} and {{auth_readme}} are substituted)
+        #[arg(long, value_name = "DIR")]
+        template: Option<String>,
+
+        /// How the API takes credentials: bearer, header:<name>,
+        /// query:<name>, basic, or oauth2[:<token-url>] to refresh and cache
+        /// OAuth2 tokens (asked from a terminal; default: bearer). Without
+        /// --template, the daemon comes from the built-in template
+        #[arg(long, alias = "auth", value_name = "STYLE")]
+        auth_style: Option<String>,
+
+        /// API environments, each NAME or NAME=BASE_URL (e.g.
+        /// dev,staging,prod); <SERVICE>_ENV picks one at runtime. Without
+        /// --template, the daemon comes from the built-in template
+        #[arg(long, value_name = "ENVS", value_delimiter = ',')]
+        environments: Vec<String>,
     },
 }
 
 fn main() -> Result<()> {
+    // Answer <Tab> requests from scripts installed with `completions --dynamic`
+    clap_complete::CompleteEnv::with_factory(Cli::command)
+        .var(commands::completions::COMPLETE_ENV)
+        .complete();
+
     let cli = Cli::parse();
+    let network = if cli.offline {
+        commands::network::Network::offline()
+    } else {
+        commands::network::Network::online()
+    };
 
     match cli.command {
-        Commands::Agents => commands::agents::run(),
+        Commands::Agents { json } => commands::agents::run(json),
         Commands::Generate { action } => match action {
             GenerateAction::List => commands::generate::list(),
+            GenerateAction::Show { service } => commands::generate::show(&service),
             GenerateAction::NewDaemon {
                 service,
                 preset,
@@ -466,14 +1105,22 @@ fn main() -> Result<()> {
                 env_token,
                 output,
                 author,
+                template,
+                auth_style,
+                environments,
             } => commands::generate::new_daemon(
                 &service,
-                preset,
-                display_name.as_deref(),
-                api_url.as_deref(),
-                env_token.as_deref(),
-                output.as_deref(),
-                &author,
+                &commands::generate::NewDaemonOptions {
+                    preset,
+                    display_name: display_name.as_deref(),
+                    api_url: api_url.as_deref(),
+                    env_token: env_token.as_deref(),
+                    output_dir: output.as_deref(),
+                    author: &author,
+                    template: template.as_deref(),
+                    auth_style: auth_style.as_deref(),
+                    environments: &environments,
+                },
             ),
         },
         Commands::New {
@@ -482,21 +1129,146 @@ fn main() -> Result<()> {
             language,
             no_git,
         } => commands::new::run(&name, description.as_deref(), &language, no_git),
+        Commands::Dev {
+            skill,
+            targets,
+            clean,
+        } => commands::dev::run(&skill, &targets, clean),
         Commands::Start {
             service,
             foreground,
         } => commands::start::run(&service, foreground),
-        Commands::Stop { service } => commands::stop::run(&service),
-        Commands::Status { verbose } => commands::status::run(verbose),
+        Commands::Stop {
+            service,
+            orphan,
+            yes,
+        } => match orphan {
+            Some(pid) => commands::stop::orphan(pid, yes),
+            None => commands::stop::run(service.as_deref().unwrap_or_default()),
+        },
+        Commands::Restart {
+            service,
+            all,
+            wait_timeout,
+        } => commands::restart::run(service.as_deref(), all, wait_timeout),
+        Commands::Status {
+            verbose,
+            json,
+            exit_code,
+        } => commands::status::run(verbose, json, exit_code),
+        Commands::Doctor { fix, yes } => commands::doctor::run(fix, yes),
+        Commands::Up { wait_timeout } => commands::autostart::up(wait_timeout),
+        Commands::Down => commands::autostart::down(),
         Commands::Call {
             method,
             params,
+            pairs,
             service,
             no_auto_start,
-        } => commands::call::run(&method, &params, service.as_deref(), no_auto_start),
-        Commands::Install { path } => commands::install::run(&path),
-        Commands::Methods { service } => commands::methods::run(&service),
-        Commands::Health { service } => commands::health::run(&service),
+            dry_run,
+            stream,
+            output,
+            no_history,
+            skip_validation,
+            validate,
+            yes,
+            context,
+            param_from_call,
+            from_call_params,
+            verbose,
+            select,
+            expect_exit,
+            expect_contains,
+        } => commands::call::run(
+            &method,
+            &params,
+            &pairs,
+            &commands::call::CallOptions {
+                service: service.as_deref(),
+                no_auto_start,
+                dry_run,
+                stream,
+                json_output: output == "json",
+                no_history,
+                skip_validation,
+                validate,
+                yes,
+                context: context.as_deref(),
+                param_from_call: param_from_call.as_deref(),
+                from_call_params: &from_call_params,
+                verbose,
+                select: select.as_deref(),
+                expect_exit,
+                expect_contains: expect_contains.as_deref(),
+            },
+        ),
+        Commands::History {
+            service,
+            method,
+            since,
+            limit,
+            json,
+        } => commands::history::run(
+            service.as_deref(),
+            method.as_deref(),
+            since.as_deref(),
+            limit,
+            json,
+        ),
+        Commands::DaemonProxy { action } => match action {
+            None => commands::daemon_proxy::start(false),
+            Some(DaemonProxyAction::Start { foreground }) => {
+                commands::daemon_proxy::start(foreground)
+            }
+            Some(DaemonProxyAction::Stop) => commands::daemon_proxy::stop(),
+            Some(DaemonProxyAction::Status) => commands::daemon_proxy::status(),
+        },
+        Commands::Install { path, git_ref } => {
+            commands::install::run(&path, git_ref.as_deref(), network)
+        }
+        Commands::Uninstall {
+            service,
+            keep_config,
+            dry_run,
+            force,
+        } => commands::uninstall::run(&service, keep_config, dry_run, force),
+        Commands::Methods {
+            service,
+            method,
+            export: Some(format),
+            output,
+            ..
+        } => commands::methods_export::run(
+            service.as_deref(),
+            method.as_deref(),
+            &format,
+            output.as_deref(),
+        ),
+        Commands::Methods {
+            service,
+            method,
+            json,
+            ..
+        } => commands::methods::run(
+            service.as_deref().unwrap_or_default(),
+            method.as_deref(),
+            json,
+        ),
+        Commands::Health {
+            service,
+            all,
+            watch,
+            interval,
+            timeout,
+        } => commands::health::run(
+            service.as_deref(),
+            &commands::health::HealthOptions {
+                all,
+                watch,
+                interval: std::time::Duration::from_secs(interval),
+                timeout: std::time::Duration::from_secs(timeout),
+            },
+        ),
         Commands::Dashboard { port, open } => commands::dashboard::run(port, open),
         Commands::Tui { poll } => commands::tui::run(poll),
         Commands::Logs {
@@ -505,8 +1277,13 @@ fn main() -> Result<()> {
             lines,
         } => commands::logs::run(&service, follow, lines),
         Commands::Mcp { action } => match action {
-            McpBridgeAction::Serve => commands::mcp_bridge::serve(),
-            McpBridgeAction::Install => commands::mcp_bridge::install(),
+            McpBridgeAction::Serve {
+                group_by_daemon,
+                services,
+            } => commands::mcp_bridge::serve(group_by_daemon, &services),
+            McpBridgeAction::Install { group_by_daemon } => {
+                commands::mcp_bridge::install(group_by_daemon)
+            }
             McpBridgeAction::Tools => commands::mcp_bridge::tools(),
         },
         Commands::Monitor {
@@ -517,43 +1294,192 @@ fn main() -> Result<()> {
             restart_delay,
         } => commands::monitor::run(interval, daemon, auto_restart, max_restarts, restart_delay),
         Commands::Workflow { action } => match action {
-            WorkflowAction::Run { file, verbose } => commands::workflow::run(&file, verbose),
-            WorkflowAction::Validate { file } => commands::workflow::validate(&file),
+            WorkflowAction::Run {
+                file,
+                verbose,
+                dry_run,
+                tui,
+            } => commands::workflow::run(&file, verbose, dry_run, tui),
+            WorkflowAction::Validate { file, lint } => commands::workflow::validate(&file, lint),
             WorkflowAction::List { builtin } => commands::workflow::list(builtin),
             WorkflowAction::Init { template } => commands::workflow::init(&template),
         },
         Commands::Skill { action } => match action {
             SkillAction::List => commands::skill::list(),
-            SkillAction::Search { query } => commands::skill::search(&query),
-            SkillAction::Install { name, from, license } => commands::skill::install(&name, from.as_deref(), license.as_deref()),
+            SkillAction::Search {
+                query,
+                limit,
+                installed,
+                json,
+                auto_update,
+            } => commands::skill::search(&query, limit, installed, json, auto_update, network),
+            SkillAction::Install {
+                all_from: Some(tap),
+                category,
+                pattern,
+                yes,
+                fail_fast,
+                trust,
+                with_deps,
+                ..
+            } => commands::skill::install_all_from(
+                &tap,
+                &commands::skill::InstallAllOptions {
+                    category: category.as_deref(),
+                    pattern: pattern.as_deref(),
+                    yes,
+                    trust,
+                    with_deps,
+                    fail_fast,
+                },
+                network,
+            ),
+            SkillAction::Install {
+                name,
+                from,
+                license,
+                trust,
+                with_deps,
+                ..
+            } => commands::skill::install(
+                name.as_deref().unwrap_or_default(),
+                from.as_deref(),
+                license.as_deref(),
+                trust,
+                with_deps,
+                network,
+            ),
             SkillAction::Update => commands::skill::check_updates(),
-            SkillAction::Upgrade { skill } => commands::skill::upgrade(skill.as_deref()),
-            SkillAction::Remove { name } => commands::skill::remove(&name),
-            SkillAction::Info { name } => commands::skill::info(&name),
-            SkillAction::Validate { path } => commands::skill_validate::validate(&path),
+            SkillAction::Upgrade {
+                skill,
+                dry_run,
+                latest,
+                trust,
+            } => commands::skill::upgrade(skill.as_deref(), dry_run, latest, trust, network),
+            SkillAction::Diff {
+                name,
+                to,
+                json,
+                stat,
+                side_by_side,
+                full_diff,
+            } => commands::skill_diff::run(
+                &name,
+                to.as_deref(),
+                json,
+                stat,
+                &commands::diff::DiffStyle::terminal(side_by_side, full_diff),
+            ),
+            SkillAction::Remove { name, keep_exports } => {
+                commands::skill::remove(&name, keep_exports)
+            }
+            SkillAction::Info {
+                name,
+                instructions,
+                raw,
+            } => commands::skill::info(&name, instructions, raw),
+            SkillAction::Readme { name, raw } => commands::skill::readme(&name, raw),
+            SkillAction::Validate { path, strict, json } => {
+                commands::skill_validate::validate(&path, strict, json)
+            }
+            SkillAction::Triggers { conflicts, path } => {
+                commands::skill_triggers::run(conflicts, &path)
+            }
+            SkillAction::MigrateManifest {
+                path,
+                output,
+                dry_run,
+                force,
+            } => {
+                commands::skill_migrate::migrate_manifest(&path, output.as_deref(), dry_run, force)
+            }
             SkillAction::Export {
                 target,
                 skill,
                 output,
-            } => commands::skill_export::export(&target, &skill, output.as_deref()),
+                check,
+                layout,
+                gitignore,
+                tools_scope,
+                since_version,
+                annotate_confidence,
+                daemons,
+                group_by_daemon,
+                include_cost,
+                example_seed,
+                with_commands,
+                line_endings,
+                format,
+                merge_into,
+            } => commands::skill_export::export(
+                &target,
+                &skill,
+                &commands::skill_export::ExportOptions {
+                    output: output.as_deref(),
+                    check,
+                    layout: &layout,
+                    gitignore: gitignore == "add",
+                    tools_scope: tools_scope.as_deref(),
+                    since_version: since_version.as_deref(),
+                    annotate_confidence: annotate_confidence.as_deref(),
+                    daemons: &daemons,
+                    group_by_daemon,
+                    include_cost,
+                    example_seed,
+                    with_commands,
+                    line_endings: &line_endings,
+                    format: format.as_deref(),
+                    merge_into: merge_into.as_deref(),
+                },
+            ),
             SkillAction::Import {
                 path,
+                merge_dir,
                 format,
                 output,
                 dry_run,
                 enrich,
-            } => commands::skill_import::import_skill(&path, format.as_deref(), output.as_deref(), dry_run, enrich),
+                add_missing_methods,
+                continue_on_error,
+                min_grade,
+                emit_json,
+            } => {
+                let options = commands::skill_import::ImportOptions {
+                    format: format.as_deref(),
+                    output: output.as_deref(),
+                    dry_run,
+                    enrich,
+                    add_missing_methods,
+                    continue_on_error,
+                    min_grade: min_grade
+                        .as_deref()
+                        .and_then(commands::skill_import::QualityGrade::parse),
+                    emit_json,
+                };
+                match merge_dir {
+                    Some(dir) => commands::skill_import::import_merged(&dir, &options),
+                    None => commands::skill_import::import_skill(
+                        path.as_deref().unwrap_or_default(),
+                        &options,
+                    ),
+                }
+            }
             SkillAction::Tap { action } => match action {
-                TapAction::Add { repo } => commands::skill_tap::add(&repo),
+                TapAction::Add { repo, name } => {
+                    commands::skill_tap::add(&repo, name.as_deref(), network)
+                }
                 TapAction::Remove { name } => commands::skill_tap::remove(&name),
                 TapAction::List => commands::skill_tap::list(),
-                TapAction::Update => commands::skill_tap::update(),
+                TapAction::Update => commands::skill_tap::update(network),
                 TapAction::Show { name } => commands::skill_tap::show(&name),
             },
             SkillAction::Marketplace { action } => match action {
-                MarketplaceAction::List => commands::skill::marketplace_list(),
-                MarketplaceAction::Add { url } => commands::skill::marketplace_add(&url),
-                MarketplaceAction::Update => commands::skill::marketplace_update(),
+                MarketplaceAction::List => commands::skill::marketplace_list(network),
+                MarketplaceAction::Add { url } => commands::skill::marketplace_add(&url, network),
+                MarketplaceAction::Update => commands::skill::marketplace_update(network),
+                MarketplaceAction::Migrate { name } => {
+                    commands::skill_legacy::migrate(name.as_deref(), network)
+                }
             },
             SkillAction::McpReg { action } => match action {
                 McpAction::Register { name, target } => {
@@ -563,10 +1489,20 @@ fn main() -> Result<()> {
                         commands::skill::register_with_targets(&name, &target)
                     }
                 }
-                McpAction::RegisterAll => commands::skill::mcp_register_all(),
+                McpAction::RegisterAll { dry_run, only } => {
+                    commands::skill::mcp_register_all(dry_run, only.as_deref())
+                }
                 McpAction::List => commands::skill::mcp_list(),
                 McpAction::Status { name } => commands::skill::registration_status(&name),
             },
         },
+        Commands::Registry { action } => match action {
+            RegistryAction::List { errors } => commands::registry::list(errors),
+        },
+        Commands::Completions {
+            shell,
+            dynamic,
+            output,
+        } => commands::completions::run(Cli::command(), shell, dynamic, output.as_deref()),
     }
 }
@@ -1,8 +1,18 @@
 //! Application state for the TUI dashboard.
 
 use std::fs;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use crossterm::event::KeyEvent;
+
+use crate::commands::client::{self, Client};
+use crate::commands::daemon_command;
+
+use super::event::EventHandler;
+use super::logs::LogPane;
+use super::palette::{self, Palette};
+
 /// Service status information.
 #[derive(Debug, Clone)]
 pub struct ServiceInfo {
@@ -86,6 +96,16 @@ pub struct App {
 
     /// Methods for the currently selected service (for detail view).
     pub detail_methods: Vec<String>,
+
+    /// Whether polling refreshes are ignored (manual refresh still works).
+    pub paused: bool,
+
+    /// Log pane for the selected service, when open.
+    pub log_pane: Option<LogPane>,
+
+    /// Method palette, when open. While it is, keys go to it instead of the
+    /// normal keybindings.
+    pub palette: Option<Palette>,
 }
 
 impl App {
@@ -101,6 +121,9 @@ impl App {
             show_help: false,
             show_detail: false,
             detail_methods: Vec::new(),
+            paused: false,
+            log_pane: None,
+            palette: None,
         }
     }
 
@@ -112,6 +135,15 @@ impl App {
                 self.message = None;
             }
         }
+
+        // Keep the log pane on the selected service and take new lines
+        let selected = self.selected_service().map(|s| s.name.clone());
+        if let Some(pane) = &mut self.log_pane {
+            match selected {
+                Some(name) if name != pane.service => *pane = LogPane::open(&name),
+                _ => pane.poll(),
+            }
+        }
     }
 
     /// Refresh service list from filesystem.
@@ -160,7 +192,7 @@ impl App {
     pub fn start_selected(&mut self) {
         if let Some(service) = self.selected_service().cloned() {
             if service.status == ServiceStatus::Stopped || service.status == ServiceStatus::Error {
-                match fgp_daemon::lifecycle::start_service(&service.name) {
+                match daemon_command::start_service(&service.name) {
                     Ok(()) => {
                         self.set_message(format!("Started {}", service.name), MessageType::Success);
                         self.refresh_services();
@@ -182,7 +214,7 @@ impl App {
             if service.status == ServiceStatus::Running
                 || service.status == ServiceStatus::Unhealthy
             {
-                match fgp_daemon::lifecycle::stop_service(&service.name) {
+                match daemon_command::stop_service(&service.name) {
                     Ok(()) => {
                         self.set_message(format!("Stopped {}", service.name), MessageType::Success);
                         self.refresh_services();
@@ -205,7 +237,7 @@ impl App {
                 || service.status == ServiceStatus::Unhealthy
             {
                 // Stop first
-                if let Err(e) = fgp_daemon::lifecycle::stop_service(&service.name) {
+                if let Err(e) = daemon_command::stop_service(&service.name) {
                     self.set_message(
                         format!("Failed to stop {}: {}", service.name, e),
                         MessageType::Error,
@@ -221,17 +253,13 @@ impl App {
                         break;
                     }
                     // Also check if socket exists but daemon is not responding
-                    if let Ok(client) = fgp_daemon::FgpClient::new(&socket) {
-                        if client.health().is_err() {
-                            break;
-                        }
-                    } else {
+                    if Client::at(&socket).health().is_err() {
                         break;
                     }
                 }
 
                 // Start again
-                match fgp_daemon::lifecycle::start_service(&service.name) {
+                match daemon_command::start_service(&service.name) {
                     Ok(()) => {
                         self.set_message(
                             format!("Restarted {}", service.name),
@@ -263,29 +291,27 @@ impl App {
         self.detail_methods.clear();
         if let Some(service) = self.selected_service() {
             let socket = fgp_daemon::lifecycle::service_socket_path(&service.name);
-            match fgp_daemon::FgpClient::new(&socket) {
-                Ok(client) => match client.methods() {
-                    Ok(response) if response.ok => {
-                        if let Some(result) = response.result {
-                            if let Some(methods) = result["methods"].as_array() {
-                                for method in methods {
-                                    let name = method["name"].as_str().unwrap_or("unknown");
-                                    // Skip internal methods
-                                    if name != "health" && name != "stop" && name != "methods" {
-                                        self.detail_methods.push(name.to_string());
-                                    }
+            match Client::at(&socket).methods() {
+                Ok(response) if response.ok => {
+                    if let Some(result) = response.result {
+                        if let Some(methods) = result["methods"].as_array() {
+                            for method in methods {
+                                let name = method["name"].as_str().unwrap_or("unknown");
+                                // Skip internal methods
+                                if name != "health" && name != "stop" && name != "methods" {
+                                    self.detail_methods.push(name.to_string());
                                 }
                             }
                         }
                     }
-                    _ => {
-                        self.detail_methods
-                            .push("Error loading methods".to_string());
-                    }
-                },
-                Err(_) => {
+                }
+                Err(client::Error::Connect { .. }) => {
                     self.detail_methods.push("Daemon not running".to_string());
                 }
+                _ => {
+                    self.detail_methods
+                        .push("Error loading methods".to_string());
+                }
             }
         }
     }
@@ -299,6 +325,75 @@ impl App {
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
+
+    /// Open or close the log pane for the selected service.
+    pub fn toggle_logs(&mut self) {
+        if self.log_pane.take().is_some() {
+            return;
+        }
+        match self.selected_service() {
+            Some(service) => self.log_pane = Some(LogPane::open(&service.name)),
+            None => self.set_message("No service selected".to_string(), MessageType::Error),
+        }
+    }
+
+    /// Open the method palette for the selected service.
+    pub fn open_palette(&mut self) {
+        let Some(name) = self.selected_service().map(|s| s.name.clone()) else {
+            self.set_message("No service selected".to_string(), MessageType::Error);
+            return;
+        };
+        let methods = crate::commands::guardrails::declared_methods(&name);
+        if methods.is_empty() {
+            self.set_message(
+                format!("No methods declared in {}'s manifest", name),
+                MessageType::Error,
+            );
+            return;
+        }
+        self.palette = Some(Palette::new(&name, methods));
+    }
+
+    /// Pass a key to the open palette, starting the call it asks for on a
+    /// background thread.
+    pub fn palette_key(&mut self, key: KeyEvent, events: &EventHandler) {
+        let Some(open) = &mut self.palette else {
+            return;
+        };
+        match open.handle_key(key) {
+            palette::Action::None => {}
+            palette::Action::Close => self.palette = None,
+            palette::Action::Call { method, params } => {
+                palette::spawn_call(open.service.clone(), method, params, events.sender());
+            }
+        }
+    }
+
+    /// Save a status snapshot (the `fgp status --json` structure, or a
+    /// markdown table) to a timestamped file in the current directory.
+    pub fn save_snapshot(&mut self, markdown: bool) {
+        match write_snapshot(markdown) {
+            Ok(path) => self.set_message(
+                format!("Saved snapshot to {}", path.display()),
+                MessageType::Success,
+            ),
+            Err(e) => self.set_message(
+                format!("Failed to save snapshot: {}", e),
+                MessageType::Error,
+            ),
+        }
+    }
+
+    /// Pause or resume auto-refresh.
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        let text = if self.paused {
+            "Auto-refresh paused (r refreshes, p resumes)"
+        } else {
+            "Auto-refresh resumed"
+        };
+        self.set_message(text.to_string(), MessageType::Success);
+    }
 }
 
 impl Default for App {
@@ -307,6 +402,28 @@ impl Default for App {
     }
 }
 
+/// Query every service and write the snapshot, returning the file written.
+fn write_snapshot(markdown: bool) -> anyhow::Result<PathBuf> {
+    use crate::commands::status;
+
+    let snapshots = status::collect_snapshots()?;
+    let now = chrono::Local::now();
+    let (content, extension) = if markdown {
+        let taken_at = now.format("%Y-%m-%d %H:%M:%S %Z").to_string();
+        (status::snapshot_markdown(&snapshots, &taken_at), "md")
+    } else {
+        (serde_json::to_string_pretty(&snapshots)? + "\n", "json")
+    };
+
+    let path = std::env::current_dir()?.join(format!(
+        "fgp-status-{}.{}",
+        now.format("%Y%m%d-%H%M%S"),
+        extension
+    ));
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
 /// Discover all installed services.
 fn discover_services() -> Vec<ServiceInfo> {
     let services_dir = fgp_daemon::lifecycle::fgp_services_dir();
@@ -359,25 +476,22 @@ fn get_service_status(
         return (ServiceStatus::Stopped, None, None);
     }
 
-    match fgp_daemon::FgpClient::new(socket_path) {
-        Ok(client) => match client.health() {
-            Ok(response) if response.ok => {
-                let result = response.result.unwrap_or_default();
-                let version = result["version"].as_str().map(String::from);
-                let uptime = result["uptime_seconds"].as_u64();
-                let status_str = result["status"].as_str().unwrap_or("running");
-
-                let status = match status_str {
-                    "healthy" | "running" => ServiceStatus::Running,
-                    "degraded" | "unhealthy" => ServiceStatus::Unhealthy,
-                    _ => ServiceStatus::Running,
-                };
-
-                (status, version, uptime)
-            }
-            _ => (ServiceStatus::Error, None, None),
-        },
-        Err(_) => (ServiceStatus::Error, None, None),
+    match Client::at(socket_path).health() {
+        Ok(response) if response.ok => {
+            let result = response.result.unwrap_or_default();
+            let version = result["version"].as_str().map(String::from);
+            let uptime = result["uptime_seconds"].as_u64();
+            let status_str = result["status"].as_str().unwrap_or("running");
+
+            let status = match status_str {
+                "healthy" | "running" => ServiceStatus::Running,
+                "degraded" | "unhealthy" => ServiceStatus::Unhealthy,
+                _ => ServiceStatus::Running,
+            };
+
+            (status, version, uptime)
+        }
+        _ => (ServiceStatus::Error, None, None),
     }
 }
 
@@ -6,6 +6,9 @@ use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
+use super::palette::CallReply;
+use crate::commands::workflow_engine::RunUpdate;
+
 /// Application events.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -18,21 +21,33 @@ pub enum Event {
     Refresh,
     /// Terminal resize.
     Resize(u16, u16),
+    /// A call made from the method palette finished.
+    Call(CallReply),
+    /// Progress of the workflow being watched.
+    Workflow(RunUpdate),
+}
+
+impl From<RunUpdate> for Event {
+    fn from(update: RunUpdate) -> Self {
+        Event::Workflow(update)
+    }
 }
 
 /// Event handler that manages input and tick events.
 pub struct EventHandler {
     /// Event receiver.
     receiver: mpsc::Receiver<Event>,
+    /// Sender for events produced by background work.
+    sender: mpsc::Sender<Event>,
     /// Input handler thread.
     #[allow(dead_code)]
     input_handle: thread::JoinHandle<()>,
     /// Tick handler thread.
     #[allow(dead_code)]
     tick_handle: thread::JoinHandle<()>,
-    /// Refresh handler thread.
+    /// Refresh handler thread, if services are polled.
     #[allow(dead_code)]
-    refresh_handle: thread::JoinHandle<()>,
+    refresh_handle: Option<thread::JoinHandle<()>>,
 }
 
 impl EventHandler {
@@ -42,6 +57,16 @@ impl EventHandler {
     /// * `tick_rate` - How often to send tick events (for UI refresh)
     /// * `poll_rate` - How often to poll service health
     pub fn new(tick_rate: Duration, poll_rate: Duration) -> Self {
+        Self::spawn(tick_rate, Some(poll_rate))
+    }
+
+    /// Create an event handler that doesn't poll services, for views driven
+    /// by events sent through [`sender`](Self::sender).
+    pub fn without_polling(tick_rate: Duration) -> Self {
+        Self::spawn(tick_rate, None)
+    }
+
+    fn spawn(tick_rate: Duration, poll_rate: Option<Duration>) -> Self {
         let (sender, receiver) = mpsc::channel();
 
         // Input handler thread
@@ -77,22 +102,30 @@ impl EventHandler {
         });
 
         // Refresh handler thread (service polling)
-        let refresh_sender = sender;
-        let refresh_handle = thread::spawn(move || loop {
-            thread::sleep(poll_rate);
-            if refresh_sender.send(Event::Refresh).is_err() {
-                break;
-            }
+        let refresh_handle = poll_rate.map(|poll_rate| {
+            let refresh_sender = sender.clone();
+            thread::spawn(move || loop {
+                thread::sleep(poll_rate);
+                if refresh_sender.send(Event::Refresh).is_err() {
+                    break;
+                }
+            })
         });
 
         Self {
             receiver,
+            sender,
             input_handle,
             tick_handle,
             refresh_handle,
         }
     }
 
+    /// A sender for background work (such as palette calls) to report back on.
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
+
     /// Get the next event, blocking until one is available.
     pub fn next(&self) -> Result<Event> {
         Ok(self.receiver.recv()?)
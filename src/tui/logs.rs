@@ -0,0 +1,342 @@
+//! Log pane for the TUI dashboard.
+//!
+//! The selected service's log file is tailed on a dedicated reader thread,
+//! so a large or slow log never blocks rendering. The pane takes whatever the
+//! reader has sent on each tick.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::commands::logs::{file_identity, log_file_path, reopen_if_rotated};
+
+/// Lines kept in the pane.
+pub const MAX_LINES: usize = 500;
+
+/// Lines moved by PageUp/PageDown.
+const PAGE: usize = 10;
+
+/// How much of the end of the log is read when the pane opens.
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Interval between checks for new log output.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Marker shown where the log was rotated or truncated.
+pub const ROTATED_MARKER: &str = "── log rotated ──";
+
+/// What the reader thread reports.
+enum Update {
+    /// The log file the service writes to
+    Path(PathBuf),
+    /// There is no log file yet
+    Missing,
+    /// A complete line of output
+    Line(String),
+    /// The file was replaced or truncated, and is read again from the start
+    Rotated,
+}
+
+/// Recent log lines for one service.
+pub struct LogPane {
+    pub service: String,
+    /// Log file, once the reader has resolved it
+    pub path: Option<PathBuf>,
+    pub lines: VecDeque<String>,
+    /// Whether the log file is missing (the reader keeps waiting for it)
+    pub missing: bool,
+    /// Lines scrolled up from the bottom; 0 follows new output.
+    pub scroll: usize,
+    receiver: mpsc::Receiver<Update>,
+    stop: Arc<AtomicBool>,
+}
+
+impl LogPane {
+    /// Start tailing `service`'s log.
+    pub fn open(service: &str) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let reader_service = service.to_string();
+        let reader_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let path = log_file_path(&reader_service);
+            if sender.send(Update::Path(path.clone())).is_ok() {
+                tail(&path, &sender, &reader_stop);
+            }
+        });
+
+        Self {
+            service: service.to_string(),
+            path: None,
+            lines: VecDeque::new(),
+            missing: false,
+            scroll: 0,
+            receiver,
+            stop,
+        }
+    }
+
+    /// Take everything the reader has sent since the last call.
+    pub fn poll(&mut self) {
+        while let Ok(update) = self.receiver.try_recv() {
+            match update {
+                Update::Path(path) => self.path = Some(path),
+                Update::Missing => self.missing = true,
+                Update::Line(line) => {
+                    self.missing = false;
+                    self.push(line);
+                }
+                Update::Rotated => self.push(ROTATED_MARKER.to_string()),
+            }
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+
+        // Keep a scrolled-up view on the lines being read
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.max_scroll());
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(1)
+    }
+
+    /// Scroll up a page, which stops following new output.
+    pub fn scroll_up(&mut self) {
+        self.scroll = (self.scroll + PAGE).min(self.max_scroll());
+    }
+
+    /// Scroll down a page; reaching the bottom follows new output again.
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_sub(PAGE);
+    }
+
+    /// Whether the pane shows the newest lines as they arrive.
+    pub fn following(&self) -> bool {
+        self.scroll == 0
+    }
+
+    /// The `height` lines to show, ending `scroll` lines above the newest.
+    pub fn visible(&self, height: usize) -> impl Iterator<Item = &String> {
+        let end = self.lines.len() - self.scroll.min(self.lines.len());
+        let start = end.saturating_sub(height);
+        self.lines.range(start..end)
+    }
+}
+
+impl Drop for LogPane {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Send the end of `path` and then each line appended to it, until `stop` is
+/// set or the pane is gone.
+///
+/// A missing file is waited for. If the file is truncated or replaced (log
+/// rotation), it is reopened and read from the start.
+fn tail(path: &Path, sender: &mpsc::Sender<Update>, stop: &AtomicBool) {
+    let mut file = loop {
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+        match File::open(path) {
+            Ok(file) => break file,
+            Err(_) => {
+                if sender.send(Update::Missing).is_err() {
+                    return;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    };
+    let mut identity = file_identity(path);
+
+    let Ok((lines, mut position)) = last_lines(&mut file) else {
+        return;
+    };
+    for line in lines {
+        if sender.send(Update::Line(line)).is_err() {
+            return;
+        }
+    }
+
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => {
+                if let Some(reopened) = reopen_if_rotated(path, position, &mut identity) {
+                    if sender.send(Update::Rotated).is_err() {
+                        return;
+                    }
+                    reader = BufReader::new(reopened);
+                    position = 0;
+                    line.clear();
+                    continue;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+            Ok(n) => {
+                position += n as u64;
+                // Partial lines are completed on the next read
+                if line.ends_with(b"\n") {
+                    let text = String::from_utf8_lossy(&line).trim_end().to_string();
+                    if sender.send(Update::Line(text)).is_err() {
+                        return;
+                    }
+                    line.clear();
+                }
+            }
+            Err(_) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// The last [`MAX_LINES`] complete lines of `file`, reading at most
+/// [`TAIL_BYTES`] from its end, and the offset just after them.
+fn last_lines(file: &mut File) -> std::io::Result<(Vec<String>, u64)> {
+    let len = file.seek(SeekFrom::End(0))?;
+    let start = len.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut bytes = Vec::new();
+    file.by_ref().take(len - start).read_to_end(&mut bytes)?;
+
+    // A trailing partial line is left for the follow loop
+    let Some(end) = bytes.iter().rposition(|&b| b == b'\n') else {
+        file.seek(SeekFrom::Start(start))?;
+        return Ok((Vec::new(), start));
+    };
+    let position = start + end as u64 + 1;
+    file.seek(SeekFrom::Start(position))?;
+
+    let text = String::from_utf8_lossy(&bytes[..end]);
+    let mut lines: Vec<String> = text.split('\n').map(|l| l.trim_end().to_string()).collect();
+    if start > 0 {
+        // Reading started mid-file, so the first line is likely cut off
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(MAX_LINES);
+    Ok((lines.split_off(skip), position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::Instant;
+
+    /// A pane tailing `path` directly, skipping log file resolution.
+    fn pane_for(path: &Path) -> LogPane {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_path = path.to_path_buf();
+        let reader_stop = Arc::clone(&stop);
+        thread::spawn(move || tail(&reader_path, &sender, &reader_stop));
+        LogPane {
+            service: "test".to_string(),
+            path: Some(path.to_path_buf()),
+            lines: VecDeque::new(),
+            missing: false,
+            scroll: 0,
+            receiver,
+            stop,
+        }
+    }
+
+    /// Poll until the pane's last line is `last`.
+    fn wait_for(pane: &mut LogPane, last: &str) {
+        let start = Instant::now();
+        while pane.lines.back().map(String::as_str) != Some(last) {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "never saw {:?}: {:?}",
+                last,
+                pane.lines
+            );
+            thread::sleep(Duration::from_millis(20));
+            pane.poll();
+        }
+    }
+
+    #[test]
+    fn test_tail_follows_and_survives_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.log");
+        let mut pane = pane_for(&path);
+
+        // Waits for the file to appear
+        let start = Instant::now();
+        while !pane.missing {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(20));
+            pane.poll();
+        }
+
+        let history: String = (0..MAX_LINES + 20)
+            .map(|i| format!("line {}\n", i))
+            .collect();
+        std::fs::write(&path, history + "partial").unwrap();
+        wait_for(&mut pane, &format!("line {}", MAX_LINES + 19));
+        assert_eq!(pane.lines.len(), MAX_LINES);
+        assert!(!pane.missing);
+
+        let mut log = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(log, " done").unwrap();
+        wait_for(&mut pane, "partial done");
+
+        // Truncated by a rotation that copies the log away and empties it
+        std::fs::write(&path, "").unwrap();
+        thread::sleep(Duration::from_millis(300));
+        std::fs::write(&path, "after rotation\n").unwrap();
+        wait_for(&mut pane, "after rotation");
+        assert!(pane.lines.iter().any(|l| l == ROTATED_MARKER));
+    }
+
+    #[test]
+    fn test_scrolling() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pane = pane_for(&dir.path().join("daemon.log"));
+        for i in 0..30 {
+            pane.push(format!("line {}", i));
+        }
+
+        let shown = |pane: &LogPane| pane.visible(5).cloned().collect::<Vec<_>>();
+        assert_eq!(shown(&pane).last().unwrap(), "line 29");
+
+        pane.scroll_up();
+        assert!(!pane.following());
+        assert_eq!(shown(&pane).last().unwrap(), "line 19");
+
+        // New output doesn't move a scrolled-up view
+        pane.push("line 30".to_string());
+        assert_eq!(shown(&pane).last().unwrap(), "line 19");
+
+        pane.scroll_down();
+        pane.scroll_down();
+        assert!(pane.following());
+        assert_eq!(shown(&pane).last().unwrap(), "line 30");
+
+        for _ in 0..10 {
+            pane.scroll_up();
+        }
+        assert_eq!(shown(&pane), vec!["line 0"]);
+    }
+}
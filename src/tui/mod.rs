@@ -1,10 +1,14 @@
 //! TUI Dashboard for FGP daemon monitoring.
 //!
-//! Interactive terminal UI with real-time service status updates.
+//! Interactive terminal UI with real-time service status updates, and the
+//! live view of a workflow run (`fgp workflow run --tui`).
 
 pub mod app;
 pub mod event;
+pub mod logs;
+pub mod palette;
 pub mod ui;
+pub mod workflow;
 
 use anyhow::Result;
 use crossterm::{
@@ -13,20 +17,36 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
-use std::io;
+use std::io::{self, Stdout};
 use std::time::Duration;
 
 use app::App;
 use event::{Event, EventHandler};
 
-/// Run the TUI dashboard.
-pub fn run(poll_interval: Duration) -> Result<()> {
-    // Setup terminal
+/// Switch the terminal to raw mode on the alternate screen.
+fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    Ok(Terminal::new(backend)?)
+}
+
+/// Put the terminal back the way [`setup_terminal`] found it.
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+/// Run the TUI dashboard.
+pub fn run(poll_interval: Duration) -> Result<()> {
+    let mut terminal = setup_terminal()?;
 
     // Create app state and event handler
     let mut app = App::new();
@@ -38,14 +58,7 @@ pub fn run(poll_interval: Duration) -> Result<()> {
     // Main loop
     let result = run_app(&mut terminal, &mut app, &mut events);
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    restore_terminal(&mut terminal)?;
 
     result
 }
@@ -65,6 +78,10 @@ fn run_app<B: Backend>(
             Event::Tick => {
                 app.tick();
             }
+            Event::Key(key) if app.palette.is_some() => {
+                // The palette takes every key while it is open
+                app.palette_key(key, events);
+            }
             Event::Key(key) => {
                 use crossterm::event::KeyCode;
 
@@ -75,76 +92,89 @@ fn run_app<B: Backend>(
                             app.show_detail = false;
                         } else if app.show_help {
                             app.show_help = false;
+                        } else if app.log_pane.is_some() {
+                            app.log_pane = None;
                         } else {
                             app.should_quit = true;
                         }
                     }
-                    KeyCode::Char('q') => {
-                        if !app.show_detail && !app.show_help {
-                            app.should_quit = true;
-                        }
+                    KeyCode::Char('q') if !app.show_detail && !app.show_help => {
+                        app.should_quit = true;
                     }
                     // Navigation
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        if !app.show_detail {
-                            app.select_previous();
-                        }
+                    KeyCode::Up | KeyCode::Char('k') if !app.show_detail => {
+                        app.select_previous();
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if !app.show_detail {
-                            app.select_next();
-                        }
+                    KeyCode::Down | KeyCode::Char('j') if !app.show_detail => {
+                        app.select_next();
                     }
-                    KeyCode::Home => {
-                        if !app.show_detail {
-                            app.select_first();
-                        }
+                    KeyCode::Home if !app.show_detail => {
+                        app.select_first();
                     }
-                    KeyCode::End => {
-                        if !app.show_detail {
-                            app.select_last();
-                        }
+                    KeyCode::End if !app.show_detail => {
+                        app.select_last();
                     }
                     // Actions
-                    KeyCode::Char('s') => {
-                        if !app.show_detail && !app.show_help {
-                            app.start_selected();
-                        }
+                    KeyCode::Char('s') if !app.show_detail && !app.show_help => {
+                        app.start_selected();
                     }
-                    KeyCode::Enter | KeyCode::Char('d') => {
-                        if !app.show_help {
-                            app.toggle_detail();
-                        }
+                    KeyCode::Enter | KeyCode::Char('d') if !app.show_help => {
+                        app.toggle_detail();
                     }
-                    KeyCode::Char('x') => {
-                        if !app.show_detail && !app.show_help {
-                            app.stop_selected();
-                        }
+                    KeyCode::Char('x') if !app.show_detail && !app.show_help => {
+                        app.stop_selected();
                     }
-                    KeyCode::Char('R') => {
-                        if !app.show_detail && !app.show_help {
-                            app.restart_selected();
-                        }
+                    KeyCode::Char('R') if !app.show_detail && !app.show_help => {
+                        app.restart_selected();
+                    }
+                    KeyCode::Char('r') if !app.show_detail && !app.show_help => {
+                        app.refresh_services();
+                    }
+                    KeyCode::Char('p') if !app.show_detail && !app.show_help => {
+                        app.toggle_pause();
+                    }
+                    KeyCode::Char('w') if !app.show_detail && !app.show_help => {
+                        app.save_snapshot(false);
+                    }
+                    KeyCode::Char('W') if !app.show_detail && !app.show_help => {
+                        app.save_snapshot(true);
+                    }
+                    KeyCode::Char('l') if !app.show_detail && !app.show_help => {
+                        app.toggle_logs();
                     }
-                    KeyCode::Char('r') => {
-                        if !app.show_detail && !app.show_help {
-                            app.refresh_services();
+                    KeyCode::Char('c') if !app.show_detail && !app.show_help => {
+                        app.open_palette();
+                    }
+                    KeyCode::PageUp => {
+                        if let Some(pane) = &mut app.log_pane {
+                            pane.scroll_up();
                         }
                     }
-                    KeyCode::Char('?') => {
-                        if !app.show_detail {
-                            app.toggle_help();
+                    KeyCode::PageDown => {
+                        if let Some(pane) = &mut app.log_pane {
+                            pane.scroll_down();
                         }
                     }
+                    KeyCode::Char('?') if !app.show_detail => {
+                        app.toggle_help();
+                    }
                     _ => {}
                 }
             }
             Event::Refresh => {
-                app.refresh_services();
+                if !app.paused {
+                    app.refresh_services();
+                }
             }
             Event::Resize(_, _) => {
                 // Terminal will redraw automatically
             }
+            Event::Call(reply) => {
+                if let Some(palette) = &mut app.palette {
+                    palette.finish(reply);
+                }
+            }
+            Event::Workflow(_) => {}
         }
 
         if app.should_quit {
@@ -0,0 +1,442 @@
+//! Method palette for the TUI dashboard.
+//!
+//! Lists the selected service's methods from its manifest, narrows them with
+//! a fuzzy filter, takes JSON params and shows the daemon's response. The call
+//! runs on its own thread and reports back through the event channel, so the
+//! dashboard keeps drawing while a slow method runs.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::event::Event;
+use crate::commands::client::Client;
+use crate::commands::skill_import::ManifestMethod;
+
+/// How long a call may take before it is reported as failed.
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lines moved by PageUp/PageDown in the result view.
+const PAGE: u16 = 10;
+
+/// Where the palette is in picking and calling a method.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stage {
+    /// Filtering the method list
+    Pick,
+    /// Typing params for the chosen method
+    Params,
+    /// Waiting for the daemon
+    Running,
+    /// Showing the response (or the failure)
+    Result(CallReply),
+}
+
+/// What the palette wants the app to do after a key.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    None,
+    Close,
+    /// Send `method` (fully qualified) with `params`
+    Call {
+        method: String,
+        params: Value,
+    },
+}
+
+/// Outcome of a call made from the palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallReply {
+    pub method: String,
+    /// The method's result, or why the call failed
+    pub result: Result<Value, String>,
+    pub elapsed: Duration,
+}
+
+/// Palette state for one service.
+pub struct Palette {
+    pub service: String,
+    methods: Vec<ManifestMethod>,
+    pub filter: String,
+    /// Index into [`Palette::matches`]
+    pub selected: usize,
+    /// Index into `methods` of the method being called
+    chosen: Option<usize>,
+    pub params: String,
+    /// Why the params can't be sent, or the destructive-method prompt
+    pub notice: Option<String>,
+    /// Enter was pressed once on a destructive method's params
+    confirming: bool,
+    pub stage: Stage,
+    /// Result lines scrolled past
+    pub scroll: u16,
+}
+
+impl Palette {
+    pub fn new(service: &str, methods: Vec<ManifestMethod>) -> Self {
+        Self {
+            service: service.to_string(),
+            methods,
+            filter: String::new(),
+            selected: 0,
+            chosen: None,
+            params: String::new(),
+            notice: None,
+            confirming: false,
+            stage: Stage::Pick,
+            scroll: 0,
+        }
+    }
+
+    /// Methods matching the filter, best match first.
+    pub fn matches(&self) -> Vec<&ManifestMethod> {
+        let mut scored: Vec<(i64, usize)> = self
+            .methods
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| fuzzy_score(&self.filter, &m.name).map(|score| (score, i)))
+            .collect();
+        // Stable, so equal scores keep manifest order
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, i)| &self.methods[i]).collect()
+    }
+
+    /// The method whose params are being edited or that was called.
+    pub fn chosen(&self) -> Option<&ManifestMethod> {
+        self.chosen.map(|i| &self.methods[i])
+    }
+
+    /// Whether a destructive call is waiting for its second Enter.
+    pub fn confirming(&self) -> bool {
+        self.confirming
+    }
+
+    /// The wire name of `method` on this service.
+    fn qualified(&self, method: &ManifestMethod) -> String {
+        if method.name.contains('.') {
+            method.name.clone()
+        } else {
+            format!("{}.{}", self.service, method.name)
+        }
+    }
+
+    /// Handle a key; every key goes here while the palette is open.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match self.stage {
+            Stage::Pick => self.pick_key(key),
+            Stage::Params => self.params_key(key),
+            Stage::Running => match key.code {
+                // The reply is dropped when it arrives
+                KeyCode::Esc => Action::Close,
+                _ => Action::None,
+            },
+            Stage::Result(_) => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter => self.stage = Stage::Params,
+                    KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.scroll = self.scroll.saturating_add(1)
+                    }
+                    KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE),
+                    KeyCode::PageDown => self.scroll = self.scroll.saturating_add(PAGE),
+                    _ => {}
+                }
+                Action::None
+            }
+        }
+    }
+
+    fn pick_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => return Action::Close,
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.matches().len() => self.selected += 1,
+            KeyCode::Enter => {
+                let Some(name) = self.matches().get(self.selected).map(|m| m.name.clone()) else {
+                    return Action::None;
+                };
+                self.chosen = self.methods.iter().position(|m| m.name == name);
+                self.params.clear();
+                self.notice = None;
+                self.confirming = false;
+                self.stage = Stage::Params;
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn params_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => {
+                self.notice = None;
+                self.stage = Stage::Pick;
+            }
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Backspace => {
+                self.params.pop();
+                self.edited();
+            }
+            KeyCode::Char(c) => {
+                self.params.push(c);
+                self.edited();
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn edited(&mut self) {
+        self.notice = None;
+        self.confirming = false;
+    }
+
+    /// Validate the params and, if they are good (and a destructive method
+    /// was confirmed with a second Enter), ask for the call.
+    fn submit(&mut self) -> Action {
+        let Some(method) = self.chosen() else {
+            return Action::None;
+        };
+        let params = match parse_params(&self.params) {
+            Ok(params) => params,
+            Err(e) => {
+                self.notice = Some(e);
+                return Action::None;
+            }
+        };
+
+        let method_name = self.qualified(method);
+        if method.destructive && !self.confirming {
+            self.confirming = true;
+            self.notice = Some(format!(
+                "{} is marked destructive. Press Enter again to run it.",
+                method_name
+            ));
+            return Action::None;
+        }
+
+        self.notice = None;
+        self.confirming = false;
+        self.scroll = 0;
+        self.stage = Stage::Running;
+        Action::Call {
+            method: method_name,
+            params,
+        }
+    }
+
+    /// Show the reply to the call in flight (ignored if the palette moved on).
+    pub fn finish(&mut self, reply: CallReply) {
+        if self.stage == Stage::Running {
+            self.stage = Stage::Result(reply);
+        }
+    }
+}
+
+/// Parse typed params; empty input means no params.
+pub fn parse_params(input: &str) -> Result<Value, String> {
+    if input.trim().is_empty() {
+        return Ok(Value::Object(Default::default()));
+    }
+    match serde_json::from_str::<Value>(input) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err("Params must be a JSON object".to_string()),
+        Err(e) => Err(format!("Invalid JSON: {}", e)),
+    }
+}
+
+/// Score `candidate` against a fuzzy `query`, or `None` if the query's
+/// characters don't all appear in it in order (ignoring case).
+///
+/// Runs of consecutive characters and matches at the start of a segment
+/// (after `.`, `_` or `-`) score higher; characters skipped between matches
+/// cost a little. Each place the first character appears is tried.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let Some(&first) = query.first() else {
+        return Some(0);
+    };
+    (0..candidate.len())
+        .filter(|&start| candidate[start] == first)
+        .filter_map(|start| score_from(&query, &candidate, start))
+        .max()
+}
+
+/// Score a match of `query` whose first character is `candidate[start]`,
+/// taking the earliest match for each following character.
+fn score_from(query: &[char], candidate: &[char], start: usize) -> Option<i64> {
+    let segment_start = |i: usize| i == 0 || matches!(candidate[i - 1], '.' | '_' | '-');
+    let mut score = 1 + if segment_start(start) { 3 } else { 0 };
+    let mut previous = start;
+
+    for &q in &query[1..] {
+        let found = (previous + 1..candidate.len()).find(|&i| candidate[i] == q)?;
+        score += 1;
+        if found == previous + 1 {
+            score += 5;
+        } else if segment_start(found) {
+            score += 3;
+        }
+        score -= (found - previous - 1) as i64;
+        previous = found;
+    }
+    Some(score)
+}
+
+/// Call `method` on `service` in the background and send the outcome back as
+/// an [`Event::Call`].
+pub fn spawn_call(service: String, method: String, params: Value, sender: mpsc::Sender<Event>) {
+    thread::spawn(move || {
+        let start = Instant::now();
+        let result = Client::builder(&service)
+            .timeout(CALL_TIMEOUT)
+            .connect()
+            .and_then(|client| client.call(&method, params))
+            .and_then(|response| response.into_result())
+            .map_err(|e| e.to_string());
+        let _ = sender.send(Event::Call(CallReply {
+            method,
+            result,
+            elapsed: start.elapsed(),
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use serde_json::json;
+
+    fn method(name: &str, destructive: bool) -> ManifestMethod {
+        serde_json::from_value(json!({"name": name, "destructive": destructive})).unwrap()
+    }
+
+    fn palette() -> Palette {
+        Palette::new(
+            "gmail",
+            vec![
+                method("inbox", false),
+                method("gmail.search", false),
+                method("send", true),
+                method("mark_read", false),
+            ],
+        )
+    }
+
+    fn press(palette: &mut Palette, code: KeyCode) -> Action {
+        palette.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(palette: &mut Palette, text: &str) {
+        for c in text.chars() {
+            press(palette, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("", "anything").is_some());
+        assert!(fuzzy_score("snd", "send").is_some());
+        assert!(fuzzy_score("SEND", "gmail.send").is_some());
+        assert!(fuzzy_score("sned", "send").is_none());
+
+        // Consecutive and segment-start matches rank first
+        assert!(fuzzy_score("read", "mark_read") > fuzzy_score("read", "reload_data"));
+        assert!(fuzzy_score("send", "send") > fuzzy_score("send", "sync_end"));
+    }
+
+    #[test]
+    fn test_filter_and_pick() {
+        let mut palette = palette();
+        assert_eq!(palette.matches().len(), 4);
+
+        type_text(&mut palette, "se");
+        let names: Vec<&str> = palette.matches().iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["gmail.search", "send"]);
+
+        press(&mut palette, KeyCode::Down);
+        press(&mut palette, KeyCode::Enter);
+        assert_eq!(palette.stage, Stage::Params);
+        assert_eq!(palette.chosen().unwrap().name, "send");
+
+        // Esc goes back to the list, and again closes the palette
+        press(&mut palette, KeyCode::Esc);
+        assert_eq!(palette.stage, Stage::Pick);
+        assert_eq!(press(&mut palette, KeyCode::Esc), Action::Close);
+    }
+
+    #[test]
+    fn test_params_are_validated() {
+        let mut palette = palette();
+        press(&mut palette, KeyCode::Enter);
+
+        type_text(&mut palette, "{\"limit\": ");
+        assert_eq!(press(&mut palette, KeyCode::Enter), Action::None);
+        assert!(palette
+            .notice
+            .as_deref()
+            .unwrap()
+            .starts_with("Invalid JSON"));
+
+        type_text(&mut palette, "5}");
+        assert_eq!(palette.notice, None);
+        assert_eq!(
+            press(&mut palette, KeyCode::Enter),
+            Action::Call {
+                method: "gmail.inbox".to_string(),
+                params: json!({"limit": 5}),
+            }
+        );
+        assert_eq!(palette.stage, Stage::Running);
+
+        assert_eq!(
+            parse_params("[1, 2]").unwrap_err(),
+            "Params must be a JSON object"
+        );
+        assert_eq!(parse_params("  ").unwrap(), json!({}));
+    }
+
+    #[test]
+    fn test_destructive_methods_need_a_second_enter() {
+        let mut palette = palette();
+        type_text(&mut palette, "send");
+        press(&mut palette, KeyCode::Enter);
+
+        assert_eq!(press(&mut palette, KeyCode::Enter), Action::None);
+        assert!(palette.notice.as_deref().unwrap().contains("destructive"));
+        assert!(matches!(
+            press(&mut palette, KeyCode::Enter),
+            Action::Call { method, .. } if method == "gmail.send"
+        ));
+    }
+
+    #[test]
+    fn test_reply_after_close_is_ignored() {
+        let mut palette = palette();
+        let reply = CallReply {
+            method: "gmail.inbox".to_string(),
+            result: Err("timed out waiting for the daemon".to_string()),
+            elapsed: Duration::from_secs(30),
+        };
+
+        palette.finish(reply.clone());
+        assert_eq!(palette.stage, Stage::Pick);
+
+        press(&mut palette, KeyCode::Enter);
+        press(&mut palette, KeyCode::Enter);
+        palette.finish(reply.clone());
+        assert_eq!(palette.stage, Stage::Result(reply));
+    }
+}
@@ -4,11 +4,14 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
 use super::app::{format_uptime, App, MessageType, ServiceStatus};
+use super::logs::{LogPane, ROTATED_MARKER};
+use super::palette::{Palette, Stage};
+use crate::commands::logs::detect_log_level;
 
 /// Draw the entire UI.
 pub fn draw(frame: &mut Frame, app: &App) {
@@ -22,7 +25,16 @@ pub fn draw(frame: &mut Frame, app: &App) {
         .split(frame.area());
 
     draw_header(frame, chunks[0], app);
-    draw_service_table(frame, chunks[1], app);
+    if let Some(pane) = &app.log_pane {
+        let main = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        draw_service_table(frame, main[0], app);
+        draw_log_pane(frame, main[1], pane);
+    } else {
+        draw_service_table(frame, chunks[1], app);
+    }
     draw_footer(frame, chunks[2], app);
 
     // Draw overlays
@@ -33,6 +45,9 @@ pub fn draw(frame: &mut Frame, app: &App) {
     } else if app.show_help {
         draw_help_overlay(frame);
     }
+    if let Some(palette) = &app.palette {
+        draw_palette(frame, palette);
+    }
 }
 
 /// Draw the header with title and last update time.
@@ -44,7 +59,7 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
         format!("{}m ago", elapsed / 60)
     };
 
-    let title = Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             " FGP Dashboard ",
             Style::default()
@@ -56,7 +71,17 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
             format!("Updated: {} ", time_str),
             Style::default().fg(Color::DarkGray),
         ),
-    ]);
+    ];
+    if app.paused {
+        spans.push(Span::styled(
+            " PAUSED ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let title = Line::from(spans);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -165,6 +190,60 @@ fn draw_service_table(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(table, area);
 }
 
+/// Draw the log pane for the selected service.
+fn draw_log_pane(frame: &mut Frame, area: Rect, pane: &LogPane) {
+    let height = area.height.saturating_sub(2) as usize;
+
+    let lines: Vec<Line> = if pane.lines.is_empty() {
+        let text = match (&pane.path, pane.missing) {
+            (Some(path), true) => format!(" No log file yet at {}", path.display()),
+            (Some(_), false) => " Log is empty".to_string(),
+            (None, _) => " Finding log file...".to_string(),
+        };
+        vec![Line::from(Span::styled(
+            text,
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        pane.visible(height)
+            .map(|line| {
+                let color = match detect_log_level(line) {
+                    _ if line == ROTATED_MARKER => Color::DarkGray,
+                    Some("ERROR") => Color::Red,
+                    Some("WARN") => Color::Yellow,
+                    Some("DEBUG") | Some("TRACE") => Color::DarkGray,
+                    _ => Color::Reset,
+                };
+                Line::from(Span::styled(line.clone(), Style::default().fg(color)))
+            })
+            .collect()
+    };
+
+    let mut title = vec![Span::styled(
+        format!(" Logs: {} ", pane.service),
+        Style::default().fg(Color::White),
+    )];
+    if let Some(path) = &pane.path {
+        title.push(Span::styled(
+            format!("{} ", path.display()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if !pane.following() {
+        title.push(Span::styled(
+            format!(" ↑{} lines, PgDn to follow ", pane.scroll),
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Line::from(title));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 /// Draw the footer with keybindings and messages.
 fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::default()
@@ -186,6 +265,14 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
         Span::raw(" Restart  "),
         Span::styled("[d]", Style::default().fg(Color::Cyan)),
         Span::raw(" Detail  "),
+        Span::styled("[l]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Logs  "),
+        Span::styled("[c]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Call  "),
+        Span::styled("[w]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Save  "),
+        Span::styled("[p]", Style::default().fg(Color::Yellow)),
+        Span::raw(if app.paused { " Resume  " } else { " Pause  " }),
         Span::styled("[?]", Style::default().fg(Color::Magenta)),
         Span::raw(" Help  "),
         Span::styled("[q]", Style::default().fg(Color::DarkGray)),
@@ -270,10 +357,30 @@ fn draw_help_overlay(frame: &mut Frame) {
             Span::styled("  d/Enter  ", Style::default().fg(Color::Cyan)),
             Span::raw("View service details"),
         ]),
+        Line::from(vec![
+            Span::styled("  l        ", Style::default().fg(Color::Cyan)),
+            Span::raw("Toggle log pane"),
+        ]),
+        Line::from(vec![
+            Span::styled("  PgUp/Dn  ", Style::default().fg(Color::Cyan)),
+            Span::raw("Scroll logs (PgDn to the end follows)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c        ", Style::default().fg(Color::Cyan)),
+            Span::raw("Call a method (type to filter, Esc to go back)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  w/W      ", Style::default().fg(Color::Cyan)),
+            Span::raw("Save status snapshot (JSON/markdown)"),
+        ]),
         Line::from(vec![
             Span::styled("  r        ", Style::default().fg(Color::Cyan)),
             Span::raw("Refresh service list"),
         ]),
+        Line::from(vec![
+            Span::styled("  p        ", Style::default().fg(Color::Yellow)),
+            Span::raw("Pause/resume auto-refresh"),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("  ?        ", Style::default().fg(Color::Magenta)),
@@ -398,8 +505,211 @@ fn draw_detail_overlay(frame: &mut Frame, service: &super::app::ServiceInfo, met
     frame.render_widget(detail_paragraph, area);
 }
 
+/// Draw the method palette: the method list, the params input, or the
+/// call's outcome, depending on its stage.
+fn draw_palette(frame: &mut Frame, palette: &Palette) {
+    let area = centered_rect(70, 70, frame.area());
+
+    // Clear the area first
+    frame.render_widget(Clear, area);
+
+    let (title, border, lines, scroll) = match &palette.stage {
+        Stage::Pick => (
+            format!(" Call {} ", palette.service),
+            Color::Cyan,
+            palette_pick_lines(palette, area.height),
+            0,
+        ),
+        Stage::Params | Stage::Running => {
+            let name = palette.chosen().map(|m| m.name.as_str()).unwrap_or("");
+            (
+                format!(" {} ", name),
+                Color::Cyan,
+                palette_params_lines(palette),
+                0,
+            )
+        }
+        Stage::Result(reply) => {
+            let elapsed = format!("{:.1} ms", reply.elapsed.as_secs_f64() * 1000.0);
+            let (title, color, body) = match &reply.result {
+                Ok(value) => (
+                    format!(" {} ✓ {} ", reply.method, elapsed),
+                    Color::Green,
+                    serde_json::to_string_pretty(value).unwrap_or_default(),
+                ),
+                Err(e) => (
+                    format!(" {} ✗ failed after {} ", reply.method, elapsed),
+                    Color::Red,
+                    e.clone(),
+                ),
+            };
+            let mut lines: Vec<Line> = body
+                .lines()
+                .map(|l| Line::styled(format!("  {}", l), Style::default().fg(color)))
+                .collect();
+            lines.insert(0, Line::from(""));
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                "  ↑/↓ PgUp/PgDn scroll · Enter/Esc back to params",
+                Style::default().fg(Color::DarkGray),
+            ));
+            (title, color, lines, palette.scroll)
+        }
+    };
+
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .style(Style::default().bg(Color::Black));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+/// The filter input and matching methods, with the selection kept in view.
+fn palette_pick_lines(palette: &Palette, height: u16) -> Vec<Line<'static>> {
+    let matches = palette.matches();
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  > ", Style::default().fg(Color::Yellow)),
+            Span::raw(palette.filter.clone()),
+            Span::styled("▏", Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(""),
+    ];
+
+    // Borders, the lines above, and the hint below
+    let room = (height as usize).saturating_sub(7).max(1);
+    let first = palette.selected.saturating_sub(room - 1);
+    for (i, method) in matches.iter().enumerate().skip(first).take(room) {
+        let selected = i == palette.selected;
+        let marker = if selected { "  ▸ " } else { "    " };
+        let name_style = if selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        let mut spans = vec![
+            Span::raw(marker),
+            Span::styled(method.name.clone(), name_style),
+        ];
+        if method.destructive {
+            spans.push(Span::styled(
+                " (destructive)",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if let Some(description) = &method.description {
+            spans.push(Span::styled(
+                format!("  {}", description),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    if matches.is_empty() {
+        lines.push(Line::styled(
+            "    No matching methods",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "  Type to filter · ↑/↓ select · Enter choose · Esc close",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines
+}
+
+/// The chosen method's params, the JSON input, and any notice about it.
+fn palette_params_lines(palette: &Palette) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from("")];
+
+    if let Some(method) = palette.chosen() {
+        if let Some(description) = &method.description {
+            lines.push(Line::raw(format!("  {}", description)));
+            lines.push(Line::from(""));
+        }
+        if !method.params.is_empty() {
+            lines.push(Line::styled(
+                "  Params:",
+                Style::default().fg(Color::Yellow),
+            ));
+            for param in &method.params {
+                let mut spans = vec![
+                    Span::raw("    "),
+                    Span::styled(param.name.clone(), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        format!(" {}", param.param_type.as_deref().unwrap_or("any")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ];
+                if param.required {
+                    spans.push(Span::styled(
+                        " required",
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                if let Some(description) = &param.description {
+                    spans.push(Span::styled(
+                        format!("  {}", description),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+            lines.push(Line::from(""));
+        }
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled("  { } ", Style::default().fg(Color::Yellow)),
+        Span::raw(palette.params.clone()),
+        Span::styled("▏", Style::default().fg(Color::Yellow)),
+    ]));
+    lines.push(Line::from(""));
+
+    if palette.stage == Stage::Running {
+        lines.push(Line::styled(
+            "  Calling… (Esc closes without waiting)",
+            Style::default().fg(Color::Blue),
+        ));
+    } else {
+        if let Some(notice) = &palette.notice {
+            let color = if palette.confirming() {
+                Color::Yellow
+            } else {
+                Color::Red
+            };
+            lines.push(Line::styled(
+                format!("  {}", notice),
+                Style::default().fg(color),
+            ));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            "  JSON object, empty for none · Enter call · Esc back to methods",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    lines
+}
+
 /// Create a centered rectangle.
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub(super) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -0,0 +1,707 @@
+//! Live view of a workflow run (`fgp workflow run --tui`).
+//!
+//! The run happens on its own thread ([`workflow_engine::spawn`]) and
+//! reports its progress as events, which update the step list and each
+//! step's log. Cancelling works as Ctrl-C does in the plain output: the
+//! first press stops the run gracefully, a second one quits at once.
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use super::event::{Event, EventHandler};
+use super::ui::centered_rect;
+use crate::commands::workflow_engine::{
+    self, CancelReason, Cancellation, RunStatus, RunUpdate, StepRecord, StepStatus, Workflow,
+    WorkflowRun,
+};
+
+/// Lines moved by PageUp/PageDown.
+const PAGE: usize = 10;
+
+/// Lines of a step's result shown in its log.
+const MAX_RESULT_LINES: usize = 200;
+
+/// How the view ended.
+pub enum Outcome {
+    /// The run ended and the view was closed
+    Finished(Box<WorkflowRun>),
+    /// Cancel was pressed again while a cancelled run was winding down
+    ForceQuit,
+}
+
+/// What a key press asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Quit,
+    ForceQuit,
+}
+
+/// One step of the run.
+struct StepView {
+    /// `service.method`
+    label: String,
+    parallel: bool,
+    status: StepStatus,
+    /// When the step's first attempt started
+    started: Option<Instant>,
+    /// Set once the step reaches its final state
+    duration_ms: Option<f64>,
+    log: Vec<String>,
+}
+
+impl StepView {
+    fn running(&self) -> bool {
+        self.started.is_some() && self.duration_ms.is_none()
+    }
+}
+
+/// State of the workflow view.
+struct WorkflowView {
+    name: String,
+    steps: Vec<StepView>,
+    selected: usize,
+    /// Whether the selection moves to each step as it starts
+    follow: bool,
+    /// Log lines scrolled up from the bottom; 0 shows the newest.
+    scroll: usize,
+    started: Instant,
+    cancelling: bool,
+    message: Option<String>,
+    /// The finished run, once there is one
+    run: Option<Box<WorkflowRun>>,
+    show_summary: bool,
+}
+
+impl WorkflowView {
+    fn new(workflow: &Workflow) -> Self {
+        Self {
+            name: workflow.name.clone(),
+            steps: workflow
+                .steps
+                .iter()
+                .map(|step| StepView {
+                    label: step.method.clone(),
+                    parallel: step.group.is_some(),
+                    status: StepStatus::Pending,
+                    started: None,
+                    duration_ms: None,
+                    log: Vec::new(),
+                })
+                .collect(),
+            selected: 0,
+            follow: true,
+            scroll: 0,
+            started: Instant::now(),
+            cancelling: false,
+            message: None,
+            run: None,
+            show_summary: false,
+        }
+    }
+
+    fn update(&mut self, update: RunUpdate) {
+        match update {
+            RunUpdate::StepStarted { index, attempt } => {
+                let Some(step) = self.steps.get_mut(index) else {
+                    return;
+                };
+                if attempt > 1 {
+                    step.log.push(format!("↻ retrying (attempt {})", attempt));
+                } else {
+                    step.started = Some(Instant::now());
+                    step.log.push(format!("→ calling {}", step.label));
+                }
+                if self.follow {
+                    self.selected = index;
+                    self.scroll = 0;
+                }
+            }
+            RunUpdate::StepFinished(record) => self.finish_step(&record),
+            RunUpdate::Finished(run) => {
+                for record in &run.steps {
+                    if let Some(step) = self.steps.get_mut(record.index) {
+                        step.status = record.status;
+                    }
+                }
+                self.run = Some(run);
+                self.show_summary = true;
+                self.message = None;
+            }
+        }
+    }
+
+    fn finish_step(&mut self, record: &StepRecord) {
+        let Some(step) = self.steps.get_mut(record.index) else {
+            return;
+        };
+        step.status = record.status;
+        step.duration_ms = Some(record.duration_ms);
+
+        match record.status {
+            StepStatus::Ok => {
+                step.log
+                    .push(format!("✓ finished ({:.1}ms)", record.duration_ms));
+                if let Some(ref saved) = record.saved {
+                    step.log.push(format!("saved to {}", saved));
+                }
+                let result = serde_json::to_string_pretty(&record.result).unwrap_or_default();
+                let lines: Vec<&str> = result.lines().collect();
+                step.log.extend(
+                    lines
+                        .iter()
+                        .take(MAX_RESULT_LINES)
+                        .map(|line| line.to_string()),
+                );
+                if lines.len() > MAX_RESULT_LINES {
+                    step.log
+                        .push(format!("… {} more lines", lines.len() - MAX_RESULT_LINES));
+                }
+            }
+            StepStatus::Failed => {
+                step.log
+                    .push(format!("✗ failed ({:.1}ms)", record.duration_ms));
+                let error = record.error.as_deref().unwrap_or("unknown error");
+                step.log.extend(error.lines().map(str::to_string));
+            }
+            StepStatus::Cancelled => step.log.push("! cancelled".to_string()),
+            StepStatus::Skipped => {
+                step.started.get_or_insert_with(Instant::now);
+                step.log.push("○ skipped: condition is false".to_string());
+            }
+            StepStatus::Pending => {}
+        }
+    }
+
+    fn key(&mut self, key: KeyEvent, cancel: &Cancellation) -> Action {
+        let ctrl_c =
+            key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl_c && self.run.is_some() => return Action::Quit,
+            KeyCode::Char('c') if self.run.is_none() => {
+                if self.cancelling {
+                    return Action::ForceQuit;
+                }
+                cancel.cancel(CancelReason::Interrupted);
+                self.cancelling = true;
+                self.message =
+                    Some("Cancelling workflow (press c again to force quit)...".to_string());
+            }
+            KeyCode::Char('q') | KeyCode::Esc if self.run.is_none() => {
+                self.message = Some("The workflow is still running; press c to cancel".to_string());
+            }
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Esc => {
+                if !self.show_summary {
+                    return Action::Quit;
+                }
+                self.show_summary = false;
+            }
+            KeyCode::Char('s') if self.run.is_some() => self.show_summary = !self.show_summary,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.follow = false;
+                self.scroll = 0;
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.follow = false;
+                self.scroll = 0;
+                self.selected = (self.selected + 1).min(self.steps.len().saturating_sub(1));
+            }
+            KeyCode::Char('f') => {
+                self.follow = true;
+                self.scroll = 0;
+                if let Some(index) = self.steps.iter().rposition(StepView::running) {
+                    self.selected = index;
+                }
+            }
+            KeyCode::PageUp => {
+                let len = self.steps.get(self.selected).map_or(0, |s| s.log.len());
+                self.scroll = (self.scroll + PAGE).min(len.saturating_sub(1));
+            }
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(PAGE),
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// The `height` log lines of the selected step to show, ending `scroll`
+    /// lines above the newest.
+    fn visible_log(&self, height: usize) -> &[String] {
+        let Some(step) = self.steps.get(self.selected) else {
+            return &[];
+        };
+        let end = step.log.len() - self.scroll.min(step.log.len());
+        &step.log[end.saturating_sub(height)..end]
+    }
+}
+
+/// Run `workflow` and show its progress until the run ends and the view is
+/// closed. `cancel` is the run's cancellation, shared with its timeout.
+pub fn run(workflow: &Workflow, cancel: &Cancellation) -> Result<Outcome> {
+    let mut terminal = super::setup_terminal()?;
+    let events = EventHandler::without_polling(Duration::from_millis(100));
+    workflow_engine::spawn(workflow.clone(), cancel.clone(), events.sender());
+
+    let mut view = WorkflowView::new(workflow);
+    let result = (|| loop {
+        terminal.draw(|frame| draw(frame, &view))?;
+
+        match events.next()? {
+            Event::Workflow(update) => view.update(update),
+            Event::Key(key) => match view.key(key, cancel) {
+                Action::None => {}
+                Action::Quit => {
+                    if let Some(run) = view.run.take() {
+                        return Ok(Outcome::Finished(run));
+                    }
+                }
+                Action::ForceQuit => return Ok(Outcome::ForceQuit),
+            },
+            _ => {}
+        }
+    })();
+
+    super::restore_terminal(&mut terminal)?;
+    result
+}
+
+fn draw(frame: &mut Frame, view: &WorkflowView) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Header
+            Constraint::Percentage(40), // Steps
+            Constraint::Min(5),         // Log
+            Constraint::Length(4),      // Footer
+        ])
+        .split(frame.area());
+
+    draw_header(frame, chunks[0], view);
+    draw_steps(frame, chunks[1], view);
+    draw_log(frame, chunks[2], view);
+    draw_footer(frame, chunks[3], view);
+
+    if view.show_summary {
+        if let Some(run) = &view.run {
+            draw_summary(frame, run);
+        }
+    }
+}
+
+fn draw_header(frame: &mut Frame, area: Rect, view: &WorkflowView) {
+    let (status, color) = match view.run.as_ref().map(|run| run.status) {
+        Some(RunStatus::Completed) => ("completed".to_string(), Color::Green),
+        Some(RunStatus::Failed) => ("failed".to_string(), Color::Red),
+        Some(RunStatus::Cancelled) => ("cancelled".to_string(), Color::Yellow),
+        Some(RunStatus::TimedOut) => ("timed out".to_string(), Color::Yellow),
+        None if view.cancelling => ("cancelling".to_string(), Color::Yellow),
+        None => {
+            let done = view
+                .steps
+                .iter()
+                .filter(|s| s.duration_ms.is_some())
+                .count();
+            (
+                format!("running {}/{}", done, view.steps.len()),
+                Color::Blue,
+            )
+        }
+    };
+    let elapsed = match &view.run {
+        Some(run) => format_ms(run.total_ms),
+        None => format_ms(view.started.elapsed().as_secs_f64() * 1000.0),
+    };
+
+    let title = Line::from(vec![
+        Span::styled(
+            format!(" Workflow: {} ", view.name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {} ", status),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {} ", elapsed),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(title);
+
+    frame.render_widget(block, area);
+}
+
+/// Icon, label and color for a step's state.
+fn step_status(step: &StepView) -> (&'static str, &'static str, Color) {
+    match step.status {
+        StepStatus::Pending if step.running() => ("●", "running", Color::Blue),
+        StepStatus::Pending => ("○", "pending", Color::DarkGray),
+        StepStatus::Ok => ("✓", "ok", Color::Green),
+        StepStatus::Failed => ("✗", "failed", Color::Red),
+        StepStatus::Cancelled => ("!", "cancelled", Color::Yellow),
+        StepStatus::Skipped => ("○", "skipped", Color::DarkGray),
+    }
+}
+
+fn draw_steps(frame: &mut Frame, area: Rect, view: &WorkflowView) {
+    let header_cells = ["", "#", "Step", "Status", "Time"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header = Row::new(header_cells).height(1);
+
+    // Keep the selected step in view
+    let height = area.height.saturating_sub(3) as usize;
+    let first = (view.selected + 1).saturating_sub(height);
+
+    let rows: Vec<Row> = view
+        .steps
+        .iter()
+        .enumerate()
+        .skip(first)
+        .map(|(i, step)| {
+            let selected = i == view.selected;
+            let (icon, status, color) = step_status(step);
+            let time = match (step.duration_ms, step.started) {
+                (Some(ms), _) if step.status != StepStatus::Skipped => format_ms(ms),
+                (None, Some(started)) => format_ms(started.elapsed().as_secs_f64() * 1000.0),
+                _ => "-".to_string(),
+            };
+            let label = if step.parallel {
+                format!("{} [parallel]", step.label)
+            } else {
+                step.label.clone()
+            };
+
+            let row = Row::new(vec![
+                Cell::from(if selected { "▸" } else { " " })
+                    .style(Style::default().fg(Color::Cyan)),
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(label),
+                Cell::from(format!("{} {}", icon, status)).style(Style::default().fg(color)),
+                Cell::from(time),
+            ]);
+            if selected {
+                row.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(" Steps "),
+    );
+
+    frame.render_widget(table, area);
+}
+
+fn draw_log(frame: &mut Frame, area: Rect, view: &WorkflowView) {
+    let height = area.height.saturating_sub(2) as usize;
+    let Some(step) = view.steps.get(view.selected) else {
+        return;
+    };
+
+    let lines: Vec<Line> = if step.log.is_empty() {
+        vec![Line::from(Span::styled(
+            " Not started yet",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        view.visible_log(height)
+            .iter()
+            .map(|line| {
+                let color = match line.chars().next() {
+                    Some('✗') => Color::Red,
+                    Some('✓') => Color::Green,
+                    Some('!') | Some('↻') => Color::Yellow,
+                    Some('→') | Some('○') => Color::DarkGray,
+                    _ => Color::Reset,
+                };
+                Line::from(Span::styled(line.clone(), Style::default().fg(color)))
+            })
+            .collect()
+    };
+
+    let mut title = vec![Span::styled(
+        format!(" Log: {}. {} ", view.selected + 1, step.label),
+        Style::default().fg(Color::White),
+    )];
+    if view.scroll > 0 {
+        title.push(Span::styled(
+            format!(" ↑{} lines, PgDn to follow ", view.scroll),
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Line::from(title));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_footer(frame: &mut Frame, area: Rect, view: &WorkflowView) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Length(2)])
+        .split(area);
+
+    let mut keys = vec![
+        Span::styled(" [↑/k]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Up  "),
+        Span::styled("[↓/j]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Down  "),
+        Span::styled("[f]", Style::default().fg(Color::Cyan)),
+        Span::raw(if view.follow {
+            " Following  "
+        } else {
+            " Follow  "
+        }),
+        Span::styled("[PgUp/PgDn]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Scroll log  "),
+    ];
+    if view.run.is_some() {
+        keys.extend([
+            Span::styled("[s]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Summary  "),
+            Span::styled("[q]", Style::default().fg(Color::DarkGray)),
+            Span::raw(" Quit"),
+        ]);
+    } else {
+        keys.extend([
+            Span::styled("[c]", Style::default().fg(Color::Red)),
+            Span::raw(if view.cancelling {
+                " Force quit"
+            } else {
+                " Cancel"
+            }),
+        ]);
+    }
+
+    let keys_block = Block::default()
+        .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(
+        Paragraph::new(Line::from(keys)).block(keys_block),
+        chunks[0],
+    );
+
+    let message = match &view.message {
+        Some(text) => Line::from(vec![
+            Span::styled(" ! ", Style::default().fg(Color::Yellow)),
+            Span::styled(text.clone(), Style::default().fg(Color::Yellow)),
+        ]),
+        None => Line::from(""),
+    };
+    let message_block = Block::default()
+        .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(Paragraph::new(message).block(message_block), chunks[1]);
+}
+
+fn draw_summary(frame: &mut Frame, run: &WorkflowRun) {
+    let area = centered_rect(60, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let (title, color) = match run.status {
+        RunStatus::Completed => ("✓ Workflow completed", Color::Green),
+        RunStatus::Failed => ("✗ Workflow failed", Color::Red),
+        RunStatus::Cancelled => ("! Workflow cancelled", Color::Yellow),
+        RunStatus::TimedOut => ("! Workflow timed out", Color::Yellow),
+    };
+    let count = |status: StepStatus| run.steps.iter().filter(|s| s.status == status).count();
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {}", title),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "  Steps: {} ok, {} failed, {} skipped, {} not run",
+            count(StepStatus::Ok),
+            count(StepStatus::Failed),
+            count(StepStatus::Skipped),
+            count(StepStatus::Pending) + count(StepStatus::Cancelled)
+        )),
+        Line::from(format!("  Total time: {}", format_ms(run.total_ms))),
+    ];
+
+    if let Some(step) = run.failed_step() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Step {} ({}): {}",
+                step.index + 1,
+                step.method,
+                step.error.as_deref().unwrap_or("unknown error")
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let saved: Vec<&str> = run
+        .steps
+        .iter()
+        .filter_map(|s| s.saved.as_deref())
+        .collect();
+    if !saved.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from("  Saved:"));
+        for path in saved {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", path),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  [s] Back to steps  [q] Quit",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(" Summary ");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `850.2ms`, or `12.3s` from 10 seconds on.
+fn format_ms(ms: f64) -> String {
+    if ms < 10_000.0 {
+        format!("{:.1}ms", ms)
+    } else {
+        format!("{:.1}s", ms / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(index: usize, status: StepStatus, result: serde_json::Value) -> StepRecord {
+        StepRecord {
+            index,
+            id: None,
+            service: "gmail".to_string(),
+            method: "gmail.list".to_string(),
+            status,
+            duration_ms: 12.5,
+            attempts: 1,
+            output: None,
+            error: (status == StepStatus::Failed).then(|| "boom".to_string()),
+            group: None,
+            saved: None,
+            result,
+        }
+    }
+
+    #[test]
+    fn test_workflow_view() {
+        let workflow = workflow_engine::parse(
+            "name: demo\nsteps:\n  - service: gmail\n    method: gmail.list\n  \
+             - service: gmail\n    method: gmail.send\n",
+        )
+        .unwrap();
+        let mut view = WorkflowView::new(&workflow);
+        let cancel = Cancellation::new();
+
+        view.update(RunUpdate::StepStarted {
+            index: 0,
+            attempt: 1,
+        });
+        assert!(view.steps[0].running());
+        view.update(RunUpdate::StepFinished(Box::new(record(
+            0,
+            StepStatus::Ok,
+            serde_json::json!({"count": 2}),
+        ))));
+        assert_eq!(view.steps[0].status, StepStatus::Ok);
+        assert_eq!(
+            view.steps[0].log,
+            [
+                "→ calling gmail.list",
+                "✓ finished (12.5ms)",
+                "{",
+                "  \"count\": 2",
+                "}"
+            ]
+        );
+
+        // The selection follows each step as it starts, until moved by hand
+        view.update(RunUpdate::StepStarted {
+            index: 1,
+            attempt: 1,
+        });
+        assert_eq!(view.selected, 1);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        view.key(key(KeyCode::Up), &cancel);
+        view.update(RunUpdate::StepStarted {
+            index: 1,
+            attempt: 2,
+        });
+        assert_eq!(view.selected, 0);
+        assert_eq!(view.visible_log(2), ["  \"count\": 2", "}"]);
+        view.key(key(KeyCode::PageUp), &cancel);
+        assert_eq!(view.visible_log(2), ["→ calling gmail.list"]);
+        view.key(key(KeyCode::Char('f')), &cancel);
+        assert_eq!(view.selected, 1);
+
+        // Quitting waits for the run; cancelling twice forces it
+        assert_eq!(view.key(key(KeyCode::Char('q')), &cancel), Action::None);
+        assert_eq!(view.key(key(KeyCode::Char('c')), &cancel), Action::None);
+        assert!(cancel.is_cancelled());
+        assert_eq!(
+            view.key(
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                &cancel
+            ),
+            Action::ForceQuit
+        );
+
+        view.update(RunUpdate::StepFinished(Box::new(record(
+            1,
+            StepStatus::Failed,
+            serde_json::Value::Null,
+        ))));
+        assert_eq!(view.steps[1].log.last().unwrap(), "boom");
+    }
+}
@@ -72,6 +72,99 @@ fn test_agents_command() {
     );
 }
 
+/// Test that the command exported MCP server entries run (`fgp-<daemon>` in
+/// Claude Desktop, Cursor and Continue configs) starts the bridge
+#[test]
+fn test_mcp_serve_service() {
+    use std::io::Write;
+
+    let home = tempfile::tempdir().unwrap();
+    let mut child = Command::new("cargo")
+        .args(["run", "--", "mcp", "serve", "--service", "gmail"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("HOME", home.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"{\"jsonrpc\": \"2.0\", \"id\": 1, \"method\": \"initialize\"}\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "mcp serve --service should parse: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("fgp-mcp-bridge"));
+}
+
+/// Test that the bash completion script covers the top-level subcommands
+#[test]
+fn test_completions_bash() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "completions", "bash"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Completions command should succeed"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for subcommand in [
+        "start",
+        "stop",
+        "call",
+        "methods",
+        "skill",
+        "workflow",
+        "completions",
+    ] {
+        assert!(
+            stdout.contains(subcommand),
+            "Completion script should include '{}'",
+            subcommand
+        );
+    }
+}
+
+/// Test that every import fixture goes through `fgp skill import` (golden
+/// output for each is checked by the skill_import unit tests)
+#[test]
+fn test_skill_import_fixtures() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--",
+            "skill",
+            "import",
+            "tests/fixtures/import",
+            "--dry-run",
+        ])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "Importing the fixtures should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("Found 19 importable file(s)") && stdout.contains(" 0 failed"),
+        "Every fixture should import: {}",
+        stdout
+    );
+}
+
 /// Test that the crate compiles
 #[test]
 fn test_crate_compiles() {